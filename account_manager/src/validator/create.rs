@@ -1,22 +1,27 @@
 use crate::common::read_wallet_name_from_cli;
 use crate::wallet::create::STDIN_INPUTS_FLAG;
 use crate::{SECRETS_DIR_FLAG, WALLETS_DIR_FLAG};
+use account_utils::eth2_keystore::keypair_from_secret;
 use account_utils::{
     random_password, read_password_from_user, strip_off_newlines, validator_definitions, PlainText,
 };
+use bls::PublicKey;
 use clap::{Arg, ArgAction, ArgMatches, Command};
 use clap_utils::FLAG_HEADER;
 use directory::{
     ensure_dir_exists, parse_path_or_default_with_flag, DEFAULT_SECRET_DIR, DEFAULT_WALLET_DIR,
 };
 use environment::Environment;
+use eth2_wallet::{recover_validator_secret_from_mnemonic, validator_keystores_from_seed, KeyType};
 use eth2_wallet_manager::WalletManager;
+use rayon::prelude::*;
 use slashing_protection::{SlashingDatabase, SLASHING_PROTECTION_FILENAME};
 use std::ffi::OsStr;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use types::EthSpec;
-use validator_dir::Builder as ValidatorDirBuilder;
+use validator_dir::{Builder as ValidatorDirBuilder, VOTING_KEYSTORE_FILE};
 
 pub const CMD: &str = "create";
 pub const WALLET_NAME_FLAG: &str = "wallet-name";
@@ -31,7 +36,8 @@ pub fn cli_app() -> Command {
     Command::new(CMD)
         .about(
             "Creates new validators from an existing EIP-2386 wallet using the EIP-2333 HD key \
-            derivation scheme.",
+            derivation scheme. Keys are generated in parallel using all CPU cores. An interrupted \
+            run may be resumed by running the same command again.",
         )
         .arg(
             Arg::new(WALLET_NAME_FLAG)
@@ -97,7 +103,10 @@ pub fn cli_app() -> Command {
             Arg::new(COUNT_FLAG)
                 .long(COUNT_FLAG)
                 .value_name("VALIDATOR_COUNT")
-                .help("The number of validators to create, regardless of how many already exist")
+                .help(
+                    "The number of validators to create, regardless of how many already exist. \
+                    Validators created by an interrupted run are included in the count.",
+                )
                 .conflicts_with("at-most")
                 .action(ArgAction::Set)
                 .display_order(0)
@@ -226,55 +235,166 @@ pub fn cli_run<E: EthSpec>(
         )
     })?;
 
-    for i in 0..n {
-        let voting_password = random_password();
-        let withdrawal_password = random_password();
+    let seed = wallet
+        .wallet()
+        .decrypt_seed(wallet_password.as_bytes())
+        .map_err(|e| format!("Unable to decrypt wallet: {:?}", e))?;
+    let store_withdrawal_keystore = matches.get_flag(STORE_WITHDRAW_FLAG);
 
-        let keystores = wallet
-            .next_validator(
-                wallet_password.as_bytes(),
-                voting_password.as_bytes(),
-                withdrawal_password.as_bytes(),
-            )
-            .map_err(|e| format!("Unable to create validator keys: {:?}", e))?;
+    // Keys are derived and encrypted in parallel, in batches of the validators which remain to be
+    // created. The `nextaccount` of the wallet is only advanced once a batch is complete, so an
+    // interrupted run is resumed by deriving the same keys again and skipping the validators which
+    // were already created.
+    //
+    // With --count the skipped validators count towards `n`, so that resuming a run creates the
+    // same validators as an uninterrupted run. With --at-most they were already counted by
+    // `existing_validator_count`.
+    let count_resumed = count.is_some();
+    let progress = AtomicUsize::new(0);
+    let mut created = Vec::with_capacity(n);
+    while created.len() < n {
+        let first_index = wallet.wallet().nextaccount();
+        let end_index = u32::try_from(n - created.len())
+            .ok()
+            .and_then(|remaining| first_index.checked_add(remaining))
+            .ok_or("The wallet has no more validator keys")?;
 
-        let voting_pubkey = keystores.voting.public_key().ok_or_else(|| {
-            format!(
-                "Keystore public key is invalid: {}",
-                keystores.voting.pubkey()
-            )
-        })?;
+        let (resumed, pending): (Vec<_>, Vec<_>) = (first_index..end_index)
+            .into_par_iter()
+            .map(|index| {
+                let voting_pubkey = derive_pubkey(seed.as_bytes(), index, KeyType::Voting)?;
+                let exists = validator_exists(&validator_dir, &secrets_dir, &voting_pubkey)?;
+                Ok((index, voting_pubkey, exists))
+            })
+            .collect::<Result<Vec<_>, String>>()?
+            .into_iter()
+            .partition(|(_, _, exists)| *exists);
+        if count_resumed && !resumed.is_empty() {
+            print_progress(
+                progress.fetch_add(resumed.len(), Ordering::Relaxed) + resumed.len(),
+                n,
+            );
+            created.extend(
+                resumed
+                    .into_iter()
+                    .map(|(index, voting_pubkey, _)| (index, voting_pubkey)),
+            );
+        }
 
         slashing_protection
-            .register_validator(voting_pubkey.compress())
-            .map_err(|e| {
-                format!(
-                    "Error registering validator {}: {:?}",
-                    voting_pubkey.as_hex_string(),
-                    e
+            .register_validators(
+                pending
+                    .iter()
+                    .map(|(_, voting_pubkey, _)| voting_pubkey.compress())
+                    .collect::<Vec<_>>()
+                    .iter(),
+            )
+            .map_err(|e| format!("Error registering validators: {:?}", e))?;
+
+        let batch = pending
+            .into_par_iter()
+            .map(|(index, voting_pubkey, _)| {
+                let voting_password = random_password();
+                let withdrawal_password = random_password();
+
+                let keystores = validator_keystores_from_seed(
+                    seed.as_bytes(),
+                    index,
+                    voting_password.as_bytes(),
+                    withdrawal_password.as_bytes(),
                 )
-            })?;
+                .map_err(|e| format!("Unable to create validator keys: {:?}", e))?;
+
+                ValidatorDirBuilder::new(validator_dir.clone())
+                    .password_dir(secrets_dir.clone())
+                    .voting_keystore(keystores.voting, voting_password.as_bytes())
+                    .withdrawal_keystore(keystores.withdrawal, withdrawal_password.as_bytes())
+                    .create_eth1_tx_data(deposit_gwei, &spec)
+                    .store_withdrawal_keystore(store_withdrawal_keystore)
+                    .build()
+                    .map_err(|e| format!("Unable to build validator directory: {:?}", e))?;
 
-        ValidatorDirBuilder::new(validator_dir.clone())
-            .password_dir(secrets_dir.clone())
-            .voting_keystore(keystores.voting, voting_password.as_bytes())
-            .withdrawal_keystore(keystores.withdrawal, withdrawal_password.as_bytes())
-            .create_eth1_tx_data(deposit_gwei, &spec)
-            .store_withdrawal_keystore(matches.get_flag(STORE_WITHDRAW_FLAG))
-            .build()
-            .map_err(|e| format!("Unable to build validator directory: {:?}", e))?;
+                print_progress(progress.fetch_add(1, Ordering::Relaxed) + 1, n);
+                Ok((index, voting_pubkey))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
 
+        wallet
+            .set_nextaccount(end_index)
+            .map_err(|e| format!("Unable to update wallet: {:?}", e))?;
+        created.extend(batch);
+    }
+    created.sort_by_key(|(index, _)| *index);
+
+    for (i, (_, voting_pubkey)) in created.iter().enumerate() {
         println!("{}/{}\t{}", i + 1, n, voting_pubkey.as_hex_string());
     }
 
     Ok(())
 }
 
+/// Returns the public key of the `key_type` for the validator at `index`, derived from the `seed`
+/// of a wallet.
+fn derive_pubkey(seed: &[u8], index: u32, key_type: KeyType) -> Result<PublicKey, String> {
+    let (secret, _) = recover_validator_secret_from_mnemonic(seed, index, key_type)
+        .map_err(|e| format!("Unable to derive validator key: {:?}", e))?;
+    keypair_from_secret(secret.as_bytes())
+        .map(|keypair| keypair.pk)
+        .map_err(|e| format!("Unable to derive validator key: {:?}", e))
+}
+
+/// Returns `true` if the validator with `voting_pubkey` was created by an earlier run.
+///
+/// The voting keystore is the last file written to a validator directory, so a directory without
+/// one was left behind by an interrupted run. Such a directory is removed along with its voting
+/// password, allowing the validator to be created again.
+fn validator_exists(
+    validator_dir: &Path,
+    secrets_dir: &Path,
+    voting_pubkey: &PublicKey,
+) -> Result<bool, String> {
+    let dir = validator_dir.join(voting_pubkey.as_hex_string());
+    if dir.join(VOTING_KEYSTORE_FILE).exists() {
+        return Ok(true);
+    }
+
+    if dir.exists() {
+        eprintln!("Removing incomplete validator directory {:?}", dir);
+        fs::remove_dir_all(&dir).map_err(|e| format!("Unable to remove {:?}: {:?}", dir, e))?;
+
+        let password_path = secrets_dir.join(voting_pubkey.as_hex_string());
+        if password_path.exists() {
+            fs::remove_file(&password_path)
+                .map_err(|e| format!("Unable to remove {:?}: {:?}", password_path, e))?;
+        }
+    }
+    Ok(false)
+}
+
+/// Prints a bar showing that `done` of `total` validators were created to stderr, replacing the
+/// previous bar.
+fn print_progress(done: usize, total: usize) {
+    const WIDTH: usize = 40;
+
+    let filled = done * WIDTH / total.max(1);
+    eprint!(
+        "\r[{}{}] {}/{} validators created",
+        "#".repeat(filled),
+        " ".repeat(WIDTH - filled),
+        done,
+        total
+    );
+    if done == total {
+        eprintln!();
+    }
+}
+
 /// Returns the number of validators that exist in the given `validator_dir`.
 ///
 /// This function just assumes all files and directories, excluding the validator definitions YAML
 /// and slashing protection database are validator directories, making it likely to return a higher
-/// number than accurate but never a lower one.
+/// number than accurate but never a lower one. Directories without a voting keystore are left
+/// behind by an interrupted run and are not counted, so that the run can be resumed.
 fn existing_validator_count<P: AsRef<Path>>(validator_dir: P) -> Result<usize, String> {
     fs::read_dir(validator_dir.as_ref())
         .map(|iter| {
@@ -283,6 +403,7 @@ fn existing_validator_count<P: AsRef<Path>>(validator_dir: P) -> Result<usize, S
                     e.file_name() != OsStr::new(validator_definitions::CONFIG_FILENAME)
                         && e.file_name()
                             != OsStr::new(slashing_protection::SLASHING_PROTECTION_FILENAME)
+                        && !(e.path().is_dir() && !e.path().join(VOTING_KEYSTORE_FILE).exists())
                 })
                 .count()
         })
@@ -3,6 +3,7 @@ pub mod exit;
 pub mod import;
 pub mod list;
 pub mod modify;
+pub mod rebind_network;
 pub mod recover;
 pub mod slashing_protection;
 
@@ -49,6 +50,7 @@ pub fn cli_app() -> Command {
         .subcommand(recover::cli_app())
         .subcommand(slashing_protection::cli_app())
         .subcommand(exit::cli_app())
+        .subcommand(rebind_network::cli_app())
 }
 
 pub fn cli_run<E: EthSpec>(matches: &ArgMatches, env: Environment<E>) -> Result<(), String> {
@@ -70,6 +72,9 @@ pub fn cli_run<E: EthSpec>(matches: &ArgMatches, env: Environment<E>) -> Result<
             slashing_protection::cli_run(matches, env, validator_base_dir)
         }
         Some((exit::CMD, matches)) => exit::cli_run(matches, env),
+        Some((rebind_network::CMD, matches)) => {
+            rebind_network::cli_run(matches, env, validator_base_dir)
+        }
         Some((unknown, _)) => Err(format!(
             "{} does not have a {} command. See --help",
             CMD, unknown
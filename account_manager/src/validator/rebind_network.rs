@@ -0,0 +1,122 @@
+use account_utils::network_binding::NetworkBinding;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use clap_utils::FLAG_HEADER;
+use environment::Environment;
+use slashing_protection::{SlashingDatabase, SLASHING_PROTECTION_FILENAME};
+use std::fs::File;
+use std::path::PathBuf;
+use types::EthSpec;
+
+pub const CMD: &str = "rebind-network";
+pub const FORCE_FLAG: &str = "force";
+pub const EXPORT_FLAG: &str = "slashing-protection-export";
+
+pub fn cli_app() -> Command {
+    Command::new(CMD)
+        .about(
+            "Bind the validator directory to the network given by --network. The validator \
+             client refuses to use a directory which is bound to a different network.",
+        )
+        .display_order(0)
+        .arg(
+            Arg::new(FORCE_FLAG)
+                .long(FORCE_FLAG)
+                .help(
+                    "Move a directory which is bound to a different network. Its keys and \
+                     slashing protection history will be used on the new network.",
+                )
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(EXPORT_FLAG)
+                .long(EXPORT_FLAG)
+                .value_name("FILE")
+                .help(
+                    "When moving a directory which is bound to a different network, first export \
+                     its slashing protection history for that network to this interchange file.",
+                )
+                .action(ArgAction::Set)
+                .requires(FORCE_FLAG)
+                .display_order(0),
+        )
+}
+
+pub fn cli_run<E: EthSpec>(
+    matches: &ArgMatches,
+    env: Environment<E>,
+    validator_dir: PathBuf,
+) -> Result<(), String> {
+    let force = matches.get_flag(FORCE_FLAG);
+    let export_path: Option<PathBuf> = clap_utils::parse_optional(matches, EXPORT_FLAG)?;
+
+    let genesis_validators_root = env
+        .eth2_network_config
+        .ok_or("Unable to get testnet configuration from the environment")?
+        .genesis_validators_root::<E>()?
+        .ok_or_else(|| "Unable to get genesis state, has genesis occurred?".to_string())?;
+
+    let binding = NetworkBinding::open(&validator_dir)
+        .map_err(|e| format!("Unable to read network binding: {:?}", e))?;
+
+    match binding {
+        Some(binding) if binding.genesis_validators_root == genesis_validators_root => {
+            eprintln!(
+                "Validator directory is already bound to genesis validators root {:?}",
+                genesis_validators_root
+            );
+            return Ok(());
+        }
+        Some(binding) => {
+            let bound_root = binding.genesis_validators_root;
+            if !force {
+                return Err(format!(
+                    "Validator directory is bound to the network with genesis validators root \
+                     {:?}. Re-run with --{} to move it to the network with genesis validators \
+                     root {:?}",
+                    bound_root, FORCE_FLAG, genesis_validators_root
+                ));
+            }
+
+            if let Some(export_path) = export_path {
+                let slashing_protection_database =
+                    SlashingDatabase::open(&validator_dir.join(SLASHING_PROTECTION_FILENAME))
+                        .map_err(|e| {
+                            format!("Unable to open slashing protection database: {:?}", e)
+                        })?;
+                let interchange = slashing_protection_database
+                    .export_interchange_info(bound_root, None)
+                    .map_err(|e| format!("Error during export: {:?}", e))?;
+                let output_file = File::create(&export_path)
+                    .map_err(|e| format!("Error creating output file: {:?}", e))?;
+                interchange
+                    .write_to(&output_file)
+                    .map_err(|e| format!("Error writing output file: {:?}", e))?;
+                eprintln!(
+                    "Exported slashing protection history to {}",
+                    export_path.display()
+                );
+            }
+
+            eprintln!(
+                "WARNING: moving validator directory from genesis validators root {:?}",
+                bound_root
+            );
+        }
+        None => (),
+    }
+
+    NetworkBinding {
+        genesis_validators_root,
+    }
+    .save(&validator_dir)
+    .map_err(|e| format!("Unable to save network binding: {:?}", e))?;
+
+    eprintln!(
+        "Bound validator directory to genesis validators root {:?}",
+        genesis_validators_root
+    );
+
+    Ok(())
+}
@@ -2,7 +2,7 @@ use clap::{Arg, ArgAction, ArgMatches, Command};
 use environment::Environment;
 use slashing_protection::{
     interchange::Interchange, InterchangeError, InterchangeImportOutcome, SlashingDatabase,
-    SLASHING_PROTECTION_FILENAME,
+    DEFAULT_RETENTION_EPOCHS, SLASHING_PROTECTION_FILENAME,
 };
 use std::fs::File;
 use std::path::PathBuf;
@@ -12,15 +12,20 @@ use types::{Epoch, EthSpec, PublicKeyBytes, Slot};
 pub const CMD: &str = "slashing-protection";
 pub const IMPORT_CMD: &str = "import";
 pub const EXPORT_CMD: &str = "export";
+pub const PRUNE_CMD: &str = "prune";
 
 pub const IMPORT_FILE_ARG: &str = "IMPORT-FILE";
 pub const EXPORT_FILE_ARG: &str = "EXPORT-FILE";
 
 pub const PUBKEYS_FLAG: &str = "pubkeys";
+pub const RETENTION_EPOCHS_FLAG: &str = "retention-epochs";
 
 pub fn cli_app() -> Command {
     Command::new(CMD)
-        .about("Import or export slashing protection data to or from another client")
+        .about(
+            "Import or export slashing protection data to or from another client, or prune \
+             old data from the database",
+        )
         .display_order(0)
         .subcommand(
             Command::new(IMPORT_CMD)
@@ -55,6 +60,27 @@ pub fn cli_app() -> Command {
                         .display_order(0)
                 )
         )
+        .subcommand(
+            Command::new(PRUNE_CMD)
+                .about(
+                    "Remove old blocks and attestations from the slashing protection database. \
+                     The latest block and attestation of each validator are always kept",
+                )
+                .arg(
+                    Arg::new(RETENTION_EPOCHS_FLAG)
+                        .long(RETENTION_EPOCHS_FLAG)
+                        .action(ArgAction::Set)
+                        .value_name("EPOCHS")
+                        .help(format!(
+                            "The number of epochs of history to keep, counting back from the \
+                             latest attestation in the database. Must be at least the default, so \
+                             that no more history is removed than the validator client would \
+                             remove itself [default: {}]",
+                            DEFAULT_RETENTION_EPOCHS
+                        ))
+                        .display_order(0)
+                )
+        )
 }
 
 pub fn cli_run<E: EthSpec>(
@@ -219,6 +245,55 @@ pub fn cli_run<E: EthSpec>(
 
             Ok(())
         }
+        Some((PRUNE_CMD, matches)) => {
+            let retention_epochs = clap_utils::parse_optional(matches, RETENTION_EPOCHS_FLAG)?
+                .unwrap_or(DEFAULT_RETENTION_EPOCHS);
+            if retention_epochs < DEFAULT_RETENTION_EPOCHS {
+                return Err(format!(
+                    "--{} must be at least {}, the retention used by the validator client",
+                    RETENTION_EPOCHS_FLAG, DEFAULT_RETENTION_EPOCHS
+                ));
+            }
+
+            if !slashing_protection_db_path.exists() {
+                return Err(format!(
+                    "No slashing protection database exists at: {}",
+                    slashing_protection_db_path.display()
+                ));
+            }
+
+            let slashing_protection_database = SlashingDatabase::open(&slashing_protection_db_path)
+                .map_err(|e| {
+                    format!(
+                        "Unable to open database at {}: {:?}",
+                        slashing_protection_db_path.display(),
+                        e
+                    )
+                })?;
+
+            // Count back from the latest attestation rather than the wall clock so that a database
+            // which hasn't been used for a while isn't pruned any further than it needs to be.
+            let Some(latest_epoch) = slashing_protection_database
+                .max_attestation_target()
+                .map_err(|e| format!("Error reading database: {:?}", e))?
+            else {
+                eprintln!("No attestations in the database, nothing to prune");
+                return Ok(());
+            };
+
+            let summary = slashing_protection_database
+                .prune_all(latest_epoch, retention_epochs, E::slots_per_epoch())
+                .map_err(|e| format!("Error during pruning: {:?}", e))?;
+
+            eprintln!(
+                "Pruned {} blocks and {} attestations older than epoch {}",
+                summary.blocks,
+                summary.attestations,
+                latest_epoch.saturating_sub(retention_epochs)
+            );
+
+            Ok(())
+        }
         Some((command, _)) => Err(format!("No such subcommand `{}`", command)),
         _ => Err("No subcommand provided, see --help for options".to_string()),
     }
@@ -612,7 +612,9 @@ impl<'a, T: BeaconChainTypes> IndexedAggregatedAttestation<'a, T> {
                 }
 
                 // Ensure the aggregator is a member of the committee for which it is aggregating.
-                if !committee.committee.contains(&(aggregator_index as usize)) {
+                if !u32::try_from(aggregator_index)
+                    .map_or(false, |index| committee.committee.contains(&index))
+                {
                     return Err(Error::AggregatorNotInCommittee { aggregator_index });
                 }
 
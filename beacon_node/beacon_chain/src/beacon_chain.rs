@@ -31,6 +31,7 @@ use crate::eth1_finalization_cache::{Eth1FinalizationCache, Eth1FinalizationData
 use crate::events::ServerSentEventHandler;
 use crate::execution_payload::{get_execution_payload, NotifyExecutionLayer, PreparePayloadHandle};
 use crate::fork_choice_signal::{ForkChoiceSignalRx, ForkChoiceSignalTx, ForkChoiceWaitResult};
+use crate::graffiti_analysis::GraffitiAnalysisCache;
 use crate::graffiti_calculator::GraffitiCalculator;
 use crate::head_tracker::{HeadTracker, HeadTrackerReader, SszHeadTracker};
 use crate::historical_blocks::HistoricalBlockError;
@@ -56,6 +57,7 @@ use crate::observed_blob_sidecars::ObservedBlobSidecars;
 use crate::observed_block_producers::ObservedBlockProducers;
 use crate::observed_operations::{ObservationOutcome, ObservedOperations};
 use crate::observed_slashable::ObservedSlashable;
+use crate::payload_prefetch::PayloadPrefetchCache;
 use crate::persisted_beacon_chain::{PersistedBeaconChain, DUMMY_CANONICAL_HEAD_BLOCK_ROOT};
 use crate::persisted_fork_choice::PersistedForkChoice;
 use crate::pre_finalization_cache::PreFinalizationBlockCache;
@@ -72,10 +74,13 @@ use crate::{
     kzg_utils, metrics, AvailabilityPendingExecutedBlock, BeaconChainError, BeaconForkChoiceStore,
     BeaconSnapshot, CachedHead,
 };
+use eth2::lighthouse::{
+    BuilderCircuitBreaker, ChainHealthStatus, CircuitBreakerCheck, CircuitBreakerCondition,
+};
 use eth2::types::{EventKind, SseBlobSidecar, SseBlock, SseExtendedPayloadAttributes};
 use execution_layer::{
     BlockProposalContents, BlockProposalContentsType, BuilderParams, ChainHealth, ExecutionLayer,
-    FailedCondition, PayloadAttributes, PayloadStatus,
+    PayloadAttributes, PayloadStatus,
 };
 use fork_choice::{
     AttestationFromBlock, ExecutionStatus, ForkChoice, ForkchoiceUpdateParameters,
@@ -468,6 +473,10 @@ pub struct BeaconChain<T: BeaconChainTypes> {
     pub reqresp_pre_import_cache: Arc<RwLock<ReqRespPreImportCache<T::EthSpec>>>,
     /// A cache used to keep track of various block timings.
     pub block_times_cache: Arc<RwLock<BlockTimesCache>>,
+    /// The clients detected in the graffiti of recently imported blocks.
+    pub graffiti_analysis_cache: RwLock<GraffitiAnalysisCache>,
+    /// Responses to `newPayload` requests sent ahead of block import during range sync.
+    pub(crate) payload_prefetch_cache: PayloadPrefetchCache,
     /// A cache used to track pre-finalization block roots for quick rejection.
     pub pre_finalization_block_cache: PreFinalizationBlockCache,
     /// A cache used to produce light_client server messages
@@ -528,6 +537,31 @@ impl<E: EthSpec> BeaconBlockResponseWrapper<E> {
     pub fn is_blinded(&self) -> bool {
         matches!(self, BeaconBlockResponseWrapper::Blinded(_))
     }
+
+    pub fn slot(&self) -> Slot {
+        match self {
+            BeaconBlockResponseWrapper::Full(resp) => resp.block.slot(),
+            BeaconBlockResponseWrapper::Blinded(resp) => resp.block.slot(),
+        }
+    }
+
+    /// Returns the parent hash of the block's execution payload, if it has one.
+    pub fn execution_parent_hash(&self) -> Option<ExecutionBlockHash> {
+        match self {
+            BeaconBlockResponseWrapper::Full(resp) => resp
+                .block
+                .body()
+                .execution_payload()
+                .ok()
+                .map(|payload| payload.parent_hash()),
+            BeaconBlockResponseWrapper::Blinded(resp) => resp
+                .block
+                .body()
+                .execution_payload()
+                .ok()
+                .map(|payload| payload.parent_hash()),
+        }
+    }
 }
 
 /// The components produced when the local beacon node creates a new block to extend the chain
@@ -815,14 +849,17 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         &self,
         block_root: Hash256,
     ) -> Result<impl Iterator<Item = Result<(Hash256, Slot), Error>> + '_, Error> {
-        let block = self
-            .get_blinded_block(&block_root)?
+        let (state_root, slot) = self
+            .store
+            .get_block_view_with(&block_root, |block| {
+                (block.message().state_root(), block.slot())
+            })?
             .ok_or(Error::MissingBeaconBlock(block_root))?;
         let state = self
-            .get_state(&block.state_root(), Some(block.slot()))?
-            .ok_or_else(|| Error::MissingBeaconState(block.state_root()))?;
+            .get_state(&state_root, Some(slot))?
+            .ok_or(Error::MissingBeaconState(state_root))?;
         let iter = BlockRootsIterator::owned(&self.store, state);
-        Ok(std::iter::once(Ok((block_root, block.slot())))
+        Ok(std::iter::once(Ok((block_root, slot)))
             .chain(iter)
             .map(|result| result.map_err(|e| e.into())))
     }
@@ -968,6 +1005,54 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         )?
     }
 
+    /// Reconstructs the canonical state at `slot`, which lies in the gap between the historic
+    /// states stored in the freezer database, by replaying blocks on top of the stored state below
+    /// it.
+    ///
+    /// Returns the state along with the number of slots replayed, or `None` if the blocks to
+    /// replay haven't been backfilled. Nothing is replayed if more than `max_replay_slots` slots
+    /// would need to be.
+    pub fn replay_historic_state(
+        &self,
+        slot: Slot,
+        max_replay_slots: u64,
+    ) -> Result<Option<(BeaconState<T::EthSpec>, u64)>, Error> {
+        let (lower_limit, _) = self.store.get_historic_state_limits();
+        let replay_depth = slot.safe_sub(lower_limit)?.as_u64();
+        if replay_depth > max_replay_slots {
+            return Err(Error::HistoricStateReplayLimitExceeded {
+                slot,
+                replay_depth,
+                max_replay_slots,
+            });
+        }
+        if self.store.get_oldest_block_slot() > lower_limit.safe_add(1)? {
+            return Ok(None);
+        }
+
+        let Some(base_state) = self.store.load_cold_state_by_slot(lower_limit)? else {
+            return Ok(None);
+        };
+        let Some(block_root) = self.block_root_at_slot(slot, WhenSlotSkipped::Prev)? else {
+            return Ok(None);
+        };
+        let blocks = self
+            .store
+            .load_blocks_to_replay(lower_limit, slot, block_root)?;
+
+        // State roots aren't stored for the gap, so they're computed as the blocks are replayed.
+        let mut state = self.store.replay_blocks(
+            base_state,
+            blocks,
+            slot,
+            None::<std::iter::Empty<Result<(Hash256, Slot), DBError>>>,
+            None,
+        )?;
+        state.apply_pending_mutations()?;
+
+        Ok(Some((state, replay_depth)))
+    }
+
     /// Returns the block root at the given slot, if any. Only returns roots in the canonical chain.
     ///
     /// ## Notes
@@ -2816,6 +2901,19 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                 }
             };
 
+            // Send the payloads to the EL ahead of import, so that it can verify them concurrently.
+            let _prefetch_guard = match notify_execution_layer {
+                NotifyExecutionLayer::Yes => Some(
+                    self.prefetch_new_payloads(
+                        signature_verified_blocks
+                            .iter()
+                            .map(|block| (block.block_root(), block.block_cloned()))
+                            .collect(),
+                    ),
+                ),
+                NotifyExecutionLayer::No => None,
+            };
+
             // Import the blocks into the chain.
             for signature_verified_block in signature_verified_blocks {
                 match self
@@ -4039,6 +4137,15 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             );
         }
 
+        let graffiti_window = T::EthSpec::slots_per_historical_root() as u64;
+        if block.slot() + graffiti_window >= current_slot {
+            self.graffiti_analysis_cache.write().observe(
+                block_root,
+                block.slot(),
+                block.body().graffiti(),
+            );
+        }
+
         if let Some(event_handler) = self.event_handler.as_ref() {
             if event_handler.has_block_subscribers() {
                 event_handler.register(EventKind::Block(SseBlock {
@@ -5182,7 +5289,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
     fn complete_partial_beacon_block<Payload: AbstractExecPayload<T::EthSpec>>(
         &self,
         partial_beacon_block: PartialBeaconBlock<T::EthSpec>,
-        block_contents: Option<BlockProposalContents<T::EthSpec, Payload>>,
+        mut block_contents: Option<BlockProposalContents<T::EthSpec, Payload>>,
         verification: ProduceBlockVerification,
     ) -> Result<BeaconBlockResponse<T::EthSpec, Payload>, BlockProductionError> {
         let PartialBeaconBlock {
@@ -5206,6 +5313,12 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             bls_to_execution_changes,
         } = partial_beacon_block;
 
+        // Re-use the header of a full payload computed by the execution layer, rather than
+        // hashing its transactions again during block processing.
+        let payload_header = block_contents
+            .as_mut()
+            .and_then(BlockProposalContents::take_payload_header);
+
         let (attester_slashings_base, attester_slashings_electra) =
             attester_slashings.into_iter().fold(
                 (Vec::new(), Vec::new()),
@@ -5441,6 +5554,9 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
         // Use a context without block root or proposer index so that both are checked.
         let mut ctxt = ConsensusContext::new(block.slot());
+        if let Some(payload_header) = payload_header {
+            ctxt = ctxt.set_execution_payload_header(payload_header);
+        }
 
         let consensus_block_value = self
             .compute_beacon_block_reward(block.message(), Hash256::zero(), &mut state)
@@ -6249,6 +6365,9 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             // sync anyway).
             self.naive_aggregation_pool.write().prune(slot);
             self.block_times_cache.write().prune(slot);
+            self.graffiti_analysis_cache
+                .write()
+                .prune(slot, T::EthSpec::slots_per_historical_root() as u64);
 
             // Don't run heavy-weight tasks during sync.
             if self.best_slot() + MAX_PER_SLOT_FORK_CHOICE_DISTANCE < slot {
@@ -6461,6 +6580,42 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         }
     }
 
+    /// Compute the committees of a finalized `epoch` from the RANDAO mixes and validator registry
+    /// in the freezer database, without loading a full state.
+    ///
+    /// Returns `None` if the data required isn't in the freezer database, e.g. because `epoch`
+    /// was finalized recently or its restore points weren't stored after checkpoint sync.
+    pub fn historic_committee_cache(
+        &self,
+        epoch: Epoch,
+    ) -> Result<Option<Arc<CommitteeCache>>, Error> {
+        // The seeds of the first epochs use the genesis RANDAO mix, which the genesis state has.
+        let Ok(randao_epoch) = epoch
+            .safe_sub(self.spec.min_seed_lookahead)
+            .and_then(|epoch| epoch.safe_sub(1))
+        else {
+            return Ok(None);
+        };
+        let Some(mix) = self.store.get_cold_randao_mix(randao_epoch)? else {
+            return Ok(None);
+        };
+        let Some(validators) = self
+            .store
+            .load_cold_validators(epoch.start_slot(T::EthSpec::slots_per_epoch()))?
+        else {
+            return Ok(None);
+        };
+
+        let seed = compute_seed(&mix, epoch, Domain::BeaconAttester, &self.spec);
+        let committee_cache = CommitteeCache::initialized_from_validators::<T::EthSpec>(
+            &validators,
+            epoch,
+            seed,
+            &self.spec,
+        )?;
+        Ok(Some(committee_cache))
+    }
+
     /// Dumps the entire canonical chain, from the head to genesis to a vector for analysis.
     ///
     /// This could be a very expensive operation and should only be done in testing/analysis
@@ -6547,72 +6702,140 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
     /// This method serves to get a sense of the current chain health. It is used in block proposal
     /// to determine whether we should outsource payload production duties.
+    pub fn is_healthy(&self, parent_root: &Hash256) -> Result<ChainHealth, Error> {
+        let circuit_breaker = self.builder_circuit_breaker(parent_root)?;
+        Ok(match circuit_breaker.status {
+            ChainHealthStatus::Healthy => ChainHealth::Healthy,
+            ChainHealthStatus::Optimistic => ChainHealth::Optimistic,
+            ChainHealthStatus::PreMerge => ChainHealth::PreMerge,
+            ChainHealthStatus::Unhealthy => circuit_breaker
+                .tripped_condition()
+                .map_or(ChainHealth::Healthy, ChainHealth::Unhealthy),
+        })
+    }
+
+    /// Evaluate each of the chain health conditions which decide whether the builder API may be
+    /// used to produce a block on top of `parent_root`.
     ///
     /// Since we are likely calling this during the slot we are going to propose in, don't take into
     /// account the current slot when accounting for skips.
-    pub fn is_healthy(&self, parent_root: &Hash256) -> Result<ChainHealth, Error> {
+    pub fn builder_circuit_breaker(
+        &self,
+        parent_root: &Hash256,
+    ) -> Result<BuilderCircuitBreaker, Error> {
+        let checks_disabled = self.config.builder_fallback_disable_checks;
         let cached_head = self.canonical_head.cached_head();
-        if let Some(head_hash) = cached_head.forkchoice_update_parameters().head_hash {
-            if ExecutionBlockHash::zero() == head_hash {
-                return Ok(ChainHealth::PreMerge);
-            }
-        } else {
-            return Ok(ChainHealth::PreMerge);
+        let pre_merge = match cached_head.forkchoice_update_parameters().head_hash {
+            Some(head_hash) => head_hash == ExecutionBlockHash::zero(),
+            None => true,
         };
+        if pre_merge {
+            return Ok(BuilderCircuitBreaker {
+                status: ChainHealthStatus::PreMerge,
+                checks_disabled,
+                conditions: vec![],
+            });
+        }
 
         // Check that the parent is NOT optimistic.
-        if let Some(execution_status) = self
+        let optimistic = self
             .canonical_head
             .fork_choice_read_lock()
             .get_block_execution_status(parent_root)
-        {
-            if execution_status.is_strictly_optimistic() {
-                return Ok(ChainHealth::Optimistic);
-            }
+            .is_some_and(|execution_status| execution_status.is_strictly_optimistic());
+        if optimistic {
+            return Ok(BuilderCircuitBreaker {
+                status: ChainHealthStatus::Optimistic,
+                checks_disabled,
+                conditions: vec![],
+            });
         }
 
-        if self.config.builder_fallback_disable_checks {
-            return Ok(ChainHealth::Healthy);
+        // Avoid reading the block roots of the skips window if the conditions are ignored anyway.
+        if checks_disabled {
+            return Ok(BuilderCircuitBreaker {
+                status: ChainHealthStatus::Healthy,
+                checks_disabled,
+                conditions: vec![],
+            });
         }
 
         let current_slot = self.slot()?;
+        let slots_per_epoch = T::EthSpec::slots_per_epoch();
 
         // Check slots at the head of the chain.
         let prev_slot = current_slot.saturating_sub(Slot::new(1));
         let head_skips = prev_slot.saturating_sub(cached_head.head_slot());
-        let head_skips_check = head_skips.as_usize() <= self.config.builder_fallback_skips;
 
         // Check if finalization is advancing.
-        let current_epoch = current_slot.epoch(T::EthSpec::slots_per_epoch());
+        let current_epoch = current_slot.epoch(slots_per_epoch);
         let epochs_since_finalization =
             current_epoch.saturating_sub(cached_head.finalized_checkpoint().epoch);
-        let finalization_check = epochs_since_finalization.as_usize()
-            <= self.config.builder_fallback_epochs_since_finalization;
 
-        // Check skip slots in the last `SLOTS_PER_EPOCH`.
-        let start_slot = current_slot.saturating_sub(T::EthSpec::slots_per_epoch());
-        let mut epoch_skips = 0;
+        // Check skip slots in the last `builder_fallback_skips_window` slots.
+        let window = self
+            .config
+            .builder_fallback_skips_window
+            .unwrap_or(slots_per_epoch);
+        let start_slot = current_slot.saturating_sub(window);
+        let mut window_skips = 0;
         for slot in start_slot.as_u64()..current_slot.as_u64() {
             if self
                 .block_root_at_slot_skips_none(Slot::new(slot))?
                 .is_none()
             {
-                epoch_skips += 1;
+                window_skips += 1;
             }
         }
-        let epoch_skips_check = epoch_skips <= self.config.builder_fallback_skips_per_epoch;
 
-        if !head_skips_check {
-            Ok(ChainHealth::Unhealthy(FailedCondition::Skips))
-        } else if !finalization_check {
-            Ok(ChainHealth::Unhealthy(
-                FailedCondition::EpochsSinceFinalization,
-            ))
-        } else if !epoch_skips_check {
-            Ok(ChainHealth::Unhealthy(FailedCondition::SkipsPerEpoch))
+        // Check how late the head block arrived.
+        let head_delay = self
+            .block_times_cache
+            .read()
+            .get_block_delays(
+                cached_head.head_block_root(),
+                self.slot_clock
+                    .start_of(cached_head.head_slot())
+                    .unwrap_or_default(),
+            )
+            .observed
+            .map(|delay| delay.as_millis() as u64);
+
+        // The order of the conditions determines which is reported when several have tripped.
+        let conditions = vec![
+            CircuitBreakerCheck::new(
+                CircuitBreakerCondition::Skips,
+                Some(head_skips.as_u64()),
+                Some(self.config.builder_fallback_skips as u64),
+            ),
+            CircuitBreakerCheck::new(
+                CircuitBreakerCondition::EpochsSinceFinalization,
+                Some(epochs_since_finalization.as_u64()),
+                Some(self.config.builder_fallback_epochs_since_finalization as u64),
+            ),
+            CircuitBreakerCheck::new(
+                CircuitBreakerCondition::SkipsPerEpoch,
+                Some(window_skips),
+                Some(self.config.builder_fallback_skips_per_epoch as u64),
+            ),
+            CircuitBreakerCheck::new(
+                CircuitBreakerCondition::HeadDelay,
+                head_delay,
+                self.config.builder_fallback_head_delay_ms,
+            ),
+        ];
+
+        let status = if conditions.iter().any(|check| check.tripped) {
+            ChainHealthStatus::Unhealthy
         } else {
-            Ok(ChainHealth::Healthy)
-        }
+            ChainHealthStatus::Healthy
+        };
+
+        Ok(BuilderCircuitBreaker {
+            status,
+            checks_disabled,
+            conditions,
+        })
     }
 
     pub fn dump_as_dot<W: Write>(&self, output: &mut W) {
@@ -6797,6 +7020,133 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         }
     }
 
+    /// Gets the best `LightClientUpdate` of the sync committee `period`, generating it from the
+    /// blocks and states in the database if it isn't cached.
+    ///
+    /// The best update is the one with the greatest sync committee participation, with ties going
+    /// to the latest update since it carries the most recent finality. Returns `None` if the period
+    /// hasn't started, if its blocks haven't been backfilled, or if no block in the period has
+    /// enough participation.
+    pub fn get_light_client_update(
+        &self,
+        period: u64,
+    ) -> Result<Option<(LightClientUpdate<T::EthSpec>, ForkName)>, Error> {
+        if let Some(update) = self.light_client_server_cache.get_historical_update(period) {
+            return Ok(Some(update));
+        }
+
+        let slots_per_period = self
+            .spec
+            .epochs_per_sync_committee_period
+            .as_u64()
+            .safe_mul(T::EthSpec::slots_per_epoch())?;
+        let start_slot = Slot::new(period.safe_mul(slots_per_period)?);
+        let end_slot = start_slot.safe_add(slots_per_period)?.safe_sub(1)?;
+
+        let cached_head = self.canonical_head.cached_head();
+        let head_slot = cached_head.head_slot();
+        let finalized_slot = cached_head
+            .finalized_checkpoint()
+            .epoch
+            .start_slot(T::EthSpec::slots_per_epoch());
+        if start_slot > head_slot || start_slot < self.store.get_oldest_block_slot() {
+            return Ok(None);
+        }
+
+        // Find the block with the best sync aggregate whose parent, the attested block, is also in
+        // the period.
+        let mut best: Option<(usize, Hash256, Hash256)> = None;
+        let mut prev_block: Option<(Hash256, Slot)> = None;
+        for result in self.forwards_iter_block_roots_until(start_slot, end_slot.min(head_slot))? {
+            let (block_root, _) = result?;
+            if prev_block.map_or(false, |(prev_root, _)| prev_root == block_root) {
+                continue;
+            }
+            let block = self
+                .get_blinded_block(&block_root)?
+                .ok_or(Error::MissingBeaconBlock(block_root))?;
+            if let Some((parent_root, parent_slot)) = prev_block {
+                let participation = block
+                    .message()
+                    .body()
+                    .sync_aggregate()
+                    .map_or(0, |sync_aggregate| sync_aggregate.num_set_bits());
+                if parent_slot >= start_slot
+                    && block.parent_root() == parent_root
+                    && self.spec.fork_name_at_slot::<T::EthSpec>(parent_slot) != ForkName::Base
+                    && participation >= self.spec.min_sync_committee_participants as usize
+                    && best.map_or(true, |(best_participation, _, _)| {
+                        participation >= best_participation
+                    })
+                {
+                    best = Some((participation, block_root, parent_root));
+                }
+            }
+            prev_block = Some((block_root, block.slot()));
+        }
+        let Some((_, signature_root, attested_root)) = best else {
+            return Ok(None);
+        };
+
+        let handle = self
+            .task_executor
+            .handle()
+            .ok_or(BeaconChainError::RuntimeShutdown)?;
+
+        let Some(signature_block) = handle.block_on(self.get_block(&signature_root))? else {
+            return Ok(None);
+        };
+        let Some(attested_block) = handle.block_on(self.get_block(&attested_root))? else {
+            return Ok(None);
+        };
+        let Some(mut attested_state) =
+            self.get_state(&attested_block.state_root(), Some(attested_block.slot()))?
+        else {
+            return Ok(None);
+        };
+        let finalized_root = attested_state.finalized_checkpoint().root;
+        let Some(finalized_block) = handle.block_on(self.get_block(&finalized_root))? else {
+            return Ok(None);
+        };
+
+        let fork_name = attested_state
+            .fork_name(&self.spec)
+            .map_err(Error::InconsistentFork)?;
+        let update = LightClientUpdate::new(
+            attested_state.clone(),
+            signature_block.message().clone(),
+            &mut attested_state,
+            &attested_block,
+            &finalized_block,
+            &self.spec,
+        )?;
+
+        // A better update may yet be included in a period which isn't finalized.
+        if end_slot < finalized_slot {
+            self.light_client_server_cache
+                .cache_historical_update(period, (update.clone(), fork_name));
+        }
+
+        Ok(Some((update, fork_name)))
+    }
+
+    /// Gets the best `LightClientUpdate` of up to `count` consecutive sync committee periods,
+    /// starting at `start_period`. Stops at the first period without an update.
+    pub fn get_light_client_updates(
+        &self,
+        start_period: u64,
+        count: u64,
+    ) -> Result<Vec<(LightClientUpdate<T::EthSpec>, ForkName)>, Error> {
+        let mut updates = vec![];
+        for period in start_period..start_period.saturating_add(count) {
+            let Some(update) = self.get_light_client_update(period)? else {
+                break;
+            };
+            updates.push(update);
+        }
+        Ok(updates)
+    }
+
     pub fn metrics(&self) -> BeaconChainMetrics {
         BeaconChainMetrics {
             reqresp_pre_import_cache_len: self.reqresp_pre_import_cache.read().len(),
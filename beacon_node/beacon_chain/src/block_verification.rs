@@ -1341,6 +1341,7 @@ impl<T: BeaconChainTypes> ExecutionPendingBlock<T> {
         // with the payload verification.
         let payload_notifier = PayloadNotifier::new(
             chain.clone(),
+            block_root,
             block.block_cloned(),
             &parent.pre_state,
             notify_execution_layer,
@@ -49,12 +49,19 @@ pub struct ChainConfig {
     pub fork_choice_before_proposal_timeout_ms: u64,
     /// Number of skip slots in a row before the BN refuses to use connected builders during payload construction.
     pub builder_fallback_skips: usize,
-    /// Number of skip slots in the past `SLOTS_PER_EPOCH` before the BN refuses to use connected
-    /// builders during payload construction.
+    /// Number of skip slots in the past `builder_fallback_skips_window` slots before the BN refuses
+    /// to use connected builders during payload construction.
     pub builder_fallback_skips_per_epoch: usize,
+    /// Number of slots over which `builder_fallback_skips_per_epoch` is counted. Defaults to
+    /// `SLOTS_PER_EPOCH` if `None`.
+    pub builder_fallback_skips_window: Option<u64>,
     /// Number of epochs since finalization before the BN refuses to use connected builders during
     /// payload construction.
     pub builder_fallback_epochs_since_finalization: usize,
+    /// Number of milliseconds after the start of its slot that the head block may be observed
+    /// before the BN refuses to use connected builders during payload construction. Disabled if
+    /// `None`.
+    pub builder_fallback_head_delay_ms: Option<u64>,
     /// Whether any chain health checks should be considered when deciding whether to use the builder API.
     pub builder_fallback_disable_checks: bool,
     /// When set to `true`, forget any valid/invalid/optimistic statuses in fork choice during start
@@ -84,6 +91,11 @@ pub struct ChainConfig {
     pub epochs_per_migration: u64,
     /// When set to true Light client server computes and caches state proofs for serving updates
     pub enable_light_client_server: bool,
+    /// The maximum number of execution payloads sent to the EL concurrently, ahead of block
+    /// import, whilst importing a chain segment.
+    ///
+    /// If set to 0 then payloads are only sent to the EL as each block is imported.
+    pub payload_prefetch_window: usize,
 }
 
 impl Default for ChainConfig {
@@ -102,7 +114,9 @@ impl Default for ChainConfig {
             // Builder fallback configs that are set in `clap` will override these.
             builder_fallback_skips: 3,
             builder_fallback_skips_per_epoch: 8,
+            builder_fallback_skips_window: None,
             builder_fallback_epochs_since_finalization: 3,
+            builder_fallback_head_delay_ms: None,
             builder_fallback_disable_checks: false,
             always_reset_payload_statuses: false,
             paranoid_block_proposal: false,
@@ -115,6 +129,7 @@ impl Default for ChainConfig {
             always_prepare_payload: false,
             epochs_per_migration: crate::migrate::DEFAULT_EPOCHS_PER_MIGRATION,
             enable_light_client_server: false,
+            payload_prefetch_window: crate::payload_prefetch::DEFAULT_PAYLOAD_PREFETCH_WINDOW,
         }
     }
 }
@@ -126,6 +126,11 @@ pub enum BeaconChainError {
         state_slot: Slot,
     },
     HistoricalBlockError(HistoricalBlockError),
+    HistoricStateReplayLimitExceeded {
+        slot: Slot,
+        replay_depth: u64,
+        max_replay_slots: u64,
+    },
     InvalidStateForShuffling {
         state_epoch: Epoch,
         shuffling_epoch: Epoch,
@@ -1,4 +1,4 @@
-pub use eth2::types::{EventKind, SseBlock, SseFinalizedCheckpoint, SseHead};
+pub use eth2::types::{EventKind, EventTopic, SseBlock, SseFinalizedCheckpoint, SseHead};
 use slog::{trace, Logger};
 use tokio::sync::broadcast;
 use tokio::sync::broadcast::{error::SendError, Receiver, Sender};
@@ -160,6 +160,31 @@ impl<E: EthSpec> ServerSentEventHandler<E> {
         }
     }
 
+    /// Subscribes to the events of `topic`.
+    pub fn subscribe(&self, topic: EventTopic) -> Receiver<EventKind<E>> {
+        match topic {
+            EventTopic::Head => self.subscribe_head(),
+            EventTopic::Block => self.subscribe_block(),
+            EventTopic::BlobSidecar => self.subscribe_blob_sidecar(),
+            EventTopic::Attestation => self.subscribe_attestation(),
+            EventTopic::VoluntaryExit => self.subscribe_exit(),
+            EventTopic::FinalizedCheckpoint => self.subscribe_finalized(),
+            EventTopic::ChainReorg => self.subscribe_reorgs(),
+            EventTopic::ContributionAndProof => self.subscribe_contributions(),
+            EventTopic::PayloadAttributes => self.subscribe_payload_attributes(),
+            EventTopic::LateHead => self.subscribe_late_head(),
+            EventTopic::LightClientFinalityUpdate => self.subscribe_light_client_finality_update(),
+            EventTopic::LightClientOptimisticUpdate => {
+                self.subscribe_light_client_optimistic_update()
+            }
+            EventTopic::BlockReward => self.subscribe_block_reward(),
+            EventTopic::AttesterSlashing => self.subscribe_attester_slashing(),
+            EventTopic::ProposerSlashing => self.subscribe_proposer_slashing(),
+            EventTopic::BlsToExecutionChange => self.subscribe_bls_to_execution_change(),
+            EventTopic::BlockGossip => self.subscribe_block_gossip(),
+        }
+    }
+
     pub fn subscribe_attestation(&self) -> Receiver<EventKind<E>> {
         self.attestation_tx.subscribe()
     }
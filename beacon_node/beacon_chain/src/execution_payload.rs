@@ -52,6 +52,7 @@ pub enum NotifyExecutionLayer {
 /// Used to await the result of executing payload with a remote EE.
 pub struct PayloadNotifier<T: BeaconChainTypes> {
     pub chain: Arc<BeaconChain<T>>,
+    pub block_root: Hash256,
     pub block: Arc<SignedBeaconBlock<T::EthSpec>>,
     payload_verification_status: Option<PayloadVerificationStatus>,
 }
@@ -59,6 +60,7 @@ pub struct PayloadNotifier<T: BeaconChainTypes> {
 impl<T: BeaconChainTypes> PayloadNotifier<T> {
     pub fn new(
         chain: Arc<BeaconChain<T>>,
+        block_root: Hash256,
         block: Arc<SignedBeaconBlock<T::EthSpec>>,
         state: &BeaconState<T::EthSpec>,
         notify_execution_layer: NotifyExecutionLayer,
@@ -105,6 +107,7 @@ impl<T: BeaconChainTypes> PayloadNotifier<T> {
 
         Ok(Self {
             chain,
+            block_root,
             block,
             payload_verification_status,
         })
@@ -116,7 +119,7 @@ impl<T: BeaconChainTypes> PayloadNotifier<T> {
         if let Some(precomputed_status) = self.payload_verification_status {
             Ok(precomputed_status)
         } else {
-            notify_new_payload(&self.chain, self.block.message()).await
+            notify_new_payload(&self.chain, self.block_root, self.block.message()).await
         }
     }
 }
@@ -132,6 +135,7 @@ impl<T: BeaconChainTypes> PayloadNotifier<T> {
 /// https://github.com/ethereum/consensus-specs/blob/v1.1.9/specs/bellatrix/beacon-chain.md#notify_new_payload
 async fn notify_new_payload<'a, T: BeaconChainTypes>(
     chain: &Arc<BeaconChain<T>>,
+    block_root: Hash256,
     block: BeaconBlockRef<'a, T::EthSpec>,
 ) -> Result<PayloadVerificationStatus, BlockError<T::EthSpec>> {
     let execution_layer = chain
@@ -140,6 +144,18 @@ async fn notify_new_payload<'a, T: BeaconChainTypes>(
         .ok_or(ExecutionPayloadError::NoExecutionConnection)?;
 
     let execution_block_hash = block.execution_payload()?.block_hash();
+
+    // Use the response to this block's payload if it was sent ahead of import, but only if it
+    // was valid. Any other status may have been caused by the EL not yet having processed the
+    // parent, so the payload is sent again.
+    if let Some(PayloadStatus::Valid) = chain
+        .payload_prefetch_cache
+        .take_status(block_root, execution_block_hash)
+        .await
+    {
+        return Ok(PayloadVerificationStatus::Verified);
+    }
+
     let new_payload_response = execution_layer.notify_new_payload(block.try_into()?).await;
 
     match new_payload_response {
@@ -506,6 +522,7 @@ where
                 BlockProposalContents::Payload {
                     payload: FullPayload::default_at_fork(fork)?,
                     block_value: Uint256::zero(),
+                    payload_header: None,
                 },
             ));
         }
@@ -524,6 +541,7 @@ where
                 BlockProposalContents::Payload {
                     payload: FullPayload::default_at_fork(fork)?,
                     block_value: Uint256::zero(),
+                    payload_header: None,
                 },
             ));
         }
@@ -0,0 +1,209 @@
+//! Tracks the graffiti of recently imported blocks, so that the clients run by block proposers can
+//! be analysed without indexing the chain externally.
+//!
+//! Clients are detected from the client codes recommended for graffiti by the
+//! `engine_getClientVersionV1` spec, e.g. `GE1a2bLH3c4d`, or else from client names such as
+//! `Lighthouse/v5.2.0` appearing in the graffiti.
+
+use crate::BeaconSnapshot;
+use eth2::lighthouse::GraffitiAnalysis;
+use execution_layer::ClientCode;
+use std::collections::BTreeMap;
+use types::{EthSpec, Graffiti, Hash256, Slot};
+
+/// The key of blocks in which no client was detected.
+const UNKNOWN_CLIENT: &str = "unknown";
+
+/// The clients detected in the graffiti of a block.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ClientTags {
+    pub consensus: Option<ClientCode>,
+    pub execution: Option<ClientCode>,
+}
+
+impl ClientTags {
+    pub fn from_graffiti(graffiti: &Graffiti) -> Self {
+        let graffiti = graffiti.as_utf8_lossy();
+        let mut tags = Self::default();
+        for code in leading_client_codes(&graffiti).unwrap_or_else(|| named_client_codes(&graffiti))
+        {
+            if is_consensus_client(&code) {
+                tags.consensus.get_or_insert(code);
+            } else {
+                tags.execution.get_or_insert(code);
+            }
+        }
+        tags
+    }
+}
+
+fn known_client_code(code: &str) -> Option<ClientCode> {
+    match ClientCode::try_from(code.to_string()) {
+        Ok(ClientCode::Unknown(_)) | Err(_) => None,
+        Ok(code) => Some(code),
+    }
+}
+
+fn is_consensus_client(code: &ClientCode) -> bool {
+    matches!(
+        code,
+        ClientCode::Grandine
+            | ClientCode::Lighthouse
+            | ClientCode::Lodestar
+            | ClientCode::Nimbus
+            | ClientCode::Teku
+            | ClientCode::Prysm
+    )
+}
+
+/// Returns the client codes at the start of `graffiti`, each optionally followed by a lowercase
+/// commit prefix, e.g. `GE1a2bLH3c4d` or `NMLH`.
+///
+/// At least two codes are required, so that graffiti which happens to start with two capitals
+/// isn't mistaken for a client.
+fn leading_client_codes(graffiti: &str) -> Option<Vec<ClientCode>> {
+    let mut codes = vec![];
+    let mut rest = graffiti;
+    while let Some(code) = rest.get(..2).and_then(known_client_code) {
+        codes.push(code);
+        rest = rest
+            .get(2..)
+            .unwrap_or_default()
+            .trim_start_matches(|c: char| matches!(c, '0'..='9' | 'a'..='f'));
+    }
+    (codes.len() >= 2).then_some(codes)
+}
+
+/// Returns the clients named by the words of `graffiti`, e.g. `Lighthouse/v5.2.0`.
+fn named_client_codes(graffiti: &str) -> Vec<ClientCode> {
+    graffiti
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter_map(|word| match word.to_lowercase().as_str() {
+            "besu" => Some(ClientCode::Besu),
+            "ethereumjs" => Some(ClientCode::EtherumJS),
+            "erigon" => Some(ClientCode::Erigon),
+            "geth" => Some(ClientCode::GoEthereum),
+            "grandine" => Some(ClientCode::Grandine),
+            "lighthouse" => Some(ClientCode::Lighthouse),
+            "lodestar" => Some(ClientCode::Lodestar),
+            "nethermind" => Some(ClientCode::Nethermind),
+            "nimbus" => Some(ClientCode::Nimbus),
+            "teku" => Some(ClientCode::Teku),
+            "prysm" => Some(ClientCode::Prysm),
+            "reth" => Some(ClientCode::Reth),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The client tags of the blocks imported in a window of recent slots, including blocks which
+/// may not be canonical.
+#[derive(Default)]
+pub struct GraffitiAnalysisCache {
+    blocks: BTreeMap<Slot, Vec<(Hash256, ClientTags)>>,
+}
+
+impl GraffitiAnalysisCache {
+    pub fn observe(&mut self, block_root: Hash256, slot: Slot, graffiti: &Graffiti) {
+        self.blocks
+            .entry(slot)
+            .or_default()
+            .push((block_root, ClientTags::from_graffiti(graffiti)));
+    }
+
+    /// Removes blocks more than `window` slots before `current_slot`.
+    pub fn prune(&mut self, current_slot: Slot, window: u64) {
+        let start_slot = current_slot.saturating_sub(window);
+        self.blocks = self.blocks.split_off(&start_slot);
+    }
+
+    /// Counts the clients of the blocks which are canonical in the chain of `head`, within the
+    /// `SLOTS_PER_HISTORICAL_ROOT` slots up to the head.
+    pub fn analyse<E: EthSpec>(&self, head: &BeaconSnapshot<E>) -> GraffitiAnalysis {
+        let head_slot = head.beacon_block.slot();
+        let start_slot = (head_slot + 1).saturating_sub(E::slots_per_historical_root() as u64);
+
+        let mut analysis = GraffitiAnalysis {
+            start_slot,
+            end_slot: head_slot,
+            blocks: 0,
+            consensus_clients: BTreeMap::new(),
+            execution_clients: BTreeMap::new(),
+        };
+        for (&slot, blocks) in self.blocks.range(start_slot..=head_slot) {
+            let canonical_root = if slot == head_slot {
+                head.beacon_block_root
+            } else {
+                match head.beacon_state.get_block_root(slot) {
+                    Ok(root) => *root,
+                    Err(_) => continue,
+                }
+            };
+            let Some((_, tags)) = blocks.iter().find(|(root, _)| *root == canonical_root) else {
+                continue;
+            };
+
+            analysis.blocks += 1;
+            for (code, counts) in [
+                (&tags.consensus, &mut analysis.consensus_clients),
+                (&tags.execution, &mut analysis.execution_clients),
+            ] {
+                let key = code
+                    .as_ref()
+                    .map_or_else(|| UNKNOWN_CLIENT.to_string(), ToString::to_string);
+                *counts.entry(key).or_default() += 1;
+            }
+        }
+        analysis
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(graffiti: &str) -> ClientTags {
+        let mut bytes = [0; 32];
+        bytes[..graffiti.len()].copy_from_slice(graffiti.as_bytes());
+        ClientTags::from_graffiti(&Graffiti::from(bytes))
+    }
+
+    #[test]
+    fn client_codes() {
+        assert_eq!(
+            tags("GE1a2bLH3c4d"),
+            ClientTags {
+                consensus: Some(ClientCode::Lighthouse),
+                execution: Some(ClientCode::GoEthereum),
+            }
+        );
+        assert_eq!(
+            tags("NMTK"),
+            ClientTags {
+                consensus: Some(ClientCode::Teku),
+                execution: Some(ClientCode::Nethermind),
+            }
+        );
+        // A single leading code is too likely to be a coincidence.
+        assert_eq!(tags("NMBR was here"), ClientTags::default());
+    }
+
+    #[test]
+    fn client_names() {
+        assert_eq!(
+            tags("Lighthouse/v5.2.0-1a2b3c4"),
+            ClientTags {
+                consensus: Some(ClientCode::Lighthouse),
+                execution: None,
+            }
+        );
+        assert_eq!(
+            tags("prysm + reth"),
+            ClientTags {
+                consensus: Some(ClientCode::Prysm),
+                execution: Some(ClientCode::Reth),
+            }
+        );
+        assert_eq!(tags("hello world"), ClientTags::default());
+    }
+}
@@ -352,6 +352,16 @@ mod tests {
         expected_graffiti_bytes[..expected_graffiti_prefix_len]
             .copy_from_slice(expected_graffiti_string.as_bytes());
         assert_eq!(found_graffiti_bytes, expected_graffiti_bytes);
+
+        // The calculated graffiti should be recognisable as a client version.
+        let client_version = Graffiti::from(found_graffiti_bytes)
+            .client_version()
+            .expect("graffiti should contain client version");
+        assert_eq!(
+            client_version.execution_code,
+            DEFAULT_CLIENT_VERSION.code.to_string()
+        );
+        assert_eq!(client_version.consensus_code, "LH");
     }
 
     #[tokio::test]
@@ -30,6 +30,7 @@ pub mod events;
 pub mod execution_payload;
 pub mod fork_choice_signal;
 pub mod fork_revert;
+pub mod graffiti_analysis;
 pub mod graffiti_calculator;
 mod head_tracker;
 pub mod historical_blocks;
@@ -47,6 +48,7 @@ pub mod observed_block_producers;
 pub mod observed_operations;
 mod observed_slashable;
 pub mod otb_verification_service;
+pub mod payload_prefetch;
 mod persisted_beacon_chain;
 mod persisted_fork_choice;
 mod pre_finalization_cache;
@@ -86,7 +88,7 @@ pub use eth1_chain::{Eth1Chain, Eth1ChainBackend};
 pub use events::ServerSentEventHandler;
 pub use execution_layer::EngineState;
 pub use execution_payload::NotifyExecutionLayer;
-pub use fork_choice::{ExecutionStatus, ForkchoiceUpdateParameters};
+pub use fork_choice::{ExecutionStatus, ForkChoiceStore, ForkchoiceUpdateParameters};
 pub use kzg::{Kzg, TrustedSetup};
 pub use metrics::scrape_for_metrics;
 pub use migrate::MigratorConfig;
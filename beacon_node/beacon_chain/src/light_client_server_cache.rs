@@ -8,7 +8,7 @@ use types::light_client_update::{FinalizedRootProofLen, FINALIZED_ROOT_INDEX};
 use types::non_zero_usize::new_non_zero_usize;
 use types::{
     BeaconBlockRef, BeaconState, ChainSpec, EthSpec, ForkName, Hash256, LightClientFinalityUpdate,
-    LightClientOptimisticUpdate, Slot, SyncAggregate,
+    LightClientOptimisticUpdate, LightClientUpdate, Slot, SyncAggregate,
 };
 
 /// A prev block cache miss requires to re-generate the state of the post-parent block. Items in the
@@ -16,6 +16,11 @@ use types::{
 /// represents unlikely re-orgs, while keeping the cache very small.
 const PREV_BLOCK_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(32);
 
+/// The number of finalized sync committee periods whose best update is cached. Each update holds
+/// a sync committee, so is roughly 25KB on mainnet. 128 is the maximum number of updates which may
+/// be requested at once.
+const HISTORICAL_UPDATE_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(128);
+
 /// This cache computes light client messages ahead of time, required to satisfy p2p and API
 /// requests. These messages include proofs on historical states, so on-demand computation is
 /// expensive.
@@ -32,6 +37,9 @@ pub struct LightClientServerCache<T: BeaconChainTypes> {
     latest_optimistic_update: RwLock<Option<LightClientOptimisticUpdate<T::EthSpec>>>,
     /// Caches state proofs by block root
     prev_block_cache: Mutex<lru::LruCache<Hash256, LightClientCachedData>>,
+    /// Caches the best update of finalized sync committee periods by period. These updates are
+    /// generated from historical states on request, and never change once generated.
+    historical_updates: Mutex<lru::LruCache<u64, (LightClientUpdate<T::EthSpec>, ForkName)>>,
 }
 
 impl<T: BeaconChainTypes> LightClientServerCache<T> {
@@ -40,6 +48,7 @@ impl<T: BeaconChainTypes> LightClientServerCache<T> {
             latest_finality_update: None.into(),
             latest_optimistic_update: None.into(),
             prev_block_cache: lru::LruCache::new(PREV_BLOCK_CACHE_SIZE).into(),
+            historical_updates: lru::LruCache::new(HISTORICAL_UPDATE_CACHE_SIZE).into(),
         }
     }
 
@@ -191,6 +200,22 @@ impl<T: BeaconChainTypes> LightClientServerCache<T> {
     pub fn get_latest_optimistic_update(&self) -> Option<LightClientOptimisticUpdate<T::EthSpec>> {
         self.latest_optimistic_update.read().clone()
     }
+
+    pub fn get_historical_update(
+        &self,
+        period: u64,
+    ) -> Option<(LightClientUpdate<T::EthSpec>, ForkName)> {
+        self.historical_updates.lock().get(&period).cloned()
+    }
+
+    /// Cache the best update of `period`, which must be finalized.
+    pub fn cache_historical_update(
+        &self,
+        period: u64,
+        update: (LightClientUpdate<T::EthSpec>, ForkName),
+    ) {
+        self.historical_updates.lock().put(period, update);
+    }
 }
 
 impl<T: BeaconChainTypes> Default for LightClientServerCache<T> {
@@ -32,6 +32,11 @@ lazy_static! {
         "beacon_block_processing_successes_total",
         "Count of blocks processed without error"
     );
+    pub static ref BLOCK_PROCESSING_PREFETCHED_PAYLOADS: Result<IntCounterVec> = try_create_int_counter_vec(
+        "beacon_block_processing_prefetched_payloads_total",
+        "Count of execution payloads sent to the EL ahead of import, by the status returned",
+        &["outcome"]
+    );
     // Keeping the existing "snapshot_cache" metric name as it would break existing dashboards
     pub static ref BLOCK_PROCESSING_SNAPSHOT_CACHE_SIZE: Result<IntGauge> = try_create_int_gauge(
         "beacon_block_processing_snapshot_cache_size",
@@ -1140,6 +1145,11 @@ lazy_static! {
         "Number of non-finalized blocks missed",
         &["validator"]
     );
+    pub static ref VALIDATOR_MONITOR_MISSED_DUTY_CAUSES_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "validator_monitor_missed_duty_causes_total",
+        "Number of missed attestations and proposals, by their most likely cause",
+        &["duty", "cause", "validator"]
+    );
 
     /*
     * Kzg related metrics
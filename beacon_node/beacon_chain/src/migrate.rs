@@ -2,9 +2,10 @@ use crate::beacon_chain::BEACON_CHAIN_DB_KEY;
 use crate::errors::BeaconChainError;
 use crate::head_tracker::{HeadTracker, SszHeadTracker};
 use crate::persisted_beacon_chain::{PersistedBeaconChain, DUMMY_CANONICAL_HEAD_BLOCK_ROOT};
+use eth2::lighthouse::{DatabaseJob, DatabaseJobStatus, DatabaseOperation};
 use parking_lot::Mutex;
 use slog::{debug, error, info, warn, Logger};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::mem;
 use std::sync::{mpsc, Arc};
 use std::thread;
@@ -28,6 +29,9 @@ const COMPACTION_FINALITY_DISTANCE: u64 = 1024;
 /// Default number of epochs to wait between finalization migrations.
 pub const DEFAULT_EPOCHS_PER_MIGRATION: u64 = 1;
 
+/// The number of finished maintenance jobs to remember, after which the oldest are forgotten.
+const MAX_FINISHED_DATABASE_JOBS: usize = 64;
+
 /// The background migrator runs a thread to perform pruning and migrate state from the hot
 /// to the cold database.
 pub struct BackgroundMigrator<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> {
@@ -38,6 +42,8 @@ pub struct BackgroundMigrator<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>
     tx_thread: Option<Mutex<(mpsc::Sender<Notification>, thread::JoinHandle<()>)>>,
     /// Genesis block root, for persisting the `PersistedBeaconChain`.
     genesis_block_root: Hash256,
+    /// Maintenance jobs requested via the HTTP API, and their progress.
+    database_jobs: Arc<Mutex<DatabaseJobs>>,
     log: Logger,
 }
 
@@ -79,6 +85,82 @@ pub struct PrevMigration {
     epochs_per_migration: u64,
 }
 
+/// The maintenance jobs which are queued or running, and those which finished most recently.
+#[derive(Default)]
+pub struct DatabaseJobs {
+    next_id: u64,
+    jobs: BTreeMap<u64, DatabaseJob>,
+}
+
+impl DatabaseJobs {
+    fn queue(&mut self, operation: DatabaseOperation) -> DatabaseJob {
+        let job = DatabaseJob {
+            id: self.next_id,
+            operation,
+            status: DatabaseJobStatus::Queued,
+            error: None,
+            queued_at: unix_timestamp(),
+            started_at: None,
+            finished_at: None,
+        };
+        self.next_id += 1;
+        self.jobs.insert(job.id, job.clone());
+
+        let finished = self
+            .jobs
+            .values()
+            .filter(|job| job.finished_at.is_some())
+            .map(|job| job.id)
+            .collect::<Vec<_>>();
+        for id in finished
+            .iter()
+            .take(finished.len().saturating_sub(MAX_FINISHED_DATABASE_JOBS))
+        {
+            self.jobs.remove(id);
+        }
+        job
+    }
+
+    fn start(&mut self, id: u64) {
+        if let Some(job) = self.jobs.get_mut(&id) {
+            job.status = DatabaseJobStatus::Running;
+            job.started_at = Some(unix_timestamp());
+        }
+    }
+
+    fn finish(&mut self, id: u64, result: Result<(), Error>) {
+        if let Some(job) = self.jobs.get_mut(&id) {
+            match result {
+                Ok(()) => job.status = DatabaseJobStatus::Completed,
+                Err(e) => {
+                    job.status = DatabaseJobStatus::Failed;
+                    job.error = Some(format!("{e:?}"));
+                }
+            }
+            job.finished_at = Some(unix_timestamp());
+        }
+    }
+
+    /// Mark every unfinished job other than `except` as failed, because the thread which would
+    /// have run it has died.
+    fn fail_unfinished(&mut self, except: Option<u64>) {
+        let now = unix_timestamp();
+        for job in self.jobs.values_mut() {
+            if job.finished_at.is_none() && Some(job.id) != except {
+                job.status = DatabaseJobStatus::Failed;
+                job.error = Some("the migration thread died before the job finished".to_string());
+                job.finished_at = Some(now);
+            }
+        }
+    }
+}
+
+fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
 /// Pruning can be successful, or in rare cases deferred to a later point.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PruningOutcome {
@@ -118,6 +200,8 @@ pub enum Notification {
     Finalization(FinalizationNotification),
     Reconstruction,
     PruneBlobs(Epoch),
+    /// A maintenance job requested via the HTTP API, with its id.
+    Maintenance(u64, DatabaseOperation),
 }
 
 pub struct FinalizationNotification {
@@ -141,16 +225,22 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> BackgroundMigrator<E, Ho
             epoch: db.get_split_slot().epoch(E::slots_per_epoch()),
             epochs_per_migration: config.epochs_per_migration,
         }));
+        let database_jobs = Arc::new(Mutex::new(DatabaseJobs::default()));
         let tx_thread = if config.blocking {
             None
         } else {
-            Some(Mutex::new(Self::spawn_thread(db.clone(), log.clone())))
+            Some(Mutex::new(Self::spawn_thread(
+                db.clone(),
+                database_jobs.clone(),
+                log.clone(),
+            )))
         };
         Self {
             db,
             tx_thread,
             prev_migration,
             genesis_block_root,
+            database_jobs,
             log,
         }
     }
@@ -200,6 +290,59 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> BackgroundMigrator<E, Ho
         }
     }
 
+    /// Queue `operation` to run after any migration in progress, returning the job which tracks
+    /// its progress.
+    pub fn process_maintenance(&self, operation: DatabaseOperation) -> DatabaseJob {
+        let job = self.database_jobs.lock().queue(operation);
+        if let Some(Notification::Maintenance(id, operation)) =
+            self.send_background_notification(Notification::Maintenance(job.id, operation))
+        {
+            Self::run_maintenance(
+                self.db.clone(),
+                &self.database_jobs,
+                id,
+                operation,
+                &self.log,
+            );
+        }
+        self.database_job(job.id).unwrap_or(job)
+    }
+
+    /// Return the maintenance job with `id`, unless it finished long enough ago to be forgotten.
+    pub fn database_job(&self, id: u64) -> Option<DatabaseJob> {
+        self.database_jobs.lock().jobs.get(&id).cloned()
+    }
+
+    pub fn run_maintenance(
+        db: Arc<HotColdDB<E, Hot, Cold>>,
+        database_jobs: &Mutex<DatabaseJobs>,
+        id: u64,
+        operation: DatabaseOperation,
+        log: &Logger,
+    ) {
+        info!(log, "Starting database maintenance"; "operation" => %operation, "job" => id);
+        database_jobs.lock().start(id);
+
+        let result = match operation {
+            DatabaseOperation::PrunePayloads => db.try_prune_execution_payloads(true),
+            DatabaseOperation::PruneBlobs => db.try_prune_most_blobs(true),
+            DatabaseOperation::Compact => db.compact(),
+        };
+        match &result {
+            Ok(()) => {
+                info!(log, "Database maintenance complete"; "operation" => %operation, "job" => id)
+            }
+            Err(e) => error!(
+                log,
+                "Database maintenance failed";
+                "operation" => %operation,
+                "job" => id,
+                "error" => ?e,
+            ),
+        }
+        database_jobs.lock().finish(id, result);
+    }
+
     pub fn run_reconstruction(db: Arc<HotColdDB<E, Hot, Cold>>, log: &Logger) {
         if let Err(e) = db.reconstruct_historic_states() {
             error!(
@@ -235,7 +378,11 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> BackgroundMigrator<E, Ho
 
             // Restart the background thread if it has crashed.
             if let Err(tx_err) = tx.send(notif) {
-                let (new_tx, new_thread) = Self::spawn_thread(self.db.clone(), self.log.clone());
+                let (new_tx, new_thread) = Self::spawn_thread(
+                    self.db.clone(),
+                    self.database_jobs.clone(),
+                    self.log.clone(),
+                );
 
                 *tx = new_tx;
                 let old_thread = mem::replace(thread, new_thread);
@@ -250,6 +397,13 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> BackgroundMigrator<E, Ho
                     );
                 }
 
+                // Any jobs sent to the old thread, other than the one being retried, are lost.
+                let retried = match &tx_err.0 {
+                    Notification::Maintenance(id, _) => Some(*id),
+                    _ => None,
+                };
+                self.database_jobs.lock().fail_unfinished(retried);
+
                 // Retry at most once, we could recurse but that would risk overflowing the stack.
                 let _ = tx.send(tx_err.0);
             }
@@ -385,6 +539,7 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> BackgroundMigrator<E, Ho
     /// Return a channel handle for sending requests to the thread.
     fn spawn_thread(
         db: Arc<HotColdDB<E, Hot, Cold>>,
+        database_jobs: Arc<Mutex<DatabaseJobs>>,
         log: Logger,
     ) -> (mpsc::Sender<Notification>, thread::JoinHandle<()>) {
         let (tx, rx) = mpsc::channel();
@@ -393,10 +548,12 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> BackgroundMigrator<E, Ho
                 let mut reconstruction_notif = None;
                 let mut finalization_notif = None;
                 let mut prune_blobs_notif = None;
+                let mut maintenance_notifs = vec![];
                 match notif {
                     Notification::Reconstruction => reconstruction_notif = Some(notif),
                     Notification::Finalization(fin) => finalization_notif = Some(fin),
                     Notification::PruneBlobs(dab) => prune_blobs_notif = Some(dab),
+                    Notification::Maintenance(id, op) => maintenance_notifs.push((id, op)),
                 }
                 // Read the rest of the messages in the channel, taking the best of each type.
                 for notif in rx.try_iter() {
@@ -416,6 +573,7 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> BackgroundMigrator<E, Ho
                         Notification::PruneBlobs(dab) => {
                             prune_blobs_notif = std::cmp::max(prune_blobs_notif, Some(dab));
                         }
+                        Notification::Maintenance(id, op) => maintenance_notifs.push((id, op)),
                     }
                 }
                 // If reconstruction is on-going, ignore finalization migration and blob pruning.
@@ -429,6 +587,10 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> BackgroundMigrator<E, Ho
                         Self::run_prune_blobs(db.clone(), dab, &log);
                     }
                 }
+                // Maintenance jobs are each run once, in the order they were requested.
+                for (id, op) in maintenance_notifs {
+                    Self::run_maintenance(db.clone(), &database_jobs, id, op, &log);
+                }
             }
         });
         (tx, thread)
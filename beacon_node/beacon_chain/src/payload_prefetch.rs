@@ -0,0 +1,203 @@
+//! Sends execution payloads to the execution engine ahead of block import during range sync.
+//!
+//! Blocks in a chain segment must be imported one after the other, since each block is verified
+//! against the post-state of its parent. Left alone, this means the EL only ever receives one
+//! `newPayload` at a time and sits idle whilst the beacon node processes each block.
+//!
+//! Before a run of blocks is imported, their payloads are sent to the EL in order with up to
+//! `ChainConfig::payload_prefetch_window` requests in flight at once. When import reaches a block,
+//! its payload verification waits for the prefetched response and uses it if it is `VALID`. Any
+//! other response is discarded and the payload is sent again in the usual way, after its parent
+//! has been imported, so that optimistic import and invalidation are unaffected.
+//!
+//! Responses are keyed by both the block root and the payload's block hash, so that a response is
+//! only ever used for the block whose payload was sent, and never for another block which carries
+//! the same payload.
+//!
+//! Prefetching is disabled by default.
+use crate::{metrics, BeaconChain, BeaconChainTypes};
+use execution_layer::{NewPayloadRequest, PayloadStatus};
+use futures::stream::{self, StreamExt};
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::oneshot;
+use types::{ExecutionBlockHash, Hash256, SignedBeaconBlock};
+
+/// The default maximum number of payloads sent to the EL ahead of block import at once.
+pub const DEFAULT_PAYLOAD_PREFETCH_WINDOW: usize = 0;
+
+/// The block root and payload block hash of a prefetched payload.
+type PrefetchKey = (Hash256, ExecutionBlockHash);
+
+/// Responses to `newPayload` requests sent ahead of block import.
+#[derive(Default)]
+pub struct PayloadPrefetchCache {
+    pending: Mutex<HashMap<PrefetchKey, oneshot::Receiver<PayloadStatus>>>,
+}
+
+impl PayloadPrefetchCache {
+    /// Register a prefetch of the payload of `block_root`, returning the sender of its response.
+    fn insert(
+        &self,
+        block_root: Hash256,
+        block_hash: ExecutionBlockHash,
+    ) -> oneshot::Sender<PayloadStatus> {
+        let (sender, receiver) = oneshot::channel();
+        self.pending
+            .lock()
+            .insert((block_root, block_hash), receiver);
+        sender
+    }
+
+    /// Wait for the prefetched response for the payload `block_hash` of the block `block_root`,
+    /// if it was prefetched.
+    ///
+    /// Returns `None` if the payload was not prefetched for this block or the request failed.
+    pub async fn take_status(
+        &self,
+        block_root: Hash256,
+        block_hash: ExecutionBlockHash,
+    ) -> Option<PayloadStatus> {
+        let receiver = self.pending.lock().remove(&(block_root, block_hash))?;
+        let status = receiver.await.ok();
+        let outcome = match status {
+            Some(PayloadStatus::Valid) => "valid",
+            Some(_) => "not_valid",
+            None => "failed",
+        };
+        metrics::inc_counter_vec(&metrics::BLOCK_PROCESSING_PREFETCHED_PAYLOADS, &[outcome]);
+        status
+    }
+}
+
+/// Forgets the prefetched responses for a run of blocks when dropped.
+///
+/// Responses are normally consumed during import, but will be left behind if import of the chain
+/// segment fails part-way through.
+pub struct PayloadPrefetchGuard<'a> {
+    cache: &'a PayloadPrefetchCache,
+    keys: Vec<PrefetchKey>,
+}
+
+impl<'a> Drop for PayloadPrefetchGuard<'a> {
+    fn drop(&mut self) {
+        let mut pending = self.cache.pending.lock();
+        for key in &self.keys {
+            pending.remove(key);
+        }
+    }
+}
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Start sending the payloads of `blocks`, given with their roots, to the EL, in order,
+    /// without waiting for the responses.
+    pub(crate) fn prefetch_new_payloads(
+        &self,
+        blocks: Vec<(Hash256, Arc<SignedBeaconBlock<T::EthSpec>>)>,
+    ) -> PayloadPrefetchGuard<'_> {
+        let mut guard = PayloadPrefetchGuard {
+            cache: &self.payload_prefetch_cache,
+            keys: vec![],
+        };
+        let window = self.config.payload_prefetch_window;
+        let Some(execution_layer) = self.execution_layer.clone() else {
+            return guard;
+        };
+        if window == 0 {
+            return guard;
+        }
+
+        let mut requests = vec![];
+        for (block_root, block) in blocks {
+            let Ok(payload) = block.message().execution_payload() else {
+                continue;
+            };
+            let block_hash = payload.block_hash();
+            // Skip pre-merge blocks.
+            if block_hash == ExecutionBlockHash::zero() {
+                continue;
+            }
+            let sender = self.payload_prefetch_cache.insert(block_root, block_hash);
+            guard.keys.push((block_root, block_hash));
+            requests.push((block, sender));
+        }
+
+        if !requests.is_empty() {
+            self.task_executor.spawn(
+                stream::iter(requests).for_each_concurrent(window, move |(block, sender)| {
+                    let execution_layer = execution_layer.clone();
+                    async move {
+                        let Ok(request) = NewPayloadRequest::try_from(block.message()) else {
+                            return;
+                        };
+                        if let Ok(status) = execution_layer.notify_new_payload(request).await {
+                            // The receiver is dropped if the block has already been imported
+                            // or its chain segment failed.
+                            let _ = sender.send(status);
+                        }
+                    }
+                }),
+                "prefetch_new_payloads",
+            );
+        }
+
+        guard
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block_hash(byte: u8) -> ExecutionBlockHash {
+        ExecutionBlockHash::repeat_byte(byte)
+    }
+
+    #[tokio::test]
+    async fn hit() {
+        let cache = PayloadPrefetchCache::default();
+        let block_root = Hash256::repeat_byte(1);
+        let sender = cache.insert(block_root, block_hash(1));
+        sender.send(PayloadStatus::Valid).unwrap();
+
+        assert_eq!(
+            cache.take_status(block_root, block_hash(1)).await,
+            Some(PayloadStatus::Valid)
+        );
+        // Each response is only used once.
+        assert_eq!(cache.take_status(block_root, block_hash(1)).await, None);
+    }
+
+    #[tokio::test]
+    async fn miss() {
+        let cache = PayloadPrefetchCache::default();
+        let block_root = Hash256::repeat_byte(1);
+        assert_eq!(cache.take_status(block_root, block_hash(1)).await, None);
+
+        // A request which failed without a response is also a miss.
+        drop(cache.insert(block_root, block_hash(1)));
+        assert_eq!(cache.take_status(block_root, block_hash(1)).await, None);
+    }
+
+    #[tokio::test]
+    async fn mismatched_block() {
+        let cache = PayloadPrefetchCache::default();
+        let block_root = Hash256::repeat_byte(1);
+        let other_block_root = Hash256::repeat_byte(2);
+        let sender = cache.insert(block_root, block_hash(1));
+        sender.send(PayloadStatus::Valid).unwrap();
+
+        // A different block carrying the same payload must not reuse the response.
+        assert_eq!(
+            cache.take_status(other_block_root, block_hash(1)).await,
+            None
+        );
+        // Nor may the block reuse the response for a different payload.
+        assert_eq!(cache.take_status(block_root, block_hash(2)).await, None);
+        assert_eq!(
+            cache.take_status(block_root, block_hash(1)).await,
+            Some(PayloadStatus::Valid)
+        );
+    }
+}
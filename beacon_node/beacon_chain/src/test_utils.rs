@@ -682,24 +682,19 @@ where
         let port = addr.port();
         mock_el
             .el
-            .set_builder_url(
-                SensitiveUrl::parse(format!("http://127.0.0.1:{port}").as_str()).unwrap(),
+            .set_builder_urls(
+                vec![SensitiveUrl::parse(format!("http://127.0.0.1:{port}").as_str()).unwrap()],
                 None,
                 None,
+                false,
             )
             .unwrap();
 
         self.mock_builder = Some(Arc::new(mock_builder));
 
         // Sanity check.
-        let el_builder = self
-            .chain
-            .execution_layer
-            .as_ref()
-            .unwrap()
-            .builder()
-            .unwrap();
-        let mock_el_builder = mock_el.el.builder().unwrap();
+        let el_builder = self.chain.execution_layer.as_ref().unwrap().builders();
+        let mock_el_builder = mock_el.el.builders();
         assert!(Arc::ptr_eq(&el_builder, &mock_el_builder));
 
         mock_builder_server
@@ -1094,8 +1089,9 @@ where
                 bc.committee
                     .par_iter()
                     .enumerate()
-                    .filter_map(|(i, validator_index)| {
-                        if !attesting_validators.contains(validator_index) {
+                    .filter_map(|(i, &validator_index)| {
+                        let validator_index = validator_index as usize;
+                        if !attesting_validators.contains(&validator_index) {
                             return None;
                         }
 
@@ -1140,7 +1136,7 @@ where
                             let mut agg_sig = AggregateSignature::infinity();
 
                             agg_sig.add_assign(
-                                &self.validator_keypairs[*validator_index].sk.sign(message),
+                                &self.validator_keypairs[validator_index].sk.sign(message),
                             );
 
                             agg_sig
@@ -1332,7 +1328,8 @@ where
                         let aggregator_index = bc
                             .committee
                             .iter()
-                            .find(|&validator_index| {
+                            .map(|&validator_index| validator_index as usize)
+                            .find(|validator_index| {
                                 if !attesters.contains(validator_index) {
                                     return false;
                                 }
@@ -1348,8 +1345,7 @@ where
                                 selection_proof
                                     .is_aggregator(bc.committee.len(), &self.spec)
                                     .unwrap_or(false)
-                            })
-                            .copied()?;
+                            })?;
 
                         let fork_name = self.spec.fork_name_at_slot::<E>(slot);
 
@@ -277,6 +277,101 @@ impl ValidatorMetrics {
     }
 }
 
+/// A duty which a monitored validator failed to perform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MissedDuty {
+    Attestation,
+    Proposal,
+}
+
+impl MissedDuty {
+    fn as_str(&self) -> &'static str {
+        match self {
+            MissedDuty::Attestation => "attestation",
+            MissedDuty::Proposal => "proposal",
+        }
+    }
+}
+
+/// The most likely reason a monitored validator failed to perform a duty, as far as this node can
+/// tell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MissedDutyCause {
+    /// No validator client subscribed to the validator's attestation duties for the epoch on this
+    /// node, so the validator was most likely not running.
+    NoDutyLoaded,
+    /// A validator client loaded the duty from this node, but nothing was published for it. The
+    /// validator client was probably unable to reach the node when the duty was due.
+    BeaconNodeUnreachable,
+    /// The attestation arrived too late to be aggregated, as happens when signing is slow.
+    SignerTimeout,
+    /// The block the validator should have attested to arrived too late to be attested to, or the
+    /// validator's own block arrived too late to become canonical.
+    LateBlock,
+    /// None of the above, e.g. a timely attestation that was not included in a block.
+    Unknown,
+}
+
+impl MissedDutyCause {
+    /// Determine the cause of a missed attestation.
+    ///
+    /// - `duty_slot`: the slot of the duty, if a validator client subscribed to it on this node.
+    /// - `min_delay`: the smallest observed delay of the validator's attestations in the epoch.
+    /// - `max_delay`: the delay after which an attestation is too late to be aggregated.
+    /// - `late_block`: whether the block at `duty_slot` arrived too late to be attested to.
+    pub fn for_attestation(
+        duty_slot: Option<Slot>,
+        min_delay: Option<Duration>,
+        max_delay: Duration,
+        late_block: bool,
+    ) -> Self {
+        match (duty_slot, min_delay) {
+            (None, None) => MissedDutyCause::NoDutyLoaded,
+            (Some(_), None) => MissedDutyCause::BeaconNodeUnreachable,
+            (_, Some(delay)) if delay > max_delay => MissedDutyCause::SignerTimeout,
+            (Some(_), Some(_)) if late_block => MissedDutyCause::LateBlock,
+            (_, Some(_)) => MissedDutyCause::Unknown,
+        }
+    }
+
+    /// Determine the cause of a missed proposal.
+    ///
+    /// - `block_seen`: whether a block from the validator was observed for the epoch.
+    /// - `duty_loaded`: whether a validator client subscribed to the validator's attestation
+    ///     duties for the epoch on this node.
+    pub fn for_proposal(block_seen: bool, duty_loaded: bool) -> Self {
+        if block_seen {
+            MissedDutyCause::LateBlock
+        } else if duty_loaded {
+            MissedDutyCause::BeaconNodeUnreachable
+        } else {
+            MissedDutyCause::NoDutyLoaded
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            MissedDutyCause::NoDutyLoaded => "no_duty_loaded",
+            MissedDutyCause::BeaconNodeUnreachable => "beacon_node_unreachable",
+            MissedDutyCause::SignerTimeout => "signer_timeout",
+            MissedDutyCause::LateBlock => "late_block",
+            MissedDutyCause::Unknown => "unknown",
+        }
+    }
+}
+
+/// A duty missed by a monitored validator and its most likely cause.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidatorIssue {
+    /// The slot of the duty. For attestations with an unknown duty slot this is the first slot of
+    /// the epoch.
+    pub slot: Slot,
+    pub duty: MissedDuty,
+    pub cause: MissedDutyCause,
+}
+
 /// A validator that is being monitored by the `ValidatorMonitor`.
 pub struct MonitoredValidator {
     /// A human-readable identifier for the validator.
@@ -287,6 +382,10 @@ pub struct MonitoredValidator {
     pub summaries: RwLock<SummaryMap>,
     /// Validator metrics to be exposed over the HTTP API.
     pub metrics: RwLock<ValidatorMetrics>,
+    /// The slots of the attestation duties loaded by validator clients, by epoch.
+    attestation_duties: RwLock<HashMap<Epoch, Slot>>,
+    /// Recently missed duties, to be exposed over the HTTP API.
+    issues: RwLock<Vec<ValidatorIssue>>,
 }
 
 impl MonitoredValidator {
@@ -298,6 +397,8 @@ impl MonitoredValidator {
             index,
             summaries: <_>::default(),
             metrics: <_>::default(),
+            attestation_duties: <_>::default(),
+            issues: <_>::default(),
         }
     }
 
@@ -366,6 +467,32 @@ impl MonitoredValidator {
             summary_opt.and_then(|summary| summary.total_balance)
         })
     }
+
+    /// Record that a validator client loaded the attestation duty at `slot` in `epoch`.
+    fn register_attestation_duty(&self, epoch: Epoch, slot: Slot) {
+        let mut duties = self.attestation_duties.write();
+        duties.insert(epoch, slot);
+        duties.retain(|duty_epoch, _| *duty_epoch + HISTORIC_EPOCHS as u64 > epoch);
+    }
+
+    fn attestation_duty(&self, epoch: Epoch) -> Option<Slot> {
+        self.attestation_duties.read().get(&epoch).copied()
+    }
+
+    /// Record a missed duty, pruning issues more than `HISTORIC_EPOCHS` older than it.
+    fn register_issue(&self, issue: ValidatorIssue, slots_per_epoch: u64) {
+        let min_slot = issue
+            .slot
+            .saturating_sub(HISTORIC_EPOCHS as u64 * slots_per_epoch);
+        let mut issues = self.issues.write();
+        issues.retain(|existing| existing.slot >= min_slot);
+        issues.push(issue);
+    }
+
+    /// Returns the recently missed duties of the validator.
+    pub fn get_issues(&self) -> Vec<ValidatorIssue> {
+        self.issues.read().clone()
+    }
 }
 
 #[derive(PartialEq, Hash, Eq)]
@@ -401,6 +528,8 @@ pub struct ValidatorMonitor<E: EthSpec> {
     beacon_proposer_cache: Arc<Mutex<BeaconProposerCache>>,
     // Unaggregated attestations generated by the committee index at each slot.
     unaggregated_attestations: HashMap<Slot, Attestation<E>>,
+    /// Recent slots whose block arrived after attestations for the slot were due.
+    late_block_slots: Mutex<HashSet<Slot>>,
     log: Logger,
     _phantom: PhantomData<E>,
 }
@@ -425,6 +554,7 @@ impl<E: EthSpec> ValidatorMonitor<E> {
             missed_blocks: <_>::default(),
             beacon_proposer_cache,
             unaggregated_attestations: <_>::default(),
+            late_block_slots: <_>::default(),
             log,
             _phantom: PhantomData,
         };
@@ -660,13 +790,29 @@ impl<E: EthSpec> ValidatorMonitor<E> {
                                                 &[label],
                                             );
                                         });
+                                        let block_seen = validator
+                                            .get_from_epoch_summary(slot_epoch, |summary| {
+                                                summary.map(|summary| summary.blocks > 0)
+                                            })
+                                            .unwrap_or(false);
+                                        let duty_loaded =
+                                            validator.attestation_duty(slot_epoch).is_some();
+                                        let cause =
+                                            MissedDutyCause::for_proposal(block_seen, duty_loaded);
                                         error!(
                                             self.log,
                                             "Validator missed a block";
                                             "index" => i,
                                             "slot" => slot,
                                             "parent block root" => ?prev_block_root,
+                                            "cause" => cause.as_str(),
                                         );
+                                        let issue = ValidatorIssue {
+                                            slot,
+                                            duty: MissedDuty::Proposal,
+                                            cause,
+                                        };
+                                        self.register_issue(validator, issue);
                                     }
                                 }
                             } else {
@@ -892,6 +1038,26 @@ impl<E: EthSpec> ValidatorMonitor<E> {
                             "validator" => id,
                         )
                     }
+
+                    // Attestations seen more than a third of a slot after they were due are
+                    // too late to be aggregated.
+                    let duty_slot = monitored_validator.attestation_duty(prev_epoch);
+                    let min_delay = monitored_validator
+                        .get_from_epoch_summary(prev_epoch, |summary| {
+                            summary.and_then(|summary| summary.attestation_min_delay)
+                        });
+                    let max_delay = Duration::from_secs(spec.seconds_per_slot) / 3;
+                    let late_block = duty_slot
+                        .map_or(false, |slot| self.late_block_slots.lock().contains(&slot));
+                    let issue = ValidatorIssue {
+                        slot: duty_slot
+                            .unwrap_or_else(|| prev_epoch.start_slot(E::slots_per_epoch())),
+                        duty: MissedDuty::Attestation,
+                        cause: MissedDutyCause::for_attestation(
+                            duty_slot, min_delay, max_delay, late_block,
+                        ),
+                    };
+                    self.register_issue(monitored_validator, issue);
                 }
 
                 // Indicates if any on-chain attestation hit the head.
@@ -1136,6 +1302,35 @@ impl<E: EthSpec> ValidatorMonitor<E> {
         }
     }
 
+    /// Record that a validator client loaded the attestation duty of `validator_index` at `slot`.
+    pub fn register_attestation_duty(&self, validator_index: u64, slot: Slot) {
+        if let Some(validator) = self.get_validator(validator_index) {
+            validator.register_attestation_duty(slot.epoch(E::slots_per_epoch()), slot);
+        }
+    }
+
+    /// Record a missed duty of `validator`, with metrics and logging.
+    fn register_issue(&self, validator: &MonitoredValidator, issue: ValidatorIssue) {
+        let id = &validator.id;
+        self.aggregatable_metric(id, |label| {
+            metrics::inc_counter_vec(
+                &metrics::VALIDATOR_MONITOR_MISSED_DUTY_CAUSES_TOTAL,
+                &[issue.duty.as_str(), issue.cause.as_str(), label],
+            );
+        });
+        if self.individual_tracking() {
+            debug!(
+                self.log,
+                "Determined cause of missed duty";
+                "cause" => issue.cause.as_str(),
+                "duty" => issue.duty.as_str(),
+                "slot" => issue.slot,
+                "validator" => id,
+            );
+        }
+        validator.register_issue(issue, E::slots_per_epoch());
+    }
+
     /// Process a block received on gossip.
     pub fn register_gossip_block<S: SlotClock>(
         &self,
@@ -1167,9 +1362,20 @@ impl<E: EthSpec> ValidatorMonitor<E> {
         slot_clock: &S,
     ) {
         let epoch = block.slot().epoch(E::slots_per_epoch());
+        let delay = get_block_delay_ms(seen_timestamp, block, slot_clock);
+
+        // Remember late blocks in order to explain missed attestations.
+        if delay > slot_clock.unagg_attestation_production_delay() {
+            let min_slot = block
+                .slot()
+                .saturating_sub(HISTORIC_EPOCHS as u64 * E::slots_per_epoch());
+            let mut late_block_slots = self.late_block_slots.lock();
+            late_block_slots.retain(|slot| *slot >= min_slot);
+            late_block_slots.insert(block.slot());
+        }
+
         if let Some(validator) = self.get_validator(block.proposer_index()) {
             let id = &validator.id;
-            let delay = get_block_delay_ms(seen_timestamp, block, slot_clock);
 
             self.aggregatable_metric(id, |label| {
                 metrics::inc_counter_vec(
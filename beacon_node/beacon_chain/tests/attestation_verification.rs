@@ -127,7 +127,7 @@ fn get_valid_unaggregated_attestation<T: BeaconChainTypes>(
         .expect("should not error while producing attestation");
 
     let validator_committee_index = 0;
-    let validator_index = *head
+    let validator_index = head
         .beacon_state
         .get_beacon_committee(
             current_slot,
@@ -138,6 +138,7 @@ fn get_valid_unaggregated_attestation<T: BeaconChainTypes>(
         .expect("should get committees")
         .committee
         .get(validator_committee_index)
+        .map(|&index| index as usize)
         .expect("there should be an attesting validator");
 
     let validator_sk = generate_deterministic_keypair(validator_index).sk;
@@ -192,6 +193,7 @@ fn get_valid_aggregated_attestation<T: BeaconChainTypes>(
         .committee
         .iter()
         .find_map(|&val_index| {
+            let val_index = val_index as usize;
             let aggregator_sk = generate_deterministic_keypair(val_index).sk;
 
             let proof = SelectionProof::new::<T::EthSpec>(
@@ -247,6 +249,7 @@ fn get_non_aggregator<T: BeaconChainTypes>(
         .committee
         .iter()
         .find_map(|&val_index| {
+            let val_index = val_index as usize;
             let aggregator_sk = generate_deterministic_keypair(val_index).sk;
 
             let proof = SelectionProof::new::<T::EthSpec>(
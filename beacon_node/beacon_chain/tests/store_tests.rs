@@ -3419,6 +3419,53 @@ fn check_blob_existence(
     }
 }
 
+#[tokio::test]
+async fn historic_committee_cache_from_freezer() {
+    let num_blocks_produced = E::slots_per_epoch() * 10;
+    let db_path = tempdir().unwrap();
+    let store = get_store_generic(
+        &db_path,
+        StoreConfig {
+            slots_per_restore_point: 2 * E::slots_per_epoch(),
+            ..Default::default()
+        },
+        test_spec::<E>(),
+    );
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+    let spec = &harness.chain.spec;
+
+    harness
+        .extend_chain(
+            num_blocks_produced as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    // The seeds of the first epochs use the genesis RANDAO mix, which isn't read from the freezer.
+    assert!(harness
+        .chain
+        .historic_committee_cache(Epoch::new(1))
+        .unwrap()
+        .is_none());
+
+    let split_epoch = store.get_split_slot().epoch(E::slots_per_epoch());
+    let mut epoch = Epoch::new(2);
+    while let Some(committee_cache) = harness.chain.historic_committee_cache(epoch).unwrap() {
+        let state = store
+            .load_cold_state_by_slot(epoch.start_slot(E::slots_per_epoch()))
+            .unwrap()
+            .unwrap();
+        let expected = CommitteeCache::initialized(&state, epoch, spec).unwrap();
+        assert_eq!(committee_cache, expected, "epoch {epoch}");
+        epoch += 1;
+    }
+
+    // Committees are available up to the last restore point before the split.
+    let last_restore_point_epoch = (split_epoch - 1) / 2 * 2;
+    assert_eq!(epoch, last_restore_point_epoch + 1);
+}
+
 #[tokio::test]
 async fn prune_historic_states() {
     let num_blocks_produced = E::slots_per_epoch() * 5;
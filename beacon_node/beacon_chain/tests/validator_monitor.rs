@@ -1,9 +1,12 @@
 use beacon_chain::test_utils::{
     AttestationStrategy, BeaconChainHarness, BlockStrategy, EphemeralHarnessType,
 };
-use beacon_chain::validator_monitor::{ValidatorMonitorConfig, MISSED_BLOCK_LAG_SLOTS};
+use beacon_chain::validator_monitor::{
+    MissedDuty, MissedDutyCause, ValidatorIssue, ValidatorMonitorConfig, MISSED_BLOCK_LAG_SLOTS,
+};
 use lazy_static::lazy_static;
 use logging::test_logger;
+use std::time::Duration;
 use types::{Epoch, EthSpec, Keypair, MainnetEthSpec, PublicKeyBytes, Slot};
 
 // Should ideally be divisible by 3.
@@ -195,6 +198,19 @@ async fn produces_missed_blocks() {
             validator_monitor.get_monitored_validator_missed_block_count(validator_index as u64),
             1
         );
+
+        // No block was seen and no duties were loaded for the validator, so it wasn't running.
+        assert_eq!(
+            validator_monitor
+                .get_monitored_validator(validator_index as u64)
+                .unwrap()
+                .get_issues(),
+            vec![ValidatorIssue {
+                slot,
+                duty: MissedDuty::Proposal,
+                cause: MissedDutyCause::NoDutyLoaded,
+            }]
+        );
     }
 
     // 2nd scenario //
@@ -387,3 +403,40 @@ async fn produces_missed_blocks() {
         );
     }
 }
+
+#[test]
+fn missed_duty_causes() {
+    let slot = Some(Slot::new(1));
+    let max_delay = Duration::from_secs(4);
+    let timely = Some(Duration::from_secs(1));
+    let late = Some(Duration::from_secs(5));
+
+    let cases = [
+        (None, None, false, MissedDutyCause::NoDutyLoaded),
+        (slot, None, true, MissedDutyCause::BeaconNodeUnreachable),
+        (slot, late, true, MissedDutyCause::SignerTimeout),
+        (None, late, false, MissedDutyCause::SignerTimeout),
+        (slot, timely, true, MissedDutyCause::LateBlock),
+        (slot, timely, false, MissedDutyCause::Unknown),
+        (None, timely, false, MissedDutyCause::Unknown),
+    ];
+    for (duty_slot, min_delay, late_block, cause) in cases {
+        assert_eq!(
+            MissedDutyCause::for_attestation(duty_slot, min_delay, max_delay, late_block),
+            cause
+        );
+    }
+
+    assert_eq!(
+        MissedDutyCause::for_proposal(true, true),
+        MissedDutyCause::LateBlock
+    );
+    assert_eq!(
+        MissedDutyCause::for_proposal(false, true),
+        MissedDutyCause::BeaconNodeUnreachable
+    );
+    assert_eq!(
+        MissedDutyCause::for_proposal(false, false),
+        MissedDutyCause::NoDutyLoaded
+    );
+}
@@ -1,16 +1,19 @@
 use eth2::types::builder_bid::SignedBuilderBid;
 use eth2::types::{
-    EthSpec, ExecutionBlockHash, ForkVersionedResponse, PublicKeyBytes,
-    SignedValidatorRegistrationData, Slot,
+    EthSpec, ExecutionBlockHash, ForkName, ForkVersionDecode, ForkVersionedResponse,
+    PublicKeyBytes, SignedValidatorRegistrationData, Slot,
 };
 use eth2::types::{FullPayloadContents, SignedBlindedBeaconBlock};
 pub use eth2::Error;
-use eth2::{ok_or_error, StatusCode, CONSENSUS_VERSION_HEADER};
-use reqwest::header::{HeaderMap, HeaderValue};
+use eth2::{ok_or_error, StatusCode, CONSENSUS_VERSION_HEADER, SSZ_CONTENT_TYPE_HEADER};
+use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, CONTENT_TYPE};
 use reqwest::{IntoUrl, Response};
 use sensitive_url::SensitiveUrl;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+use ssz::Encode;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
 
 pub const DEFAULT_TIMEOUT_MILLIS: u64 = 15000;
@@ -21,6 +24,9 @@ pub const DEFAULT_GET_HEADER_TIMEOUT_MILLIS: u64 = 1000;
 /// Default user agent for HTTP requests.
 pub const DEFAULT_USER_AGENT: &str = lighthouse_version::VERSION;
 
+/// `Accept` header value which asks the relay for SSZ, while still allowing a JSON response.
+const PREFER_SSZ_ACCEPT_VALUE: &str = "application/octet-stream;q=1.0,application/json;q=0.9";
+
 #[derive(Clone)]
 pub struct Timeouts {
     get_header: Duration,
@@ -49,6 +55,11 @@ pub struct BuilderHttpClient {
     server: SensitiveUrl,
     timeouts: Timeouts,
     user_agent: String,
+    /// Whether to request and send SSZ-encoded bodies.
+    ///
+    /// This starts out `true` unless SSZ is disabled via config, and is switched off the first
+    /// time the relay rejects an SSZ request body, after which only JSON is used.
+    ssz_enabled: Arc<AtomicBool>,
 }
 
 impl BuilderHttpClient {
@@ -56,6 +67,7 @@ impl BuilderHttpClient {
         server: SensitiveUrl,
         user_agent: Option<String>,
         builder_header_timeout: Option<Duration>,
+        disable_ssz: bool,
     ) -> Result<Self, Error> {
         let user_agent = user_agent.unwrap_or(DEFAULT_USER_AGENT.to_string());
         let client = reqwest::Client::builder().user_agent(&user_agent).build()?;
@@ -64,6 +76,7 @@ impl BuilderHttpClient {
             server,
             timeouts: Timeouts::new(builder_header_timeout),
             user_agent,
+            ssz_enabled: Arc::new(AtomicBool::new(!disable_ssz)),
         })
     }
 
@@ -71,12 +84,21 @@ impl BuilderHttpClient {
         &self.user_agent
     }
 
+    pub fn server(&self) -> &SensitiveUrl {
+        &self.server
+    }
+
+    /// Returns `true` if SSZ will be used for the next request to the relay.
+    pub fn is_ssz_enabled(&self) -> bool {
+        self.ssz_enabled.load(Ordering::Relaxed)
+    }
+
     async fn get_with_timeout<T: DeserializeOwned, U: IntoUrl>(
         &self,
         url: U,
         timeout: Duration,
     ) -> Result<T, Error> {
-        self.get_response_with_timeout(url, Some(timeout))
+        self.get_response_with_timeout(url, HeaderMap::new(), Some(timeout))
             .await?
             .json()
             .await
@@ -87,13 +109,14 @@ impl BuilderHttpClient {
     async fn get_response_with_timeout<U: IntoUrl>(
         &self,
         url: U,
+        headers: HeaderMap,
         timeout: Option<Duration>,
     ) -> Result<Response, Error> {
         let mut builder = self.client.get(url);
         if let Some(timeout) = timeout {
             builder = builder.timeout(timeout);
         }
-        let response = builder.send().await.map_err(Error::from)?;
+        let response = builder.headers(headers).send().await.map_err(Error::from)?;
         ok_or_error(response).await
     }
 
@@ -133,6 +156,35 @@ impl BuilderHttpClient {
         ok_or_error(response).await
     }
 
+    /// Generic POST function for SSZ-encoded request bodies.
+    ///
+    /// The `Content-Type` header is set by this function, any other headers must be supplied.
+    async fn post_ssz_with_raw_response<U: IntoUrl>(
+        &self,
+        url: U,
+        body: Vec<u8>,
+        mut headers: HeaderMap,
+        timeout: Option<Duration>,
+    ) -> Result<Response, Error> {
+        let mut builder = self.client.post(url);
+        if let Some(timeout) = timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        headers.insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static(SSZ_CONTENT_TYPE_HEADER),
+        );
+
+        let response = builder
+            .headers(headers)
+            .body(body)
+            .send()
+            .await
+            .map_err(Error::from)?;
+        ok_or_error(response).await
+    }
+
     /// `POST /eth/v1/builder/validators`
     pub async fn post_builder_validators(
         &self,
@@ -166,11 +218,36 @@ impl BuilderHttpClient {
             .push("builder")
             .push("blinded_blocks");
 
+        let fork_name = blinded_block.fork_name_unchecked();
         let mut headers = HeaderMap::new();
-        if let Ok(value) = HeaderValue::from_str(&blinded_block.fork_name_unchecked().to_string()) {
+        if let Ok(value) = HeaderValue::from_str(&fork_name.to_string()) {
             headers.insert(CONSENSUS_VERSION_HEADER, value);
         }
 
+        if self.is_ssz_enabled() {
+            let mut ssz_headers = headers.clone();
+            ssz_headers.insert(ACCEPT, HeaderValue::from_static(PREFER_SSZ_ACCEPT_VALUE));
+
+            match self
+                .post_ssz_with_raw_response(
+                    path.clone(),
+                    blinded_block.as_ssz_bytes(),
+                    ssz_headers,
+                    Some(self.timeouts.post_blinded_blocks),
+                )
+                .await
+            {
+                Ok(response) => {
+                    return decode_fork_versioned_response(response, Some(fork_name)).await;
+                }
+                // The relay doesn't understand SSZ, use JSON for this and all future requests.
+                Err(e) if is_unsupported_encoding(&e) => {
+                    self.ssz_enabled.store(false, Ordering::Relaxed);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
         Ok(self
             .post_with_raw_response(
                 path,
@@ -202,13 +279,23 @@ impl BuilderHttpClient {
             .push(format!("{parent_hash:?}").as_str())
             .push(pubkey.as_hex_string().as_str());
 
-        let resp = self.get_with_timeout(path, self.timeouts.get_header).await;
-
-        if matches!(resp, Err(Error::StatusCode(StatusCode::NO_CONTENT))) {
-            Ok(None)
-        } else {
-            resp.map(Some)
+        let mut headers = HeaderMap::new();
+        if self.is_ssz_enabled() {
+            headers.insert(ACCEPT, HeaderValue::from_static(PREFER_SSZ_ACCEPT_VALUE));
         }
+
+        let response = match self
+            .get_response_with_timeout(path, headers, Some(self.timeouts.get_header))
+            .await
+        {
+            Ok(response) => response,
+            Err(Error::StatusCode(StatusCode::NO_CONTENT)) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+
+        decode_fork_versioned_response(response, None)
+            .await
+            .map(Some)
     }
 
     /// `GET /eth/v1/builder/status`
@@ -226,3 +313,55 @@ impl BuilderHttpClient {
             .await
     }
 }
+
+/// Returns `true` if the error indicates that the relay doesn't support the SSZ encoding.
+fn is_unsupported_encoding(error: &Error) -> bool {
+    matches!(
+        error.status(),
+        Some(StatusCode::UNSUPPORTED_MEDIA_TYPE | StatusCode::NOT_ACCEPTABLE)
+    )
+}
+
+/// Decode a `ForkVersionedResponse` from either an SSZ or a JSON response body, depending on the
+/// `Content-Type` returned by the relay.
+///
+/// SSZ responses carry the fork in the `Eth-Consensus-Version` header, if it is missing then
+/// `default_fork` is used instead.
+async fn decode_fork_versioned_response<T: ForkVersionDecode>(
+    response: Response,
+    default_fork: Option<ForkName>,
+) -> Result<ForkVersionedResponse<T>, Error>
+where
+    ForkVersionedResponse<T>: DeserializeOwned,
+{
+    let is_ssz = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|content_type| content_type.starts_with(SSZ_CONTENT_TYPE_HEADER));
+
+    if !is_ssz {
+        return response.json().await.map_err(Into::into);
+    }
+
+    let fork_name = match response.headers().get(CONSENSUS_VERSION_HEADER) {
+        Some(value) => value
+            .to_str()
+            .map_err(|e| format!("{e:?}"))
+            .and_then(|s| s.parse::<ForkName>())
+            .map_err(|e| {
+                Error::InvalidHeaders(format!("invalid {CONSENSUS_VERSION_HEADER}: {e}"))
+            })?,
+        None => default_fork
+            .ok_or_else(|| Error::InvalidHeaders(format!("missing {CONSENSUS_VERSION_HEADER}")))?,
+    };
+
+    let bytes = response.bytes().await?;
+    let data = T::from_ssz_bytes_by_fork(&bytes, fork_name).map_err(Error::InvalidSsz)?;
+
+    Ok(ForkVersionedResponse {
+        version: Some(fork_name),
+        metadata: Default::default(),
+        data,
+    })
+}
@@ -46,8 +46,8 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use timer::spawn_timer;
 use tokio::sync::oneshot;
 use types::{
-    test_utils::generate_deterministic_keypairs, BeaconState, BlobSidecarList, ChainSpec, EthSpec,
-    ExecutionBlockHash, Hash256, SignedBeaconBlock,
+    test_utils::generate_deterministic_keypairs, BeaconState, BlobSidecarList, ChainSpec,
+    DepositTreeSnapshot, EthSpec, ExecutionBlockHash, Hash256, SignedBeaconBlock,
 };
 
 /// Interval between polling the eth1 node for genesis information.
@@ -337,6 +337,7 @@ where
                 anchor_state_bytes,
                 anchor_block_bytes,
                 anchor_blobs_bytes,
+                deposit_snapshot_bytes,
             } => {
                 info!(context.log(), "Starting checkpoint sync");
                 if config.chain.genesis_backfill {
@@ -360,8 +361,24 @@ where
                 } else {
                     None
                 };
+                let deposit_snapshot = deposit_snapshot_bytes
+                    .map(|bytes| {
+                        DepositTreeSnapshot::from_ssz_bytes(&bytes)
+                            .map_err(|e| format!("Unable to parse deposit snapshot SSZ: {:?}", e))
+                    })
+                    .transpose()?;
                 let genesis_state = genesis_state(&runtime_context, &config, log).await?;
 
+                let service = deposit_snapshot.and_then(|snapshot| {
+                    eth1_service_from_deposit_snapshot(
+                        config.eth1,
+                        context.log(),
+                        spec,
+                        &snapshot,
+                        &anchor_state,
+                    )
+                });
+
                 builder
                     .weak_subjectivity_state(
                         anchor_state,
@@ -369,9 +386,12 @@ where
                         anchor_blobs,
                         genesis_state,
                     )
-                    .map(|v| (v, None))?
+                    .map(|v| (v, service))?
             }
-            ClientGenesis::CheckpointSyncUrl { url } => {
+            ClientGenesis::CheckpointSyncUrl {
+                url,
+                deposit_snapshot_bytes,
+            } => {
                 info!(
                     context.log(),
                     "Starting checkpoint sync";
@@ -391,7 +411,13 @@ where
                     )),
                 );
 
-                let deposit_snapshot = if config.sync_eth1_chain {
+                let deposit_snapshot = if let Some(bytes) = deposit_snapshot_bytes {
+                    Some(
+                        DepositTreeSnapshot::from_ssz_bytes(&bytes).map_err(|e| {
+                            format!("Unable to parse deposit snapshot SSZ: {:?}", e)
+                        })?,
+                    )
+                } else if config.sync_eth1_chain {
                     // We want to fetch deposit snapshot before fetching the finalized beacon state to
                     // ensure that the snapshot is not newer than the beacon state that satisfies the
                     // deposit finalization conditions
@@ -500,29 +526,15 @@ where
                     "block_root" => ?block_root,
                 );
 
-                let service =
-                    deposit_snapshot.and_then(|snapshot| match Eth1Service::from_deposit_snapshot(
+                let service = deposit_snapshot.and_then(|snapshot| {
+                    eth1_service_from_deposit_snapshot(
                         config.eth1,
-                        context.log().clone(),
+                        context.log(),
                         spec,
                         &snapshot,
-                    ) {
-                        Ok(service) => {
-                            info!(
-                                context.log(),
-                                "Loaded deposit tree snapshot";
-                                "deposits loaded" => snapshot.deposit_count,
-                            );
-                            Some(service)
-                        }
-                        Err(e) => {
-                            warn!(context.log(),
-                                "Unable to load deposit snapshot";
-                                "error" => ?e
-                            );
-                            None
-                        }
-                    });
+                        &state,
+                    )
+                });
 
                 builder
                     .weak_subjectivity_state(state, block, blobs, genesis_state)
@@ -566,6 +578,8 @@ where
                         eth1_service: Some(genesis_service.eth1_service.clone()),
                         log: context.log().clone(),
                         sse_logging_components: runtime_context.sse_logging_components.clone(),
+                        log_filter: runtime_context.log_filter.clone(),
+                        recent_logs: runtime_context.recent_logs.clone(),
                     });
 
                     // Discard the error from the oneshot.
@@ -813,6 +827,8 @@ where
                     beacon_processor_channels.work_reprocessing_tx.clone(),
                 ),
                 sse_logging_components: runtime_context.sse_logging_components.clone(),
+                log_filter: runtime_context.log_filter.clone(),
+                recent_logs: runtime_context.recent_logs.clone(),
                 log: log.clone(),
             });
 
@@ -1228,6 +1244,51 @@ where
     }
 }
 
+/// Initialize the eth1 service from an EIP-4881 deposit tree snapshot.
+///
+/// The snapshot is ignored if it is invalid or contains deposits which have not yet been
+/// processed by the checkpoint `anchor_state`, in which case the deposit logs will be downloaded
+/// from the execution node as usual.
+fn eth1_service_from_deposit_snapshot<E: EthSpec>(
+    config: Eth1Config,
+    log: &Logger,
+    spec: ChainSpec,
+    snapshot: &DepositTreeSnapshot,
+    anchor_state: &BeaconState<E>,
+) -> Option<Eth1Service> {
+    if !snapshot.is_valid() {
+        warn!(log, "Deposit snapshot is invalid");
+        return None;
+    }
+    if snapshot.deposit_count > anchor_state.eth1_deposit_index() {
+        warn!(
+            log,
+            "Deposit snapshot is newer than the checkpoint state";
+            "snapshot_deposit_count" => snapshot.deposit_count,
+            "state_deposit_index" => anchor_state.eth1_deposit_index(),
+        );
+        return None;
+    }
+
+    match Eth1Service::from_deposit_snapshot(config, log.clone(), spec, snapshot) {
+        Ok(service) => {
+            info!(
+                log,
+                "Loaded deposit tree snapshot";
+                "deposits loaded" => snapshot.deposit_count,
+            );
+            Some(service)
+        }
+        Err(e) => {
+            warn!(log,
+                "Unable to load deposit snapshot";
+                "error" => ?e
+            );
+            None
+        }
+    }
+}
+
 /// Obtain the genesis state from the `eth2_network_config` in `context`.
 async fn genesis_state<E: EthSpec>(
     context: &RuntimeContext<E>,
@@ -41,9 +41,13 @@ pub enum ClientGenesis {
         anchor_state_bytes: Vec<u8>,
         anchor_block_bytes: Vec<u8>,
         anchor_blobs_bytes: Option<Vec<u8>>,
+        /// An EIP-4881 deposit tree snapshot, used to avoid downloading historic deposit logs.
+        deposit_snapshot_bytes: Option<Vec<u8>>,
     },
     CheckpointSyncUrl {
         url: SensitiveUrl,
+        /// An EIP-4881 deposit tree snapshot, used instead of downloading one from `url`.
+        deposit_snapshot_bytes: Option<Vec<u8>>,
     },
 }
 
@@ -0,0 +1,98 @@
+//! A bounded log of the headers received from builders.
+//!
+//! Each entry records the bid alongside the decision taken about it, allowing operators to audit
+//! whether bids are being selected correctly and to compare relays.
+use eth2::lighthouse::BuilderBidAudit;
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use types::Slot;
+
+/// The default number of bids retained in the log.
+pub const DEFAULT_BID_AUDIT_LOG_SIZE: usize = 256;
+
+pub struct BidAuditLog {
+    bids: Mutex<VecDeque<BuilderBidAudit>>,
+    capacity: usize,
+}
+
+impl Default for BidAuditLog {
+    fn default() -> Self {
+        Self::new(DEFAULT_BID_AUDIT_LOG_SIZE)
+    }
+}
+
+impl BidAuditLog {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            bids: Mutex::new(VecDeque::with_capacity(capacity)),
+            capacity,
+        }
+    }
+
+    /// Add a bid to the log, evicting the oldest bid if the log is full.
+    pub fn record(&self, bid: BuilderBidAudit) {
+        let mut bids = self.bids.lock();
+        while bids.len() >= self.capacity {
+            bids.pop_front();
+        }
+        bids.push_back(bid);
+    }
+
+    /// Returns the retained bids for `slot`, or all retained bids if `slot` is `None`.
+    ///
+    /// Bids are returned in the order they were received.
+    pub fn get(&self, slot: Option<Slot>) -> Vec<BuilderBidAudit> {
+        self.bids
+            .lock()
+            .iter()
+            .filter(|bid| slot.is_none() || slot == Some(bid.slot))
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use eth2::lighthouse::BuilderBidDecision;
+    use types::{Address, ExecutionBlockHash, PublicKeyBytes, Uint256};
+
+    fn bid(slot: u64) -> BuilderBidAudit {
+        BuilderBidAudit {
+            slot: Slot::new(slot),
+            relay: "https://relay.example.com/".to_string(),
+            proposer_pubkey: PublicKeyBytes::empty(),
+            parent_hash: ExecutionBlockHash::zero(),
+            block_hash: ExecutionBlockHash::zero(),
+            fee_recipient: Address::zero(),
+            value: Uint256::from(slot),
+            boosted_value: Uint256::from(slot),
+            local_value: None,
+            relay_response_ms: 0,
+            decision: BuilderBidDecision::Accepted,
+        }
+    }
+
+    #[test]
+    fn filters_by_slot() {
+        let log = BidAuditLog::default();
+        log.record(bid(1));
+        log.record(bid(2));
+        log.record(bid(2));
+
+        assert_eq!(log.get(Some(Slot::new(1))), vec![bid(1)]);
+        assert_eq!(log.get(Some(Slot::new(2))).len(), 2);
+        assert!(log.get(Some(Slot::new(3))).is_empty());
+        assert_eq!(log.get(None).len(), 3);
+    }
+
+    #[test]
+    fn evicts_oldest() {
+        let log = BidAuditLog::new(2);
+        log.record(bid(1));
+        log.record(bid(2));
+        log.record(bid(3));
+
+        assert_eq!(log.get(None), vec![bid(2), bid(3)]);
+    }
+}
@@ -0,0 +1,187 @@
+//! Submission of validator registrations to the builder API.
+//!
+//! A validator client with many keys re-sends all of its registrations every epoch. Posting these
+//! to a relay in one request can easily time out, so registrations are split into batches which
+//! are submitted one after the other with a short pause in between. Failed batches are retried
+//! with exponential backoff.
+//!
+//! Registrations which are unchanged since the relay last accepted them are not re-submitted
+//! until `REGISTRATION_REFRESH_INTERVAL` has elapsed.
+//!
+//! The most recent registration of each validator is also kept, so that operators can inspect
+//! exactly what was forwarded.
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use types::{PublicKeyBytes, SignedValidatorRegistrationData};
+
+/// The default maximum number of registrations to send to the builder in a single request.
+pub const DEFAULT_REGISTRATION_BATCH_SIZE: usize = 500;
+
+/// The default pause between consecutive batches of registrations.
+pub const DEFAULT_REGISTRATION_BATCH_DELAY: Duration = Duration::from_millis(100);
+
+/// The number of times a failed batch is retried before giving up on it.
+pub const REGISTRATION_MAX_RETRIES: u32 = 3;
+
+/// The delay before the first retry of a failed batch, doubled for each subsequent retry.
+pub const REGISTRATION_INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Unchanged registrations are re-submitted after this long, in case the relay has lost them.
+pub const REGISTRATION_REFRESH_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Tracks the registrations which have been accepted by the builder.
+#[derive(Default)]
+pub struct RegistrationCache {
+    submitted: Mutex<HashMap<PublicKeyBytes, (SignedValidatorRegistrationData, Instant)>>,
+}
+
+impl RegistrationCache {
+    /// Returns the registrations which must be sent to the builder, dropping any that are
+    /// identical to a registration accepted within the last `REGISTRATION_REFRESH_INTERVAL`.
+    pub fn filter_unchanged(
+        &self,
+        registrations: &[SignedValidatorRegistrationData],
+    ) -> Vec<SignedValidatorRegistrationData> {
+        let mut submitted = self.submitted.lock();
+
+        submitted
+            .retain(|_, (_, submitted_at)| submitted_at.elapsed() < REGISTRATION_REFRESH_INTERVAL);
+
+        registrations
+            .iter()
+            .filter(|registration| {
+                !matches!(
+                    submitted.get(&registration.message.pubkey),
+                    Some((previous, _)) if previous == *registration
+                )
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Record that `registrations` have been accepted by the builder.
+    pub fn mark_submitted(&self, registrations: &[SignedValidatorRegistrationData]) {
+        let now = Instant::now();
+        let mut submitted = self.submitted.lock();
+        for registration in registrations {
+            submitted.insert(registration.message.pubkey, (registration.clone(), now));
+        }
+    }
+
+    /// Returns `true` if `registration` is identical to the one last accepted by the builder for
+    /// its pubkey.
+    pub fn is_submitted(&self, registration: &SignedValidatorRegistrationData) -> bool {
+        matches!(
+            self.submitted.lock().get(&registration.message.pubkey),
+            Some((previous, _)) if previous == registration
+        )
+    }
+
+    /// Forget all submitted registrations, e.g. because the builder has changed.
+    pub fn clear(&self) {
+        self.submitted.lock().clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.submitted.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.submitted.lock().is_empty()
+    }
+}
+
+/// The most recent registration of each validator forwarded to the builders, and the UNIX
+/// timestamp in seconds at which it was forwarded.
+#[derive(Default)]
+pub struct ForwardedRegistrations {
+    registrations: Mutex<HashMap<PublicKeyBytes, (SignedValidatorRegistrationData, u64)>>,
+}
+
+impl ForwardedRegistrations {
+    /// Record that `registrations` were forwarded at `timestamp`, replacing any earlier
+    /// registrations of the same validators.
+    pub fn record(&self, registrations: &[SignedValidatorRegistrationData], timestamp: u64) {
+        let mut forwarded = self.registrations.lock();
+        for registration in registrations {
+            forwarded.insert(
+                registration.message.pubkey,
+                (registration.clone(), timestamp),
+            );
+        }
+    }
+
+    pub fn get(&self, pubkey: &PublicKeyBytes) -> Option<(SignedValidatorRegistrationData, u64)> {
+        self.registrations.lock().get(pubkey).cloned()
+    }
+}
+
+/// Returns the delay to wait before retry number `attempt` (starting at 1).
+pub fn retry_backoff(attempt: u32) -> Duration {
+    REGISTRATION_INITIAL_BACKOFF.saturating_mul(2_u32.saturating_pow(attempt.saturating_sub(1)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use types::{Address, Signature, ValidatorRegistrationData};
+
+    fn registration(pubkey_byte: u8, gas_limit: u64) -> SignedValidatorRegistrationData {
+        SignedValidatorRegistrationData {
+            message: ValidatorRegistrationData {
+                fee_recipient: Address::repeat_byte(pubkey_byte),
+                gas_limit,
+                timestamp: 0,
+                pubkey: PublicKeyBytes::deserialize(&[pubkey_byte; 48]).unwrap(),
+            },
+            signature: Signature::empty(),
+        }
+    }
+
+    #[test]
+    fn unchanged_registrations_are_filtered() {
+        let cache = RegistrationCache::default();
+        let registrations = vec![registration(1, 30_000_000), registration(2, 30_000_000)];
+
+        assert_eq!(cache.filter_unchanged(&registrations), registrations);
+
+        cache.mark_submitted(&registrations);
+        assert!(cache.filter_unchanged(&registrations).is_empty());
+
+        let changed = vec![registration(1, 36_000_000), registration(2, 30_000_000)];
+        assert_eq!(cache.filter_unchanged(&changed), vec![changed[0].clone()]);
+
+        cache.clear();
+        assert_eq!(cache.filter_unchanged(&changed), changed);
+    }
+
+    #[test]
+    fn most_recent_registration_is_forwarded() {
+        let forwarded = ForwardedRegistrations::default();
+        let first = registration(1, 30_000_000);
+        let second = registration(1, 36_000_000);
+        let pubkey = first.message.pubkey;
+
+        assert_eq!(forwarded.get(&pubkey), None);
+
+        forwarded.record(&[first.clone(), registration(2, 30_000_000)], 10);
+        assert_eq!(forwarded.get(&pubkey), Some((first, 10)));
+
+        forwarded.record(&[second.clone()], 20);
+        assert_eq!(forwarded.get(&pubkey), Some((second.clone(), 20)));
+
+        let cache = RegistrationCache::default();
+        assert!(!cache.is_submitted(&second));
+        cache.mark_submitted(&[second.clone()]);
+        assert!(cache.is_submitted(&second));
+        assert!(!cache.is_submitted(&registration(1, 30_000_000)));
+    }
+
+    #[test]
+    fn backoff_doubles() {
+        assert_eq!(retry_backoff(1), REGISTRATION_INITIAL_BACKOFF);
+        assert_eq!(retry_backoff(2), REGISTRATION_INITIAL_BACKOFF * 2);
+        assert_eq!(retry_backoff(3), REGISTRATION_INITIAL_BACKOFF * 4);
+    }
+}
@@ -530,9 +530,13 @@ impl<E: EthSpec> From<GetPayloadResponse<E>>
     }
 }
 
-pub enum GetPayloadResponseType<E: EthSpec> {
-    Full(GetPayloadResponse<E>),
-    Blinded(GetPayloadResponse<E>),
+/// A payload from the local execution engine, along with its header.
+///
+/// The header is computed once when the payload is received, so that the transactions of the
+/// payload are hashed at most once during block production.
+pub struct LocalPayload<E: EthSpec> {
+    pub response: GetPayloadResponse<E>,
+    pub header: ExecutionPayloadHeader<E>,
 }
 
 impl<E: EthSpec> GetPayloadResponse<E> {
@@ -11,6 +11,7 @@ use serde::de::DeserializeOwned;
 use serde_json::json;
 use std::collections::HashSet;
 use tokio::sync::Mutex;
+use types::ExecutionRequests;
 
 use std::time::{Duration, Instant};
 
@@ -838,10 +839,13 @@ impl HttpJsonRpc {
         &self,
         new_payload_request_electra: NewPayloadRequestElectra<'_, E>,
     ) -> Result<PayloadStatusV1, Error> {
+        let execution_requests =
+            ExecutionRequests::from(new_payload_request_electra.execution_payload);
         let params = json!([
             JsonExecutionPayload::V4(new_payload_request_electra.execution_payload.clone().into()),
             new_payload_request_electra.versioned_hashes,
             new_payload_request_electra.parent_beacon_block_root,
+            JsonExecutionRequests::from(execution_requests),
         ]);
 
         let response: JsonPayloadStatusV1 = self
@@ -1326,7 +1330,7 @@ impl HttpJsonRpc {
 mod test {
     use super::auth::JwtKey;
     use super::*;
-    use crate::test_utils::{MockServer, DEFAULT_JWT_SECRET};
+    use crate::test_utils::{FaultInjection, MockServer, DEFAULT_JWT_SECRET};
     use std::future::Future;
     use std::str::FromStr;
     use std::sync::Arc;
@@ -2109,4 +2113,81 @@ mod test {
             )
             .await;
     }
+
+    #[tokio::test]
+    async fn fault_injection_delays() {
+        let tester = Tester::new(true);
+        let response_delay = Duration::from_millis(50);
+        let method_delay = Duration::from_millis(500);
+        tester.server.set_fault_injection(FaultInjection {
+            response_delay,
+            method_delays: [(ENGINE_EXCHANGE_CAPABILITIES.to_string(), method_delay)].into(),
+            ..FaultInjection::default()
+        });
+
+        let start = Instant::now();
+        tester.rpc_client.exchange_capabilities().await.unwrap();
+        assert!(start.elapsed() >= method_delay);
+
+        let start = Instant::now();
+        tester
+            .rpc_client
+            .get_block_by_number(BlockByNumberQuery::Tag(LATEST_TAG))
+            .await
+            .unwrap();
+        let elapsed = start.elapsed();
+        assert!(elapsed >= response_delay && elapsed < method_delay);
+    }
+
+    #[tokio::test]
+    async fn fault_injection_errors() {
+        let tester = Tester::new(true);
+        tester.server.set_fault_injection(FaultInjection {
+            error_probability: 1.0,
+            ..FaultInjection::default()
+        });
+        let result = tester
+            .rpc_client
+            .get_block_by_number(BlockByNumberQuery::Tag(LATEST_TAG))
+            .await;
+        match result {
+            Err(Error::ServerMessage { message, .. }) => assert_eq!(message, "injected fault"),
+            other => panic!("expected an injected fault, got {:?}", other),
+        }
+
+        tester.server.set_fault_injection(FaultInjection::default());
+        tester
+            .rpc_client
+            .get_block_by_number(BlockByNumberQuery::Tag(LATEST_TAG))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn fault_injection_invalid_statuses() {
+        let tester = Tester::new(true);
+        tester.server.set_fault_injection(FaultInjection {
+            invalid_status_probability: 1.0,
+            ..FaultInjection::default()
+        });
+        let status = tester
+            .rpc_client
+            .new_payload_v1::<MainnetEthSpec>(ExecutionPayload::Bellatrix(
+                ExecutionPayloadBellatrix {
+                    parent_hash: ExecutionBlockHash::repeat_byte(1),
+                    block_hash: ExecutionBlockHash::repeat_byte(2),
+                    ..ExecutionPayloadBellatrix::default()
+                },
+            ))
+            .await
+            .unwrap();
+        assert_eq!(
+            status,
+            PayloadStatusV1 {
+                status: PayloadStatusV1Status::Invalid,
+                latest_valid_hash: Some(ExecutionBlockHash::repeat_byte(1)),
+                validation_error: Some("injected fault".to_string()),
+            }
+        );
+    }
 }
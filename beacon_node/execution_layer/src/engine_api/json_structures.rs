@@ -7,8 +7,8 @@ use superstruct::superstruct;
 use types::beacon_block_body::KzgCommitments;
 use types::blob_sidecar::BlobsList;
 use types::{
-    DepositRequest, ExecutionLayerWithdrawalRequest, FixedVector, PublicKeyBytes, Signature,
-    Unsigned,
+    DepositRequest, ExecutionLayerWithdrawalRequest, ExecutionRequests, FixedVector,
+    PublicKeyBytes, Signature, Unsigned,
 };
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -64,6 +64,19 @@ pub struct JsonPayloadIdResponse {
     pub payload_id: PayloadId,
 }
 
+/// The EIP-7685 `executionRequests` parameter of `engine_newPayloadV4`.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(transparent, bound = "E: EthSpec")]
+pub struct JsonExecutionRequests<E: EthSpec>(
+    #[serde(with = "types::execution_requests::hex_requests_list")] pub ExecutionRequests<E>,
+);
+
+impl<E: EthSpec> From<ExecutionRequests<E>> for JsonExecutionRequests<E> {
+    fn from(requests: ExecutionRequests<E>) -> Self {
+        Self(requests)
+    }
+}
+
 #[superstruct(
     variants(V1, V2, V3, V4),
     variant_attributes(
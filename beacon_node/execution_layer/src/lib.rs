@@ -4,8 +4,14 @@
 //! This crate only provides useful functionality for "The Merge", it does not provide any of the
 //! deposit-contract functionality that the `beacon_node/eth1` crate already provides.
 
+use crate::bid_audit::BidAuditLog;
+use crate::builder_registration::{
+    retry_backoff, ForwardedRegistrations, DEFAULT_REGISTRATION_BATCH_DELAY,
+    DEFAULT_REGISTRATION_BATCH_SIZE, REGISTRATION_MAX_RETRIES,
+};
 use crate::payload_cache::PayloadCache;
-use arc_swap::ArcSwapOption;
+use crate::relay::{BidRelayCache, Relay, RELAY_SKIP_DURATION};
+use arc_swap::ArcSwap;
 use auth::{strip_prefix, Auth, JwtKey};
 pub use block_hash::calculate_execution_block_hash;
 use builder_client::BuilderHttpClient;
@@ -15,10 +21,16 @@ pub use engine_api::*;
 pub use engine_api::{http, http::deposit_methods, http::HttpJsonRpc};
 use engines::{Engine, EngineError};
 pub use engines::{EngineState, ForkchoiceState};
+use eth2::lighthouse::{
+    BuilderBidAudit, BuilderBidDecision, CircuitBreakerCondition, ForwardedRegistration,
+    RelayRegistration,
+};
 use eth2::types::FullPayloadContents;
 use eth2::types::{builder_bid::SignedBuilderBid, BlobsBundle, ForkVersionedResponse};
+use eth2::StatusCode;
 use ethers_core::types::Transaction as EthersTransaction;
 use fork_choice::ForkchoiceUpdateParameters;
+use futures::future::{join_all, select_ok};
 use lru::LruCache;
 use payload_status::process_payload_status;
 pub use payload_status::PayloadStatus;
@@ -52,16 +64,19 @@ use types::{
 use types::{
     BeaconStateError, BlindedPayload, ChainSpec, Epoch, ExecPayload, ExecutionPayloadBellatrix,
     ExecutionPayloadCapella, ExecutionPayloadElectra, FullPayload, ProposerPreparationData,
-    PublicKeyBytes, Signature, Slot,
+    PublicKeyBytes, Signature, SignedValidatorRegistrationData, Slot,
 };
 
+pub mod bid_audit;
 mod block_hash;
+pub mod builder_registration;
 mod engine_api;
 pub mod engines;
 mod keccak;
 mod metrics;
 pub mod payload_cache;
 mod payload_status;
+pub mod relay;
 pub mod test_utils;
 mod versioned_hashes;
 
@@ -101,22 +116,26 @@ impl<E: EthSpec> TryFrom<BuilderBid<E>> for ProvenancedPayload<BlockProposalCont
             BuilderBid::Bellatrix(builder_bid) => BlockProposalContents::Payload {
                 payload: ExecutionPayloadHeader::Bellatrix(builder_bid.header).into(),
                 block_value: builder_bid.value,
+                payload_header: None,
             },
             BuilderBid::Capella(builder_bid) => BlockProposalContents::Payload {
                 payload: ExecutionPayloadHeader::Capella(builder_bid.header).into(),
                 block_value: builder_bid.value,
+                payload_header: None,
             },
             BuilderBid::Deneb(builder_bid) => BlockProposalContents::PayloadAndBlobs {
                 payload: ExecutionPayloadHeader::Deneb(builder_bid.header).into(),
                 block_value: builder_bid.value,
                 kzg_commitments: builder_bid.blob_kzg_commitments,
                 blobs_and_proofs: None,
+                payload_header: None,
             },
             BuilderBid::Electra(builder_bid) => BlockProposalContents::PayloadAndBlobs {
                 payload: ExecutionPayloadHeader::Electra(builder_bid.header).into(),
                 block_value: builder_bid.value,
                 kzg_commitments: builder_bid.blob_kzg_commitments,
                 blobs_and_proofs: None,
+                payload_header: None,
             },
         };
         Ok(ProvenancedPayload::Builder(
@@ -149,7 +168,6 @@ pub enum Error {
     InvalidPayloadConversion,
     InvalidBlobConversion(String),
     BeaconStateError(BeaconStateError),
-    PayloadTypeMismatch,
     VerifyingVersionedHashes(versioned_hashes::Error),
 }
 
@@ -185,6 +203,8 @@ pub enum BlockProposalContents<E: EthSpec, Payload: AbstractExecPayload<E>> {
     Payload {
         payload: Payload,
         block_value: Uint256,
+        /// The header of a full payload, if it was computed when the payload was received.
+        payload_header: Option<ExecutionPayloadHeader<E>>,
     },
     PayloadAndBlobs {
         payload: Payload,
@@ -192,9 +212,22 @@ pub enum BlockProposalContents<E: EthSpec, Payload: AbstractExecPayload<E>> {
         kzg_commitments: KzgCommitments<E>,
         /// `None` for blinded `PayloadAndBlobs`.
         blobs_and_proofs: Option<(BlobsList<E>, KzgProofs<E>)>,
+        /// The header of a full payload, if it was computed when the payload was received.
+        payload_header: Option<ExecutionPayloadHeader<E>>,
     },
 }
 
+/// Blind `payload`, re-using its header if it has already been computed.
+fn blind_payload<E: EthSpec>(
+    payload: FullPayload<E>,
+    payload_header: Option<ExecutionPayloadHeader<E>>,
+) -> BlindedPayload<E> {
+    match payload_header {
+        Some(header) => header.into(),
+        None => payload.execution_payload().into(),
+    }
+}
+
 impl<E: EthSpec> From<BlockProposalContents<E, FullPayload<E>>>
     for BlockProposalContents<E, BlindedPayload<E>>
 {
@@ -203,20 +236,24 @@ impl<E: EthSpec> From<BlockProposalContents<E, FullPayload<E>>>
             BlockProposalContents::Payload {
                 payload,
                 block_value,
+                payload_header,
             } => BlockProposalContents::Payload {
-                payload: payload.execution_payload().into(),
+                payload: blind_payload(payload, payload_header),
                 block_value,
+                payload_header: None,
             },
             BlockProposalContents::PayloadAndBlobs {
                 payload,
                 block_value,
                 kzg_commitments,
                 blobs_and_proofs: _,
+                payload_header,
             } => BlockProposalContents::PayloadAndBlobs {
-                payload: payload.execution_payload().into(),
+                payload: blind_payload(payload, payload_header),
                 block_value,
                 kzg_commitments,
                 blobs_and_proofs: None,
+                payload_header: None,
             },
         }
     }
@@ -235,23 +272,36 @@ impl<E: EthSpec, Payload: AbstractExecPayload<E>> TryFrom<GetPayloadResponse<E>>
                 block_value,
                 kzg_commitments: bundle.commitments,
                 blobs_and_proofs: Some((bundle.blobs, bundle.proofs)),
+                payload_header: None,
             }),
             None => Ok(Self::Payload {
                 payload: execution_payload.into(),
                 block_value,
+                payload_header: None,
             }),
         }
     }
 }
 
-impl<E: EthSpec> TryFrom<GetPayloadResponseType<E>> for BlockProposalContentsType<E> {
+impl<E: EthSpec> TryFrom<LocalPayload<E>> for BlockProposalContents<E, FullPayload<E>> {
     type Error = Error;
 
-    fn try_from(response_type: GetPayloadResponseType<E>) -> Result<Self, Error> {
-        match response_type {
-            GetPayloadResponseType::Full(response) => Ok(Self::Full(response.try_into()?)),
-            GetPayloadResponseType::Blinded(response) => Ok(Self::Blinded(response.try_into()?)),
+    fn try_from(local: LocalPayload<E>) -> Result<Self, Error> {
+        let mut contents = Self::try_from(local.response)?;
+        match &mut contents {
+            Self::Payload { payload_header, .. } | Self::PayloadAndBlobs { payload_header, .. } => {
+                *payload_header = Some(local.header)
+            }
         }
+        Ok(contents)
+    }
+}
+
+impl<E: EthSpec> TryFrom<LocalPayload<E>> for BlockProposalContentsType<E> {
+    type Error = Error;
+
+    fn try_from(local: LocalPayload<E>) -> Result<Self, Error> {
+        Ok(Self::Full(local.try_into()?))
     }
 }
 
@@ -269,12 +319,14 @@ impl<E: EthSpec, Payload: AbstractExecPayload<E>> BlockProposalContents<E, Paylo
             Self::Payload {
                 payload,
                 block_value,
+                payload_header: _,
             } => (payload, None, None, block_value),
             Self::PayloadAndBlobs {
                 payload,
                 block_value,
                 kzg_commitments,
                 blobs_and_proofs,
+                payload_header: _,
             } => (
                 payload,
                 Some(kzg_commitments),
@@ -302,6 +354,13 @@ impl<E: EthSpec, Payload: AbstractExecPayload<E>> BlockProposalContents<E, Paylo
             Self::PayloadAndBlobs { block_value, .. } => block_value,
         }
     }
+    /// Take the header of the payload, if it was computed when the payload was received.
+    pub fn take_payload_header(&mut self) -> Option<ExecutionPayloadHeader<E>> {
+        match self {
+            Self::Payload { payload_header, .. } => payload_header.take(),
+            Self::PayloadAndBlobs { payload_header, .. } => payload_header.take(),
+        }
+    }
 }
 
 #[derive(Clone, PartialEq)]
@@ -332,23 +391,30 @@ pub struct BuilderParams {
 #[derive(PartialEq)]
 pub enum ChainHealth {
     Healthy,
-    Unhealthy(FailedCondition),
+    Unhealthy(CircuitBreakerCondition),
     Optimistic,
     PreMerge,
 }
 
-#[derive(Debug, PartialEq)]
-pub enum FailedCondition {
-    Skips,
-    SkipsPerEpoch,
-    EpochsSinceFinalization,
+type PayloadContentsRefTuple<'a, E> = (ExecutionPayloadRef<'a, E>, Option<&'a BlobsBundle<E>>);
+
+/// The response to a header request sent to a single relay.
+struct RelayResponse<E: EthSpec> {
+    relay: Arc<Relay>,
+    result: Result<Option<ForkVersionedResponse<SignedBuilderBid<E>>>, builder_client::Error>,
+    duration: Duration,
 }
 
-type PayloadContentsRefTuple<'a, E> = (ExecutionPayloadRef<'a, E>, Option<&'a BlobsBundle<E>>);
+/// The most valuable valid bid received from any relay.
+struct SelectedBid<E: EthSpec> {
+    relay: Arc<Relay>,
+    bid: ForkVersionedResponse<SignedBuilderBid<E>>,
+    duration: Duration,
+}
 
 struct Inner<E: EthSpec> {
     engine: Arc<Engine>,
-    builder: ArcSwapOption<BuilderHttpClient>,
+    builders: ArcSwap<Vec<Arc<Relay>>>,
     execution_engine_forkchoice_lock: Mutex<()>,
     suggested_fee_recipient: Option<Address>,
     proposer_preparation_data: Mutex<HashMap<u64, ProposerPreparationDataEntry>>,
@@ -356,6 +422,14 @@ struct Inner<E: EthSpec> {
     proposers: RwLock<HashMap<ProposerKey, Proposer>>,
     executor: TaskExecutor,
     payload_cache: PayloadCache<E>,
+    /// The relays which offered each recently selected bid.
+    bid_relay_cache: BidRelayCache,
+    /// Recently received builder bids and the decisions taken about them.
+    bid_audit_log: BidAuditLog,
+    /// The most recent registration of each validator forwarded to the builders.
+    forwarded_registrations: ForwardedRegistrations,
+    builder_registration_batch_size: usize,
+    builder_registration_batch_delay: Duration,
     log: Logger,
     /// Track whether the last `newPayload` call errored.
     ///
@@ -369,12 +443,18 @@ pub struct Config {
     /// Endpoint url for EL nodes that are running the engine api.
     pub execution_endpoint: Option<SensitiveUrl>,
     /// Endpoint urls for services providing the builder api.
-    pub builder_url: Option<SensitiveUrl>,
+    pub builder_urls: Vec<SensitiveUrl>,
     /// The timeout value used when making a request to fetch a block header
     /// from the builder api.
     pub builder_header_timeout: Option<Duration>,
     /// User agent to send with requests to the builder API.
     pub builder_user_agent: Option<String>,
+    /// Disable the use of SSZ encoding when communicating with the builder API.
+    pub builder_disable_ssz: bool,
+    /// The maximum number of validator registrations to send to the builder in one request.
+    pub builder_registration_batch_size: Option<usize>,
+    /// The time to wait between consecutive batches of validator registrations.
+    pub builder_registration_batch_delay: Option<Duration>,
     /// JWT secret for the above endpoint running the engine api.
     pub secret_file: Option<PathBuf>,
     /// The default fee recipient to use on the beacon node if none if provided from
@@ -401,9 +481,12 @@ impl<E: EthSpec> ExecutionLayer<E> {
     pub fn from_config(config: Config, executor: TaskExecutor, log: Logger) -> Result<Self, Error> {
         let Config {
             execution_endpoint: url,
-            builder_url,
+            builder_urls,
             builder_user_agent,
             builder_header_timeout,
+            builder_disable_ssz,
+            builder_registration_batch_size,
+            builder_registration_batch_delay,
             secret_file,
             suggested_fee_recipient,
             jwt_id,
@@ -456,7 +539,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
 
         let inner = Inner {
             engine: Arc::new(engine),
-            builder: ArcSwapOption::empty(),
+            builders: ArcSwap::from_pointee(vec![]),
             execution_engine_forkchoice_lock: <_>::default(),
             suggested_fee_recipient,
             proposer_preparation_data: Mutex::new(HashMap::new()),
@@ -464,6 +547,13 @@ impl<E: EthSpec> ExecutionLayer<E> {
             execution_blocks: Mutex::new(LruCache::new(EXECUTION_BLOCKS_LRU_CACHE_SIZE)),
             executor,
             payload_cache: PayloadCache::default(),
+            bid_relay_cache: BidRelayCache::default(),
+            bid_audit_log: BidAuditLog::default(),
+            forwarded_registrations: ForwardedRegistrations::default(),
+            builder_registration_batch_size: builder_registration_batch_size
+                .unwrap_or(DEFAULT_REGISTRATION_BATCH_SIZE),
+            builder_registration_batch_delay: builder_registration_batch_delay
+                .unwrap_or(DEFAULT_REGISTRATION_BATCH_DELAY),
             log,
             last_new_payload_errored: RwLock::new(false),
         };
@@ -472,8 +562,13 @@ impl<E: EthSpec> ExecutionLayer<E> {
             inner: Arc::new(inner),
         };
 
-        if let Some(builder_url) = builder_url {
-            el.set_builder_url(builder_url, builder_user_agent, builder_header_timeout)?;
+        if !builder_urls.is_empty() {
+            el.set_builder_urls(
+                builder_urls,
+                builder_user_agent,
+                builder_header_timeout,
+                builder_disable_ssz,
+            )?;
         }
 
         Ok(el)
@@ -483,33 +578,72 @@ impl<E: EthSpec> ExecutionLayer<E> {
         &self.inner.engine
     }
 
-    pub fn builder(&self) -> Option<Arc<BuilderHttpClient>> {
-        self.inner.builder.load_full()
+    /// Returns the builder relays that the execution layer is connected to.
+    pub fn builders(&self) -> Arc<Vec<Arc<Relay>>> {
+        self.inner.builders.load_full()
+    }
+
+    /// Returns the recently received builder bids for `slot`, or for all slots if `None`.
+    pub fn builder_bids(&self, slot: Option<Slot>) -> Vec<BuilderBidAudit> {
+        self.inner.bid_audit_log.get(slot)
     }
 
-    /// Set the builder URL after initialization.
+    /// Returns the most recent registration of `pubkey` forwarded to the builders, and whether
+    /// each builder has accepted it.
+    pub fn forwarded_registration(&self, pubkey: &PublicKeyBytes) -> Option<ForwardedRegistration> {
+        let (registration, forwarded_at) = self.inner.forwarded_registrations.get(pubkey)?;
+        let relays = self
+            .builders()
+            .iter()
+            .map(|relay| RelayRegistration {
+                relay: relay.name().to_string(),
+                accepted: relay.registration_cache().is_submitted(&registration),
+            })
+            .collect();
+        Some(ForwardedRegistration {
+            registration,
+            forwarded_at,
+            relays,
+        })
+    }
+
+    /// Set the builder URLs after initialization, replacing any existing builders.
     ///
     /// This is useful for breaking circular dependencies between mock ELs and mock builders in
     /// tests.
-    pub fn set_builder_url(
+    pub fn set_builder_urls(
         &self,
-        builder_url: SensitiveUrl,
+        builder_urls: Vec<SensitiveUrl>,
         builder_user_agent: Option<String>,
         builder_header_timeout: Option<Duration>,
+        disable_ssz: bool,
     ) -> Result<(), Error> {
-        let builder_client = BuilderHttpClient::new(
-            builder_url.clone(),
-            builder_user_agent,
-            builder_header_timeout,
-        )
-        .map_err(Error::Builder)?;
-        info!(
-            self.log(),
-            "Using external block builder";
-            "builder_url" => ?builder_url,
-            "local_user_agent" => builder_client.get_user_agent(),
-        );
-        self.inner.builder.swap(Some(Arc::new(builder_client)));
+        let relays = builder_urls
+            .into_iter()
+            .map(|builder_url| {
+                let builder_client = BuilderHttpClient::new(
+                    builder_url.clone(),
+                    builder_user_agent.clone(),
+                    builder_header_timeout,
+                    disable_ssz,
+                )
+                .map_err(Error::Builder)?;
+                info!(
+                    self.log(),
+                    "Using external block builder";
+                    "builder_url" => ?builder_url,
+                    "local_user_agent" => builder_client.get_user_agent(),
+                    "ssz_enabled" => builder_client.is_ssz_enabled(),
+                );
+                metrics::set_int_gauge(
+                    &metrics::EXECUTION_LAYER_BUILDER_RELAY_SKIPPED,
+                    &[builder_client.server().as_ref()],
+                    0,
+                );
+                Ok(Arc::new(Relay::new(builder_client)))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        self.inner.builders.store(Arc::new(relays));
         Ok(())
     }
 
@@ -517,6 +651,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
     fn cache_payload(
         &self,
         payload_and_blobs: PayloadContentsRefTuple<E>,
+        payload_root: Hash256,
     ) -> Option<FullPayloadContents<E>> {
         let (payload_ref, maybe_json_blobs_bundle) = payload_and_blobs;
 
@@ -529,9 +664,10 @@ impl<E: EthSpec> ExecutionLayer<E> {
                 blobs: blobs_bundle.blobs,
             });
 
-        self.inner
-            .payload_cache
-            .put(FullPayloadContents::new(payload, maybe_blobs_bundle))
+        self.inner.payload_cache.put(
+            payload_root,
+            FullPayloadContents::new(payload, maybe_blobs_bundle),
+        )
     }
 
     /// Attempt to retrieve a full payload from the payload cache by the payload root
@@ -862,7 +998,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
                     noop,
                 )
                 .await
-                .and_then(GetPayloadResponseType::try_into)
+                .and_then(BlockProposalContentsType::try_from)
                 .map(ProvenancedPayload::Local)?,
         };
 
@@ -903,37 +1039,64 @@ impl<E: EthSpec> ExecutionLayer<E> {
         }
     }
 
-    /// Fetches local and builder paylaods concurrently, Logs and returns results.
+    /// Fetches local and builder payloads concurrently, Logs and returns results.
+    ///
+    /// Headers are requested from all relays which are not currently being skipped, each relay
+    /// being subject to its own header timeout.
     async fn fetch_builder_and_local_payloads(
         &self,
-        builder: &BuilderHttpClient,
+        builders: &[Arc<Relay>],
         parent_hash: ExecutionBlockHash,
         builder_params: &BuilderParams,
         payload_attributes: &PayloadAttributes,
         forkchoice_update_params: ForkchoiceUpdateParameters,
         current_fork: ForkName,
-    ) -> (
-        Result<Option<ForkVersionedResponse<SignedBuilderBid<E>>>, builder_client::Error>,
-        Result<GetPayloadResponse<E>, Error>,
-    ) {
+    ) -> (Vec<RelayResponse<E>>, Result<LocalPayload<E>, Error>) {
         let slot = builder_params.slot;
         let pubkey = &builder_params.pubkey;
 
+        let (skipped, relays): (Vec<_>, Vec<_>) =
+            builders.iter().partition(|relay| relay.is_skipped());
+        for relay in skipped {
+            metrics::inc_counter_vec(
+                &metrics::EXECUTION_LAYER_BUILDER_RELAY_HEADER_OUTCOME,
+                &[relay.name(), metrics::SKIPPED],
+            );
+            debug!(
+                self.log(),
+                "Skipping unreliable relay";
+                "relay" => relay.name(),
+                "slot" => ?slot,
+            );
+        }
+
         info!(
             self.log(),
-            "Requesting blinded header from connected builder";
+            "Requesting blinded header from connected builders";
             "slot" => ?slot,
             "pubkey" => ?pubkey,
             "parent_hash" => ?parent_hash,
+            "relays" => relays.len(),
         );
 
-        // Wait for the builder *and* local EL to produce a payload (or return an error).
-        let ((relay_result, relay_duration), (local_result, local_duration)) = tokio::join!(
-            timed_future(metrics::GET_BLINDED_PAYLOAD_BUILDER, async {
-                builder
-                    .get_builder_header::<E>(slot, parent_hash, pubkey)
-                    .await
-            }),
+        // Wait for the builders *and* local EL to produce a payload (or return an error).
+        let (relay_responses, (local_result, local_duration)) = tokio::join!(
+            join_all(relays.into_iter().map(|relay| async move {
+                let (result, duration) =
+                    timed_future(metrics::GET_BLINDED_PAYLOAD_BUILDER, async {
+                        relay
+                            .client()
+                            .get_builder_header::<E>(slot, parent_hash, pubkey)
+                            .await
+                    })
+                    .await;
+                self.record_relay_response(relay, &result, duration);
+                RelayResponse {
+                    relay: relay.clone(),
+                    result,
+                    duration,
+                }
+            })),
             timed_future(metrics::GET_BLINDED_PAYLOAD_LOCAL, async {
                 self.get_full_payload_caching(
                     parent_hash,
@@ -942,31 +1105,210 @@ impl<E: EthSpec> ExecutionLayer<E> {
                     current_fork,
                 )
                 .await
-                .and_then(|local_result_type| match local_result_type {
-                    GetPayloadResponseType::Full(payload) => Ok(payload),
-                    GetPayloadResponseType::Blinded(_) => Err(Error::PayloadTypeMismatch),
-                })
             })
         );
 
+        for response in &relay_responses {
+            info!(
+                self.log(),
+                "Requested blinded execution payload";
+                "relay" => response.relay.name(),
+                "relay_fee_recipient" => match &response.result {
+                    Ok(Some(r)) => format!("{:?}", r.data.message.header().fee_recipient()),
+                    Ok(None) => "empty response".to_string(),
+                    Err(_) => "request failed".to_string(),
+                },
+                "relay_response_ms" => response.duration.as_millis(),
+                "parent_hash" => ?parent_hash,
+            );
+        }
         info!(
             self.log(),
-            "Requested blinded execution payload";
-            "relay_fee_recipient" => match &relay_result {
-                Ok(Some(r)) => format!("{:?}", r.data.message.header().fee_recipient()),
-                Ok(None) => "empty response".to_string(),
-                Err(_) => "request failed".to_string(),
-            },
-            "relay_response_ms" => relay_duration.as_millis(),
+            "Requested local execution payload";
             "local_fee_recipient" => match &local_result {
-                Ok(get_payload_response) => format!("{:?}", get_payload_response.fee_recipient()),
+                Ok(local) => format!("{:?}", local.response.fee_recipient()),
                 Err(_) => "request failed".to_string()
             },
             "local_response_ms" => local_duration.as_millis(),
             "parent_hash" => ?parent_hash,
         );
 
-        (relay_result, local_result)
+        (relay_responses, local_result)
+    }
+
+    /// Update the reliability and metrics of `relay` following a header request.
+    fn record_relay_response(
+        &self,
+        relay: &Relay,
+        result: &Result<Option<ForkVersionedResponse<SignedBuilderBid<E>>>, builder_client::Error>,
+        duration: Duration,
+    ) {
+        metrics::observe_timer_vec(
+            &metrics::EXECUTION_LAYER_BUILDER_RELAY_HEADER_TIMES,
+            &[relay.name()],
+            duration,
+        );
+        let outcome = match result {
+            Ok(Some(_)) => metrics::SUCCESS,
+            Ok(None) => metrics::NO_BID,
+            Err(_) => metrics::FAILURE,
+        };
+        metrics::inc_counter_vec(
+            &metrics::EXECUTION_LAYER_BUILDER_RELAY_HEADER_OUTCOME,
+            &[relay.name(), outcome],
+        );
+
+        if result.is_ok() {
+            relay.record_success();
+        } else if relay.record_failure() {
+            warn!(
+                self.log(),
+                "Temporarily skipping unreliable relay";
+                "relay" => relay.name(),
+                "skip_duration_secs" => RELAY_SKIP_DURATION.as_secs(),
+            );
+        }
+        metrics::set_int_gauge(
+            &metrics::EXECUTION_LAYER_BUILDER_RELAY_SKIPPED,
+            &[relay.name()],
+            relay.is_skipped() as i64,
+        );
+    }
+
+    /// Verify the bids received from all relays and select the most valuable valid bid.
+    ///
+    /// Returns an error only if every relay request failed.
+    #[allow(clippy::too_many_arguments)]
+    fn select_builder_bid(
+        &self,
+        relay_responses: Vec<RelayResponse<E>>,
+        parent_hash: ExecutionBlockHash,
+        payload_attributes: &PayloadAttributes,
+        builder_params: &BuilderParams,
+        builder_boost_factor: Option<u64>,
+        local: Option<&GetPayloadResponse<E>>,
+        current_fork: ForkName,
+        spec: &ChainSpec,
+    ) -> Result<Option<SelectedBid<E>>, builder_client::Error> {
+        let local_block_number = local.map(|local| local.block_number());
+        let local_value = local.map(|local| *local.block_value());
+        // Every bid must commit to the same withdrawals, so only hash them once.
+        let expected_withdrawals_root = payload_attributes
+            .withdrawals()
+            .ok()
+            .cloned()
+            .map(|withdrawals| Withdrawals::<E>::from(withdrawals).tree_hash_root());
+
+        let mut first_error = None;
+        let mut any_response = false;
+        let mut valid_bids = vec![];
+        for RelayResponse {
+            relay,
+            result,
+            duration,
+        } in relay_responses
+        {
+            let bid = match result {
+                Ok(Some(bid)) => bid,
+                Ok(None) => {
+                    any_response = true;
+                    continue;
+                }
+                Err(e) => {
+                    warn!(
+                        self.log(),
+                        "Builder error when requesting payload";
+                        "relay" => relay.name(),
+                        "relay_error" => ?e,
+                        "parent_hash" => ?parent_hash,
+                    );
+                    first_error.get_or_insert(e);
+                    continue;
+                }
+            };
+            any_response = true;
+
+            let boosted_value = boost_relay_value(*bid.data.message.value(), builder_boost_factor);
+            if let Err(reason) = verify_builder_bid(
+                &bid,
+                parent_hash,
+                payload_attributes,
+                expected_withdrawals_root,
+                local_block_number,
+                current_fork,
+                spec,
+            ) {
+                metrics::inc_counter_vec(
+                    &metrics::EXECUTION_LAYER_GET_PAYLOAD_BUILDER_REJECTIONS,
+                    &[reason.as_ref().as_ref()],
+                );
+                self.record_builder_bid(
+                    &relay,
+                    builder_params,
+                    &bid,
+                    boosted_value,
+                    local_value,
+                    duration,
+                    BuilderBidDecision::Invalid {
+                        reason: reason.to_string(),
+                    },
+                );
+                warn!(
+                    self.log(),
+                    "Builder returned invalid payload";
+                    "relay" => relay.name(),
+                    "reason" => %reason,
+                    "relay_block_hash" => ?bid.data.message.header().block_hash(),
+                    "parent_hash" => ?parent_hash,
+                );
+                continue;
+            }
+
+            self.inner
+                .bid_relay_cache
+                .insert(bid.data.message.header().block_hash(), relay.clone());
+            valid_bids.push(SelectedBid {
+                relay,
+                bid,
+                duration,
+            });
+        }
+
+        if !any_response {
+            if let Some(e) = first_error {
+                return Err(e);
+            }
+        }
+
+        let Some(best_index) = valid_bids
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, selected)| *selected.bid.data.message.value())
+            .map(|(i, _)| i)
+        else {
+            return Ok(None);
+        };
+        let best = valid_bids.swap_remove(best_index);
+
+        for outbid in valid_bids {
+            self.record_builder_bid(
+                &outbid.relay,
+                builder_params,
+                &outbid.bid,
+                boost_relay_value(*outbid.bid.data.message.value(), builder_boost_factor),
+                local_value,
+                outbid.duration,
+                BuilderBidDecision::Outbid {
+                    winning_relay: best.relay.name().to_string(),
+                },
+            );
+        }
+        metrics::inc_counter_vec(
+            &metrics::EXECUTION_LAYER_BUILDER_RELAY_SELECTED_BIDS,
+            &[best.relay.name()],
+        );
+
+        Ok(Some(best))
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -980,7 +1322,8 @@ impl<E: EthSpec> ExecutionLayer<E> {
         builder_boost_factor: Option<u64>,
         spec: &ChainSpec,
     ) -> Result<ProvenancedPayload<BlockProposalContentsType<E>>, Error> {
-        let Some(builder) = self.builder() else {
+        let builders = self.builders();
+        if builders.is_empty() {
             // no builder.. return local payload
             return self
                 .get_full_payload_caching(
@@ -990,9 +1333,9 @@ impl<E: EthSpec> ExecutionLayer<E> {
                     current_fork,
                 )
                 .await
-                .and_then(GetPayloadResponseType::try_into)
+                .and_then(BlockProposalContentsType::try_from)
                 .map(ProvenancedPayload::Local);
-        };
+        }
 
         // check chain health
         if builder_params.chain_health != ChainHealth::Healthy {
@@ -1002,7 +1345,8 @@ impl<E: EthSpec> ExecutionLayer<E> {
                     self.log(),
                     "Chain is unhealthy, using local payload";
                     "info" => "this helps protect the network. the --builder-fallback flags \
-                        can adjust the expected health conditions.",
+                        can adjust the expected health conditions, and the current conditions \
+                        are available at /lighthouse/builder/circuit_breaker",
                     "failed_condition" => ?condition
                 ),
                 // Intentional no-op, so we never attempt builder API proposals pre-merge.
@@ -1026,13 +1370,13 @@ impl<E: EthSpec> ExecutionLayer<E> {
                     current_fork,
                 )
                 .await
-                .and_then(GetPayloadResponseType::try_into)
+                .and_then(BlockProposalContentsType::try_from)
                 .map(ProvenancedPayload::Local);
         }
 
-        let (relay_result, local_result) = self
+        let (relay_responses, local_result) = self
             .fetch_builder_and_local_payloads(
-                builder.as_ref(),
+                &builders,
                 parent_hash,
                 &builder_params,
                 payload_attributes,
@@ -1041,14 +1385,25 @@ impl<E: EthSpec> ExecutionLayer<E> {
             )
             .await;
 
+        let relay_result = self.select_builder_bid(
+            relay_responses,
+            parent_hash,
+            payload_attributes,
+            &builder_params,
+            builder_boost_factor,
+            local_result.as_ref().ok().map(|local| &local.response),
+            current_fork,
+            spec,
+        );
+
         match (relay_result, local_result) {
             (Err(e), Ok(local)) => {
                 warn!(
                     self.log(),
-                    "Builder error when requesting payload";
+                    "All builders failed when requesting payload";
                     "info" => "falling back to local execution client",
                     "relay_error" => ?e,
-                    "local_block_hash" => ?local.block_hash(),
+                    "local_block_hash" => ?local.response.block_hash(),
                     "parent_hash" => ?parent_hash,
                 );
                 Ok(ProvenancedPayload::Local(BlockProposalContentsType::Full(
@@ -1058,9 +1413,9 @@ impl<E: EthSpec> ExecutionLayer<E> {
             (Ok(None), Ok(local)) => {
                 info!(
                     self.log(),
-                    "Builder did not return a payload";
+                    "Builders did not return a valid payload";
                     "info" => "falling back to local execution client",
-                    "local_block_hash" => ?local.block_hash(),
+                    "local_block_hash" => ?local.response.block_hash(),
                     "parent_hash" => ?parent_hash,
                 );
                 Ok(ProvenancedPayload::Local(BlockProposalContentsType::Full(
@@ -1091,56 +1446,36 @@ impl<E: EthSpec> ExecutionLayer<E> {
 
                 Err(Error::CannotProduceHeader)
             }
-            (Ok(Some(relay)), Ok(local)) => {
+            (Ok(Some(selected)), Ok(local)) => {
+                let relay = selected.bid;
                 let header = &relay.data.message.header();
 
                 info!(
                     self.log(),
                     "Received local and builder payloads";
+                    "relay" => selected.relay.name(),
                     "relay_block_hash" => ?header.block_hash(),
-                    "local_block_hash" => ?local.block_hash(),
+                    "local_block_hash" => ?local.response.block_hash(),
                     "parent_hash" => ?parent_hash,
                 );
 
-                // check relay payload validity
-                if let Err(reason) = verify_builder_bid(
-                    &relay,
-                    parent_hash,
-                    payload_attributes,
-                    Some(local.block_number()),
-                    current_fork,
-                    spec,
-                ) {
-                    // relay payload invalid -> return local
-                    metrics::inc_counter_vec(
-                        &metrics::EXECUTION_LAYER_GET_PAYLOAD_BUILDER_REJECTIONS,
-                        &[reason.as_ref().as_ref()],
-                    );
-                    warn!(
-                        self.log(),
-                        "Builder returned invalid payload";
-                        "info" => "using local payload",
-                        "reason" => %reason,
-                        "relay_block_hash" => ?header.block_hash(),
-                        "parent_hash" => ?parent_hash,
-                    );
-                    return Ok(ProvenancedPayload::Local(BlockProposalContentsType::Full(
-                        local.try_into()?,
-                    )));
-                }
-
                 let relay_value = *relay.data.message.value();
-
-                let boosted_relay_value = match builder_boost_factor {
-                    Some(builder_boost_factor) => {
-                        (relay_value / 100).saturating_mul(builder_boost_factor.into())
-                    }
-                    None => relay_value,
+                let boosted_relay_value = boost_relay_value(relay_value, builder_boost_factor);
+                let local_value = *local.response.block_value();
+                let record_bid = |decision| {
+                    self.record_builder_bid(
+                        &selected.relay,
+                        &builder_params,
+                        &relay,
+                        boosted_relay_value,
+                        Some(local_value),
+                        selected.duration,
+                        decision,
+                    )
                 };
 
-                let local_value = *local.block_value();
-
                 if local_value >= boosted_relay_value {
+                    record_bid(BuilderBidDecision::LocalMoreProfitable);
                     info!(
                         self.log(),
                         "Local block is more profitable than relay block";
@@ -1154,7 +1489,8 @@ impl<E: EthSpec> ExecutionLayer<E> {
                     )));
                 }
 
-                if local.should_override_builder().unwrap_or(false) {
+                if local.response.should_override_builder().unwrap_or(false) {
+                    record_bid(BuilderBidDecision::LocalOverride);
                     info!(
                         self.log(),
                         "Using local payload because execution engine suggested we ignore builder payload";
@@ -1166,6 +1502,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
                     )));
                 }
 
+                record_bid(BuilderBidDecision::Accepted);
                 info!(
                     self.log(),
                     "Relay block is more profitable than local block";
@@ -1177,46 +1514,63 @@ impl<E: EthSpec> ExecutionLayer<E> {
 
                 Ok(ProvenancedPayload::try_from(relay.data.message)?)
             }
-            (Ok(Some(relay)), Err(local_error)) => {
+            (Ok(Some(selected)), Err(local_error)) => {
+                let relay = selected.bid;
                 let header = &relay.data.message.header();
 
                 info!(
                     self.log(),
                     "Received builder payload with local error";
+                    "relay" => selected.relay.name(),
                     "relay_block_hash" => ?header.block_hash(),
                     "local_error" => ?local_error,
                     "parent_hash" => ?parent_hash,
                 );
 
-                match verify_builder_bid(
+                let boosted_relay_value =
+                    boost_relay_value(*relay.data.message.value(), builder_boost_factor);
+                self.record_builder_bid(
+                    &selected.relay,
+                    &builder_params,
                     &relay,
-                    parent_hash,
-                    payload_attributes,
+                    boosted_relay_value,
                     None,
-                    current_fork,
-                    spec,
-                ) {
-                    Ok(()) => Ok(ProvenancedPayload::try_from(relay.data.message)?),
-                    Err(reason) => {
-                        metrics::inc_counter_vec(
-                            &metrics::EXECUTION_LAYER_GET_PAYLOAD_BUILDER_REJECTIONS,
-                            &[reason.as_ref().as_ref()],
-                        );
-                        crit!(
-                            self.log(),
-                            "Builder returned invalid payload";
-                            "info" => "no local payload either - unable to propose block",
-                            "reason" => %reason,
-                            "relay_block_hash" => ?header.block_hash(),
-                            "parent_hash" => ?parent_hash,
-                        );
-                        Err(Error::CannotProduceHeader)
-                    }
-                }
+                    selected.duration,
+                    BuilderBidDecision::Accepted,
+                );
+                Ok(ProvenancedPayload::try_from(relay.data.message)?)
             }
         }
     }
 
+    /// Add a bid received from `builder` to the bid audit log.
+    #[allow(clippy::too_many_arguments)]
+    fn record_builder_bid(
+        &self,
+        builder: &Relay,
+        builder_params: &BuilderParams,
+        relay: &ForkVersionedResponse<SignedBuilderBid<E>>,
+        boosted_value: Uint256,
+        local_value: Option<Uint256>,
+        relay_duration: Duration,
+        decision: BuilderBidDecision,
+    ) {
+        let header = relay.data.message.header();
+        self.inner.bid_audit_log.record(BuilderBidAudit {
+            slot: builder_params.slot,
+            relay: builder.name().to_string(),
+            proposer_pubkey: builder_params.pubkey,
+            parent_hash: header.parent_hash(),
+            block_hash: header.block_hash(),
+            fee_recipient: header.fee_recipient(),
+            value: *relay.data.message.value(),
+            boosted_value,
+            local_value,
+            relay_response_ms: relay_duration.as_millis() as u64,
+            decision,
+        });
+    }
+
     /// Get a full payload and cache its result in the execution layer's payload cache.
     async fn get_full_payload_caching(
         &self,
@@ -1224,7 +1578,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
         payload_attributes: &PayloadAttributes,
         forkchoice_update_params: ForkchoiceUpdateParameters,
         current_fork: ForkName,
-    ) -> Result<GetPayloadResponseType<E>, Error> {
+    ) -> Result<LocalPayload<E>, Error> {
         self.get_full_payload_with(
             parent_hash,
             payload_attributes,
@@ -1244,8 +1598,9 @@ impl<E: EthSpec> ExecutionLayer<E> {
         cache_fn: fn(
             &ExecutionLayer<E>,
             PayloadContentsRefTuple<E>,
+            Hash256,
         ) -> Option<FullPayloadContents<E>>,
-    ) -> Result<GetPayloadResponseType<E>, Error> {
+    ) -> Result<LocalPayload<E>, Error> {
         self.engine()
             .request(move |engine| async move {
                 let payload_id = if let Some(id) = engine
@@ -1327,7 +1682,15 @@ impl<E: EthSpec> ExecutionLayer<E> {
                         "suggested_fee_recipient" => ?payload_attributes.suggested_fee_recipient(),
                     );
                 }
-                if cache_fn(self, (payload_response.execution_payload_ref(), payload_response.blobs_bundle().ok())).is_some() {
+                // The root of a payload is equal to the root of its header.
+                let payload_header =
+                    ExecutionPayloadHeader::from(payload_response.execution_payload_ref());
+                let payload_root = payload_header.tree_hash_root();
+                let payload_and_blobs = (
+                    payload_response.execution_payload_ref(),
+                    payload_response.blobs_bundle().ok(),
+                );
+                if cache_fn(self, payload_and_blobs, payload_root).is_some() {
                     warn!(
                         self.log(),
                         "Duplicate payload cached, this might indicate redundant proposal \
@@ -1335,7 +1698,10 @@ impl<E: EthSpec> ExecutionLayer<E> {
                     );
                 }
 
-                Ok(GetPayloadResponseType::Full(payload_response))
+                Ok(LocalPayload {
+                    response: payload_response,
+                    header: payload_header,
+                })
             })
             .await
             .map_err(Box::new)
@@ -2048,59 +2414,222 @@ impl<E: EthSpec> ExecutionLayer<E> {
             "root" => ?block_root,
         );
 
-        if let Some(builder) = self.builder() {
-            let (payload_result, duration) =
-                timed_future(metrics::POST_BLINDED_PAYLOAD_BUILDER, async {
-                    builder
-                        .post_builder_blinded_blocks(block)
-                        .await
-                        .map_err(Error::Builder)
-                        .map(|d| d.data)
-                })
-                .await;
+        let builders = self.builders();
+        if builders.is_empty() {
+            return Err(Error::NoPayloadBuilder);
+        }
 
-            match &payload_result {
-                Ok(unblinded_response) => {
-                    metrics::inc_counter_vec(
-                        &metrics::EXECUTION_LAYER_BUILDER_REVEAL_PAYLOAD_OUTCOME,
-                        &[metrics::SUCCESS],
-                    );
-                    let payload = unblinded_response.payload_ref();
-                    info!(
-                        self.log(),
-                        "Builder successfully revealed payload";
-                        "relay_response_ms" => duration.as_millis(),
-                        "block_root" => ?block_root,
-                        "fee_recipient" => ?payload.fee_recipient(),
-                        "block_hash" => ?payload.block_hash(),
-                        "parent_hash" => ?payload.parent_hash()
-                    )
-                }
-                Err(e) => {
-                    metrics::inc_counter_vec(
-                        &metrics::EXECUTION_LAYER_BUILDER_REVEAL_PAYLOAD_OUTCOME,
-                        &[metrics::FAILURE],
-                    );
-                    warn!(
-                        self.log(),
-                        "Builder failed to reveal payload";
-                        "info" => "this is common behaviour for some builders and may not indicate an issue",
-                        "error" => ?e,
-                        "relay_response_ms" => duration.as_millis(),
-                        "block_root" => ?block_root,
-                        "parent_hash" => ?block
-                            .message()
-                            .execution_payload()
-                            .map(|payload| format!("{}", payload.parent_hash()))
-                            .unwrap_or_else(|_| "unknown".to_string())
-                    )
+        // Only the relays which offered this payload are able to reveal it. If the relays are
+        // unknown (e.g. because the block was produced before a restart), try them all.
+        let relays = block
+            .message()
+            .execution_payload()
+            .ok()
+            .and_then(|payload| self.inner.bid_relay_cache.get(&payload.block_hash()))
+            .unwrap_or_else(|| builders.to_vec());
+
+        let (payload_result, duration) =
+            timed_future(metrics::POST_BLINDED_PAYLOAD_BUILDER, async {
+                select_ok(relays.iter().map(|relay| {
+                    Box::pin(async move {
+                        relay
+                            .client()
+                            .post_builder_blinded_blocks(block)
+                            .await
+                            .map(|d| (relay.name(), d.data))
+                            .map_err(|e| {
+                                debug!(
+                                    self.log(),
+                                    "Relay failed to reveal payload";
+                                    "relay" => relay.name(),
+                                    "error" => ?e,
+                                    "block_root" => ?block_root,
+                                );
+                                Error::Builder(e)
+                            })
+                    })
+                }))
+                .await
+                .map(|(response, _)| response)
+            })
+            .await;
+
+        match payload_result {
+            Ok((relay, unblinded_response)) => {
+                metrics::inc_counter_vec(
+                    &metrics::EXECUTION_LAYER_BUILDER_REVEAL_PAYLOAD_OUTCOME,
+                    &[metrics::SUCCESS],
+                );
+                let payload = unblinded_response.payload_ref();
+                info!(
+                    self.log(),
+                    "Builder successfully revealed payload";
+                    "relay" => relay,
+                    "relay_response_ms" => duration.as_millis(),
+                    "block_root" => ?block_root,
+                    "fee_recipient" => ?payload.fee_recipient(),
+                    "block_hash" => ?payload.block_hash(),
+                    "parent_hash" => ?payload.parent_hash()
+                );
+                Ok(unblinded_response)
+            }
+            Err(e) => {
+                metrics::inc_counter_vec(
+                    &metrics::EXECUTION_LAYER_BUILDER_REVEAL_PAYLOAD_OUTCOME,
+                    &[metrics::FAILURE],
+                );
+                warn!(
+                    self.log(),
+                    "Builder failed to reveal payload";
+                    "info" => "this is common behaviour for some builders and may not indicate an issue",
+                    "error" => ?e,
+                    "relays" => relays.len(),
+                    "relay_response_ms" => duration.as_millis(),
+                    "block_root" => ?block_root,
+                    "parent_hash" => ?block
+                        .message()
+                        .execution_payload()
+                        .map(|payload| format!("{}", payload.parent_hash()))
+                        .unwrap_or_else(|_| "unknown".to_string())
+                );
+                Err(e)
+            }
+        }
+    }
+
+    /// Submit validator registrations to all builder relays concurrently.
+    ///
+    /// Registrations are sent in batches, pausing between batches and retrying each failed batch
+    /// with exponential backoff. Registrations which a relay has already accepted are skipped.
+    ///
+    /// All batches are attempted even if one of them fails, in which case the error from the first
+    /// failed batch is returned.
+    pub async fn post_builder_validators(
+        &self,
+        registrations: &[SignedValidatorRegistrationData],
+    ) -> Result<(), Error> {
+        let builders = self.builders();
+        if builders.is_empty() {
+            return Err(Error::NoPayloadBuilder);
+        }
+
+        join_all(
+            builders
+                .iter()
+                .map(|relay| self.post_builder_validators_to_relay(relay, registrations)),
+        )
+        .await
+        .into_iter()
+        .collect()
+    }
+
+    async fn post_builder_validators_to_relay(
+        &self,
+        relay: &Relay,
+        registrations: &[SignedValidatorRegistrationData],
+    ) -> Result<(), Error> {
+        let builder = relay.client();
+        let cache = relay.registration_cache();
+
+        let pending = cache.filter_unchanged(registrations);
+        let unchanged = registrations.len().saturating_sub(pending.len());
+        // Only the registrations which are actually sent to the relay count as forwarded.
+        self.inner
+            .forwarded_registrations
+            .record(&pending, timestamp_now());
+        metrics::inc_counter_vec_by(
+            &metrics::EXECUTION_LAYER_BUILDER_REGISTRATIONS,
+            &[metrics::UNCHANGED],
+            unchanged as u64,
+        );
+
+        debug!(
+            self.log(),
+            "Submitting validator registrations to builder";
+            "relay" => relay.name(),
+            "count" => pending.len(),
+            "unchanged" => unchanged,
+            "batch_size" => self.inner.builder_registration_batch_size,
+        );
+
+        let mut first_error = None;
+        for (i, batch) in pending
+            .chunks(self.inner.builder_registration_batch_size)
+            .enumerate()
+        {
+            if i > 0 {
+                sleep(self.inner.builder_registration_batch_delay).await;
+            }
+
+            let mut attempt = 0;
+            loop {
+                match builder.post_builder_validators(batch).await {
+                    Ok(()) => {
+                        cache.mark_submitted(batch);
+                        metrics::inc_counter_vec_by(
+                            &metrics::EXECUTION_LAYER_BUILDER_REGISTRATIONS,
+                            &[metrics::SUCCESS],
+                            batch.len() as u64,
+                        );
+                        break;
+                    }
+                    Err(e) if attempt < REGISTRATION_MAX_RETRIES && is_retryable(&e) => {
+                        attempt += 1;
+                        let backoff = retry_backoff(attempt);
+                        debug!(
+                            self.log(),
+                            "Retrying validator registration batch";
+                            "relay" => relay.name(),
+                            "error" => ?e,
+                            "attempt" => attempt,
+                            "backoff_ms" => backoff.as_millis(),
+                            "count" => batch.len(),
+                        );
+                        sleep(backoff).await;
+                    }
+                    Err(e) => {
+                        metrics::inc_counter_vec_by(
+                            &metrics::EXECUTION_LAYER_BUILDER_REGISTRATIONS,
+                            &[metrics::FAILURE],
+                            batch.len() as u64,
+                        );
+                        warn!(
+                            self.log(),
+                            "Failed to submit validator registration batch";
+                            "relay" => relay.name(),
+                            "error" => ?e,
+                            "attempts" => attempt + 1,
+                            "count" => batch.len(),
+                        );
+                        first_error.get_or_insert(Error::Builder(e));
+                        break;
+                    }
                 }
             }
+        }
 
-            payload_result
-        } else {
-            Err(Error::NoPayloadBuilder)
+        first_error.map_or(Ok(()), Err)
+    }
+}
+
+/// Apply the builder boost factor (a percentage) to the value of a relay's bid.
+fn boost_relay_value(relay_value: Uint256, builder_boost_factor: Option<u64>) -> Uint256 {
+    match builder_boost_factor {
+        Some(builder_boost_factor) => {
+            (relay_value / 100).saturating_mul(builder_boost_factor.into())
         }
+        None => relay_value,
+    }
+}
+
+/// Returns `true` if a failed request to the builder is worth retrying.
+///
+/// Requests rejected by the builder as invalid will be rejected again, so only network errors,
+/// rate limiting and server errors are retried.
+fn is_retryable(error: &builder_client::Error) -> bool {
+    match error.status() {
+        Some(status) => status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS,
+        None => true,
     }
 }
 
@@ -2182,6 +2711,7 @@ fn verify_builder_bid<E: EthSpec>(
     bid: &ForkVersionedResponse<SignedBuilderBid<E>>,
     parent_hash: ExecutionBlockHash,
     payload_attributes: &PayloadAttributes,
+    expected_withdrawals_root: Option<Hash256>,
     block_number: Option<u64>,
     current_fork: ForkName,
     spec: &ChainSpec,
@@ -2199,11 +2729,6 @@ fn verify_builder_bid<E: EthSpec>(
         );
     }
 
-    let expected_withdrawals_root = payload_attributes
-        .withdrawals()
-        .ok()
-        .cloned()
-        .map(|withdrawals| Withdrawals::<E>::from(withdrawals).tree_hash_root());
     let payload_withdrawals_root = header.withdrawals_root().ok();
 
     if header.parent_hash() != parent_hash {
@@ -2255,7 +2780,6 @@ async fn timed_future<F: Future<Output = T>, T>(metric: &str, future: F) -> (T,
     (result, duration)
 }
 
-#[cfg(test)]
 /// Returns the duration since the unix epoch.
 fn timestamp_now() -> u64 {
     SystemTime::now()
@@ -2267,6 +2791,7 @@ fn timestamp_now() -> u64 {
 fn noop<E: EthSpec>(
     _: &ExecutionLayer<E>,
     _: PayloadContentsRefTuple<E>,
+    _: Hash256,
 ) -> Option<FullPayloadContents<E>> {
     None
 }
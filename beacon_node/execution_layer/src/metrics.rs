@@ -15,6 +15,9 @@ pub const LOCAL: &str = "local";
 pub const BUILDER: &str = "builder";
 pub const SUCCESS: &str = "success";
 pub const FAILURE: &str = "failure";
+pub const UNCHANGED: &str = "unchanged";
+pub const NO_BID: &str = "no_bid";
+pub const SKIPPED: &str = "skipped";
 
 lazy_static::lazy_static! {
     pub static ref EXECUTION_LAYER_PROPOSER_INSERTED: Result<IntCounter> = try_create_int_counter(
@@ -79,6 +82,33 @@ lazy_static::lazy_static! {
         "The reasons why a payload from a builder was rejected",
         &["reason"]
     );
+    pub static ref EXECUTION_LAYER_BUILDER_REGISTRATIONS: Result<IntCounterVec> = try_create_int_counter_vec(
+        "execution_layer_builder_registrations",
+        "Count of validator registrations sent to the builder, or skipped because they are unchanged",
+        &["outcome"]
+    );
+    pub static ref EXECUTION_LAYER_BUILDER_RELAY_HEADER_OUTCOME: Result<IntCounterVec> = try_create_int_counter_vec(
+        "execution_layer_builder_relay_header_outcome",
+        "The outcomes of header requests sent to each relay",
+        &["relay", "outcome"]
+    );
+    pub static ref EXECUTION_LAYER_BUILDER_RELAY_HEADER_TIMES: Result<HistogramVec> =
+        try_create_histogram_vec_with_buckets(
+        "execution_layer_builder_relay_header_times",
+        "Duration of header requests sent to each relay",
+        decimal_buckets(-2, 1),
+        &["relay"]
+    );
+    pub static ref EXECUTION_LAYER_BUILDER_RELAY_SKIPPED: Result<IntGaugeVec> = try_create_int_gauge_vec(
+        "execution_layer_builder_relay_skipped",
+        "Set to 1 whilst a relay is being skipped due to repeated failures",
+        &["relay"]
+    );
+    pub static ref EXECUTION_LAYER_BUILDER_RELAY_SELECTED_BIDS: Result<IntCounterVec> = try_create_int_counter_vec(
+        "execution_layer_builder_relay_selected_bids",
+        "Count of times each relay offered the most valuable valid bid",
+        &["relay"]
+    );
     pub static ref EXECUTION_LAYER_PAYLOAD_BIDS: Result<IntGaugeVec> = try_create_int_gauge_vec(
         "execution_layer_payload_bids",
         "The gwei bid value of payloads received by local EEs or builders. Only shows values up to i64::MAX.",
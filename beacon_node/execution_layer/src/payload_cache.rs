@@ -2,7 +2,6 @@ use eth2::types::FullPayloadContents;
 use lru::LruCache;
 use parking_lot::Mutex;
 use std::num::NonZeroUsize;
-use tree_hash::TreeHash;
 use types::non_zero_usize::new_non_zero_usize;
 use types::{EthSpec, Hash256};
 
@@ -25,8 +24,14 @@ impl<E: EthSpec> Default for PayloadCache<E> {
 }
 
 impl<E: EthSpec> PayloadCache<E> {
-    pub fn put(&self, payload: FullPayloadContents<E>) -> Option<FullPayloadContents<E>> {
-        let root = payload.payload_ref().tree_hash_root();
+    /// Cache `payload` under `root`, which must be the `tree_hash_root` of the payload.
+    ///
+    /// The root is taken from the caller, who will usually have already hashed the payload.
+    pub fn put(
+        &self,
+        root: Hash256,
+        payload: FullPayloadContents<E>,
+    ) -> Option<FullPayloadContents<E>> {
         self.payloads.lock().put(PayloadCacheId(root), payload)
     }
 
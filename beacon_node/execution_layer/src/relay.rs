@@ -0,0 +1,154 @@
+//! The builder relays that the execution layer is connected to.
+//!
+//! Each relay tracks its own reliability: a relay which fails `RELAY_FAILURE_THRESHOLD` header
+//! requests in a row is skipped for `RELAY_SKIP_DURATION`, after which it is given another
+//! chance. A single further failure will cause it to be skipped again, whilst a success restores
+//! it fully.
+use crate::builder_registration::RegistrationCache;
+use builder_client::BuilderHttpClient;
+use lru::LruCache;
+use parking_lot::Mutex;
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use types::non_zero_usize::new_non_zero_usize;
+use types::ExecutionBlockHash;
+
+/// The number of consecutive failures after which a relay is skipped.
+pub const RELAY_FAILURE_THRESHOLD: u32 = 5;
+
+/// The length of time for which a failing relay is skipped.
+pub const RELAY_SKIP_DURATION: Duration = Duration::from_secs(10 * 60);
+
+/// The number of block hashes for which the relays offering them are remembered.
+pub const BID_RELAY_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(16);
+
+pub struct Relay {
+    client: BuilderHttpClient,
+    /// Registrations which have already been accepted by this relay.
+    registration_cache: RegistrationCache,
+    reliability: Mutex<Reliability>,
+}
+
+#[derive(Default)]
+struct Reliability {
+    consecutive_failures: u32,
+    skip_until: Option<Instant>,
+}
+
+impl Relay {
+    pub fn new(client: BuilderHttpClient) -> Self {
+        Self {
+            client,
+            registration_cache: RegistrationCache::default(),
+            reliability: Mutex::new(Reliability::default()),
+        }
+    }
+
+    pub fn client(&self) -> &BuilderHttpClient {
+        &self.client
+    }
+
+    /// The redacted URL of the relay, suitable for logs and metrics.
+    pub fn name(&self) -> &str {
+        self.client.server().as_ref()
+    }
+
+    pub fn registration_cache(&self) -> &RegistrationCache {
+        &self.registration_cache
+    }
+
+    /// Returns `true` if the relay has failed too often recently and should not be queried.
+    pub fn is_skipped(&self) -> bool {
+        self.reliability
+            .lock()
+            .skip_until
+            .is_some_and(|skip_until| Instant::now() < skip_until)
+    }
+
+    pub fn record_success(&self) {
+        *self.reliability.lock() = Reliability::default();
+    }
+
+    /// Record a failed request, returning `true` if the relay will now be skipped.
+    pub fn record_failure(&self) -> bool {
+        let mut reliability = self.reliability.lock();
+        reliability.consecutive_failures = reliability.consecutive_failures.saturating_add(1);
+        if reliability.consecutive_failures >= RELAY_FAILURE_THRESHOLD {
+            reliability.skip_until = Some(Instant::now() + RELAY_SKIP_DURATION);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Remembers which relays offered a bid for each block hash, so that a blinded block can be sent
+/// to the relays which are able to reveal its payload.
+pub struct BidRelayCache {
+    relays: Mutex<LruCache<ExecutionBlockHash, Vec<Arc<Relay>>>>,
+}
+
+impl Default for BidRelayCache {
+    fn default() -> Self {
+        Self {
+            relays: Mutex::new(LruCache::new(BID_RELAY_CACHE_SIZE)),
+        }
+    }
+}
+
+impl BidRelayCache {
+    pub fn insert(&self, block_hash: ExecutionBlockHash, relay: Arc<Relay>) {
+        let mut relays = self.relays.lock();
+        if let Some(existing) = relays.get_mut(&block_hash) {
+            if !existing.iter().any(|other| Arc::ptr_eq(other, &relay)) {
+                existing.push(relay);
+            }
+        } else {
+            relays.put(block_hash, vec![relay]);
+        }
+    }
+
+    pub fn get(&self, block_hash: &ExecutionBlockHash) -> Option<Vec<Arc<Relay>>> {
+        self.relays.lock().get(block_hash).cloned()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use sensitive_url::SensitiveUrl;
+
+    fn relay() -> Relay {
+        let url = SensitiveUrl::parse("https://0xabcd@relay.example.com/path").unwrap();
+        Relay::new(BuilderHttpClient::new(url, None, None, false).unwrap())
+    }
+
+    #[test]
+    fn name_is_redacted() {
+        assert_eq!(relay().name(), "https://relay.example.com/");
+    }
+
+    #[test]
+    fn skipped_after_consecutive_failures() {
+        let relay = relay();
+
+        for _ in 1..RELAY_FAILURE_THRESHOLD {
+            assert!(!relay.record_failure());
+        }
+        assert!(!relay.is_skipped());
+
+        // A success resets the count.
+        relay.record_success();
+        for _ in 1..RELAY_FAILURE_THRESHOLD {
+            assert!(!relay.record_failure());
+        }
+        assert!(!relay.is_skipped());
+
+        assert!(relay.record_failure());
+        assert!(relay.is_skipped());
+
+        relay.record_success();
+        assert!(!relay.is_skipped());
+    }
+}
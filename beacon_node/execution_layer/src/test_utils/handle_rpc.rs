@@ -5,6 +5,7 @@ use crate::test_utils::{DEFAULT_CLIENT_VERSION, DEFAULT_MOCK_EL_PAYLOAD_VALUE_WE
 use serde::{de::DeserializeOwned, Deserialize};
 use serde_json::Value as JsonValue;
 use std::sync::Arc;
+use types::{ExecutionPayloadElectra, ExecutionRequests};
 
 pub const GENERIC_ERROR_CODE: i64 = -1234;
 pub const BAD_PARAMS_ERROR_CODE: i64 = -32602;
@@ -227,6 +228,18 @@ pub async fn handle_rpc<E: EthSpec>(
                 _ => unreachable!(),
             };
 
+            if let JsonExecutionPayload::V4(payload) = &request {
+                let execution_requests = get_param::<JsonExecutionRequests<E>>(params, 3)
+                    .map_err(|s| (s, BAD_PARAMS_ERROR_CODE))?;
+                let payload = ExecutionPayloadElectra::from(payload.clone());
+                if execution_requests.0 != ExecutionRequests::from(&payload) {
+                    return Err((
+                        format!("{} called with mismatched `executionRequests`", method),
+                        BAD_PARAMS_ERROR_CODE,
+                    ));
+                }
+            }
+
             // Canned responses set by block hash take priority.
             if let Some(status) = ctx.get_new_payload_status(request.block_hash()) {
                 return status
@@ -234,6 +247,19 @@ pub async fn handle_rpc<E: EthSpec>(
                     .map_err(|message| (message, GENERIC_ERROR_CODE));
             }
 
+            let inject_invalid_status = {
+                let fault_injection = ctx.fault_injection.read();
+                rand::random::<f64>() < fault_injection.invalid_status_probability
+            };
+            if inject_invalid_status {
+                let status = PayloadStatusV1 {
+                    status: PayloadStatusV1Status::Invalid,
+                    latest_valid_hash: Some(*request.parent_hash()),
+                    validation_error: Some("injected fault".to_string()),
+                };
+                return Ok(serde_json::to_value(JsonPayloadStatusV1::from(status)).unwrap());
+            }
+
             let (static_response, should_import) =
                 if let Some(mut response) = ctx.static_new_payload_response.lock().clone() {
                     if response.status.status == PayloadStatusV1Status::Valid {
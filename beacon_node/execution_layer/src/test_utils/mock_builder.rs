@@ -1,10 +1,12 @@
 use crate::test_utils::{DEFAULT_BUILDER_PAYLOAD_VALUE_WEI, DEFAULT_JWT_SECRET};
 use crate::{Config, ExecutionLayer, PayloadAttributes};
-use eth2::types::{BlobsBundle, BlockId, StateId, ValidatorId};
-use eth2::{BeaconNodeHttpClient, Timeouts, CONSENSUS_VERSION_HEADER};
+use bytes::Bytes;
+use eth2::types::{Accept, BlobsBundle, BlockId, StateId, ValidatorId};
+use eth2::{BeaconNodeHttpClient, Timeouts, CONSENSUS_VERSION_HEADER, SSZ_CONTENT_TYPE_HEADER};
 use fork_choice::ForkchoiceUpdateParameters;
 use parking_lot::RwLock;
 use sensitive_url::SensitiveUrl;
+use ssz::Encode;
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::future::Future;
@@ -20,9 +22,9 @@ use types::builder_bid::{
 };
 use types::{
     Address, BeaconState, ChainSpec, EthSpec, ExecPayload, ExecutionPayload,
-    ExecutionPayloadHeaderRefMut, ForkName, ForkVersionedResponse, Hash256, PublicKeyBytes,
-    Signature, SignedBlindedBeaconBlock, SignedRoot, SignedValidatorRegistrationData, Slot,
-    Uint256,
+    ExecutionPayloadHeaderRefMut, ForkName, ForkVersionDecode, ForkVersionedResponse, Hash256,
+    PublicKeyBytes, Signature, SignedBlindedBeaconBlock, SignedRoot,
+    SignedValidatorRegistrationData, Slot, Uint256,
 };
 use types::{ExecutionBlockHash, SecretKey};
 use warp::{Filter, Rejection};
@@ -321,57 +323,79 @@ pub fn serve<E: EthSpec>(
             },
         );
 
-    let blinded_block =
-        prefix
-            .and(warp::path("blinded_blocks"))
-            .and(warp::body::json())
-            .and(warp::header::header::<ForkName>(CONSENSUS_VERSION_HEADER))
-            .and(warp::path::end())
-            .and(ctx_filter.clone())
-            .and_then(
-                |block: SignedBlindedBeaconBlock<E>,
-                 fork_name: ForkName,
-                 builder: MockBuilder<E>| async move {
-                    let root = match block {
-                        SignedBlindedBeaconBlock::Base(_) | types::SignedBeaconBlock::Altair(_) => {
-                            return Err(reject("invalid fork"));
-                        }
-                        SignedBlindedBeaconBlock::Bellatrix(block) => {
-                            block.message.body.execution_payload.tree_hash_root()
-                        }
-                        SignedBlindedBeaconBlock::Capella(block) => {
-                            block.message.body.execution_payload.tree_hash_root()
-                        }
-                        SignedBlindedBeaconBlock::Deneb(block) => {
-                            block.message.body.execution_payload.tree_hash_root()
-                        }
-                        SignedBlindedBeaconBlock::Electra(block) => {
-                            block.message.body.execution_payload.tree_hash_root()
-                        }
-                    };
-                    let payload = builder
-                        .el
-                        .get_payload_by_root(&root)
-                        .ok_or_else(|| reject("missing payload for tx root"))?;
-                    let resp: ForkVersionedResponse<_> = ForkVersionedResponse {
-                        version: Some(fork_name),
-                        metadata: Default::default(),
-                        data: payload,
-                    };
-
-                    let json_payload = serde_json::to_string(&resp)
-                        .map_err(|_| reject("coudn't serialize response"))?;
-                    Ok::<_, warp::reject::Rejection>(
+    let blinded_block = prefix
+        .and(warp::path("blinded_blocks"))
+        .and(warp::body::bytes())
+        .and(warp::header::optional::<String>("content-type"))
+        .and(warp::header::header::<ForkName>(CONSENSUS_VERSION_HEADER))
+        .and(warp::path::end())
+        .and(ctx_filter.clone())
+        .and_then(
+            |body: Bytes,
+             content_type: Option<String>,
+             fork_name: ForkName,
+             builder: MockBuilder<E>| async move {
+                let is_ssz = content_type
+                    .is_some_and(|content_type| content_type == SSZ_CONTENT_TYPE_HEADER);
+                let block = if is_ssz {
+                    SignedBlindedBeaconBlock::<E>::from_ssz_bytes_by_fork(&body, fork_name)
+                        .map_err(|_| reject("invalid SSZ block"))?
+                } else {
+                    serde_json::from_slice(&body).map_err(|_| reject("invalid JSON block"))?
+                };
+                let root = match block {
+                    SignedBlindedBeaconBlock::Base(_) | types::SignedBeaconBlock::Altair(_) => {
+                        return Err(reject("invalid fork"));
+                    }
+                    SignedBlindedBeaconBlock::Bellatrix(block) => {
+                        block.message.body.execution_payload.tree_hash_root()
+                    }
+                    SignedBlindedBeaconBlock::Capella(block) => {
+                        block.message.body.execution_payload.tree_hash_root()
+                    }
+                    SignedBlindedBeaconBlock::Deneb(block) => {
+                        block.message.body.execution_payload.tree_hash_root()
+                    }
+                    SignedBlindedBeaconBlock::Electra(block) => {
+                        block.message.body.execution_payload.tree_hash_root()
+                    }
+                };
+                let payload = builder
+                    .el
+                    .get_payload_by_root(&root)
+                    .ok_or_else(|| reject("missing payload for tx root"))?;
+
+                if is_ssz {
+                    return Ok::<_, warp::reject::Rejection>(
                         warp::http::Response::builder()
                             .status(200)
-                            .body(
-                                serde_json::to_string(&json_payload)
-                                    .map_err(|_| reject("invalid JSON"))?,
-                            )
+                            .header("content-type", SSZ_CONTENT_TYPE_HEADER)
+                            .header(CONSENSUS_VERSION_HEADER, fork_name.to_string())
+                            .body(payload.as_ssz_bytes())
                             .unwrap(),
-                    )
-                },
-            );
+                    );
+                }
+
+                let resp: ForkVersionedResponse<_> = ForkVersionedResponse {
+                    version: Some(fork_name),
+                    metadata: Default::default(),
+                    data: payload,
+                };
+
+                let json_payload = serde_json::to_string(&resp)
+                    .map_err(|_| reject("coudn't serialize response"))?;
+                Ok::<_, warp::reject::Rejection>(
+                    warp::http::Response::builder()
+                        .status(200)
+                        .body(
+                            serde_json::to_string(&json_payload)
+                                .map_err(|_| reject("invalid JSON"))?
+                                .into_bytes(),
+                        )
+                        .unwrap(),
+                )
+            },
+        );
 
     let status = prefix
         .and(warp::path("status"))
@@ -389,11 +413,13 @@ pub fn serve<E: EthSpec>(
                 .or_else(|_| async { Err(reject("Invalid pubkey")) }),
         )
         .and(warp::path::end())
+        .and(warp::header::optional::<Accept>("accept"))
         .and(ctx_filter.clone())
         .and_then(
             |slot: Slot,
              parent_hash: ExecutionBlockHash,
              pubkey: PublicKeyBytes,
+             accept: Option<Accept>,
              builder: MockBuilder<E>| async move {
                 let fork = builder.spec.fork_name_at_slot::<E>(slot);
                 let signed_cached_data = builder
@@ -527,7 +553,7 @@ pub fn serve<E: EthSpec>(
                     finalized_hash: Some(finalized_execution_hash),
                 };
 
-                let payload_response_type = builder
+                let local_payload = builder
                     .el
                     .get_full_payload_caching(
                         head_execution_hash,
@@ -538,108 +564,52 @@ pub fn serve<E: EthSpec>(
                     .await
                     .map_err(|_| reject("couldn't get payload"))?;
 
-                let mut message = match payload_response_type {
-                    crate::GetPayloadResponseType::Full(payload_response) => {
-                        let (payload, _block_value, maybe_blobs_bundle): (
-                            ExecutionPayload<E>,
-                            Uint256,
-                            Option<BlobsBundle<E>>,
-                        ) = payload_response.into();
-
-                        match fork {
-                            ForkName::Electra => BuilderBid::Electra(BuilderBidElectra {
-                                header: payload
-                                    .as_electra()
-                                    .map_err(|_| reject("incorrect payload variant"))?
-                                    .into(),
-                                blob_kzg_commitments: maybe_blobs_bundle
-                                    .map(|b| b.commitments)
-                                    .unwrap_or_default(),
-                                value: Uint256::from(DEFAULT_BUILDER_PAYLOAD_VALUE_WEI),
-                                pubkey: builder.builder_sk.public_key().compress(),
-                            }),
-                            ForkName::Deneb => BuilderBid::Deneb(BuilderBidDeneb {
-                                header: payload
-                                    .as_deneb()
-                                    .map_err(|_| reject("incorrect payload variant"))?
-                                    .into(),
-                                blob_kzg_commitments: maybe_blobs_bundle
-                                    .map(|b| b.commitments)
-                                    .unwrap_or_default(),
-                                value: Uint256::from(DEFAULT_BUILDER_PAYLOAD_VALUE_WEI),
-                                pubkey: builder.builder_sk.public_key().compress(),
-                            }),
-                            ForkName::Capella => BuilderBid::Capella(BuilderBidCapella {
-                                header: payload
-                                    .as_capella()
-                                    .map_err(|_| reject("incorrect payload variant"))?
-                                    .into(),
-                                value: Uint256::from(DEFAULT_BUILDER_PAYLOAD_VALUE_WEI),
-                                pubkey: builder.builder_sk.public_key().compress(),
-                            }),
-                            ForkName::Bellatrix => BuilderBid::Bellatrix(BuilderBidBellatrix {
-                                header: payload
-                                    .as_bellatrix()
-                                    .map_err(|_| reject("incorrect payload variant"))?
-                                    .into(),
-                                value: Uint256::from(DEFAULT_BUILDER_PAYLOAD_VALUE_WEI),
-                                pubkey: builder.builder_sk.public_key().compress(),
-                            }),
-                            ForkName::Base | ForkName::Altair => {
-                                return Err(reject("invalid fork"))
-                            }
-                        }
-                    }
-                    crate::GetPayloadResponseType::Blinded(payload_response) => {
-                        let (payload, _block_value, maybe_blobs_bundle): (
-                            ExecutionPayload<E>,
-                            Uint256,
-                            Option<BlobsBundle<E>>,
-                        ) = payload_response.into();
-                        match fork {
-                            ForkName::Electra => BuilderBid::Electra(BuilderBidElectra {
-                                header: payload
-                                    .as_electra()
-                                    .map_err(|_| reject("incorrect payload variant"))?
-                                    .into(),
-                                blob_kzg_commitments: maybe_blobs_bundle
-                                    .map(|b| b.commitments)
-                                    .unwrap_or_default(),
-                                value: Uint256::from(DEFAULT_BUILDER_PAYLOAD_VALUE_WEI),
-                                pubkey: builder.builder_sk.public_key().compress(),
-                            }),
-                            ForkName::Deneb => BuilderBid::Deneb(BuilderBidDeneb {
-                                header: payload
-                                    .as_deneb()
-                                    .map_err(|_| reject("incorrect payload variant"))?
-                                    .into(),
-                                blob_kzg_commitments: maybe_blobs_bundle
-                                    .map(|b| b.commitments)
-                                    .unwrap_or_default(),
-                                value: Uint256::from(DEFAULT_BUILDER_PAYLOAD_VALUE_WEI),
-                                pubkey: builder.builder_sk.public_key().compress(),
-                            }),
-                            ForkName::Capella => BuilderBid::Capella(BuilderBidCapella {
-                                header: payload
-                                    .as_capella()
-                                    .map_err(|_| reject("incorrect payload variant"))?
-                                    .into(),
-                                value: Uint256::from(DEFAULT_BUILDER_PAYLOAD_VALUE_WEI),
-                                pubkey: builder.builder_sk.public_key().compress(),
-                            }),
-                            ForkName::Bellatrix => BuilderBid::Bellatrix(BuilderBidBellatrix {
-                                header: payload
-                                    .as_bellatrix()
-                                    .map_err(|_| reject("incorrect payload variant"))?
-                                    .into(),
-                                value: Uint256::from(DEFAULT_BUILDER_PAYLOAD_VALUE_WEI),
-                                pubkey: builder.builder_sk.public_key().compress(),
-                            }),
-                            ForkName::Base | ForkName::Altair => {
-                                return Err(reject("invalid fork"))
-                            }
-                        }
-                    }
+                let (payload, _block_value, maybe_blobs_bundle): (
+                    ExecutionPayload<E>,
+                    Uint256,
+                    Option<BlobsBundle<E>>,
+                ) = local_payload.response.into();
+
+                let mut message = match fork {
+                    ForkName::Electra => BuilderBid::Electra(BuilderBidElectra {
+                        header: payload
+                            .as_electra()
+                            .map_err(|_| reject("incorrect payload variant"))?
+                            .into(),
+                        blob_kzg_commitments: maybe_blobs_bundle
+                            .map(|b| b.commitments)
+                            .unwrap_or_default(),
+                        value: Uint256::from(DEFAULT_BUILDER_PAYLOAD_VALUE_WEI),
+                        pubkey: builder.builder_sk.public_key().compress(),
+                    }),
+                    ForkName::Deneb => BuilderBid::Deneb(BuilderBidDeneb {
+                        header: payload
+                            .as_deneb()
+                            .map_err(|_| reject("incorrect payload variant"))?
+                            .into(),
+                        blob_kzg_commitments: maybe_blobs_bundle
+                            .map(|b| b.commitments)
+                            .unwrap_or_default(),
+                        value: Uint256::from(DEFAULT_BUILDER_PAYLOAD_VALUE_WEI),
+                        pubkey: builder.builder_sk.public_key().compress(),
+                    }),
+                    ForkName::Capella => BuilderBid::Capella(BuilderBidCapella {
+                        header: payload
+                            .as_capella()
+                            .map_err(|_| reject("incorrect payload variant"))?
+                            .into(),
+                        value: Uint256::from(DEFAULT_BUILDER_PAYLOAD_VALUE_WEI),
+                        pubkey: builder.builder_sk.public_key().compress(),
+                    }),
+                    ForkName::Bellatrix => BuilderBid::Bellatrix(BuilderBidBellatrix {
+                        header: payload
+                            .as_bellatrix()
+                            .map_err(|_| reject("incorrect payload variant"))?
+                            .into(),
+                        value: Uint256::from(DEFAULT_BUILDER_PAYLOAD_VALUE_WEI),
+                        pubkey: builder.builder_sk.public_key().compress(),
+                    }),
+                    ForkName::Base | ForkName::Altair => return Err(reject("invalid fork")),
                 };
 
                 message.set_gas_limit(cached_data.gas_limit);
@@ -657,6 +627,18 @@ pub fn serve<E: EthSpec>(
                     .spec
                     .fork_name_at_epoch(slot.epoch(E::slots_per_epoch()));
                 let signed_bid = SignedBuilderBid { message, signature };
+
+                if accept == Some(Accept::Ssz) {
+                    return Ok::<_, Rejection>(
+                        warp::http::Response::builder()
+                            .status(200)
+                            .header("content-type", SSZ_CONTENT_TYPE_HEADER)
+                            .header(CONSENSUS_VERSION_HEADER, fork_name.to_string())
+                            .body(signed_bid.as_ssz_bytes())
+                            .unwrap(),
+                    );
+                }
+
                 let resp: ForkVersionedResponse<_> = ForkVersionedResponse {
                     version: Some(fork_name),
                     metadata: Default::default(),
@@ -667,7 +649,7 @@ pub fn serve<E: EthSpec>(
                 Ok::<_, Rejection>(
                     warp::http::Response::builder()
                         .status(200)
-                        .body(json_bid)
+                        .body(json_bid.into_bytes())
                         .unwrap(),
                 )
             },
@@ -8,7 +8,7 @@ use crate::json_structures::JsonClientVersionV1;
 use bytes::Bytes;
 use environment::null_logger;
 use execution_block_generator::PoWBlock;
-use handle_rpc::handle_rpc;
+use handle_rpc::{handle_rpc, GENERIC_ERROR_CODE};
 use kzg::Kzg;
 use lazy_static::lazy_static;
 use parking_lot::{Mutex, RwLock, RwLockWriteGuard};
@@ -21,6 +21,7 @@ use std::future::Future;
 use std::marker::PhantomData;
 use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::{runtime, sync::oneshot};
 use types::{EthSpec, ExecutionBlockHash, Uint256};
 use warp::{http::StatusCode, Filter, Rejection};
@@ -82,6 +83,7 @@ pub struct MockExecutionConfig {
     pub shanghai_time: Option<u64>,
     pub cancun_time: Option<u64>,
     pub prague_time: Option<u64>,
+    pub fault_injection: FaultInjection,
 }
 
 impl Default for MockExecutionConfig {
@@ -95,10 +97,35 @@ impl Default for MockExecutionConfig {
             shanghai_time: None,
             cancun_time: None,
             prague_time: None,
+            fault_injection: FaultInjection::default(),
         }
     }
 }
 
+/// Artificial latency, errors and invalid payload statuses to apply to JSON-RPC requests, for
+/// testing how consumers of the engine API cope with a slow or unreliable execution node.
+#[derive(Clone, Debug, Default)]
+pub struct FaultInjection {
+    /// Delay applied before responding to every request.
+    pub response_delay: Duration,
+    /// Delays for specific methods (e.g. `engine_newPayloadV3`), overriding `response_delay`.
+    pub method_delays: HashMap<String, Duration>,
+    /// The probability, between 0 and 1, that a request will fail with a JSON-RPC error.
+    pub error_probability: f64,
+    /// The probability, between 0 and 1, that a payload passed to `engine_newPayload` will be
+    /// reported as `INVALID` without being imported.
+    pub invalid_status_probability: f64,
+}
+
+impl FaultInjection {
+    fn delay(&self, method: Option<&str>) -> Duration {
+        method
+            .and_then(|method| self.method_delays.get(method))
+            .copied()
+            .unwrap_or(self.response_delay)
+    }
+}
+
 pub struct MockServer<E: EthSpec> {
     _shutdown_tx: oneshot::Sender<()>,
     listen_socket_addr: SocketAddr,
@@ -135,6 +162,7 @@ impl<E: EthSpec> MockServer<E> {
             shanghai_time,
             cancun_time,
             prague_time,
+            fault_injection,
         } = config;
         let last_echo_request = Arc::new(RwLock::new(None));
         let preloaded_responses = Arc::new(Mutex::new(vec![]));
@@ -164,6 +192,7 @@ impl<E: EthSpec> MockServer<E> {
             fcu_payload_statuses: <_>::default(),
             syncing_response: Arc::new(Mutex::new(Ok(false))),
             engine_capabilities: Arc::new(RwLock::new(DEFAULT_ENGINE_CAPABILITIES)),
+            fault_injection: Arc::new(RwLock::new(fault_injection)),
             _phantom: PhantomData,
         });
 
@@ -198,6 +227,10 @@ impl<E: EthSpec> MockServer<E> {
         *self.ctx.engine_capabilities.write() = engine_capabilities;
     }
 
+    pub fn set_fault_injection(&self, fault_injection: FaultInjection) {
+        *self.ctx.fault_injection.write() = fault_injection;
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         handle: &runtime::Handle,
@@ -221,6 +254,7 @@ impl<E: EthSpec> MockServer<E> {
                 shanghai_time,
                 cancun_time,
                 prague_time,
+                fault_injection: FaultInjection::default(),
             },
             kzg,
         )
@@ -540,6 +574,7 @@ pub struct Context<E: EthSpec> {
     pub syncing_response: Arc<Mutex<Result<bool, String>>>,
 
     pub engine_capabilities: Arc<RwLock<EngineCapabilities>>,
+    pub fault_injection: Arc<RwLock<FaultInjection>>,
     pub _phantom: PhantomData<E>,
 }
 
@@ -685,7 +720,25 @@ pub fn serve<E: EthSpec>(
             let response = if let Some(preloaded_response) = preloaded_response {
                 preloaded_response
             } else {
-                match handle_rpc(body, ctx).await {
+                let (delay, inject_error) = {
+                    let fault_injection = ctx.fault_injection.read();
+                    let method = body.get("method").and_then(serde_json::Value::as_str);
+                    (
+                        fault_injection.delay(method),
+                        rand::random::<f64>() < fault_injection.error_probability,
+                    )
+                };
+                if !delay.is_zero() {
+                    tokio::time::sleep(delay).await;
+                }
+
+                let result = if inject_error {
+                    Err(("injected fault".to_string(), GENERIC_ERROR_CODE))
+                } else {
+                    handle_rpc(body, ctx).await
+                };
+
+                match result {
                     Ok(result) => json!({
                         "id": id,
                         "jsonrpc": JSONRPC_VERSION,
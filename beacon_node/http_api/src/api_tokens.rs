@@ -0,0 +1,293 @@
+//! Optional bearer token authentication of the HTTP API.
+//!
+//! Each token grants a single scope, and each scope includes the routes of the scopes below it:
+//!
+//! - `read-only`: queries of the chain and the node, such as `beacon/states` and `node/syncing`.
+//! - `validator`: the routes used by validator clients to perform their duties, such as the
+//!   `validator` routes and publishing blocks and pool operations.
+//! - `admin`: the `debug` and `lighthouse` routes, except the health probes of
+//!   `lighthouse/health/subsystems` and the WebSocket events stream of `lighthouse/events`.
+//!
+//! This lets operators give validator clients access to their duties without exposing the
+//! expensive or sensitive debug and admin routes.
+
+use crate::rate_limit::unversioned_segments;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use warp::http::Method;
+
+/// The access granted by an API token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ApiScope {
+    ReadOnly,
+    Validator,
+    Admin,
+}
+
+impl FromStr for ApiScope {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "read-only" => Ok(Self::ReadOnly),
+            "validator" => Ok(Self::Validator),
+            "admin" => Ok(Self::Admin),
+            other => Err(format!(
+                "unknown scope {other:?}, expected read-only, validator or admin"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ApiScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ReadOnly => write!(f, "read-only"),
+            Self::Validator => write!(f, "validator"),
+            Self::Admin => write!(f, "admin"),
+        }
+    }
+}
+
+impl ApiScope {
+    /// Returns the scope required to make a `method` request to `path`.
+    pub fn required_for(method: &Method, path: &str) -> Self {
+        let segments = unversioned_segments(path);
+        let segments = segments.iter().map(String::as_str).collect::<Vec<_>>();
+        match segments.as_slice() {
+            // Validator clients check this whilst waiting for genesis.
+            ["lighthouse", "staking", ..] => Self::ReadOnly,
+            // Probes of orchestrators such as Kubernetes shouldn't need an admin token.
+            ["lighthouse", "health", "subsystems"] => Self::ReadOnly,
+            // The same events as the read-only `events` route, over a WebSocket.
+            ["lighthouse", "events"] => Self::ReadOnly,
+            ["lighthouse", ..] | ["debug", ..] => Self::Admin,
+            ["validator", ..] => Self::Validator,
+            ["beacon", "blocks" | "blinded_blocks" | "pool", ..] if method == Method::POST => {
+                Self::Validator
+            }
+            _ => Self::ReadOnly,
+        }
+    }
+}
+
+/// The API tokens accepted by the server and the scope of each.
+#[derive(Debug)]
+pub struct ApiTokens {
+    tokens: Vec<(String, ApiScope)>,
+}
+
+impl ApiTokens {
+    /// Loads the tokens in the file at `path`.
+    ///
+    /// Each line of the file gives the scope and then the token, separated by whitespace. Blank
+    /// lines and lines starting with `#` are ignored.
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("unable to read API tokens file {path:?}: {e}"))?;
+        Self::parse(&contents).map_err(|e| format!("API tokens file {path:?}: {e}"))
+    }
+
+    fn parse(contents: &str) -> Result<Self, String> {
+        let mut tokens: Vec<(String, ApiScope)> = vec![];
+        for (i, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (scope, token) = line
+                .split_once(char::is_whitespace)
+                .ok_or_else(|| format!("line {} has no token", i + 1))?;
+            let scope = scope
+                .parse::<ApiScope>()
+                .map_err(|e| format!("line {}: {e}", i + 1))?;
+            let token = token.trim().to_string();
+            // A repeated token takes the scope of its last line.
+            tokens.retain(|(existing, _)| *existing != token);
+            tokens.push((token, scope));
+        }
+
+        if tokens.is_empty() {
+            return Err("contains no tokens".to_string());
+        }
+        Ok(Self { tokens })
+    }
+
+    /// Returns the scope of `token`, if it is known.
+    ///
+    /// Every known token is compared in full, so that the time taken doesn't reveal how much of a
+    /// guess matches a token.
+    fn scope_of(&self, token: &str) -> Option<ApiScope> {
+        self.tokens.iter().fold(None, |found, (known, scope)| {
+            if constant_time_eq(known.as_bytes(), token.as_bytes()) {
+                Some(*scope)
+            } else {
+                found
+            }
+        })
+    }
+
+    /// Checks that the `Authorization` header of a `method` request to `path` is a bearer token
+    /// with the required scope.
+    pub fn authorize(
+        &self,
+        authorization: Option<&str>,
+        method: &Method,
+        path: &str,
+    ) -> Result<(), warp::Rejection> {
+        let authorization = authorization.ok_or_else(|| {
+            warp_utils::reject::custom_unauthorized("missing Authorization header".to_string())
+        })?;
+        let scope = authorization
+            .strip_prefix("Bearer ")
+            .and_then(|token| self.scope_of(token.trim()))
+            .ok_or_else(|| warp_utils::reject::custom_forbidden("unknown API token".to_string()))?;
+
+        let required = ApiScope::required_for(method, path);
+        if scope >= required {
+            Ok(())
+        } else {
+            Err(warp_utils::reject::custom_forbidden(format!(
+                "API token has the {scope} scope but {required} is required"
+            )))
+        }
+    }
+}
+
+/// Compares `a` and `b` in a time which depends only on their lengths.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len()
+        && a.iter()
+            .zip(b)
+            .fold(0, |difference, (x, y)| difference | (x ^ y))
+            == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use warp_utils::reject::{CustomForbidden, CustomUnauthorized};
+
+    const TOKENS: &str = "
+        # Scope    Token
+        read-only  reader
+        validator  validator
+        admin      administrator
+    ";
+
+    fn status(tokens: &ApiTokens, authorization: Option<&str>, method: Method, path: &str) -> u16 {
+        let Err(rejection) = tokens.authorize(authorization, &method, path) else {
+            return 200;
+        };
+        if rejection.find::<CustomUnauthorized>().is_some() {
+            401
+        } else if rejection.find::<CustomForbidden>().is_some() {
+            403
+        } else {
+            panic!("unexpected rejection {rejection:?}")
+        }
+    }
+
+    #[test]
+    fn required_scopes() {
+        let cases = [
+            (Method::GET, "/eth/v1/node/syncing", ApiScope::ReadOnly),
+            (
+                Method::GET,
+                "/eth/v2/beacon/blocks/head",
+                ApiScope::ReadOnly,
+            ),
+            (Method::POST, "/eth/v2/beacon/blocks", ApiScope::Validator),
+            (
+                Method::POST,
+                "/eth/v1/beacon/pool/attestations",
+                ApiScope::Validator,
+            ),
+            (
+                Method::GET,
+                "/eth/v1/beacon/pool/attestations",
+                ApiScope::ReadOnly,
+            ),
+            (
+                Method::GET,
+                "/eth/v1/validator/duties/proposer/1",
+                ApiScope::Validator,
+            ),
+            (
+                Method::GET,
+                "/eth/v2/debug/beacon/states/head",
+                ApiScope::Admin,
+            ),
+            (Method::GET, "/lighthouse/health", ApiScope::Admin),
+            (
+                Method::GET,
+                "/lighthouse/health/subsystems",
+                ApiScope::ReadOnly,
+            ),
+            (Method::GET, "/lighthouse/staking", ApiScope::ReadOnly),
+            (Method::GET, "/lighthouse/events", ApiScope::ReadOnly),
+        ];
+        for (method, path, expected) in cases {
+            assert_eq!(ApiScope::required_for(&method, path), expected, "{path}");
+        }
+    }
+
+    #[test]
+    fn authorize_by_scope() {
+        let tokens = ApiTokens::parse(TOKENS).unwrap();
+        let read_only = "/eth/v1/node/syncing";
+        let validator = "/eth/v1/validator/duties/proposer/1";
+        let admin = "/lighthouse/health";
+
+        let reader = Some("Bearer reader");
+        assert_eq!(status(&tokens, reader, Method::GET, read_only), 200);
+        assert_eq!(status(&tokens, reader, Method::GET, validator), 403);
+        assert_eq!(status(&tokens, reader, Method::GET, admin), 403);
+
+        let validator_token = Some("Bearer validator");
+        assert_eq!(
+            status(&tokens, validator_token, Method::GET, read_only),
+            200
+        );
+        assert_eq!(
+            status(&tokens, validator_token, Method::GET, validator),
+            200
+        );
+        assert_eq!(status(&tokens, validator_token, Method::GET, admin), 403);
+
+        let administrator = Some("Bearer administrator");
+        for path in [read_only, validator, admin] {
+            assert_eq!(status(&tokens, administrator, Method::GET, path), 200);
+        }
+    }
+
+    #[test]
+    fn reject_missing_and_unknown_tokens() {
+        let tokens = ApiTokens::parse(TOKENS).unwrap();
+        let path = "/eth/v1/node/syncing";
+        assert_eq!(status(&tokens, None, Method::GET, path), 401);
+        for authorization in ["Bearer unknown", "Bearer read", "Bearer readers", "reader"] {
+            assert_eq!(status(&tokens, Some(authorization), Method::GET, path), 403);
+        }
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(ApiTokens::parse("# no tokens\n\n").is_err());
+        assert!(ApiTokens::parse("read-only").is_err());
+        assert!(ApiTokens::parse("root token").is_err());
+
+        let tokens = ApiTokens::parse("read-only token\nadmin token").unwrap();
+        assert_eq!(tokens.scope_of("token"), Some(ApiScope::Admin));
+    }
+
+    #[test]
+    fn constant_time_comparison() {
+        assert!(constant_time_eq(b"token", b"token"));
+        assert!(!constant_time_eq(b"token", b"tokem"));
+        assert!(!constant_time_eq(b"token", b"tokens"));
+        assert!(constant_time_eq(b"", b""));
+    }
+}
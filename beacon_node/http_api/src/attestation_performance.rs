@@ -1,6 +1,7 @@
 use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes};
 use eth2::lighthouse::{
-    AttestationPerformance, AttestationPerformanceQuery, AttestationPerformanceStatistics,
+    AttestationPerformance, AttestationPerformanceQuery, AttestationPerformanceRequest,
+    AttestationPerformanceStatistics,
 };
 use state_processing::{
     per_epoch_processing::EpochProcessingSummary, BlockReplayError, BlockReplayer,
@@ -36,6 +37,55 @@ pub fn get_attestation_performance<T: BeaconChainTypes>(
     target: String,
     query: AttestationPerformanceQuery,
     chain: Arc<BeaconChain<T>>,
+) -> Result<Vec<AttestationPerformance>, warp::Rejection> {
+    // Either use the global validator set, or the specified index.
+    //
+    // Does no further validation of the indices, so in the event an index has not yet been
+    // activated or does not yet exist (according to the head state), it will return all fields as
+    // `false`.
+    let index_range = if target.to_lowercase() == "global" {
+        chain
+            .with_head(|head| Ok((0..head.beacon_state.validators().len() as u64).collect()))
+            .map_err(beacon_chain_error)?
+    } else {
+        vec![target.parse::<u64>().map_err(|_| {
+            custom_bad_request(format!(
+                "Invalid validator index: {:?}",
+                target.to_lowercase()
+            ))
+        })?]
+    };
+
+    compute_attestation_performance(index_range, query, chain)
+}
+
+/// Computes the attestation performance of all of the validators in `request` with a single
+/// replay of the blocks in its epoch range.
+pub fn post_attestation_performance<T: BeaconChainTypes>(
+    request: AttestationPerformanceRequest,
+    chain: Arc<BeaconChain<T>>,
+) -> Result<Vec<AttestationPerformance>, warp::Rejection> {
+    if request.indices.is_empty() {
+        return Err(custom_bad_request(
+            "at least one validator index is required".to_string(),
+        ));
+    }
+
+    let mut index_range = request.indices;
+    index_range.sort_unstable();
+    index_range.dedup();
+
+    let query = AttestationPerformanceQuery {
+        start_epoch: request.start_epoch,
+        end_epoch: request.end_epoch,
+    };
+    compute_attestation_performance(index_range, query, chain)
+}
+
+fn compute_attestation_performance<T: BeaconChainTypes>(
+    index_range: Vec<u64>,
+    query: AttestationPerformanceQuery,
+    chain: Arc<BeaconChain<T>>,
 ) -> Result<Vec<AttestationPerformance>, warp::Rejection> {
     let spec = &chain.spec;
     // We increment by 2 here so that when we build the state from the `prior_slot` it is
@@ -75,24 +125,6 @@ pub fn get_attestation_performance<T: BeaconChainTypes>(
         )));
     }
 
-    // Either use the global validator set, or the specified index.
-    //
-    // Does no further validation of the indices, so in the event an index has not yet been
-    // activated or does not yet exist (according to the head state), it will return all fields as
-    // `false`.
-    let index_range = if target.to_lowercase() == "global" {
-        chain
-            .with_head(|head| Ok((0..head.beacon_state.validators().len() as u64).collect()))
-            .map_err(beacon_chain_error)?
-    } else {
-        vec![target.parse::<u64>().map_err(|_| {
-            custom_bad_request(format!(
-                "Invalid validator index: {:?}",
-                target.to_lowercase()
-            ))
-        })?]
-    };
-
     // Load block roots.
     let mut block_roots: Vec<Hash256> = chain
         .forwards_iter_block_roots_until(start_slot, end_slot)
@@ -0,0 +1,112 @@
+//! Streams blinded blocks from the canonical chain for `lighthouse/debug/blinded_blocks`.
+
+use crate::compression;
+use crate::task_spawner::{Priority, TaskSpawner};
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::{encode_blinded_block_frame, BlindedBlocksQuery};
+use futures::stream::{self, StreamExt};
+use std::sync::Arc;
+use types::{Hash256, Slot};
+use warp::http::header::CONTENT_TYPE;
+use warp::http::Response;
+use warp::hyper::Body;
+use warp_utils::reject::{beacon_chain_error, custom_bad_request, custom_server_error};
+
+/// The maximum number of slots which may be requested at once.
+const MAX_REQUEST_SLOTS: u64 = 8192;
+
+/// The number of blocks loaded from the database by each task.
+const BLOCKS_PER_CHUNK: usize = 64;
+
+/// Streams the blinded blocks of the canonical chain in the range of slots of `query`, up to the
+/// head.
+///
+/// Each block is SSZ encoded and preceded by its length as a little-endian `u32`. Blocks are read
+/// from the database without their execution payloads, so the execution node isn't needed. The
+/// range is checked before the response starts, but a later failure can only be reported by ending
+/// the response early.
+pub async fn stream_blinded_blocks<T: BeaconChainTypes>(
+    query: BlindedBlocksQuery,
+    chain: Arc<BeaconChain<T>>,
+    task_spawner: TaskSpawner<T::EthSpec>,
+) -> Result<Response<Body>, warp::Rejection> {
+    let BlindedBlocksQuery { start_slot, count } = query;
+    if count == 0 || count > MAX_REQUEST_SLOTS {
+        return Err(custom_bad_request(format!(
+            "count must be between 1 and {MAX_REQUEST_SLOTS}"
+        )));
+    }
+
+    let roots_chain = chain.clone();
+    let block_roots = task_spawner
+        .clone()
+        .blocking_task(Priority::P1, move || {
+            canonical_block_roots(&roots_chain, start_slot, count)
+        })
+        .await?;
+
+    let chunks = block_roots
+        .chunks(BLOCKS_PER_CHUNK)
+        .map(<[_]>::to_vec)
+        .collect::<Vec<_>>();
+    let body = stream::iter(chunks)
+        .then(move |chunk| {
+            let chain = chain.clone();
+            task_spawner.clone().blocking_task(Priority::P1, move || {
+                let mut bytes = vec![];
+                for block_root in chunk {
+                    let block = chain
+                        .get_blinded_block(&block_root)
+                        .map_err(beacon_chain_error)?
+                        .ok_or_else(|| {
+                            custom_server_error(format!("missing block {block_root:?}"))
+                        })?;
+                    // The first root may be that of a block before the range, if the start
+                    // slot was skipped.
+                    if block.slot() >= start_slot {
+                        encode_blinded_block_frame(&block, &mut bytes);
+                    }
+                }
+                Ok(bytes)
+            })
+        })
+        .map(|result| result.map_err(|e| format!("failed to load blinded blocks: {:?}", e)));
+
+    Response::builder()
+        .header(CONTENT_TYPE, "application/octet-stream")
+        .body(Body::wrap_stream(body))
+        .map(compression::uncompressed)
+        .map_err(|e| custom_server_error(format!("failed to build response: {:?}", e)))
+}
+
+/// Returns the distinct roots of the canonical blocks at the `count` slots from `start_slot`, up
+/// to the head.
+fn canonical_block_roots<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    start_slot: Slot,
+    count: u64,
+) -> Result<Vec<Hash256>, warp::Rejection> {
+    let oldest_block_slot = chain.store.get_oldest_block_slot();
+    if start_slot < oldest_block_slot {
+        return Err(custom_bad_request(format!(
+            "blocks before slot {oldest_block_slot} are not stored"
+        )));
+    }
+    let head_slot = chain.canonical_head.cached_head().head_slot();
+    let end_slot = std::cmp::min(start_slot + (count - 1), head_slot);
+    if start_slot > end_slot {
+        return Ok(vec![]);
+    }
+
+    let mut block_roots: Vec<Hash256> = vec![];
+    for result in chain
+        .forwards_iter_block_roots_until(start_slot, end_slot)
+        .map_err(beacon_chain_error)?
+    {
+        let (block_root, _) = result.map_err(beacon_chain_error)?;
+        if block_roots.last() != Some(&block_root) {
+            block_roots.push(block_root);
+        }
+    }
+    Ok(block_roots)
+}
@@ -23,6 +23,12 @@ impl BlockId {
         Self(CoreBlockId::Root(root))
     }
 
+    /// Returns `true` if `self` identifies a block by slot or root, so that once finalized it
+    /// always identifies the same block.
+    pub fn is_fixed(&self) -> bool {
+        matches!(self.0, CoreBlockId::Slot(_) | CoreBlockId::Root(_))
+    }
+
     /// Return the block root identified by `self`.
     pub fn root<T: BeaconChainTypes>(
         &self,
@@ -1,5 +1,8 @@
+use crate::compression;
+use crate::task_spawner::{Priority, TaskSpawner};
 use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes, WhenSlotSkipped};
 use eth2::lighthouse::{BlockReward, BlockRewardsQuery};
+use futures::stream::{self, StreamExt};
 use lru::LruCache;
 use slog::{debug, warn, Logger};
 use state_processing::BlockReplayer;
@@ -7,20 +10,112 @@ use std::num::NonZeroUsize;
 use std::sync::Arc;
 use types::beacon_block::BlindedBeaconBlock;
 use types::non_zero_usize::new_non_zero_usize;
-use warp_utils::reject::{beacon_chain_error, beacon_state_error, custom_bad_request};
+use types::{Hash256, Slot};
+use warp::http::header::CONTENT_TYPE;
+use warp::http::Response;
+use warp::hyper::Body;
+use warp_utils::reject::{
+    beacon_chain_error, beacon_state_error, custom_bad_request, custom_server_error,
+};
 
 const STATE_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(2);
 
-/// Fetch block rewards for blocks from the canonical chain.
-pub fn get_block_rewards<T: BeaconChainTypes>(
+/// The number of slots replayed by each task when computing the rewards of a range of blocks.
+const REPLAY_CHUNK_SLOTS: u64 = 256;
+
+/// The maximum number of chunks of a single range which are replayed at once.
+const MAX_PARALLEL_REPLAYS: usize = 4;
+
+/// Stream the block rewards for blocks from the canonical chain as a JSON array.
+///
+/// The range is split into chunks which are replayed in parallel, and the rewards of each chunk
+/// are sent as soon as it and all the chunks before it are complete. The range is checked before
+/// the response starts, but a later failure can only be reported by ending the response early,
+/// leaving the array unterminated.
+pub async fn stream_block_rewards<T: BeaconChainTypes>(
     query: BlockRewardsQuery,
     chain: Arc<BeaconChain<T>>,
+    task_spawner: TaskSpawner<T::EthSpec>,
     log: Logger,
-) -> Result<Vec<BlockReward>, warp::Rejection> {
-    let start_slot = query.start_slot;
-    let end_slot = query.end_slot;
-    let prior_slot = start_slot - 1;
+) -> Result<Response<Body>, warp::Rejection> {
+    let BlockRewardsQuery {
+        start_slot,
+        end_slot,
+        include_attestations,
+    } = query;
+
+    let check_chain = chain.clone();
+    task_spawner
+        .clone()
+        .blocking_task(Priority::P1, move || {
+            check_range(&check_chain, start_slot, end_slot).map(|_| ())
+        })
+        .await?;
+
+    let chunks = (start_slot.as_u64()..=end_slot.as_u64())
+        .step_by(REPLAY_CHUNK_SLOTS as usize)
+        .map(|chunk_start| {
+            let chunk_end = std::cmp::min(chunk_start + REPLAY_CHUNK_SLOTS - 1, end_slot.as_u64());
+            BlockRewardsQuery {
+                start_slot: Slot::new(chunk_start),
+                end_slot: Slot::new(chunk_end),
+                include_attestations,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let replay_log = log.clone();
+    let mut first = true;
+    let rewards = stream::iter(chunks)
+        .map(move |chunk| {
+            let chain = chain.clone();
+            let log = replay_log.clone();
+            task_spawner
+                .clone()
+                .blocking_task(Priority::P1, move || get_block_rewards(chunk, chain, log))
+        })
+        .buffered(MAX_PARALLEL_REPLAYS)
+        .map(move |result| {
+            let rewards = result.map_err(|e| {
+                warn!(
+                    log,
+                    "Failed to compute block rewards";
+                    "error" => ?e,
+                    "start_slot" => start_slot,
+                    "end_slot" => end_slot,
+                );
+                format!("failed to compute block rewards: {:?}", e)
+            })?;
+
+            let mut bytes = vec![];
+            for reward in rewards {
+                if !std::mem::take(&mut first) {
+                    bytes.push(b',');
+                }
+                serde_json::to_writer(&mut bytes, &reward)
+                    .map_err(|e| format!("failed to serialize block reward: {:?}", e))?;
+            }
+            Ok::<_, String>(bytes)
+        });
+
+    let body = stream::once(async { Ok(b"[".to_vec()) })
+        .chain(rewards)
+        .chain(stream::once(async { Ok(b"]".to_vec()) }));
+
+    Response::builder()
+        .header(CONTENT_TYPE, "application/json")
+        .body(Body::wrap_stream(body))
+        .map(compression::uncompressed)
+        .map_err(|e| custom_server_error(format!("failed to build response: {:?}", e)))
+}
 
+/// Check that `start_slot..=end_slot` is a valid range, returning the root of the block at the
+/// end of the range.
+fn check_range<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    start_slot: Slot,
+    end_slot: Slot,
+) -> Result<Hash256, warp::Rejection> {
     if start_slot > end_slot || start_slot == 0 {
         return Err(custom_bad_request(format!(
             "invalid start and end: {}, {}",
@@ -28,10 +123,23 @@ pub fn get_block_rewards<T: BeaconChainTypes>(
         )));
     }
 
-    let end_block_root = chain
+    chain
         .block_root_at_slot(end_slot, WhenSlotSkipped::Prev)
         .map_err(beacon_chain_error)?
-        .ok_or_else(|| custom_bad_request(format!("block at end slot {} unknown", end_slot)))?;
+        .ok_or_else(|| custom_bad_request(format!("block at end slot {} unknown", end_slot)))
+}
+
+/// Fetch block rewards for blocks from the canonical chain.
+pub fn get_block_rewards<T: BeaconChainTypes>(
+    query: BlockRewardsQuery,
+    chain: Arc<BeaconChain<T>>,
+    log: Logger,
+) -> Result<Vec<BlockReward>, warp::Rejection> {
+    let start_slot = query.start_slot;
+    let end_slot = query.end_slot;
+    let prior_slot = start_slot - 1;
+
+    let end_block_root = check_range(&chain, start_slot, end_slot)?;
 
     let blocks = chain
         .store
@@ -6,10 +6,14 @@ use state_processing::state_advance::partial_state_advance;
 use std::sync::Arc;
 use types::{BeaconState, EthSpec, ForkName, Slot, Withdrawals};
 
+/// The number of epochs after the state within which the proposal slot must be.
 const MAX_EPOCH_LOOKAHEAD: u64 = 2;
 
 /// Get the withdrawals computed from the specified state, that will be included in the block
-/// that gets built on the specified state.
+/// that gets built on the specified state at `proposal_slot`.
+///
+/// The state is advanced to `proposal_slot` if it is in a later epoch, so that the withdrawals
+/// reflect any epoch processing in between, such as partial withdrawals becoming withdrawable.
 pub fn get_next_withdrawals<T: BeaconChainTypes>(
     chain: &Arc<BeaconChain<T>>,
     mut state: BeaconState<T::EthSpec>,
@@ -0,0 +1,112 @@
+//! Helpers for the `GET beacon/states/{state_id}/committees` endpoint.
+
+use crate::state_id::StateId;
+use crate::ExecutionOptimistic;
+use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes};
+use eth2::types::{self as api_types, StateId as CoreStateId};
+use types::{CommitteeCache, Epoch, EthSpec};
+
+/// Returns the committees of `epoch` in `committee_cache` matching the slot and index of `query`.
+pub fn committees_from_cache<E: EthSpec>(
+    committee_cache: &CommitteeCache,
+    epoch: Epoch,
+    query: &api_types::CommitteesQuery,
+) -> Result<Vec<api_types::CommitteeData>, warp::Rejection> {
+    // Use either the supplied slot or all slots in the epoch.
+    let slots = query
+        .slot
+        .map(|slot| vec![slot])
+        .unwrap_or_else(|| epoch.slot_iter(E::slots_per_epoch()).collect());
+
+    // Use either the supplied committee index or all available indices.
+    let indices = query
+        .index
+        .map(|index| vec![index])
+        .unwrap_or_else(|| (0..committee_cache.committees_per_slot()).collect());
+
+    let mut response = Vec::with_capacity(slots.len() * indices.len());
+
+    for slot in slots {
+        // It is not acceptable to query with a slot that is not within the
+        // specified epoch.
+        if slot.epoch(E::slots_per_epoch()) != epoch {
+            return Err(warp_utils::reject::custom_bad_request(format!(
+                "{} is not in epoch {}",
+                slot, epoch
+            )));
+        }
+
+        for &index in &indices {
+            let committee = committee_cache
+                .get_beacon_committee(slot, index)
+                .ok_or_else(|| {
+                    warp_utils::reject::custom_bad_request(format!(
+                        "committee index {} does not exist in epoch {}",
+                        index, epoch
+                    ))
+                })?;
+
+            response.push(api_types::CommitteeData {
+                index,
+                slot,
+                validators: committee.committee.iter().map(|i| *i as u64).collect(),
+            });
+        }
+    }
+
+    Ok(response)
+}
+
+/// Returns the committees for a state in the freezer database, computed from the RANDAO mixes and
+/// validator registry of the freezer rather than a full state.
+///
+/// Returns `None` if the state isn't in the freezer database, or the data required to compute the
+/// committees isn't stored there. The committees should then be computed from the state.
+pub fn historic_committees<T: BeaconChainTypes>(
+    state_id: &StateId,
+    query: &api_types::CommitteesQuery,
+    chain: &BeaconChain<T>,
+) -> Result<Option<(Vec<api_types::CommitteeData>, ExecutionOptimistic)>, warp::Rejection> {
+    let (state_slot, execution_optimistic) = match &state_id.0 {
+        // States at slots without a restore point nearby may not be stored at all, so avoid
+        // looking up their roots.
+        CoreStateId::Slot(slot) => (
+            *slot,
+            chain
+                .is_optimistic_or_invalid_head()
+                .map_err(warp_utils::reject::beacon_chain_error)?,
+        ),
+        CoreStateId::Root(_) => {
+            let (state_root, execution_optimistic, _finalized) = state_id.root(chain)?;
+            let Some(slot) = chain
+                .store
+                .load_cold_state_slot(&state_root)
+                .map_err(BeaconChainError::DBError)
+                .map_err(warp_utils::reject::beacon_chain_error)?
+            else {
+                return Ok(None);
+            };
+            (slot, execution_optimistic)
+        }
+        _ => return Ok(None),
+    };
+    if state_slot >= chain.store.get_split_slot() {
+        return Ok(None);
+    }
+
+    // Committees can only be computed up to the epoch after that of the state.
+    let state_epoch = state_slot.epoch(T::EthSpec::slots_per_epoch());
+    let epoch = query.epoch.unwrap_or(state_epoch);
+    if epoch > state_epoch + 1 {
+        return Ok(None);
+    }
+
+    let Some(committee_cache) = chain
+        .historic_committee_cache(epoch)
+        .map_err(warp_utils::reject::beacon_chain_error)?
+    else {
+        return Ok(None);
+    };
+    let committees = committees_from_cache::<T::EthSpec>(&committee_cache, epoch, query)?;
+    Ok(Some((committees, execution_optimistic)))
+}
@@ -0,0 +1,148 @@
+//! Compression of responses, negotiated using the `Accept-Encoding` request header.
+//!
+//! Some responses, such as the validators of a state or a whole state from the debug endpoints,
+//! are hundreds of megabytes uncompressed. Clients which send `Accept-Encoding: zstd` or
+//! `Accept-Encoding: gzip` receive them compressed, which is much faster over a network.
+//!
+//! Compression buffers the whole body, so handlers which stream their response, or upgrade the
+//! connection, opt out by passing their response through [`uncompressed`].
+
+use crate::etag::weak_etag;
+use std::io::{self, Write};
+use warp::http::header::{HeaderValue, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE, ETAG, VARY};
+use warp::http::StatusCode;
+use warp::hyper::{self, Body};
+use warp::reply::Response;
+
+/// Responses smaller than this are sent uncompressed, as compression would barely reduce them.
+const MIN_COMPRESSION_SIZE: usize = 1024;
+
+/// The default level of the `zstd` command-line tool, which compresses quickly.
+const ZSTD_LEVEL: i32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Encoding {
+    Zstd,
+    Gzip,
+}
+
+impl Encoding {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Zstd => "zstd",
+            Self::Gzip => "gzip",
+        }
+    }
+
+    fn compress(self, bytes: &[u8]) -> io::Result<Vec<u8>> {
+        match self {
+            Self::Zstd => zstd::encode_all(bytes, ZSTD_LEVEL),
+            Self::Gzip => {
+                let mut encoder =
+                    flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(bytes)?;
+                encoder.finish()
+            }
+        }
+    }
+}
+
+/// Marks a response which must be sent as it is, see [`uncompressed`].
+#[derive(Debug, Clone, Copy)]
+struct Uncompressed;
+
+/// Marks `response` to be sent without compression, so that its body is passed through to the
+/// client as it is produced instead of being buffered.
+pub fn uncompressed(mut response: Response) -> Response {
+    response.extensions_mut().insert(Uncompressed);
+    response
+}
+
+/// Returns the supported encoding with the highest quality in an `Accept-Encoding` header,
+/// preferring `zstd` when both are equally acceptable.
+fn negotiate(accept_encoding: &str) -> Option<Encoding> {
+    let mut best: Option<(Encoding, f32)> = None;
+    for item in accept_encoding.split(',') {
+        let mut params = item.split(';');
+        let encoding = match params.next().unwrap_or_default().trim() {
+            coding if coding.eq_ignore_ascii_case("zstd") => Encoding::Zstd,
+            coding if coding.eq_ignore_ascii_case("gzip") => Encoding::Gzip,
+            _ => continue,
+        };
+        let quality = match params.find_map(|param| param.trim().strip_prefix("q=")) {
+            Some(quality) => quality.trim().parse::<f32>().unwrap_or(0.0),
+            None => 1.0,
+        };
+        if quality <= 0.0 {
+            continue;
+        }
+        if best.map_or(true, |(best_encoding, best_quality)| {
+            quality > best_quality || (quality >= best_quality && encoding == Encoding::Zstd)
+        }) {
+            best = Some((encoding, quality));
+        }
+    }
+    best.map(|(encoding, _)| encoding)
+}
+
+/// Compresses `response` with the preferred encoding of the `accept_encoding` request header.
+///
+/// Responses marked by [`uncompressed`] are passed through untouched. Server-sent event streams
+/// never end, so they are always sent uncompressed too.
+pub async fn compress_response(response: Response, accept_encoding: Option<String>) -> Response {
+    let Some(encoding) = accept_encoding.as_deref().and_then(negotiate) else {
+        return response;
+    };
+    if response.extensions().get::<Uncompressed>().is_some() {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let is_event_stream = parts
+        .headers
+        .get(CONTENT_TYPE)
+        .map_or(false, |content_type| {
+            content_type.as_bytes().starts_with(b"text/event-stream")
+        });
+    if is_event_stream || parts.headers.contains_key(CONTENT_ENCODING) {
+        return Response::from_parts(parts, body);
+    }
+    parts
+        .headers
+        .insert(VARY, HeaderValue::from_static("Accept-Encoding"));
+    // The `ETag` was derived from the uncompressed body, so it is weakened for every response which
+    // may be compressed, including `304 Not Modified`, rather than claiming identical bytes.
+    let weak = parts
+        .headers
+        .get(ETAG)
+        .and_then(|etag| etag.to_str().ok())
+        .and_then(|etag| HeaderValue::from_str(&weak_etag(etag)).ok());
+    if let Some(weak) = weak {
+        parts.headers.insert(ETAG, weak);
+    }
+
+    let bytes = match hyper::body::to_bytes(body).await {
+        Ok(bytes) => bytes,
+        Err(_) => {
+            parts.status = StatusCode::INTERNAL_SERVER_ERROR;
+            return Response::from_parts(parts, Body::empty());
+        }
+    };
+    if bytes.len() < MIN_COMPRESSION_SIZE {
+        return Response::from_parts(parts, Body::from(bytes));
+    }
+
+    // Compressing a large response takes long enough that it shouldn't block the executor.
+    let uncompressed = bytes.clone();
+    match tokio::task::spawn_blocking(move || encoding.compress(&uncompressed)).await {
+        Ok(Ok(compressed)) => {
+            parts
+                .headers
+                .insert(CONTENT_ENCODING, HeaderValue::from_static(encoding.name()));
+            parts.headers.remove(CONTENT_LENGTH);
+            Response::from_parts(parts, Body::from(compressed))
+        }
+        // Fall back to the uncompressed response, which the client also accepts.
+        Ok(Err(_)) | Err(_) => Response::from_parts(parts, Body::from(bytes)),
+    }
+}
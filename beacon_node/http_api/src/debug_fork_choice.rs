@@ -0,0 +1,75 @@
+//! Dumps the nodes of the fork choice proto-array for the `debug/fork_choice` endpoint.
+
+use beacon_chain::{BeaconChain, BeaconChainTypes, ForkChoiceStore};
+use eth2::types::{ForkChoice, ForkChoiceNode, ForkChoiceQuery};
+use std::collections::HashSet;
+
+/// Returns the fork choice nodes, restricted to the subtree of `query.ancestor` if it is given.
+pub fn get_fork_choice<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    query: ForkChoiceQuery,
+) -> Result<ForkChoice, warp::Rejection> {
+    let beacon_fork_choice = chain.canonical_head.fork_choice_read_lock();
+    let proto_array = beacon_fork_choice.proto_array().core_proto_array();
+    let queued_weights = beacon_fork_choice.queued_attestation_weights();
+
+    // Nodes are stored after their parents, so a single pass finds every descendant.
+    let mut subtree = HashSet::new();
+    if let Some(ancestor) = query.ancestor {
+        let ancestor_index = *proto_array.indices.get(&ancestor).ok_or_else(|| {
+            warp_utils::reject::custom_not_found(format!(
+                "ancestor {ancestor:?} is not known to fork choice"
+            ))
+        })?;
+        subtree.insert(ancestor_index);
+        for (index, node) in proto_array.nodes.iter().enumerate().skip(ancestor_index) {
+            if node
+                .parent
+                .map_or(false, |parent| subtree.contains(&parent))
+            {
+                subtree.insert(index);
+            }
+        }
+    }
+
+    let fork_choice_nodes = proto_array
+        .nodes
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| query.ancestor.is_none() || subtree.contains(index))
+        .map(|(_, node)| {
+            let execution_status = if node.execution_status.is_execution_enabled() {
+                Some(node.execution_status.to_string())
+            } else {
+                None
+            };
+
+            ForkChoiceNode {
+                slot: node.slot,
+                block_root: node.root,
+                parent_root: node
+                    .parent
+                    .and_then(|index| proto_array.nodes.get(index))
+                    .map(|parent| parent.root),
+                justified_epoch: node.justified_checkpoint.epoch,
+                finalized_epoch: node.finalized_checkpoint.epoch,
+                justified_root: node.justified_checkpoint.root,
+                finalized_root: node.finalized_checkpoint.root,
+                weight: node.weight,
+                queued_weight: queued_weights.get(&node.root).copied().unwrap_or(0),
+                validity: execution_status,
+                execution_block_hash: node
+                    .execution_status
+                    .block_hash()
+                    .map(|block_hash| block_hash.into_root()),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    Ok(ForkChoice {
+        justified_checkpoint: proto_array.justified_checkpoint,
+        finalized_checkpoint: proto_array.finalized_checkpoint,
+        proposer_boost_root: beacon_fork_choice.fc_store().proposer_boost_root(),
+        fork_choice_nodes,
+    })
+}
@@ -0,0 +1,63 @@
+//! Conditional requests for endpoints whose responses rarely change, such as the spec and genesis.
+//!
+//! Each response carries an `ETag` derived from its body. A client which polls the endpoint can
+//! send the tag back in `If-None-Match` and receives an empty `304 Not Modified` response if the
+//! body is unchanged, rather than downloading and parsing it again.
+
+use ethereum_hashing::hash;
+use serde::Serialize;
+use warp::http::header::{CONTENT_TYPE, ETAG};
+use warp::http::StatusCode;
+use warp::reply::{self, Reply, Response};
+
+/// The number of bytes of the body's hash used as its tag.
+const ETAG_HASH_BYTES: usize = 16;
+
+/// Returns `body` as JSON with an `ETag` header, or `304 Not Modified` if `if_none_match` already
+/// includes that tag.
+pub fn json_with_etag<T: Serialize>(
+    body: &T,
+    if_none_match: Option<String>,
+) -> Result<Response, warp::Rejection> {
+    let bytes = serde_json::to_vec(body).map_err(|e| {
+        warp_utils::reject::custom_server_error(format!("unable to serialize response: {e:?}"))
+    })?;
+    let etag = etag_for(&bytes);
+
+    let response = if matches_etag(if_none_match.as_deref(), &etag) {
+        reply::with_status(reply::reply(), StatusCode::NOT_MODIFIED).into_response()
+    } else {
+        reply::with_header(bytes, CONTENT_TYPE, "application/json").into_response()
+    };
+    Ok(reply::with_header(response, ETAG, etag).into_response())
+}
+
+/// Returns the tag of a response with the given body.
+pub fn etag_for(body: &[u8]) -> String {
+    format!("\"{}\"", hex::encode(&hash(body)[..ETAG_HASH_BYTES]))
+}
+
+/// Returns the weak form of `etag`, which promises only an equivalent body rather than identical
+/// bytes, as for a response which has been compressed.
+pub fn weak_etag(etag: &str) -> String {
+    if etag.starts_with("W/") {
+        etag.to_string()
+    } else {
+        format!("W/{etag}")
+    }
+}
+
+/// Returns `true` if the `If-None-Match` header includes `etag`, meaning that the client already
+/// has the response.
+///
+/// Tags are compared weakly, as `If-None-Match` requires, so that a client which received the weak
+/// tag of a compressed response also receives `304 Not Modified`.
+pub fn matches_etag(if_none_match: Option<&str>, etag: &str) -> bool {
+    let opaque = |tag: &str| tag.strip_prefix("W/").unwrap_or(tag).to_string();
+    let etag = opaque(etag);
+    if_none_match.map_or(false, |tags| {
+        tags.split(',')
+            .map(str::trim)
+            .any(|tag| opaque(tag) == etag || tag == "*")
+    })
+}
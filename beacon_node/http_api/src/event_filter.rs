@@ -0,0 +1,170 @@
+//! Server-side filtering of the `attestation` and `block` events of the SSE events stream.
+//!
+//! Consumers which only track a handful of validators can restrict these topics to the events
+//! of those validators or slots, rather than receiving and discarding every event on the network.
+
+use beacon_chain::shuffling_cache::{BlockShufflingIds, CacheItem};
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::types::{EventKind, EventQuery};
+use slog::debug;
+use state_processing::common::{attesting_indices_base, attesting_indices_electra};
+use std::collections::HashSet;
+use types::{Attestation, EthSpec, Hash256, Slot};
+
+/// The filters given alongside the topics of an events subscription.
+///
+/// Events which are neither attestations nor blocks are never filtered. Attestations and blocks
+/// are only sent if they match every filter which is present.
+#[derive(Debug)]
+pub struct EventFilter {
+    /// Only send attestations from, and blocks proposed by, these validators.
+    validator_indices: Option<HashSet<u64>>,
+    /// Only send attestations and blocks for these slots.
+    slots: Option<HashSet<Slot>>,
+}
+
+impl EventFilter {
+    pub fn from_query(query: &EventQuery) -> Self {
+        Self::new(query.validator_indices.as_deref(), query.slots.as_deref())
+    }
+
+    pub fn new(validator_indices: Option<&[u64]>, slots: Option<&[Slot]>) -> Self {
+        Self {
+            validator_indices: validator_indices.map(|indices| indices.iter().copied().collect()),
+            slots: slots.map(|slots| slots.iter().copied().collect()),
+        }
+    }
+
+    /// Returns `true` if `event` should be sent to the subscriber.
+    ///
+    /// This is called on the async executor for every event and subscriber, so it only reads the
+    /// in-memory committee and proposer caches. An event whose committee or proposer isn't cached
+    /// is sent rather than dropped, leaving the subscriber to discard it if it isn't wanted.
+    pub fn matches<T: BeaconChainTypes>(
+        &self,
+        chain: &BeaconChain<T>,
+        event: &EventKind<T::EthSpec>,
+    ) -> bool {
+        match event {
+            EventKind::Attestation(attestation) => {
+                self.slot_matches(attestation.data().slot)
+                    && self.validator_indices.as_ref().map_or(true, |indices| {
+                        match cached_attesting_indices(chain, attestation) {
+                            Some(attesters) => {
+                                attesters.iter().any(|index| indices.contains(index))
+                            }
+                            None => {
+                                debug!(
+                                    chain.logger(),
+                                    "Unable to filter attestation event";
+                                    "msg" => "committee not cached, sending unfiltered",
+                                    "slot" => attestation.data().slot,
+                                );
+                                true
+                            }
+                        }
+                    })
+            }
+            EventKind::Block(block) => {
+                self.slot_matches(block.slot)
+                    && self.validator_indices.as_ref().map_or(true, |indices| {
+                        match cached_proposer_index(chain, block.block, block.slot) {
+                            Some(proposer_index) => indices.contains(&proposer_index),
+                            None => {
+                                debug!(
+                                    chain.logger(),
+                                    "Unable to filter block event";
+                                    "msg" => "proposer not cached, sending unfiltered",
+                                    "block_root" => ?block.block,
+                                );
+                                true
+                            }
+                        }
+                    })
+            }
+            _ => true,
+        }
+    }
+
+    fn slot_matches(&self, slot: Slot) -> bool {
+        self.slots
+            .as_ref()
+            .map_or(true, |slots| slots.contains(&slot))
+    }
+}
+
+/// Returns the index of the proposer of the block at `block_root` and `slot`, or `None` if it
+/// isn't in the proposer cache.
+///
+/// The proposer cache is populated when a block is verified, so the proposer of a block which has
+/// just been imported will normally be cached.
+fn cached_proposer_index<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    block_root: Hash256,
+    slot: Slot,
+) -> Option<u64> {
+    // The proposer shuffling of the block's epoch is decided by the last block of the previous
+    // epoch, which is also the decision block of the attester shuffling of the next epoch.
+    let decision_block = chain
+        .canonical_head
+        .fork_choice_read_lock()
+        .get_block(&block_root)?
+        .next_epoch_shuffling_id
+        .shuffling_decision_block;
+    chain
+        .beacon_proposer_cache
+        .lock()
+        .get_slot::<T::EthSpec>(decision_block, slot)
+        .map(|proposer| proposer.index as u64)
+}
+
+/// Returns the indices of the validators which participated in `attestation`, or `None` if its
+/// committees aren't in the shuffling cache.
+///
+/// Attestations are only sent once they have been verified, so their committees will normally be
+/// cached.
+fn cached_attesting_indices<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    attestation: &Attestation<T::EthSpec>,
+) -> Option<Vec<u64>> {
+    let data = attestation.data();
+    let target_block = chain
+        .canonical_head
+        .fork_choice_read_lock()
+        .get_block(&data.target.root)?;
+    let shuffling_id = BlockShufflingIds {
+        current: target_block.current_epoch_shuffling_id,
+        next: target_block.next_epoch_shuffling_id,
+        previous: None,
+        block_root: target_block.root,
+    }
+    .id_for_epoch(data.slot.epoch(T::EthSpec::slots_per_epoch()))?;
+    // Don't wait for a committee which is still being computed.
+    let CacheItem::Committee(committee_cache) = chain.shuffling_cache.write().get(&shuffling_id)?
+    else {
+        return None;
+    };
+
+    match attestation {
+        Attestation::Base(att) => committee_cache
+            .get_beacon_committee(data.slot, data.index)
+            .and_then(|committee| {
+                attesting_indices_base::get_attesting_indices::<T::EthSpec>(
+                    committee.committee,
+                    &att.aggregation_bits,
+                )
+                .ok()
+            }),
+        Attestation::Electra(att) => committee_cache
+            .get_beacon_committees_at_slot(data.slot)
+            .ok()
+            .and_then(|committees| {
+                attesting_indices_electra::get_attesting_indices::<T::EthSpec>(
+                    &committees,
+                    &att.aggregation_bits,
+                    &att.committee_bits,
+                )
+                .ok()
+            }),
+    }
+}
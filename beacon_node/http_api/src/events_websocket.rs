@@ -0,0 +1,145 @@
+//! Streams the topics of the `eth/v1/events` SSE endpoint over a WebSocket, for the
+//! `GET lighthouse/events` endpoint.
+//!
+//! Clients change the topics of a connection by sending `EventsWebSocketRequest`s, and the server
+//! replies to each with the topics now subscribed to.
+//!
+//! Each topic is read from its own broadcast channel, and the channels are only read once the
+//! previous message has been written to the socket. A client which can't keep up with the events
+//! therefore falls behind the channels, rather than events being buffered on the server without
+//! bound. The client is sent an error with the number of events it missed.
+//!
+//! Events are filtered in the same way as the SSE endpoint, using only in-memory caches so that the
+//! connection's task never waits on the database.
+
+use crate::event_filter::EventFilter;
+use beacon_chain::{BeaconChain, BeaconChainTypes, ServerSentEventHandler};
+use eth2::lighthouse::{EventsWebSocketMessage, EventsWebSocketRequest};
+use eth2::types::{EventKind, EventTopic};
+use futures::{SinkExt, StreamExt};
+use slog::{debug, Logger};
+use std::sync::Arc;
+use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
+use tokio_stream::StreamMap;
+use types::EthSpec;
+use warp::ws::{Message, WebSocket};
+
+/// The maximum size of a message sent by the client, which is far larger than any request.
+pub const MAX_REQUEST_SIZE: usize = 64 * 1024;
+
+type Subscriptions<E> = StreamMap<EventTopic, BroadcastStream<EventKind<E>>>;
+
+/// Sends the events of the subscribed topics over `socket` until it's closed.
+pub async fn serve_events<T: BeaconChainTypes>(
+    socket: WebSocket,
+    chain: Arc<BeaconChain<T>>,
+    topics: Vec<EventTopic>,
+    filter: EventFilter,
+    log: Logger,
+) {
+    let Some(event_handler) = chain.event_handler.as_ref() else {
+        return;
+    };
+    let (mut sender, mut receiver) = socket.split();
+
+    let mut subscriptions = StreamMap::new();
+    subscribe(&mut subscriptions, event_handler, topics);
+    let mut message = Some(subscriptions_message(&subscriptions));
+
+    loop {
+        if let Some(message) = message.take() {
+            let text = serde_json::to_string(&message).unwrap_or_else(|e| {
+                format!("{{\"error\":\"unable to serialize message: {e:?}\"}}")
+            });
+            if let Err(e) = sender.send(Message::text(text)).await {
+                debug!(log, "Events WebSocket closed"; "error" => %e);
+                return;
+            }
+        }
+
+        message = tokio::select! {
+            request = receiver.next() => match request {
+                Some(Ok(request)) => match request.to_str() {
+                    Ok(request) => Some(handle_request(request, &mut subscriptions, event_handler)),
+                    Err(()) if request.is_close() => break,
+                    // Pings are answered by the socket itself, and binary messages are ignored.
+                    Err(()) => None,
+                },
+                Some(Err(e)) => {
+                    debug!(log, "Events WebSocket failed"; "error" => %e);
+                    break;
+                }
+                None => break,
+            },
+            Some((topic, event)) = subscriptions.next(), if !subscriptions.is_empty() => {
+                match event {
+                    // The filter only reads in-memory caches, so it can't stall the socket.
+                    Ok(event) => filter.matches(&chain, &event).then(|| event_message(&event)),
+                    // Don't close the connection if the client falls behind. Just tell it how many
+                    // events it missed.
+                    Err(BroadcastStreamRecvError::Lagged(n)) => {
+                        Some(EventsWebSocketMessage::Error {
+                            error: format!("dropped {n} {topic} messages"),
+                        })
+                    }
+                }
+            }
+        };
+    }
+
+    let _ = sender.close().await;
+}
+
+fn handle_request<E: EthSpec>(
+    request: &str,
+    subscriptions: &mut Subscriptions<E>,
+    event_handler: &ServerSentEventHandler<E>,
+) -> EventsWebSocketMessage {
+    match serde_json::from_str(request) {
+        Ok(EventsWebSocketRequest::Subscribe { topics }) => {
+            subscribe(subscriptions, event_handler, topics);
+            subscriptions_message(subscriptions)
+        }
+        Ok(EventsWebSocketRequest::Unsubscribe { topics }) => {
+            for topic in topics {
+                subscriptions.remove(&topic);
+            }
+            subscriptions_message(subscriptions)
+        }
+        Err(e) => EventsWebSocketMessage::Error {
+            error: format!("invalid request: {e}"),
+        },
+    }
+}
+
+/// Subscribes to each of `topics` which isn't already subscribed to.
+fn subscribe<E: EthSpec>(
+    subscriptions: &mut Subscriptions<E>,
+    event_handler: &ServerSentEventHandler<E>,
+    topics: Vec<EventTopic>,
+) {
+    for topic in topics {
+        if !subscriptions.contains_key(&topic) {
+            let receiver = event_handler.subscribe(topic);
+            subscriptions.insert(topic, BroadcastStream::new(receiver));
+        }
+    }
+}
+
+fn subscriptions_message<E: EthSpec>(subscriptions: &Subscriptions<E>) -> EventsWebSocketMessage {
+    EventsWebSocketMessage::Subscriptions {
+        topics: subscriptions.keys().copied().collect(),
+    }
+}
+
+fn event_message<E: EthSpec>(event: &EventKind<E>) -> EventsWebSocketMessage {
+    match serde_json::to_value(event) {
+        Ok(data) => EventsWebSocketMessage::Event {
+            event: event.topic_name().to_string(),
+            data,
+        },
+        Err(e) => EventsWebSocketMessage::Error {
+            error: format!("bad json: {e:?}"),
+        },
+    }
+}
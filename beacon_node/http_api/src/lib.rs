@@ -5,31 +5,47 @@
 //! There are also some additional, non-standard endpoints behind the `/lighthouse/` path which are
 //! used for development.
 
+mod api_tokens;
 mod attestation_performance;
 mod attester_duties;
+mod blinded_blocks;
 mod block_id;
 mod block_packing_efficiency;
 mod block_rewards;
 mod build_block_contents;
 mod builder_states;
+mod committees;
+mod compression;
 mod database;
+mod debug_fork_choice;
+mod etag;
+mod event_filter;
+mod events_websocket;
+mod light_client;
+mod log_level;
+mod merkle_proofs;
 mod metrics;
 mod produce_block;
 mod proposer_duties;
 mod publish_attestations;
 mod publish_blocks;
+mod rate_limit;
+mod response_cache;
 mod standard_block_rewards;
 mod state_id;
+mod subsystem_health;
 mod sync_committee_rewards;
 mod sync_committees;
 mod task_spawner;
 pub mod test_utils;
 mod ui;
 mod validator;
+mod validator_exits;
 mod validator_inclusion;
 mod validators;
 mod version;
 
+use crate::api_tokens::ApiTokens;
 use crate::produce_block::{produce_blinded_block_v2, produce_block_v2, produce_block_v3};
 use crate::version::fork_versioned_response;
 use beacon_chain::{
@@ -43,20 +59,27 @@ use builder_states::get_next_withdrawals;
 use bytes::Bytes;
 use directory::DEFAULT_ROOT_DIR;
 use eth2::types::{
-    self as api_types, BroadcastValidation, EndpointVersion, ForkChoice, ForkChoiceNode,
-    PublishBlockRequest, ValidatorBalancesRequestBody, ValidatorId, ValidatorStatus,
-    ValidatorsRequestBody,
+    self as api_types, BroadcastValidation, EndpointVersion, PublishBlockRequest,
+    ValidatorBalancesRequestBody, ValidatorId, ValidatorStatus, ValidatorsRequestBody,
 };
 use eth2::{CONSENSUS_VERSION_HEADER, CONTENT_TYPE_HEADER, SSZ_CONTENT_TYPE_HEADER};
-use lighthouse_network::{types::SyncState, EnrExt, NetworkGlobals, PeerId, PubsubMessage};
+use event_filter::EventFilter;
+use execution_layer::Error as ExecutionLayerError;
+use lighthouse_network::rpc::GoodbyeReason;
+use lighthouse_network::{
+    types::SyncState, EnrAddresses, EnrExt, NetworkGlobals, PeerId, PubsubMessage, ReportSource,
+};
 use lighthouse_version::version_with_platform;
-use logging::SSELoggingComponents;
+use logging::{LogFilterHandle, RecentLogs, SSELoggingComponents};
 use network::{NetworkMessage, NetworkSenders, ValidatorSubscriptionMessage};
 use operation_pool::ReceivedPreCapella;
 use parking_lot::RwLock;
 pub use publish_blocks::{
     publish_blinded_block, publish_block, reconstruct_block, ProvenancedBlock,
 };
+use rate_limit::RateLimiter;
+pub use rate_limit::{RateLimitConfig, RouteQuota};
+use response_cache::{ResponseCache, ResponseCacheKey};
 use serde::{Deserialize, Serialize};
 use slog::{crit, debug, error, info, warn, Logger};
 use slot_clock::SlotClock;
@@ -81,19 +104,20 @@ use tokio_stream::{
 use types::{
     fork_versioned_response::EmptyMetadata, Attestation, AttestationData, AttestationShufflingId,
     AttesterSlashing, BeaconStateError, CommitteeCache, ConfigAndPreset, Epoch, EthSpec, ForkName,
-    ForkVersionedResponse, Hash256, ProposerPreparationData, ProposerSlashing, RelativeEpoch,
-    SignedAggregateAndProof, SignedBlindedBeaconBlock, SignedBlsToExecutionChange,
-    SignedContributionAndProof, SignedValidatorRegistrationData, SignedVoluntaryExit, Slot,
-    SyncCommitteeMessage, SyncContributionData,
+    ForkVersionDecode, ForkVersionedResponse, Hash256, ProposerPreparationData, ProposerSlashing,
+    PublicKeyBytes, RelativeEpoch, SignedAggregateAndProof, SignedBlindedBeaconBlock,
+    SignedBlsToExecutionChange, SignedContributionAndProof, SignedValidatorRegistrationData,
+    SignedVoluntaryExit, Slot, SyncCommitteeMessage, SyncContributionData,
 };
 use validator::pubkey_to_validator_index;
 use version::{
-    add_consensus_version_header, add_ssz_content_type_header,
+    add_consensus_version_header, add_replay_depth_header, add_ssz_content_type_header,
     execution_optimistic_finalized_fork_versioned_response, inconsistent_fork_rejection,
     unsupported_version_rejection, V1, V2, V3,
 };
 use warp::http::StatusCode;
 use warp::hyper::Body;
+use warp::path::FullPath;
 use warp::sse::Event;
 use warp::Reply;
 use warp::{http::Response, Filter, Rejection};
@@ -119,6 +143,8 @@ pub type ExecutionOptimistic = bool;
 pub struct TlsConfig {
     pub cert: PathBuf,
     pub key: PathBuf,
+    /// If set, clients must present a certificate signed by one of the CAs in this file.
+    pub client_ca: Option<PathBuf>,
 }
 
 /// A wrapper around all the items required to spawn the HTTP server.
@@ -133,6 +159,8 @@ pub struct Context<T: BeaconChainTypes> {
     pub beacon_processor_reprocess_send: Option<Sender<ReprocessQueueMessage>>,
     pub eth1_service: Option<eth1::Service>,
     pub sse_logging_components: Option<SSELoggingComponents>,
+    pub log_filter: Option<LogFilterHandle>,
+    pub recent_logs: Option<RecentLogs>,
     pub log: Logger,
 }
 
@@ -151,6 +179,14 @@ pub struct Config {
     #[serde(with = "eth2::types::serde_status_code")]
     pub duplicate_block_status_code: StatusCode,
     pub enable_light_client_server: bool,
+    /// The maximum number of slots to replay when reconstructing a state which isn't stored.
+    pub historic_state_replay_limit: u64,
+    /// The per-route quotas of requests from each client, or `None` to disable rate limiting.
+    pub rate_limit: Option<RateLimitConfig>,
+    /// A file of bearer tokens and their scopes, or `None` to allow requests without a token.
+    pub api_tokens_file: Option<PathBuf>,
+    /// The maximum total size of the cached responses which can never change, in megabytes.
+    pub response_cache_size_mb: usize,
 }
 
 impl Default for Config {
@@ -167,6 +203,10 @@ impl Default for Config {
             enable_beacon_processor: true,
             duplicate_block_status_code: StatusCode::ACCEPTED,
             enable_light_client_server: false,
+            historic_state_replay_limit: 0,
+            rate_limit: None,
+            api_tokens_file: None,
+            response_cache_size_mb: 64,
         }
     }
 }
@@ -328,7 +368,7 @@ pub fn serve<T: BeaconChainTypes>(
     let cors_builder = {
         let builder = warp::cors()
             .allow_methods(vec!["GET", "POST"])
-            .allow_headers(vec!["Content-Type"]);
+            .allow_headers(vec!["Content-Type", "Authorization"]);
 
         warp_utils::cors::set_builder_origins(
             builder,
@@ -345,6 +385,53 @@ pub fn serve<T: BeaconChainTypes>(
         ));
     }
 
+    // Create a filter that rejects requests which exceed the client's quota for their route.
+    let rate_limiter = config
+        .rate_limit
+        .as_ref()
+        .map(RateLimiter::new)
+        .transpose()
+        .map_err(Error::Other)?
+        .map(Arc::new);
+    let rate_limit_filter = warp::addr::remote()
+        .and(warp::path::full())
+        .and_then(move |remote: Option<SocketAddr>, path: FullPath| {
+            let rate_limiter = rate_limiter.clone();
+            async move {
+                if let (Some(rate_limiter), Some(remote)) = (rate_limiter, remote) {
+                    rate_limiter
+                        .allows(remote.ip(), path.as_str())
+                        .map_err(warp_utils::reject::rate_limited)?;
+                }
+                Ok::<_, warp::Rejection>(())
+            }
+        })
+        .untuple_one();
+
+    // Create a filter that rejects requests without an API token granting the route's scope.
+    let api_tokens = config
+        .api_tokens_file
+        .as_deref()
+        .map(ApiTokens::from_file)
+        .transpose()
+        .map_err(Error::Other)?
+        .map(Arc::new);
+    let api_token_filter = warp::method()
+        .and(warp::path::full())
+        .and(warp::header::optional::<String>("Authorization"))
+        .and_then(
+            move |method: warp::http::Method, path: FullPath, authorization: Option<String>| {
+                let api_tokens = api_tokens.clone();
+                async move {
+                    if let Some(api_tokens) = api_tokens {
+                        api_tokens.authorize(authorization.as_deref(), &method, path.as_str())?;
+                    }
+                    Ok::<_, warp::Rejection>(())
+                }
+            },
+        )
+        .untuple_one();
+
     // Create a filter that extracts the endpoint version.
     let any_version = warp::path(API_PREFIX).and(warp::path::param::<EndpointVersion>().or_else(
         |_| async move {
@@ -494,6 +581,13 @@ pub fn serve<T: BeaconChainTypes>(
     let inner_components = ctx.sse_logging_components.clone();
     let sse_component_filter = warp::any().map(move || inner_components.clone());
 
+    // Create `warp` filters that provide access to the runtime log filter and the recent logs.
+    let inner_log_filter = ctx.log_filter.clone();
+    let log_filter_handle_filter = warp::any().map(move || inner_log_filter.clone());
+
+    let inner_recent_logs = ctx.recent_logs.clone();
+    let recent_logs_filter = warp::any().map(move || inner_recent_logs.clone());
+
     // Create a `warp` filter that provides access to local system information.
     let system_info = Arc::new(RwLock::new(sysinfo::System::new()));
     {
@@ -551,17 +645,24 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("beacon"))
         .and(warp::path("genesis"))
         .and(warp::path::end())
+        .and(warp::header::optional::<String>("if-none-match"))
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
         .then(
-            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
+            |if_none_match: Option<String>,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
                     let genesis_data = api_types::GenesisData {
                         genesis_time: chain.genesis_time,
                         genesis_validators_root: chain.genesis_validators_root,
                         genesis_fork_version: chain.spec.genesis_fork_version,
                     };
-                    Ok(api_types::GenericResponse::from(genesis_data))
+                    etag::json_with_etag(
+                        &api_types::GenericResponse::from(genesis_data),
+                        if_none_match,
+                    )
+                    .map(response_cache::immutable)
                 })
             },
         );
@@ -658,6 +759,8 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    let historic_state_replay_limit = ctx.config.historic_state_replay_limit;
+
     // GET beacon/states/{state_id}/validator_balances?id
     let get_beacon_state_validator_balances = beacon_states_path
         .clone()
@@ -665,17 +768,21 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path::end())
         .and(multi_key_query::<api_types::ValidatorBalancesQuery>())
         .then(
-            |state_id: StateId,
-             task_spawner: TaskSpawner<T::EthSpec>,
-             chain: Arc<BeaconChain<T>>,
-             query_res: Result<api_types::ValidatorBalancesQuery, warp::Rejection>| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
+            move |state_id: StateId,
+                  task_spawner: TaskSpawner<T::EthSpec>,
+                  chain: Arc<BeaconChain<T>>,
+                  query_res: Result<api_types::ValidatorBalancesQuery, warp::Rejection>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
                     let query = query_res?;
                     crate::validators::get_beacon_state_validator_balances(
                         state_id,
                         chain,
                         query.id.as_deref(),
+                        historic_state_replay_limit,
                     )
+                    .map(|(response, replay_depth)| {
+                        add_replay_depth_header(warp::reply::json(&response), replay_depth)
+                    })
                 })
             },
         );
@@ -687,21 +794,25 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path::end())
         .and(warp_utils::json::json())
         .then(
-            |state_id: StateId,
-             task_spawner: TaskSpawner<T::EthSpec>,
-             chain: Arc<BeaconChain<T>>,
-             query: ValidatorBalancesRequestBody| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
+            move |state_id: StateId,
+                  task_spawner: TaskSpawner<T::EthSpec>,
+                  chain: Arc<BeaconChain<T>>,
+                  query: ValidatorBalancesRequestBody| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
                     crate::validators::get_beacon_state_validator_balances(
                         state_id,
                         chain,
                         Some(&query.ids),
+                        historic_state_replay_limit,
                     )
+                    .map(|(response, replay_depth)| {
+                        add_replay_depth_header(warp::reply::json(&response), replay_depth)
+                    })
                 })
             },
         );
 
-    // GET beacon/states/{state_id}/validators?id,status
+    // GET beacon/states/{state_id}/validators?id,status,offset,limit
     let get_beacon_state_validators = beacon_states_path
         .clone()
         .and(warp::path("validators"))
@@ -719,6 +830,8 @@ pub fn serve<T: BeaconChainTypes>(
                         chain,
                         &query.id,
                         &query.status,
+                        query.offset,
+                        query.limit,
                     )
                 })
             },
@@ -741,6 +854,8 @@ pub fn serve<T: BeaconChainTypes>(
                         chain,
                         &query.ids,
                         &query.statuses,
+                        query.offset,
+                        query.limit,
                     )
                 })
             },
@@ -830,6 +945,18 @@ pub fn serve<T: BeaconChainTypes>(
              chain: Arc<BeaconChain<T>>,
              query: api_types::CommitteesQuery| {
                 task_spawner.blocking_json_task(Priority::P1, move || {
+                    // Avoid loading historic states, which may need blocks to be replayed or be
+                    // missing altogether.
+                    if let Some((data, execution_optimistic)) =
+                        committees::historic_committees(&state_id, &query, &chain)?
+                    {
+                        return Ok(api_types::ExecutionOptimisticFinalizedResponse {
+                            data,
+                            execution_optimistic: Some(execution_optimistic),
+                            finalized: Some(true),
+                        });
+                    }
+
                     let (data, execution_optimistic, finalized) = state_id
                         .map_state_and_execution_optimistic_and_finalized(
                             &chain,
@@ -941,50 +1068,11 @@ pub fn serve<T: BeaconChainTypes>(
                                     possibly_built_cache
                                 };
 
-                                // Use either the supplied slot or all slots in the epoch.
-                                let slots =
-                                    query.slot.map(|slot| vec![slot]).unwrap_or_else(|| {
-                                        epoch.slot_iter(T::EthSpec::slots_per_epoch()).collect()
-                                    });
-
-                                // Use either the supplied committee index or all available indices.
-                                let indices =
-                                    query.index.map(|index| vec![index]).unwrap_or_else(|| {
-                                        (0..committee_cache.committees_per_slot()).collect()
-                                    });
-
-                                let mut response = Vec::with_capacity(slots.len() * indices.len());
-
-                                for slot in slots {
-                                    // It is not acceptable to query with a slot that is not within the
-                                    // specified epoch.
-                                    if slot.epoch(T::EthSpec::slots_per_epoch()) != epoch {
-                                        return Err(warp_utils::reject::custom_bad_request(
-                                            format!("{} is not in epoch {}", slot, epoch),
-                                        ));
-                                    }
-
-                                    for &index in &indices {
-                                        let committee = committee_cache
-                                            .get_beacon_committee(slot, index)
-                                            .ok_or_else(|| {
-                                                warp_utils::reject::custom_bad_request(format!(
-                                                    "committee index {} does not exist in epoch {}",
-                                                    index, epoch
-                                                ))
-                                            })?;
-
-                                        response.push(api_types::CommitteeData {
-                                            index,
-                                            slot,
-                                            validators: committee
-                                                .committee
-                                                .iter()
-                                                .map(|i| *i as u64)
-                                                .collect(),
-                                        });
-                                    }
-                                }
+                                let response = committees::committees_from_cache::<T::EthSpec>(
+                                    &committee_cache,
+                                    epoch,
+                                    &query,
+                                )?;
 
                                 Ok((response, execution_optimistic, finalized))
                             },
@@ -1216,7 +1304,7 @@ pub fn serve<T: BeaconChainTypes>(
             |block_id: BlockId,
              task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
+                task_spawner.blocking_response_task(Priority::P1, move || {
                     let (root, execution_optimistic, finalized) = block_id.root(&chain)?;
                     // Ignore the second `execution_optimistic` since the first one has more
                     // information about the original request.
@@ -1237,11 +1325,16 @@ pub fn serve<T: BeaconChainTypes>(
                         },
                     };
 
-                    Ok(api_types::ExecutionOptimisticFinalizedResponse {
-                        execution_optimistic: Some(execution_optimistic),
-                        finalized: Some(finalized),
-                        data,
-                    })
+                    let response =
+                        warp::reply::json(&api_types::ExecutionOptimisticFinalizedResponse {
+                            execution_optimistic: Some(execution_optimistic),
+                            finalized: Some(finalized),
+                            data,
+                        });
+                    Ok(response_cache::immutable_if(
+                        response.into_response(),
+                        finalized && !execution_optimistic && block_id.is_fixed(),
+                    ))
                 })
             },
         );
@@ -1301,7 +1394,7 @@ pub fn serve<T: BeaconChainTypes>(
                   network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>,
                   log: Logger| {
                 task_spawner.spawn_async_with_rejection(Priority::P0, async move {
-                    let block_contents = PublishBlockRequest::<T::EthSpec>::from_ssz_bytes(
+                    let block_contents = PublishBlockRequest::<T::EthSpec>::from_ssz_bytes_by_fork(
                         &block_bytes,
                         consensus_version,
                     )
@@ -1374,7 +1467,7 @@ pub fn serve<T: BeaconChainTypes>(
                   network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>,
                   log: Logger| {
                 task_spawner.spawn_async_with_rejection(Priority::P0, async move {
-                    let block_contents = PublishBlockRequest::<T::EthSpec>::from_ssz_bytes(
+                    let block_contents = PublishBlockRequest::<T::EthSpec>::from_ssz_bytes_by_fork(
                         &block_bytes,
                         consensus_version,
                     )
@@ -1435,20 +1528,22 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("blinded_blocks"))
         .and(warp::path::end())
         .and(warp::body::bytes())
+        .and(consensus_version_header_filter)
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
         .and(network_tx_filter.clone())
         .and(log_filter.clone())
         .then(
             move |block_bytes: Bytes,
+                  consensus_version: ForkName,
                   task_spawner: TaskSpawner<T::EthSpec>,
                   chain: Arc<BeaconChain<T>>,
                   network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>,
                   log: Logger| {
                 task_spawner.spawn_async_with_rejection(Priority::P0, async move {
-                    let block = SignedBlindedBeaconBlock::<T::EthSpec>::from_ssz_bytes(
+                    let block = SignedBlindedBeaconBlock::<T::EthSpec>::from_ssz_bytes_by_fork(
                         &block_bytes,
-                        &chain.spec,
+                        consensus_version,
                     )
                     .map(Arc::new)
                     .map_err(|e| {
@@ -1504,6 +1599,7 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::query::<api_types::BroadcastValidationQuery>())
         .and(warp::path::end())
         .and(warp::body::bytes())
+        .and(consensus_version_header_filter)
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
         .and(network_tx_filter.clone())
@@ -1511,14 +1607,15 @@ pub fn serve<T: BeaconChainTypes>(
         .then(
             move |validation_level: api_types::BroadcastValidationQuery,
                   block_bytes: Bytes,
+                  consensus_version: ForkName,
                   task_spawner: TaskSpawner<T::EthSpec>,
                   chain: Arc<BeaconChain<T>>,
                   network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>,
                   log: Logger| {
                 task_spawner.spawn_async_with_rejection(Priority::P0, async move {
-                    let block = SignedBlindedBeaconBlock::<T::EthSpec>::from_ssz_bytes(
+                    let block = SignedBlindedBeaconBlock::<T::EthSpec>::from_ssz_bytes_by_fork(
                         &block_bytes,
-                        &chain.spec,
+                        consensus_version,
                     )
                     .map(Arc::new)
                     .map_err(|e| {
@@ -1596,6 +1693,12 @@ pub fn serve<T: BeaconChainTypes>(
                         .map(|res| warp::reply::json(&res).into_response()),
                     }
                     .map(|resp| add_consensus_version_header(resp, fork_name))
+                    .map(|resp| {
+                        response_cache::immutable_if(
+                            resp,
+                            finalized && !execution_optimistic && block_id.is_fixed(),
+                        )
+                    })
                 })
             },
         );
@@ -1708,6 +1811,12 @@ pub fn serve<T: BeaconChainTypes>(
                         }
                     }
                     .map(|resp| add_consensus_version_header(resp, fork_name))
+                    .map(|resp| {
+                        response_cache::immutable_if(
+                            resp,
+                            finalized && !execution_optimistic && block_id.is_fixed(),
+                        )
+                    })
                 })
             },
         );
@@ -1756,6 +1865,65 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // POST beacon/blob_sidecars
+    let post_blobs = eth_v1
+        .and(warp::path("beacon"))
+        .and(warp::path("blob_sidecars"))
+        .and(warp::path::end())
+        .and(warp_utils::json::json())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
+        .then(
+            |requests: Vec<api_types::BlobSidecarsByRootRequest>,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>,
+             accept_header: Option<api_types::Accept>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
+                    if requests.len() as u64 > chain.spec.max_request_blocks_deneb {
+                        return Err(warp_utils::reject::custom_bad_request(format!(
+                            "at most {} blocks may be requested",
+                            chain.spec.max_request_blocks_deneb
+                        )));
+                    }
+
+                    // Unknown blocks are omitted, as for the blobs by root RPC.
+                    let mut blob_sidecars = vec![];
+                    for request in requests {
+                        let blob_sidecar_list = chain
+                            .get_blobs(&request.block_root)
+                            .map_err(warp_utils::reject::beacon_chain_error)?;
+                        blob_sidecars.extend(blob_sidecar_list.into_iter().filter(
+                            |blob_sidecar| {
+                                request.indices.as_ref().map_or(true, |indices| {
+                                    indices
+                                        .iter()
+                                        .any(|index| index.value == blob_sidecar.index)
+                                })
+                            },
+                        ));
+                    }
+
+                    match accept_header {
+                        Some(api_types::Accept::Ssz) => Response::builder()
+                            .status(200)
+                            .body(blob_sidecars.as_ssz_bytes().into())
+                            .map(|res: Response<Body>| add_ssz_content_type_header(res))
+                            .map_err(|e| {
+                                warp_utils::reject::custom_server_error(format!(
+                                    "failed to create response: {}",
+                                    e
+                                ))
+                            }),
+                        _ => Ok(warp::reply::json(&api_types::GenericResponse::from(
+                            blob_sidecars,
+                        ))
+                        .into_response()),
+                    }
+                })
+            },
+        );
+
     /*
      * beacon/pool
      */
@@ -2484,6 +2652,25 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET beacon/light_client/updates
+    let get_beacon_light_client_updates = beacon_light_client_path
+        .clone()
+        .and(task_spawner_filter.clone())
+        .and(warp::path("updates"))
+        .and(warp::path::end())
+        .and(warp::query::<api_types::LightClientUpdatesQuery>())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
+        .then(
+            |chain: Arc<BeaconChain<T>>,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             query: api_types::LightClientUpdatesQuery,
+             accept_header: Option<api_types::Accept>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
+                    light_client::get_light_client_updates(query, &chain, accept_header)
+                })
+            },
+        );
+
     /*
      * beacon/rewards
      */
@@ -2600,14 +2787,20 @@ pub fn serve<T: BeaconChainTypes>(
     let get_config_spec = config_path
         .and(warp::path("spec"))
         .and(warp::path::end())
+        .and(warp::header::optional::<String>("if-none-match"))
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
         .then(
-            move |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
-                task_spawner.blocking_json_task(Priority::P0, move || {
+            move |if_none_match: Option<String>,
+                  task_spawner: TaskSpawner<T::EthSpec>,
+                  chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_response_task(Priority::P0, move || {
                     let config_and_preset =
                         ConfigAndPreset::from_chain_spec::<T::EthSpec>(&chain.spec, spec_fork_name);
-                    Ok(api_types::GenericResponse::from(config_and_preset))
+                    etag::json_with_etag(
+                        &api_types::GenericResponse::from(config_and_preset),
+                        if_none_match,
+                    )
                 })
             },
         );
@@ -2650,53 +2843,42 @@ pub fn serve<T: BeaconChainTypes>(
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
         .then(
-            |endpoint_version: EndpointVersion,
-             state_id: StateId,
-             accept_header: Option<api_types::Accept>,
-             task_spawner: TaskSpawner<T::EthSpec>,
-             chain: Arc<BeaconChain<T>>| {
-                task_spawner.blocking_response_task(Priority::P1, move || match accept_header {
-                    Some(api_types::Accept::Ssz) => {
+            move |endpoint_version: EndpointVersion,
+                  state_id: StateId,
+                  accept_header: Option<api_types::Accept>,
+                  task_spawner: TaskSpawner<T::EthSpec>,
+                  chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
+                    let (state, execution_optimistic, finalized, replay_depth) =
+                        state_id.state_with_replay(&chain, historic_state_replay_limit)?;
+                    let fork_name = state
+                        .fork_name(&chain.spec)
+                        .map_err(inconsistent_fork_rejection)?;
+                    match accept_header {
                         // We can ignore the optimistic status for the "fork" since it's a
                         // specification constant that doesn't change across competing heads of the
                         // beacon chain.
-                        let (state, _execution_optimistic, _finalized) = state_id.state(&chain)?;
-                        let fork_name = state
-                            .fork_name(&chain.spec)
-                            .map_err(inconsistent_fork_rejection)?;
-                        Response::builder()
+                        Some(api_types::Accept::Ssz) => Response::builder()
                             .status(200)
                             .body(state.as_ssz_bytes().into())
                             .map(|res: Response<Body>| add_ssz_content_type_header(res))
-                            .map(|resp: warp::reply::Response| {
-                                add_consensus_version_header(resp, fork_name)
-                            })
                             .map_err(|e| {
                                 warp_utils::reject::custom_server_error(format!(
                                     "failed to create response: {}",
                                     e
                                 ))
-                            })
+                            }),
+                        _ => execution_optimistic_finalized_fork_versioned_response(
+                            endpoint_version,
+                            fork_name,
+                            execution_optimistic,
+                            finalized,
+                            &state,
+                        )
+                        .map(|res| warp::reply::json(&res).into_response()),
                     }
-                    _ => state_id.map_state_and_execution_optimistic_and_finalized(
-                        &chain,
-                        |state, execution_optimistic, finalized| {
-                            let fork_name = state
-                                .fork_name(&chain.spec)
-                                .map_err(inconsistent_fork_rejection)?;
-                            let res = execution_optimistic_finalized_fork_versioned_response(
-                                endpoint_version,
-                                fork_name,
-                                execution_optimistic,
-                                finalized,
-                                &state,
-                            )?;
-                            Ok(add_consensus_version_header(
-                                warp::reply::json(&res).into_response(),
-                                fork_name,
-                            ))
-                        },
-                    ),
+                    .map(|resp| add_consensus_version_header(resp, fork_name))
+                    .map(|resp| add_replay_depth_header(resp, replay_depth))
                 })
             },
         );
@@ -2741,53 +2923,20 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
-    // GET debug/fork_choice
+    // GET debug/fork_choice?ancestor
     let get_debug_fork_choice = eth_v1
         .and(warp::path("debug"))
         .and(warp::path("fork_choice"))
         .and(warp::path::end())
+        .and(warp::query::<api_types::ForkChoiceQuery>())
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
         .then(
-            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+            |query: api_types::ForkChoiceQuery,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
                 task_spawner.blocking_json_task(Priority::P1, move || {
-                    let beacon_fork_choice = chain.canonical_head.fork_choice_read_lock();
-
-                    let proto_array = beacon_fork_choice.proto_array().core_proto_array();
-
-                    let fork_choice_nodes = proto_array
-                        .nodes
-                        .iter()
-                        .map(|node| {
-                            let execution_status = if node.execution_status.is_execution_enabled() {
-                                Some(node.execution_status.to_string())
-                            } else {
-                                None
-                            };
-
-                            ForkChoiceNode {
-                                slot: node.slot,
-                                block_root: node.root,
-                                parent_root: node
-                                    .parent
-                                    .and_then(|index| proto_array.nodes.get(index))
-                                    .map(|parent| parent.root),
-                                justified_epoch: node.justified_checkpoint.epoch,
-                                finalized_epoch: node.finalized_checkpoint.epoch,
-                                weight: node.weight,
-                                validity: execution_status,
-                                execution_block_hash: node
-                                    .execution_status
-                                    .block_hash()
-                                    .map(|block_hash| block_hash.into_root()),
-                            }
-                        })
-                        .collect::<Vec<_>>();
-                    Ok(ForkChoice {
-                        justified_checkpoint: proto_array.justified_checkpoint,
-                        finalized_checkpoint: proto_array.finalized_checkpoint,
-                        fork_choice_nodes,
-                    })
+                    debug_fork_choice::get_fork_choice(&chain, query)
                 })
             },
         );
@@ -3138,9 +3287,21 @@ pub fn serve<T: BeaconChainTypes>(
              task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>,
              log: Logger| {
-                task_spawner.blocking_json_task(Priority::P0, move || {
+                task_spawner.blocking_response_task(Priority::P0, move || {
                     not_synced_filter?;
-                    proposer_duties::proposer_duties(epoch, &chain, &log)
+                    let duties = proposer_duties::proposer_duties(epoch, &chain, &log)?;
+                    // Duties of finalized epochs can't change.
+                    let finalized_epoch = chain
+                        .canonical_head
+                        .cached_head()
+                        .finalized_checkpoint()
+                        .epoch;
+                    let is_immutable =
+                        epoch < finalized_epoch && duties.execution_optimistic == Some(false);
+                    Ok(response_cache::immutable_if(
+                        warp::reply::json(&duties).into_response(),
+                        is_immutable,
+                    ))
                 })
             },
         );
@@ -3566,13 +3727,18 @@ pub fn serve<T: BeaconChainTypes>(
              chain: Arc<BeaconChain<T>>,
              log: Logger| {
                 task_spawner.blocking_json_task(Priority::P0, move || {
+                    // Take the lock once for the whole batch, a VC may send a subscription for
+                    // each of thousands of validators in one request.
+                    let mut validator_monitor = chain.validator_monitor.write();
                     let subscriptions: std::collections::BTreeSet<_> = subscriptions
                         .iter()
                         .map(|subscription| {
-                            chain
-                                .validator_monitor
-                                .write()
+                            validator_monitor
                                 .auto_register_local_validator(subscription.validator_index);
+                            validator_monitor.register_attestation_duty(
+                                subscription.validator_index,
+                                subscription.slot,
+                            );
                             api_types::ValidatorSubscription {
                                 attestation_committee_index: subscription.committee_index,
                                 slot: subscription.slot,
@@ -3581,6 +3747,7 @@ pub fn serve<T: BeaconChainTypes>(
                             }
                         })
                         .collect();
+                    drop(validator_monitor);
                     let message =
                         ValidatorSubscriptionMessage::AttestationSubscribe { subscriptions };
                     if let Err(e) = validator_subscription_tx.try_send(message) {
@@ -3767,22 +3934,18 @@ pub fn serve<T: BeaconChainTypes>(
                         // they have frequent timeouts). Spawn a new task and
                         // send the response back to our original HTTP request
                         // task via a channel.
+                        let execution_layer = execution_layer.clone();
                         let builder_future = async move {
-                            let arc_builder = chain
-                                .execution_layer
-                                .as_ref()
-                                .ok_or(BeaconChainError::ExecutionLayerMissing)
-                                .map_err(warp_utils::reject::beacon_chain_error)?
-                                .builder();
-                            let builder = arc_builder
-                                .as_ref()
-                                .ok_or(BeaconChainError::BuilderMissing)
-                                .map_err(warp_utils::reject::beacon_chain_error)?;
-                            builder
+                            execution_layer
                                 .post_builder_validators(&filtered_registration_data)
                                 .await
-                                .map(|resp| warp::reply::json(&resp).into_response())
+                                .map(|()| warp::reply::json(&()).into_response())
                                 .map_err(|e| {
+                                    if let ExecutionLayerError::NoPayloadBuilder = e {
+                                        return warp_utils::reject::beacon_chain_error(
+                                            BeaconChainError::BuilderMissing,
+                                        );
+                                    }
                                     warn!(
                                         log,
                                         "Relay error when registering validator(s)";
@@ -3791,7 +3954,10 @@ pub fn serve<T: BeaconChainTypes>(
                                     );
                                     // Forward the HTTP status code if we are able to, otherwise fall back
                                     // to a server error.
-                                    if let eth2::Error::ServerMessage(message) = e {
+                                    if let ExecutionLayerError::Builder(
+                                        eth2::Error::ServerMessage(message),
+                                    ) = e
+                                    {
                                         if message.code == StatusCode::BAD_REQUEST.as_u16() {
                                             return warp_utils::reject::custom_bad_request(
                                                 message.message,
@@ -3847,29 +4013,30 @@ pub fn serve<T: BeaconChainTypes>(
              validator_subscription_tx: Sender<ValidatorSubscriptionMessage>,
              task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>,
-             log: Logger
-             | {
+             log: Logger| {
                 task_spawner.blocking_json_task(Priority::P0, move || {
-                    for subscription in subscriptions {
-                        chain
-                            .validator_monitor
-                            .write()
+                    let mut validator_monitor = chain.validator_monitor.write();
+                    for subscription in &subscriptions {
+                        validator_monitor
                             .auto_register_local_validator(subscription.validator_index);
+                    }
+                    drop(validator_monitor);
 
-                        let message = ValidatorSubscriptionMessage::SyncCommitteeSubscribe {
-                                subscriptions: vec![subscription],
-                            };
-                        if let Err(e) = validator_subscription_tx.try_send(message) {
-                            warn!(
-                                log,
-                                "Unable to process sync subscriptions";
-                                "info" => "the host may be overloaded or resource-constrained",
-                                "error" => ?e
-                            );
-                            return Err(warp_utils::reject::custom_server_error(
-                                "unable to queue subscription, host may be overloaded or shutting down".to_string(),
-                            ));
-                        }
+                    // Queue the whole batch as a single message, so that a large batch can't
+                    // exhaust the capacity of the subscription channel.
+                    let message =
+                        ValidatorSubscriptionMessage::SyncCommitteeSubscribe { subscriptions };
+                    if let Err(e) = validator_subscription_tx.try_send(message) {
+                        warn!(
+                            log,
+                            "Unable to process sync subscriptions";
+                            "info" => "the host may be overloaded or resource-constrained",
+                            "error" => ?e
+                        );
+                        return Err(warp_utils::reject::custom_server_error(
+                            "unable to queue subscription, host may be overloaded or shutting down"
+                                .to_string(),
+                        ));
                     }
 
                     Ok(())
@@ -3967,6 +4134,79 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/duties/proposer/{epoch}
+    let get_lighthouse_duties_proposer = warp::path("lighthouse")
+        .and(warp::path("duties"))
+        .and(warp::path("proposer"))
+        .and(warp::path::param::<Epoch>().or_else(|_| async {
+            Err(warp_utils::reject::custom_bad_request(
+                "Invalid epoch".to_string(),
+            ))
+        }))
+        .and(warp::path::end())
+        .and(not_while_syncing_filter.clone())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .and(log_filter.clone())
+        .then(
+            |epoch: Epoch,
+             not_synced_filter: Result<(), Rejection>,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>,
+             log: Logger| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    not_synced_filter?;
+                    proposer_duties::proposer_duties_lookahead(epoch, &chain, &log)
+                })
+            },
+        );
+
+    // GET lighthouse/proof/state/{state_id}?gindex
+    let get_lighthouse_proof_state = warp::path("lighthouse")
+        .and(warp::path("proof"))
+        .and(warp::path("state"))
+        .and(warp::path::param::<StateId>().or_else(|_| async {
+            Err(warp_utils::reject::custom_bad_request(
+                "Invalid state ID".to_string(),
+            ))
+        }))
+        .and(warp::path::end())
+        .and(multi_key_query::<eth2::lighthouse::MerkleProofQuery>())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |state_id: StateId,
+             query_res: Result<eth2::lighthouse::MerkleProofQuery, warp::Rejection>,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let query = query_res?;
+                    merkle_proofs::state_proof(state_id, query, &chain)
+                })
+            },
+        );
+
+    // GET lighthouse/proof/block/{block_id}?gindex
+    let get_lighthouse_proof_block = warp::path("lighthouse")
+        .and(warp::path("proof"))
+        .and(warp::path("block"))
+        .and(block_id_or_err)
+        .and(warp::path::end())
+        .and(multi_key_query::<eth2::lighthouse::MerkleProofQuery>())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |block_id: BlockId,
+             query_res: Result<eth2::lighthouse::MerkleProofQuery, warp::Rejection>,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let query = query_res?;
+                    merkle_proofs::block_proof(block_id, query, &chain)
+                })
+            },
+        );
+
     // GET lighthouse/health
     let get_lighthouse_health = warp::path("lighthouse")
         .and(warp::path("health"))
@@ -3980,6 +4220,44 @@ pub fn serve<T: BeaconChainTypes>(
             })
         });
 
+    // GET lighthouse/health/subsystems
+    let get_lighthouse_health_subsystems = warp::path("lighthouse")
+        .and(warp::path("health"))
+        .and(warp::path("subsystems"))
+        .and(warp::path::end())
+        .and(multi_key_query::<eth2::lighthouse::SubsystemsHealthQuery>())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .and(network_globals.clone())
+        .then(
+            |query_res: Result<eth2::lighthouse::SubsystemsHealthQuery, warp::Rejection>,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>| async move {
+                let el_offline = match &chain.execution_layer {
+                    Some(el) => Some(el.is_offline_or_erroring().await),
+                    None => None,
+                };
+
+                task_spawner
+                    .blocking_response_task(Priority::P0, move || {
+                        let health = subsystem_health::observe_subsystems_health(
+                            query_res?,
+                            el_offline,
+                            &chain,
+                            &network_globals,
+                        );
+                        let status = StatusCode::from_u16(health.code)
+                            .map_err(|e| warp_utils::reject::custom_server_error(e.to_string()))?;
+                        Ok(warp::reply::with_status(
+                            warp::reply::json(&api_types::GenericResponse::from(health)),
+                            status,
+                        ))
+                    })
+                    .await
+            },
+        );
+
     // GET lighthouse/ui/health
     let get_lighthouse_ui_health = warp::path("lighthouse")
         .and(warp::path("ui"))
@@ -4061,6 +4339,23 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/ui/validator_issues
+    let get_lighthouse_ui_validator_issues = warp::path("lighthouse")
+        .and(warp::path("ui"))
+        .and(warp::path("validator_issues"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    Ok(api_types::GenericResponse::from(ui::get_validator_issues(
+                        chain,
+                    )))
+                })
+            },
+        );
+
     // GET lighthouse/syncing
     let get_lighthouse_syncing = warp::path("lighthouse")
         .and(warp::path("syncing"))
@@ -4089,6 +4384,114 @@ pub fn serve<T: BeaconChainTypes>(
             })
         });
 
+    // GET lighthouse/enr
+    let get_lighthouse_enr = warp::path("lighthouse")
+        .and(warp::path("enr"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(network_globals.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let enr = network_globals.local_enr();
+                    Ok(api_types::GenericResponse::from(
+                        eth2::lighthouse::LocalEnr {
+                            enr: enr.to_base64(),
+                            seq: enr.seq(),
+                            addresses: EnrAddresses::from_enr(&enr),
+                        },
+                    ))
+                })
+            },
+        );
+
+    // POST lighthouse/enr
+    let post_lighthouse_enr = warp::path("lighthouse")
+        .and(warp::path("enr"))
+        .and(warp::path::end())
+        .and(warp_utils::json::json())
+        .and(task_spawner_filter.clone())
+        .and(network_globals.clone())
+        .and(network_tx_filter.clone())
+        .then(
+            |update: EnrAddresses,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>,
+             network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P0, move || {
+                    if update.is_empty() {
+                        return Err(warp_utils::reject::custom_bad_request(
+                            "no address or port to update".to_string(),
+                        ));
+                    }
+                    // Check the update can be applied before it is sent to the network service,
+                    // which applies it asynchronously.
+                    let current = EnrAddresses::from_enr(&network_globals.local_enr());
+                    update
+                        .udp_socket_updates(&current)
+                        .map_err(warp_utils::reject::custom_bad_request)?;
+                    publish_network_message(
+                        &network_tx,
+                        NetworkMessage::UpdateEnrAddresses { update },
+                    )
+                })
+            },
+        );
+
+    // GET lighthouse/logging
+    let get_lighthouse_logging = warp::path("lighthouse")
+        .and(warp::path("logging"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(log_filter_handle_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, log_filter_handle: Option<LogFilterHandle>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    log_level::get_log_level(log_filter_handle)
+                        .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
+    // POST lighthouse/logging
+    let post_lighthouse_logging = warp::path("lighthouse")
+        .and(warp::path("logging"))
+        .and(warp::path::end())
+        .and(warp_utils::json::json())
+        .and(task_spawner_filter.clone())
+        .and(log_filter_handle_filter)
+        .and(log_filter.clone())
+        .then(
+            |request: eth2::lighthouse::LogFilterData,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             log_filter_handle: Option<LogFilterHandle>,
+             log: Logger| {
+                task_spawner.blocking_json_task(Priority::P0, move || {
+                    log_level::set_log_level(request, log_filter_handle, log)
+                })
+            },
+        );
+
+    // GET lighthouse/logging/recent
+    let get_lighthouse_logging_recent = warp::path("lighthouse")
+        .and(warp::path("logging"))
+        .and(warp::path("recent"))
+        .and(warp::path::end())
+        .and(warp::query::<eth2::lighthouse::RecentLogsQuery>())
+        .and(task_spawner_filter.clone())
+        .and(recent_logs_filter)
+        .then(
+            |query: eth2::lighthouse::RecentLogsQuery,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             recent_logs: Option<RecentLogs>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    log_level::get_recent_logs(recent_logs, query.limit)
+                        .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
     // GET lighthouse/peers
     let get_lighthouse_peers = warp::path("lighthouse")
         .and(warp::path("peers"))
@@ -4112,6 +4515,67 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // POST lighthouse/peers/{peer_id}/{disconnect,ban,unban}
+    let post_lighthouse_peers_action = warp::path("lighthouse")
+        .and(warp::path("peers"))
+        .and(warp::path::param::<String>())
+        .and(warp::path::param::<String>())
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(network_globals.clone())
+        .and(network_tx_filter.clone())
+        .and(log_filter.clone())
+        .then(
+            |peer_id: String,
+             action: String,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>,
+             network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>,
+             log: Logger| {
+                task_spawner.blocking_json_task(Priority::P0, move || {
+                    let peer_id = peer_id.parse::<PeerId>().map_err(|e| {
+                        warp_utils::reject::custom_bad_request(format!("invalid peer id: {e}"))
+                    })?;
+                    let is_connected = {
+                        let peers = network_globals.peers.read();
+                        if peers.peer_info(&peer_id).is_none() {
+                            return Err(warp_utils::reject::custom_not_found(format!(
+                                "unknown peer {peer_id}"
+                            )));
+                        }
+                        peers.is_connected(&peer_id)
+                    };
+
+                    let message = match action.as_str() {
+                        "disconnect" if !is_connected => {
+                            return Err(warp_utils::reject::custom_bad_request(format!(
+                                "peer {peer_id} is not connected"
+                            )));
+                        }
+                        "disconnect" => NetworkMessage::DisconnectPeer { peer_id },
+                        "ban" => NetworkMessage::GoodbyePeer {
+                            peer_id,
+                            reason: GoodbyeReason::Banned,
+                            source: ReportSource::HttpApi,
+                        },
+                        "unban" => NetworkMessage::UnbanPeer { peer_id },
+                        other => {
+                            return Err(warp_utils::reject::custom_not_found(format!(
+                                "unknown peer action {other:?}, expected disconnect, ban or unban"
+                            )));
+                        }
+                    };
+                    info!(
+                        log,
+                        "Peer action requested via HTTP API";
+                        "peer_id" => %peer_id,
+                        "action" => action,
+                    );
+                    publish_network_message(&network_tx, message)
+                })
+            },
+        );
+
     // GET lighthouse/peers/connected
     let get_lighthouse_peers_connected = warp::path("lighthouse")
         .and(warp::path("peers"))
@@ -4328,6 +4792,152 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // POST lighthouse/database/{operation}
+    //
+    // The `api_token_filter` requires the admin scope for this route, but without API tokens it
+    // would be open to anyone who can reach the server, so it is refused unless they are set.
+    let api_tokens_configured = ctx.config.api_tokens_file.is_some();
+    let post_lighthouse_database_maintenance = database_path
+        .and(warp::path::param::<eth2::lighthouse::DatabaseOperation>())
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            move |operation: eth2::lighthouse::DatabaseOperation,
+                  task_spawner: TaskSpawner<T::EthSpec>,
+                  chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    if !api_tokens_configured {
+                        return Err(warp_utils::reject::custom_forbidden(
+                            "database maintenance requires an admin API token, but no API \
+                             tokens are configured"
+                                .to_string(),
+                        ));
+                    }
+                    let job = chain.store_migrator.process_maintenance(operation);
+                    Ok(api_types::GenericResponse::from(job))
+                })
+            },
+        );
+
+    // GET lighthouse/database/jobs/{id}
+    let get_lighthouse_database_job = database_path
+        .and(warp::path("jobs"))
+        .and(warp::path::param::<u64>())
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |id: u64, task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    chain
+                        .store_migrator
+                        .database_job(id)
+                        .map(api_types::GenericResponse::from)
+                        .ok_or_else(|| {
+                            warp_utils::reject::custom_not_found(format!(
+                                "unknown database job {id}"
+                            ))
+                        })
+                })
+            },
+        );
+
+    // GET lighthouse/builder/bids
+    let get_lighthouse_builder_bids = warp::path("lighthouse")
+        .and(warp::path("builder"))
+        .and(warp::path("bids"))
+        .and(warp::query::<eth2::lighthouse::BuilderBidsQuery>())
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |query: eth2::lighthouse::BuilderBidsQuery,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let execution_layer = chain
+                        .execution_layer
+                        .as_ref()
+                        .ok_or(BeaconChainError::ExecutionLayerMissing)
+                        .map_err(warp_utils::reject::beacon_chain_error)?;
+                    Ok(api_types::GenericResponse::from(
+                        execution_layer.builder_bids(query.slot),
+                    ))
+                })
+            },
+        );
+
+    // GET lighthouse/builder/registrations/{pubkey}
+    let get_lighthouse_builder_registration = warp::path("lighthouse")
+        .and(warp::path("builder"))
+        .and(warp::path("registrations"))
+        .and(warp::path::param::<PublicKeyBytes>().or_else(|_| async {
+            Err(warp_utils::reject::custom_bad_request(
+                "Invalid validator pubkey".to_string(),
+            ))
+        }))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |pubkey: PublicKeyBytes,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let execution_layer = chain
+                        .execution_layer
+                        .as_ref()
+                        .ok_or(BeaconChainError::ExecutionLayerMissing)
+                        .map_err(warp_utils::reject::beacon_chain_error)?;
+                    execution_layer
+                        .forwarded_registration(&pubkey)
+                        .map(api_types::GenericResponse::from)
+                        .ok_or_else(|| {
+                            warp_utils::reject::custom_not_found(format!(
+                                "no registration has been forwarded for {pubkey}"
+                            ))
+                        })
+                })
+            },
+        );
+
+    // GET lighthouse/builder/circuit_breaker
+    let get_lighthouse_builder_circuit_breaker = warp::path("lighthouse")
+        .and(warp::path("builder"))
+        .and(warp::path("circuit_breaker"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let head_root = chain.canonical_head.cached_head().head_block_root();
+                    chain
+                        .builder_circuit_breaker(&head_root)
+                        .map(api_types::GenericResponse::from)
+                        .map_err(warp_utils::reject::beacon_chain_error)
+                })
+            },
+        );
+
+    // GET lighthouse/debug/blinded_blocks?start_slot,count
+    let get_lighthouse_debug_blinded_blocks = warp::path("lighthouse")
+        .and(warp::path("debug"))
+        .and(warp::path("blinded_blocks"))
+        .and(warp::query::<eth2::lighthouse::BlindedBlocksQuery>())
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |query, task_spawner: TaskSpawner<T::EthSpec>, chain| async move {
+                convert_rejection(
+                    blinded_blocks::stream_blinded_blocks(query, chain, task_spawner).await,
+                )
+                .await
+            },
+        );
+
     // GET lighthouse/analysis/block_rewards
     let get_lighthouse_block_rewards = warp::path("lighthouse")
         .and(warp::path("analysis"))
@@ -4337,11 +4947,14 @@ pub fn serve<T: BeaconChainTypes>(
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
         .and(log_filter.clone())
-        .then(|query, task_spawner: TaskSpawner<T::EthSpec>, chain, log| {
-            task_spawner.blocking_json_task(Priority::P1, move || {
-                block_rewards::get_block_rewards(query, chain, log)
-            })
-        });
+        .then(
+            |query, task_spawner: TaskSpawner<T::EthSpec>, chain, log| async move {
+                convert_rejection(
+                    block_rewards::stream_block_rewards(query, chain, task_spawner, log).await,
+                )
+                .await
+            },
+        );
 
     // POST lighthouse/analysis/block_rewards
     let post_lighthouse_block_rewards = warp::path("lighthouse")
@@ -4360,6 +4973,40 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // POST lighthouse/spec/diff
+    let post_lighthouse_spec_diff = warp::path("lighthouse")
+        .and(warp::path("spec"))
+        .and(warp::path("diff"))
+        .and(warp::path::end())
+        .and(warp_utils::json::json())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |config: types::Config,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let other = config
+                        .apply_to_chain_spec::<T::EthSpec>(&chain.spec)
+                        .ok_or_else(|| {
+                            warp_utils::reject::custom_bad_request(format!(
+                                "config is not for the {} preset",
+                                T::EthSpec::spec_name()
+                            ))
+                        })?;
+                    Ok(api_types::GenericResponse::from(
+                        eth2::lighthouse::SpecDiff {
+                            differences: chain.spec.diff(&other),
+                            validation_errors: other
+                                .validate::<T::EthSpec>()
+                                .err()
+                                .unwrap_or_default(),
+                        },
+                    ))
+                })
+            },
+        );
+
     // GET lighthouse/analysis/attestation_performance/{index}
     let get_lighthouse_attestation_performance = warp::path("lighthouse")
         .and(warp::path("analysis"))
@@ -4377,6 +5024,22 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // POST lighthouse/analysis/attestation_performance
+    let post_lighthouse_attestation_performance = warp::path("lighthouse")
+        .and(warp::path("analysis"))
+        .and(warp::path("attestation_performance"))
+        .and(warp::path::end())
+        .and(warp_utils::json::json())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |request, task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    attestation_performance::post_attestation_performance(request, chain)
+                })
+            },
+        );
+
     // GET lighthouse/analysis/block_packing_efficiency
     let get_lighthouse_block_packing_efficiency = warp::path("lighthouse")
         .and(warp::path("analysis"))
@@ -4393,6 +5056,54 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/analysis/graffiti
+    let get_lighthouse_graffiti_analysis = warp::path("lighthouse")
+        .and(warp::path("analysis"))
+        .and(warp::path("graffiti"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let head = chain.head_snapshot();
+                    Ok(chain.graffiti_analysis_cache.read().analyse(&head))
+                })
+            },
+        );
+
+    // GET lighthouse/validators/slashed
+    let get_lighthouse_validators_slashed = warp::path("lighthouse")
+        .and(warp::path("validators"))
+        .and(warp::path("slashed"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    validator_exits::slashed_validators(&chain)
+                        .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
+    // GET lighthouse/validators/exiting
+    let get_lighthouse_validators_exiting = warp::path("lighthouse")
+        .and(warp::path("validators"))
+        .and(warp::path("exiting"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    validator_exits::exiting_validators(&chain)
+                        .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
     // GET lighthouse/merge_readiness
     let get_lighthouse_merge_readiness = warp::path("lighthouse")
         .and(warp::path("merge_readiness"))
@@ -4412,6 +5123,37 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/events
+    let get_lighthouse_events = warp::path("lighthouse")
+        .and(warp::path("events"))
+        .and(warp::path::end())
+        .and(warp::ws())
+        .and(multi_key_query::<eth2::lighthouse::EventsWebSocketQuery>())
+        .and(chain_filter.clone())
+        .and(log_filter.clone())
+        .and_then(
+            |ws: warp::ws::Ws,
+             query_res: Result<eth2::lighthouse::EventsWebSocketQuery, warp::Rejection>,
+             chain: Arc<BeaconChain<T>>,
+             log: Logger| async move {
+                let query = query_res?;
+                if chain.event_handler.is_none() {
+                    return Err(warp_utils::reject::custom_server_error(
+                        "event handler was not initialized".to_string(),
+                    ));
+                }
+                let filter =
+                    EventFilter::new(query.validator_indices.as_deref(), query.slots.as_deref());
+                let topics = query.topics.unwrap_or_default();
+                let upgrade = ws
+                    .max_message_size(events_websocket::MAX_REQUEST_SIZE)
+                    .on_upgrade(move |socket| {
+                        events_websocket::serve_events(socket, chain, topics, filter, log)
+                    });
+                Ok::<_, warp::Rejection>(compression::uncompressed(upgrade.into_response()))
+            },
+        );
+
     let get_events = eth_v1
         .and(warp::path("events"))
         .and(warp::path::end())
@@ -4424,63 +5166,22 @@ pub fn serve<T: BeaconChainTypes>(
              chain: Arc<BeaconChain<T>>| {
                 task_spawner.blocking_response_task(Priority::P0, move || {
                     let topics = topics_res?;
+                    let filter = Arc::new(EventFilter::from_query(&topics));
                     // for each topic subscribed spawn a new subscription
                     let mut receivers = Vec::with_capacity(topics.topics.len());
 
                     if let Some(event_handler) = chain.event_handler.as_ref() {
                         for topic in topics.topics {
-                            let receiver = match topic {
-                                api_types::EventTopic::Head => event_handler.subscribe_head(),
-                                api_types::EventTopic::Block => event_handler.subscribe_block(),
-                                api_types::EventTopic::BlobSidecar => {
-                                    event_handler.subscribe_blob_sidecar()
-                                }
-                                api_types::EventTopic::Attestation => {
-                                    event_handler.subscribe_attestation()
-                                }
-                                api_types::EventTopic::VoluntaryExit => {
-                                    event_handler.subscribe_exit()
-                                }
-                                api_types::EventTopic::FinalizedCheckpoint => {
-                                    event_handler.subscribe_finalized()
-                                }
-                                api_types::EventTopic::ChainReorg => {
-                                    event_handler.subscribe_reorgs()
-                                }
-                                api_types::EventTopic::ContributionAndProof => {
-                                    event_handler.subscribe_contributions()
-                                }
-                                api_types::EventTopic::PayloadAttributes => {
-                                    event_handler.subscribe_payload_attributes()
-                                }
-                                api_types::EventTopic::LateHead => {
-                                    event_handler.subscribe_late_head()
-                                }
-                                api_types::EventTopic::LightClientFinalityUpdate => {
-                                    event_handler.subscribe_light_client_finality_update()
-                                }
-                                api_types::EventTopic::LightClientOptimisticUpdate => {
-                                    event_handler.subscribe_light_client_optimistic_update()
-                                }
-                                api_types::EventTopic::BlockReward => {
-                                    event_handler.subscribe_block_reward()
-                                }
-                                api_types::EventTopic::AttesterSlashing => {
-                                    event_handler.subscribe_attester_slashing()
-                                }
-                                api_types::EventTopic::ProposerSlashing => {
-                                    event_handler.subscribe_proposer_slashing()
-                                }
-                                api_types::EventTopic::BlsToExecutionChange => {
-                                    event_handler.subscribe_bls_to_execution_change()
-                                }
-                                api_types::EventTopic::BlockGossip => {
-                                    event_handler.subscribe_block_gossip()
-                                }
-                            };
+                            let receiver = event_handler.subscribe(topic);
 
+                            let filter = filter.clone();
+                            let chain = chain.clone();
                             receivers.push(
                                 BroadcastStream::new(receiver)
+                                    .filter(move |msg| match msg {
+                                        Ok(data) => filter.matches(&chain, data),
+                                        Err(_) => true,
+                                    })
                                     .map(|msg| {
                                         match msg {
                                             Ok(data) => Event::default()
@@ -4562,9 +5263,33 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // Responses which can never change are cached, and served from the cache when possible.
+    let response_cache = Arc::new(ResponseCache::new(
+        config.response_cache_size_mb.saturating_mul(1024 * 1024),
+    ));
+    let response_cache_filter = warp::any().map(move || response_cache.clone());
+    let response_cache_key = warp::path::full()
+        .and(warp::query::raw().or(warp::any().map(String::new)).unify())
+        .and(warp::header::optional::<String>("accept"))
+        .map(ResponseCacheKey::new);
+    let get_cached_response = response_cache_key
+        .clone()
+        .and(warp::header::optional::<String>("if-none-match"))
+        .and(response_cache_filter.clone())
+        .and_then(
+            |key: ResponseCacheKey,
+             if_none_match: Option<String>,
+             response_cache: Arc<ResponseCache>| async move {
+                response_cache
+                    .get(&key, if_none_match.as_deref())
+                    .ok_or_else(warp::reject::not_found)
+            },
+        );
+
     // Define the ultimate set of routes that will be provided to the server.
     // Use `uor` rather than `or` in order to simplify types (see `UnifyingOrFilter`).
-    let routes = warp::get()
+    let get_routes = response_cache_key
+        .and(response_cache_filter)
         .and(
             get_beacon_genesis
                 .uor(get_beacon_state_root)
@@ -4609,11 +5334,19 @@ pub fn serve<T: BeaconChainTypes>(
                 .uor(get_validator_attestation_data)
                 .uor(get_validator_aggregate_attestation)
                 .uor(get_validator_sync_committee_contribution)
+                .uor(get_lighthouse_duties_proposer)
+                .uor(get_lighthouse_proof_state)
+                .uor(get_lighthouse_proof_block)
                 .uor(get_lighthouse_health)
+                .uor(get_lighthouse_health_subsystems)
                 .uor(get_lighthouse_ui_health)
                 .uor(get_lighthouse_ui_validator_count)
+                .uor(get_lighthouse_ui_validator_issues)
                 .uor(get_lighthouse_syncing)
                 .uor(get_lighthouse_nat)
+                .uor(get_lighthouse_enr)
+                .uor(get_lighthouse_logging)
+                .uor(get_lighthouse_logging_recent)
                 .uor(get_lighthouse_peers)
                 .uor(get_lighthouse_peers_connected)
                 .uor(get_lighthouse_proto_array)
@@ -4624,6 +5357,10 @@ pub fn serve<T: BeaconChainTypes>(
                 .uor(get_lighthouse_eth1_deposit_cache)
                 .uor(get_lighthouse_staking)
                 .uor(get_lighthouse_database_info)
+                .uor(get_lighthouse_database_job)
+                .uor(get_lighthouse_builder_bids)
+                .uor(get_lighthouse_builder_registration)
+                .uor(get_lighthouse_builder_circuit_breaker)
                 .uor(get_lighthouse_block_rewards)
                 .uor(get_lighthouse_attestation_performance)
                 .uor(
@@ -4638,56 +5375,89 @@ pub fn serve<T: BeaconChainTypes>(
                     enable(ctx.config.enable_light_client_server)
                         .and(get_beacon_light_client_bootstrap),
                 )
+                .uor(
+                    enable(ctx.config.enable_light_client_server)
+                        .and(get_beacon_light_client_updates),
+                )
                 .uor(get_lighthouse_block_packing_efficiency)
+                .uor(get_lighthouse_graffiti_analysis)
+                .uor(get_lighthouse_debug_blinded_blocks)
+                .uor(get_lighthouse_validators_slashed)
+                .uor(get_lighthouse_validators_exiting)
                 .uor(get_lighthouse_merge_readiness)
                 .uor(get_events)
+                .uor(get_lighthouse_events)
                 .uor(get_expected_withdrawals)
                 .uor(lighthouse_log_events.boxed())
                 .recover(warp_utils::reject::handle_rejection),
         )
+        .then(
+            |key: ResponseCacheKey, response_cache: Arc<ResponseCache>, reply| async move {
+                response_cache.store(key, Reply::into_response(reply)).await
+            },
+        );
+
+    let routes = warp::get()
+        .and(rate_limit_filter.clone())
+        .and(api_token_filter.clone())
+        .and(get_cached_response.uor(get_routes))
         .boxed()
         .uor(
-            warp::post().and(
-                warp::header::exact(CONTENT_TYPE_HEADER, SSZ_CONTENT_TYPE_HEADER)
-                    // Routes which expect `application/octet-stream` go within this `and`.
-                    .and(
-                        post_beacon_blocks_ssz
-                            .uor(post_beacon_blocks_v2_ssz)
-                            .uor(post_beacon_blinded_blocks_ssz)
-                            .uor(post_beacon_blinded_blocks_v2_ssz),
-                    )
-                    .uor(post_beacon_blocks)
-                    .uor(post_beacon_blinded_blocks)
-                    .uor(post_beacon_blocks_v2)
-                    .uor(post_beacon_blinded_blocks_v2)
-                    .uor(post_beacon_pool_attestations)
-                    .uor(post_beacon_pool_attester_slashings)
-                    .uor(post_beacon_pool_proposer_slashings)
-                    .uor(post_beacon_pool_voluntary_exits)
-                    .uor(post_beacon_pool_sync_committees)
-                    .uor(post_beacon_pool_bls_to_execution_changes)
-                    .uor(post_beacon_state_validators)
-                    .uor(post_beacon_state_validator_balances)
-                    .uor(post_beacon_rewards_attestations)
-                    .uor(post_beacon_rewards_sync_committee)
-                    .uor(post_validator_duties_attester)
-                    .uor(post_validator_duties_sync)
-                    .uor(post_validator_aggregate_and_proofs)
-                    .uor(post_validator_contribution_and_proofs)
-                    .uor(post_validator_beacon_committee_subscriptions)
-                    .uor(post_validator_sync_committee_subscriptions)
-                    .uor(post_validator_prepare_beacon_proposer)
-                    .uor(post_validator_register_validator)
-                    .uor(post_validator_liveness_epoch)
-                    .uor(post_lighthouse_liveness)
-                    .uor(post_lighthouse_database_reconstruct)
-                    .uor(post_lighthouse_block_rewards)
-                    .uor(post_lighthouse_ui_validator_metrics)
-                    .uor(post_lighthouse_ui_validator_info)
-                    .recover(warp_utils::reject::handle_rejection),
-            ),
+            warp::post()
+                .and(rate_limit_filter)
+                .and(api_token_filter)
+                .and(
+                    warp::header::exact(CONTENT_TYPE_HEADER, SSZ_CONTENT_TYPE_HEADER)
+                        // Routes which expect `application/octet-stream` go within this `and`.
+                        .and(
+                            post_beacon_blocks_ssz
+                                .uor(post_beacon_blocks_v2_ssz)
+                                .uor(post_beacon_blinded_blocks_ssz)
+                                .uor(post_beacon_blinded_blocks_v2_ssz),
+                        )
+                        .uor(post_beacon_blocks)
+                        .uor(post_beacon_blinded_blocks)
+                        .uor(post_beacon_blocks_v2)
+                        .uor(post_beacon_blinded_blocks_v2)
+                        .uor(post_blobs)
+                        .uor(post_beacon_pool_attestations)
+                        .uor(post_beacon_pool_attester_slashings)
+                        .uor(post_beacon_pool_proposer_slashings)
+                        .uor(post_beacon_pool_voluntary_exits)
+                        .uor(post_beacon_pool_sync_committees)
+                        .uor(post_beacon_pool_bls_to_execution_changes)
+                        .uor(post_beacon_state_validators)
+                        .uor(post_beacon_state_validator_balances)
+                        .uor(post_beacon_rewards_attestations)
+                        .uor(post_beacon_rewards_sync_committee)
+                        .uor(post_validator_duties_attester)
+                        .uor(post_validator_duties_sync)
+                        .uor(post_validator_aggregate_and_proofs)
+                        .uor(post_validator_contribution_and_proofs)
+                        .uor(post_validator_beacon_committee_subscriptions)
+                        .uor(post_validator_sync_committee_subscriptions)
+                        .uor(post_validator_prepare_beacon_proposer)
+                        .uor(post_validator_register_validator)
+                        .uor(post_validator_liveness_epoch)
+                        .uor(post_lighthouse_liveness)
+                        .uor(post_lighthouse_peers_action)
+                        .uor(post_lighthouse_enr)
+                        .uor(post_lighthouse_logging)
+                        .uor(post_lighthouse_database_reconstruct)
+                        .uor(post_lighthouse_database_maintenance)
+                        .uor(post_lighthouse_block_rewards)
+                        .uor(post_lighthouse_attestation_performance)
+                        .uor(post_lighthouse_spec_diff)
+                        .uor(post_lighthouse_ui_validator_metrics)
+                        .uor(post_lighthouse_ui_validator_info)
+                        .recover(warp_utils::reject::handle_rejection),
+                ),
         )
         .recover(warp_utils::reject::handle_rejection)
+        .and(warp::header::optional::<String>("Accept-Encoding"))
+        .then(|reply, accept_encoding: Option<String>| {
+            compression::compress_response(Reply::into_response(reply), accept_encoding)
+        })
         .with(slog_logging(log.clone()))
         .with(prometheus_metrics())
         // Add a `Server` header.
@@ -4698,15 +5468,24 @@ pub fn serve<T: BeaconChainTypes>(
     let http_socket: SocketAddr = SocketAddr::new(config.listen_addr, config.listen_port);
     let http_server: HttpServer = match config.tls_config {
         Some(tls_config) => {
-            let (socket, server) = warp::serve(routes)
+            let client_auth = tls_config.client_ca.is_some();
+            let mut tls_server = warp::serve(routes)
                 .tls()
                 .cert_path(tls_config.cert)
-                .key_path(tls_config.key)
-                .try_bind_with_graceful_shutdown(http_socket, async {
+                .key_path(tls_config.key);
+            if let Some(client_ca) = tls_config.client_ca {
+                tls_server = tls_server.client_auth_required_path(client_ca);
+            }
+            let (socket, server) =
+                tls_server.try_bind_with_graceful_shutdown(http_socket, async {
                     shutdown.await;
                 })?;
 
-            info!(log, "HTTP API is being served over TLS";);
+            info!(
+                log,
+                "HTTP API is being served over TLS";
+                "client_auth" => client_auth,
+            );
 
             (socket, Box::pin(server))
         }
@@ -0,0 +1,83 @@
+//! Serves the best `LightClientUpdate` of past sync committee periods.
+//!
+//! Updates are generated from the blocks and states in the database when first requested, so a
+//! light client can sync from any period since Altair, not only those seen since the node started.
+
+use crate::version::add_ssz_content_type_header;
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::types::{self as api_types, LightClientUpdatesQuery};
+use ssz::Encode;
+use types::fork_versioned_response::EmptyMetadata;
+use types::{ChainSpec, ForkName, ForkVersionedResponse, LightClientUpdate};
+use warp::http::Response;
+use warp::hyper::Body;
+use warp::Reply;
+
+/// The maximum number of updates which may be requested at once, as per the spec.
+pub const MAX_REQUEST_LIGHT_CLIENT_UPDATES: u64 = 128;
+
+/// Returns the updates of the consecutive periods in `query`, as a JSON list or as SSZ chunks.
+pub fn get_light_client_updates<T: BeaconChainTypes>(
+    query: LightClientUpdatesQuery,
+    chain: &BeaconChain<T>,
+    accept_header: Option<api_types::Accept>,
+) -> Result<Response<Body>, warp::Rejection> {
+    let count = query.count.min(MAX_REQUEST_LIGHT_CLIENT_UPDATES);
+    let updates = chain
+        .get_light_client_updates(query.start_period, count)
+        .map_err(|e| {
+            warp_utils::reject::custom_server_error(format!(
+                "Unable to obtain LightClientUpdate instances: {e:?}"
+            ))
+        })?;
+
+    match accept_header {
+        Some(api_types::Accept::Ssz) => {
+            let bytes = updates
+                .iter()
+                .flat_map(|(update, fork_name)| ssz_response_chunk(chain, update, *fork_name))
+                .collect::<Vec<u8>>();
+            Response::builder()
+                .status(200)
+                .body(bytes.into())
+                .map(|res: Response<Body>| add_ssz_content_type_header(res))
+                .map_err(|e| {
+                    warp_utils::reject::custom_server_error(format!(
+                        "failed to create response: {}",
+                        e
+                    ))
+                })
+        }
+        _ => {
+            let responses = updates
+                .into_iter()
+                .map(|(update, fork_name)| ForkVersionedResponse {
+                    version: Some(fork_name),
+                    metadata: EmptyMetadata {},
+                    data: update,
+                })
+                .collect::<Vec<_>>();
+            Ok(warp::reply::json(&responses).into_response())
+        }
+    }
+}
+
+/// Encodes `update` as a `response_chunk`: its length as a little-endian `u64`, followed by the
+/// fork digest of its fork and then the update itself.
+fn ssz_response_chunk<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    update: &LightClientUpdate<T::EthSpec>,
+    fork_name: ForkName,
+) -> Vec<u8> {
+    let fork_digest = ChainSpec::compute_fork_digest(
+        chain.spec.fork_version_for_name(fork_name),
+        chain.genesis_validators_root,
+    );
+    let payload = update.as_ssz_bytes();
+
+    let mut chunk = Vec::with_capacity(8 + fork_digest.len() + payload.len());
+    chunk.extend_from_slice(&(payload.len() as u64).to_le_bytes());
+    chunk.extend_from_slice(&fork_digest);
+    chunk.extend(payload);
+    chunk
+}
@@ -0,0 +1,120 @@
+//! Handlers for the `lighthouse/proof` endpoints, which return Merkle multiproofs of the nodes of
+//! states and blocks.
+
+use crate::block_id::BlockId;
+use crate::state_id::StateId;
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::{MerkleMultiproof, MerkleProofQuery};
+use eth2::types::ExecutionOptimisticFinalizedResponse;
+use types::{BeaconStateError, Hash256};
+
+/// The maximum number of generalized indices which may be proven by a single request.
+const MAX_GINDICES: usize = 64;
+
+/// Returns the generalized indices requested by `query`, and those of the nodes which prove them.
+fn proof_indices(query: &MerkleProofQuery) -> Result<(Vec<usize>, Vec<usize>), warp::Rejection> {
+    let gindices = query.gindex.as_deref().unwrap_or_default();
+    if gindices.is_empty() {
+        return Err(warp_utils::reject::custom_bad_request(
+            "at least one gindex is required".to_string(),
+        ));
+    }
+    if gindices.len() > MAX_GINDICES {
+        return Err(warp_utils::reject::custom_bad_request(format!(
+            "at most {MAX_GINDICES} gindices may be requested"
+        )));
+    }
+    let gindices = gindices
+        .iter()
+        .map(|&gindex| usize::try_from(gindex).ok().filter(|&gindex| gindex > 0))
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| warp_utils::reject::custom_bad_request("invalid gindex".to_string()))?;
+    let helper_indices = merkle_proof::get_helper_indices(&gindices);
+    Ok((gindices, helper_indices))
+}
+
+fn node_error(e: BeaconStateError) -> warp::Rejection {
+    match e {
+        BeaconStateError::IndexNotSupported(gindex) => warp_utils::reject::custom_bad_request(
+            format!("proofs of gindex {gindex} are not supported"),
+        ),
+        e => warp_utils::reject::beacon_state_error(e),
+    }
+}
+
+fn multiproof(
+    root: Hash256,
+    gindices: Vec<usize>,
+    helper_indices: Vec<usize>,
+    nodes: impl FnOnce(&[usize]) -> Result<Vec<Hash256>, BeaconStateError>,
+) -> Result<MerkleMultiproof, warp::Rejection> {
+    // The leaves and the proof usually lie within the same fields, so they are fetched together
+    // in order that the chunks of each list or vector are only computed once.
+    let indices = gindices
+        .iter()
+        .chain(&helper_indices)
+        .copied()
+        .collect::<Vec<_>>();
+    let mut leaves = nodes(&indices).map_err(node_error)?;
+    let proof = leaves.split_off(gindices.len());
+    Ok(MerkleMultiproof {
+        root,
+        leaves,
+        proof,
+        gindices: gindices.into_iter().map(|index| index as u64).collect(),
+        helper_indices: helper_indices
+            .into_iter()
+            .map(|index| index as u64)
+            .collect(),
+    })
+}
+
+/// Returns a multiproof of the nodes of the state identified by `state_id` at the generalized
+/// indices of `query`.
+pub fn state_proof<T: BeaconChainTypes>(
+    state_id: StateId,
+    query: MerkleProofQuery,
+    chain: &BeaconChain<T>,
+) -> Result<ExecutionOptimisticFinalizedResponse<MerkleMultiproof>, warp::Rejection> {
+    let (gindices, helper_indices) = proof_indices(&query)?;
+    let (mut state, execution_optimistic, finalized) = state_id.state(chain)?;
+    let root = state
+        .canonical_root()
+        .map_err(warp_utils::reject::beacon_state_error)?;
+    let data = multiproof(root, gindices, helper_indices, |indices| {
+        state.merkle_nodes(indices)
+    })?;
+    Ok(ExecutionOptimisticFinalizedResponse {
+        execution_optimistic: Some(execution_optimistic),
+        finalized: Some(finalized),
+        data,
+    })
+}
+
+/// Returns a multiproof of the nodes of the block identified by `block_id` at the generalized
+/// indices of `query`.
+pub fn block_proof<T: BeaconChainTypes>(
+    block_id: BlockId,
+    query: MerkleProofQuery,
+    chain: &BeaconChain<T>,
+) -> Result<ExecutionOptimisticFinalizedResponse<MerkleMultiproof>, warp::Rejection> {
+    let (gindices, helper_indices) = proof_indices(&query)?;
+    // A blinded block has the same tree as the full block, down to the fields of its body.
+    let (block, execution_optimistic, finalized) = block_id.blinded_block(chain)?;
+    let data = multiproof(
+        block.canonical_root(),
+        gindices,
+        helper_indices,
+        |indices| {
+            indices
+                .iter()
+                .map(|&index| block.message().merkle_node(index))
+                .collect()
+        },
+    )?;
+    Ok(ExecutionOptimisticFinalizedResponse {
+        execution_optimistic: Some(execution_optimistic),
+        finalized: Some(finalized),
+        data,
+    })
+}
@@ -3,12 +3,14 @@ use crate::{
     version::{
         add_consensus_block_value_header, add_consensus_version_header,
         add_execution_payload_blinded_header, add_execution_payload_value_header,
-        add_ssz_content_type_header, fork_versioned_response, inconsistent_fork_rejection,
+        add_payload_values_headers, add_ssz_content_type_header, fork_versioned_response,
+        inconsistent_fork_rejection,
     },
 };
 use beacon_chain::{
     BeaconBlockResponseWrapper, BeaconChain, BeaconChainTypes, ProduceBlockVerification,
 };
+use eth2::lighthouse::BuilderBidDecision;
 use eth2::types::{
     self as api_types, EndpointVersion, ProduceBlockV3Metadata, SkipRandaoVerification,
 };
@@ -90,12 +92,15 @@ pub fn build_response_v3<T: BeaconChainTypes>(
     let execution_payload_value = block_response.execution_payload_value();
     let consensus_block_value = block_response.consensus_block_value_wei();
     let execution_payload_blinded = block_response.is_blinded();
+    let (local_payload_value, builder_bid_value) = payload_values(&chain, &block_response);
 
     let metadata = ProduceBlockV3Metadata {
         consensus_version: fork_name,
         execution_payload_blinded,
         execution_payload_value,
         consensus_block_value,
+        local_payload_value,
+        builder_bid_value,
     };
 
     let block_contents = build_block_contents::build_block_contents(fork_name, block_response)?;
@@ -111,6 +116,7 @@ pub fn build_response_v3<T: BeaconChainTypes>(
                 add_execution_payload_value_header(res, execution_payload_value)
             })
             .map(|res| add_consensus_block_value_header(res, consensus_block_value))
+            .map(|res| add_payload_values_headers(res, local_payload_value, builder_bid_value))
             .map_err(|e| -> warp::Rejection {
                 warp_utils::reject::custom_server_error(format!("failed to create response: {}", e))
             }),
@@ -124,7 +130,51 @@ pub fn build_response_v3<T: BeaconChainTypes>(
         .map(|res| add_consensus_version_header(res, fork_name))
         .map(|res| add_execution_payload_blinded_header(res, execution_payload_blinded))
         .map(|res| add_execution_payload_value_header(res, execution_payload_value))
-        .map(|res| add_consensus_block_value_header(res, consensus_block_value)),
+        .map(|res| add_consensus_block_value_header(res, consensus_block_value))
+        .map(|res| add_payload_values_headers(res, local_payload_value, builder_bid_value)),
+    }
+}
+
+/// Returns the values of the local payload and of the best builder bid which were compared when
+/// producing `block_response`, as far as they are known.
+///
+/// The value of the chosen payload is always known. The value of the payload which wasn't chosen
+/// is taken from the bid audit log, which records the local payload value alongside the selected
+/// builder bid.
+fn payload_values<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    block_response: &BeaconBlockResponseWrapper<T::EthSpec>,
+) -> (Option<Uint256>, Option<Uint256>) {
+    let execution_payload_value = block_response.execution_payload_value();
+    let (local_payload_value, builder_bid_value) = if block_response.is_blinded() {
+        (None, Some(execution_payload_value))
+    } else {
+        (Some(execution_payload_value), None)
+    };
+
+    let selected_bid = chain
+        .execution_layer
+        .as_ref()
+        .zip(block_response.execution_parent_hash())
+        .and_then(|(execution_layer, parent_hash)| {
+            execution_layer
+                .builder_bids(Some(block_response.slot()))
+                .into_iter()
+                .rev()
+                .find(|bid| {
+                    bid.parent_hash == parent_hash
+                        && matches!(
+                            bid.decision,
+                            BuilderBidDecision::Accepted
+                                | BuilderBidDecision::LocalMoreProfitable
+                                | BuilderBidDecision::LocalOverride
+                        )
+                })
+        });
+
+    match selected_bid {
+        Some(bid) => (bid.local_value.or(local_payload_value), Some(bid.value)),
+        None => (local_payload_value, builder_bid_value),
     }
 }
 
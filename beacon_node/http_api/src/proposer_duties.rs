@@ -1,10 +1,12 @@
-//! Contains the handler for the `GET validator/duties/proposer/{epoch}` endpoint.
+//! Contains the handlers for the `GET validator/duties/proposer/{epoch}` and
+//! `GET lighthouse/duties/proposer/{epoch}` endpoints.
 
 use crate::state_id::StateId;
 use beacon_chain::{
     beacon_proposer_cache::{compute_proposer_duties_from_head, ensure_state_is_in_epoch},
     BeaconChain, BeaconChainError, BeaconChainTypes,
 };
+use eth2::lighthouse::{ProposerDutiesCertainty, ProposerDutiesLookahead};
 use eth2::types::{self as api_types};
 use safe_arith::SafeArith;
 use slog::{debug, Logger};
@@ -15,6 +17,11 @@ use types::{Epoch, EthSpec, Hash256, Slot};
 /// The struct that is returned to the requesting HTTP client.
 type ApiDuties = api_types::DutiesResponse<Vec<api_types::ProposerData>>;
 
+/// The number of epochs after the current epoch for which `lighthouse/duties/proposer/{epoch}`
+/// predicts duties. Each epoch requires the head state to be advanced through another epoch
+/// transition.
+pub const MAX_PROPOSER_DUTIES_LOOKAHEAD: u64 = 4;
+
 /// Handles a request from the HTTP API for proposer duties.
 pub fn proposer_duties<T: BeaconChainTypes>(
     request_epoch: Epoch,
@@ -92,6 +99,72 @@ pub fn proposer_duties<T: BeaconChainTypes>(
     }
 }
 
+/// Handles a request from the HTTP API for proposer duties up to `MAX_PROPOSER_DUTIES_LOOKAHEAD`
+/// epochs ahead of the current epoch.
+///
+/// Duties up to the next epoch are served as for the standard endpoint, whilst those of later
+/// epochs are predicted by advancing the head state without caching the result.
+pub fn proposer_duties_lookahead<T: BeaconChainTypes>(
+    request_epoch: Epoch,
+    chain: &BeaconChain<T>,
+    log: &Logger,
+) -> Result<ProposerDutiesLookahead, warp::reject::Rejection> {
+    let current_epoch = chain
+        .slot_clock
+        .now_or_genesis()
+        .map(|slot| slot.epoch(T::EthSpec::slots_per_epoch()))
+        .ok_or(BeaconChainError::UnableToReadSlot)
+        .map_err(warp_utils::reject::beacon_chain_error)?;
+    let next_epoch = current_epoch
+        .safe_add(1)
+        .map_err(warp_utils::reject::arith_error)?;
+    let max_epoch = current_epoch
+        .safe_add(MAX_PROPOSER_DUTIES_LOOKAHEAD)
+        .map_err(warp_utils::reject::arith_error)?;
+    if request_epoch > max_epoch {
+        return Err(warp_utils::reject::custom_bad_request(format!(
+            "request epoch {} is more than {} epochs ahead of the current epoch {}",
+            request_epoch, MAX_PROPOSER_DUTIES_LOOKAHEAD, current_epoch
+        )));
+    }
+
+    let head_block_root = chain.canonical_head.cached_head().head_block_root();
+    let (duties, dependent_root, certainty) = if request_epoch <= current_epoch {
+        let duties = proposer_duties(request_epoch, chain, log)?;
+        let dependent_root = duties.dependent_root;
+        (
+            duties,
+            Some(dependent_root),
+            ProposerDutiesCertainty::Decided,
+        )
+    } else if request_epoch == next_epoch {
+        // The dependent root of the standard response is the head block, which may not be the
+        // last block of the current epoch.
+        let duties = proposer_duties(request_epoch, chain, log)?;
+        (duties, None, ProposerDutiesCertainty::SeedKnown)
+    } else {
+        let (indices, dependent_root, execution_status, _fork) =
+            compute_proposer_duties_from_head(request_epoch, chain)
+                .map_err(warp_utils::reject::beacon_chain_error)?;
+        let duties = convert_to_api_response(
+            chain,
+            request_epoch,
+            dependent_root,
+            execution_status.is_optimistic_or_invalid(),
+            indices,
+        )?;
+        (duties, None, ProposerDutiesCertainty::Speculative)
+    };
+
+    Ok(ProposerDutiesLookahead {
+        dependent_root,
+        certainty,
+        head_block_root,
+        execution_optimistic: duties.execution_optimistic.unwrap_or(false),
+        data: duties.data,
+    })
+}
+
 /// Attempt to load the proposer duties from the `chain.beacon_proposer_cache`, returning `Ok(None)`
 /// if there is a cache miss.
 ///
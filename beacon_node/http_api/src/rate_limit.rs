@@ -0,0 +1,365 @@
+//! Per-client rate limiting of the HTTP API.
+//!
+//! Each configured route has a token bucket per client IP address. A request takes one token from
+//! the bucket of the most specific route matching its path, and is rejected with
+//! `429 Too Many Requests` if the bucket is empty. This allows a public beacon node to give
+//! expensive endpoints, such as state queries, a smaller budget than the rest of the API.
+//!
+//! Clients are identified by the remote address of their connection. Forwarding headers such as
+//! `X-Forwarded-For` are deliberately ignored, since they are set by the client unless a trusted
+//! proxy overwrites them. Behind a reverse proxy every request has the proxy's address, so all
+//! clients share a single bucket per route.
+
+use lighthouse_network::rpc::{Limiter, Quota, RateLimitedErr};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// The route whose quota applies to requests which match no other route.
+const DEFAULT_ROUTE: &str = "default";
+
+/// How often clients with full buckets are forgotten.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The quota of a single route, specified as `route:tokens/time_in_seconds`.
+///
+/// The route is a path prefix with the `/eth/v{n}` version omitted, such as `beacon/states`, so
+/// that it covers every version of the matching endpoints. The `default` route matches all paths.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RouteQuota {
+    route: String,
+    quota: Quota,
+}
+
+impl FromStr for RouteQuota {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (route, quota_str) = s
+            .split_once(':')
+            .ok_or_else(|| format!("missing ':' from route quota {s:?}"))?;
+        let (tokens_str, time_str) = quota_str
+            .split_once('/')
+            .ok_or_else(|| format!("quota should be defined as \"n/t\" in {s:?}"))?;
+        let tokens = tokens_str
+            .parse::<u64>()
+            .map_err(|e| format!("invalid number of tokens in {s:?}: {e}"))?;
+        let seconds = time_str
+            .parse::<u64>()
+            .map_err(|e| format!("invalid number of seconds in {s:?}: {e}"))?;
+        if tokens == 0 || seconds == 0 {
+            return Err(format!("tokens and seconds must be positive in {s:?}"));
+        }
+
+        Ok(Self {
+            route: route.trim_matches('/').to_string(),
+            quota: Quota::n_every(tokens, seconds),
+        })
+    }
+}
+
+/// The quotas of the rate limited routes.
+///
+/// Requests to routes without a quota are not limited, unless a `default` quota is given.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimitConfig {
+    pub quotas: Vec<RouteQuota>,
+}
+
+/// Parses a ';' separated list of [`RouteQuota`]. Routes specified more than once use only the
+/// first given quota.
+impl FromStr for RateLimitConfig {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut quotas: Vec<RouteQuota> = vec![];
+        for route_quota in s.split(';') {
+            let route_quota = route_quota.parse::<RouteQuota>()?;
+            if quotas
+                .iter()
+                .all(|existing| existing.route != route_quota.route)
+            {
+                quotas.push(route_quota);
+            }
+        }
+        Ok(Self { quotas })
+    }
+}
+
+struct RouteLimiter {
+    /// The path segments which a request must start with, empty for the default route.
+    segments: Vec<String>,
+    limiter: Limiter<IpAddr>,
+}
+
+struct Inner {
+    /// Ordered from the most to the least specific route.
+    routes: Vec<RouteLimiter>,
+    last_prune: Duration,
+}
+
+/// Limits the rate of requests from each client IP address according to a [`RateLimitConfig`].
+pub struct RateLimiter {
+    init_time: Instant,
+    inner: Mutex<Inner>,
+}
+
+impl RateLimiter {
+    pub fn new(config: &RateLimitConfig) -> Result<Self, String> {
+        let mut routes = config
+            .quotas
+            .iter()
+            .map(|route_quota| {
+                let segments = if route_quota.route == DEFAULT_ROUTE {
+                    vec![]
+                } else {
+                    route_quota.route.split('/').map(String::from).collect()
+                };
+                let limiter = Limiter::from_quota(route_quota.quota.clone())
+                    .map_err(|e| format!("invalid quota for route {}: {}", route_quota.route, e))?;
+                Ok(RouteLimiter { segments, limiter })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        routes.sort_by(|a, b| b.segments.len().cmp(&a.segments.len()));
+
+        Ok(Self {
+            init_time: Instant::now(),
+            inner: Mutex::new(Inner {
+                routes,
+                last_prune: Duration::ZERO,
+            }),
+        })
+    }
+
+    /// Takes a token for a request to `path` from `ip`.
+    ///
+    /// Returns how long the client must wait before retrying if the request exceeds its quota.
+    pub fn allows(&self, ip: IpAddr, path: &str) -> Result<(), Duration> {
+        self.allows_at(self.init_time.elapsed(), ip, path)
+    }
+
+    fn allows_at(
+        &self,
+        time_since_start: Duration,
+        ip: IpAddr,
+        path: &str,
+    ) -> Result<(), Duration> {
+        let segments = unversioned_segments(path);
+
+        let mut inner = self.inner.lock();
+        if time_since_start.saturating_sub(inner.last_prune) >= PRUNE_INTERVAL {
+            for route in &mut inner.routes {
+                route.limiter.prune(time_since_start);
+            }
+            inner.last_prune = time_since_start;
+        }
+
+        let Some(route) = inner
+            .routes
+            .iter_mut()
+            .find(|route| segments.starts_with(&route.segments))
+        else {
+            return Ok(());
+        };
+
+        match route.limiter.allows(time_since_start, &ip, 1) {
+            Ok(()) => Ok(()),
+            Err(RateLimitedErr::TooSoon(wait_time)) => Err(wait_time),
+            // Quotas always have at least one token, so a single token is never too large.
+            Err(RateLimitedErr::TooLarge) => Ok(()),
+        }
+    }
+}
+
+/// Returns the segments of `path`, omitting the `/eth/v{n}` prefix of the standard API.
+pub(crate) fn unversioned_segments(path: &str) -> Vec<String> {
+    let mut segments = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .peekable();
+    if segments.peek() == Some(&"eth") {
+        segments.next();
+        if segments
+            .peek()
+            .map_or(false, |version| version.starts_with('v'))
+        {
+            segments.next();
+        }
+    }
+    segments.map(String::from).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    const CLIENT: IpAddr = IpAddr::V4(Ipv4Addr::new(192, 168, 0, 1));
+    const OTHER_CLIENT: IpAddr = IpAddr::V4(Ipv4Addr::new(192, 168, 0, 2));
+
+    fn rate_limiter(quotas: &str) -> RateLimiter {
+        RateLimiter::new(&quotas.parse().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn parse_route_quota() {
+        assert_eq!(
+            "beacon/states:5/10".parse::<RouteQuota>(),
+            Ok(RouteQuota {
+                route: "beacon/states".into(),
+                quota: Quota::n_every(5, 10),
+            })
+        );
+        // Leading and trailing slashes are ignored.
+        assert_eq!(
+            "/beacon/states/:5/10".parse::<RouteQuota>().unwrap().route,
+            "beacon/states"
+        );
+
+        for invalid in [
+            "beacon/states",
+            "beacon/states:5",
+            "beacon/states:five/10",
+            "beacon/states:5/ten",
+            "beacon/states:0/10",
+            "beacon/states:5/0",
+            "beacon/states:-1/10",
+        ] {
+            assert!(invalid.parse::<RouteQuota>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn parse_config() {
+        let config = "default:100/10;beacon/states:5/10;default:1/1"
+            .parse::<RateLimitConfig>()
+            .unwrap();
+        // Only the first quota of a route is used.
+        assert_eq!(
+            config.quotas,
+            vec![
+                "default:100/10".parse().unwrap(),
+                "beacon/states:5/10".parse().unwrap(),
+            ]
+        );
+
+        assert!("default:100/10;beacon/states"
+            .parse::<RateLimitConfig>()
+            .is_err());
+        assert!("".parse::<RateLimitConfig>().is_err());
+    }
+
+    #[test]
+    fn unversioned_path_segments() {
+        assert_eq!(
+            unversioned_segments("/eth/v1/beacon/states/head/root"),
+            vec!["beacon", "states", "head", "root"]
+        );
+        assert_eq!(
+            unversioned_segments("/eth/v2/debug/beacon/states/head"),
+            vec!["debug", "beacon", "states", "head"]
+        );
+        assert_eq!(
+            unversioned_segments("/lighthouse/health/"),
+            vec!["lighthouse", "health"]
+        );
+        // Only a version following `eth` is omitted.
+        assert_eq!(unversioned_segments("/eth/beacon"), vec!["beacon"]);
+        assert_eq!(unversioned_segments("/v1/eth"), vec!["v1", "eth"]);
+    }
+
+    #[test]
+    fn versions_share_a_quota() {
+        let limiter = rate_limiter("beacon/states:1/10");
+        let now = Duration::ZERO;
+
+        assert_eq!(
+            limiter.allows_at(now, CLIENT, "/eth/v1/beacon/states/head/root"),
+            Ok(())
+        );
+        assert!(limiter
+            .allows_at(now, CLIENT, "/eth/v2/beacon/states/head/fork")
+            .is_err());
+
+        // Routes must match whole segments.
+        assert_eq!(
+            limiter.allows_at(now, CLIENT, "/eth/v1/beacon/statesx"),
+            Ok(())
+        );
+        assert_eq!(
+            limiter.allows_at(now, CLIENT, "/eth/v2/debug/beacon/states/head"),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn most_specific_route_applies() {
+        let limiter = rate_limiter("default:3/10;beacon:2/10;beacon/states:1/10");
+        let now = Duration::ZERO;
+
+        let states = "/eth/v1/beacon/states/head/root";
+        assert_eq!(limiter.allows_at(now, CLIENT, states), Ok(()));
+        assert!(limiter.allows_at(now, CLIENT, states).is_err());
+
+        // Each route has its own budget.
+        let headers = "/eth/v1/beacon/headers";
+        assert_eq!(limiter.allows_at(now, CLIENT, headers), Ok(()));
+        assert_eq!(limiter.allows_at(now, CLIENT, headers), Ok(()));
+        assert!(limiter.allows_at(now, CLIENT, headers).is_err());
+
+        let health = "/lighthouse/health";
+        for _ in 0..3 {
+            assert_eq!(limiter.allows_at(now, CLIENT, health), Ok(()));
+        }
+        assert!(limiter.allows_at(now, CLIENT, health).is_err());
+
+        // Each client has its own budget.
+        assert_eq!(limiter.allows_at(now, OTHER_CLIENT, states), Ok(()));
+    }
+
+    #[test]
+    fn routes_without_quota_are_unlimited() {
+        let limiter = rate_limiter("beacon/states:1/10");
+        for _ in 0..100 {
+            assert_eq!(
+                limiter.allows_at(Duration::ZERO, CLIENT, "/eth/v1/node/health"),
+                Ok(())
+            );
+        }
+    }
+
+    #[test]
+    fn quota_refills() {
+        let limiter = rate_limiter("default:2/10");
+        let path = "/eth/v1/node/version";
+
+        // The full quota may be used at once.
+        assert_eq!(limiter.allows_at(Duration::ZERO, CLIENT, path), Ok(()));
+        assert_eq!(limiter.allows_at(Duration::ZERO, CLIENT, path), Ok(()));
+
+        // One token is replenished every 5 seconds.
+        assert_eq!(
+            limiter.allows_at(Duration::ZERO, CLIENT, path),
+            Err(Duration::from_secs(5))
+        );
+        assert_eq!(
+            limiter.allows_at(Duration::from_secs(4), CLIENT, path),
+            Err(Duration::from_secs(1))
+        );
+        assert_eq!(
+            limiter.allows_at(Duration::from_secs(5), CLIENT, path),
+            Ok(())
+        );
+        assert!(limiter
+            .allows_at(Duration::from_secs(5), CLIENT, path)
+            .is_err());
+
+        // Tokens do not accumulate beyond the quota.
+        let later = Duration::from_secs(100);
+        assert_eq!(limiter.allows_at(later, CLIENT, path), Ok(()));
+        assert_eq!(limiter.allows_at(later, CLIENT, path), Ok(()));
+        assert!(limiter.allows_at(later, CLIENT, path).is_err());
+    }
+}
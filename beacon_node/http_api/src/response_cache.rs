@@ -0,0 +1,171 @@
+//! Server-side cache of responses which can never change, such as finalized blocks.
+//!
+//! Handlers mark such responses with [`immutable`]. They are cached by path, query and `Accept`
+//! header, so that when many validator clients share a beacon node, repeated requests for the same
+//! finalized data are served without reading the database again. Cached responses carry an
+//! `ETag`, and a client which already has the response receives `304 Not Modified`.
+
+use crate::etag::{etag_for, matches_etag};
+use bytes::Bytes;
+use lru::LruCache;
+use parking_lot::Mutex;
+use warp::http::header::{HeaderMap, HeaderValue, CACHE_CONTROL, CONTENT_LENGTH, ETAG};
+use warp::http::StatusCode;
+use warp::hyper::{self, Body};
+use warp::path::FullPath;
+use warp::reply::Response;
+
+/// The `Cache-Control` header of responses which never change.
+const IMMUTABLE_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
+/// Marks `response` as never changing, so that it may be cached by the server and by clients.
+pub fn immutable(mut response: Response) -> Response {
+    response.headers_mut().insert(
+        CACHE_CONTROL,
+        HeaderValue::from_static(IMMUTABLE_CACHE_CONTROL),
+    );
+    response
+}
+
+/// Marks `response` with [`immutable`] if `is_immutable`.
+pub fn immutable_if(response: Response, is_immutable: bool) -> Response {
+    if is_immutable {
+        immutable(response)
+    } else {
+        response
+    }
+}
+
+fn is_immutable(response: &Response) -> bool {
+    response.status() == StatusCode::OK
+        && response
+            .headers()
+            .get(CACHE_CONTROL)
+            .map_or(false, |value| value == IMMUTABLE_CACHE_CONTROL)
+}
+
+/// Identifies a request whose response may be cached.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ResponseCacheKey {
+    path: String,
+    query: String,
+    accept: Option<String>,
+}
+
+impl ResponseCacheKey {
+    pub fn new(path: FullPath, query: String, accept: Option<String>) -> Self {
+        Self {
+            path: path.as_str().to_string(),
+            query,
+            accept,
+        }
+    }
+}
+
+struct CachedResponse {
+    headers: HeaderMap,
+    etag: HeaderValue,
+    body: Bytes,
+}
+
+struct Inner {
+    responses: LruCache<ResponseCacheKey, CachedResponse>,
+    total_bytes: usize,
+}
+
+/// A cache of immutable responses, bounded by the total size of their bodies.
+pub struct ResponseCache {
+    max_bytes: usize,
+    inner: Mutex<Inner>,
+}
+
+impl ResponseCache {
+    pub fn new(max_bytes: usize) -> Self {
+        Self {
+            max_bytes,
+            inner: Mutex::new(Inner {
+                responses: LruCache::unbounded(),
+                total_bytes: 0,
+            }),
+        }
+    }
+
+    /// Returns the cached response to the request identified by `key`, or `304 Not Modified` if
+    /// `if_none_match` shows that the client already has it.
+    pub fn get(&self, key: &ResponseCacheKey, if_none_match: Option<&str>) -> Option<Response> {
+        let mut inner = self.inner.lock();
+        let cached = inner.responses.get(key)?;
+
+        let not_modified = cached
+            .etag
+            .to_str()
+            .map_or(false, |etag| matches_etag(if_none_match, etag));
+        let mut response = if not_modified {
+            let mut response = Response::new(Body::empty());
+            *response.status_mut() = StatusCode::NOT_MODIFIED;
+            response
+        } else {
+            let mut response = Response::new(Body::from(cached.body.clone()));
+            *response.headers_mut() = cached.headers.clone();
+            response
+        };
+        response.headers_mut().insert(ETAG, cached.etag.clone());
+        response.headers_mut().insert(
+            CACHE_CONTROL,
+            HeaderValue::from_static(IMMUTABLE_CACHE_CONTROL),
+        );
+        Some(response)
+    }
+
+    /// Caches `response` to the request identified by `key` if it is immutable, returning it with
+    /// an `ETag`.
+    pub async fn store(&self, key: ResponseCacheKey, response: Response) -> Response {
+        if self.max_bytes == 0 || !is_immutable(&response) {
+            return response;
+        }
+
+        let (mut parts, body) = response.into_parts();
+        let body = match hyper::body::to_bytes(body).await {
+            Ok(body) => body,
+            Err(_) => {
+                parts.status = StatusCode::INTERNAL_SERVER_ERROR;
+                return Response::from_parts(parts, Body::empty());
+            }
+        };
+        if body.len() > self.max_bytes {
+            return Response::from_parts(parts, Body::from(body));
+        }
+
+        let etag = match parts.headers.get(ETAG) {
+            Some(etag) => etag.clone(),
+            None => match HeaderValue::from_str(&etag_for(&body)) {
+                Ok(etag) => etag,
+                Err(_) => return Response::from_parts(parts, Body::from(body)),
+            },
+        };
+        parts.headers.insert(ETAG, etag.clone());
+
+        let mut headers = parts.headers.clone();
+        headers.remove(CONTENT_LENGTH);
+        let cached = CachedResponse {
+            headers,
+            etag,
+            body: body.clone(),
+        };
+
+        let mut inner = self.inner.lock();
+        inner.total_bytes += cached.body.len();
+        if let Some(replaced) = inner.responses.put(key, cached) {
+            inner.total_bytes -= replaced.body.len();
+        }
+        while inner.total_bytes > self.max_bytes {
+            let Some((_, evicted)) = inner.responses.pop_lru() else {
+                break;
+            };
+            inner.total_bytes -= evicted.body.len();
+        }
+        drop(inner);
+
+        Response::from_parts(parts, Body::from(body))
+    }
+}
@@ -202,6 +202,56 @@ impl StateId {
         Ok((state, execution_optimistic, finalized))
     }
 
+    /// Return the `BeaconState` identified by `self`, as per `Self::state`.
+    ///
+    /// A state requested by slot which lies between the historic states stored in the database is
+    /// reconstructed by replaying at most `max_replay_slots` slots of blocks. The number of slots
+    /// replayed is returned alongside the state, or `None` if the state was loaded directly.
+    pub fn state_with_replay<T: BeaconChainTypes>(
+        &self,
+        chain: &BeaconChain<T>,
+        max_replay_slots: u64,
+    ) -> Result<
+        (
+            BeaconState<T::EthSpec>,
+            ExecutionOptimistic,
+            Finalized,
+            Option<u64>,
+        ),
+        warp::Rejection,
+    > {
+        if let CoreStateId::Slot(slot) = self.0 {
+            let (lower_limit, upper_limit) = chain.store.get_historic_state_limits();
+            if max_replay_slots > 0 && slot > lower_limit && slot < upper_limit {
+                let execution_optimistic = chain
+                    .is_optimistic_or_invalid_head()
+                    .map_err(warp_utils::reject::beacon_chain_error)?;
+                return match chain.replay_historic_state(slot, max_replay_slots) {
+                    Ok(Some((state, replay_depth))) => {
+                        Ok((state, execution_optimistic, true, Some(replay_depth)))
+                    }
+                    Ok(None) => Err(warp_utils::reject::custom_not_found(format!(
+                        "beacon state at slot {slot}, blocks to replay are unavailable"
+                    ))),
+                    Err(BeaconChainError::HistoricStateReplayLimitExceeded {
+                        replay_depth,
+                        max_replay_slots,
+                        ..
+                    }) => Err(warp_utils::reject::custom_not_found(format!(
+                        "beacon state at slot {slot}, replaying {replay_depth} slots exceeds the \
+                         limit of {max_replay_slots}"
+                    ))),
+                    Err(e) => Err(warp_utils::reject::beacon_chain_error(e)),
+                };
+            }
+        }
+
+        self.state(chain)
+            .map(|(state, execution_optimistic, finalized)| {
+                (state, execution_optimistic, finalized, None)
+            })
+    }
+
     /// Map a function across the `BeaconState` identified by `self`.
     ///
     /// The optimistic and finalization status of the requested state is also provided to the `func`
@@ -0,0 +1,167 @@
+//! Reports the health of each subsystem of the beacon node for `lighthouse/health/subsystems`.
+//!
+//! Each subsystem has its own status, and the response has the HTTP status of the worst of the
+//! subsystems required by the query. This lets orchestrators such as Kubernetes probe the node with
+//! differing strictness, e.g. restarting it only when the database fails, but routing requests to
+//! it only once it is synced.
+
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::{
+    Subsystem, SubsystemHealth, SubsystemStatus, SubsystemsHealth, SubsystemsHealthQuery,
+};
+use lighthouse_network::{types::SyncState, NetworkGlobals};
+use types::EthSpec;
+
+/// The default number of connected peers below which the peers are unhealthy.
+const DEFAULT_MIN_PEERS: usize = 1;
+
+/// Returns the health of every subsystem, aggregating those required by `query`.
+///
+/// `el_offline` is `None` if there is no execution layer, since checking it requires an `await`.
+pub fn observe_subsystems_health<T: BeaconChainTypes>(
+    query: SubsystemsHealthQuery,
+    el_offline: Option<bool>,
+    chain: &BeaconChain<T>,
+    network_globals: &NetworkGlobals<T::EthSpec>,
+) -> SubsystemsHealth {
+    let subsystems = Subsystem::ALL
+        .into_iter()
+        .map(|subsystem| {
+            let (status, detail) = match subsystem {
+                Subsystem::ExecutionLayer => execution_layer_health(el_offline, chain),
+                Subsystem::Sync => sync_health(&network_globals.sync_state.read()),
+                Subsystem::Database => database_health(chain),
+                Subsystem::Slasher => slasher_health(chain),
+                Subsystem::Peers => peers_health(&query, network_globals.connected_peers()),
+            };
+            SubsystemHealth::new(subsystem, status, detail)
+        })
+        .collect();
+    let required = query.require.unwrap_or_else(|| Subsystem::ALL.to_vec());
+    SubsystemsHealth::new(subsystems, required, query.strict)
+}
+
+fn execution_layer_health<T: BeaconChainTypes>(
+    el_offline: Option<bool>,
+    chain: &BeaconChain<T>,
+) -> (SubsystemStatus, String) {
+    match el_offline {
+        None => (
+            SubsystemStatus::Disabled,
+            "no execution endpoint is configured".to_string(),
+        ),
+        Some(true) => (
+            SubsystemStatus::Unhealthy,
+            "the execution node is offline or erroring".to_string(),
+        ),
+        Some(false) => match chain.is_optimistic_or_invalid_head() {
+            Ok(false) => (
+                SubsystemStatus::Healthy,
+                "the execution node is online".to_string(),
+            ),
+            Ok(true) => (
+                SubsystemStatus::Degraded,
+                "the head is optimistic whilst the execution node syncs".to_string(),
+            ),
+            Err(e) => (
+                SubsystemStatus::Unhealthy,
+                format!("unable to check whether the head is optimistic: {e:?}"),
+            ),
+        },
+    }
+}
+
+fn sync_health(sync_state: &SyncState) -> (SubsystemStatus, String) {
+    let status = match sync_state {
+        SyncState::Synced | SyncState::BackFillSyncing { .. } => SubsystemStatus::Healthy,
+        SyncState::SyncingFinalized { .. }
+        | SyncState::SyncingHead { .. }
+        | SyncState::SyncTransition => SubsystemStatus::Degraded,
+        SyncState::Stalled => SubsystemStatus::Unhealthy,
+    };
+    (status, sync_state.to_string())
+}
+
+/// Checks that the head block can be read from the database.
+fn database_health<T: BeaconChainTypes>(chain: &BeaconChain<T>) -> (SubsystemStatus, String) {
+    let head_block_root = chain.canonical_head.cached_head().head_block_root();
+    match chain.get_blinded_block(&head_block_root) {
+        Ok(Some(_)) => (
+            SubsystemStatus::Healthy,
+            "the head block was read from the database".to_string(),
+        ),
+        Ok(None) => (
+            SubsystemStatus::Unhealthy,
+            format!("the head block {head_block_root:?} is missing from the database"),
+        ),
+        Err(e) => (
+            SubsystemStatus::Unhealthy,
+            format!("unable to read the head block from the database: {e:?}"),
+        ),
+    }
+}
+
+/// Checks that the slasher has processed a batch recently.
+fn slasher_health<T: BeaconChainTypes>(chain: &BeaconChain<T>) -> (SubsystemStatus, String) {
+    let Some(slasher) = &chain.slasher else {
+        return (
+            SubsystemStatus::Disabled,
+            "the slasher is not enabled".to_string(),
+        );
+    };
+    let Some(last_processed_epoch) = slasher.last_processed_epoch() else {
+        return (
+            SubsystemStatus::Degraded,
+            "the slasher has not processed a batch yet".to_string(),
+        );
+    };
+    let current_epoch = match chain.epoch() {
+        Ok(epoch) => epoch,
+        Err(e) => {
+            return (
+                SubsystemStatus::Unhealthy,
+                format!("unable to read the current epoch: {e:?}"),
+            )
+        }
+    };
+
+    // A batch is processed every update period, and is labelled with the epoch it started in.
+    let seconds_per_epoch = chain.spec.seconds_per_slot * T::EthSpec::slots_per_epoch();
+    let max_lag = slasher
+        .config()
+        .update_period
+        .div_ceil(seconds_per_epoch.max(1))
+        + 1;
+    let lag = current_epoch.saturating_sub(last_processed_epoch).as_u64();
+    if lag > max_lag {
+        (
+            SubsystemStatus::Unhealthy,
+            format!(
+                "the last batch processed was at epoch {last_processed_epoch}, {lag} epochs ago"
+            ),
+        )
+    } else {
+        (
+            SubsystemStatus::Healthy,
+            format!("the last batch processed was at epoch {last_processed_epoch}"),
+        )
+    }
+}
+
+fn peers_health(
+    query: &SubsystemsHealthQuery,
+    connected_peers: usize,
+) -> (SubsystemStatus, String) {
+    let min_peers = query.min_peers.unwrap_or(DEFAULT_MIN_PEERS);
+    let status = if connected_peers < min_peers {
+        SubsystemStatus::Unhealthy
+    } else if query
+        .target_peers
+        .map_or(false, |target_peers| connected_peers < target_peers)
+    {
+        SubsystemStatus::Degraded
+    } else {
+        SubsystemStatus::Healthy
+    };
+    (status, format!("{connected_peers} connected peers"))
+}
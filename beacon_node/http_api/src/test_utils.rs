@@ -18,7 +18,7 @@ use lighthouse_network::{
     types::{EnrAttestationBitfield, EnrSyncCommitteeBitfield, SyncState},
     ConnectedPoint, Enr, NetworkGlobals, PeerId, PeerManager,
 };
-use logging::test_logger;
+use logging::{test_logger, LogFilterHandle, RecentLogs};
 use network::{NetworkReceivers, NetworkSenders};
 use sensitive_url::SensitiveUrl;
 use slog::Logger;
@@ -64,11 +64,38 @@ impl<E: EthSpec> InteractiveTester<E> {
         Self::new_with_initializer_and_mutator(spec, validator_count, None, None).await
     }
 
+    /// Creates a tester whose server uses `config`, e.g. to require API tokens.
+    pub async fn new_with_config(
+        spec: Option<ChainSpec>,
+        validator_count: usize,
+        config: Config,
+    ) -> Self {
+        Self::new_with_initializer_mutator_and_config(spec, validator_count, None, None, config)
+            .await
+    }
+
     pub async fn new_with_initializer_and_mutator(
         spec: Option<ChainSpec>,
         validator_count: usize,
         initializer: Option<Initializer<E>>,
         mutator: Option<Mutator<E>>,
+    ) -> Self {
+        Self::new_with_initializer_mutator_and_config(
+            spec,
+            validator_count,
+            initializer,
+            mutator,
+            test_config(),
+        )
+        .await
+    }
+
+    async fn new_with_initializer_mutator_and_config(
+        spec: Option<ChainSpec>,
+        validator_count: usize,
+        initializer: Option<Initializer<E>>,
+        mutator: Option<Mutator<E>>,
+        config: Config,
     ) -> Self {
         let mut harness_builder = BeaconChainHarness::builder(E::default())
             .spec_or_default(spec)
@@ -99,10 +126,11 @@ impl<E: EthSpec> InteractiveTester<E> {
             listening_socket,
             network_rx,
             ..
-        } = create_api_server(
+        } = create_api_server_with_config(
             harness.chain.clone(),
             &harness.runtime,
             harness.logger().clone(),
+            config,
         )
         .await;
 
@@ -127,14 +155,32 @@ impl<E: EthSpec> InteractiveTester<E> {
     }
 }
 
+/// The configuration of the servers of tests, which listen on an unused port.
+pub fn test_config() -> Config {
+    Config {
+        enabled: true,
+        // Use port 0 to allocate a new unused port.
+        listen_port: 0,
+        data_dir: std::path::PathBuf::from(DEFAULT_ROOT_DIR),
+        enable_light_client_server: true,
+        ..Config::default()
+    }
+}
+
 pub async fn create_api_server<T: BeaconChainTypes>(
     chain: Arc<BeaconChain<T>>,
     test_runtime: &TestRuntime,
     log: Logger,
 ) -> ApiServer<T, impl Future<Output = ()>> {
-    // Use port 0 to allocate a new unused port.
-    let port = 0;
+    create_api_server_with_config(chain, test_runtime, log, test_config()).await
+}
 
+pub async fn create_api_server_with_config<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+    test_runtime: &TestRuntime,
+    log: Logger,
+    config: Config,
+) -> ApiServer<T, impl Future<Output = ()>> {
     let (network_senders, network_receivers) = NetworkSenders::new();
 
     // Default metadata
@@ -217,13 +263,7 @@ pub async fn create_api_server<T: BeaconChainTypes>(
     .unwrap();
 
     let ctx = Arc::new(Context {
-        config: Config {
-            enabled: true,
-            listen_port: port,
-            data_dir: std::path::PathBuf::from(DEFAULT_ROOT_DIR),
-            enable_light_client_server: true,
-            ..Config::default()
-        },
+        config,
         chain: Some(chain),
         network_senders: Some(network_senders),
         network_globals: Some(network_globals),
@@ -231,6 +271,8 @@ pub async fn create_api_server<T: BeaconChainTypes>(
         beacon_processor_reprocess_send: Some(reprocess_send),
         eth1_service: Some(eth1_service),
         sse_logging_components: None,
+        log_filter: Some(LogFilterHandle::new(slog::Level::Info)),
+        recent_logs: Some(RecentLogs::new(16)),
         log,
     });
 
@@ -1,5 +1,6 @@
 use beacon_chain::{
-    validator_monitor::HISTORIC_EPOCHS, BeaconChain, BeaconChainError, BeaconChainTypes,
+    validator_monitor::{ValidatorIssue, HISTORIC_EPOCHS},
+    BeaconChain, BeaconChainError, BeaconChainTypes,
 };
 use eth2::types::{Epoch, ValidatorStatus};
 use serde::{Deserialize, Serialize};
@@ -255,3 +256,29 @@ pub fn post_validator_monitor_metrics<T: BeaconChainTypes>(
 
     Ok(ValidatorMetricsResponse { validators })
 }
+
+#[derive(PartialEq, Serialize, Deserialize)]
+pub struct ValidatorIssuesResponse {
+    validators: HashMap<String, Vec<ValidatorIssue>>,
+}
+
+/// Returns the recently missed duties of all monitored validators, with their most likely causes.
+pub fn get_validator_issues<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+) -> ValidatorIssuesResponse {
+    let validator_monitor = chain.validator_monitor.read();
+
+    let validators = validator_monitor
+        .get_all_monitored_validators()
+        .into_iter()
+        .filter_map(|id| {
+            let index = id.parse::<u64>().ok()?;
+            let issues = validator_monitor
+                .get_monitored_validator(index)?
+                .get_issues();
+            (!issues.is_empty()).then_some((id, issues))
+        })
+        .collect();
+
+    ValidatorIssuesResponse { validators }
+}
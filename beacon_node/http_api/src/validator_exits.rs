@@ -0,0 +1,58 @@
+//! Helpers for the `GET lighthouse/validators/slashed` and `GET lighthouse/validators/exiting`
+//! endpoints, which list the few validators of interest without paging through the whole registry.
+
+use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes};
+use eth2::lighthouse::ExitingValidatorData;
+use eth2::types::ValidatorStatus;
+use types::{Epoch, Validator};
+use warp_utils::reject::beacon_chain_error;
+
+/// Returns the validators of the head state which have been slashed, including those which have
+/// since been withdrawn.
+pub fn slashed_validators<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+) -> Result<Vec<ExitingValidatorData>, warp::Rejection> {
+    head_validators_matching(chain, |validator, _| validator.slashed)
+}
+
+/// Returns the validators of the head state which have initiated their exit, or been slashed, and
+/// aren't yet withdrawable.
+pub fn exiting_validators<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+) -> Result<Vec<ExitingValidatorData>, warp::Rejection> {
+    let far_future_epoch = chain.spec.far_future_epoch;
+    head_validators_matching(chain, |validator, epoch| {
+        validator.exit_epoch != far_future_epoch && epoch < validator.withdrawable_epoch
+    })
+}
+
+/// Scans the validators of the head state once, returning those matching `predicate` in order of
+/// index.
+fn head_validators_matching<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    predicate: impl Fn(&Validator, Epoch) -> bool,
+) -> Result<Vec<ExitingValidatorData>, warp::Rejection> {
+    let far_future_epoch = chain.spec.far_future_epoch;
+    chain
+        .with_head(|head| {
+            let state = &head.beacon_state;
+            let epoch = state.current_epoch();
+            Ok::<_, BeaconChainError>(
+                state
+                    .validators()
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, validator)| predicate(validator, epoch))
+                    .map(|(index, validator)| ExitingValidatorData {
+                        index: index as u64,
+                        pubkey: validator.pubkey,
+                        status: ValidatorStatus::from_validator(validator, epoch, far_future_epoch),
+                        slashed: validator.slashed,
+                        exit_epoch: validator.exit_epoch,
+                        withdrawable_epoch: validator.withdrawable_epoch,
+                    })
+                    .collect(),
+            )
+        })
+        .map_err(beacon_chain_error)
+}
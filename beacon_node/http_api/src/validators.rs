@@ -11,6 +11,8 @@ pub fn get_beacon_state_validators<T: BeaconChainTypes>(
     chain: Arc<BeaconChain<T>>,
     query_ids: &Option<Vec<ValidatorId>>,
     query_statuses: &Option<Vec<ValidatorStatus>>,
+    offset: Option<u64>,
+    limit: Option<u64>,
 ) -> Result<ExecutionOptimisticFinalizedResponse<Vec<ValidatorData>>, warp::Rejection> {
     let (data, execution_optimistic, finalized) = state_id
         .map_state_and_execution_optimistic_and_finalized(
@@ -58,6 +60,9 @@ pub fn get_beacon_state_validators<T: BeaconChainTypes>(
                                 None
                             }
                         })
+                        // paginate in order of index if requested
+                        .skip(offset.unwrap_or(0) as usize)
+                        .take(limit.map_or(usize::MAX, |limit| limit as usize))
                         .collect::<Vec<_>>(),
                     execution_optimistic,
                     finalized,
@@ -72,45 +77,50 @@ pub fn get_beacon_state_validators<T: BeaconChainTypes>(
     })
 }
 
+/// Returns the balances of the validators in `optional_ids`, or of all validators, along with the
+/// number of slots replayed to reconstruct the state, if any.
 pub fn get_beacon_state_validator_balances<T: BeaconChainTypes>(
     state_id: StateId,
     chain: Arc<BeaconChain<T>>,
     optional_ids: Option<&[ValidatorId]>,
-) -> Result<ExecutionOptimisticFinalizedResponse<Vec<ValidatorBalanceData>>, warp::Rejection> {
-    let (data, execution_optimistic, finalized) = state_id
-        .map_state_and_execution_optimistic_and_finalized(
-            &chain,
-            |state, execution_optimistic, finalized| {
-                let ids_filter_set: Option<HashSet<&ValidatorId>> =
-                    optional_ids.map(|f| HashSet::from_iter(f.iter()));
+    max_replay_slots: u64,
+) -> Result<
+    (
+        ExecutionOptimisticFinalizedResponse<Vec<ValidatorBalanceData>>,
+        Option<u64>,
+    ),
+    warp::Rejection,
+> {
+    let (state, execution_optimistic, finalized, replay_depth) =
+        state_id.state_with_replay(&chain, max_replay_slots)?;
 
-                Ok((
-                    state
-                        .validators()
-                        .iter()
-                        .zip(state.balances().iter())
-                        .enumerate()
-                        // filter by validator id(s) if provided
-                        .filter(|(index, (validator, _))| {
-                            ids_filter_set.as_ref().map_or(true, |ids_set| {
-                                ids_set.contains(&ValidatorId::PublicKey(validator.pubkey))
-                                    || ids_set.contains(&ValidatorId::Index(*index as u64))
-                            })
-                        })
-                        .map(|(index, (_, balance))| ValidatorBalanceData {
-                            index: index as u64,
-                            balance: *balance,
-                        })
-                        .collect::<Vec<_>>(),
-                    execution_optimistic,
-                    finalized,
-                ))
-            },
-        )?;
+    let ids_filter_set: Option<HashSet<&ValidatorId>> =
+        optional_ids.map(|f| HashSet::from_iter(f.iter()));
 
-    Ok(api_types::ExecutionOptimisticFinalizedResponse {
-        data,
-        execution_optimistic: Some(execution_optimistic),
-        finalized: Some(finalized),
-    })
+    let data = state
+        .validators()
+        .iter()
+        .zip(state.balances().iter())
+        .enumerate()
+        // filter by validator id(s) if provided
+        .filter(|(index, (validator, _))| {
+            ids_filter_set.as_ref().map_or(true, |ids_set| {
+                ids_set.contains(&ValidatorId::PublicKey(validator.pubkey))
+                    || ids_set.contains(&ValidatorId::Index(*index as u64))
+            })
+        })
+        .map(|(index, (_, balance))| ValidatorBalanceData {
+            index: index as u64,
+            balance: *balance,
+        })
+        .collect::<Vec<_>>();
+
+    Ok((
+        api_types::ExecutionOptimisticFinalizedResponse {
+            data,
+            execution_optimistic: Some(execution_optimistic),
+            finalized: Some(finalized),
+        },
+        replay_depth,
+    ))
 }
@@ -1,7 +1,8 @@
 use crate::api_types::EndpointVersion;
 use eth2::{
-    CONSENSUS_BLOCK_VALUE_HEADER, CONSENSUS_VERSION_HEADER, CONTENT_TYPE_HEADER,
-    EXECUTION_PAYLOAD_BLINDED_HEADER, EXECUTION_PAYLOAD_VALUE_HEADER, SSZ_CONTENT_TYPE_HEADER,
+    BUILDER_BID_VALUE_HEADER, CONSENSUS_BLOCK_VALUE_HEADER, CONSENSUS_VERSION_HEADER,
+    CONTENT_TYPE_HEADER, EXECUTION_PAYLOAD_BLINDED_HEADER, EXECUTION_PAYLOAD_VALUE_HEADER,
+    LOCAL_PAYLOAD_VALUE_HEADER, REPLAY_DEPTH_HEADER, SSZ_CONTENT_TYPE_HEADER,
 };
 use serde::Serialize;
 use types::{
@@ -108,6 +109,36 @@ pub fn add_consensus_block_value_header<T: Reply>(
     .into_response()
 }
 
+/// Add the `Lighthouse-Local-Payload-Value` and `Lighthouse-Builder-Bid-Value` headers to a
+/// response, for whichever of the values are known.
+pub fn add_payload_values_headers<T: Reply>(
+    reply: T,
+    local_payload_value: Option<Uint256>,
+    builder_bid_value: Option<Uint256>,
+) -> Response {
+    let response = match local_payload_value {
+        Some(value) => {
+            reply::with_header(reply, LOCAL_PAYLOAD_VALUE_HEADER, value.to_string()).into_response()
+        }
+        None => reply.into_response(),
+    };
+    match builder_bid_value {
+        Some(value) => reply::with_header(response, BUILDER_BID_VALUE_HEADER, value.to_string())
+            .into_response(),
+        None => response,
+    }
+}
+
+/// Add the `Lighthouse-Replay-Depth` header to a response if its state had to be replayed.
+pub fn add_replay_depth_header<T: Reply>(reply: T, replay_depth: Option<u64>) -> Response {
+    match replay_depth {
+        Some(depth) => {
+            reply::with_header(reply, REPLAY_DEPTH_HEADER, depth.to_string()).into_response()
+        }
+        None => reply.into_response(),
+    }
+}
+
 pub fn inconsistent_fork_rejection(error: InconsistentFork) -> warp::reject::Rejection {
     warp_utils::reject::custom_server_error(format!("wrong fork: {:?}", error))
 }
@@ -5,11 +5,18 @@ use beacon_chain::{
     ChainConfig,
 };
 use beacon_processor::work_reprocessing_queue::ReprocessQueueMessage;
+use eth2::lighthouse::{DatabaseJobStatus, DatabaseOperation};
+use eth2::reqwest::{
+    self,
+    header::{HeaderMap, HeaderValue, AUTHORIZATION},
+};
 use eth2::types::ProduceBlockV3Response;
 use eth2::types::{DepositContractData, StateId};
+use eth2::{BeaconNodeHttpClient, StatusCode, Timeouts};
 use execution_layer::{ForkchoiceState, PayloadAttributes};
-use http_api::test_utils::InteractiveTester;
+use http_api::test_utils::{test_config, InteractiveTester};
 use parking_lot::Mutex;
+use sensitive_url::SensitiveUrl;
 use slot_clock::SlotClock;
 use state_processing::{
     per_block_processing::get_expected_withdrawals, state_advance::complete_state_advance,
@@ -922,3 +929,110 @@ async fn queue_attestations_from_http() {
 
     attestation_future.await.unwrap();
 }
+
+// Test that each class of route requires a token with at least its scope.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn api_token_scopes() {
+    let validator_count = 24;
+    let tokens_dir = tempfile::tempdir().unwrap();
+    let tokens_file = tokens_dir.path().join("api-tokens.txt");
+    std::fs::write(
+        &tokens_file,
+        "read-only reader\nvalidator validator\nadmin administrator\n",
+    )
+    .unwrap();
+    let config = http_api::Config {
+        api_tokens_file: Some(tokens_file),
+        ..test_config()
+    };
+    let tester = InteractiveTester::<E>::new_with_config(None, validator_count, config).await;
+
+    let read_only = "eth/v1/node/syncing";
+    let validator = "eth/v1/validator/duties/proposer/0";
+    let admin = "lighthouse/syncing";
+    let status = |path: &'static str, token: Option<&'static str>| {
+        let url = format!("{}{path}", tester.client.as_ref());
+        let client = &tester.client;
+        async move {
+            let result = client
+                .get_response(url, |b| match token {
+                    Some(token) => b.header("Authorization", format!("Bearer {token}")),
+                    None => b,
+                })
+                .await;
+            match result {
+                Ok(response) => response.status().as_u16(),
+                Err(e) => e.status().unwrap().as_u16(),
+            }
+        }
+    };
+
+    for path in [read_only, validator, admin] {
+        // Missing tokens are unauthorized and unknown tokens are forbidden.
+        assert_eq!(status(path, None).await, 401);
+        assert_eq!(status(path, Some("unknown")).await, 403);
+        assert_eq!(status(path, Some("administrator")).await, 200);
+    }
+
+    assert_eq!(status(read_only, Some("reader")).await, 200);
+    assert_eq!(status(validator, Some("reader")).await, 403);
+    assert_eq!(status(admin, Some("reader")).await, 403);
+
+    assert_eq!(status(read_only, Some("validator")).await, 200);
+    assert_eq!(status(validator, Some("validator")).await, 200);
+    assert_eq!(status(admin, Some("validator")).await, 403);
+}
+
+// Test that database maintenance runs only for callers with an admin API token.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn database_maintenance_requires_admin_token() {
+    let validator_count = 24;
+    let tokens_dir = tempfile::tempdir().unwrap();
+    let tokens_file = tokens_dir.path().join("api-tokens.txt");
+    std::fs::write(&tokens_file, "validator validator\nadmin administrator\n").unwrap();
+    let config = http_api::Config {
+        api_tokens_file: Some(tokens_file),
+        ..test_config()
+    };
+    let tester = InteractiveTester::<E>::new_with_config(None, validator_count, config).await;
+
+    let client_with_token = |token: &str| {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {token}")).unwrap(),
+        );
+        let client = reqwest::Client::builder()
+            .default_headers(headers)
+            .build()
+            .unwrap();
+        BeaconNodeHttpClient::from_components(
+            SensitiveUrl::parse(tester.client.as_ref()).unwrap(),
+            client,
+            Timeouts::set_all(Duration::from_secs(1)),
+        )
+    };
+
+    let error = client_with_token("validator")
+        .post_lighthouse_database_maintenance(DatabaseOperation::Compact)
+        .await
+        .unwrap_err();
+    assert_eq!(error.status(), Some(StatusCode::FORBIDDEN));
+
+    let admin = client_with_token("administrator");
+    let job = admin
+        .post_lighthouse_database_maintenance(DatabaseOperation::Compact)
+        .await
+        .unwrap()
+        .data;
+    assert_eq!(job.operation, DatabaseOperation::Compact);
+
+    // The test harness runs the migrator in the foreground, so the job has already run.
+    let job = admin
+        .get_lighthouse_database_job(job.id)
+        .await
+        .unwrap()
+        .data;
+    assert_eq!(job.status, DatabaseJobStatus::Completed, "{job:?}");
+    assert!(job.started_at.is_some() && job.finished_at.is_some());
+}
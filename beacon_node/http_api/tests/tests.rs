@@ -1,10 +1,17 @@
+use beacon_chain::beacon_proposer_cache::compute_proposer_duties_from_head;
 use beacon_chain::test_utils::RelativeSyncCommittee;
 use beacon_chain::{
     test_utils::{AttestationStrategy, BeaconChainHarness, BlockStrategy, EphemeralHarnessType},
-    BeaconChain, ChainConfig, StateSkipConfig, WhenSlotSkipped,
+    BeaconChain, ChainConfig, ForkChoiceStore, StateSkipConfig, WhenSlotSkipped,
 };
 use environment::null_logger;
 use eth2::{
+    lighthouse::{
+        AttestationPerformanceRequest, BlockReward, DatabaseOperation, EnrAddresses,
+        EventsWebSocketMessage, EventsWebSocketRequest, ExitingValidatorData, LogFilterData,
+        MerkleMultiproof, ProposerDutiesCertainty, Subsystem, SubsystemStatus,
+        SubsystemsHealthQuery,
+    },
     mixin::{RequestAccept, ResponseForkName, ResponseOptional},
     reqwest::RequestBuilder,
     types::{
@@ -19,19 +26,24 @@ use execution_layer::test_utils::{
 };
 use futures::stream::{Stream, StreamExt};
 use futures::FutureExt;
+use futures::SinkExt;
 use http_api::{
     test_utils::{create_api_server, ApiServer},
     BlockId, StateId,
 };
 use lighthouse_network::{types::SyncState, Enr, EnrExt, PeerId};
-use network::NetworkReceivers;
+use network::{NetworkMessage, NetworkReceivers};
 use proto_array::ExecutionStatus;
 use sensitive_url::SensitiveUrl;
+use serde_utils::quoted_u64::Quoted;
 use slot_clock::SlotClock;
+use state_processing::common::get_attesting_indices_from_state;
 use state_processing::per_block_processing::get_expected_withdrawals;
 use state_processing::per_slot_processing;
 use state_processing::state_advance::partial_state_advance;
 use std::convert::TryInto;
+use std::io::Read;
+use std::net::Ipv4Addr;
 use std::sync::Arc;
 use tokio::time::Duration;
 use tree_hash::TreeHash;
@@ -1033,6 +1045,59 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_beacon_states_validators_pagination(self) -> Self {
+        let state_id = CoreStateId::Head;
+        let all_validators = self
+            .client
+            .get_beacon_states_validators(state_id, None, None)
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+        assert!(all_validators.len() > 3);
+
+        // Paging through the validators returns each of them once, in order of index.
+        let limit = 3;
+        let mut paged_validators = vec![];
+        for offset in (0..all_validators.len() as u64 + limit).step_by(limit as usize) {
+            let page = self
+                .client
+                .get_beacon_states_validators_page(state_id, None, None, Some(offset), Some(limit))
+                .await
+                .unwrap()
+                .unwrap()
+                .data;
+            assert!(page.len() <= limit as usize);
+            paged_validators.extend(page);
+        }
+        assert_eq!(paged_validators, all_validators);
+
+        // Pagination applies to the validators which match the filters.
+        let ids = all_validators
+            .iter()
+            .skip(1)
+            .step_by(2)
+            .map(|validator| ValidatorId::Index(validator.index))
+            .collect::<Vec<_>>();
+        let page = self
+            .client
+            .get_beacon_states_validators_page(state_id, Some(&ids), None, Some(1), Some(2))
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+        let expected = all_validators
+            .into_iter()
+            .skip(1)
+            .step_by(2)
+            .skip(1)
+            .take(2)
+            .collect::<Vec<_>>();
+        assert_eq!(page, expected);
+
+        self
+    }
+
     pub async fn test_beacon_states_validator_id(self) -> Self {
         for state_id in self.interesting_state_ids() {
             let state_opt = state_id
@@ -1123,7 +1188,7 @@ impl ApiTester {
                     result
                         .validators
                         .into_iter()
-                        .map(|i| i as usize)
+                        .map(|i| i as u32)
                         .collect::<Vec<_>>(),
                     expected.committee.to_vec(),
                     "{}",
@@ -1558,6 +1623,67 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_beacon_blocks_finalized_cached(self) -> Self {
+        let finalized_root = self
+            .chain
+            .canonical_head
+            .cached_head()
+            .finalized_checkpoint()
+            .root;
+        let url = self
+            .client
+            .get_beacon_blocks_path(CoreBlockId::Root(finalized_root))
+            .unwrap();
+
+        let response = self.client.get_response(url.clone(), |b| b).await.unwrap();
+        assert_eq!(
+            response.headers()["Cache-Control"],
+            "public, max-age=31536000, immutable"
+        );
+        let etag = response.headers()["ETag"].to_str().unwrap().to_string();
+        let expected = response.bytes().await.unwrap();
+
+        // The second response is served from the cache.
+        let response = self.client.get_response(url.clone(), |b| b).await.unwrap();
+        assert_eq!(response.headers()["ETag"], etag.as_str());
+        assert_eq!(response.bytes().await.unwrap(), expected);
+
+        let error = self
+            .client
+            .get_response(url.clone(), |b| b.header("If-None-Match", etag.as_str()))
+            .await
+            .unwrap_err();
+        assert_eq!(error.status(), Some(StatusCode::NOT_MODIFIED));
+
+        // Responses which may be compressed carry the weak form of the tag, which also matches.
+        let weak_etag = format!("W/{etag}");
+        let response = self
+            .client
+            .get_response(url.clone(), |b| b.header("Accept-Encoding", "zstd"))
+            .await
+            .unwrap();
+        assert_eq!(response.headers()["ETag"], weak_etag.as_str());
+        let error = self
+            .client
+            .get_response(url, |b| {
+                b.header("Accept-Encoding", "zstd")
+                    .header("If-None-Match", weak_etag)
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(error.status(), Some(StatusCode::NOT_MODIFIED));
+
+        // The head block may still be reorged, so it is never cached.
+        let url = self
+            .client
+            .get_beacon_blocks_path(CoreBlockId::Head)
+            .unwrap();
+        let response = self.client.get_response(url, |b| b).await.unwrap();
+        assert!(response.headers().get("Cache-Control").is_none());
+
+        self
+    }
+
     pub async fn test_beacon_blinded_blocks(self) -> Self {
         for block_id in self.interesting_block_ids() {
             let expected = block_id
@@ -1667,6 +1793,58 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_post_blob_sidecars(self) -> Self {
+        let finalized_root = BlockId(CoreBlockId::Finalized).root(&self.chain).unwrap().0;
+        let head_root = BlockId(CoreBlockId::Head).root(&self.chain).unwrap().0;
+        let finalized_blobs = self
+            .client
+            .get_blobs::<E>(CoreBlockId::Root(finalized_root), None)
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+        let head_blobs = self
+            .client
+            .get_blobs::<E>(CoreBlockId::Root(head_root), None)
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+        assert!(!finalized_blobs.is_empty());
+
+        // Unknown blocks are omitted, and the indices filter applies to each block separately.
+        let requests = vec![
+            BlobSidecarsByRootRequest {
+                block_root: finalized_root,
+                indices: Some(vec![Quoted { value: 0 }]),
+            },
+            BlobSidecarsByRootRequest {
+                block_root: Hash256::repeat_byte(0xff),
+                indices: None,
+            },
+            BlobSidecarsByRootRequest {
+                block_root: head_root,
+                indices: None,
+            },
+        ];
+        let result = self
+            .client
+            .post_beacon_blob_sidecars::<E>(&requests)
+            .await
+            .unwrap()
+            .data;
+
+        let expected = finalized_blobs
+            .iter()
+            .take(1)
+            .chain(head_blobs.iter())
+            .cloned()
+            .collect::<Vec<_>>();
+        assert_eq!(result, expected);
+
+        self
+    }
+
     pub async fn test_beacon_blocks_attestations(self) -> Self {
         for block_id in self.interesting_block_ids() {
             let result = self
@@ -1872,6 +2050,36 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_beacon_light_client_updates(self) -> Self {
+        let current_period = self
+            .chain
+            .epoch()
+            .unwrap()
+            .sync_committee_period(&self.chain.spec)
+            .unwrap();
+
+        let result = match self
+            .client
+            .get_beacon_light_client_updates::<E>(current_period, 2)
+            .await
+        {
+            Ok(result) => result,
+            Err(e) => panic!("query failed incorrectly: {e:?}"),
+        };
+
+        // The following period hasn't started, so only the current period has an update.
+        let expected = self
+            .chain
+            .get_light_client_updates(current_period, 2)
+            .unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(expected.len(), 1);
+        assert_eq!(result[0].data, expected[0].0);
+        assert_eq!(result[0].version, Some(expected[0].1));
+
+        self
+    }
+
     pub async fn test_get_beacon_pool_attestations(self) -> Self {
         let result = self
             .client
@@ -2122,6 +2330,49 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_config_spec_revalidation(self) -> Self {
+        let (first, etag) = self
+            .client
+            .get_config_spec_if_none_match::<ConfigAndPresetElectra>(None)
+            .await
+            .unwrap()
+            .expect("response without a tag is always returned");
+        let etag = etag.expect("response has a tag");
+        assert_eq!(
+            ConfigAndPreset::Electra(first.data),
+            ConfigAndPreset::from_chain_spec::<E>(&self.chain.spec, None)
+        );
+
+        // The unchanged spec isn't sent again, while a stale tag receives the full response.
+        assert!(self
+            .client
+            .get_config_spec_if_none_match::<ConfigAndPresetElectra>(Some(&etag))
+            .await
+            .unwrap()
+            .is_none());
+        assert!(self
+            .client
+            .get_config_spec_if_none_match::<ConfigAndPresetElectra>(Some("\"stale\""))
+            .await
+            .unwrap()
+            .is_some());
+
+        let (_, genesis_etag) = self
+            .client
+            .get_beacon_genesis_if_none_match(None)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(self
+            .client
+            .get_beacon_genesis_if_none_match(genesis_etag.as_deref())
+            .await
+            .unwrap()
+            .is_none());
+
+        self
+    }
+
     pub async fn test_get_config_deposit_contract(self) -> Self {
         let result = self
             .client
@@ -2140,6 +2391,38 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_post_lighthouse_spec_diff(self) -> Self {
+        let config = Config::from_chain_spec::<E>(&self.chain.spec);
+        let result = self
+            .client
+            .post_lighthouse_spec_diff(&config)
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(result.differences, vec![]);
+        assert_eq!(result.validation_errors, Vec::<String>::new());
+
+        let mut spec = self.chain.spec.clone();
+        spec.seconds_per_slot += 1;
+        let mut config = Config::from_chain_spec::<E>(&spec);
+        let result = self
+            .client
+            .post_lighthouse_spec_diff(&config)
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(result.differences.len(), 1);
+        assert_eq!(result.differences[0].field_name, "seconds_per_slot");
+
+        config.preset_base = "gnosis".to_string();
+        self.client
+            .post_lighthouse_spec_diff(&config)
+            .await
+            .unwrap_err();
+
+        self
+    }
+
     pub async fn test_get_node_version(self) -> Self {
         let result = self.client.get_node_version().await.unwrap().data;
 
@@ -2384,6 +2667,40 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_debug_beacon_states_compressed(self) -> Self {
+        let url = self
+            .client
+            .get_debug_beacon_states_path(CoreStateId::Head)
+            .unwrap();
+        let get_ssz = |accept_encoding: &'static str| {
+            self.client.get_response(url.clone(), move |b| {
+                b.accept(Accept::Ssz)
+                    .header("Accept-Encoding", accept_encoding)
+            })
+        };
+
+        let uncompressed = get_ssz("identity").await.unwrap();
+        assert!(uncompressed.headers().get("Content-Encoding").is_none());
+        let expected = uncompressed.bytes().await.unwrap();
+
+        let response = get_ssz("gzip, zstd").await.unwrap();
+        assert_eq!(response.headers()["Content-Encoding"], "zstd");
+        let compressed = response.bytes().await.unwrap();
+        assert!(compressed.len() < expected.len());
+        assert_eq!(zstd::decode_all(&compressed[..]).unwrap(), expected);
+
+        let response = get_ssz("gzip;q=1.0, zstd;q=0.5").await.unwrap();
+        assert_eq!(response.headers()["Content-Encoding"], "gzip");
+        let compressed = response.bytes().await.unwrap();
+        let mut decompressed = vec![];
+        flate2::read::GzDecoder::new(&compressed[..])
+            .read_to_end(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, expected);
+
+        self
+    }
+
     pub async fn test_get_debug_beacon_heads(self) -> Self {
         let result = self
             .client
@@ -2417,7 +2734,12 @@ impl ApiTester {
             result.finalized_checkpoint,
             expected_proto_array.finalized_checkpoint
         );
+        assert_eq!(
+            result.proposer_boost_root,
+            beacon_fork_choice.fc_store().proposer_boost_root()
+        );
 
+        let queued_weights = beacon_fork_choice.queued_attestation_weights();
         let expected_fork_choice_nodes: Vec<ForkChoiceNode> = expected_proto_array
             .nodes
             .iter()
@@ -2436,7 +2758,10 @@ impl ApiTester {
                         .map(|parent| parent.root),
                     justified_epoch: node.justified_checkpoint.epoch,
                     finalized_epoch: node.finalized_checkpoint.epoch,
+                    justified_root: node.justified_checkpoint.root,
+                    finalized_root: node.finalized_checkpoint.root,
                     weight: node.weight,
+                    queued_weight: queued_weights.get(&node.root).copied().unwrap_or(0),
                     validity: execution_status,
                     execution_block_hash: node
                         .execution_status
@@ -2455,6 +2780,44 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_debug_fork_choice_descendants(self) -> Self {
+        let all_nodes = self
+            .client
+            .get_debug_fork_choice()
+            .await
+            .unwrap()
+            .fork_choice_nodes;
+
+        // The subtree of the head's parent contains the parent and its children, one of which is
+        // the head.
+        let head = self.chain.head_snapshot();
+        let parent_root = head.beacon_block.parent_root();
+        let result = self
+            .client
+            .get_debug_fork_choice_descendants(parent_root)
+            .await
+            .unwrap();
+        let expected = all_nodes
+            .into_iter()
+            .filter(|node| node.block_root == parent_root || node.parent_root == Some(parent_root))
+            .collect::<Vec<_>>();
+        assert_eq!(result.fork_choice_nodes, expected);
+        assert!(result
+            .fork_choice_nodes
+            .iter()
+            .any(|node| node.block_root == head.beacon_block_root));
+
+        // An unknown ancestor is not found.
+        let error = self
+            .client
+            .get_debug_fork_choice_descendants(Hash256::repeat_byte(0xff))
+            .await
+            .unwrap_err();
+        assert_eq!(error.status(), Some(StatusCode::NOT_FOUND));
+
+        self
+    }
+
     fn validator_count(&self) -> usize {
         self.chain.head_snapshot().beacon_state.validators().len()
     }
@@ -2955,6 +3318,22 @@ impl ApiTester {
             response.metadata.consensus_block_value
         );
         assert!(!metadata.consensus_block_value.is_zero());
+        assert_eq!(
+            metadata.local_payload_value,
+            response.metadata.local_payload_value
+        );
+        assert_eq!(
+            metadata.builder_bid_value,
+            response.metadata.builder_bid_value
+        );
+
+        // The value of the chosen payload is always reported.
+        let chosen_value = if metadata.execution_payload_blinded {
+            metadata.builder_bid_value
+        } else {
+            metadata.local_payload_value
+        };
+        assert_eq!(chosen_value, Some(metadata.execution_payload_value));
     }
 
     pub async fn test_block_production_v3_ssz(self) -> Self {
@@ -3630,6 +4009,43 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_post_validator_sync_committee_subscriptions(mut self) -> Self {
+        let subscriptions = (0..VALIDATOR_COUNT as u64)
+            .map(|validator_index| types::SyncCommitteeSubscription {
+                validator_index,
+                sync_committee_indices: vec![validator_index],
+                until_epoch: Epoch::new(1),
+            })
+            .collect::<Vec<_>>();
+
+        self.client
+            .post_validator_sync_committee_subscriptions(&subscriptions)
+            .await
+            .unwrap();
+
+        // The whole batch is queued as a single message.
+        match self
+            .network_rx
+            .validator_subscription_recv
+            .recv()
+            .now_or_never()
+            .unwrap()
+        {
+            Some(network::ValidatorSubscriptionMessage::SyncCommitteeSubscribe {
+                subscriptions: received,
+            }) => assert_eq!(received, subscriptions),
+            _ => panic!("expected a sync committee subscription message"),
+        }
+        assert!(self
+            .network_rx
+            .validator_subscription_recv
+            .recv()
+            .now_or_never()
+            .is_none());
+
+        self
+    }
+
     pub async fn test_post_validator_register_validator(self) -> Self {
         let mut registrations = vec![];
         let mut fee_recipients = vec![];
@@ -3699,6 +4115,36 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_lighthouse_builder_registration(self) -> Self {
+        // The registrations are forwarded by `test_post_validator_register_validator`.
+        for (val_index, keypair) in self.validator_keypairs().iter().enumerate() {
+            let forwarded = self
+                .client
+                .get_lighthouse_builder_registration(&keypair.pk.compress())
+                .await
+                .unwrap()
+                .unwrap()
+                .data;
+            assert_eq!(
+                forwarded.registration.message.fee_recipient,
+                Address::from_low_u64_be(val_index as u64)
+            );
+            assert_eq!(forwarded.registration.message.gas_limit, 11_111_111);
+            assert!(!forwarded.relays.is_empty());
+            assert!(forwarded.relays.iter().all(|relay| relay.accepted));
+        }
+
+        let unknown = Keypair::random().pk.compress();
+        assert!(self
+            .client
+            .get_lighthouse_builder_registration(&unknown)
+            .await
+            .unwrap()
+            .is_none());
+
+        self
+    }
+
     pub async fn test_post_validator_register_validator_slashed(self) -> Self {
         // slash a validator
         self.client
@@ -3831,7 +4277,7 @@ impl ApiTester {
             .unwrap();
         let attesting_validators: Vec<usize> = committees
             .into_iter()
-            .flat_map(|committee| committee.committee.iter().cloned())
+            .flat_map(|committee| committee.committee.iter().map(|&i| i as usize))
             .collect();
         // All attesters should now be considered live
         let expected = expected
@@ -3923,6 +4369,8 @@ impl ApiTester {
             .await
             .unwrap();
         Self::check_block_v3_metadata(&metadata, &payload_type);
+        // The builder bid which was rejected in favour of the local payload is reported.
+        assert!(metadata.builder_bid_value.is_some());
 
         let payload: FullPayload<E> = match payload_type.data {
             ProduceBlockV3Response::Full(payload) => {
@@ -3950,6 +4398,8 @@ impl ApiTester {
             .await
             .unwrap();
         Self::check_block_v3_metadata(&metadata, &payload_type);
+        // The local payload which was rejected in favour of the builder bid is reported.
+        assert!(metadata.local_payload_value.is_some());
 
         let payload: BlindedPayload<E> = match payload_type.data {
             ProduceBlockV3Response::Blinded(payload) => {
@@ -4561,6 +5011,21 @@ impl ApiTester {
             self.harness.advance_slot();
         }
 
+        let circuit_breaker = self
+            .client
+            .get_lighthouse_builder_circuit_breaker()
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(
+            circuit_breaker.status,
+            eth2::lighthouse::ChainHealthStatus::Unhealthy
+        );
+        assert_eq!(
+            circuit_breaker.tripped_condition(),
+            Some(eth2::lighthouse::CircuitBreakerCondition::Skips)
+        );
+
         let (_, randao_reveal) = self.get_test_randao(slot, epoch).await;
 
         let payload: BlindedPayload<E> = self
@@ -5336,22 +5801,363 @@ impl ApiTester {
         self
     }
 
-    pub async fn test_get_lighthouse_syncing(self) -> Self {
-        self.client.get_lighthouse_syncing().await.unwrap();
-
-        self
-    }
-
-    pub async fn test_get_lighthouse_proto_array(self) -> Self {
-        self.client.get_lighthouse_proto_array().await.unwrap();
-
-        self
-    }
-
-    pub async fn test_get_lighthouse_validator_inclusion_global(self) -> Self {
-        let epoch = self.chain.epoch().unwrap() - 1;
-        self.client
-            .get_lighthouse_validator_inclusion_global(epoch)
+    pub async fn test_get_lighthouse_health_subsystems(self) -> Self {
+        let health = self
+            .client
+            .get_lighthouse_health_subsystems(&SubsystemsHealthQuery::default())
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(health.required, Subsystem::ALL.to_vec());
+        assert_eq!(
+            health
+                .subsystems
+                .iter()
+                .map(|subsystem| subsystem.subsystem)
+                .collect::<Vec<_>>(),
+            Subsystem::ALL.to_vec()
+        );
+        let status_of = |subsystem| {
+            health
+                .subsystems
+                .iter()
+                .find(|health| health.subsystem == subsystem)
+                .unwrap()
+                .status
+        };
+        assert_eq!(status_of(Subsystem::Database), SubsystemStatus::Healthy);
+        assert_eq!(status_of(Subsystem::Sync), SubsystemStatus::Healthy);
+        assert_eq!(status_of(Subsystem::Slasher), SubsystemStatus::Disabled);
+
+        let client = &self.client;
+        let aggregate = |query: SubsystemsHealthQuery| async move {
+            let health = client
+                .get_lighthouse_health_subsystems(&query)
+                .await
+                .unwrap()
+                .data;
+            assert_eq!(health.code, health.status.status_code());
+            health.status
+        };
+        let require = |subsystems: &[Subsystem]| SubsystemsHealthQuery {
+            require: Some(subsystems.to_vec()),
+            ..SubsystemsHealthQuery::default()
+        };
+
+        assert_eq!(
+            aggregate(require(&[Subsystem::Database, Subsystem::Slasher])).await,
+            SubsystemStatus::Healthy
+        );
+
+        // Unhealthy responses are returned with `503 Service Unavailable`.
+        let too_few_peers = SubsystemsHealthQuery {
+            min_peers: Some(usize::MAX),
+            ..require(&[Subsystem::Peers])
+        };
+        assert_eq!(aggregate(too_few_peers).await, SubsystemStatus::Unhealthy);
+
+        // Syncing is degraded, unless the probe is strict.
+        *self
+            .ctx
+            .network_globals
+            .as_ref()
+            .unwrap()
+            .sync_state
+            .write() = SyncState::SyncTransition;
+        assert_eq!(
+            aggregate(require(&[Subsystem::Sync])).await,
+            SubsystemStatus::Degraded
+        );
+        let strict = SubsystemsHealthQuery {
+            strict: true,
+            ..require(&[Subsystem::Sync])
+        };
+        assert_eq!(aggregate(strict).await, SubsystemStatus::Unhealthy);
+        *self
+            .ctx
+            .network_globals
+            .as_ref()
+            .unwrap()
+            .sync_state
+            .write() = SyncState::Synced;
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_duties_proposer(self) -> Self {
+        let current_epoch = self.chain.epoch().unwrap();
+
+        // Up to the next epoch, the duties are those of the standard endpoint.
+        for (epoch, certainty) in [
+            (current_epoch, ProposerDutiesCertainty::Decided),
+            (current_epoch + 1, ProposerDutiesCertainty::SeedKnown),
+        ] {
+            let lookahead = self
+                .client
+                .get_lighthouse_duties_proposer(epoch)
+                .await
+                .unwrap();
+            let standard = self
+                .client
+                .get_validator_duties_proposer(epoch)
+                .await
+                .unwrap();
+            assert_eq!(lookahead.certainty, certainty);
+            assert_eq!(lookahead.data, standard.data);
+            if certainty == ProposerDutiesCertainty::Decided {
+                assert_eq!(lookahead.dependent_root, Some(standard.dependent_root));
+            } else {
+                assert_eq!(lookahead.dependent_root, None);
+            }
+        }
+
+        // Later epochs are predicted from the head state.
+        let epoch = current_epoch + 4;
+        let lookahead = self
+            .client
+            .get_lighthouse_duties_proposer(epoch)
+            .await
+            .unwrap();
+        let (indices, _, _, _) = compute_proposer_duties_from_head(epoch, &self.chain).unwrap();
+        assert_eq!(lookahead.certainty, ProposerDutiesCertainty::Speculative);
+        assert_eq!(lookahead.dependent_root, None);
+        assert_eq!(
+            lookahead.head_block_root,
+            self.chain.head_beacon_block_root()
+        );
+        assert_eq!(
+            lookahead
+                .data
+                .iter()
+                .map(|duty| duty.validator_index as usize)
+                .collect::<Vec<_>>(),
+            indices
+        );
+        assert_eq!(
+            lookahead.data[0].slot,
+            epoch.start_slot(E::slots_per_epoch())
+        );
+
+        let error = self
+            .client
+            .get_lighthouse_duties_proposer(current_epoch + 5)
+            .await
+            .unwrap_err();
+        assert_eq!(error.status(), Some(StatusCode::BAD_REQUEST));
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_proof(self) -> Self {
+        let head = self.chain.head_snapshot();
+        let mut state = head.beacon_state.clone();
+        let state_root = state.canonical_root().unwrap();
+
+        // The `slot` and `balances` fields, and the chunk of the first four balances, which lies
+        // at the depth of 2^40 balances packed 4 to a chunk beneath the list's data root.
+        let first_leaf = state.num_fields_pow2() as u64;
+        let balances_chunk = ((first_leaf + 12) * 2) << 38;
+        let gindices = [first_leaf + 2, first_leaf + 12, balances_chunk];
+        let proof = self
+            .client
+            .get_lighthouse_proof_state(CoreStateId::Head, &gindices)
+            .await
+            .unwrap()
+            .data;
+        let mut first_chunk = [0; 32];
+        for (i, balance) in state.balances().iter().take(4).enumerate() {
+            first_chunk[i * 8..(i + 1) * 8].copy_from_slice(&balance.to_le_bytes());
+        }
+        assert_eq!(proof.root, state_root);
+        assert_eq!(proof.gindices, gindices);
+        assert_eq!(
+            proof.leaves,
+            vec![
+                state.slot().tree_hash_root(),
+                state.balances().tree_hash_root(),
+                Hash256::from(first_chunk),
+            ]
+        );
+        assert!(verify_multiproof(&proof));
+
+        // The block's slot, its body root and the left child of the body root.
+        let block = &head.beacon_block;
+        let gindices = [8, 12, 24];
+        let proof = self
+            .client
+            .get_lighthouse_proof_block(CoreBlockId::Head, &gindices)
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(proof.root, block.canonical_root());
+        assert_eq!(
+            proof.leaves[..2],
+            [
+                block.slot().tree_hash_root(),
+                block.message().body().tree_hash_root()
+            ]
+        );
+        assert!(verify_multiproof(&proof));
+
+        // Nodes within the elements of lists are not supported.
+        let error = self
+            .client
+            .get_lighthouse_proof_state(CoreStateId::Head, &[balances_chunk * 2])
+            .await
+            .unwrap_err();
+        assert_eq!(error.status(), Some(StatusCode::BAD_REQUEST));
+        let error = self
+            .client
+            .get_lighthouse_proof_block(CoreBlockId::Head, &[])
+            .await
+            .unwrap_err();
+        assert_eq!(error.status(), Some(StatusCode::BAD_REQUEST));
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_validators_slashed_and_exiting(self) -> Self {
+        // Validators 0 and 1 are slashed by the attester slashing, 2 by the proposer slashing, and
+        // 3 exits voluntarily.
+        self.client
+            .post_beacon_pool_attester_slashings_v1(&self.attester_slashing)
+            .await
+            .unwrap();
+        self.client
+            .post_beacon_pool_proposer_slashings(&self.proposer_slashing)
+            .await
+            .unwrap();
+        self.client
+            .post_beacon_pool_voluntary_exits(&self.voluntary_exit)
+            .await
+            .unwrap();
+        self.harness
+            .extend_chain(
+                1,
+                BlockStrategy::OnCanonicalHead,
+                AttestationStrategy::AllValidators,
+            )
+            .await;
+
+        let state = self.chain.head_beacon_state_cloned();
+        let slashed = self
+            .client
+            .get_lighthouse_validators_slashed()
+            .await
+            .unwrap()
+            .data;
+        let exiting = self
+            .client
+            .get_lighthouse_validators_exiting()
+            .await
+            .unwrap()
+            .data;
+
+        let indices = |validators: &[ExitingValidatorData]| {
+            validators.iter().map(|v| v.index).collect::<Vec<_>>()
+        };
+        assert_eq!(indices(&slashed), vec![0, 1, 2]);
+        assert_eq!(indices(&exiting), vec![0, 1, 2, 3]);
+
+        for data in slashed.iter().chain(&exiting) {
+            let validator = state.get_validator(data.index as usize).unwrap();
+            assert_eq!(data.pubkey, validator.pubkey);
+            assert_eq!(data.slashed, validator.slashed);
+            assert_eq!(data.exit_epoch, validator.exit_epoch);
+            assert_eq!(data.withdrawable_epoch, validator.withdrawable_epoch);
+        }
+        assert_eq!(exiting[3].status, ValidatorStatus::ActiveExiting);
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_analysis_graffiti(self) -> Self {
+        let analysis = self
+            .client
+            .get_lighthouse_analysis_graffiti()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            analysis.end_slot,
+            self.chain.head_snapshot().beacon_block.slot()
+        );
+        assert!(analysis.blocks > 0);
+        assert_eq!(
+            analysis.consensus_clients.values().sum::<u64>(),
+            analysis.blocks
+        );
+        assert_eq!(
+            analysis.execution_clients.values().sum::<u64>(),
+            analysis.blocks
+        );
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_debug_blinded_blocks(self) -> Self {
+        let head_slot = self.chain.head_snapshot().beacon_block.slot();
+        let expected = (0..=head_slot.as_u64())
+            .filter_map(|slot| {
+                self.chain
+                    .block_root_at_slot(Slot::new(slot), WhenSlotSkipped::None)
+                    .unwrap()
+            })
+            .map(|root| self.chain.get_blinded_block(&root).unwrap().unwrap())
+            .collect::<Vec<_>>();
+
+        // The range is truncated at the head.
+        let blocks = self
+            .client
+            .get_lighthouse_debug_blinded_blocks::<E>(
+                Slot::new(0),
+                head_slot.as_u64() + 10,
+                &self.chain.spec,
+            )
+            .await
+            .unwrap();
+        assert_eq!(blocks, expected);
+
+        let blocks = self
+            .client
+            .get_lighthouse_debug_blinded_blocks::<E>(Slot::new(1), 2, &self.chain.spec)
+            .await
+            .unwrap();
+        assert_eq!(
+            blocks,
+            expected
+                .iter()
+                .filter(|block| block.slot() == 1 || block.slot() == 2)
+                .cloned()
+                .collect::<Vec<_>>()
+        );
+
+        let error = self
+            .client
+            .get_lighthouse_debug_blinded_blocks::<E>(Slot::new(0), 0, &self.chain.spec)
+            .await
+            .unwrap_err();
+        assert_eq!(error.status(), Some(StatusCode::BAD_REQUEST));
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_syncing(self) -> Self {
+        self.client.get_lighthouse_syncing().await.unwrap();
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_proto_array(self) -> Self {
+        self.client.get_lighthouse_proto_array().await.unwrap();
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_validator_inclusion_global(self) -> Self {
+        let epoch = self.chain.epoch().unwrap() - 1;
+        self.client
+            .get_lighthouse_validator_inclusion_global(epoch)
             .await
             .unwrap();
 
@@ -5394,6 +6200,124 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_post_lighthouse_peers_actions(mut self) -> Self {
+        let peer_id = self.external_peer_id;
+        let peer = peer_id.to_string();
+
+        self.client
+            .post_lighthouse_peers_disconnect(&peer)
+            .await
+            .unwrap();
+        assert!(matches!(
+            self.network_rx.network_recv.recv().await,
+            Some(NetworkMessage::DisconnectPeer { peer_id: id }) if id == peer_id
+        ));
+
+        self.client.post_lighthouse_peers_ban(&peer).await.unwrap();
+        assert!(matches!(
+            self.network_rx.network_recv.recv().await,
+            Some(NetworkMessage::GoodbyePeer { peer_id: id, .. }) if id == peer_id
+        ));
+
+        self.client
+            .post_lighthouse_peers_unban(&peer)
+            .await
+            .unwrap();
+        assert!(matches!(
+            self.network_rx.network_recv.recv().await,
+            Some(NetworkMessage::UnbanPeer { peer_id: id }) if id == peer_id
+        ));
+
+        // Unknown peers and invalid peer IDs are rejected.
+        let error = self
+            .client
+            .post_lighthouse_peers_ban(&PeerId::random().to_string())
+            .await
+            .unwrap_err();
+        assert_eq!(error.status(), Some(StatusCode::NOT_FOUND));
+        let error = self
+            .client
+            .post_lighthouse_peers_ban("not-a-peer-id")
+            .await
+            .unwrap_err();
+        assert_eq!(error.status(), Some(StatusCode::BAD_REQUEST));
+
+        self
+    }
+
+    pub async fn test_lighthouse_enr(mut self) -> Self {
+        let result = self.client.get_lighthouse_enr().await.unwrap().data;
+        assert_eq!(result.enr, self.local_enr.to_base64());
+        assert_eq!(result.seq, self.local_enr.seq());
+        assert_eq!(result.addresses, EnrAddresses::from_enr(&self.local_enr));
+
+        let update = EnrAddresses {
+            ip4: Some(Ipv4Addr::new(1, 2, 3, 4)),
+            udp4: Some(9001),
+            tcp4: Some(9002),
+            ..EnrAddresses::default()
+        };
+        self.client.post_lighthouse_enr(&update).await.unwrap();
+        assert!(matches!(
+            self.network_rx.network_recv.recv().await,
+            Some(NetworkMessage::UpdateEnrAddresses { update: received }) if received == update
+        ));
+
+        let error = self
+            .client
+            .post_lighthouse_enr(&EnrAddresses::default())
+            .await
+            .unwrap_err();
+        assert_eq!(error.status(), Some(StatusCode::BAD_REQUEST));
+
+        self
+    }
+
+    pub async fn test_lighthouse_logging(self) -> Self {
+        let initial = self.client.get_lighthouse_logging().await.unwrap().data;
+        assert_eq!(initial.level, "info");
+        assert!(initial.targets.is_empty());
+
+        let filter = LogFilterData {
+            level: "warn".to_string(),
+            targets: [("network".to_string(), "debug".to_string())]
+                .into_iter()
+                .collect(),
+        };
+        self.client.post_lighthouse_logging(&filter).await.unwrap();
+        assert_eq!(
+            self.client.get_lighthouse_logging().await.unwrap().data,
+            filter
+        );
+
+        let invalid = LogFilterData {
+            level: "loud".to_string(),
+            targets: Default::default(),
+        };
+        let error = self
+            .client
+            .post_lighthouse_logging(&invalid)
+            .await
+            .unwrap_err();
+        assert_eq!(error.status(), Some(StatusCode::BAD_REQUEST));
+        assert_eq!(
+            self.client.get_lighthouse_logging().await.unwrap().data,
+            filter
+        );
+
+        self.client.post_lighthouse_logging(&initial).await.unwrap();
+
+        let recent = self
+            .client
+            .get_lighthouse_logging_recent(Some(10))
+            .await
+            .unwrap()
+            .data;
+        assert!(recent.len() <= 10);
+
+        self
+    }
+
     pub async fn test_get_lighthouse_staking(self) -> Self {
         let result = self.client.get_lighthouse_staking().await.unwrap();
 
@@ -5402,6 +6326,99 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_lighthouse_analysis_block_rewards(self) -> Self {
+        let head_slot = self.chain.head_snapshot().beacon_block.slot();
+        let rewards = self
+            .client
+            .get_lighthouse_analysis_block_rewards(Slot::new(1), head_slot)
+            .await
+            .unwrap();
+
+        // There is one reward for each block in the range, in order of slot.
+        let expected_roots = (1..=head_slot.as_u64())
+            .filter_map(|slot| {
+                self.chain
+                    .block_root_at_slot(Slot::new(slot), WhenSlotSkipped::None)
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rewards
+                .iter()
+                .map(|reward| reward.block_root)
+                .collect::<Vec<_>>(),
+            expected_roots
+        );
+
+        // The streamed response is passed through uncompressed, even when compression is accepted.
+        let url = format!(
+            "{}lighthouse/analysis/block_rewards?start_slot=1&end_slot={}",
+            self.client.as_ref(),
+            head_slot
+        );
+        let response = self
+            .client
+            .get_response(url, |b| b.header("Accept-Encoding", "zstd, gzip"))
+            .await
+            .unwrap();
+        assert!(response.headers().get("Content-Encoding").is_none());
+        let streamed: Vec<BlockReward> = response.json().await.unwrap();
+        assert_eq!(streamed.len(), expected_roots.len());
+
+        // Invalid ranges are rejected before the response starts.
+        assert!(self
+            .client
+            .get_lighthouse_analysis_block_rewards(head_slot, Slot::new(1))
+            .await
+            .is_err());
+
+        self
+    }
+
+    pub async fn test_post_lighthouse_analysis_attestation_performance(self) -> Self {
+        let (start_epoch, end_epoch) = (Epoch::new(0), Epoch::new(1));
+
+        // Duplicate indices are only included once.
+        let request = AttestationPerformanceRequest {
+            indices: vec![2, 0, 2],
+            start_epoch,
+            end_epoch,
+        };
+        let result = self
+            .client
+            .post_lighthouse_analysis_attestation_performance(&request)
+            .await
+            .unwrap();
+
+        let mut expected = vec![];
+        for index in [0, 2] {
+            expected.extend(
+                self.client
+                    .get_lighthouse_analysis_attestation_performance(
+                        start_epoch,
+                        end_epoch,
+                        index.to_string(),
+                    )
+                    .await
+                    .unwrap(),
+            );
+        }
+        assert_eq!(result, expected);
+
+        let request = AttestationPerformanceRequest {
+            indices: vec![],
+            start_epoch,
+            end_epoch,
+        };
+        assert!(self
+            .client
+            .post_lighthouse_analysis_attestation_performance(&request)
+            .await
+            .is_err());
+
+        self
+    }
+
     pub async fn test_get_lighthouse_database_info(self) -> Self {
         let info = self.client.get_lighthouse_database_info().await.unwrap();
 
@@ -5425,6 +6442,32 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_post_lighthouse_database_maintenance(self) -> Self {
+        // Without API tokens there is no admin scope to require, so maintenance is refused.
+        // Running it with an admin token is tested in `interactive_tests`.
+        for operation in [
+            DatabaseOperation::PrunePayloads,
+            DatabaseOperation::PruneBlobs,
+            DatabaseOperation::Compact,
+        ] {
+            let error = self
+                .client
+                .post_lighthouse_database_maintenance(operation)
+                .await
+                .unwrap_err();
+            assert_eq!(error.status(), Some(StatusCode::FORBIDDEN));
+        }
+
+        let error = self
+            .client
+            .get_lighthouse_database_job(u64::MAX)
+            .await
+            .unwrap_err();
+        assert_eq!(error.status(), Some(StatusCode::NOT_FOUND));
+
+        self
+    }
+
     pub async fn test_post_lighthouse_liveness(self) -> Self {
         let epoch = self.chain.epoch().unwrap();
         let head_state = self.chain.head_beacon_state_cloned();
@@ -5471,7 +6514,7 @@ impl ApiTester {
             .unwrap();
         let attesting_validators: Vec<usize> = committees
             .into_iter()
-            .flat_map(|committee| committee.committee.iter().cloned())
+            .flat_map(|committee| committee.committee.iter().map(|&i| i as usize))
             .collect();
         // All attesters should now be considered live
         let expected = expected
@@ -5755,6 +6798,45 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_expected_withdrawals_proposal_slot(self) -> Self {
+        let state_id = CoreStateId::Head;
+        let (mut state, _, _) = StateId(state_id).state(&self.chain).unwrap();
+        let (state_root, _, _) = StateId(state_id).root(&self.chain).unwrap();
+        let state_slot = state.slot();
+
+        // A proposal in the next epoch requires the state to be advanced through an epoch boundary.
+        let proposal_slot = (state.current_epoch() + 1).start_slot(E::slots_per_epoch()) + 1;
+        partial_state_advance(
+            &mut state,
+            Some(state_root),
+            proposal_slot,
+            &self.chain.spec,
+        )
+        .unwrap();
+        let expected_withdrawals = get_expected_withdrawals(&state, &self.chain.spec)
+            .unwrap()
+            .0;
+
+        let response = self
+            .client
+            .get_expected_withdrawals_for_proposal(&state_id, Some(proposal_slot))
+            .await
+            .unwrap();
+        assert_eq!(response.data, expected_withdrawals.to_vec());
+
+        // The proposal must be after the state, and within the lookahead limit.
+        for proposal_slot in [state_slot, state_slot + 2 * E::slots_per_epoch()] {
+            let error = self
+                .client
+                .get_expected_withdrawals_for_proposal(&state_id, Some(proposal_slot))
+                .await
+                .unwrap_err();
+            assert_eq!(error.status(), Some(StatusCode::BAD_REQUEST));
+        }
+
+        self
+    }
+
     pub async fn test_get_expected_withdrawals_pre_capella(self) -> Self {
         let state_id = CoreStateId::Head;
 
@@ -5770,6 +6852,151 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_events_filtered(self) -> Self {
+        let mut state = self.chain.head_beacon_state_cloned();
+        state
+            .build_committee_cache(RelativeEpoch::Current, &self.chain.spec)
+            .unwrap();
+        let attesters = |attestation: &Attestation<E>| {
+            get_attesting_indices_from_state(&state, attestation.to_ref()).unwrap()
+        };
+        let validator_index = attesters(&self.attestations[0])[0];
+        let slot = self.attestations[0].data().slot;
+
+        // Attestations for other slots are not sent.
+        let mut other_slot_events = self
+            .client
+            .get_events_filtered::<E>(
+                &[EventTopic::Attestation, EventTopic::VoluntaryExit],
+                None,
+                Some(&[slot + 1]),
+            )
+            .await
+            .unwrap();
+        // Only the attestations of `validator_index` are sent.
+        let mut validator_events = self
+            .client
+            .get_events_filtered::<E>(
+                &[EventTopic::Attestation, EventTopic::VoluntaryExit],
+                Some(&[validator_index]),
+                Some(&[slot]),
+            )
+            .await
+            .unwrap();
+
+        self.client
+            .post_beacon_pool_attestations_v1(self.attestations.as_slice())
+            .await
+            .unwrap();
+        // Other topics are not filtered, so the exit marks the end of the attestation events.
+        self.client
+            .post_beacon_pool_voluntary_exits(&self.voluntary_exit)
+            .await
+            .unwrap();
+
+        let exit_event = EventKind::VoluntaryExit(self.voluntary_exit.clone());
+        let mut expected_events = self
+            .attestations
+            .iter()
+            .filter(|attestation| attesters(attestation).contains(&validator_index))
+            .map(|attestation| EventKind::Attestation(Box::new(attestation.clone())))
+            .collect::<Vec<_>>();
+        assert_eq!(expected_events.len(), 1);
+        expected_events.push(exit_event.clone());
+
+        let events = poll_events(&mut other_slot_events, 1, Duration::from_millis(10000)).await;
+        assert_eq!(events.as_slice(), &[exit_event]);
+
+        let events = poll_events(
+            &mut validator_events,
+            expected_events.len(),
+            Duration::from_millis(10000),
+        )
+        .await;
+        assert_eq!(events, expected_events);
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_events_websocket(self) -> Self {
+        let url = self
+            .client
+            .lighthouse_events_websocket_url(&[EventTopic::Head])
+            .unwrap();
+        let (mut socket, _) = tokio_tungstenite::connect_async(url.as_str())
+            .await
+            .unwrap();
+
+        // The topics of the query are subscribed to when the socket opens.
+        assert_eq!(
+            next_websocket_message(&mut socket).await,
+            EventsWebSocketMessage::Subscriptions {
+                topics: vec![EventTopic::Head]
+            }
+        );
+
+        for (request, expected_topics) in [
+            (
+                EventsWebSocketRequest::Subscribe {
+                    topics: vec![EventTopic::VoluntaryExit, EventTopic::Head],
+                },
+                vec![EventTopic::Head, EventTopic::VoluntaryExit],
+            ),
+            (
+                EventsWebSocketRequest::Unsubscribe {
+                    topics: vec![EventTopic::Head],
+                },
+                vec![EventTopic::VoluntaryExit],
+            ),
+        ] {
+            send_websocket_message(&mut socket, &request).await;
+            assert_eq!(
+                next_websocket_message(&mut socket).await,
+                EventsWebSocketMessage::Subscriptions {
+                    topics: expected_topics
+                }
+            );
+        }
+
+        self.client
+            .post_beacon_pool_voluntary_exits(&self.voluntary_exit)
+            .await
+            .unwrap();
+
+        let EventsWebSocketMessage::Event { event, data } =
+            next_websocket_message(&mut socket).await
+        else {
+            panic!("expected an event");
+        };
+        assert_eq!(
+            EventKind::<E>::from_event_data(&event, &data.to_string()).unwrap(),
+            EventKind::VoluntaryExit(self.voluntary_exit.clone())
+        );
+
+        // Invalid requests are reported without closing the socket.
+        socket
+            .send(tokio_tungstenite::tungstenite::Message::text("{}"))
+            .await
+            .unwrap();
+        assert!(matches!(
+            next_websocket_message(&mut socket).await,
+            EventsWebSocketMessage::Error { .. }
+        ));
+        send_websocket_message(
+            &mut socket,
+            &EventsWebSocketRequest::Unsubscribe {
+                topics: vec![EventTopic::VoluntaryExit],
+            },
+        )
+        .await;
+        assert_eq!(
+            next_websocket_message(&mut socket).await,
+            EventsWebSocketMessage::Subscriptions { topics: vec![] }
+        );
+
+        self
+    }
+
     pub async fn test_get_events_altair(self) -> Self {
         let topics = vec![EventTopic::ContributionAndProof];
         let mut events_future = self
@@ -5877,6 +7104,51 @@ impl ApiTester {
     }
 }
 
+/// Checks that `proof` has the expected helper indices and verifies against its root.
+fn verify_multiproof(proof: &MerkleMultiproof) -> bool {
+    let gindices = proof
+        .gindices
+        .iter()
+        .map(|&gindex| gindex as usize)
+        .collect::<Vec<_>>();
+    let helper_indices = merkle_proof::get_helper_indices(&gindices)
+        .into_iter()
+        .map(|index| index as u64)
+        .collect::<Vec<_>>();
+    helper_indices == proof.helper_indices
+        && merkle_proof::verify_merkle_multiproof(
+            &proof.leaves,
+            &proof.proof,
+            &gindices,
+            proof.root,
+        )
+}
+
+type EventsWebSocket =
+    tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>;
+
+async fn send_websocket_message(socket: &mut EventsWebSocket, request: &EventsWebSocketRequest) {
+    let text = serde_json::to_string(request).unwrap();
+    socket
+        .send(tokio_tungstenite::tungstenite::Message::text(text))
+        .await
+        .unwrap();
+}
+
+/// Returns the next text message sent by the server, skipping pings.
+async fn next_websocket_message(socket: &mut EventsWebSocket) -> EventsWebSocketMessage {
+    loop {
+        let message = tokio::time::timeout(Duration::from_secs(10), socket.next())
+            .await
+            .expect("timed out waiting for a message")
+            .expect("socket closed")
+            .unwrap();
+        if let tokio_tungstenite::tungstenite::Message::Text(text) = message {
+            return serde_json::from_str(&text).unwrap();
+        }
+    }
+}
+
 async fn poll_events<S: Stream<Item = Result<EventKind<E>, eth2::Error>> + Unpin, E: EthSpec>(
     stream: &mut S,
     num_events: usize,
@@ -5906,6 +7178,27 @@ async fn get_events() {
     ApiTester::new().await.test_get_events().await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn get_events_filtered() {
+    ApiTester::new().await.test_get_events_filtered().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn get_lighthouse_validators_slashed_and_exiting() {
+    ApiTester::new()
+        .await
+        .test_get_lighthouse_validators_slashed_and_exiting()
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn get_lighthouse_events_websocket() {
+    ApiTester::new()
+        .await
+        .test_get_lighthouse_events_websocket()
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn get_events_altair() {
     let mut config = ApiTesterConfig::default();
@@ -5960,6 +7253,8 @@ async fn beacon_get() {
         .await
         .test_beacon_states_validators()
         .await
+        .test_beacon_states_validators_pagination()
+        .await
         .test_beacon_states_validator_balances()
         .await
         .test_beacon_states_committees()
@@ -5976,6 +7271,8 @@ async fn beacon_get() {
         .await
         .test_beacon_blocks()
         .await
+        .test_beacon_blocks_finalized_cached()
+        .await
         .test_beacon_blinded_blocks()
         .await
         .test_beacon_blocks_attestations()
@@ -6133,7 +7430,11 @@ async fn config_get() {
         .await
         .test_get_config_spec()
         .await
+        .test_get_config_spec_revalidation()
+        .await
         .test_get_config_deposit_contract()
+        .await
+        .test_post_lighthouse_spec_diff()
         .await;
 }
 
@@ -6143,9 +7444,13 @@ async fn debug_get() {
         .await
         .test_get_debug_beacon_states()
         .await
+        .test_get_debug_beacon_states_compressed()
+        .await
         .test_get_debug_beacon_heads()
         .await
         .test_get_debug_fork_choice()
+        .await
+        .test_get_debug_fork_choice_descendants()
         .await;
 }
 
@@ -6207,6 +7512,18 @@ async fn get_light_client_finality_update() {
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn get_light_client_updates() {
+    let config = ApiTesterConfig {
+        spec: ForkName::Altair.make_genesis_spec(E::default_spec()),
+        ..<_>::default()
+    };
+    ApiTester::new_from_config(config)
+        .await
+        .test_get_beacon_light_client_updates()
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn get_validator_duties_early() {
     ApiTester::new()
@@ -6494,11 +7811,21 @@ async fn get_validator_beacon_committee_subscriptions() {
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn post_validator_sync_committee_subscriptions() {
+    ApiTester::new()
+        .await
+        .test_post_validator_sync_committee_subscriptions()
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn post_validator_register_validator() {
     ApiTester::new()
         .await
         .test_post_validator_register_validator()
+        .await
+        .test_get_lighthouse_builder_registration()
         .await;
 }
 
@@ -6801,6 +8128,8 @@ async fn get_blob_sidecars() {
         .test_get_blob_sidecars(false)
         .await
         .test_get_blob_sidecars(true)
+        .await
+        .test_post_blob_sidecars()
         .await;
 }
 
@@ -6818,6 +8147,16 @@ async fn lighthouse_endpoints() {
         .await
         .test_get_lighthouse_health()
         .await
+        .test_get_lighthouse_health_subsystems()
+        .await
+        .test_get_lighthouse_duties_proposer()
+        .await
+        .test_get_lighthouse_proof()
+        .await
+        .test_get_lighthouse_analysis_graffiti()
+        .await
+        .test_get_lighthouse_debug_blinded_blocks()
+        .await
         .test_get_lighthouse_syncing()
         .await
         .test_get_lighthouse_proto_array()
@@ -6834,10 +8173,22 @@ async fn lighthouse_endpoints() {
         .await
         .test_get_lighthouse_staking()
         .await
+        .test_post_lighthouse_peers_actions()
+        .await
+        .test_lighthouse_enr()
+        .await
+        .test_lighthouse_logging()
+        .await
         .test_get_lighthouse_database_info()
         .await
+        .test_get_lighthouse_analysis_block_rewards()
+        .await
+        .test_post_lighthouse_analysis_attestation_performance()
+        .await
         .test_post_lighthouse_database_reconstruct()
         .await
+        .test_post_lighthouse_database_maintenance()
+        .await
         .test_post_lighthouse_liveness()
         .await;
 }
@@ -6881,5 +8232,7 @@ async fn expected_withdrawals_valid_capella() {
     ApiTester::new_from_config(config)
         .await
         .test_get_expected_withdrawals_capella()
+        .await
+        .test_get_expected_withdrawals_proposal_slot()
         .await;
 }
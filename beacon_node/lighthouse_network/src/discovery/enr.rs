@@ -7,11 +7,13 @@ use super::ENR_FILENAME;
 use crate::types::{Enr, EnrAttestationBitfield, EnrSyncCommitteeBitfield};
 use crate::NetworkConfig;
 use libp2p::identity::Keypair;
+use serde::{Deserialize, Serialize};
 use slog::{debug, warn};
 use ssz::{Decode, Encode};
 use ssz_types::BitVector;
 use std::fs::File;
 use std::io::prelude::*;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
 use std::path::Path;
 use std::str::FromStr;
 use types::{EnrForkId, EthSpec};
@@ -25,6 +27,73 @@ pub const ATTESTATION_BITFIELD_ENR_KEY: &str = "attnets";
 /// The ENR field specifying the sync committee subnet bitfield.
 pub const SYNC_COMMITTEE_BITFIELD_ENR_KEY: &str = "syncnets";
 
+/// The addresses and ports advertised in an ENR.
+///
+/// When used to update the local ENR, fields which are `None` are left unchanged.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EnrAddresses {
+    pub ip4: Option<Ipv4Addr>,
+    pub ip6: Option<Ipv6Addr>,
+    pub udp4: Option<u16>,
+    pub udp6: Option<u16>,
+    pub tcp4: Option<u16>,
+    pub tcp6: Option<u16>,
+    pub quic4: Option<u16>,
+    pub quic6: Option<u16>,
+}
+
+impl EnrAddresses {
+    pub fn from_enr(enr: &Enr) -> Self {
+        Self {
+            ip4: enr.ip4(),
+            ip6: enr.ip6(),
+            udp4: enr.udp4(),
+            udp6: enr.udp6(),
+            tcp4: enr.tcp4(),
+            tcp6: enr.tcp6(),
+            quic4: enr.quic4(),
+            quic6: enr.quic6(),
+        }
+    }
+
+    /// Returns `true` if no address or port is given.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Returns the UDP sockets to advertise when applying `self` as an update to an ENR which
+    /// currently advertises `current`.
+    ///
+    /// An IP address and UDP port are always advertised together, so one of them can only be
+    /// updated if the ENR already has the other.
+    pub fn udp_socket_updates(&self, current: &Self) -> Result<Vec<SocketAddr>, String> {
+        let mut sockets = vec![];
+        if self.ip4.is_some() || self.udp4.is_some() {
+            let ip = self
+                .ip4
+                .or(current.ip4)
+                .ok_or("the ENR has no IPv4 address to use with the UDP port")?;
+            let port = self
+                .udp4
+                .or(current.udp4)
+                .ok_or("the ENR has no IPv4 UDP port to use with the address")?;
+            sockets.push(SocketAddr::new(ip.into(), port));
+        }
+        if self.ip6.is_some() || self.udp6.is_some() {
+            let ip = self
+                .ip6
+                .or(current.ip6)
+                .ok_or("the ENR has no IPv6 address to use with the UDP port")?;
+            let port = self
+                .udp6
+                .or(current.udp6)
+                .ok_or("the ENR has no IPv6 UDP port to use with the address")?;
+            sockets.push(SocketAddr::new(ip.into(), port));
+        }
+        Ok(sockets)
+    }
+}
+
 /// Extension trait for ENR's within Eth2.
 pub trait Eth2Enr {
     /// The attestation subnet bitfield associated with the ENR.
@@ -11,7 +11,7 @@ use crate::service::TARGET_SUBNET_PEERS;
 use crate::{error, Enr, NetworkConfig, NetworkGlobals, Subnet, SubnetDiscovery};
 use crate::{metrics, ClearDialError};
 use discv5::{enr::NodeId, Discv5};
-pub use enr::{build_enr, load_enr_from_disk, use_or_load_enr, CombinedKey, Eth2Enr};
+pub use enr::{build_enr, load_enr_from_disk, use_or_load_enr, CombinedKey, EnrAddresses, Eth2Enr};
 pub use enr_ext::{peer_id_to_node_id, CombinedKeyExt, EnrExt};
 pub use libp2p::identity::{Keypair, PublicKey};
 
@@ -479,6 +479,32 @@ impl<E: EthSpec> Discovery<E> {
         Ok(())
     }
 
+    /// Updates the addresses and ports of the local ENR which are given in `update`.
+    ///
+    /// Each change increments the ENR's sequence number, so peers fetch the new ENR the next time
+    /// they contact us.
+    pub fn update_enr_addresses(&mut self, update: &EnrAddresses) -> Result<(), String> {
+        let current = EnrAddresses::from_enr(&self.local_enr());
+        for socket_addr in update.udp_socket_updates(&current)? {
+            self.update_enr_udp_socket(socket_addr)?;
+        }
+        if let Some(port) = update.tcp4 {
+            self.update_enr_tcp_port(port, false)?;
+        }
+        if let Some(port) = update.tcp6 {
+            self.update_enr_tcp_port(port, true)?;
+        }
+        if let Some(port) = update.quic4 {
+            self.update_enr_quic_port(port, false)?;
+        }
+        if let Some(port) = update.quic6 {
+            self.update_enr_quic_port(port, true)?;
+        }
+
+        info!(self.log, "Updated the local ENR addresses"; "enr" => %self.local_enr());
+        Ok(())
+    }
+
     /// Adds/Removes a subnet from the ENR attnets/syncnets Bitfield
     pub fn update_enr_bitfield(&mut self, subnet: Subnet, value: bool) -> Result<(), String> {
         let local_enr = self.discv5.local_enr();
@@ -222,6 +222,25 @@ impl<E: EthSpec> PeerManager<E> {
         self.handle_score_action(peer_id, action, reason);
     }
 
+    /// Gracefully disconnects a peer without banning it, if it is connected.
+    pub fn disconnect_connected_peer(&mut self, peer_id: &PeerId) {
+        if self.network_globals.peers.read().is_connected(peer_id) {
+            self.disconnect_peer(*peer_id, GoodbyeReason::Unknown);
+        }
+    }
+
+    /// Lifts any ban on a peer and resets its score.
+    pub fn unban_peer(&mut self, peer_id: &PeerId) {
+        let action = self.network_globals.peers.write().unban_peer(peer_id);
+        if self.temporary_banned_peers.raw_remove(peer_id)
+            && !matches!(action, ScoreUpdateResult::Unbanned(_))
+        {
+            self.events
+                .push(PeerManagerEvent::UnBanned(*peer_id, Vec::new()));
+        }
+        self.handle_score_action(peer_id, action, None);
+    }
+
     /// Upon adjusting a Peer's score, there are times the peer manager must pass messages up to
     /// libp2p. This function handles the conditional logic associated with each score update
     /// result.
@@ -576,6 +576,33 @@ impl<E: EthSpec> PeerDB<E> {
         }
     }
 
+    /// Resets the score of a peer, unbanning it if its score had it banned.
+    #[must_use = "Unbanned peers need to be handled in libp2p"]
+    pub(super) fn unban_peer(&mut self, peer_id: &PeerId) -> ScoreUpdateResult {
+        let Some(info) = self.peers.get_mut(peer_id) else {
+            return ScoreUpdateResult::NoAction;
+        };
+        let previous_state = info.score_state();
+        info.clear_score();
+
+        match Self::handle_score_transition(previous_state, peer_id, info, &self.log) {
+            ScoreTransitionResult::Unbanned => {
+                self.update_connection_state(peer_id, NewConnectionState::Unbanned);
+                let seen_ip_addresses = self
+                    .peers
+                    .get(peer_id)
+                    .map(|info| {
+                        info.seen_ip_addresses()
+                            .filter(|ip| !self.is_ip_banned(ip))
+                            .collect::<Vec<_>>()
+                    })
+                    .unwrap_or_default();
+                ScoreUpdateResult::Unbanned(seen_ip_addresses)
+            }
+            _ => ScoreUpdateResult::NoAction,
+        }
+    }
+
     /// Update min ttl of a peer.
     // VISIBILITY: Only the peer manager can update the min_ttl
     pub(super) fn update_min_ttl(&mut self, peer_id: &PeerId, min_ttl: Instant) {
@@ -1912,6 +1939,28 @@ mod tests {
         assert!(pdb.ban_status(&p5).is_none());
     }
 
+    #[test]
+    fn test_unban_peer() {
+        let mut pdb = get_db();
+        let ip = Ipv4Addr::new(1, 2, 3, 4).into();
+        let peer = connect_peer_with_ips(&mut pdb, vec![ip]);
+
+        // Unbanning a peer which isn't banned has no effect.
+        assert!(matches!(pdb.unban_peer(&peer), ScoreUpdateResult::NoAction));
+
+        let _ = pdb.report_peer(&peer, PeerAction::Fatal, ReportSource::PeerManager, "");
+        pdb.inject_disconnect(&peer);
+        assert!(pdb.ban_status(&peer).is_some());
+
+        match pdb.unban_peer(&peer) {
+            ScoreUpdateResult::Unbanned(ips) => assert_eq!(ips, vec![ip]),
+            _ => panic!("peer should be unbanned"),
+        }
+        assert!(pdb.ban_status(&peer).is_none());
+        assert_eq!(pdb.banned_peers_count.banned_peers(), 0);
+        assert_eq!(pdb.score(&peer), 0.0);
+    }
+
     #[test]
     fn test_banned_ip_consistent_after_changing_ips() {
         let mut pdb = get_db();
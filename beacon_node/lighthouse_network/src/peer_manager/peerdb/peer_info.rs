@@ -402,6 +402,13 @@ impl<E: EthSpec> PeerInfo<E> {
         }
     }
 
+    /// Resets a non-trusted peer's score to its default.
+    pub(super) fn clear_score(&mut self) {
+        if !self.is_trusted {
+            self.score.reset()
+        }
+    }
+
     /// Updates the gossipsub score with a new score. Optionally ignore the gossipsub score.
     pub(super) fn update_gossipsub_score(&mut self, new_score: f64, ignore: bool) {
         self.score.update_gossipsub_score(new_score, ignore);
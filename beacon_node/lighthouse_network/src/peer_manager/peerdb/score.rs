@@ -74,6 +74,7 @@ pub enum ReportSource {
     Processor,
     SyncService,
     PeerManager,
+    HttpApi,
 }
 
 impl From<ReportSource> for &'static str {
@@ -84,6 +85,7 @@ impl From<ReportSource> for &'static str {
             ReportSource::Processor => "processor",
             ReportSource::SyncService => "sync",
             ReportSource::PeerManager => "peer_manager",
+            ReportSource::HttpApi => "http_api",
         }
     }
 }
@@ -200,6 +202,11 @@ impl RealScore {
         }
     }
 
+    /// Resets the score to its default, lifting any ban.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
     /// Add an f64 to the score abiding by the limits.
     #[cfg(test)]
     pub fn test_add(&mut self, score: f64) {
@@ -291,6 +298,7 @@ macro_rules! apply {
 apply!(apply_peer_action, peer_action: PeerAction);
 apply!(update);
 apply!(update_gossipsub_score, new_score: f64, ignore: bool);
+apply!(reset);
 #[cfg(test)]
 apply!(test_add, score: f64);
 #[cfg(test)]
@@ -16,10 +16,8 @@ use std::marker::PhantomData;
 use std::sync::Arc;
 use tokio_util::codec::{Decoder, Encoder};
 use types::{
-    BlobSidecar, ChainSpec, EthSpec, ForkContext, ForkName, Hash256, LightClientBootstrap,
-    LightClientFinalityUpdate, LightClientOptimisticUpdate, RuntimeVariableList, SignedBeaconBlock,
-    SignedBeaconBlockAltair, SignedBeaconBlockBase, SignedBeaconBlockBellatrix,
-    SignedBeaconBlockCapella, SignedBeaconBlockDeneb, SignedBeaconBlockElectra,
+    BlobSidecar, ChainSpec, EthSpec, ForkContext, ForkName, ForkVersionDecode, Hash256,
+    RuntimeVariableList, SignedBeaconBlock, SignedBeaconBlockBase,
 };
 use unsigned_varint::codec::Uvi;
 
@@ -139,7 +137,9 @@ impl<E: EthSpec> Decoder for SSZSnappyInboundCodec<E> {
 
         // Should not attempt to decode rpc chunks with `length > max_packet_size` or not within bounds of
         // packet size for ssz container corresponding to `self.protocol`.
-        let ssz_limits = self.protocol.rpc_request_limits(&self.fork_context.spec);
+        let ssz_limits = self
+            .protocol
+            .rpc_request_limits(&self.fork_context.spec, self.fork_context.current_fork());
         if ssz_limits.is_out_of_bounds(length, self.max_packet_size) {
             return Err(RPCError::InvalidData(format!(
                 "RPC request length for protocol {:?} is out of bounds, length {}",
@@ -164,6 +164,7 @@ impl<E: EthSpec> Decoder for SSZSnappyInboundCodec<E> {
                     self.protocol.versioned_protocol,
                     &decoded_buffer,
                     &self.fork_context.spec,
+                    self.fork_context.current_fork(),
                 )
             }
             Err(e) => handle_error(e, reader.get_ref().get_ref().position(), max_compressed_len),
@@ -467,10 +468,14 @@ fn handle_length(
 /// Decodes an `InboundRequest` from the byte stream.
 /// `decoded_buffer` should be an ssz-encoded bytestream with
 // length = length-prefix received in the beginning of the stream.
+//
+// The maximum lengths of by-root requests are read from `spec` for the `current_fork`, so that
+// networks with non-default limits are supported without recompiling.
 fn handle_rpc_request<E: EthSpec>(
     versioned_protocol: SupportedProtocol,
     decoded_buffer: &[u8],
     spec: &ChainSpec,
+    current_fork: ForkName,
 ) -> Result<Option<InboundRequest<E>>, RPCError> {
     match versioned_protocol {
         SupportedProtocol::StatusV1 => Ok(Some(InboundRequest::Status(
@@ -489,7 +494,7 @@ fn handle_rpc_request<E: EthSpec>(
             BlocksByRootRequest::V2(BlocksByRootRequestV2 {
                 block_roots: RuntimeVariableList::from_ssz_bytes(
                     decoded_buffer,
-                    spec.max_request_blocks as usize,
+                    spec.max_request_blocks(current_fork),
                 )?,
             }),
         ))),
@@ -497,7 +502,7 @@ fn handle_rpc_request<E: EthSpec>(
             BlocksByRootRequest::V1(BlocksByRootRequestV1 {
                 block_roots: RuntimeVariableList::from_ssz_bytes(
                     decoded_buffer,
-                    spec.max_request_blocks as usize,
+                    spec.max_request_blocks(current_fork),
                 )?,
             }),
         ))),
@@ -610,113 +615,51 @@ fn handle_rpc_response<E: EthSpec>(
         SupportedProtocol::MetaDataV1 => Ok(Some(RPCResponse::MetaData(MetaData::V1(
             MetaDataV1::from_ssz_bytes(decoded_buffer)?,
         )))),
-        SupportedProtocol::LightClientBootstrapV1 => match fork_name {
-            Some(fork_name) => Ok(Some(RPCResponse::LightClientBootstrap(Arc::new(
-                LightClientBootstrap::from_ssz_bytes(decoded_buffer, fork_name)?,
-            )))),
-            None => Err(RPCError::ErrorResponse(
-                RPCResponseErrorCode::InvalidRequest,
-                format!(
-                    "No context bytes provided for {:?} response",
-                    versioned_protocol
-                ),
-            )),
-        },
-        SupportedProtocol::LightClientOptimisticUpdateV1 => match fork_name {
-            Some(fork_name) => Ok(Some(RPCResponse::LightClientOptimisticUpdate(Arc::new(
-                LightClientOptimisticUpdate::from_ssz_bytes(decoded_buffer, fork_name)?,
-            )))),
-            None => Err(RPCError::ErrorResponse(
-                RPCResponseErrorCode::InvalidRequest,
-                format!(
-                    "No context bytes provided for {:?} response",
-                    versioned_protocol
-                ),
-            )),
-        },
-        SupportedProtocol::LightClientFinalityUpdateV1 => match fork_name {
-            Some(fork_name) => Ok(Some(RPCResponse::LightClientFinalityUpdate(Arc::new(
-                LightClientFinalityUpdate::from_ssz_bytes(decoded_buffer, fork_name)?,
-            )))),
-            None => Err(RPCError::ErrorResponse(
-                RPCResponseErrorCode::InvalidRequest,
-                format!(
-                    "No context bytes provided for {:?} response",
-                    versioned_protocol
-                ),
-            )),
-        },
+        SupportedProtocol::LightClientBootstrapV1 => Ok(Some(RPCResponse::LightClientBootstrap(
+            decode_by_fork(versioned_protocol, decoded_buffer, fork_name)?,
+        ))),
+        SupportedProtocol::LightClientOptimisticUpdateV1 => {
+            Ok(Some(RPCResponse::LightClientOptimisticUpdate(
+                decode_by_fork(versioned_protocol, decoded_buffer, fork_name)?,
+            )))
+        }
+        SupportedProtocol::LightClientFinalityUpdateV1 => {
+            Ok(Some(RPCResponse::LightClientFinalityUpdate(
+                decode_by_fork(versioned_protocol, decoded_buffer, fork_name)?,
+            )))
+        }
         // MetaData V2 responses have no context bytes, so behave similarly to V1 responses
         SupportedProtocol::MetaDataV2 => Ok(Some(RPCResponse::MetaData(MetaData::V2(
             MetaDataV2::from_ssz_bytes(decoded_buffer)?,
         )))),
-        SupportedProtocol::BlocksByRangeV2 => match fork_name {
-            Some(ForkName::Altair) => Ok(Some(RPCResponse::BlocksByRange(Arc::new(
-                SignedBeaconBlock::Altair(SignedBeaconBlockAltair::from_ssz_bytes(decoded_buffer)?),
-            )))),
+        SupportedProtocol::BlocksByRangeV2 => Ok(Some(RPCResponse::BlocksByRange(decode_by_fork(
+            versioned_protocol,
+            decoded_buffer,
+            fork_name,
+        )?))),
+        SupportedProtocol::BlocksByRootV2 => Ok(Some(RPCResponse::BlocksByRoot(decode_by_fork(
+            versioned_protocol,
+            decoded_buffer,
+            fork_name,
+        )?))),
+    }
+}
 
-            Some(ForkName::Base) => Ok(Some(RPCResponse::BlocksByRange(Arc::new(
-                SignedBeaconBlock::Base(SignedBeaconBlockBase::from_ssz_bytes(decoded_buffer)?),
-            )))),
-            Some(ForkName::Bellatrix) => Ok(Some(RPCResponse::BlocksByRange(Arc::new(
-                SignedBeaconBlock::Bellatrix(SignedBeaconBlockBellatrix::from_ssz_bytes(
-                    decoded_buffer,
-                )?),
-            )))),
-            Some(ForkName::Capella) => Ok(Some(RPCResponse::BlocksByRange(Arc::new(
-                SignedBeaconBlock::Capella(SignedBeaconBlockCapella::from_ssz_bytes(
-                    decoded_buffer,
-                )?),
-            )))),
-            Some(ForkName::Deneb) => Ok(Some(RPCResponse::BlocksByRange(Arc::new(
-                SignedBeaconBlock::Deneb(SignedBeaconBlockDeneb::from_ssz_bytes(decoded_buffer)?),
-            )))),
-            Some(ForkName::Electra) => Ok(Some(RPCResponse::BlocksByRange(Arc::new(
-                SignedBeaconBlock::Electra(SignedBeaconBlockElectra::from_ssz_bytes(
-                    decoded_buffer,
-                )?),
-            )))),
-            None => Err(RPCError::ErrorResponse(
-                RPCResponseErrorCode::InvalidRequest,
-                format!(
-                    "No context bytes provided for {:?} response",
-                    versioned_protocol
-                ),
-            )),
-        },
-        SupportedProtocol::BlocksByRootV2 => match fork_name {
-            Some(ForkName::Altair) => Ok(Some(RPCResponse::BlocksByRoot(Arc::new(
-                SignedBeaconBlock::Altair(SignedBeaconBlockAltair::from_ssz_bytes(decoded_buffer)?),
-            )))),
-            Some(ForkName::Base) => Ok(Some(RPCResponse::BlocksByRoot(Arc::new(
-                SignedBeaconBlock::Base(SignedBeaconBlockBase::from_ssz_bytes(decoded_buffer)?),
-            )))),
-            Some(ForkName::Bellatrix) => Ok(Some(RPCResponse::BlocksByRoot(Arc::new(
-                SignedBeaconBlock::Bellatrix(SignedBeaconBlockBellatrix::from_ssz_bytes(
-                    decoded_buffer,
-                )?),
-            )))),
-            Some(ForkName::Capella) => Ok(Some(RPCResponse::BlocksByRoot(Arc::new(
-                SignedBeaconBlock::Capella(SignedBeaconBlockCapella::from_ssz_bytes(
-                    decoded_buffer,
-                )?),
-            )))),
-            Some(ForkName::Deneb) => Ok(Some(RPCResponse::BlocksByRoot(Arc::new(
-                SignedBeaconBlock::Deneb(SignedBeaconBlockDeneb::from_ssz_bytes(decoded_buffer)?),
-            )))),
-            Some(ForkName::Electra) => Ok(Some(RPCResponse::BlocksByRoot(Arc::new(
-                SignedBeaconBlock::Electra(SignedBeaconBlockElectra::from_ssz_bytes(
-                    decoded_buffer,
-                )?),
-            )))),
-            None => Err(RPCError::ErrorResponse(
-                RPCResponseErrorCode::InvalidRequest,
-                format!(
-                    "No context bytes provided for {:?} response",
-                    versioned_protocol
-                ),
-            )),
-        },
+/// Decodes a response whose variant is determined by the context bytes.
+fn decode_by_fork<T: ForkVersionDecode>(
+    versioned_protocol: SupportedProtocol,
+    decoded_buffer: &[u8],
+    fork_name: Option<ForkName>,
+) -> Result<T, RPCError> {
+    match fork_name {
+        Some(fork_name) => Ok(T::from_ssz_bytes_by_fork(decoded_buffer, fork_name)?),
+        None => Err(RPCError::ErrorResponse(
+            RPCResponseErrorCode::InvalidRequest,
+            format!(
+                "No context bytes provided for {:?} response",
+                versioned_protocol
+            ),
+        )),
     }
 }
 
@@ -855,12 +798,12 @@ mod tests {
         }
     }
 
-    fn bbroot_request_v1(spec: &ChainSpec) -> BlocksByRootRequest {
-        BlocksByRootRequest::new_v1(vec![Hash256::zero()], spec)
+    fn bbroot_request_v1(fork_name: ForkName, spec: &ChainSpec) -> BlocksByRootRequest {
+        BlocksByRootRequest::new_v1(vec![Hash256::zero()], fork_name, spec)
     }
 
-    fn bbroot_request_v2(spec: &ChainSpec) -> BlocksByRootRequest {
-        BlocksByRootRequest::new(vec![Hash256::zero()], spec)
+    fn bbroot_request_v2(fork_name: ForkName, spec: &ChainSpec) -> BlocksByRootRequest {
+        BlocksByRootRequest::new(vec![Hash256::zero()], fork_name, spec)
     }
 
     fn blbroot_request(spec: &ChainSpec) -> BlobsByRootRequest {
@@ -1480,27 +1423,63 @@ mod tests {
     fn test_encode_then_decode_request() {
         let chain_spec = Spec::default_spec();
 
-        let requests: &[OutboundRequest<Spec>] = &[
-            OutboundRequest::Ping(ping_message()),
-            OutboundRequest::Status(status_message()),
-            OutboundRequest::Goodbye(GoodbyeReason::Fault),
-            OutboundRequest::BlocksByRange(bbrange_request_v1()),
-            OutboundRequest::BlocksByRange(bbrange_request_v2()),
-            OutboundRequest::BlocksByRoot(bbroot_request_v1(&chain_spec)),
-            OutboundRequest::BlocksByRoot(bbroot_request_v2(&chain_spec)),
-            OutboundRequest::MetaData(MetadataRequest::new_v1()),
-            OutboundRequest::BlobsByRange(blbrange_request()),
-            OutboundRequest::BlobsByRoot(blbroot_request(&chain_spec)),
-            OutboundRequest::MetaData(MetadataRequest::new_v2()),
-        ];
-
-        for req in requests.iter() {
-            for fork_name in ForkName::list_all() {
+        for fork_name in ForkName::list_all() {
+            let requests: &[OutboundRequest<Spec>] = &[
+                OutboundRequest::Ping(ping_message()),
+                OutboundRequest::Status(status_message()),
+                OutboundRequest::Goodbye(GoodbyeReason::Fault),
+                OutboundRequest::BlocksByRange(bbrange_request_v1()),
+                OutboundRequest::BlocksByRange(bbrange_request_v2()),
+                OutboundRequest::BlocksByRoot(bbroot_request_v1(fork_name, &chain_spec)),
+                OutboundRequest::BlocksByRoot(bbroot_request_v2(fork_name, &chain_spec)),
+                OutboundRequest::MetaData(MetadataRequest::new_v1()),
+                OutboundRequest::BlobsByRange(blbrange_request()),
+                OutboundRequest::BlobsByRoot(blbroot_request(&chain_spec)),
+                OutboundRequest::MetaData(MetadataRequest::new_v2()),
+            ];
+
+            for req in requests.iter() {
                 encode_then_decode_request(req.clone(), fork_name, &chain_spec);
             }
         }
     }
 
+    /// Test that the maximum number of roots in a `BlocksByRoot` request is read from the spec for
+    /// the current fork.
+    #[test]
+    fn test_blocks_by_root_request_limit_per_fork() {
+        let chain_spec = Spec::default_spec();
+        let num_roots = chain_spec.max_request_blocks_deneb as usize + 1;
+        assert!(num_roots <= chain_spec.max_request_blocks as usize);
+
+        for (fork_name, valid) in [(ForkName::Capella, true), (ForkName::Deneb, false)] {
+            let fork_context = Arc::new(fork_context(fork_name));
+            let max_packet_size = max_rpc_size(&fork_context, chain_spec.max_chunk_size as usize);
+            let req = OutboundRequest::<Spec>::BlocksByRoot(BlocksByRootRequest::new(
+                vec![Hash256::zero(); num_roots],
+                ForkName::Capella,
+                &chain_spec,
+            ));
+            let protocol = ProtocolId::new(req.versioned_protocol(), Encoding::SSZSnappy);
+
+            let mut buf = BytesMut::new();
+            let mut outbound_codec = SSZSnappyOutboundCodec::<Spec>::new(
+                protocol.clone(),
+                max_packet_size,
+                fork_context.clone(),
+            );
+            outbound_codec.encode(req, &mut buf).unwrap();
+
+            let mut inbound_codec =
+                SSZSnappyInboundCodec::<Spec>::new(protocol, max_packet_size, fork_context);
+            assert_eq!(
+                inbound_codec.decode(&mut buf).is_ok(),
+                valid,
+                "unexpected result at {fork_name}"
+            );
+        }
+    }
+
     /// Test a malicious snappy encoding for a V1 `Status` message where the attacker
     /// sends a valid message filled with a stream of useless padding before the actual message.
     #[test]
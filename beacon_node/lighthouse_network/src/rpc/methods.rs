@@ -14,7 +14,7 @@ use strum::IntoStaticStr;
 use superstruct::superstruct;
 use types::blob_sidecar::BlobIdentifier;
 use types::{
-    blob_sidecar::BlobSidecar, ChainSpec, Epoch, EthSpec, Hash256, LightClientBootstrap,
+    blob_sidecar::BlobSidecar, ChainSpec, Epoch, EthSpec, ForkName, Hash256, LightClientBootstrap,
     LightClientFinalityUpdate, LightClientOptimisticUpdate, RuntimeVariableList, SignedBeaconBlock,
     Slot,
 };
@@ -342,15 +342,16 @@ pub struct BlocksByRootRequest {
 }
 
 impl BlocksByRootRequest {
-    pub fn new(block_roots: Vec<Hash256>, spec: &ChainSpec) -> Self {
+    /// Create a request for `block_roots`, bounded by the maximum request size at `fork_name`.
+    pub fn new(block_roots: Vec<Hash256>, fork_name: ForkName, spec: &ChainSpec) -> Self {
         let block_roots =
-            RuntimeVariableList::from_vec(block_roots, spec.max_request_blocks as usize);
+            RuntimeVariableList::from_vec(block_roots, spec.max_request_blocks(fork_name));
         Self::V2(BlocksByRootRequestV2 { block_roots })
     }
 
-    pub fn new_v1(block_roots: Vec<Hash256>, spec: &ChainSpec) -> Self {
+    pub fn new_v1(block_roots: Vec<Hash256>, fork_name: ForkName, spec: &ChainSpec) -> Self {
         let block_roots =
-            RuntimeVariableList::from_vec(block_roots, spec.max_request_blocks as usize);
+            RuntimeVariableList::from_vec(block_roots, spec.max_request_blocks(fork_name));
         Self::V1(BlocksByRootRequestV1 { block_roots })
     }
 }
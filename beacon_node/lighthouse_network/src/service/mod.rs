@@ -2,7 +2,7 @@ use self::behaviour::Behaviour;
 use self::gossip_cache::GossipCache;
 use crate::config::{gossipsub_config, GossipsubConfigParams, NetworkLoad};
 use crate::discovery::{
-    subnet_predicate, DiscoveredPeers, Discovery, FIND_NODE_QUERY_CLOSEST_PEERS,
+    subnet_predicate, DiscoveredPeers, Discovery, EnrAddresses, FIND_NODE_QUERY_CLOSEST_PEERS,
 };
 use crate::peer_manager::{
     config::Config as PeerManagerCfg, peerdb::score::PeerAction, peerdb::score::ReportSource,
@@ -978,6 +978,16 @@ impl<E: EthSpec> Network<E> {
             .goodbye_peer(peer_id, reason, source);
     }
 
+    /// Disconnects from a connected peer without banning it.
+    pub fn disconnect_peer(&mut self, peer_id: &PeerId) {
+        self.peer_manager_mut().disconnect_connected_peer(peer_id);
+    }
+
+    /// Lifts any ban on a peer.
+    pub fn unban_peer(&mut self, peer_id: &PeerId) {
+        self.peer_manager_mut().unban_peer(peer_id);
+    }
+
     /// Hard (ungraceful) disconnect for testing purposes only
     /// Use goodbye_peer for disconnections, do not use this function.
     pub fn __hard_disconnect_testing_only(&mut self, peer_id: PeerId) {
@@ -994,6 +1004,13 @@ impl<E: EthSpec> Network<E> {
         self.discovery_mut().add_enr(enr);
     }
 
+    /// Updates the addresses and ports advertised in the local ENR.
+    pub fn update_enr_addresses(&mut self, update: &EnrAddresses) {
+        if let Err(e) = self.discovery_mut().update_enr_addresses(update) {
+            warn!(self.log, "Could not update ENR addresses"; "error" => e);
+        }
+    }
+
     /// Updates a subnet value to the ENR attnets/syncnets bitfield.
     ///
     /// The `value` is `true` if a subnet is being added and false otherwise.
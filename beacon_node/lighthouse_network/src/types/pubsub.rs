@@ -9,12 +9,13 @@ use std::sync::Arc;
 use types::{
     Attestation, AttestationBase, AttestationElectra, AttesterSlashing, AttesterSlashingBase,
     AttesterSlashingElectra, BlobSidecar, DataColumnSidecar, DataColumnSubnetId, EthSpec,
-    ForkContext, ForkName, LightClientFinalityUpdate, LightClientOptimisticUpdate,
-    ProposerSlashing, SignedAggregateAndProof, SignedAggregateAndProofBase,
-    SignedAggregateAndProofElectra, SignedBeaconBlock, SignedBeaconBlockAltair,
-    SignedBeaconBlockBase, SignedBeaconBlockBellatrix, SignedBeaconBlockCapella,
-    SignedBeaconBlockDeneb, SignedBeaconBlockElectra, SignedBlsToExecutionChange,
-    SignedContributionAndProof, SignedVoluntaryExit, SubnetId, SyncCommitteeMessage, SyncSubnetId,
+    ForkContext, ForkName, ForkVersionDecode, LightClientFinalityUpdate,
+    LightClientOptimisticUpdate, ProposerSlashing, SignedAggregateAndProof,
+    SignedAggregateAndProofBase, SignedAggregateAndProofElectra, SignedBeaconBlock,
+    SignedBeaconBlockAltair, SignedBeaconBlockBase, SignedBeaconBlockBellatrix,
+    SignedBeaconBlockCapella, SignedBeaconBlockDeneb, SignedBeaconBlockElectra,
+    SignedBeaconBlockView, SignedBlsToExecutionChange, SignedContributionAndProof,
+    SignedVoluntaryExit, SubnetId, SyncCommitteeMessage, SyncSubnetId,
 };
 
 #[derive(Debug, Clone, PartialEq)]
@@ -216,6 +217,23 @@ impl<E: EthSpec> PubsubMessage<E> {
                         ))))
                     }
                     GossipKind::BeaconBlock => {
+                        // Check that the slot of the block matches the fork of the topic before
+                        // paying to decode the entire block.
+                        let block_slot = SignedBeaconBlockView::from_ssz_bytes(data)
+                            .map_err(|e| format!("{:?}", e))?
+                            .slot();
+                        let slot_fork = fork_context.spec.fork_name_at_slot::<E>(block_slot);
+                        if let Some(&topic_fork) =
+                            fork_context.from_context_bytes(gossip_topic.fork_digest)
+                        {
+                            if topic_fork != slot_fork {
+                                return Err(format!(
+                                    "Block at slot {} is for the {} fork, not {}",
+                                    block_slot, slot_fork, topic_fork
+                                ));
+                            }
+                        }
+
                         let beacon_block =
                             match fork_context.from_context_bytes(gossip_topic.fork_digest) {
                                 Some(ForkName::Base) => SignedBeaconBlock::<E>::Base(
@@ -366,7 +384,7 @@ impl<E: EthSpec> PubsubMessage<E> {
                     GossipKind::LightClientFinalityUpdate => {
                         let light_client_finality_update = match fork_context.from_context_bytes(gossip_topic.fork_digest) {
                             Some(&fork_name) => {
-                                    LightClientFinalityUpdate::from_ssz_bytes(data, fork_name)
+                                    LightClientFinalityUpdate::from_ssz_bytes_by_fork(data, fork_name)
                                     .map_err(|e| format!("{:?}", e))?
                             },
                             None => return Err(format!(
@@ -381,7 +399,7 @@ impl<E: EthSpec> PubsubMessage<E> {
                     GossipKind::LightClientOptimisticUpdate => {
                         let light_client_optimistic_update = match fork_context.from_context_bytes(gossip_topic.fork_digest) {
                             Some(&fork_name) => {
-                                LightClientOptimisticUpdate::from_ssz_bytes(data, fork_name)
+                                LightClientOptimisticUpdate::from_ssz_bytes_by_fork(data, fork_name)
                                 .map_err(|e| format!("{:?}", e))?
                             },
                             None => return Err(format!(
@@ -763,6 +763,7 @@ fn test_tcp_blocks_by_root_chunked_rpc() {
                 Hash256::from_low_u64_be(0),
                 Hash256::from_low_u64_be(0),
             ],
+            ForkName::Bellatrix,
             &spec,
         ));
 
@@ -905,6 +906,7 @@ fn test_tcp_blocks_by_root_chunked_rpc_terminates_correctly() {
                 Hash256::from_low_u64_be(0),
                 Hash256::from_low_u64_be(0),
             ],
+            ForkName::Base,
             &spec,
         ));
 
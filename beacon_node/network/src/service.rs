@@ -19,7 +19,8 @@ use lighthouse_network::types::GossipKind;
 use lighthouse_network::{prometheus_client::registry::Registry, MessageAcceptance};
 use lighthouse_network::{
     rpc::{GoodbyeReason, RPCResponseErrorCode},
-    Context, PeerAction, PeerRequestId, PubsubMessage, ReportSource, Request, Response, Subnet,
+    Context, EnrAddresses, PeerAction, PeerRequestId, PubsubMessage, ReportSource, Request,
+    Response, Subnet,
 };
 use lighthouse_network::{
     service::api_types::AppRequestId,
@@ -101,6 +102,12 @@ pub enum NetworkMessage<E: EthSpec> {
         reason: GoodbyeReason,
         source: ReportSource,
     },
+    /// Disconnect a peer without banning it.
+    DisconnectPeer { peer_id: PeerId },
+    /// Lift any ban on a peer.
+    UnbanPeer { peer_id: PeerId },
+    /// Update the addresses and ports advertised in the local ENR.
+    UpdateEnrAddresses { update: EnrAddresses },
 }
 
 /// Messages triggered by validators that may trigger a subscription to a subnet.
@@ -673,6 +680,11 @@ impl<T: BeaconChainTypes> NetworkService<T> {
                 reason,
                 source,
             } => self.libp2p.goodbye_peer(&peer_id, reason, source),
+            NetworkMessage::DisconnectPeer { peer_id } => self.libp2p.disconnect_peer(&peer_id),
+            NetworkMessage::UnbanPeer { peer_id } => self.libp2p.unban_peer(&peer_id),
+            NetworkMessage::UpdateEnrAddresses { update } => {
+                self.libp2p.update_enr_addresses(&update)
+            }
             NetworkMessage::SubscribeCoreTopics => {
                 if self.subscribed_core_topics() {
                     return;
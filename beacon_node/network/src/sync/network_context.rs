@@ -64,6 +64,8 @@ pub enum RpcResponseError {
 pub enum RpcRequestSendError {
     /// Network channel send failed
     NetworkSendError,
+    /// The current epoch could not be read from the slot clock
+    SlotClockError,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -415,6 +417,11 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
         );
 
         let request = BlocksByRootSingleRequest(block_root);
+        let current_epoch = self.chain.epoch().map_err(|e| {
+            error!(self.log, "Failed to read the current epoch"; "error" => ?e);
+            RpcRequestSendError::SlotClockError
+        })?;
+        let fork_name = self.chain.spec.fork_name_at_epoch(current_epoch);
 
         // Lookup sync event safety: If network_send.send() returns Ok(_) we are guaranteed that
         // eventually at least one this 3 events will be received:
@@ -425,7 +432,7 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
         self.network_send
             .send(NetworkMessage::SendRequest {
                 peer_id,
-                request: Request::BlocksByRoot(request.into_request(&self.chain.spec)),
+                request: Request::BlocksByRoot(request.into_request(fork_name, &self.chain.spec)),
                 request_id: AppRequestId::Sync(SyncRequestId::SingleBlock { id }),
             })
             .map_err(|_| RpcRequestSendError::NetworkSendError)?;
@@ -6,7 +6,8 @@ use lighthouse_network::{
 use std::sync::Arc;
 use strum::IntoStaticStr;
 use types::{
-    blob_sidecar::BlobIdentifier, BlobSidecar, ChainSpec, EthSpec, Hash256, SignedBeaconBlock,
+    blob_sidecar::BlobIdentifier, BlobSidecar, ChainSpec, EthSpec, ForkName, Hash256,
+    SignedBeaconBlock,
 };
 
 #[derive(Debug, PartialEq, Eq, IntoStaticStr)]
@@ -69,8 +70,8 @@ impl ActiveBlocksByRootRequest {
 pub struct BlocksByRootSingleRequest(pub Hash256);
 
 impl BlocksByRootSingleRequest {
-    pub fn into_request(self, spec: &ChainSpec) -> BlocksByRootRequest {
-        BlocksByRootRequest::new(vec![self.0], spec)
+    pub fn into_request(self, fork_name: ForkName, spec: &ChainSpec) -> BlocksByRootRequest {
+        BlocksByRootRequest::new(vec![self.0], fork_name, spec)
     }
 }
 
@@ -532,6 +532,17 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("http-tls-client-ca")
+                .long("http-tls-client-ca")
+                .value_name("CERTIFICATE-FILE")
+                .requires("http-enable-tls")
+                .help("The path of a PEM file containing the certificate authorities which sign \
+                    client certificates. If set, the HTTP API server requires every client to \
+                    present a certificate signed by one of these authorities.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("http-allow-sync-stalled")
                 .long("http-allow-sync-stalled")
@@ -553,6 +564,60 @@ pub fn cli_app() -> Command {
                        Increasing this value can prevent messages from being dropped.")
                 .display_order(0)
         )
+        .arg(
+            Arg::new("http-historic-state-replay-limit")
+                .long("http-historic-state-replay-limit")
+                .requires("enable_http")
+                .action(ArgAction::Set)
+                .default_value_if("enable_http", ArgPredicate::IsPresent, "0")
+                .value_name("SLOTS")
+                .help("The maximum number of slots of blocks to replay when a state is requested \
+                       by slot but lies between the historic states stored in the database. \
+                       Such states are otherwise unavailable. Defaults to 0, which disables \
+                       replay.")
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("http-api-tokens-file")
+                .long("http-api-tokens-file")
+                .requires("enable_http")
+                .action(ArgAction::Set)
+                .value_name("PATH")
+                .help("Path to a file of bearer tokens which HTTP API requests must present. Each \
+                       line gives a scope and a token separated by a space. The scope is one of \
+                       read-only, validator (which also allows validator duties) or admin (which \
+                       also allows the debug and lighthouse routes). Authentication is disabled \
+                       by default.")
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("http-rate-limit")
+                .long("http-rate-limit")
+                .requires("enable_http")
+                .action(ArgAction::Set)
+                .value_name("QUOTAS")
+                .help("Limits the rate of HTTP API requests from each client IP address. Quotas \
+                       are set per route in the form <route>:<tokens>/<time_in_seconds>, where \
+                       the route is a path prefix without the /eth/v1 version, such as \
+                       beacon/states. The route \"default\" applies to all other requests. To set \
+                       quotas for multiple routes, separate them by ';'. Requests which exceed \
+                       their quota receive a 429 response. Clients are identified by the address \
+                       of their connection, so behind a reverse proxy all clients share the \
+                       proxy's quota. Disabled by default.")
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("http-response-cache-size")
+                .long("http-response-cache-size")
+                .requires("enable_http")
+                .action(ArgAction::Set)
+                .default_value_if("enable_http", ArgPredicate::IsPresent, "64")
+                .value_name("MEGABYTES")
+                .help("The maximum total size of the HTTP API responses which can never change, \
+                       such as finalized blocks, to cache in memory. Set to 0 to disable the \
+                       cache.")
+                .display_order(0)
+        )
         .arg(
             Arg::new("http-duplicate-block-status")
                 .long("http-duplicate-block-status")
@@ -618,6 +683,18 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("payload-prefetch-window")
+                .long("payload-prefetch-window")
+                .value_name("COUNT")
+                .help("The maximum number of execution payloads to send to the execution engine \
+                       concurrently whilst syncing, ahead of importing their blocks. Execution \
+                       engines which can verify payloads in parallel may sync faster with a \
+                       higher value. Set to 0 to only send each payload as its block is imported.")
+                .action(ArgAction::Set)
+                .default_value("0")
+                .display_order(0)
+        )
         .arg(
             Arg::new("shuffling-cache-size")
             .long("shuffling-cache-size")
@@ -848,7 +925,9 @@ pub fn cli_app() -> Command {
                 .long("builder")
                 .alias("payload-builder")
                 .alias("payload-builders")
-                .help("The URL of a service compatible with the MEV-boost API.")
+                .help("The URL of a service compatible with the MEV-boost API. Multiple \
+                       comma-separated relays may be supplied, in which case headers are \
+                       requested from all of them and the most valuable valid bid is used.")
                 .requires("execution-endpoint")
                 .action(ArgAction::Set)
                 .display_order(0)
@@ -1163,6 +1242,18 @@ pub fn cli_app() -> Command {
                 .requires("checkpoint-block")
                 .display_order(0)
         )
+        .arg(
+            Arg::new("checkpoint-deposit-snapshot")
+                .long("checkpoint-deposit-snapshot")
+                .alias("deposit-snapshot-file")
+                .help("Set an EIP-4881 deposit tree snapshot to load when checkpoint syncing, \
+                       rather than downloading historic deposit logs from the execution node. \
+                       The snapshot must not be newer than the checkpoint state. When used with \
+                       --checkpoint-sync-url it replaces the snapshot from the remote node.")
+                .value_name("SNAPSHOT_SSZ")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("checkpoint-sync-url")
                 .long("checkpoint-sync-url")
@@ -1374,13 +1465,26 @@ pub fn cli_app() -> Command {
             Arg::new("builder-fallback-skips-per-epoch")
                 .long("builder-fallback-skips-per-epoch")
                 .help("If this node is proposing a block and has seen this number of skip slots \
-                        on the canonical chain in the past `SLOTS_PER_EPOCH`, it will NOT query \
-                        any connected builders, and will use the local execution engine for \
+                        on the canonical chain in the past `SLOTS_PER_EPOCH` (or the number of \
+                        slots set by --builder-fallback-skips-window), it will NOT query any \
+                        connected builders, and will use the local execution engine for \
                         payload construction.")
                 .default_value("8")
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("builder-fallback-skips-window")
+                .long("builder-fallback-skips-window")
+                .value_name("SLOTS")
+                .help("The number of recent slots in which skip slots are counted for \
+                        --builder-fallback-skips-per-epoch. Must be between 1 and \
+                        `SLOTS_PER_EPOCH`, which is the default. Setting it below \
+                        --builder-fallback-skips-per-epoch will cause that condition to NEVER \
+                        be hit.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("builder-fallback-epochs-since-finalization")
                 .long("builder-fallback-epochs-since-finalization")
@@ -1395,6 +1499,17 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("builder-fallback-head-delay-ms")
+                .long("builder-fallback-head-delay-ms")
+                .value_name("MILLISECONDS")
+                .help("If this node is proposing a block and the head block was first seen more \
+                        than this number of milliseconds after the start of its slot, it will NOT \
+                        query any connected builders, and will use the local execution engine for \
+                        payload construction. Disabled by default.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("builder-fallback-disable-checks")
                 .long("builder-fallback-disable-checks")
@@ -1405,6 +1520,16 @@ pub fn cli_app() -> Command {
                 .help_heading(FLAG_HEADER)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("builder-disable-ssz")
+                .long("builder-disable-ssz")
+                .help("Disables the use of SSZ encoding for requests to and responses from the \
+                        builder API. JSON will be used instead.")
+                .requires("builder")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+        )
         .arg(
             Arg::new("builder-user-agent")
                 .long("builder-user-agent")
@@ -1415,6 +1540,29 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("builder-registration-batch-size")
+                .long("builder-registration-batch-size")
+                .value_name("COUNT")
+                .help("The maximum number of validator registrations to send to the builder in a \
+                       single request. Larger submissions are split into batches which are sent \
+                       one after the other.")
+                .requires("builder")
+                .default_value("500")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("builder-registration-batch-delay")
+                .long("builder-registration-batch-delay")
+                .value_name("MILLISECONDS")
+                .help("The time to wait between consecutive batches of validator registrations \
+                       sent to the builder.")
+                .requires("builder")
+                .default_value("100")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("reset-payload-statuses")
                 .long("reset-payload-statuses")
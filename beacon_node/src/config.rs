@@ -149,6 +149,7 @@ pub fn get_config<E: EthSpec>(
                     .ok_or("--http-tls-key was not provided.")?
                     .parse::<PathBuf>()
                     .map_err(|_| "http-tls-key is not a valid path name.")?,
+                client_ca: clap_utils::parse_optional(cli_args, "http-tls-client-ca")?,
             });
         }
 
@@ -166,6 +167,18 @@ pub fn get_config<E: EthSpec>(
         client_config.http_api.enable_beacon_processor =
             parse_required(cli_args, "http-enable-beacon-processor")?;
 
+        client_config.http_api.historic_state_replay_limit =
+            parse_required(cli_args, "http-historic-state-replay-limit")?;
+
+        client_config.http_api.api_tokens_file =
+            clap_utils::parse_optional(cli_args, "http-api-tokens-file")?;
+
+        client_config.http_api.rate_limit =
+            clap_utils::parse_optional(cli_args, "http-rate-limit")?;
+
+        client_config.http_api.response_cache_size_mb =
+            parse_required(cli_args, "http-response-cache-size")?;
+
         client_config.http_api.duplicate_block_status_code =
             parse_required(cli_args, "http-duplicate-block-status")?;
 
@@ -181,6 +194,9 @@ pub fn get_config<E: EthSpec>(
         client_config.chain.shuffling_cache_size = cache_size;
     }
 
+    client_config.chain.payload_prefetch_window =
+        clap_utils::parse_required(cli_args, "payload-prefetch-window")?;
+
     /*
      * Prometheus metrics HTTP server
      */
@@ -322,10 +338,12 @@ pub fn get_config<E: EthSpec>(
         }
 
         // Parse and set the payload builder, if any.
-        if let Some(endpoint) = cli_args.get_one::<String>("builder") {
-            let payload_builder =
-                parse_only_one_value(endpoint, SensitiveUrl::parse, "--builder", log)?;
-            el_config.builder_url = Some(payload_builder);
+        if let Some(endpoints) = cli_args.get_one::<String>("builder") {
+            el_config.builder_urls = endpoints
+                .split(',')
+                .map(SensitiveUrl::parse)
+                .collect::<Result<_, _>>()
+                .map_err(|e| format!("--builder contains an invalid value {:?}", e))?;
 
             el_config.builder_user_agent =
                 clap_utils::parse_optional(cli_args, "builder-user-agent")?;
@@ -333,6 +351,18 @@ pub fn get_config<E: EthSpec>(
             el_config.builder_header_timeout =
                 clap_utils::parse_optional(cli_args, "builder-header-timeout")?
                     .map(Duration::from_millis);
+
+            el_config.builder_disable_ssz = cli_args.get_flag("builder-disable-ssz");
+
+            el_config.builder_registration_batch_size =
+                clap_utils::parse_optional(cli_args, "builder-registration-batch-size")?;
+            if el_config.builder_registration_batch_size == Some(0) {
+                return Err("--builder-registration-batch-size must be greater than 0".into());
+            }
+
+            el_config.builder_registration_batch_delay =
+                clap_utils::parse_optional(cli_args, "builder-registration-batch-delay")?
+                    .map(Duration::from_millis);
         }
 
         if cli_args.get_flag("always-prefer-builder-payload") {
@@ -531,40 +561,56 @@ pub fn get_config<E: EthSpec>(
 
     client_config.allow_insecure_genesis_sync = cli_args.get_flag("allow-insecure-genesis-sync");
 
+    let deposit_snapshot_path = cli_args
+        .get_one::<String>("checkpoint-deposit-snapshot")
+        .map(String::as_str);
+
     client_config.genesis = if eth2_network_config.genesis_state_is_known() {
+        let read = |path: &str| {
+            use std::fs::File;
+            use std::io::Read;
+            File::open(Path::new(path))
+                .and_then(|mut f| {
+                    let mut buffer = vec![];
+                    f.read_to_end(&mut buffer)?;
+                    Ok(buffer)
+                })
+                .map_err(|e| format!("Unable to open {}: {:?}", path, e))
+        };
+
         // Set up weak subjectivity sync, or start from the hardcoded genesis state.
         if let (Some(initial_state_path), Some(initial_block_path), opt_initial_blobs_path) = (
             cli_args.get_one::<String>("checkpoint-state"),
             cli_args.get_one::<String>("checkpoint-block"),
             cli_args.get_one::<String>("checkpoint-blobs"),
         ) {
-            let read = |path: &str| {
-                use std::fs::File;
-                use std::io::Read;
-                File::open(Path::new(path))
-                    .and_then(|mut f| {
-                        let mut buffer = vec![];
-                        f.read_to_end(&mut buffer)?;
-                        Ok(buffer)
-                    })
-                    .map_err(|e| format!("Unable to open {}: {:?}", path, e))
-            };
-
             let anchor_state_bytes = read(initial_state_path)?;
             let anchor_block_bytes = read(initial_block_path)?;
             let anchor_blobs_bytes = opt_initial_blobs_path.map(|s| read(s)).transpose()?;
+            let deposit_snapshot_bytes = deposit_snapshot_path.map(read).transpose()?;
 
             ClientGenesis::WeakSubjSszBytes {
                 anchor_state_bytes,
                 anchor_block_bytes,
                 anchor_blobs_bytes,
+                deposit_snapshot_bytes,
             }
         } else if let Some(remote_bn_url) = cli_args.get_one::<String>("checkpoint-sync-url") {
             let url = SensitiveUrl::parse(remote_bn_url)
                 .map_err(|e| format!("Invalid checkpoint sync URL: {:?}", e))?;
+            let deposit_snapshot_bytes = deposit_snapshot_path.map(read).transpose()?;
 
-            ClientGenesis::CheckpointSyncUrl { url }
+            ClientGenesis::CheckpointSyncUrl {
+                url,
+                deposit_snapshot_bytes,
+            }
         } else {
+            if deposit_snapshot_path.is_some() {
+                return Err(
+                    "--checkpoint-deposit-snapshot can only be used when checkpoint syncing"
+                        .to_string(),
+                );
+            }
             ClientGenesis::GenesisState
         }
     } else {
@@ -829,10 +875,23 @@ pub fn get_config<E: EthSpec>(
         clap_utils::parse_required(cli_args, "builder-fallback-skips")?;
     client_config.chain.builder_fallback_skips_per_epoch =
         clap_utils::parse_required(cli_args, "builder-fallback-skips-per-epoch")?;
+    client_config.chain.builder_fallback_skips_window =
+        clap_utils::parse_optional(cli_args, "builder-fallback-skips-window")?;
+    if let Some(window) = client_config.chain.builder_fallback_skips_window {
+        let slots_per_epoch = E::slots_per_epoch();
+        if window == 0 || window > slots_per_epoch {
+            return Err(format!(
+                "--builder-fallback-skips-window must be between 1 and SLOTS_PER_EPOCH \
+                 ({slots_per_epoch}), got {window}"
+            ));
+        }
+    }
     client_config
         .chain
         .builder_fallback_epochs_since_finalization =
         clap_utils::parse_required(cli_args, "builder-fallback-epochs-since-finalization")?;
+    client_config.chain.builder_fallback_head_delay_ms =
+        clap_utils::parse_optional(cli_args, "builder-fallback-head-delay-ms")?;
     client_config.chain.builder_fallback_disable_checks =
         cli_args.get_flag("builder-fallback-disable-checks");
 
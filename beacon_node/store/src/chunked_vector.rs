@@ -621,6 +621,20 @@ pub fn load_variable_list_from_db<F: VariableLengthField<E>, E: EthSpec, S: KeyV
     Ok(List::new(result).map_err(ChunkError::Milhouse)?)
 }
 
+/// Load the single value of a field at `vindex`, or `None` if it hasn't been stored.
+///
+/// Chunks are filled with default values, so a default value is also treated as missing.
+pub fn load_value_from_db<F: Field<E>, E: EthSpec, S: KeyValueStore<E>>(
+    store: &S,
+    vindex: usize,
+) -> Result<Option<F::Value>, Error> {
+    let chunk_size = F::chunk_size();
+    let chunk = Chunk::<F::Value>::load(store, F::column(), &chunk_key(vindex / chunk_size))?;
+    Ok(chunk
+        .and_then(|chunk| chunk.values.get(vindex % chunk_size).cloned())
+        .filter(|value| *value != F::Value::default()))
+}
+
 /// Index into a `List` field of the state, avoiding out of bounds and division by 0.
 fn safe_modulo_list_index<T: milhouse::Value + Copy, N: Unsigned>(
     values: &List<T, N>,
@@ -34,6 +34,10 @@ impl<E: EthSpec> OnDiskConsensusContext<E> {
             current_epoch: _,
             proposer_index,
             current_block_root,
+            // The body root and payload header are cheap to recompute relative to the rest of
+            // block processing, so we don't persist them.
+            current_block_body_root: _,
+            execution_payload_header: _,
             indexed_attestations,
         } = ctxt;
         OnDiskConsensusContext {
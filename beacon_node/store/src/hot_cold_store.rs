@@ -1,12 +1,16 @@
 use crate::chunked_vector::{
-    store_updated_vector, BlockRoots, HistoricalRoots, HistoricalSummaries, RandaoMixes, StateRoots,
+    load_value_from_db, store_updated_vector, BlockRoots, HistoricalRoots, HistoricalSummaries,
+    RandaoMixes, StateRoots,
 };
 use crate::config::{
     OnDiskStoreConfig, StoreConfig, DEFAULT_SLOTS_PER_RESTORE_POINT,
     PREV_DEFAULT_SLOTS_PER_RESTORE_POINT,
 };
 use crate::forwards_iter::{HybridForwardsBlockRootsIterator, HybridForwardsStateRootsIterator};
-use crate::impls::beacon_state::{get_full_state, store_full_state};
+use crate::impls::beacon_state::{
+    get_full_state, load_progressive_balances_cache, store_full_state,
+    store_progressive_balances_cache,
+};
 use crate::iter::{BlockRootsIterator, ParentRootBlockIterator, RootsIterator};
 use crate::leveldb_store::BytesKey;
 use crate::leveldb_store::LevelDB;
@@ -552,6 +556,21 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
             .map_err(|e| e.into())
     }
 
+    /// Read some fields of a block from the store without decoding the entire block.
+    ///
+    /// This is much cheaper than loading the block when only its slot or roots are required.
+    pub fn get_block_view_with<T>(
+        &self,
+        block_root: &Hash256,
+        f: impl FnOnce(SignedBeaconBlockView) -> T,
+    ) -> Result<Option<T>, Error> {
+        self.hot_db
+            .get_bytes(DBColumn::BeaconBlock.into(), block_root.as_bytes())?
+            .map(|block_bytes| SignedBeaconBlockView::from_ssz_bytes(&block_bytes).map(f))
+            .transpose()
+            .map_err(|e| e.into())
+    }
+
     /// Load the execution payload for a block from disk.
     /// This method deserializes with the proper fork.
     pub fn get_execution_payload(
@@ -563,7 +582,9 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         let key = block_root.as_bytes();
 
         match self.hot_db.get_bytes(column, key)? {
-            Some(bytes) => Ok(Some(ExecutionPayload::from_ssz_bytes(&bytes, fork_name)?)),
+            Some(bytes) => Ok(Some(ExecutionPayload::from_ssz_bytes_by_fork(
+                &bytes, fork_name,
+            )?)),
             None => Ok(None),
         }
     }
@@ -942,6 +963,11 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
                         let state_key =
                             get_key_for_col(DBColumn::BeaconState.into(), state_root.as_bytes());
                         key_value_batch.push(KeyValueStoreOp::DeleteKey(state_key));
+                        let cache_key = get_key_for_col(
+                            DBColumn::BeaconProgressiveBalances.into(),
+                            state_root.as_bytes(),
+                        );
+                        key_value_batch.push(KeyValueStoreOp::DeleteKey(cache_key));
                     }
                 }
 
@@ -1273,6 +1299,7 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         let partial_state = PartialBeaconState::from_state_forgetful(state);
         let op = partial_state.as_kv_store_op(*state_root);
         ops.push(op);
+        store_progressive_balances_cache(state_root, state, ops);
 
         // 2. Store updated vector entries.
         // Block roots need to be written here as well as by the `ChunkWriter` in `migrate_db`
@@ -1342,6 +1369,7 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
 
         let mut state: BeaconState<E> = partial_state.try_into()?;
         state.apply_pending_mutations()?;
+        load_progressive_balances_cache(&self.cold_db, state_root, &mut state)?;
         Ok(state)
     }
 
@@ -1417,6 +1445,46 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         Ok(state)
     }
 
+    /// Load the final RANDAO mix of `epoch` from the freezer database.
+    ///
+    /// Return `None` if the mix hasn't been stored by a restore point.
+    pub fn get_cold_randao_mix(&self, epoch: Epoch) -> Result<Option<Hash256>, Error> {
+        load_value_from_db::<RandaoMixes, E, _>(&self.cold_db, epoch.as_usize())
+    }
+
+    /// Load the validator registry of the first restore point at or after `slot`, without loading
+    /// the rest of the state.
+    ///
+    /// Activation and exit epochs are always set to an epoch which hasn't started yet, so this
+    /// registry gives the same active validators as the registry of the state at `slot`.
+    ///
+    /// Return `None` if there is no such restore point in the freezer database.
+    pub fn load_cold_validators(&self, slot: Slot) -> Result<Option<Validators<E>>, Error> {
+        // There are no restore points between the historic state limits.
+        let (lower_limit, upper_limit) = self.get_historic_state_limits();
+        let slot = if slot > lower_limit && slot < upper_limit {
+            upper_limit
+        } else {
+            slot
+        };
+
+        let restore_point_index = slot.as_u64().div_ceil(self.config.slots_per_restore_point);
+        if restore_point_index * self.config.slots_per_restore_point
+            >= self.get_split_slot().as_u64()
+        {
+            return Ok(None);
+        }
+
+        let state_root = self.load_restore_point_hash(restore_point_index)?;
+        let partial_state_bytes = self
+            .cold_db
+            .get_bytes(DBColumn::BeaconState.into(), state_root.as_bytes())?
+            .ok_or(HotColdDBError::MissingRestorePoint(state_root))?;
+        let partial_state: PartialBeaconState<E> =
+            PartialBeaconState::from_ssz_bytes(&partial_state_bytes, &self.spec)?;
+        Ok(Some(partial_state.validators().clone()))
+    }
+
     /// Get the restore point with the given index, or if it is out of bounds, the split state.
     pub(crate) fn get_restore_point(
         &self,
@@ -2445,6 +2513,7 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
             DBColumn::BeaconHistoricalRoots,
             DBColumn::BeaconRandaoMixes,
             DBColumn::BeaconHistoricalSummaries,
+            DBColumn::BeaconProgressiveBalances,
         ];
 
         for column in columns {
@@ -15,6 +15,7 @@ pub fn store_full_state<E: EthSpec>(
     metrics::inc_counter(&metrics::BEACON_STATE_WRITE_COUNT);
     let key = get_key_for_col(DBColumn::BeaconState.into(), state_root.as_bytes());
     ops.push(KeyValueStoreOp::PutKeyValue(key, bytes));
+    store_progressive_balances_cache(state_root, state, ops);
     Ok(())
 }
 
@@ -35,12 +36,56 @@ pub fn get_full_state<KV: KeyValueStore<E>, E: EthSpec>(
             metrics::inc_counter(&metrics::BEACON_STATE_READ_COUNT);
             metrics::inc_counter_by(&metrics::BEACON_STATE_READ_BYTES, bytes.len() as u64);
 
-            Ok(Some(container.try_into()?))
+            let mut state = container.try_into()?;
+            load_progressive_balances_cache(db, state_root, &mut state)?;
+            Ok(Some(state))
         }
         None => Ok(None),
     }
 }
 
+/// Store the progressive balances cache of `state`, if it is initialized.
+///
+/// The cache is stored separately from the state so that the format of stored states is
+/// unchanged, and so that states stored without it remain readable.
+pub fn store_progressive_balances_cache<E: EthSpec>(
+    state_root: &Hash256,
+    state: &BeaconState<E>,
+    ops: &mut Vec<KeyValueStoreOp>,
+) {
+    if let Some(bytes) = state.progressive_balances_cache().as_persisted_bytes() {
+        let key = get_key_for_col(
+            DBColumn::BeaconProgressiveBalances.into(),
+            state_root.as_bytes(),
+        );
+        ops.push(KeyValueStoreOp::PutKeyValue(key, bytes));
+    }
+}
+
+/// Load the progressive balances cache stored for `state`, if any.
+///
+/// The cache is ignored if it is unreadable or was built for a different epoch, in which case it
+/// will be rebuilt from the state's participation when it is next required.
+pub fn load_progressive_balances_cache<KV: KeyValueStore<E>, E: EthSpec>(
+    db: &KV,
+    state_root: &Hash256,
+    state: &mut BeaconState<E>,
+) -> Result<(), Error> {
+    let Some(bytes) = db.get_bytes(
+        DBColumn::BeaconProgressiveBalances.into(),
+        state_root.as_bytes(),
+    )?
+    else {
+        return Ok(());
+    };
+    if let Ok(cache) = ProgressiveBalancesCache::from_persisted_bytes(&bytes) {
+        if cache.current_epoch() == Some(state.current_epoch()) {
+            *state.progressive_balances_cache_mut() = cache;
+        }
+    }
+    Ok(())
+}
+
 /// A container for storing `BeaconState` components.
 // TODO: would be more space efficient with the caches stored separately and referenced by hash
 #[derive(Encode)]
@@ -188,12 +188,14 @@ impl<'a, E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> RootsIterator<'a, E,
         store: &'a HotColdDB<E, Hot, Cold>,
         block_hash: Hash256,
     ) -> Result<Self, Error> {
-        let block = store
-            .get_blinded_block(&block_hash)?
+        let (state_root, slot) = store
+            .get_block_view_with(&block_hash, |block| {
+                (block.message().state_root(), block.slot())
+            })?
             .ok_or_else(|| BeaconStateError::MissingBeaconBlock(block_hash.into()))?;
         let state = store
-            .get_state(&block.state_root(), Some(block.slot()))?
-            .ok_or_else(|| BeaconStateError::MissingBeaconState(block.state_root().into()))?;
+            .get_state(&state_root, Some(slot))?
+            .ok_or_else(|| BeaconStateError::MissingBeaconState(state_root.into()))?;
         Ok(Self::owned(store, state))
     }
 
@@ -274,6 +274,9 @@ pub enum DBColumn {
     BeaconHistoricalSummaries,
     #[strum(serialize = "olc")]
     OverflowLRUCache,
+    /// For the progressive balances caches of states stored in the `BeaconState` column.
+    #[strum(serialize = "bpb")]
+    BeaconProgressiveBalances,
 }
 
 /// A block from the database, which might have an execution payload or not.
@@ -311,7 +314,8 @@ impl DBColumn {
             | Self::PubkeyCache
             | Self::BeaconRestorePoint
             | Self::DhtEnrs
-            | Self::OptimisticTransitionBlock => 32,
+            | Self::OptimisticTransitionBlock
+            | Self::BeaconProgressiveBalances => 32,
             Self::BeaconBlockRoots
             | Self::BeaconStateRoots
             | Self::BeaconHistoricalRoots
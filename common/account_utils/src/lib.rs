@@ -18,6 +18,8 @@ use std::thread::sleep;
 use std::time::Duration;
 use zeroize::Zeroize;
 
+pub mod network_binding;
+pub mod parallel_decryption;
 pub mod validator_definitions;
 
 pub use eth2_keystore;
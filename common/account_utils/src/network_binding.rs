@@ -0,0 +1,116 @@
+//! Binds a validator directory to a single network, identified by its genesis validators root.
+//!
+//! The keys and slashing protection history in a validator directory belong to the network they
+//! were used on. The validator client binds a directory to its network when it first starts, and
+//! refuses to start if the directory is later used with another network. Moving a directory to
+//! another network must be done explicitly, with `lighthouse account validator rebind-network`.
+
+use crate::write_file_via_temporary;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io;
+use std::path::Path;
+use types::Hash256;
+
+/// The file within the validator directory which records its network.
+pub const NETWORK_BINDING_FILENAME: &str = "network_binding.yml";
+
+/// The temporary file used while atomically updating `NETWORK_BINDING_FILENAME`.
+pub const NETWORK_BINDING_TEMP_FILENAME: &str = ".network_binding.yml.tmp";
+
+#[derive(Debug)]
+pub enum Error {
+    /// The binding file could not be opened.
+    UnableToOpenFile(io::Error),
+    /// The binding file could not be parsed as YAML.
+    UnableToParseFile(serde_yaml::Error),
+    /// The binding could not be serialized as YAML.
+    UnableToEncodeFile(serde_yaml::Error),
+    /// The binding file or temp file could not be written to the filesystem.
+    UnableToWriteFile(filesystem::Error),
+    /// The validator directory is bound to a different network.
+    NetworkMismatch { bound: Hash256, actual: Hash256 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct NetworkBinding {
+    pub genesis_validators_root: Hash256,
+}
+
+impl NetworkBinding {
+    /// Returns the binding of `validators_dir`, or `None` if it isn't bound to any network.
+    pub fn open<P: AsRef<Path>>(validators_dir: P) -> Result<Option<Self>, Error> {
+        let path = validators_dir.as_ref().join(NETWORK_BINDING_FILENAME);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let file = File::open(path).map_err(Error::UnableToOpenFile)?;
+        serde_yaml::from_reader(file)
+            .map(Some)
+            .map_err(Error::UnableToParseFile)
+    }
+
+    /// Atomically writes `self` to `validators_dir`, replacing any existing binding.
+    pub fn save<P: AsRef<Path>>(&self, validators_dir: P) -> Result<(), Error> {
+        let path = validators_dir.as_ref().join(NETWORK_BINDING_FILENAME);
+        let temp_path = validators_dir.as_ref().join(NETWORK_BINDING_TEMP_FILENAME);
+        let mut bytes = vec![];
+        serde_yaml::to_writer(&mut bytes, self).map_err(Error::UnableToEncodeFile)?;
+
+        write_file_via_temporary(&path, &temp_path, &bytes).map_err(Error::UnableToWriteFile)
+    }
+
+    /// Checks that `validators_dir` is bound to the network with `genesis_validators_root`,
+    /// binding it to that network if it isn't bound yet.
+    ///
+    /// Returns `true` if the directory was newly bound.
+    pub fn check_or_bind<P: AsRef<Path>>(
+        validators_dir: P,
+        genesis_validators_root: Hash256,
+    ) -> Result<bool, Error> {
+        match Self::open(&validators_dir)? {
+            Some(binding) if binding.genesis_validators_root == genesis_validators_root => {
+                Ok(false)
+            }
+            Some(binding) => Err(Error::NetworkMismatch {
+                bound: binding.genesis_validators_root,
+                actual: genesis_validators_root,
+            }),
+            None => {
+                Self {
+                    genesis_validators_root,
+                }
+                .save(validators_dir)?;
+                Ok(true)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn binds_once_then_checks() {
+        let dir = tempdir().unwrap();
+        let root = Hash256::repeat_byte(1);
+        let other_root = Hash256::repeat_byte(2);
+
+        assert_eq!(NetworkBinding::open(dir.path()).unwrap(), None);
+        assert!(NetworkBinding::check_or_bind(dir.path(), root).unwrap());
+        assert!(!NetworkBinding::check_or_bind(dir.path(), root).unwrap());
+        assert!(matches!(
+            NetworkBinding::check_or_bind(dir.path(), other_root),
+            Err(Error::NetworkMismatch { bound, actual }) if bound == root && actual == other_root
+        ));
+
+        NetworkBinding {
+            genesis_validators_root: other_root,
+        }
+        .save(dir.path())
+        .unwrap();
+        assert!(!NetworkBinding::check_or_bind(dir.path(), other_root).unwrap());
+    }
+}
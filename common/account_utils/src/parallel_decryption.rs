@@ -0,0 +1,125 @@
+//! Decrypts many keystores at once, since decrypting a keystore is deliberately slow.
+
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+
+/// Calls `decrypt` on each of `items` using up to `max_threads` threads, passing each result to
+/// `on_result` with its item and the index of that item.
+///
+/// Results are passed to `on_result` in the order of `items`, as soon as they are available, so
+/// the caller can make progress while the remaining items are decrypted. The result is `None` if
+/// decrypting the item panicked.
+///
+/// This is a blocking function.
+pub fn decrypt_in_parallel<T, R, F>(
+    items: &[T],
+    max_threads: usize,
+    decrypt: F,
+    mut on_result: impl FnMut(usize, &T, Option<R>),
+) where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let num_threads = std::thread::available_parallelism()
+        .map_or(1, NonZeroUsize::get)
+        .min(max_threads)
+        .min(items.len());
+    let next_index = AtomicUsize::new(0);
+
+    std::thread::scope(|scope| {
+        let (result_tx, result_rx) = mpsc::channel();
+        for _ in 0..num_threads {
+            let result_tx = result_tx.clone();
+            let (decrypt, next_index) = (&decrypt, &next_index);
+            scope.spawn(move || loop {
+                let i = next_index.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(i) else {
+                    break;
+                };
+                let result = catch_unwind(AssertUnwindSafe(|| decrypt(item))).ok();
+                if result_tx.send((i, result)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(result_tx);
+
+        // Results arrive out of order, hold them until their item is reached.
+        let mut results = HashMap::new();
+        for (i, item) in items.iter().enumerate() {
+            let result = loop {
+                if let Some(result) = results.remove(&i) {
+                    break result;
+                }
+                match result_rx.recv() {
+                    Ok((j, result)) => {
+                        results.insert(j, result);
+                    }
+                    // Every item is sent unless its worker has died, which isn't expected.
+                    Err(_) => break None,
+                }
+            };
+            on_result(i, item, result);
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn results_are_in_order() {
+        let items = (0..100).collect::<Vec<u64>>();
+        let mut results = vec![];
+        decrypt_in_parallel(
+            &items,
+            8,
+            |&item| {
+                // Finish the later items first.
+                std::thread::sleep(std::time::Duration::from_micros(100 - item));
+                item * 2
+            },
+            |i, &item, result| results.push((i, item, result)),
+        );
+        assert_eq!(
+            results,
+            items
+                .iter()
+                .map(|&item| (item as usize, item, Some(item * 2)))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn panics_are_not_propagated() {
+        let items = (0..10).collect::<Vec<u64>>();
+        let mut results = vec![];
+        decrypt_in_parallel(
+            &items,
+            2,
+            |&item| {
+                assert_ne!(item, 3, "decryption failed");
+                item
+            },
+            |_, &item, result| results.push((item, result)),
+        );
+        assert_eq!(results.len(), items.len());
+        for (item, result) in results {
+            if item == 3 {
+                assert_eq!(result, None);
+            } else {
+                assert_eq!(result, Some(item));
+            }
+        }
+    }
+
+    #[test]
+    fn no_items() {
+        decrypt_in_parallel(&[] as &[u64], 8, |&item| item, |_, _, _| panic!("no items"));
+    }
+}
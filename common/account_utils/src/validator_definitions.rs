@@ -15,7 +15,7 @@ use std::collections::HashSet;
 use std::fs::{self, File};
 use std::io;
 use std::path::{Path, PathBuf};
-use types::{graffiti::GraffitiString, Address, PublicKey};
+use types::{graffiti::GraffitiString, Address, Epoch, PublicKey, SignedBlsToExecutionChange};
 use validator_dir::VOTING_KEYSTORE_FILE;
 
 /// The file name for the serialized `ValidatorDefinitions` struct.
@@ -80,6 +80,15 @@ pub struct Web3SignerDefinition {
     /// An empty password will be used if this is omitted.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub client_identity_password: Option<String>,
+
+    /// Path to an EIP-2335 keystore of the same key, used to sign messages locally when the
+    /// Web3Signer server exceeds a signing timeout.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fallback_keystore_path: Option<PathBuf>,
+
+    /// Path to the password of the fallback keystore.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fallback_keystore_password_path: Option<PathBuf>,
 }
 
 /// Defines how the validator client should attempt to sign messages for this validator.
@@ -100,6 +109,10 @@ pub enum SigningDefinition {
     /// https://github.com/ConsenSys/web3signer
     #[serde(rename = "web3signer")]
     Web3Signer(Web3SignerDefinition),
+    /// A validator without any keys, whose duties and performance are tracked but which never
+    /// signs any messages.
+    #[serde(rename = "watch_only")]
+    WatchOnly,
 }
 
 impl SigningDefinition {
@@ -107,6 +120,10 @@ impl SigningDefinition {
         matches!(self, SigningDefinition::LocalKeystore { .. })
     }
 
+    pub fn is_watch_only(&self) -> bool {
+        matches!(self, SigningDefinition::WatchOnly)
+    }
+
     pub fn voting_keystore_password(&self) -> Result<Option<ZeroizeString>, Error> {
         match self {
             SigningDefinition::LocalKeystore {
@@ -121,7 +138,7 @@ impl SigningDefinition {
                 .map(Option::Some)
                 .map_err(Error::UnableToReadKeystorePassword),
             SigningDefinition::LocalKeystore { .. } => Err(Error::KeystoreWithoutPassword),
-            SigningDefinition::Web3Signer(_) => Ok(None),
+            SigningDefinition::Web3Signer(_) | SigningDefinition::WatchOnly => Ok(None),
         }
     }
 
@@ -136,6 +153,25 @@ impl SigningDefinition {
     }
 }
 
+/// A voluntary exit scheduled for a future epoch.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ScheduledExit {
+    /// The epoch at which the exit is signed and broadcast, which is also the epoch of the exit.
+    pub epoch: Epoch,
+    /// Set once the signed exit has been accepted by a beacon node.
+    #[serde(default)]
+    pub submitted: bool,
+}
+
+/// A signed change of the validator's withdrawal credentials to an execution address.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlsToExecutionChangeRecord {
+    pub signed_change: SignedBlsToExecutionChange,
+    /// Set once the signed change has been accepted by a beacon node.
+    #[serde(default)]
+    pub submitted: bool,
+}
+
 /// A validator that may be initialized by this validator client.
 ///
 /// Presently there is only a single variant, however we expect more variants to arise (e.g.,
@@ -162,6 +198,24 @@ pub struct ValidatorDefinition {
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub prefer_builder_proposals: Option<bool>,
+    /// A voluntary exit which should be signed and broadcast once its epoch is reached.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scheduled_exit: Option<ScheduledExit>,
+    /// A change of withdrawal credentials which should be broadcast, or which has been broadcast.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bls_to_execution_change: Option<BlsToExecutionChangeRecord>,
+    /// The epoch from which blocks and attestations are no longer signed for this validator,
+    /// because it has been handed over to another validator client.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub handover_epoch: Option<Epoch>,
+    /// Whether the validator is suspended, in which case it remains loaded but performs no duties
+    /// and signs no messages until it is resumed.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub suspended: bool,
     #[serde(default)]
     pub description: String,
     #[serde(flatten)]
@@ -207,6 +261,10 @@ impl ValidatorDefinition {
             builder_proposals,
             builder_boost_factor,
             prefer_builder_proposals,
+            scheduled_exit: None,
+            bls_to_execution_change: None,
+            handover_epoch: None,
+            suspended: false,
             signing_definition: SigningDefinition::LocalKeystore {
                 voting_keystore_path,
                 voting_keystore_password_path,
@@ -214,6 +272,26 @@ impl ValidatorDefinition {
             },
         })
     }
+
+    /// Create a new definition for a watch-only validator with the given `voting_public_key`.
+    pub fn new_watch_only(voting_public_key: PublicKey, description: String) -> Self {
+        ValidatorDefinition {
+            enabled: true,
+            voting_public_key,
+            description,
+            graffiti: None,
+            suggested_fee_recipient: None,
+            gas_limit: None,
+            builder_proposals: None,
+            builder_boost_factor: None,
+            prefer_builder_proposals: None,
+            scheduled_exit: None,
+            bls_to_execution_change: None,
+            handover_epoch: None,
+            suspended: false,
+            signing_definition: SigningDefinition::WatchOnly,
+        }
+    }
 }
 
 /// A list of `ValidatorDefinition` that serves as a serde-able configuration file which defines a
@@ -283,8 +361,11 @@ impl ValidatorDefinitions {
                     voting_keystore_path,
                     ..
                 } => Some(voting_keystore_path),
-                // A Web3Signer validator does not use a local keystore file.
-                SigningDefinition::Web3Signer { .. } => None,
+                // A Web3Signer validator only uses a local keystore file as a fallback.
+                SigningDefinition::Web3Signer(web3_signer) => {
+                    web3_signer.fallback_keystore_path.as_ref()
+                }
+                SigningDefinition::WatchOnly => None,
             })
             .collect();
 
@@ -357,6 +438,10 @@ impl ValidatorDefinitions {
                     builder_proposals: None,
                     builder_boost_factor: None,
                     prefer_builder_proposals: None,
+                    scheduled_exit: None,
+                    bls_to_execution_change: None,
+                    handover_epoch: None,
+                    suspended: false,
                     signing_definition: SigningDefinition::LocalKeystore {
                         voting_keystore_path,
                         voting_keystore_password_path,
@@ -687,4 +772,20 @@ mod tests {
         let def: ValidatorDefinition = serde_yaml::from_str(valid_builder_proposals).unwrap();
         assert_eq!(def.builder_proposals, Some(true));
     }
+
+    #[test]
+    fn watch_only_round_trip() {
+        let watch_only = r#"---
+        description: ""
+        enabled: true
+        type: watch_only
+        voting_public_key: "0xaf3c7ddab7e293834710fca2d39d068f884455ede270e0d0293dc818e4f2f0f975355067e8437955cb29aec674e5c9e7"
+        "#;
+        let def: ValidatorDefinition = serde_yaml::from_str(watch_only).unwrap();
+        assert!(def.signing_definition.is_watch_only());
+
+        let encoded = serde_yaml::to_string(&def).unwrap();
+        let decoded: ValidatorDefinition = serde_yaml::from_str(&encoded).unwrap();
+        assert!(decoded.signing_definition == SigningDefinition::WatchOnly);
+    }
 }
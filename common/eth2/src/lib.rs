@@ -22,7 +22,7 @@ use lighthouse_network::PeerId;
 use pretty_reqwest_error::PrettyReqwestError;
 pub use reqwest;
 use reqwest::{
-    header::{HeaderMap, HeaderValue},
+    header::{HeaderMap, HeaderValue, ETAG, IF_NONE_MATCH},
     Body, IntoUrl, RequestBuilder, Response,
 };
 pub use reqwest::{StatusCode, Url};
@@ -32,6 +32,7 @@ use ssz::Encode;
 use std::fmt;
 use std::future::Future;
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Duration;
 use store::fork_versioned_response::ExecutionOptimisticFinalizedForkVersionedResponse;
 
@@ -43,6 +44,12 @@ pub const CONSENSUS_VERSION_HEADER: &str = "Eth-Consensus-Version";
 pub const EXECUTION_PAYLOAD_BLINDED_HEADER: &str = "Eth-Execution-Payload-Blinded";
 pub const EXECUTION_PAYLOAD_VALUE_HEADER: &str = "Eth-Execution-Payload-Value";
 pub const CONSENSUS_BLOCK_VALUE_HEADER: &str = "Eth-Consensus-Block-Value";
+/// Lighthouse extension: the value of the locally built payload considered for a block.
+pub const LOCAL_PAYLOAD_VALUE_HEADER: &str = "Lighthouse-Local-Payload-Value";
+/// Lighthouse extension: the value of the best builder bid considered for a block.
+pub const BUILDER_BID_VALUE_HEADER: &str = "Lighthouse-Builder-Bid-Value";
+/// Lighthouse extension: the number of slots replayed to reconstruct a historic state.
+pub const REPLAY_DEPTH_HEADER: &str = "Lighthouse-Replay-Depth";
 
 pub const CONTENT_TYPE_HEADER: &str = "Content-Type";
 pub const SSZ_CONTENT_TYPE_HEADER: &str = "application/octet-stream";
@@ -234,6 +241,35 @@ impl BeaconNodeHttpClient {
         Ok(response.json().await?)
     }
 
+    /// Perform a HTTP GET request which sends `etag` as `If-None-Match`.
+    ///
+    /// Returns the response and its `ETag`, or `None` if the server reports that the response
+    /// still matches `etag`.
+    async fn get_if_none_match<T: DeserializeOwned, U: IntoUrl>(
+        &self,
+        url: U,
+        etag: Option<&str>,
+    ) -> Result<Option<(T, Option<String>)>, Error> {
+        let result = self
+            .get_response(url, |b| match etag {
+                Some(etag) => b.header(IF_NONE_MATCH, etag),
+                None => b,
+            })
+            .await;
+        match result {
+            Ok(response) => {
+                let etag = response
+                    .headers()
+                    .get(ETAG)
+                    .and_then(|etag| etag.to_str().ok())
+                    .map(str::to_string);
+                Ok(Some((response.json().await?, etag)))
+            }
+            Err(Error::StatusCode(StatusCode::NOT_MODIFIED)) if etag.is_some() => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
     /// Perform a HTTP GET request, returning `None` on a 404 error.
     async fn get_opt<T: DeserializeOwned, U: IntoUrl>(&self, url: U) -> Result<Option<T>, Error> {
         match self
@@ -469,6 +505,23 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET beacon/genesis`, revalidating a previous response with the given `etag`.
+    ///
+    /// Returns `Ok(None)` if the response is unchanged.
+    pub async fn get_beacon_genesis_if_none_match(
+        &self,
+        etag: Option<&str>,
+    ) -> Result<Option<(GenericResponse<GenesisData>, Option<String>)>, Error> {
+        let mut path = self.eth_path(V1)?;
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("beacon")
+            .push("genesis");
+
+        self.get_if_none_match(path, etag).await
+    }
+
     /// `GET beacon/states/{state_id}/root`
     ///
     /// Returns `Ok(None)` on a 404 error.
@@ -607,6 +660,24 @@ impl BeaconNodeHttpClient {
         state_id: StateId,
         ids: Option<&[ValidatorId]>,
         statuses: Option<&[ValidatorStatus]>,
+    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<Vec<ValidatorData>>>, Error> {
+        self.get_beacon_states_validators_page(state_id, ids, statuses, None, None)
+            .await
+    }
+
+    /// `GET beacon/states/{state_id}/validators?id,status,offset,limit`
+    ///
+    /// Returns at most `limit` of the matching validators, after skipping the first `offset` in
+    /// order of index.
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_beacon_states_validators_page(
+        &self,
+        state_id: StateId,
+        ids: Option<&[ValidatorId]>,
+        statuses: Option<&[ValidatorStatus]>,
+        offset: Option<u64>,
+        limit: Option<u64>,
     ) -> Result<Option<ExecutionOptimisticFinalizedResponse<Vec<ValidatorData>>>, Error> {
         let mut path = self.eth_path(V1)?;
 
@@ -635,6 +706,16 @@ impl BeaconNodeHttpClient {
             path.query_pairs_mut().append_pair("status", &status_string);
         }
 
+        if let Some(offset) = offset {
+            path.query_pairs_mut()
+                .append_pair("offset", &offset.to_string());
+        }
+
+        if let Some(limit) = limit {
+            path.query_pairs_mut()
+                .append_pair("limit", &limit.to_string());
+        }
+
         self.get_opt(path).await
     }
 
@@ -656,7 +737,12 @@ impl BeaconNodeHttpClient {
             .push(&state_id.to_string())
             .push("validators");
 
-        let request = ValidatorsRequestBody { ids, statuses };
+        let request = ValidatorsRequestBody {
+            ids,
+            statuses,
+            offset: None,
+            limit: None,
+        };
 
         self.post_with_opt_response(path, &request).await
     }
@@ -818,6 +904,27 @@ impl BeaconNodeHttpClient {
         self.get_opt(path).await
     }
 
+    /// `GET beacon/light_client/updates?start_period,count`
+    pub async fn get_beacon_light_client_updates<E: EthSpec>(
+        &self,
+        start_period: u64,
+        count: u64,
+    ) -> Result<Vec<ForkVersionedResponse<LightClientUpdate<E>>>, Error> {
+        let mut path = self.eth_path(V1)?;
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("beacon")
+            .push("light_client")
+            .push("updates");
+
+        path.query_pairs_mut()
+            .append_pair("start_period", &start_period.to_string())
+            .append_pair("count", &count.to_string());
+
+        self.get(path).await
+    }
+
     /// `GET beacon/headers?slot,parent_root`
     ///
     /// Returns `Ok(None)` on a 404 error.
@@ -1135,6 +1242,23 @@ impl BeaconNodeHttpClient {
         Ok(Some(response.json().await?))
     }
 
+    /// `POST v1/beacon/blob_sidecars`
+    ///
+    /// Returns the requested blob sidecars of each block, omitting unknown blocks.
+    pub async fn post_beacon_blob_sidecars<E: EthSpec>(
+        &self,
+        requests: &[BlobSidecarsByRootRequest],
+    ) -> Result<GenericResponse<Vec<Arc<BlobSidecar<E>>>>, Error> {
+        let mut path = self.eth_path(V1)?;
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("beacon")
+            .push("blob_sidecars");
+
+        self.post_with_response(path, &requests).await
+    }
+
     /// `GET v1/beacon/blinded_blocks/{block_id}`
     ///
     /// Returns `Ok(None)` on a 404 error.
@@ -1561,44 +1685,61 @@ impl BeaconNodeHttpClient {
         Ok(())
     }
 
-    /// `GET beacon/rewards/blocks`
-    pub async fn get_beacon_rewards_blocks(&self, epoch: Epoch) -> Result<(), Error> {
+    /// `GET beacon/rewards/blocks/{block_id}`
+    #[cfg(feature = "lighthouse")]
+    pub async fn get_beacon_rewards_blocks(
+        &self,
+        block_id: BlockId,
+    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<lighthouse::StandardBlockReward>>, Error>
+    {
         let mut path = self.eth_path(V1)?;
 
         path.path_segments_mut()
             .map_err(|()| Error::InvalidUrl(self.server.clone()))?
             .push("beacon")
             .push("rewards")
-            .push("blocks");
-
-        path.query_pairs_mut()
-            .append_pair("epoch", &epoch.to_string());
+            .push("blocks")
+            .push(&block_id.to_string());
 
-        self.get(path).await
+        self.get_opt(path).await
     }
 
-    /// `POST beacon/rewards/attestations`
+    /// `POST beacon/rewards/attestations/{epoch}`
+    #[cfg(feature = "lighthouse")]
     pub async fn post_beacon_rewards_attestations(
         &self,
-        attestations: &[ValidatorId],
-    ) -> Result<(), Error> {
+        epoch: Epoch,
+        validators: &[ValidatorId],
+    ) -> Result<ExecutionOptimisticResponse<lighthouse::StandardAttestationRewards>, Error> {
         let mut path = self.eth_path(V1)?;
 
         path.path_segments_mut()
             .map_err(|()| Error::InvalidUrl(self.server.clone()))?
             .push("beacon")
             .push("rewards")
-            .push("attestations");
-
-        self.post(path, &attestations).await?;
+            .push("attestations")
+            .push(&epoch.to_string());
 
-        Ok(())
+        self.post_with_response(path, &validators).await
     }
 
     // GET builder/states/{state_id}/expected_withdrawals
     pub async fn get_expected_withdrawals(
         &self,
         state_id: &StateId,
+    ) -> Result<ExecutionOptimisticFinalizedResponse<Vec<Withdrawal>>, Error> {
+        self.get_expected_withdrawals_for_proposal(state_id, None)
+            .await
+    }
+
+    /// `GET builder/states/{state_id}/expected_withdrawals?proposal_slot`
+    ///
+    /// Returns the withdrawals of a block proposed at `proposal_slot` on top of the state, which
+    /// defaults to the slot after the state.
+    pub async fn get_expected_withdrawals_for_proposal(
+        &self,
+        state_id: &StateId,
+        proposal_slot: Option<Slot>,
     ) -> Result<ExecutionOptimisticFinalizedResponse<Vec<Withdrawal>>, Error> {
         let mut path = self.eth_path(V1)?;
 
@@ -1609,6 +1750,11 @@ impl BeaconNodeHttpClient {
             .push(&state_id.to_string())
             .push("expected_withdrawals");
 
+        if let Some(proposal_slot) = proposal_slot {
+            path.query_pairs_mut()
+                .append_pair("proposal_slot", &proposal_slot.to_string());
+        }
+
         self.get(path).await
     }
 
@@ -1694,6 +1840,23 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET config/spec`, revalidating a previous response with the given `etag`.
+    ///
+    /// Returns `Ok(None)` if the response is unchanged.
+    pub async fn get_config_spec_if_none_match<T: Serialize + DeserializeOwned>(
+        &self,
+        etag: Option<&str>,
+    ) -> Result<Option<(GenericResponse<T>, Option<String>)>, Error> {
+        let mut path = self.eth_path(V1)?;
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("config")
+            .push("spec");
+
+        self.get_if_none_match(path, etag).await
+    }
+
     /// `GET config/deposit_contract`
     pub async fn get_config_deposit_contract(
         &self,
@@ -1903,6 +2066,26 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET debug/fork_choice?ancestor`
+    ///
+    /// Returns only the `ancestor` block and its descendants.
+    pub async fn get_debug_fork_choice_descendants(
+        &self,
+        ancestor: Hash256,
+    ) -> Result<ForkChoice, Error> {
+        let mut path = self.eth_path(V1)?;
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("debug")
+            .push("fork_choice");
+
+        path.query_pairs_mut()
+            .append_pair("ancestor", &format!("{:?}", ancestor));
+
+        self.get(path).await
+    }
+
     /// `GET validator/duties/proposer/{epoch}`
     pub async fn get_validator_duties_proposer(
         &self,
@@ -2136,7 +2319,7 @@ impl BeaconNodeHttpClient {
                     // Parse bytes based on metadata.
                     let response = if metadata.execution_payload_blinded {
                         ProduceBlockV3Response::Blinded(
-                            BlindedBeaconBlock::from_ssz_bytes_for_fork(
+                            BlindedBeaconBlock::from_ssz_bytes_by_fork(
                                 &response_bytes,
                                 metadata.consensus_version,
                             )
@@ -2144,7 +2327,7 @@ impl BeaconNodeHttpClient {
                         )
                     } else {
                         ProduceBlockV3Response::Full(
-                            FullBlockContents::from_ssz_bytes_for_fork(
+                            FullBlockContents::from_ssz_bytes_by_fork(
                                 &response_bytes,
                                 metadata.consensus_version,
                             )
@@ -2548,6 +2731,19 @@ impl BeaconNodeHttpClient {
     pub async fn get_events<E: EthSpec>(
         &self,
         topic: &[EventTopic],
+    ) -> Result<impl Stream<Item = Result<EventKind<E>, Error>>, Error> {
+        self.get_events_filtered(topic, None, None).await
+    }
+
+    /// `GET events?topics,validator_indices,slots`
+    ///
+    /// The `attestation` and `block` events are restricted to those from `validator_indices` and
+    /// for `slots`, if given.
+    pub async fn get_events_filtered<E: EthSpec>(
+        &self,
+        topic: &[EventTopic],
+        validator_indices: Option<&[u64]>,
+        slots: Option<&[Slot]>,
     ) -> Result<impl Stream<Item = Result<EventKind<E>, Error>>, Error> {
         let mut path = self.eth_path(V1)?;
         path.path_segments_mut()
@@ -2561,6 +2757,25 @@ impl BeaconNodeHttpClient {
             .join(",");
         path.query_pairs_mut().append_pair("topics", &topic_string);
 
+        if let Some(validator_indices) = validator_indices {
+            let indices_string = validator_indices
+                .iter()
+                .map(|i| i.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            path.query_pairs_mut()
+                .append_pair("validator_indices", &indices_string);
+        }
+
+        if let Some(slots) = slots {
+            let slots_string = slots
+                .iter()
+                .map(|i| i.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            path.query_pairs_mut().append_pair("slots", &slots_string);
+        }
+
         Ok(self
             .client
             .get(path)
@@ -2,34 +2,66 @@
 
 mod attestation_performance;
 pub mod attestation_rewards;
+mod blinded_blocks;
 mod block_packing_efficiency;
 mod block_rewards;
+mod builder_bids;
+mod builder_circuit_breaker;
+mod builder_registrations;
+mod database_jobs;
+mod events_websocket;
+mod graffiti_analysis;
+mod merkle_proofs;
 mod standard_block_rewards;
+mod subsystem_health;
 mod sync_committee_rewards;
+mod validator_exits;
 
 use crate::{
+    mixin::RequestAccept,
+    ok_or_error,
     types::{
-        DepositTreeSnapshot, Epoch, EthSpec, FinalizedExecutionBlock, GenericResponse, ValidatorId,
+        Accept, BlockId, ChainSpec, ChainSpecDiff, Config, DepositTreeSnapshot, Epoch, EthSpec,
+        EventTopic, ExecutionOptimisticFinalizedResponse, FinalizedExecutionBlock, GenericResponse,
+        ProposerData, PublicKeyBytes, SignedBlindedBeaconBlock, StateId, ValidatorId,
     },
-    BeaconNodeHttpClient, DepositData, Error, Eth1Data, Hash256, Slot,
+    BeaconNodeHttpClient, DepositData, Error, Eth1Data, Hash256, Slot, StatusCode, Url,
 };
 use proto_array::core::ProtoArray;
 use serde::{Deserialize, Serialize};
 use ssz::four_byte_option_impl;
 use ssz_derive::{Decode, Encode};
+use std::collections::BTreeMap;
 use store::{AnchorInfo, BlobInfo, Split, StoreConfig};
 
 pub use attestation_performance::{
-    AttestationPerformance, AttestationPerformanceQuery, AttestationPerformanceStatistics,
+    AttestationPerformance, AttestationPerformanceQuery, AttestationPerformanceRequest,
+    AttestationPerformanceStatistics,
 };
 pub use attestation_rewards::StandardAttestationRewards;
+pub use blinded_blocks::{
+    decode_blinded_block_frames, encode_blinded_block_frame, BlindedBlocksQuery,
+};
 pub use block_packing_efficiency::{
     BlockPackingEfficiency, BlockPackingEfficiencyQuery, ProposerInfo, UniqueAttestation,
 };
 pub use block_rewards::{AttestationRewards, BlockReward, BlockRewardMeta, BlockRewardsQuery};
-pub use lighthouse_network::{types::SyncState, PeerInfo};
+pub use builder_bids::{BuilderBidAudit, BuilderBidDecision, BuilderBidsQuery};
+pub use builder_circuit_breaker::{
+    BuilderCircuitBreaker, ChainHealthStatus, CircuitBreakerCheck, CircuitBreakerCondition,
+};
+pub use builder_registrations::{ForwardedRegistration, RelayRegistration};
+pub use database_jobs::{DatabaseJob, DatabaseJobStatus, DatabaseOperation};
+pub use events_websocket::{EventsWebSocketMessage, EventsWebSocketQuery, EventsWebSocketRequest};
+pub use graffiti_analysis::GraffitiAnalysis;
+pub use lighthouse_network::{types::SyncState, EnrAddresses, PeerInfo};
+pub use merkle_proofs::{MerkleMultiproof, MerkleProofQuery};
 pub use standard_block_rewards::StandardBlockReward;
+pub use subsystem_health::{
+    Subsystem, SubsystemHealth, SubsystemStatus, SubsystemsHealth, SubsystemsHealthQuery,
+};
 pub use sync_committee_rewards::SyncCommitteeReward;
+pub use validator_exits::ExitingValidatorData;
 
 // Define "legacy" implementations of `Option<T>` which use four bytes for encoding the union
 // selector.
@@ -47,6 +79,62 @@ pub struct Peer<E: EthSpec> {
     pub peer_info: PeerInfo<E>,
 }
 
+/// The levels at which Lighthouse logs to the terminal, named as for `--debug-level`.
+///
+/// `targets` sets the level of individual modules (e.g. `validator_client::duties_service`) and
+/// their submodules, overriding `level`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LogFilterData {
+    pub level: String,
+    #[serde(default)]
+    pub targets: BTreeMap<String, String>,
+}
+
+/// Query parameters for the `lighthouse/logging/recent` endpoint.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecentLogsQuery {
+    /// The maximum number of log entries to return, defaulting to all of those kept.
+    pub limit: Option<usize>,
+}
+
+/// The local ENR and the addresses it advertises, returned by `lighthouse/enr`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LocalEnr {
+    /// The base64 encoded ENR.
+    pub enr: String,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub seq: u64,
+    pub addresses: EnrAddresses,
+}
+
+/// Proposer duties returned by `lighthouse/duties/proposer/{epoch}`, which predicts duties further
+/// ahead than the standard `validator/duties/proposer/{epoch}` endpoint.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProposerDutiesLookahead {
+    /// The root of the block which decides the duties, or `None` if that block may not have been
+    /// proposed yet.
+    pub dependent_root: Option<Hash256>,
+    pub certainty: ProposerDutiesCertainty,
+    /// The head block from which the duties were computed.
+    pub head_block_root: Hash256,
+    pub execution_optimistic: bool,
+    pub data: Vec<ProposerData>,
+}
+
+/// How likely proposer duties are to change before their epoch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposerDutiesCertainty {
+    /// The epoch has started, so the duties only change if the dependent block is re-orged.
+    Decided,
+    /// The epoch is next, so its RANDAO seed is known. The duties only change if the effective
+    /// balances or active validators change, or a block from the current epoch is re-orged.
+    SeedKnown,
+    /// The RANDAO seed of the epoch depends on blocks which haven't been proposed yet, so the
+    /// duties are predicted as if no further blocks are proposed and will almost certainly change.
+    Speculative,
+}
+
 /// The results of validators voting during an epoch.
 ///
 /// Provides information about the current and previous epochs.
@@ -365,6 +453,15 @@ pub struct DatabaseInfo {
     pub blob_info: BlobInfo,
 }
 
+/// The result of comparing a config against the spec of a beacon node.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct SpecDiff {
+    /// The fields of the beacon node's spec which differ once the config is applied to it.
+    pub differences: Vec<ChainSpecDiff>,
+    /// Inconsistencies within the spec resulting from the config.
+    pub validation_errors: Vec<String>,
+}
+
 impl BeaconNodeHttpClient {
     /// `GET lighthouse/health`
     pub async fn get_lighthouse_health(&self) -> Result<GenericResponse<Health>, Error> {
@@ -378,6 +475,51 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET lighthouse/health/subsystems`
+    ///
+    /// Returns the health of each subsystem whether or not the node is healthy, since the server
+    /// responds with `206 Partial Content` or `503 Service Unavailable` when it isn't.
+    pub async fn get_lighthouse_health_subsystems(
+        &self,
+        query: &SubsystemsHealthQuery,
+    ) -> Result<GenericResponse<SubsystemsHealth>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("health")
+            .push("subsystems");
+
+        if let Some(require) = &query.require {
+            let require = require
+                .iter()
+                .map(Subsystem::to_string)
+                .collect::<Vec<_>>()
+                .join(",");
+            path.query_pairs_mut().append_pair("require", &require);
+        }
+        if let Some(min_peers) = query.min_peers {
+            path.query_pairs_mut()
+                .append_pair("min_peers", &min_peers.to_string());
+        }
+        if let Some(target_peers) = query.target_peers {
+            path.query_pairs_mut()
+                .append_pair("target_peers", &target_peers.to_string());
+        }
+        if query.strict {
+            path.query_pairs_mut().append_pair("strict", "true");
+        }
+
+        let response = self.client.get(path).send().await?;
+        match response.status() {
+            StatusCode::PARTIAL_CONTENT | StatusCode::SERVICE_UNAVAILABLE => {
+                Ok(response.json().await?)
+            }
+            _ => Ok(ok_or_error(response).await?.json().await?),
+        }
+    }
+
     /// `GET lighthouse/syncing`
     pub async fn get_lighthouse_syncing(&self) -> Result<GenericResponse<SyncState>, Error> {
         let mut path = self.server.full.clone();
@@ -393,11 +535,41 @@ impl BeaconNodeHttpClient {
     /*
      * Note:
      *
-     * The `lighthouse/peers` endpoints do not have functions here. We are yet to implement
+     * The `GET lighthouse/peers` endpoints do not have functions here. We are yet to implement
      * `Deserialize` on the `PeerInfo` struct since it contains use of `Instant`. This could be
      * fairly simply achieved, if desired.
      */
 
+    /// `POST lighthouse/peers/{peer_id}/{action}`
+    async fn post_lighthouse_peers_action(&self, peer_id: &str, action: &str) -> Result<(), Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("peers")
+            .push(peer_id)
+            .push(action);
+
+        self.post(path, &()).await
+    }
+
+    /// `POST lighthouse/peers/{peer_id}/disconnect`
+    pub async fn post_lighthouse_peers_disconnect(&self, peer_id: &str) -> Result<(), Error> {
+        self.post_lighthouse_peers_action(peer_id, "disconnect")
+            .await
+    }
+
+    /// `POST lighthouse/peers/{peer_id}/ban`
+    pub async fn post_lighthouse_peers_ban(&self, peer_id: &str) -> Result<(), Error> {
+        self.post_lighthouse_peers_action(peer_id, "ban").await
+    }
+
+    /// `POST lighthouse/peers/{peer_id}/unban`
+    pub async fn post_lighthouse_peers_unban(&self, peer_id: &str) -> Result<(), Error> {
+        self.post_lighthouse_peers_action(peer_id, "unban").await
+    }
+
     /// `GET lighthouse/proto_array`
     pub async fn get_lighthouse_proto_array(&self) -> Result<GenericResponse<ProtoArray>, Error> {
         let mut path = self.server.full.clone();
@@ -490,6 +662,95 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET lighthouse/duties/proposer/{epoch}`
+    pub async fn get_lighthouse_duties_proposer(
+        &self,
+        epoch: Epoch,
+    ) -> Result<ProposerDutiesLookahead, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("duties")
+            .push("proposer")
+            .push(&epoch.to_string());
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/proof/state/{state_id}?gindex`
+    pub async fn get_lighthouse_proof_state(
+        &self,
+        state_id: StateId,
+        gindices: &[u64],
+    ) -> Result<ExecutionOptimisticFinalizedResponse<MerkleMultiproof>, Error> {
+        self.get_lighthouse_proof("state", &state_id.to_string(), gindices)
+            .await
+    }
+
+    /// `GET lighthouse/proof/block/{block_id}?gindex`
+    pub async fn get_lighthouse_proof_block(
+        &self,
+        block_id: BlockId,
+        gindices: &[u64],
+    ) -> Result<ExecutionOptimisticFinalizedResponse<MerkleMultiproof>, Error> {
+        self.get_lighthouse_proof("block", &block_id.to_string(), gindices)
+            .await
+    }
+
+    async fn get_lighthouse_proof(
+        &self,
+        kind: &str,
+        id: &str,
+        gindices: &[u64],
+    ) -> Result<ExecutionOptimisticFinalizedResponse<MerkleMultiproof>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("proof")
+            .push(kind)
+            .push(id);
+
+        let gindex_string = gindices
+            .iter()
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        path.query_pairs_mut().append_pair("gindex", &gindex_string);
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/debug/blinded_blocks?start_slot,count`
+    pub async fn get_lighthouse_debug_blinded_blocks<E: EthSpec>(
+        &self,
+        start_slot: Slot,
+        count: u64,
+        spec: &ChainSpec,
+    ) -> Result<Vec<SignedBlindedBeaconBlock<E>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("debug")
+            .push("blinded_blocks");
+
+        path.query_pairs_mut()
+            .append_pair("start_slot", &start_slot.to_string())
+            .append_pair("count", &count.to_string());
+
+        let bytes = self
+            .get_response(path, |b| b.accept(Accept::Ssz))
+            .await?
+            .bytes()
+            .await?;
+        decode_blinded_block_frames(&bytes, spec).map_err(Error::InvalidSsz)
+    }
+
     /// `GET lighthouse/staking`
     pub async fn get_lighthouse_staking(&self) -> Result<bool, Error> {
         let mut path = self.server.full.clone();
@@ -502,6 +763,75 @@ impl BeaconNodeHttpClient {
         self.get_opt::<(), _>(path).await.map(|opt| opt.is_some())
     }
 
+    /// `GET lighthouse/enr`
+    pub async fn get_lighthouse_enr(&self) -> Result<GenericResponse<LocalEnr>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("enr");
+
+        self.get(path).await
+    }
+
+    /// `POST lighthouse/enr`
+    pub async fn post_lighthouse_enr(&self, update: &EnrAddresses) -> Result<(), Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("enr");
+
+        self.post(path, update).await
+    }
+
+    /// `GET lighthouse/logging`
+    pub async fn get_lighthouse_logging(&self) -> Result<GenericResponse<LogFilterData>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("logging");
+
+        self.get(path).await
+    }
+
+    /// `POST lighthouse/logging`
+    pub async fn post_lighthouse_logging(&self, filter: &LogFilterData) -> Result<(), Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("logging");
+
+        self.post(path, filter).await
+    }
+
+    /// `GET lighthouse/logging/recent?limit`
+    pub async fn get_lighthouse_logging_recent(
+        &self,
+        limit: Option<usize>,
+    ) -> Result<GenericResponse<Vec<serde_json::Value>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("logging")
+            .push("recent");
+
+        if let Some(limit) = limit {
+            path.query_pairs_mut()
+                .append_pair("limit", &limit.to_string());
+        }
+
+        self.get(path).await
+    }
+
     /// `GET lighthouse/database/info`
     pub async fn get_lighthouse_database_info(&self) -> Result<DatabaseInfo, Error> {
         let mut path = self.server.full.clone();
@@ -528,6 +858,110 @@ impl BeaconNodeHttpClient {
         self.post_with_response(path, &()).await
     }
 
+    /// `POST lighthouse/database/{operation}`
+    pub async fn post_lighthouse_database_maintenance(
+        &self,
+        operation: DatabaseOperation,
+    ) -> Result<GenericResponse<DatabaseJob>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("database")
+            .push(&operation.to_string());
+
+        self.post_with_response(path, &()).await
+    }
+
+    /// `GET lighthouse/database/jobs/{id}`
+    pub async fn get_lighthouse_database_job(
+        &self,
+        id: u64,
+    ) -> Result<GenericResponse<DatabaseJob>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("database")
+            .push("jobs")
+            .push(&id.to_string());
+
+        self.get(path).await
+    }
+
+    /// `POST lighthouse/spec/diff`
+    pub async fn post_lighthouse_spec_diff(
+        &self,
+        config: &Config,
+    ) -> Result<GenericResponse<SpecDiff>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("spec")
+            .push("diff");
+
+        self.post_with_response(path, config).await
+    }
+
+    /// `GET lighthouse/builder/bids?slot`
+    pub async fn get_lighthouse_builder_bids(
+        &self,
+        slot: Option<Slot>,
+    ) -> Result<GenericResponse<Vec<BuilderBidAudit>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("builder")
+            .push("bids");
+
+        if let Some(slot) = slot {
+            path.query_pairs_mut()
+                .append_pair("slot", &slot.to_string());
+        }
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/builder/registrations/{pubkey}`
+    ///
+    /// Returns `None` if no registration has been forwarded for `pubkey`.
+    pub async fn get_lighthouse_builder_registration(
+        &self,
+        pubkey: &PublicKeyBytes,
+    ) -> Result<Option<GenericResponse<ForwardedRegistration>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("builder")
+            .push("registrations")
+            .push(&pubkey.to_string());
+
+        self.get_opt(path).await
+    }
+
+    /// `GET lighthouse/builder/circuit_breaker`
+    pub async fn get_lighthouse_builder_circuit_breaker(
+        &self,
+    ) -> Result<GenericResponse<BuilderCircuitBreaker>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("builder")
+            .push("circuit_breaker");
+
+        self.get(path).await
+    }
+
     ///
     /// Analysis endpoints.
     ///
@@ -596,4 +1030,94 @@ impl BeaconNodeHttpClient {
 
         self.get(path).await
     }
+
+    /// `POST` lighthouse/analysis/attestation_performance
+    pub async fn post_lighthouse_analysis_attestation_performance(
+        &self,
+        request: &AttestationPerformanceRequest,
+    ) -> Result<Vec<AttestationPerformance>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("analysis")
+            .push("attestation_performance");
+
+        self.post_with_response(path, request).await
+    }
+
+    /// `GET lighthouse/analysis/graffiti`
+    pub async fn get_lighthouse_analysis_graffiti(&self) -> Result<GraffitiAnalysis, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("analysis")
+            .push("graffiti");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/validators/slashed`
+    pub async fn get_lighthouse_validators_slashed(
+        &self,
+    ) -> Result<GenericResponse<Vec<ExitingValidatorData>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validators")
+            .push("slashed");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/validators/exiting`
+    pub async fn get_lighthouse_validators_exiting(
+        &self,
+    ) -> Result<GenericResponse<Vec<ExitingValidatorData>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validators")
+            .push("exiting");
+
+        self.get(path).await
+    }
+
+    /// Returns the URL of the `GET lighthouse/events` WebSocket, which subscribes to `topics` when
+    /// it's opened.
+    ///
+    /// Further topics can be subscribed to by sending `EventsWebSocketRequest`s over the socket.
+    pub fn lighthouse_events_websocket_url(&self, topics: &[EventTopic]) -> Result<Url, Error> {
+        let mut path = self.server.full.clone();
+
+        let scheme = if path.scheme() == "https" {
+            "wss"
+        } else {
+            "ws"
+        };
+        path.set_scheme(scheme)
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?;
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("events");
+
+        if !topics.is_empty() {
+            let topics = topics
+                .iter()
+                .map(|topic| topic.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            path.query_pairs_mut().append_pair("topics", &topics);
+        }
+
+        Ok(path)
+    }
 }
@@ -37,3 +37,12 @@ pub struct AttestationPerformanceQuery {
     pub start_epoch: Epoch,
     pub end_epoch: Epoch,
 }
+
+/// Request body for the `POST /lighthouse/analysis/attestation_performance` endpoint.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct AttestationPerformanceRequest {
+    #[serde(with = "serde_utils::quoted_u64_vec")]
+    pub indices: Vec<u64>,
+    pub start_epoch: Epoch,
+    pub end_epoch: Epoch,
+}
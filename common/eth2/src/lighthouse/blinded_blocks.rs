@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+use ssz::{Decode, DecodeError, Encode, BYTES_PER_LENGTH_OFFSET};
+use types::{ChainSpec, EthSpec, SignedBlindedBeaconBlock, Slot};
+
+/// Query parameters for the `/lighthouse/debug/blinded_blocks` endpoint.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct BlindedBlocksQuery {
+    /// The first slot of the range (inclusive).
+    pub start_slot: Slot,
+    /// The number of slots in the range.
+    pub count: u64,
+}
+
+/// Encodes a block for the response of `/lighthouse/debug/blinded_blocks`, as its SSZ encoding
+/// preceded by its length as a little-endian `u32`.
+pub fn encode_blinded_block_frame<E: EthSpec>(
+    block: &SignedBlindedBeaconBlock<E>,
+    bytes: &mut Vec<u8>,
+) {
+    let block_bytes = block.as_ssz_bytes();
+    bytes.extend_from_slice(&(block_bytes.len() as u32).as_ssz_bytes());
+    bytes.extend_from_slice(&block_bytes);
+}
+
+/// Decodes the response of `/lighthouse/debug/blinded_blocks`.
+pub fn decode_blinded_block_frames<E: EthSpec>(
+    mut bytes: &[u8],
+    spec: &ChainSpec,
+) -> Result<Vec<SignedBlindedBeaconBlock<E>>, DecodeError> {
+    let mut blocks = vec![];
+    while !bytes.is_empty() {
+        if bytes.len() < BYTES_PER_LENGTH_OFFSET {
+            return Err(DecodeError::InvalidByteLength {
+                len: bytes.len(),
+                expected: BYTES_PER_LENGTH_OFFSET,
+            });
+        }
+        let (len_bytes, rest) = bytes.split_at(BYTES_PER_LENGTH_OFFSET);
+        let len = u32::from_ssz_bytes(len_bytes)? as usize;
+        if len > rest.len() {
+            return Err(DecodeError::InvalidByteLength {
+                len: rest.len(),
+                expected: len,
+            });
+        }
+        let (block_bytes, rest) = rest.split_at(len);
+        blocks.push(SignedBlindedBeaconBlock::from_ssz_bytes(block_bytes, spec)?);
+        bytes = rest;
+    }
+    Ok(blocks)
+}
@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+use types::{Address, ExecutionBlockHash, PublicKeyBytes, Slot, Uint256};
+
+/// A record of a header received from a builder, and what was done with it.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct BuilderBidAudit {
+    /// The slot that the bid was requested for.
+    pub slot: Slot,
+    /// The (redacted) URL of the relay which provided the bid.
+    pub relay: String,
+    /// The pubkey of the proposer that the bid was requested for.
+    pub proposer_pubkey: PublicKeyBytes,
+    pub parent_hash: ExecutionBlockHash,
+    pub block_hash: ExecutionBlockHash,
+    pub fee_recipient: Address,
+    /// The value of the bid in wei, as claimed by the builder.
+    #[serde(with = "serde_utils::quoted_u256")]
+    pub value: Uint256,
+    /// The value of the bid after applying the builder boost factor.
+    #[serde(with = "serde_utils::quoted_u256")]
+    pub boosted_value: Uint256,
+    /// The value of the locally built payload in wei, if one was available.
+    #[serde(default, with = "quoted_u256_option")]
+    pub local_value: Option<Uint256>,
+    /// The time taken for the relay to respond.
+    pub relay_response_ms: u64,
+    pub decision: BuilderBidDecision,
+}
+
+/// The outcome of comparing a builder bid against the local payload.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "outcome")]
+pub enum BuilderBidDecision {
+    /// The bid was chosen for the block proposal.
+    Accepted,
+    /// The bid failed verification.
+    Invalid { reason: String },
+    /// Another relay offered a more valuable valid bid.
+    Outbid { winning_relay: String },
+    /// The local payload was worth at least as much as the (boosted) bid.
+    LocalMoreProfitable,
+    /// The execution engine asked for the builder payload to be ignored.
+    LocalOverride,
+}
+
+/// Query parameters for the `/lighthouse/builder/bids` endpoint.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct BuilderBidsQuery {
+    /// Only return bids for this slot. All retained bids are returned if omitted.
+    pub slot: Option<Slot>,
+}
+
+/// Serde for an optional `Uint256` as a quoted decimal string.
+mod quoted_u256_option {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use types::Uint256;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(transparent)]
+    struct Quoted(#[serde(with = "serde_utils::quoted_u256")] Uint256);
+
+    pub fn serialize<S: Serializer>(
+        value: &Option<Uint256>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        value.map(Quoted).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Uint256>, D::Error> {
+        Ok(Option::<Quoted>::deserialize(deserializer)?.map(|Quoted(value)| value))
+    }
+}
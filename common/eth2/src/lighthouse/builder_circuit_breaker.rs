@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+
+/// The chain health checks which decide whether the builder API may be used for a proposal.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct BuilderCircuitBreaker {
+    /// The builder API is only used when the status is `healthy`.
+    pub status: ChainHealthStatus,
+    /// Whether `--builder-fallback-disable-checks` is set.
+    pub checks_disabled: bool,
+    /// The conditions are only evaluated after the merge, when the parent block isn't optimistic
+    /// and the checks aren't disabled. Otherwise this is empty.
+    pub conditions: Vec<CircuitBreakerCheck>,
+}
+
+impl BuilderCircuitBreaker {
+    /// Returns the first condition which has tripped the breaker, if any.
+    pub fn tripped_condition(&self) -> Option<CircuitBreakerCondition> {
+        self.conditions
+            .iter()
+            .find(|check| check.tripped)
+            .map(|check| check.condition)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChainHealthStatus {
+    Healthy,
+    /// At least one of the conditions has tripped the breaker.
+    Unhealthy,
+    /// The parent block is optimistic, so the builder cannot safely be used.
+    Optimistic,
+    PreMerge,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitBreakerCondition {
+    /// Skip slots in a row at the head of the chain.
+    Skips,
+    /// Skip slots in the most recent window of slots.
+    SkipsPerEpoch,
+    EpochsSinceFinalization,
+    /// Milliseconds after the start of its slot that the head block was observed.
+    HeadDelay,
+}
+
+/// The current value of a single condition, and the threshold above which it trips the breaker.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct CircuitBreakerCheck {
+    pub condition: CircuitBreakerCondition,
+    /// The current value, if known.
+    pub value: Option<u64>,
+    /// The threshold, or `None` if the condition is disabled.
+    pub threshold: Option<u64>,
+    pub tripped: bool,
+}
+
+impl CircuitBreakerCheck {
+    pub fn new(
+        condition: CircuitBreakerCondition,
+        value: Option<u64>,
+        threshold: Option<u64>,
+    ) -> Self {
+        let tripped =
+            matches!((value, threshold), (Some(value), Some(threshold)) if value > threshold);
+        Self {
+            condition,
+            value,
+            threshold,
+            tripped,
+        }
+    }
+}
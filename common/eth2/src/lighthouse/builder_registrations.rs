@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+use types::SignedValidatorRegistrationData;
+
+/// The most recent registration of a validator forwarded to the builder relays.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ForwardedRegistration {
+    /// The registration exactly as it was forwarded, including the fee recipient and gas limit.
+    pub registration: SignedValidatorRegistrationData,
+    /// The UNIX timestamp in seconds at which the registration was last forwarded.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub forwarded_at: u64,
+    pub relays: Vec<RelayRegistration>,
+}
+
+/// Whether a single relay has accepted a forwarded registration.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct RelayRegistration {
+    /// The (redacted) URL of the relay.
+    pub relay: String,
+    /// `false` if the relay rejected the registration, or has not yet been sent it.
+    pub accepted: bool,
+}
@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// A maintenance operation on the database of a running node, which can otherwise only be done
+/// with `lighthouse db` while the node is stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DatabaseOperation {
+    /// Prune the execution payloads of finalized blocks.
+    PrunePayloads,
+    /// Prune the blobs which are outside the data availability window.
+    PruneBlobs,
+    /// Compact the hot database to free the space of deleted items.
+    Compact,
+}
+
+impl FromStr for DatabaseOperation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "prune_payloads" => Ok(Self::PrunePayloads),
+            "prune_blobs" => Ok(Self::PruneBlobs),
+            "compact" => Ok(Self::Compact),
+            other => Err(format!("unknown database operation {other:?}")),
+        }
+    }
+}
+
+impl fmt::Display for DatabaseOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PrunePayloads => write!(f, "prune_payloads"),
+            Self::PruneBlobs => write!(f, "prune_blobs"),
+            Self::Compact => write!(f, "compact"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DatabaseJobStatus {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+}
+
+/// A database operation started via `POST lighthouse/database/{operation}`, and its progress.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DatabaseJob {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub id: u64,
+    pub operation: DatabaseOperation,
+    pub status: DatabaseJobStatus,
+    /// The error of a failed job.
+    pub error: Option<String>,
+    /// The times at which the job was queued, started and finished, in seconds since the UNIX
+    /// epoch.
+    pub queued_at: u64,
+    pub started_at: Option<u64>,
+    pub finished_at: Option<u64>,
+}
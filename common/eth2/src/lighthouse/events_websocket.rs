@@ -0,0 +1,41 @@
+use crate::types::{option_query_vec, EventTopic};
+use serde::{Deserialize, Serialize};
+use types::Slot;
+
+/// The query of `lighthouse/events`, which streams the topics of `eth/v1/events` over a WebSocket.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EventsWebSocketQuery {
+    /// The topics subscribed to when the connection opens. More can be subscribed to later.
+    #[serde(default, deserialize_with = "option_query_vec")]
+    pub topics: Option<Vec<EventTopic>>,
+    /// Only send `attestation` and `block` events from these validators.
+    #[serde(default, deserialize_with = "option_query_vec")]
+    pub validator_indices: Option<Vec<u64>>,
+    /// Only send `attestation` and `block` events for these slots.
+    #[serde(default, deserialize_with = "option_query_vec")]
+    pub slots: Option<Vec<Slot>>,
+}
+
+/// A text message sent by the client to change the topics subscribed to by a connection.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum EventsWebSocketRequest {
+    Subscribe { topics: Vec<EventTopic> },
+    Unsubscribe { topics: Vec<EventTopic> },
+}
+
+/// A text message sent by the server.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum EventsWebSocketMessage {
+    /// An event of a subscribed topic, which can be parsed with `EventKind::from_event_data`.
+    Event {
+        event: String,
+        data: serde_json::Value,
+    },
+    /// The topics subscribed to, sent when the connection opens and in reply to each request.
+    Subscriptions { topics: Vec<EventTopic> },
+    /// A request which couldn't be parsed, or events dropped because the client fell behind.
+    Error { error: String },
+}
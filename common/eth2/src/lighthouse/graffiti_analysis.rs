@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use types::Slot;
+
+/// The clients detected in the graffiti of recent canonical blocks, returned by
+/// `lighthouse/analysis/graffiti`.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct GraffitiAnalysis {
+    /// The first slot of the window of blocks analysed.
+    pub start_slot: Slot,
+    /// The slot of the head block, which is the last slot of the window.
+    pub end_slot: Slot,
+    /// The number of canonical blocks in the window which were imported by this node.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub blocks: u64,
+    /// The number of blocks tagged with each consensus client code, e.g. `LH`, or `unknown`.
+    pub consensus_clients: BTreeMap<String, u64>,
+    /// The number of blocks tagged with each execution client code, e.g. `GE`, or `unknown`.
+    pub execution_clients: BTreeMap<String, u64>,
+}
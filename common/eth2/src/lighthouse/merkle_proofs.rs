@@ -0,0 +1,30 @@
+use crate::types::option_query_vec;
+use serde::{Deserialize, Serialize};
+use types::Hash256;
+
+/// A Merkle multiproof of nodes of the tree of a state or block, returned by
+/// `lighthouse/proof/state/{state_id}` and `lighthouse/proof/block/{block_id}`.
+///
+/// The proof can be verified against `root` with `merkle_proof::verify_merkle_multiproof`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MerkleMultiproof {
+    /// The hash tree root of the state or block.
+    pub root: Hash256,
+    /// The generalized indices of the proven nodes, as requested.
+    #[serde(with = "serde_utils::quoted_u64_vec")]
+    pub gindices: Vec<u64>,
+    /// The nodes at `gindices`.
+    pub leaves: Vec<Hash256>,
+    /// The generalized indices of the nodes in `proof`, in descending order.
+    #[serde(with = "serde_utils::quoted_u64_vec")]
+    pub helper_indices: Vec<u64>,
+    /// The nodes at `helper_indices`.
+    pub proof: Vec<Hash256>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MerkleProofQuery {
+    #[serde(default, deserialize_with = "option_query_vec")]
+    pub gindex: Option<Vec<u64>>,
+}
@@ -0,0 +1,202 @@
+use crate::types::option_query_vec;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// A subsystem of the beacon node whose health is reported by `lighthouse/health/subsystems`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Subsystem {
+    /// The connection to the execution node.
+    ExecutionLayer,
+    /// Syncing the chain from peers.
+    Sync,
+    /// Reading from the database.
+    Database,
+    /// Processing attestations and blocks to detect slashings.
+    Slasher,
+    /// The number of connected peers.
+    Peers,
+}
+
+impl Subsystem {
+    pub const ALL: [Subsystem; 5] = [
+        Subsystem::ExecutionLayer,
+        Subsystem::Sync,
+        Subsystem::Database,
+        Subsystem::Slasher,
+        Subsystem::Peers,
+    ];
+}
+
+impl FromStr for Subsystem {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "execution_layer" => Ok(Subsystem::ExecutionLayer),
+            "sync" => Ok(Subsystem::Sync),
+            "database" => Ok(Subsystem::Database),
+            "slasher" => Ok(Subsystem::Slasher),
+            "peers" => Ok(Subsystem::Peers),
+            other => Err(format!("unknown subsystem: {other}")),
+        }
+    }
+}
+
+impl fmt::Display for Subsystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Subsystem::ExecutionLayer => write!(f, "execution_layer"),
+            Subsystem::Sync => write!(f, "sync"),
+            Subsystem::Database => write!(f, "database"),
+            Subsystem::Slasher => write!(f, "slasher"),
+            Subsystem::Peers => write!(f, "peers"),
+        }
+    }
+}
+
+/// The status of a subsystem, ordered from the best to the worst.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubsystemStatus {
+    /// The subsystem is not enabled, so it never affects the aggregate status.
+    Disabled,
+    Healthy,
+    /// The subsystem works, but the node cannot yet perform all of its duties, e.g. whilst it is
+    /// syncing.
+    Degraded,
+    Unhealthy,
+}
+
+impl SubsystemStatus {
+    /// Returns the HTTP status code of this status, which matches the standard `node/health`
+    /// endpoint for a degraded node.
+    pub fn status_code(self) -> u16 {
+        match self {
+            SubsystemStatus::Disabled | SubsystemStatus::Healthy => 200,
+            SubsystemStatus::Degraded => 206,
+            SubsystemStatus::Unhealthy => 503,
+        }
+    }
+}
+
+/// The health of a single subsystem.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct SubsystemHealth {
+    pub subsystem: Subsystem,
+    pub status: SubsystemStatus,
+    /// The HTTP status code of `status`.
+    pub code: u16,
+    /// Describes the status, e.g. the number of connected peers.
+    pub detail: String,
+}
+
+impl SubsystemHealth {
+    pub fn new(subsystem: Subsystem, status: SubsystemStatus, detail: impl Into<String>) -> Self {
+        Self {
+            subsystem,
+            status,
+            code: status.status_code(),
+            detail: detail.into(),
+        }
+    }
+}
+
+/// The health of each subsystem of the beacon node, and their aggregate status.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct SubsystemsHealth {
+    /// The worst status of the required subsystems, which is also the HTTP status of the response.
+    pub status: SubsystemStatus,
+    /// The HTTP status code of `status`.
+    pub code: u16,
+    /// The required subsystems, which determine `status`.
+    pub required: Vec<Subsystem>,
+    pub subsystems: Vec<SubsystemHealth>,
+}
+
+impl SubsystemsHealth {
+    /// Aggregates the status of the `required` subsystems. If `strict`, a degraded subsystem makes
+    /// the aggregate status unhealthy.
+    pub fn new(subsystems: Vec<SubsystemHealth>, required: Vec<Subsystem>, strict: bool) -> Self {
+        let status = match subsystems
+            .iter()
+            .filter(|health| required.contains(&health.subsystem))
+            .map(|health| health.status)
+            .max()
+        {
+            None | Some(SubsystemStatus::Disabled) => SubsystemStatus::Healthy,
+            Some(SubsystemStatus::Degraded) if strict => SubsystemStatus::Unhealthy,
+            Some(status) => status,
+        };
+        Self {
+            status,
+            code: status.status_code(),
+            required,
+            subsystems,
+        }
+    }
+}
+
+/// Query parameters for the `lighthouse/health/subsystems` endpoint.
+///
+/// Probes of differing strictness can be made by choosing which subsystems are required, e.g. a
+/// liveness probe might require only the database whilst a readiness probe requires all of them.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SubsystemsHealthQuery {
+    /// The subsystems which determine the aggregate status, defaulting to all of them.
+    #[serde(default, deserialize_with = "option_query_vec")]
+    pub require: Option<Vec<Subsystem>>,
+    /// The number of connected peers below which the peers are unhealthy, defaulting to 1.
+    pub min_peers: Option<usize>,
+    /// The number of connected peers below which the peers are degraded.
+    pub target_peers: Option<usize>,
+    /// Whether a degraded subsystem makes the aggregate status unhealthy.
+    #[serde(default)]
+    pub strict: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn health(subsystem: Subsystem, status: SubsystemStatus) -> SubsystemHealth {
+        SubsystemHealth::new(subsystem, status, "")
+    }
+
+    #[test]
+    fn aggregate_status() {
+        let subsystems = vec![
+            health(Subsystem::ExecutionLayer, SubsystemStatus::Unhealthy),
+            health(Subsystem::Sync, SubsystemStatus::Degraded),
+            health(Subsystem::Database, SubsystemStatus::Healthy),
+            health(Subsystem::Slasher, SubsystemStatus::Disabled),
+        ];
+        let aggregate = |required: &[Subsystem], strict| {
+            SubsystemsHealth::new(subsystems.clone(), required.to_vec(), strict).status
+        };
+
+        assert_eq!(
+            aggregate(&Subsystem::ALL, false),
+            SubsystemStatus::Unhealthy
+        );
+        assert_eq!(
+            aggregate(&[Subsystem::Sync, Subsystem::Database], false),
+            SubsystemStatus::Degraded
+        );
+        assert_eq!(
+            aggregate(&[Subsystem::Sync, Subsystem::Database], true),
+            SubsystemStatus::Unhealthy
+        );
+        assert_eq!(
+            aggregate(&[Subsystem::Database, Subsystem::Slasher], true),
+            SubsystemStatus::Healthy
+        );
+        assert_eq!(
+            aggregate(&[Subsystem::Slasher], true),
+            SubsystemStatus::Healthy
+        );
+        assert_eq!(aggregate(&[], false), SubsystemStatus::Healthy);
+    }
+}
@@ -0,0 +1,17 @@
+use crate::types::ValidatorStatus;
+use serde::{Deserialize, Serialize};
+use types::{Epoch, PublicKeyBytes};
+
+/// A validator which has been slashed or has initiated its exit, returned by
+/// `lighthouse/validators/slashed` and `lighthouse/validators/exiting`.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ExitingValidatorData {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub index: u64,
+    pub pubkey: PublicKeyBytes,
+    /// The status of the validator at the epoch of the head.
+    pub status: ValidatorStatus,
+    pub slashed: bool,
+    pub exit_epoch: Epoch,
+    pub withdrawable_epoch: Epoch,
+}
@@ -412,6 +412,22 @@ impl ValidatorClientHttpClient {
         self.post(path, &request).await
     }
 
+    /// `POST lighthouse/validators/watch_only`
+    pub async fn post_lighthouse_validators_watch_only(
+        &self,
+        request: &[WatchOnlyValidatorRequest],
+    ) -> Result<(), Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validators")
+            .push("watch_only");
+
+        self.post(path, &request).await
+    }
+
     /// `PATCH lighthouse/validators/{validator_pubkey}`
     #[allow(clippy::too_many_arguments)]
     pub async fn patch_lighthouse_validators(
@@ -461,6 +477,129 @@ impl ValidatorClientHttpClient {
         self.delete_with_unsigned_response(path, req).await
     }
 
+    /// `GET lighthouse/doppelganger`
+    pub async fn get_lighthouse_doppelganger(
+        &self,
+    ) -> Result<GenericResponse<Vec<DoppelgangerStatusData>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("doppelganger");
+
+        self.get(path).await
+    }
+
+    /// `POST lighthouse/doppelganger/{validator_pubkey}`
+    pub async fn post_lighthouse_doppelganger(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        action: DoppelgangerOverride,
+    ) -> Result<(), Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("doppelganger")
+            .push(&validator_pubkey.to_string());
+
+        self.post(path, &DoppelgangerOverrideRequest { action })
+            .await
+    }
+
+    fn make_logging_url(&self) -> Result<Url, Error> {
+        let mut url = self.server.full.clone();
+        url.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("logging");
+        Ok(url)
+    }
+
+    /// `GET lighthouse/logging`
+    pub async fn get_lighthouse_logging(&self) -> Result<GenericResponse<LogFilterData>, Error> {
+        let url = self.make_logging_url()?;
+        self.get(url).await
+    }
+
+    /// `POST lighthouse/logging`
+    pub async fn post_lighthouse_logging(&self, req: &LogFilterData) -> Result<(), Error> {
+        let url = self.make_logging_url()?;
+        self.post(url, req).await
+    }
+
+    fn make_handover_url(&self) -> Result<Url, Error> {
+        let mut url = self.server.full.clone();
+        url.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("handover");
+        Ok(url)
+    }
+
+    /// `GET lighthouse/handover`
+    pub async fn get_lighthouse_handover(
+        &self,
+    ) -> Result<GenericResponse<Vec<HandoverStatus>>, Error> {
+        let url = self.make_handover_url()?;
+        self.get(url).await
+    }
+
+    /// `POST lighthouse/handover`
+    pub async fn post_lighthouse_handover(&self, req: &HandoverRequest) -> Result<(), Error> {
+        let url = self.make_handover_url()?;
+        self.post(url, req).await
+    }
+
+    /// `POST lighthouse/handover/confirm`
+    pub async fn post_lighthouse_handover_confirm(
+        &self,
+        req: &ConfirmHandoverRequest,
+    ) -> Result<ConfirmHandoverResponse, Error> {
+        let mut url = self.make_handover_url()?;
+        url.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("confirm");
+        self.post(url, req).await
+    }
+
+    /// `POST lighthouse/slashing_protection/check`
+    pub async fn post_lighthouse_slashing_protection_check(
+        &self,
+        req: &SlashingProtectionCheckRequest,
+    ) -> Result<SlashingProtectionCheckResponse, Error> {
+        let mut url = self.server.full.clone();
+        url.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("slashing_protection")
+            .push("check");
+        self.post(url, req).await
+    }
+
+    /// `GET lighthouse/ui/performance?epochs`
+    pub async fn get_lighthouse_ui_performance(
+        &self,
+        epochs: Option<u64>,
+    ) -> Result<GenericResponse<PerformanceData>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("ui")
+            .push("performance");
+
+        if let Some(epochs) = epochs {
+            path.query_pairs_mut()
+                .append_pair("epochs", &epochs.to_string());
+        }
+
+        self.get(path).await
+    }
+
     fn make_keystores_url(&self) -> Result<Url, Error> {
         let mut url = self.server.full.clone();
         url.path_segments_mut()
@@ -505,6 +644,54 @@ impl ValidatorClientHttpClient {
         Ok(url)
     }
 
+    fn make_scheduled_exit_url(&self, pubkey: &PublicKeyBytes) -> Result<Url, Error> {
+        let mut url = self.server.full.clone();
+        url.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("eth")
+            .push("v1")
+            .push("validator")
+            .push(&pubkey.to_string())
+            .push("exit");
+        Ok(url)
+    }
+
+    fn make_bls_to_execution_change_url(&self, pubkey: &PublicKeyBytes) -> Result<Url, Error> {
+        let mut url = self.server.full.clone();
+        url.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("eth")
+            .push("v1")
+            .push("validator")
+            .push(&pubkey.to_string())
+            .push("bls_to_execution_change");
+        Ok(url)
+    }
+
+    fn make_builder_settings_url(&self, pubkey: &PublicKeyBytes) -> Result<Url, Error> {
+        let mut url = self.server.full.clone();
+        url.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("eth")
+            .push("v1")
+            .push("validator")
+            .push(&pubkey.to_string())
+            .push("builder");
+        Ok(url)
+    }
+
+    fn make_suspension_url(&self, pubkey: &PublicKeyBytes) -> Result<Url, Error> {
+        let mut url = self.server.full.clone();
+        url.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("eth")
+            .push("v1")
+            .push("validator")
+            .push(&pubkey.to_string())
+            .push("suspend");
+        Ok(url)
+    }
+
     fn make_gas_limit_url(&self, pubkey: &PublicKeyBytes) -> Result<Url, Error> {
         let mut url = self.server.full.clone();
         url.path_segments_mut()
@@ -680,6 +867,117 @@ impl ValidatorClientHttpClient {
         let url = self.make_graffiti_url(pubkey)?;
         self.delete(url).await
     }
+
+    /// `GET /eth/v1/validator/{pubkey}/exit`
+    pub async fn get_scheduled_exit(
+        &self,
+        pubkey: &PublicKeyBytes,
+    ) -> Result<GetScheduledExitResponse, Error> {
+        let url = self.make_scheduled_exit_url(pubkey)?;
+        self.get(url)
+            .await
+            .map(|generic: GenericResponse<GetScheduledExitResponse>| generic.data)
+    }
+
+    /// `POST /eth/v1/validator/{pubkey}/exit`
+    pub async fn post_scheduled_exit(
+        &self,
+        pubkey: &PublicKeyBytes,
+        epoch: Epoch,
+    ) -> Result<(), Error> {
+        let url = self.make_scheduled_exit_url(pubkey)?;
+        self.post(url, &ScheduleExitRequest { epoch }).await
+    }
+
+    /// `DELETE /eth/v1/validator/{pubkey}/exit`
+    pub async fn delete_scheduled_exit(&self, pubkey: &PublicKeyBytes) -> Result<(), Error> {
+        let url = self.make_scheduled_exit_url(pubkey)?;
+        self.delete(url).await
+    }
+
+    /// `GET /eth/v1/validator/{pubkey}/bls_to_execution_change`
+    pub async fn get_bls_to_execution_change(
+        &self,
+        pubkey: &PublicKeyBytes,
+    ) -> Result<GetBlsToExecutionChangeResponse, Error> {
+        let url = self.make_bls_to_execution_change_url(pubkey)?;
+        self.get(url)
+            .await
+            .map(|generic: GenericResponse<GetBlsToExecutionChangeResponse>| generic.data)
+    }
+
+    /// `POST /eth/v1/validator/{pubkey}/bls_to_execution_change`
+    pub async fn post_bls_to_execution_change(
+        &self,
+        pubkey: &PublicKeyBytes,
+        to_execution_address: Address,
+    ) -> Result<GenericResponse<SignedBlsToExecutionChange>, Error> {
+        let url = self.make_bls_to_execution_change_url(pubkey)?;
+        self.post(
+            url,
+            &BlsToExecutionChangeRequest {
+                to_execution_address,
+            },
+        )
+        .await
+    }
+
+    /// `DELETE /eth/v1/validator/{pubkey}/bls_to_execution_change`
+    pub async fn delete_bls_to_execution_change(
+        &self,
+        pubkey: &PublicKeyBytes,
+    ) -> Result<(), Error> {
+        let url = self.make_bls_to_execution_change_url(pubkey)?;
+        self.delete(url).await
+    }
+
+    /// `GET /eth/v1/validator/{pubkey}/builder`
+    pub async fn get_builder_settings(
+        &self,
+        pubkey: &PublicKeyBytes,
+    ) -> Result<GetBuilderSettingsResponse, Error> {
+        let url = self.make_builder_settings_url(pubkey)?;
+        self.get(url)
+            .await
+            .map(|generic: GenericResponse<GetBuilderSettingsResponse>| generic.data)
+    }
+
+    /// `POST /eth/v1/validator/{pubkey}/builder`
+    pub async fn post_builder_settings(
+        &self,
+        pubkey: &PublicKeyBytes,
+        req: &UpdateBuilderSettingsRequest,
+    ) -> Result<(), Error> {
+        let url = self.make_builder_settings_url(pubkey)?;
+        self.post(url, req).await
+    }
+
+    /// `DELETE /eth/v1/validator/{pubkey}/builder`
+    pub async fn delete_builder_settings(&self, pubkey: &PublicKeyBytes) -> Result<(), Error> {
+        let url = self.make_builder_settings_url(pubkey)?;
+        self.delete(url).await
+    }
+
+    /// `GET /eth/v1/validator/{pubkey}/suspend`
+    pub async fn get_validator_suspension(
+        &self,
+        pubkey: &PublicKeyBytes,
+    ) -> Result<GetSuspensionResponse, Error> {
+        let url = self.make_suspension_url(pubkey)?;
+        self.get(url)
+            .await
+            .map(|generic: GenericResponse<GetSuspensionResponse>| generic.data)
+    }
+
+    /// `POST /eth/v1/validator/{pubkey}/suspend`
+    pub async fn post_validator_suspension(
+        &self,
+        pubkey: &PublicKeyBytes,
+        suspended: bool,
+    ) -> Result<(), Error> {
+        let url = self.make_suspension_url(pubkey)?;
+        self.post(url, &SetSuspensionRequest { suspended }).await
+    }
 }
 
 /// Returns `Ok(response)` if the response is a `200 OK` response or a
@@ -4,7 +4,7 @@ use graffiti::GraffitiString;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
-pub use crate::lighthouse::Health;
+pub use crate::lighthouse::{Health, LogFilterData};
 pub use crate::lighthouse_vc::std_types::*;
 pub use crate::types::{GenericResponse, VersionData};
 pub use types::*;
@@ -161,6 +161,15 @@ pub struct Web3SignerValidatorRequest {
     pub prefer_builder_proposals: Option<bool>,
 }
 
+/// A validator which is added without any keys, so that its duties and performance are tracked
+/// without ever signing.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WatchOnlyValidatorRequest {
+    pub enable: bool,
+    pub description: String,
+    pub voting_public_key: PublicKey,
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct UpdateFeeRecipientRequest {
     pub ethaddress: Address,
@@ -197,3 +206,213 @@ pub struct SingleExportKeystoresResponse {
 pub struct SetGraffitiRequest {
     pub graffiti: GraffitiString,
 }
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ScheduleExitRequest {
+    pub epoch: Epoch,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ScheduledExitStatus {
+    /// The exit will be signed and broadcast once its epoch is reached.
+    Scheduled,
+    /// The signed exit has been accepted by a beacon node.
+    Submitted,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct GetScheduledExitResponse {
+    pub pubkey: PublicKeyBytes,
+    pub epoch: Epoch,
+    pub status: ScheduledExitStatus,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BlsToExecutionChangeRequest {
+    pub to_execution_address: Address,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BlsToExecutionChangeStatus {
+    /// The signed change will be broadcast once a beacon node accepts it.
+    Queued,
+    /// The signed change has been accepted by a beacon node.
+    Submitted,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct GetBlsToExecutionChangeResponse {
+    pub pubkey: PublicKeyBytes,
+    pub signed_change: SignedBlsToExecutionChange,
+    pub status: BlsToExecutionChangeStatus,
+}
+
+/// Updates the builder settings of a validator. Fields which are `None` are left unchanged.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct UpdateBuilderSettingsRequest {
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub builder_proposals: Option<bool>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub builder_boost_factor: Option<u64>,
+}
+
+/// The builder settings in effect for a validator, after applying the process-wide defaults.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct GetBuilderSettingsResponse {
+    pub pubkey: PublicKeyBytes,
+    pub builder_proposals: bool,
+    pub builder_boost_factor: Option<u64>,
+}
+
+/// Suspends or resumes a validator. A suspended validator remains loaded but performs no duties
+/// and signs no messages.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SetSuspensionRequest {
+    pub suspended: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct GetSuspensionResponse {
+    pub pubkey: PublicKeyBytes,
+    pub suspended: bool,
+}
+
+/// Requests that the validator client stops signing blocks and attestations for `pubkeys` from
+/// `epoch` onwards, so that they can be handed over to another validator client.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HandoverRequest {
+    pub epoch: Epoch,
+    pub pubkeys: Vec<PublicKeyBytes>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HandoverStatus {
+    pub pubkey: PublicKeyBytes,
+    pub epoch: Epoch,
+    /// Whether the handover epoch has been reached, after which the handover can be confirmed.
+    pub complete: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ConfirmHandoverRequest {
+    pub pubkeys: Vec<PublicKeyBytes>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ConfirmHandoverResponse {
+    #[serde(with = "serde_utils::json_str")]
+    pub slashing_protection: Interchange,
+}
+
+/// A block header or attestation data to check against the slashing protection database of
+/// `pubkey`, without signing it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SlashingProtectionCheckRequest {
+    pub pubkey: PublicKeyBytes,
+    #[serde(flatten)]
+    pub message: SlashingProtectionCheckMessage,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SlashingProtectionCheckMessage {
+    BlockHeader(BeaconBlockHeader),
+    AttestationData(AttestationData),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SlashingProtectionVerdict {
+    /// The message is safe to sign.
+    Safe,
+    /// The exact same message has already been signed, so it is safe to broadcast again.
+    SameData,
+    /// The message must not be signed.
+    NotSafe,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SlashingProtectionCheckResponse {
+    pub verdict: SlashingProtectionVerdict,
+    /// Why the message must not be signed, if it is `NotSafe`.
+    pub reason: Option<String>,
+}
+
+/// The doppelganger detection state of a single validator.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DoppelgangerStatusData {
+    pub pubkey: PublicKeyBytes,
+    /// Whether doppelganger protection permits the validator to sign.
+    pub signing_enabled: bool,
+    /// Whether a doppelganger has been detected for any local validator.
+    pub detected: bool,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub remaining_epochs: u64,
+    pub next_check_epoch: Epoch,
+    pub latest_observed_epoch: Option<Epoch>,
+    pub latest_live_epoch: Option<Epoch>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DoppelgangerOverride {
+    /// Allow the validator to sign without waiting for detection to complete.
+    Release,
+    /// Restart doppelganger detection for the validator.
+    Rearm,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DoppelgangerOverrideRequest {
+    pub action: DoppelgangerOverride,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct PerformanceQuery {
+    /// Only aggregate the most recent `epochs` of the tracked epochs.
+    pub epochs: Option<u64>,
+}
+
+/// The performance of a single validator, aggregated over a range of epochs.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ValidatorPerformanceData {
+    pub pubkey: PublicKeyBytes,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub validator_index: u64,
+    /// The number of epochs in which the validator was expected to attest.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub attestations_expected: u64,
+    /// The number of epochs in which an attestation of the validator was rewarded.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub attestations_included: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub head_hits: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub target_hits: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub source_hits: u64,
+    /// The mean number of slots between an attestation's slot and its first inclusion in a block.
+    pub average_inclusion_distance: Option<f64>,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub blocks_proposed: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub blocks_missed: u64,
+    /// Net attestation rewards in gwei, after penalties.
+    #[serde(with = "serde_utils::quoted_i64")]
+    pub attestation_rewards: i64,
+    /// Consensus layer rewards of the proposed blocks in gwei.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub proposal_rewards: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PerformanceData {
+    /// The first and last epoch covered, or `None` if no epoch has been tracked yet.
+    pub start_epoch: Option<Epoch>,
+    pub end_epoch: Option<Epoch>,
+    pub validators: Vec<ValidatorPerformanceData>,
+}
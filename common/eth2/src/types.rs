@@ -2,8 +2,9 @@
 //! required for the HTTP API.
 
 use crate::{
-    Error as ServerError, CONSENSUS_BLOCK_VALUE_HEADER, CONSENSUS_VERSION_HEADER,
-    EXECUTION_PAYLOAD_BLINDED_HEADER, EXECUTION_PAYLOAD_VALUE_HEADER,
+    Error as ServerError, BUILDER_BID_VALUE_HEADER, CONSENSUS_BLOCK_VALUE_HEADER,
+    CONSENSUS_VERSION_HEADER, EXECUTION_PAYLOAD_BLINDED_HEADER, EXECUTION_PAYLOAD_VALUE_HEADER,
+    LOCAL_PAYLOAD_VALUE_HEADER,
 };
 use lighthouse_network::{ConnectionDirection, Enr, Multiaddr, PeerConnectionStatus};
 use mediatype::{names, MediaType, MediaTypeList};
@@ -488,6 +489,12 @@ pub struct RandaoQuery {
     pub epoch: Option<Epoch>,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct LightClientUpdatesQuery {
+    pub start_period: u64,
+    pub count: u64,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct AttestationPoolQuery {
     pub slot: Option<Slot>,
@@ -501,6 +508,10 @@ pub struct ValidatorsQuery {
     pub id: Option<Vec<ValidatorId>>,
     #[serde(default, deserialize_with = "option_query_vec")]
     pub status: Option<Vec<ValidatorStatus>>,
+    /// The number of matching validators to skip, in order of index.
+    pub offset: Option<u64>,
+    /// The maximum number of validators to return.
+    pub limit: Option<u64>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -510,6 +521,12 @@ pub struct ValidatorsRequestBody {
     pub ids: Option<Vec<ValidatorId>>,
     #[serde(default)]
     pub statuses: Option<Vec<ValidatorStatus>>,
+    /// The number of matching validators to skip, in order of index.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u64>,
+    /// The maximum number of validators to return.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -625,7 +642,7 @@ where
     Ok(Vec::from(QueryVec::from(vec)))
 }
 
-fn option_query_vec<'de, D, T>(deserializer: D) -> Result<Option<Vec<T>>, D::Error>
+pub(crate) fn option_query_vec<'de, D, T>(deserializer: D) -> Result<Option<Vec<T>>, D::Error>
 where
     D: serde::Deserializer<'de>,
     T: FromStr,
@@ -689,6 +706,16 @@ pub struct BlobIndicesQuery {
     pub indices: Option<Vec<u64>>,
 }
 
+/// A request for the blob sidecars of one block, in the body of `POST beacon/blob_sidecars`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BlobSidecarsByRootRequest {
+    pub block_root: Hash256,
+    /// Only return the blob sidecars with these indices, or all of them if `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub indices: Option<Vec<serde_utils::quoted_u64::Quoted<u64>>>,
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 #[serde(transparent)]
 pub struct ValidatorIndexData(#[serde(with = "serde_utils::quoted_u64_vec")] pub Vec<u64>);
@@ -1150,6 +1177,11 @@ impl<E: EthSpec> EventKind<E> {
             })?
             .trim_start_matches("data:");
 
+        Self::from_event_data(event, data)
+    }
+
+    /// Parses an event from its topic name and its JSON `data`.
+    pub fn from_event_data(event: &str, data: &str) -> Result<Self, ServerError> {
         match event {
             "attestation" => Ok(EventKind::Attestation(serde_json::from_str(data).map_err(
                 |e| ServerError::InvalidServerSentEvent(format!("Attestation: {:?}", e)),
@@ -1239,9 +1271,15 @@ impl<E: EthSpec> EventKind<E> {
 pub struct EventQuery {
     #[serde(deserialize_with = "query_vec")]
     pub topics: Vec<EventTopic>,
+    /// Only send `attestation` and `block` events from these validators.
+    #[serde(default, deserialize_with = "option_query_vec")]
+    pub validator_indices: Option<Vec<u64>>,
+    /// Only send `attestation` and `block` events for these slots.
+    #[serde(default, deserialize_with = "option_query_vec")]
+    pub slots: Option<Vec<Slot>>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum EventTopic {
     Head,
@@ -1418,6 +1456,9 @@ pub struct LivenessResponseData {
 pub struct ForkChoice {
     pub justified_checkpoint: Checkpoint,
     pub finalized_checkpoint: Checkpoint,
+    /// Lighthouse extension: the block receiving proposer boost, or zero if there is none.
+    #[serde(default)]
+    pub proposer_boost_root: Hash256,
     pub fork_choice_nodes: Vec<ForkChoiceNode>,
 }
 
@@ -1428,12 +1469,28 @@ pub struct ForkChoiceNode {
     pub parent_root: Option<Hash256>,
     pub justified_epoch: Epoch,
     pub finalized_epoch: Epoch,
+    /// Lighthouse extension: the root of the node's justified checkpoint.
+    #[serde(default)]
+    pub justified_root: Hash256,
+    /// Lighthouse extension: the root of the node's finalized checkpoint.
+    #[serde(default)]
+    pub finalized_root: Hash256,
     #[serde(with = "serde_utils::quoted_u64")]
     pub weight: u64,
+    /// Lighthouse extension: the weight of the attestations for this node which are queued until
+    /// the next slot, and not yet included in `weight`.
+    #[serde(default, with = "serde_utils::quoted_u64")]
+    pub queued_weight: u64,
     pub validity: Option<String>,
     pub execution_block_hash: Option<Hash256>,
 }
 
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ForkChoiceQuery {
+    /// Only include this block and its descendants.
+    pub ancestor: Option<Hash256>,
+}
+
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum BroadcastValidation {
@@ -1550,7 +1607,7 @@ mod tests {
         .expect("should convert into signed block contents");
 
         let decoded: PublishBlockRequest<E> =
-            PublishBlockRequest::from_ssz_bytes(&block.as_ssz_bytes(), ForkName::Capella)
+            PublishBlockRequest::from_ssz_bytes_by_fork(&block.as_ssz_bytes(), ForkName::Capella)
                 .expect("should decode Block");
         assert!(matches!(decoded, PublishBlockRequest::Block(_)));
     }
@@ -1569,7 +1626,7 @@ mod tests {
         let signed_block_contents =
             PublishBlockRequest::new(Arc::new(block), Some((kzg_proofs, blobs)));
 
-        let decoded: PublishBlockRequest<E> = PublishBlockRequest::from_ssz_bytes(
+        let decoded: PublishBlockRequest<E> = PublishBlockRequest::from_ssz_bytes_by_fork(
             &signed_block_contents.as_ssz_bytes(),
             ForkName::Deneb,
         )
@@ -1624,6 +1681,47 @@ pub struct ProduceBlockV3Metadata {
     pub execution_payload_value: Uint256,
     #[serde(with = "serde_utils::u256_dec")]
     pub consensus_block_value: Uint256,
+    /// The value of the locally built payload, if one was built.
+    ///
+    /// This is a Lighthouse extension, which is absent from the responses of other clients.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "u256_dec_option"
+    )]
+    pub local_payload_value: Option<Uint256>,
+    /// The value of the best builder bid, if any builder returned a valid bid.
+    ///
+    /// This is a Lighthouse extension, which is absent from the responses of other clients.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "u256_dec_option"
+    )]
+    pub builder_bid_value: Option<Uint256>,
+}
+
+/// Serde for an optional `Uint256` as a decimal string.
+mod u256_dec_option {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use types::Uint256;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(transparent)]
+    struct Dec(#[serde(with = "serde_utils::u256_dec")] Uint256);
+
+    pub fn serialize<S: Serializer>(
+        value: &Option<Uint256>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        value.map(Dec).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Uint256>, D::Error> {
+        Ok(Option::<Dec>::deserialize(deserializer)?.map(|Dec(value)| value))
+    }
 }
 
 impl<E: EthSpec> FullBlockContents<E> {
@@ -1649,36 +1747,7 @@ impl<E: EthSpec> FullBlockContents<E> {
             })?;
         let slot = Slot::from_ssz_bytes(slot_bytes)?;
         let fork_at_slot = spec.fork_name_at_slot::<E>(slot);
-        Self::from_ssz_bytes_for_fork(bytes, fork_at_slot)
-    }
-
-    /// SSZ decode with fork variant passed in explicitly.
-    pub fn from_ssz_bytes_for_fork(
-        bytes: &[u8],
-        fork_name: ForkName,
-    ) -> Result<Self, ssz::DecodeError> {
-        match fork_name {
-            ForkName::Base | ForkName::Altair | ForkName::Bellatrix | ForkName::Capella => {
-                BeaconBlock::from_ssz_bytes_for_fork(bytes, fork_name)
-                    .map(|block| FullBlockContents::Block(block))
-            }
-            ForkName::Deneb | ForkName::Electra => {
-                let mut builder = ssz::SszDecoderBuilder::new(bytes);
-
-                builder.register_anonymous_variable_length_item()?;
-                builder.register_type::<KzgProofs<E>>()?;
-                builder.register_type::<BlobsList<E>>()?;
-
-                let mut decoder = builder.build()?;
-                let block = decoder.decode_next_with(|bytes| {
-                    BeaconBlock::from_ssz_bytes_for_fork(bytes, fork_name)
-                })?;
-                let kzg_proofs = decoder.decode_next()?;
-                let blobs = decoder.decode_next()?;
-
-                Ok(FullBlockContents::new(block, Some((kzg_proofs, blobs))))
-            }
-        }
+        Self::from_ssz_bytes_by_fork(bytes, fork_at_slot)
     }
 
     pub fn block(&self) -> &BeaconBlock<E> {
@@ -1712,6 +1781,33 @@ impl<E: EthSpec> FullBlockContents<E> {
     }
 }
 
+impl<E: EthSpec> ForkVersionDecode for FullBlockContents<E> {
+    fn from_ssz_bytes_by_fork(bytes: &[u8], fork_name: ForkName) -> Result<Self, DecodeError> {
+        match fork_name {
+            ForkName::Base | ForkName::Altair | ForkName::Bellatrix | ForkName::Capella => {
+                BeaconBlock::from_ssz_bytes_by_fork(bytes, fork_name)
+                    .map(|block| FullBlockContents::Block(block))
+            }
+            ForkName::Deneb | ForkName::Electra => {
+                let mut builder = ssz::SszDecoderBuilder::new(bytes);
+
+                builder.register_anonymous_variable_length_item()?;
+                builder.register_type::<KzgProofs<E>>()?;
+                builder.register_type::<BlobsList<E>>()?;
+
+                let mut decoder = builder.build()?;
+                let block = decoder.decode_next_with(|bytes| {
+                    BeaconBlock::from_ssz_bytes_by_fork(bytes, fork_name)
+                })?;
+                let kzg_proofs = decoder.decode_next()?;
+                let blobs = decoder.decode_next()?;
+
+                Ok(FullBlockContents::new(block, Some((kzg_proofs, blobs))))
+            }
+        }
+    }
+}
+
 impl<E: EthSpec> ForkVersionDeserialize for FullBlockContents<E> {
     fn deserialize_by_fork<'de, D: serde::Deserializer<'de>>(
         value: serde_json::value::Value,
@@ -1744,6 +1840,22 @@ pub type SignedBlockContentsTuple<E> = (
     Option<(KzgProofs<E>, BlobsList<E>)>,
 );
 
+fn parse_optional_header<T>(
+    headers: &HeaderMap,
+    header_name: &str,
+    parse: impl FnOnce(&str) -> Result<T, String>,
+) -> Result<Option<T>, String> {
+    headers
+        .get(header_name)
+        .map(|value| {
+            let str_value = value
+                .to_str()
+                .map_err(|e| format!("invalid value in {header_name}: {e}"))?;
+            parse(str_value)
+        })
+        .transpose()
+}
+
 fn parse_required_header<T>(
     headers: &HeaderMap,
     header_name: &str,
@@ -1781,11 +1893,23 @@ impl TryFrom<&HeaderMap> for ProduceBlockV3Metadata {
                     .map_err(|e| format!("invalid {CONSENSUS_BLOCK_VALUE_HEADER}: {e:?}"))
             })?;
 
+        let local_payload_value =
+            parse_optional_header(headers, LOCAL_PAYLOAD_VALUE_HEADER, |s| {
+                Uint256::from_dec_str(s)
+                    .map_err(|e| format!("invalid {LOCAL_PAYLOAD_VALUE_HEADER}: {e:?}"))
+            })?;
+        let builder_bid_value = parse_optional_header(headers, BUILDER_BID_VALUE_HEADER, |s| {
+            Uint256::from_dec_str(s)
+                .map_err(|e| format!("invalid {BUILDER_BID_VALUE_HEADER}: {e:?}"))
+        })?;
+
         Ok(ProduceBlockV3Metadata {
             consensus_version,
             execution_payload_blinded,
             execution_payload_value,
             consensus_block_value,
+            local_payload_value,
+            builder_bid_value,
         })
     }
 }
@@ -1815,33 +1939,6 @@ impl<E: EthSpec> PublishBlockRequest<E> {
         }
     }
 
-    /// SSZ decode with fork variant determined by `fork_name`.
-    pub fn from_ssz_bytes(bytes: &[u8], fork_name: ForkName) -> Result<Self, ssz::DecodeError> {
-        match fork_name {
-            ForkName::Base | ForkName::Altair | ForkName::Bellatrix | ForkName::Capella => {
-                SignedBeaconBlock::from_ssz_bytes_for_fork(bytes, fork_name)
-                    .map(|block| PublishBlockRequest::Block(Arc::new(block)))
-            }
-            ForkName::Deneb | ForkName::Electra => {
-                let mut builder = ssz::SszDecoderBuilder::new(bytes);
-                builder.register_anonymous_variable_length_item()?;
-                builder.register_type::<KzgProofs<E>>()?;
-                builder.register_type::<BlobsList<E>>()?;
-
-                let mut decoder = builder.build()?;
-                let block = decoder.decode_next_with(|bytes| {
-                    SignedBeaconBlock::from_ssz_bytes_for_fork(bytes, fork_name)
-                })?;
-                let kzg_proofs = decoder.decode_next()?;
-                let blobs = decoder.decode_next()?;
-                Ok(PublishBlockRequest::new(
-                    Arc::new(block),
-                    Some((kzg_proofs, blobs)),
-                ))
-            }
-        }
-    }
-
     pub fn signed_block(&self) -> &Arc<SignedBeaconBlock<E>> {
         match self {
             PublishBlockRequest::BlockContents(block_and_sidecars) => {
@@ -1898,6 +1995,34 @@ pub fn into_full_block_and_blobs<E: EthSpec>(
     }
 }
 
+impl<E: EthSpec> ForkVersionDecode for PublishBlockRequest<E> {
+    fn from_ssz_bytes_by_fork(bytes: &[u8], fork_name: ForkName) -> Result<Self, DecodeError> {
+        match fork_name {
+            ForkName::Base | ForkName::Altair | ForkName::Bellatrix | ForkName::Capella => {
+                SignedBeaconBlock::from_ssz_bytes_by_fork(bytes, fork_name)
+                    .map(|block| PublishBlockRequest::Block(Arc::new(block)))
+            }
+            ForkName::Deneb | ForkName::Electra => {
+                let mut builder = ssz::SszDecoderBuilder::new(bytes);
+                builder.register_anonymous_variable_length_item()?;
+                builder.register_type::<KzgProofs<E>>()?;
+                builder.register_type::<BlobsList<E>>()?;
+
+                let mut decoder = builder.build()?;
+                let block = decoder.decode_next_with(|bytes| {
+                    SignedBeaconBlock::from_ssz_bytes_by_fork(bytes, fork_name)
+                })?;
+                let kzg_proofs = decoder.decode_next()?;
+                let blobs = decoder.decode_next()?;
+                Ok(PublishBlockRequest::new(
+                    Arc::new(block),
+                    Some((kzg_proofs, blobs)),
+                ))
+            }
+        }
+    }
+}
+
 impl<E: EthSpec> TryFrom<Arc<SignedBeaconBlock<E>>> for PublishBlockRequest<E> {
     type Error = &'static str;
     fn try_from(block: Arc<SignedBeaconBlock<E>>) -> Result<Self, Self::Error> {
@@ -2007,6 +2132,36 @@ impl<E: EthSpec> FullPayloadContents<E> {
     }
 }
 
+impl<E: EthSpec> ForkVersionDecode for FullPayloadContents<E> {
+    fn from_ssz_bytes_by_fork(bytes: &[u8], fork_name: ForkName) -> Result<Self, DecodeError> {
+        match fork_name {
+            ForkName::Bellatrix | ForkName::Capella => {
+                ExecutionPayload::from_ssz_bytes_by_fork(bytes, fork_name).map(Self::Payload)
+            }
+            ForkName::Deneb | ForkName::Electra => {
+                let mut builder = ssz::SszDecoderBuilder::new(bytes);
+
+                builder.register_anonymous_variable_length_item()?;
+                builder.register_type::<BlobsBundle<E>>()?;
+
+                let mut decoder = builder.build()?;
+                let execution_payload = decoder.decode_next_with(|bytes| {
+                    ExecutionPayload::from_ssz_bytes_by_fork(bytes, fork_name)
+                })?;
+                let blobs_bundle = decoder.decode_next()?;
+
+                Ok(Self::PayloadAndBlobs(ExecutionPayloadAndBlobs {
+                    execution_payload,
+                    blobs_bundle,
+                }))
+            }
+            ForkName::Base | ForkName::Altair => Err(ssz::DecodeError::BytesInvalid(format!(
+                "FullPayloadContents decoding for {fork_name} not implemented"
+            ))),
+        }
+    }
+}
+
 impl<E: EthSpec> ForkVersionDeserialize for FullPayloadContents<E> {
     fn deserialize_by_fork<'de, D: Deserializer<'de>>(
         value: Value,
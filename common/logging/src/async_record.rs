@@ -19,6 +19,8 @@ thread_local! {
 /// Serialized record.
 #[derive(Clone)]
 pub struct AsyncRecord {
+    /// The local time at which the record was logged.
+    time: String,
     msg: String,
     level: Level,
     location: Box<slog::RecordLocation>,
@@ -37,6 +39,7 @@ impl AsyncRecord {
             .expect("`ToSendSerializer` can't fail");
 
         AsyncRecord {
+            time: chrono::Local::now().format("%b %e %T").to_string(),
             msg: fmt::format(*record.msg()),
             level: record.level(),
             location: Box::new(*record.location()),
@@ -156,9 +159,6 @@ impl Serialize for AsyncRecord {
     where
         S: serde::Serializer,
     {
-        // Get the current time
-        let dt = chrono::Local::now().format("%b %e %T").to_string();
-
         let rs = RecordStatic {
             location: &self.location,
             level: self.level,
@@ -167,7 +167,7 @@ impl Serialize for AsyncRecord {
         let mut map_serializer = SerdeSerializer::new(serializer)?;
 
         // Serialize the time and log level first
-        map_serializer.serialize_entry("time", &dt)?;
+        map_serializer.serialize_entry("time", &self.time)?;
         map_serializer.serialize_entry("level", self.level.as_short_str())?;
 
         let kv = self.kv.lock();
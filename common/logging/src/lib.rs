@@ -15,10 +15,16 @@ use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 pub const MAX_MESSAGE_WIDTH: usize = 40;
 
 pub mod async_record;
+mod recent_logs;
+mod runtime_log_filter;
 mod sse_logging_components;
 mod tracing_logging_layer;
 mod tracing_metrics_layer;
 
+pub use recent_logs::RecentLogs;
+pub use runtime_log_filter::{
+    level_name, parse_level, LogFilter, LogFilterHandle, RuntimeLevelFilter,
+};
 pub use sse_logging_components::SSELoggingComponents;
 pub use tracing_metrics_layer::MetricsLayer;
 
@@ -0,0 +1,80 @@
+//! This module provides an implementation of `slog::Drain` which keeps the most recent records in
+//! memory, so that they can be fetched via the HTTP API, e.g. to attach to a support request.
+
+use crate::async_record::AsyncRecord;
+use parking_lot::Mutex;
+use slog::{Drain, OwnedKVList, Record};
+use std::collections::VecDeque;
+use std::panic::AssertUnwindSafe;
+use std::sync::Arc;
+
+/// Keeps the most recent records logged to it, up to a fixed number.
+#[derive(Clone)]
+pub struct RecentLogs {
+    capacity: usize,
+    records: Arc<AssertUnwindSafe<Mutex<VecDeque<AsyncRecord>>>>,
+}
+
+impl RecentLogs {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            records: Arc::new(AssertUnwindSafe(Mutex::new(VecDeque::with_capacity(
+                capacity,
+            )))),
+        }
+    }
+
+    /// Returns up to `limit` of the most recent records as JSON, from the oldest to the newest.
+    pub fn tail(&self, limit: Option<usize>) -> Result<Vec<serde_json::Value>, String> {
+        let records = self.records.lock();
+        let skip = limit.map_or(0, |limit| records.len().saturating_sub(limit));
+        records
+            .iter()
+            .skip(skip)
+            .map(|record| serde_json::to_value(record).map_err(|e| format!("{:?}", e)))
+            .collect()
+    }
+}
+
+impl Drain for RecentLogs {
+    type Ok = ();
+    type Err = slog::Never;
+
+    fn log(&self, record: &Record, logger_values: &OwnedKVList) -> Result<Self::Ok, Self::Err> {
+        if self.capacity == 0 {
+            return Ok(());
+        }
+        let record = AsyncRecord::from(record, logger_values);
+        let mut records = self.records.lock();
+        if records.len() >= self.capacity {
+            records.pop_front();
+        }
+        records.push_back(record);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use slog::{info, o, Logger};
+
+    #[test]
+    fn keeps_most_recent_records() {
+        let recent_logs = RecentLogs::new(2);
+        let log = Logger::root(recent_logs.clone(), o!());
+        for i in 0..3 {
+            info!(log, "Message"; "i" => i);
+        }
+
+        let tail = recent_logs.tail(None).unwrap();
+        assert_eq!(tail.len(), 2);
+        assert_eq!(tail[0]["i"], 1);
+        assert_eq!(tail[1]["i"], 2);
+
+        let tail = recent_logs.tail(Some(1)).unwrap();
+        assert_eq!(tail.len(), 1);
+        assert_eq!(tail[0]["i"], 2);
+    }
+}
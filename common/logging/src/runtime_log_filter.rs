@@ -0,0 +1,185 @@
+//! This module provides an implementation of `slog::Drain` which filters records by level, where
+//! the level can be changed while Lighthouse is running, both globally and for individual modules.
+
+use parking_lot::RwLock;
+use slog::{Drain, Level, OwnedKVList, Record};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Parses a level as accepted by the `--debug-level` flag.
+pub fn parse_level(level: &str) -> Result<Level, String> {
+    match level {
+        "info" => Ok(Level::Info),
+        "debug" => Ok(Level::Debug),
+        "trace" => Ok(Level::Trace),
+        "warn" => Ok(Level::Warning),
+        "error" => Ok(Level::Error),
+        "crit" => Ok(Level::Critical),
+        unknown => Err(format!("Unknown log level: {}", unknown)),
+    }
+}
+
+/// Returns the name of `level` as accepted by `parse_level`.
+pub fn level_name(level: Level) -> &'static str {
+    match level {
+        Level::Info => "info",
+        Level::Debug => "debug",
+        Level::Trace => "trace",
+        Level::Warning => "warn",
+        Level::Error => "error",
+        Level::Critical => "crit",
+    }
+}
+
+/// The levels at which records are logged.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogFilter {
+    /// The level of records from modules without a target.
+    pub level: Level,
+    /// The levels of records from the modules with the given paths (e.g.
+    /// `validator_client::duties_service`) and their submodules. The longest matching path takes
+    /// precedence.
+    pub targets: BTreeMap<String, Level>,
+}
+
+impl LogFilter {
+    /// Returns the level at which records from `module` are logged.
+    pub fn level_for(&self, module: &str) -> Level {
+        self.targets
+            .iter()
+            .filter(|(target, _)| {
+                module
+                    .strip_prefix(target.as_str())
+                    .map_or(false, |rest| rest.is_empty() || rest.starts_with("::"))
+            })
+            .max_by_key(|(target, _)| target.len())
+            .map_or(self.level, |(_, level)| *level)
+    }
+
+    /// Returns the most verbose level of any module.
+    fn max_level(&self) -> Level {
+        self.targets
+            .values()
+            .copied()
+            .fold(
+                self.level,
+                |max, level| {
+                    if level.is_at_least(max) {
+                        max
+                    } else {
+                        level
+                    }
+                },
+            )
+    }
+}
+
+/// A handle to the filter of a `RuntimeLevelFilter`, which may be cloned and used to change the
+/// filter from elsewhere.
+#[derive(Debug, Clone)]
+pub struct LogFilterHandle {
+    filter: Arc<RwLock<LogFilter>>,
+}
+
+impl LogFilterHandle {
+    /// Creates a handle which logs records from all modules at `level`.
+    pub fn new(level: Level) -> Self {
+        Self {
+            filter: Arc::new(RwLock::new(LogFilter {
+                level,
+                targets: BTreeMap::new(),
+            })),
+        }
+    }
+
+    /// Returns the current filter.
+    pub fn get(&self) -> LogFilter {
+        self.filter.read().clone()
+    }
+
+    /// Replaces the current filter.
+    pub fn set(&self, filter: LogFilter) {
+        *self.filter.write() = filter;
+    }
+}
+
+/// Filters records by the level of their module in a `LogFilterHandle`.
+pub struct RuntimeLevelFilter<D: Drain> {
+    drain: D,
+    filter: LogFilterHandle,
+}
+
+impl<D: Drain> RuntimeLevelFilter<D> {
+    pub fn new(drain: D, filter: LogFilterHandle) -> Self {
+        Self { drain, filter }
+    }
+}
+
+impl<D: Drain> Drain for RuntimeLevelFilter<D> {
+    type Ok = Option<D::Ok>;
+    type Err = D::Err;
+
+    fn log(&self, record: &Record, values: &OwnedKVList) -> Result<Self::Ok, Self::Err> {
+        let level = self.filter.filter.read().level_for(record.module());
+        if record.level().is_at_least(level) {
+            self.drain.log(record, values).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn is_enabled(&self, level: Level) -> bool {
+        level.is_at_least(self.filter.filter.read().max_level()) && self.drain.is_enabled(level)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn longest_target_takes_precedence() {
+        let filter = LogFilter {
+            level: Level::Info,
+            targets: [
+                ("validator_client".to_string(), Level::Warning),
+                ("validator_client::duties_service".to_string(), Level::Debug),
+            ]
+            .into_iter()
+            .collect(),
+        };
+
+        assert_eq!(filter.level_for("beacon_node"), Level::Info);
+        assert_eq!(filter.level_for("validator_client"), Level::Warning);
+        assert_eq!(
+            filter.level_for("validator_client::block_service"),
+            Level::Warning
+        );
+        assert_eq!(
+            filter.level_for("validator_client::duties_service"),
+            Level::Debug
+        );
+        assert_eq!(
+            filter.level_for("validator_client::duties_service::sync"),
+            Level::Debug
+        );
+        // Targets only match whole path segments.
+        assert_eq!(filter.level_for("validator_client_extra"), Level::Info);
+        assert_eq!(filter.max_level(), Level::Debug);
+    }
+
+    #[test]
+    fn level_names_round_trip() {
+        for level in [
+            Level::Critical,
+            Level::Error,
+            Level::Warning,
+            Level::Info,
+            Level::Debug,
+            Level::Trace,
+        ] {
+            assert_eq!(parse_level(level_name(level)), Ok(level));
+        }
+        assert!(parse_level("verbose").is_err());
+    }
+}
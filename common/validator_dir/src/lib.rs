@@ -11,7 +11,7 @@ pub mod insecure_keys;
 mod validator_dir;
 
 pub use crate::validator_dir::{
-    unlock_keypair_from_password_path, Error, Eth1DepositData, ValidatorDir,
+    unlock_keypair, unlock_keypair_from_password_path, Error, Eth1DepositData, ValidatorDir,
     ETH1_DEPOSIT_TX_HASH_FILE,
 };
 pub use builder::{
@@ -2,6 +2,7 @@ use eth2::types::{ErrorMessage, Failure, IndexedErrorMessage};
 use std::convert::Infallible;
 use std::error::Error;
 use std::fmt;
+use std::time::Duration;
 use warp::{http::StatusCode, reject::Reject, reply::Response, Reply};
 
 #[derive(Debug)]
@@ -136,6 +137,24 @@ pub fn invalid_auth(msg: String) -> warp::reject::Rejection {
     warp::reject::custom(InvalidAuthorization(msg))
 }
 
+#[derive(Debug)]
+pub struct CustomUnauthorized(pub String);
+
+impl Reject for CustomUnauthorized {}
+
+pub fn custom_unauthorized(msg: String) -> warp::reject::Rejection {
+    warp::reject::custom(CustomUnauthorized(msg))
+}
+
+#[derive(Debug)]
+pub struct CustomForbidden(pub String);
+
+impl Reject for CustomForbidden {}
+
+pub fn custom_forbidden(msg: String) -> warp::reject::Rejection {
+    warp::reject::custom(CustomForbidden(msg))
+}
+
 #[derive(Debug)]
 pub struct UnsupportedMediaType(pub String);
 
@@ -145,6 +164,17 @@ pub fn unsupported_media_type(msg: String) -> warp::reject::Rejection {
     warp::reject::custom(UnsupportedMediaType(msg))
 }
 
+#[derive(Debug)]
+pub struct RateLimited {
+    pub retry_after: Duration,
+}
+
+impl Reject for RateLimited {}
+
+pub fn rate_limited(retry_after: Duration) -> warp::reject::Rejection {
+    warp::reject::custom(RateLimited { retry_after })
+}
+
 #[derive(Debug)]
 pub struct IndexedBadRequestErrors {
     pub message: String,
@@ -173,7 +203,27 @@ pub async fn handle_rejection(err: warp::Rejection) -> Result<impl warp::Reply,
             failures: e.failures.clone(),
         });
 
-        return Ok(warp::reply::with_status(json, code));
+        return Ok(warp::reply::with_status(json, code).into_response());
+    }
+
+    if let Some(e) = err.find::<crate::reject::RateLimited>() {
+        // Round up so that a client which waits this long is back within its quota.
+        let retry_after_secs =
+            e.retry_after.as_secs() + u64::from(e.retry_after.subsec_nanos() > 0);
+        code = StatusCode::TOO_MANY_REQUESTS;
+
+        let json = warp::reply::json(&ErrorMessage {
+            code: code.as_u16(),
+            message: format!("TOO_MANY_REQUESTS: retry after {retry_after_secs}s"),
+            stacktraces: vec![],
+        });
+
+        return Ok(warp::reply::with_header(
+            warp::reply::with_status(json, code),
+            "Retry-After",
+            retry_after_secs.to_string(),
+        )
+        .into_response());
     }
 
     if err.is_not_found() {
@@ -228,6 +278,12 @@ pub async fn handle_rejection(err: warp::Rejection) -> Result<impl warp::Reply,
     } else if let Some(e) = err.find::<crate::reject::InvalidAuthorization>() {
         code = StatusCode::FORBIDDEN;
         message = format!("FORBIDDEN: Invalid auth token: {}", e.0);
+    } else if let Some(e) = err.find::<crate::reject::CustomUnauthorized>() {
+        code = StatusCode::UNAUTHORIZED;
+        message = format!("UNAUTHORIZED: {}", e.0);
+    } else if let Some(e) = err.find::<crate::reject::CustomForbidden>() {
+        code = StatusCode::FORBIDDEN;
+        message = format!("FORBIDDEN: {}", e.0);
     } else if let Some(e) = err.find::<warp::reject::MissingHeader>() {
         if e.name().eq("Authorization") {
             code = StatusCode::UNAUTHORIZED;
@@ -253,7 +309,7 @@ pub async fn handle_rejection(err: warp::Rejection) -> Result<impl warp::Reply,
         stacktraces: vec![],
     });
 
-    Ok(warp::reply::with_status(json, code))
+    Ok(warp::reply::with_status(json, code).into_response())
 }
 
 /// Convert a warp `Rejection` into a `Response`.
@@ -9,7 +9,7 @@ use state_processing::{
     per_block_processing::errors::AttesterSlashingValidationError, per_epoch_processing,
 };
 use std::cmp::Ordering;
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashMap};
 use std::marker::PhantomData;
 use std::time::Duration;
 use types::{
@@ -1367,6 +1367,24 @@ where
     }
 
     /// Returns a reference to the underlying `fc_store`.
+    /// Returns the total justified balance of the validators whose queued attestations vote for
+    /// each block root. Queued attestations are those from the current slot, which are yet to be
+    /// applied to fork choice.
+    pub fn queued_attestation_weights(&self) -> HashMap<Hash256, u64> {
+        let balances = &self.fc_store.justified_balances().effective_balances;
+        let mut weights = HashMap::new();
+        for attestation in &self.queued_attestations {
+            let weight = attestation
+                .attesting_indices
+                .iter()
+                .filter_map(|index| balances.get(*index as usize))
+                .fold(0_u64, |sum, balance| sum.saturating_add(*balance));
+            let block_weight = weights.entry(attestation.block_root).or_insert(0_u64);
+            *block_weight = block_weight.saturating_add(weight);
+        }
+        weights
+    }
+
     pub fn fc_store(&self) -> &T {
         &self.fc_store
     }
@@ -433,7 +433,7 @@ impl ForkChoiceTest {
             .expect("should not error while producing attestation");
 
         let validator_committee_index = 0;
-        let validator_index = *head
+        let validator_index = head
             .beacon_state
             .get_beacon_committee(
                 current_slot,
@@ -444,6 +444,7 @@ impl ForkChoiceTest {
             .expect("should get committees")
             .committee
             .get(validator_committee_index)
+            .map(|&index| index as usize)
             .expect("there should be an attesting validator");
 
         let committee_count = head
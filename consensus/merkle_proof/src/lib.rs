@@ -2,6 +2,7 @@ use ethereum_hashing::{hash, hash32_concat, ZERO_HASHES};
 use ethereum_types::H256;
 use lazy_static::lazy_static;
 use safe_arith::ArithError;
+use std::collections::{BTreeSet, HashMap};
 
 const MAX_TREE_DEPTH: usize = 32;
 const EMPTY_SLICE: &[H256] = &[];
@@ -388,6 +389,78 @@ pub fn merkle_root_from_branch(leaf: H256, branch: &[H256], depth: usize, index:
     H256::from_slice(&merkle_root)
 }
 
+/// Returns the generalized indices of the nodes which are required to prove the nodes at
+/// `indices` in a multiproof, in decreasing order.
+///
+/// See `get_helper_indices` in the [SSZ Merkle proofs spec][spec].
+///
+/// [spec]: https://github.com/ethereum/consensus-specs/blob/dev/ssz/merkle-proofs.md
+pub fn get_helper_indices(indices: &[usize]) -> Vec<usize> {
+    let mut helper_indices = BTreeSet::new();
+    let mut path_indices = BTreeSet::new();
+    for &index in indices {
+        let mut index = index;
+        while index > 1 {
+            helper_indices.insert(index ^ 1);
+            path_indices.insert(index);
+            index /= 2;
+        }
+    }
+    helper_indices
+        .difference(&path_indices)
+        .rev()
+        .copied()
+        .collect()
+}
+
+/// Computes the root of a multiproof of the `leaves` at `indices`, where `proof` contains the
+/// nodes at the helper indices given by `get_helper_indices`.
+///
+/// Returns `None` if the number of leaves or proof nodes is wrong.
+pub fn calculate_multi_merkle_root(
+    leaves: &[H256],
+    proof: &[H256],
+    indices: &[usize],
+) -> Option<H256> {
+    let helper_indices = get_helper_indices(indices);
+    if leaves.len() != indices.len() || proof.len() != helper_indices.len() {
+        return None;
+    }
+
+    let mut nodes = indices
+        .iter()
+        .copied()
+        .zip(leaves.iter().copied())
+        .chain(helper_indices.iter().copied().zip(proof.iter().copied()))
+        .collect::<HashMap<_, _>>();
+    let mut keys = nodes.keys().copied().collect::<Vec<_>>();
+    keys.sort_unstable_by(|a, b| b.cmp(a));
+
+    let mut pos = 0;
+    while let Some(&index) = keys.get(pos) {
+        if index > 1 && !nodes.contains_key(&(index / 2)) {
+            if let (Some(left), Some(right)) = (nodes.get(&(index & !1)), nodes.get(&(index | 1))) {
+                let parent = H256::from_slice(&hash32_concat(left.as_bytes(), right.as_bytes()));
+                nodes.insert(index / 2, parent);
+                keys.push(index / 2);
+            }
+        }
+        pos += 1;
+    }
+
+    nodes.get(&1).copied()
+}
+
+/// Verify a multiproof that `leaves` exist at `indices` of a Merkle tree with `root`.
+pub fn verify_merkle_multiproof(
+    leaves: &[H256],
+    proof: &[H256],
+    indices: &[usize],
+    root: H256,
+) -> bool {
+    calculate_multi_merkle_root(leaves, proof, indices) == Some(root)
+}
+
 impl From<ArithError> for MerkleTreeError {
     fn from(_: ArithError) -> Self {
         MerkleTreeError::ArithError
@@ -606,4 +679,44 @@ mod tests {
         assert_eq!(res, Err(MerkleTreeError::MerkleTreeFull));
         assert_eq!(tree.hash(), expected_tree.hash());
     }
+
+    #[test]
+    fn verify_multiproof_small_example() {
+        let leaves = (0..8).map(H256::from_low_u64_be).collect::<Vec<_>>();
+        let root = MerkleTree::create(&leaves, 3).hash();
+
+        // Prove the leaves at positions 1 and 4, i.e. generalized indices 9 and 12.
+        let indices = [9, 12];
+        assert_eq!(get_helper_indices(&indices), vec![13, 8, 7, 5]);
+
+        let proof = [
+            leaves[5],
+            leaves[0],
+            MerkleTree::create(&leaves[6..8], 1).hash(),
+            MerkleTree::create(&leaves[2..4], 1).hash(),
+        ];
+        let proven_leaves = [leaves[1], leaves[4]];
+        assert!(verify_merkle_multiproof(
+            &proven_leaves,
+            &proof,
+            &indices,
+            root
+        ));
+        assert!(!verify_merkle_multiproof(
+            &[leaves[1], leaves[5]],
+            &proof,
+            &indices,
+            root
+        ));
+        assert!(!verify_merkle_multiproof(
+            &proven_leaves,
+            &proof[1..],
+            &indices,
+            root
+        ));
+
+        // A multiproof of a single leaf is its branch, from the bottom up.
+        let (_, branch) = MerkleTree::create(&leaves, 3).generate_proof(1, 3).unwrap();
+        assert!(verify_merkle_multiproof(&[leaves[1]], &branch, &[9], root));
+    }
 }
@@ -4,8 +4,9 @@ use crate::EpochCacheError;
 use std::collections::{hash_map::Entry, HashMap};
 use tree_hash::TreeHash;
 use types::{
-    AbstractExecPayload, AttestationRef, BeaconState, BeaconStateError, ChainSpec, Epoch, EthSpec,
-    Hash256, IndexedAttestation, IndexedAttestationRef, SignedBeaconBlock, Slot,
+    AbstractExecPayload, AttestationRef, BeaconBlockHeader, BeaconState, BeaconStateError,
+    ChainSpec, Epoch, EthSpec, ExecPayload, ExecutionPayloadHeader, Hash256, IndexedAttestation,
+    IndexedAttestationRef, SignedBeaconBlock, Slot,
 };
 
 #[derive(Debug, PartialEq, Clone)]
@@ -20,6 +21,13 @@ pub struct ConsensusContext<E: EthSpec> {
     pub proposer_index: Option<u64>,
     /// Block root of the block at `slot`.
     pub current_block_root: Option<Hash256>,
+    /// Body root of the block at `slot`.
+    pub current_block_body_root: Option<Hash256>,
+    /// Header of the execution payload of the block at `slot`.
+    ///
+    /// Computing the header of a full payload requires hashing all of its transactions, so we
+    /// keep it around for computing the body root and updating the state.
+    pub execution_payload_header: Option<ExecutionPayloadHeader<E>>,
     /// Cache of indexed attestations constructed during block processing.
     pub indexed_attestations: HashMap<Hash256, IndexedAttestation<E>>,
 }
@@ -54,6 +62,8 @@ impl<E: EthSpec> ConsensusContext<E> {
             current_epoch,
             proposer_index: None,
             current_block_root: None,
+            current_block_body_root: None,
+            execution_payload_header: None,
             indexed_attestations: HashMap::new(),
         }
     }
@@ -122,11 +132,71 @@ impl<E: EthSpec> ConsensusContext<E> {
             return Ok(current_block_root);
         }
 
-        let current_block_root = block.message().tree_hash_root();
+        // The root of a block is equal to the root of its header, which lets us re-use the
+        // (potentially cached) body root.
+        let message = block.message();
+        let current_block_root = BeaconBlockHeader {
+            slot: message.slot(),
+            proposer_index: message.proposer_index(),
+            parent_root: message.parent_root(),
+            state_root: message.state_root(),
+            body_root: self.get_current_block_body_root(block)?,
+        }
+        .tree_hash_root();
         self.current_block_root = Some(current_block_root);
         Ok(current_block_root)
     }
 
+    pub fn get_current_block_body_root<Payload: AbstractExecPayload<E>>(
+        &mut self,
+        block: &SignedBeaconBlock<E, Payload>,
+    ) -> Result<Hash256, ContextError> {
+        self.check_slot(block.slot())?;
+
+        if let Some(current_block_body_root) = self.current_block_body_root {
+            return Ok(current_block_body_root);
+        }
+
+        let body = block.message().body();
+        let current_block_body_root = if body.execution_payload().is_ok() {
+            // The root of a payload is equal to the root of its header.
+            let payload_root = self.get_execution_payload_header(block)?.tree_hash_root();
+            body.tree_hash_root_with_payload_root(payload_root)
+        } else {
+            body.tree_hash_root()
+        };
+        self.current_block_body_root = Some(current_block_body_root);
+        Ok(current_block_body_root)
+    }
+
+    /// Set the header of the execution payload of the block at `slot`.
+    ///
+    /// The header must have been computed from the payload of that block, e.g. when the payload
+    /// was received from the execution layer during block production.
+    pub fn set_execution_payload_header(mut self, header: ExecutionPayloadHeader<E>) -> Self {
+        self.execution_payload_header = Some(header);
+        self
+    }
+
+    /// Returns the header of the execution payload of `block`, computing it if necessary.
+    ///
+    /// Returns an error if `block` is prior to Bellatrix.
+    pub fn get_execution_payload_header<Payload: AbstractExecPayload<E>>(
+        &mut self,
+        block: &SignedBeaconBlock<E, Payload>,
+    ) -> Result<&ExecutionPayloadHeader<E>, ContextError> {
+        self.check_slot(block.slot())?;
+
+        let header = match self.execution_payload_header.take() {
+            Some(header) => header,
+            None => block
+                .message()
+                .execution_payload()?
+                .to_execution_payload_header(),
+        };
+        Ok(self.execution_payload_header.insert(header))
+    }
+
     fn check_slot(&self, slot: Slot) -> Result<(), ContextError> {
         if slot == self.slot {
             Ok(())
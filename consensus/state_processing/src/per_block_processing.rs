@@ -19,10 +19,12 @@ pub use verify_attestation::{
     verify_attestation_for_block_inclusion, verify_attestation_for_state,
 };
 pub use verify_bls_to_execution_change::verify_bls_to_execution_change;
+pub use verify_consolidation::verify_consolidation;
 pub use verify_deposit::{
     get_existing_validator_index, is_valid_deposit_signature, verify_deposit_merkle_proof,
 };
 pub use verify_exit::verify_exit;
+pub use verify_withdrawal_request::verify_withdrawal_request;
 
 pub mod altair;
 pub mod block_signature_verifier;
@@ -35,9 +37,11 @@ pub mod tests;
 mod verify_attestation;
 mod verify_attester_slashing;
 mod verify_bls_to_execution_change;
+mod verify_consolidation;
 mod verify_deposit;
 mod verify_exit;
 mod verify_proposer_slashing;
+mod verify_withdrawal_request;
 
 use crate::common::decrease_balance;
 
@@ -144,13 +148,16 @@ pub fn per_block_processing<E: EthSpec, Payload: AbstractExecPayload<E>>(
         BlockSignatureStrategy::VerifyRandao => VerifySignatures::False,
     };
 
-    let proposer_index = process_block_header(
-        state,
-        block.temporary_block_header(),
-        verify_block_root,
-        ctxt,
-        spec,
-    )?;
+    // Use the body root from the context rather than `block.temporary_block_header()`, so that
+    // the execution payload is hashed at most once per block.
+    let block_header = BeaconBlockHeader {
+        slot: block.slot(),
+        proposer_index: block.proposer_index(),
+        parent_root: block.parent_root(),
+        state_root: Hash256::zero(),
+        body_root: ctxt.get_current_block_body_root(signed_block)?,
+    };
+    let proposer_index = process_block_header(state, block_header, verify_block_root, ctxt, spec)?;
 
     if verify_signatures.is_true() {
         verify_block_signature(state, signed_block, ctxt, spec)?;
@@ -171,7 +178,8 @@ pub fn per_block_processing<E: EthSpec, Payload: AbstractExecPayload<E>>(
     if is_execution_enabled(state, block.body()) {
         let body = block.body();
         process_withdrawals::<E, Payload>(state, body.execution_payload()?, spec)?;
-        process_execution_payload::<E, Payload>(state, body, spec)?;
+        let payload_header = ctxt.get_execution_payload_header(signed_block)?;
+        process_execution_payload_with_header::<E, Payload>(state, body, payload_header, spec)?;
     }
 
     process_randao(state, block, verify_randao, ctxt, spec)?;
@@ -414,34 +422,38 @@ pub fn process_execution_payload<E: EthSpec, Payload: AbstractExecPayload<E>>(
     state: &mut BeaconState<E>,
     body: BeaconBlockBodyRef<E, Payload>,
     spec: &ChainSpec,
+) -> Result<(), BlockProcessingError> {
+    let payload_header = body.execution_payload()?.to_execution_payload_header();
+    process_execution_payload_with_header(state, body, &payload_header, spec)
+}
+
+/// As for `process_execution_payload`, but uses a `payload_header` which has already been computed
+/// from the payload of `body`, avoiding re-hashing its transactions.
+pub fn process_execution_payload_with_header<E: EthSpec, Payload: AbstractExecPayload<E>>(
+    state: &mut BeaconState<E>,
+    body: BeaconBlockBodyRef<E, Payload>,
+    payload_header: &ExecutionPayloadHeader<E>,
+    spec: &ChainSpec,
 ) -> Result<(), BlockProcessingError> {
     partially_verify_execution_payload::<E, Payload>(state, state.slot(), body, spec)?;
-    let payload = body.execution_payload()?;
-    match state.latest_execution_payload_header_mut()? {
-        ExecutionPayloadHeaderRefMut::Bellatrix(header_mut) => {
-            match payload.to_execution_payload_header() {
-                ExecutionPayloadHeader::Bellatrix(header) => *header_mut = header,
-                _ => return Err(BlockProcessingError::IncorrectStateType),
-            }
-        }
-        ExecutionPayloadHeaderRefMut::Capella(header_mut) => {
-            match payload.to_execution_payload_header() {
-                ExecutionPayloadHeader::Capella(header) => *header_mut = header,
-                _ => return Err(BlockProcessingError::IncorrectStateType),
-            }
-        }
-        ExecutionPayloadHeaderRefMut::Deneb(header_mut) => {
-            match payload.to_execution_payload_header() {
-                ExecutionPayloadHeader::Deneb(header) => *header_mut = header,
-                _ => return Err(BlockProcessingError::IncorrectStateType),
-            }
-        }
-        ExecutionPayloadHeaderRefMut::Electra(header_mut) => {
-            match payload.to_execution_payload_header() {
-                ExecutionPayloadHeader::Electra(header) => *header_mut = header,
-                _ => return Err(BlockProcessingError::IncorrectStateType),
-            }
-        }
+    match (state.latest_execution_payload_header_mut()?, payload_header) {
+        (
+            ExecutionPayloadHeaderRefMut::Bellatrix(header_mut),
+            ExecutionPayloadHeader::Bellatrix(header),
+        ) => *header_mut = header.clone(),
+        (
+            ExecutionPayloadHeaderRefMut::Capella(header_mut),
+            ExecutionPayloadHeader::Capella(header),
+        ) => *header_mut = header.clone(),
+        (
+            ExecutionPayloadHeaderRefMut::Deneb(header_mut),
+            ExecutionPayloadHeader::Deneb(header),
+        ) => *header_mut = header.clone(),
+        (
+            ExecutionPayloadHeaderRefMut::Electra(header_mut),
+            ExecutionPayloadHeader::Electra(header),
+        ) => *header_mut = header.clone(),
+        _ => return Err(BlockProcessingError::IncorrectStateType),
     }
 
     Ok(())
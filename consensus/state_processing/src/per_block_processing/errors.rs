@@ -53,6 +53,16 @@ pub enum BlockProcessingError {
         index: usize,
         reason: BlsExecutionChangeInvalid,
     },
+    ConsolidationInvalid {
+        index: usize,
+        reason: ConsolidationInvalid,
+    },
+    /// Invalid withdrawal requests are ignored rather than invalidating the block, so this is only
+    /// used to locate the request which caused an unexpected error.
+    WithdrawalRequestInvalid {
+        index: usize,
+        reason: WithdrawalRequestInvalid,
+    },
     SyncAggregateInvalid {
         reason: SyncAggregateInvalid,
     },
@@ -89,46 +99,6 @@ pub enum BlockProcessingError {
         found: Hash256,
     },
     WithdrawalCredentialsInvalid,
-    TooManyPendingConsolidations {
-        consolidations: usize,
-        limit: usize,
-    },
-    ConsolidationChurnLimitTooLow {
-        churn_limit: u64,
-        minimum: u64,
-    },
-    MatchingSourceTargetConsolidation {
-        index: u64,
-    },
-    InactiveConsolidationSource {
-        index: u64,
-        current_epoch: Epoch,
-    },
-    InactiveConsolidationTarget {
-        index: u64,
-        current_epoch: Epoch,
-    },
-    SourceValidatorExiting {
-        index: u64,
-    },
-    TargetValidatorExiting {
-        index: u64,
-    },
-    FutureConsolidationEpoch {
-        current_epoch: Epoch,
-        consolidation_epoch: Epoch,
-    },
-    NoSourceExecutionWithdrawalCredential {
-        index: u64,
-    },
-    NoTargetExecutionWithdrawalCredential {
-        index: u64,
-    },
-    MismatchedWithdrawalCredentials {
-        source_address: Address,
-        target_address: Address,
-    },
-    InavlidConsolidationSignature,
     PendingAttestationInElectra,
 }
 
@@ -237,7 +207,9 @@ impl_into_block_processing_error_with_index!(
     AttestationInvalid,
     DepositInvalid,
     ExitInvalid,
-    BlsExecutionChangeInvalid
+    BlsExecutionChangeInvalid,
+    ConsolidationInvalid,
+    WithdrawalRequestInvalid
 );
 
 pub type HeaderValidationError = BlockOperationError<HeaderInvalid>;
@@ -248,6 +220,8 @@ pub type SyncCommitteeMessageValidationError = BlockOperationError<SyncAggregate
 pub type DepositValidationError = BlockOperationError<DepositInvalid>;
 pub type ExitValidationError = BlockOperationError<ExitInvalid>;
 pub type BlsExecutionChangeValidationError = BlockOperationError<BlsExecutionChangeInvalid>;
+pub type ConsolidationValidationError = BlockOperationError<ConsolidationInvalid>;
+pub type WithdrawalRequestValidationError = BlockOperationError<WithdrawalRequestInvalid>;
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum BlockOperationError<T> {
@@ -481,6 +455,52 @@ pub enum BlsExecutionChangeInvalid {
     BadSignature,
 }
 
+#[derive(Debug, PartialEq, Clone)]
+pub enum ConsolidationInvalid {
+    /// The pending consolidations queue is full.
+    TooManyPendingConsolidations { consolidations: usize, limit: usize },
+    /// The consolidation churn limit is too low for any consolidation to be processed.
+    ChurnLimitTooLow { churn_limit: u64, minimum: u64 },
+    /// The source and target are the same validator.
+    MatchingSourceTarget(u64),
+    /// The specified validator is not in the state's validator registry.
+    ValidatorUnknown(u64),
+    /// The specified validator is not active.
+    NotActive(u64),
+    /// The specified validator has already initiated exit.
+    AlreadyInitiatedExit(u64),
+    /// The specified validator does not have execution layer withdrawal credentials.
+    NoExecutionWithdrawalCredential(u64),
+    /// The consolidation is for a future epoch.
+    FutureEpoch { state: Epoch, consolidation: Epoch },
+    /// The source and target have different withdrawal addresses.
+    MismatchedWithdrawalCredentials {
+        source_address: Address,
+        target_address: Address,
+    },
+    /// The signature is invalid.
+    BadSignature,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum WithdrawalRequestInvalid {
+    /// The pending partial withdrawals queue is full, so only full exits are possible.
+    PendingPartialWithdrawalsFull,
+    /// No validator has the requested pubkey.
+    ValidatorUnknown(PublicKeyBytes),
+    /// The validator's withdrawal credentials are not for the source address of the request.
+    IncorrectSourceAddress(u64),
+    /// The specified validator is not active.
+    NotActive(u64),
+    /// The specified validator has already initiated exit.
+    AlreadyInitiatedExit(u64),
+    /// The validator has not been active for long enough.
+    TooYoungToExit {
+        current_epoch: Epoch,
+        earliest_exit_epoch: Epoch,
+    },
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum SyncAggregateInvalid {
     /// One or more of the aggregate public keys is invalid.
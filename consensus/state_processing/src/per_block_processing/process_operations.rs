@@ -3,8 +3,9 @@ use crate::common::{
     get_attestation_participation_flag_indices, increase_balance, initiate_validator_exit,
     slash_validator,
 };
-use crate::per_block_processing::errors::{BlockProcessingError, IntoWithIndex};
-use crate::signature_sets::consolidation_signature_set;
+use crate::per_block_processing::errors::{
+    BlockOperationError, BlockProcessingError, IntoWithIndex,
+};
 use crate::VerifySignatures;
 use types::consts::altair::{PARTICIPATION_FLAG_WEIGHTS, PROPOSER_WEIGHT, WEIGHT_DENOMINATOR};
 use types::typenum::U33;
@@ -533,55 +534,18 @@ pub fn process_execution_layer_withdrawal_requests<E: EthSpec>(
     requests: &[ExecutionLayerWithdrawalRequest],
     spec: &ChainSpec,
 ) -> Result<(), BlockProcessingError> {
-    for request in requests {
+    for (i, request) in requests.iter().enumerate() {
         let amount = request.amount;
         let is_full_exit_request = amount == spec.full_exit_request_amount;
 
-        // If partial withdrawal queue is full, only full exits are processed
-        if state.pending_partial_withdrawals()?.len() == E::pending_partial_withdrawals_limit()
-            && !is_full_exit_request
-        {
-            continue;
-        }
-
-        // Verify pubkey exists
-        let index_opt = state.get_validator_index(&request.validator_pubkey)?;
-        let Some(index) = index_opt else {
-            continue;
+        let index = match verify_withdrawal_request(state, request, spec) {
+            Ok(index) => index,
+            // Invalid requests are ignored.
+            Err(BlockOperationError::Invalid(_)) => continue,
+            Err(e) => return Err(e.into_with_index(i)),
         };
-
         let validator = state.get_validator(index)?;
 
-        // Verify withdrawal credentials
-        let has_correct_credential = validator.has_execution_withdrawal_credential(spec);
-        let is_correct_source_address = validator
-            .get_execution_withdrawal_address(spec)
-            .map(|addr| addr == request.source_address)
-            .unwrap_or(false);
-
-        if !(has_correct_credential && is_correct_source_address) {
-            continue;
-        }
-
-        // Verify the validator is active
-        if !validator.is_active_at(state.current_epoch()) {
-            continue;
-        }
-
-        // Verify exit has not been initiated
-        if validator.exit_epoch != spec.far_future_epoch {
-            continue;
-        }
-
-        // Verify the validator has been active long enough
-        if state.current_epoch()
-            < validator
-                .activation_epoch
-                .safe_add(spec.shard_committee_period)?
-        {
-            continue;
-        }
-
         let pending_balance_to_withdraw = state.get_pending_balance_to_withdraw(index)?;
         if is_full_exit_request {
             // Only exit validator if it has no pending withdrawals in the queue
@@ -653,126 +617,12 @@ pub fn process_consolidations<E: EthSpec>(
     verify_signatures: VerifySignatures,
     spec: &ChainSpec,
 ) -> Result<(), BlockProcessingError> {
-    if consolidations.is_empty() {
-        return Ok(());
-    }
-
-    // If the pending consolidations queue is full, no consolidations are allowed in the block
-    let pending_consolidations = state.pending_consolidations()?.len();
-    let pending_consolidations_limit = E::pending_consolidations_limit();
-    block_verify! {
-        pending_consolidations < pending_consolidations_limit,
-        BlockProcessingError::TooManyPendingConsolidations {
-            consolidations: pending_consolidations,
-            limit: pending_consolidations_limit
-        }
-    }
-
-    // If there is too little available consolidation churn limit, no consolidations are allowed in the block
-    let churn_limit = state.get_consolidation_churn_limit(spec)?;
-    block_verify! {
-        churn_limit > spec.min_activation_balance,
-        BlockProcessingError::ConsolidationChurnLimitTooLow {
-            churn_limit,
-            minimum: spec.min_activation_balance
-        }
-    }
-
-    for signed_consolidation in consolidations {
-        let consolidation = signed_consolidation.message.clone();
-
-        // Verify that source != target, so a consolidation cannot be used as an exit.
-        block_verify! {
-            consolidation.source_index != consolidation.target_index,
-            BlockProcessingError::MatchingSourceTargetConsolidation  {
-                index: consolidation.source_index
-            }
-        }
+    for (i, signed_consolidation) in consolidations.iter().enumerate() {
+        verify_consolidation(state, signed_consolidation, verify_signatures, spec)
+            .map_err(|e| e.into_with_index(i))?;
 
+        let consolidation = &signed_consolidation.message;
         let source_validator = state.get_validator(consolidation.source_index as usize)?;
-        let target_validator = state.get_validator(consolidation.target_index as usize)?;
-
-        // Verify the source and the target are active
-        let current_epoch = state.current_epoch();
-        block_verify! {
-            source_validator.is_active_at(current_epoch),
-            BlockProcessingError::InactiveConsolidationSource{
-                index: consolidation.source_index,
-                current_epoch
-            }
-        }
-        block_verify! {
-            target_validator.is_active_at(current_epoch),
-            BlockProcessingError::InactiveConsolidationTarget{
-                index: consolidation.target_index,
-                current_epoch
-            }
-        }
-
-        // Verify exits for source and target have not been initiated
-        block_verify! {
-            source_validator.exit_epoch == spec.far_future_epoch,
-            BlockProcessingError::SourceValidatorExiting{
-                index: consolidation.source_index,
-            }
-        }
-        block_verify! {
-            target_validator.exit_epoch == spec.far_future_epoch,
-            BlockProcessingError::TargetValidatorExiting{
-                index: consolidation.target_index,
-            }
-        }
-
-        // Consolidations must specify an epoch when they become valid; they are not valid before then
-        block_verify! {
-            current_epoch >= consolidation.epoch,
-            BlockProcessingError::FutureConsolidationEpoch {
-                current_epoch,
-                consolidation_epoch: consolidation.epoch
-            }
-        }
-
-        // Verify the source and the target have Execution layer withdrawal credentials
-        block_verify! {
-            source_validator.has_execution_withdrawal_credential(spec),
-            BlockProcessingError::NoSourceExecutionWithdrawalCredential {
-                index: consolidation.source_index,
-            }
-        }
-        block_verify! {
-            target_validator.has_execution_withdrawal_credential(spec),
-            BlockProcessingError::NoTargetExecutionWithdrawalCredential {
-                index: consolidation.target_index,
-            }
-        }
-
-        // Verify the same withdrawal address
-        let source_address = source_validator
-            .get_execution_withdrawal_address(spec)
-            .ok_or(BeaconStateError::NonExecutionAddresWithdrawalCredential)?;
-        let target_address = target_validator
-            .get_execution_withdrawal_address(spec)
-            .ok_or(BeaconStateError::NonExecutionAddresWithdrawalCredential)?;
-        block_verify! {
-            source_address == target_address,
-            BlockProcessingError::MismatchedWithdrawalCredentials {
-                source_address,
-                target_address
-            }
-        }
-
-        if verify_signatures.is_true() {
-            let signature_set = consolidation_signature_set(
-                state,
-                |i| get_pubkey_from_state(state, i),
-                signed_consolidation,
-                spec,
-            )?;
-            block_verify! {
-                signature_set.verify(),
-                BlockProcessingError::InavlidConsolidationSignature
-            }
-        }
         let exit_epoch = state.compute_consolidation_epoch_and_update_churn(
             source_validator.effective_balance,
             spec,
@@ -2,12 +2,15 @@
 
 use crate::per_block_processing::errors::{
     AttestationInvalid, AttesterSlashingInvalid, BlockOperationError, BlockProcessingError,
-    DepositInvalid, HeaderInvalid, IndexedAttestationInvalid, IntoWithIndex,
-    ProposerSlashingInvalid,
+    ConsolidationInvalid, DepositInvalid, HeaderInvalid, IndexedAttestationInvalid, IntoWithIndex,
+    ProposerSlashingInvalid, WithdrawalRequestInvalid,
 };
 use crate::{per_block_processing, BlockReplayError, BlockReplayer};
 use crate::{
-    per_block_processing::{process_operations, verify_exit::verify_exit},
+    per_block_processing::{
+        process_operations, verify_consolidation, verify_exit::verify_exit,
+        verify_withdrawal_request,
+    },
     BlockSignatureStrategy, ConsensusContext, VerifyBlockRoot, VerifySignatures,
 };
 use beacon_chain::test_utils::{BeaconChainHarness, EphemeralHarnessType};
@@ -1095,6 +1098,142 @@ async fn fork_spanning_exit() {
     .expect_err("phase0 exit does not verify against bellatrix state");
 }
 
+#[tokio::test]
+async fn invalid_electra_requests() {
+    let spec = ForkName::Electra.make_genesis_spec(MainnetEthSpec::default_spec());
+    let harness = BeaconChainHarness::builder(MainnetEthSpec)
+        .spec(spec.clone())
+        .deterministic_keypairs(VALIDATOR_COUNT)
+        .mock_execution_layer()
+        .fresh_ephemeral_store()
+        .build();
+    let state = harness.get_current_state();
+
+    // There are too few validators for any consolidation churn.
+    let consolidation = SignedConsolidation {
+        message: Consolidation {
+            source_index: 0,
+            target_index: 1,
+            epoch: Epoch::new(0),
+        },
+        signature: Signature::empty(),
+    };
+    let result = verify_consolidation(&state, &consolidation, VerifySignatures::False, &spec);
+    assert!(matches!(
+        result,
+        Err(BlockOperationError::Invalid(
+            ConsolidationInvalid::ChurnLimitTooLow { .. }
+        ))
+    ));
+
+    // The validators have BLS withdrawal credentials.
+    let mut request = ExecutionLayerWithdrawalRequest {
+        source_address: Address::zero(),
+        validator_pubkey: KEYPAIRS[0].pk.compress(),
+        amount: spec.full_exit_request_amount,
+    };
+    assert_eq!(
+        verify_withdrawal_request(&state, &request, &spec),
+        Err(BlockOperationError::Invalid(
+            WithdrawalRequestInvalid::IncorrectSourceAddress(0)
+        ))
+    );
+
+    request.validator_pubkey = KEYPAIRS[VALIDATOR_COUNT].pk.compress();
+    assert_eq!(
+        verify_withdrawal_request(&state, &request, &spec),
+        Err(BlockOperationError::Invalid(
+            WithdrawalRequestInvalid::ValidatorUnknown(request.validator_pubkey)
+        ))
+    );
+}
+
+#[tokio::test]
+async fn consolidation_capacity_and_churn_limits() {
+    type E = MinimalEthSpec;
+    let mut spec = ForkName::Electra.make_genesis_spec(E::default_spec());
+    let harness = BeaconChainHarness::builder(E::default())
+        .spec(spec.clone())
+        .deterministic_keypairs(VALIDATOR_COUNT)
+        .mock_execution_layer()
+        .fresh_ephemeral_store()
+        .build();
+    let mut state = harness.get_current_state();
+
+    // Matching indices are only rejected once the queue and churn checks have passed.
+    let consolidation = SignedConsolidation {
+        message: Consolidation {
+            source_index: 0,
+            target_index: 0,
+            epoch: Epoch::new(0),
+        },
+        signature: Signature::empty(),
+    };
+    let verify = |state: &BeaconState<E>, spec: &ChainSpec| {
+        verify_consolidation(state, &consolidation, VerifySignatures::False, spec)
+    };
+
+    // A consolidation churn equal to the minimum activation balance is too low.
+    spec.max_per_epoch_activation_exit_churn_limit =
+        spec.min_per_epoch_churn_limit_electra - spec.min_activation_balance;
+    assert_eq!(
+        state.get_consolidation_churn_limit(&spec),
+        Ok(spec.min_activation_balance)
+    );
+    assert!(matches!(
+        verify(&state, &spec),
+        Err(BlockOperationError::Invalid(
+            ConsolidationInvalid::ChurnLimitTooLow { .. }
+        ))
+    ));
+
+    // Any more churn is enough.
+    spec.max_per_epoch_activation_exit_churn_limit -= spec.effective_balance_increment;
+    assert_eq!(
+        verify(&state, &spec),
+        Err(BlockOperationError::Invalid(
+            ConsolidationInvalid::MatchingSourceTarget(0)
+        ))
+    );
+
+    // The queue accepts consolidations until it is full.
+    let limit = E::pending_consolidations_limit();
+    for _ in 0..limit - 1 {
+        state
+            .pending_consolidations_mut()
+            .unwrap()
+            .push(PendingConsolidation {
+                source_index: 1,
+                target_index: 2,
+            })
+            .unwrap();
+    }
+    assert_eq!(
+        verify(&state, &spec),
+        Err(BlockOperationError::Invalid(
+            ConsolidationInvalid::MatchingSourceTarget(0)
+        ))
+    );
+
+    state
+        .pending_consolidations_mut()
+        .unwrap()
+        .push(PendingConsolidation {
+            source_index: 1,
+            target_index: 2,
+        })
+        .unwrap();
+    assert_eq!(
+        verify(&state, &spec),
+        Err(BlockOperationError::Invalid(
+            ConsolidationInvalid::TooManyPendingConsolidations {
+                consolidations: limit,
+                limit,
+            }
+        ))
+    );
+}
+
 /// Check that the block replayer does not consume state roots unnecessarily.
 #[tokio::test]
 async fn block_replayer_peeking_state_roots() {
@@ -0,0 +1,123 @@
+use super::errors::{BlockOperationError, ConsolidationInvalid as Invalid};
+use crate::per_block_processing::{
+    signature_sets::{consolidation_signature_set, get_pubkey_from_state},
+    VerifySignatures,
+};
+use types::*;
+
+type Result<T> = std::result::Result<T, BlockOperationError<Invalid>>;
+
+fn error(reason: Invalid) -> BlockOperationError<Invalid> {
+    BlockOperationError::invalid(reason)
+}
+
+/// Indicates if a `SignedConsolidation` is valid to be included in a block in the current epoch of
+/// the given state.
+///
+/// Returns `Ok(())` if the `SignedConsolidation` is valid, otherwise indicates the reason for
+/// invalidity.
+pub fn verify_consolidation<E: EthSpec>(
+    state: &BeaconState<E>,
+    signed_consolidation: &SignedConsolidation,
+    verify_signatures: VerifySignatures,
+    spec: &ChainSpec,
+) -> Result<()> {
+    let consolidation = &signed_consolidation.message;
+    let current_epoch = state.current_epoch();
+
+    // If the pending consolidations queue is full, no consolidations are allowed.
+    verify!(
+        state.pending_consolidations_has_capacity()?,
+        Invalid::TooManyPendingConsolidations {
+            consolidations: state.pending_consolidations()?.len(),
+            limit: E::pending_consolidations_limit(),
+        }
+    );
+
+    // If there is too little available consolidation churn limit, no consolidations are allowed.
+    let churn_limit = state.get_consolidation_churn_limit(spec)?;
+    verify!(
+        churn_limit > spec.min_activation_balance,
+        Invalid::ChurnLimitTooLow {
+            churn_limit,
+            minimum: spec.min_activation_balance,
+        }
+    );
+
+    // Verify that source != target, so a consolidation cannot be used as an exit.
+    verify!(
+        consolidation.source_index != consolidation.target_index,
+        Invalid::MatchingSourceTarget(consolidation.source_index)
+    );
+
+    let get_validator = |index: u64| {
+        state
+            .validators()
+            .get(index as usize)
+            .ok_or_else(|| error(Invalid::ValidatorUnknown(index)))
+    };
+    let source_validator = get_validator(consolidation.source_index)?;
+    let target_validator = get_validator(consolidation.target_index)?;
+
+    for (index, validator) in [
+        (consolidation.source_index, source_validator),
+        (consolidation.target_index, target_validator),
+    ] {
+        // Verify the validator is active.
+        verify!(
+            validator.is_active_at(current_epoch),
+            Invalid::NotActive(index)
+        );
+
+        // Verify that the validator has not initiated an exit.
+        verify!(
+            validator.exit_epoch == spec.far_future_epoch,
+            Invalid::AlreadyInitiatedExit(index)
+        );
+
+        // Verify the validator has execution layer withdrawal credentials.
+        verify!(
+            validator.has_execution_withdrawal_credential(spec),
+            Invalid::NoExecutionWithdrawalCredential(index)
+        );
+    }
+
+    // Consolidations must specify an epoch when they become valid; they are not valid before then.
+    verify!(
+        current_epoch >= consolidation.epoch,
+        Invalid::FutureEpoch {
+            state: current_epoch,
+            consolidation: consolidation.epoch,
+        }
+    );
+
+    // Verify the source and the target have the same withdrawal address.
+    let source_address = source_validator
+        .get_execution_withdrawal_address(spec)
+        .ok_or(BeaconStateError::NonExecutionAddresWithdrawalCredential)?;
+    let target_address = target_validator
+        .get_execution_withdrawal_address(spec)
+        .ok_or(BeaconStateError::NonExecutionAddresWithdrawalCredential)?;
+    verify!(
+        source_address == target_address,
+        Invalid::MismatchedWithdrawalCredentials {
+            source_address,
+            target_address,
+        }
+    );
+
+    if verify_signatures.is_true() {
+        verify!(
+            consolidation_signature_set(
+                state,
+                |i| get_pubkey_from_state(state, i),
+                signed_consolidation,
+                spec
+            )?
+            .verify(),
+            Invalid::BadSignature
+        );
+    }
+
+    Ok(())
+}
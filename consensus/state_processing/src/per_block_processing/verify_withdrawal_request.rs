@@ -0,0 +1,67 @@
+use super::errors::{BlockOperationError, WithdrawalRequestInvalid as Invalid};
+use safe_arith::SafeArith;
+use types::*;
+
+type Result<T> = std::result::Result<T, BlockOperationError<Invalid>>;
+
+fn error(reason: Invalid) -> BlockOperationError<Invalid> {
+    BlockOperationError::invalid(reason)
+}
+
+/// Indicates if an `ExecutionLayerWithdrawalRequest` can be applied to the validator it targets in
+/// the current epoch of the given state.
+///
+/// Returns the index of the validator if the request is valid, otherwise indicates the reason for
+/// invalidity. Invalid requests do not invalidate the block which includes them, they are ignored.
+pub fn verify_withdrawal_request<E: EthSpec>(
+    state: &BeaconState<E>,
+    request: &ExecutionLayerWithdrawalRequest,
+    spec: &ChainSpec,
+) -> Result<usize> {
+    let current_epoch = state.current_epoch();
+    let is_full_exit_request = request.amount == spec.full_exit_request_amount;
+
+    // If the partial withdrawal queue is full, only full exits are processed.
+    verify!(
+        is_full_exit_request || state.pending_partial_withdrawals_has_capacity()?,
+        Invalid::PendingPartialWithdrawalsFull
+    );
+
+    let index = state
+        .get_validator_index(&request.validator_pubkey)?
+        .ok_or_else(|| error(Invalid::ValidatorUnknown(request.validator_pubkey)))?;
+    let validator = state.get_validator(index)?;
+
+    // Verify the validator has execution withdrawal credentials for the source address.
+    verify!(
+        validator.has_execution_withdrawal_credential(spec)
+            && validator.get_execution_withdrawal_address(spec) == Some(request.source_address),
+        Invalid::IncorrectSourceAddress(index as u64)
+    );
+
+    // Verify the validator is active.
+    verify!(
+        validator.is_active_at(current_epoch),
+        Invalid::NotActive(index as u64)
+    );
+
+    // Verify that the validator has not initiated an exit.
+    verify!(
+        validator.exit_epoch == spec.far_future_epoch,
+        Invalid::AlreadyInitiatedExit(index as u64)
+    );
+
+    // Verify the validator has been active long enough.
+    let earliest_exit_epoch = validator
+        .activation_epoch
+        .safe_add(spec.shard_committee_period)?;
+    verify!(
+        current_epoch >= earliest_exit_epoch,
+        Invalid::TooYoungToExit {
+            current_epoch,
+            earliest_exit_epoch,
+        }
+    );
+
+    Ok(index)
+}
@@ -1,9 +1,9 @@
 use crate::per_block_processing::{
     errors::{
-        AttesterSlashingValidationError, BlsExecutionChangeValidationError, ExitValidationError,
-        ProposerSlashingValidationError,
+        AttesterSlashingValidationError, BlsExecutionChangeValidationError,
+        ConsolidationValidationError, ExitValidationError, ProposerSlashingValidationError,
     },
-    verify_attester_slashing, verify_bls_to_execution_change, verify_exit,
+    verify_attester_slashing, verify_bls_to_execution_change, verify_consolidation, verify_exit,
     verify_proposer_slashing,
 };
 use crate::VerifySignatures;
@@ -17,7 +17,7 @@ use test_random_derive::TestRandom;
 use types::{
     test_utils::TestRandom, AttesterSlashing, AttesterSlashingBase, AttesterSlashingOnDisk,
     AttesterSlashingRefOnDisk, BeaconState, ChainSpec, Epoch, EthSpec, Fork, ForkVersion,
-    ProposerSlashing, SignedBlsToExecutionChange, SignedVoluntaryExit,
+    ProposerSlashing, SignedBlsToExecutionChange, SignedConsolidation, SignedVoluntaryExit,
 };
 
 const MAX_FORKS_VERIFIED_AGAINST: usize = 2;
@@ -286,6 +286,24 @@ impl<E: EthSpec> VerifyOperation<E> for SignedBlsToExecutionChange {
     }
 }
 
+impl<E: EthSpec> VerifyOperation<E> for SignedConsolidation {
+    type Error = ConsolidationValidationError;
+
+    fn validate(
+        self,
+        state: &BeaconState<E>,
+        spec: &ChainSpec,
+    ) -> Result<SigVerifiedOp<Self, E>, Self::Error> {
+        verify_consolidation(state, &self, VerifySignatures::True, spec)?;
+        Ok(SigVerifiedOp::new(self, state))
+    }
+
+    /// Consolidations are signed over the genesis fork version, so remain valid across forks.
+    fn verification_epochs(&self) -> SmallVec<[Epoch; MAX_FORKS_VERIFIED_AGAINST]> {
+        smallvec![]
+    }
+}
+
 /// Trait for operations that can be verified and transformed into a
 /// `SigVerifiedOp`.
 ///
@@ -413,6 +431,19 @@ impl TransformPersist for SignedBlsToExecutionChange {
     }
 }
 
+impl TransformPersist for SignedConsolidation {
+    type Persistable = Self;
+    type PersistableRef<'a> = &'a Self;
+
+    fn as_persistable_ref(&self) -> Self::PersistableRef<'_> {
+        self
+    }
+
+    fn from_persistable(persistable: Self::Persistable) -> Self {
+        persistable
+    }
+}
+
 #[cfg(all(test, not(debug_assertions)))]
 mod test {
     use super::*;
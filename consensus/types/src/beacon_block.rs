@@ -1,4 +1,5 @@
 use crate::attestation::AttestationBase;
+use crate::merkle_node::{chunks_node, split_generalized_index};
 use crate::test_utils::TestRandom;
 use crate::*;
 use derivative::Derivative;
@@ -113,15 +114,7 @@ impl<E: EthSpec, Payload: AbstractExecPayload<E>> BeaconBlock<E, Payload> {
 
         let slot = Slot::from_ssz_bytes(slot_bytes)?;
         let fork_at_slot = spec.fork_name_at_slot::<E>(slot);
-        Self::from_ssz_bytes_for_fork(bytes, fork_at_slot)
-    }
-
-    /// Custom SSZ decoder that takes a `ForkName` as context.
-    pub fn from_ssz_bytes_for_fork(
-        bytes: &[u8],
-        fork_name: ForkName,
-    ) -> Result<Self, ssz::DecodeError> {
-        Ok(map_fork_name!(fork_name, Self, <_>::from_ssz_bytes(bytes)?))
+        Self::from_ssz_bytes_by_fork(bytes, fork_at_slot)
     }
 
     /// Try decoding each beacon block variant in sequence.
@@ -263,6 +256,39 @@ impl<'a, E: EthSpec, Payload: AbstractExecPayload<E>> BeaconBlockRef<'a, E, Payl
         }
     }
 
+    /// Returns the node at `generalized_index` of the Merkle tree of the block.
+    ///
+    /// Nodes down to the fields of the block and the fields of its body are supported.
+    pub fn merkle_node(&self, generalized_index: usize) -> Result<Hash256, Error> {
+        // The five fields of the block are padded to eight leaves, and the body is the fifth.
+        const DEPTH: usize = 3;
+        const BODY_INDEX: usize = 4;
+
+        match split_generalized_index(generalized_index, DEPTH) {
+            Some((BODY_INDEX, body_generalized_index)) if body_generalized_index > 1 => self
+                .body()
+                .merkle_node(body_generalized_index)
+                .map_err(|_| Error::IndexNotSupported(generalized_index)),
+            _ => {
+                // The block has the same tree hash root as its header.
+                let header = self.block_header();
+                let chunks = [
+                    header.slot.tree_hash_root(),
+                    header.proposer_index.tree_hash_root(),
+                    header.parent_root,
+                    header.state_root,
+                    header.body_root,
+                ]
+                .iter()
+                .flat_map(|leaf| leaf.as_bytes())
+                .copied()
+                .collect::<Vec<_>>();
+                chunks_node(&chunks, DEPTH, generalized_index)
+                    .ok_or(Error::IndexNotSupported(generalized_index))
+            }
+        }
+    }
+
     /// Returns a "temporary" header, where the `state_root` is `Hash256::zero()`.
     pub fn temporary_block_header(self) -> BeaconBlockHeader {
         BeaconBlockHeader {
@@ -850,6 +876,12 @@ impl<E: EthSpec> From<BeaconBlock<E, FullPayload<E>>>
     }
 }
 
+impl<E: EthSpec, Payload: AbstractExecPayload<E>> ForkVersionDecode for BeaconBlock<E, Payload> {
+    fn from_ssz_bytes_by_fork(bytes: &[u8], fork_name: ForkName) -> Result<Self, DecodeError> {
+        Ok(map_fork_name!(fork_name, Self, <_>::from_ssz_bytes(bytes)?))
+    }
+}
+
 impl<E: EthSpec, Payload: AbstractExecPayload<E>> ForkVersionDeserialize
     for BeaconBlock<E, Payload>
 {
@@ -1,3 +1,4 @@
+use crate::merkle_node::chunks_node;
 use crate::test_utils::TestRandom;
 use crate::*;
 use derivative::Derivative;
@@ -143,6 +144,12 @@ impl<'a, E: EthSpec, Payload: AbstractExecPayload<E>> BeaconBlockBodyRef<'a, E,
     }
 
     fn body_merkle_leaves(&self) -> Vec<Hash256> {
+        self.body_merkle_leaves_with_payload_root(None)
+    }
+
+    /// As for `body_merkle_leaves`, but uses `payload_root` as the root of the execution payload
+    /// instead of hashing it (if provided).
+    fn body_merkle_leaves_with_payload_root(&self, payload_root: Option<Hash256>) -> Vec<Hash256> {
         let mut leaves = vec![];
         match self {
             Self::Base(body) => {
@@ -154,25 +161,62 @@ impl<'a, E: EthSpec, Payload: AbstractExecPayload<E>> BeaconBlockBodyRef<'a, E,
                     .push(field.tree_hash_root()));
             }
             Self::Bellatrix(body) => {
-                beacon_block_body_bellatrix_fields!(body, |_, field| leaves
-                    .push(field.tree_hash_root()));
+                beacon_block_body_bellatrix_fields!(body, |name, field| leaves.push(
+                    payload_root
+                        .filter(|_| name == "execution_payload")
+                        .unwrap_or_else(|| field.tree_hash_root())
+                ));
             }
             Self::Capella(body) => {
-                beacon_block_body_capella_fields!(body, |_, field| leaves
-                    .push(field.tree_hash_root()));
+                beacon_block_body_capella_fields!(body, |name, field| leaves.push(
+                    payload_root
+                        .filter(|_| name == "execution_payload")
+                        .unwrap_or_else(|| field.tree_hash_root())
+                ));
             }
             Self::Deneb(body) => {
-                beacon_block_body_deneb_fields!(body, |_, field| leaves
-                    .push(field.tree_hash_root()));
+                beacon_block_body_deneb_fields!(body, |name, field| leaves.push(
+                    payload_root
+                        .filter(|_| name == "execution_payload")
+                        .unwrap_or_else(|| field.tree_hash_root())
+                ));
             }
             Self::Electra(body) => {
-                beacon_block_body_electra_fields!(body, |_, field| leaves
-                    .push(field.tree_hash_root()));
+                beacon_block_body_electra_fields!(body, |name, field| leaves.push(
+                    payload_root
+                        .filter(|_| name == "execution_payload")
+                        .unwrap_or_else(|| field.tree_hash_root())
+                ));
             }
         }
         leaves
     }
 
+    /// Computes the tree hash root of the body using `payload_root` as the root of the execution
+    /// payload, avoiding re-hashing the transactions of a full payload whose header is known.
+    ///
+    /// The `payload_root` is ignored for bodies prior to Bellatrix.
+    pub fn tree_hash_root_with_payload_root(&self, payload_root: Hash256) -> Hash256 {
+        let leaves = self.body_merkle_leaves_with_payload_root(Some(payload_root));
+        let depth = leaves.len().next_power_of_two().ilog2() as usize;
+        MerkleTree::create(&leaves, depth).hash()
+    }
+
+    /// Returns the node at `generalized_index` of the Merkle tree of the body.
+    ///
+    /// Nodes down to the fields of the body are supported.
+    pub fn merkle_node(&self, generalized_index: usize) -> Result<Hash256, Error> {
+        let leaves = self.body_merkle_leaves();
+        let depth = leaves.len().next_power_of_two().ilog2() as usize;
+        let chunks = leaves
+            .iter()
+            .flat_map(|leaf| leaf.as_bytes())
+            .copied()
+            .collect::<Vec<_>>();
+        chunks_node(&chunks, depth, generalized_index)
+            .ok_or(Error::IndexNotSupported(generalized_index))
+    }
+
     /// Produces the proof of inclusion for a `KzgCommitment` in `self.blob_kzg_commitments`
     /// at `index`.
     pub fn kzg_commitment_merkle_proof(
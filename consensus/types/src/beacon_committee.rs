@@ -4,7 +4,7 @@ use crate::*;
 pub struct BeaconCommittee<'a> {
     pub slot: Slot,
     pub index: CommitteeIndex,
-    pub committee: &'a [usize],
+    pub committee: &'a [u32],
 }
 
 impl<'a> BeaconCommittee<'a> {
@@ -21,5 +21,5 @@ impl<'a> BeaconCommittee<'a> {
 pub struct OwnedBeaconCommittee {
     pub slot: Slot,
     pub index: CommitteeIndex,
-    pub committee: Vec<usize>,
+    pub committee: Vec<u32>,
 }
@@ -1,5 +1,6 @@
 use self::committee_cache::get_active_validator_indices;
 use crate::historical_summary::HistoricalSummary;
+use crate::merkle_node::{chunks_node, list_nodes, split_generalized_index, vector_nodes};
 use crate::test_utils::TestRandom;
 use crate::*;
 use compare_fields::CompareFields;
@@ -13,6 +14,7 @@ use safe_arith::{ArithError, SafeArith};
 use serde::{Deserialize, Serialize};
 use ssz::{ssz_encode, Decode, DecodeError, Encode};
 use ssz_derive::{Decode, Encode};
+use std::collections::BTreeMap;
 use std::hash::Hash;
 use std::{fmt, mem, sync::Arc};
 use superstruct::superstruct;
@@ -48,6 +50,7 @@ const MAX_RANDOM_BYTE: u64 = (1 << 8) - 1;
 
 pub type Validators<E> = List<Validator, <E as EthSpec>::ValidatorRegistryLimit>;
 pub type Balances<E> = List<u64, <E as EthSpec>::ValidatorRegistryLimit>;
+pub type InactivityScores<E> = List<u64, <E as EthSpec>::ValidatorRegistryLimit>;
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum Error {
@@ -739,7 +742,7 @@ impl<E: EthSpec> BeaconState<E> {
     pub fn get_cached_active_validator_indices(
         &self,
         relative_epoch: RelativeEpoch,
-    ) -> Result<&[usize], Error> {
+    ) -> Result<&[u32], Error> {
         let cache = self.committee_cache(relative_epoch)?;
 
         Ok(cache.active_validator_indices())
@@ -765,7 +768,7 @@ impl<E: EthSpec> BeaconState<E> {
     /// Note: the indices are shuffled (i.e., not in ascending order).
     ///
     /// Returns an error if that epoch is not cached, or the cache is not initialized.
-    pub fn get_shuffling(&self, relative_epoch: RelativeEpoch) -> Result<&[usize], Error> {
+    pub fn get_shuffling(&self, relative_epoch: RelativeEpoch) -> Result<&[u32], Error> {
         let cache = self.committee_cache(relative_epoch)?;
 
         Ok(cache.shuffling())
@@ -1426,7 +1429,7 @@ impl<E: EthSpec> BeaconState<E> {
             &mut Balances<E>,
             &List<ParticipationFlags, E::ValidatorRegistryLimit>,
             &List<ParticipationFlags, E::ValidatorRegistryLimit>,
-            &mut List<u64, E::ValidatorRegistryLimit>,
+            &mut InactivityScores<E>,
             &mut ProgressiveBalancesCache,
             &mut ExitCache,
             &mut EpochCache,
@@ -1522,20 +1525,7 @@ impl<E: EthSpec> BeaconState<E> {
                 .get(i_mod)
                 .ok_or(Error::RandaoMixesOutOfBounds(i_mod))?
         };
-        let domain_bytes = int_to_bytes4(spec.get_domain_constant(domain_type));
-        let epoch_bytes = int_to_bytes8(epoch.as_u64());
-
-        const NUM_DOMAIN_BYTES: usize = 4;
-        const NUM_EPOCH_BYTES: usize = 8;
-        const MIX_OFFSET: usize = NUM_DOMAIN_BYTES + NUM_EPOCH_BYTES;
-        const NUM_MIX_BYTES: usize = 32;
-
-        let mut preimage = [0; NUM_DOMAIN_BYTES + NUM_EPOCH_BYTES + NUM_MIX_BYTES];
-        preimage[0..NUM_DOMAIN_BYTES].copy_from_slice(&domain_bytes);
-        preimage[NUM_DOMAIN_BYTES..MIX_OFFSET].copy_from_slice(&epoch_bytes);
-        preimage[MIX_OFFSET..].copy_from_slice(mix.as_bytes());
-
-        Ok(Hash256::from_slice(&hash(&preimage)))
+        Ok(compute_seed(mix, epoch, domain_type, spec))
     }
 
     /// Safe indexer for the `validators` list.
@@ -2158,6 +2148,155 @@ impl<E: EthSpec> BeaconState<E> {
         Ok(pending_balance)
     }
 
+    /// Return the total amount of the pending balance deposits queued for `validator_index`.
+    pub fn get_pending_balance_to_deposit(&self, validator_index: usize) -> Result<u64, Error> {
+        let mut pending_balance = 0;
+        for deposit in self
+            .pending_balance_deposits()?
+            .iter()
+            .filter(|deposit| deposit.index as usize == validator_index)
+        {
+            pending_balance.safe_add_assign(deposit.amount)?;
+        }
+        Ok(pending_balance)
+    }
+
+    /// Return the positions in the pending partial withdrawals queue of the withdrawals for
+    /// `validator_index`, in ascending order.
+    pub fn get_pending_partial_withdrawal_positions(
+        &self,
+        validator_index: usize,
+    ) -> Result<Vec<usize>, Error> {
+        Ok(self
+            .pending_partial_withdrawals()?
+            .iter()
+            .enumerate()
+            .filter(|(_, withdrawal)| withdrawal.index as usize == validator_index)
+            .map(|(position, _)| position)
+            .collect())
+    }
+
+    /// Return the position in the pending consolidations queue of the consolidation with
+    /// `validator_index` as its source, if any.
+    pub fn get_pending_consolidation_position(
+        &self,
+        validator_index: usize,
+    ) -> Result<Option<usize>, Error> {
+        Ok(self
+            .pending_consolidations()?
+            .iter()
+            .position(|consolidation| consolidation.source_index as usize == validator_index))
+    }
+
+    /// Returns `true` if there is room for another partial withdrawal in the queue.
+    pub fn pending_partial_withdrawals_has_capacity(&self) -> Result<bool, Error> {
+        Ok(self.pending_partial_withdrawals()?.len() < E::pending_partial_withdrawals_limit())
+    }
+
+    /// Returns `true` if there is room for another consolidation in the queue.
+    pub fn pending_consolidations_has_capacity(&self) -> Result<bool, Error> {
+        Ok(self.pending_consolidations()?.len() < E::pending_consolidations_limit())
+    }
+
+    /// Return the epoch at which a validator exiting with `exit_balance` would exit, without
+    /// consuming any churn.
+    pub fn get_exit_epoch_for_balance(
+        &self,
+        exit_balance: u64,
+        spec: &ChainSpec,
+    ) -> Result<Epoch, Error> {
+        self.compute_exit_epoch_and_churn(exit_balance, spec)
+            .map(|(exit_epoch, _)| exit_epoch)
+    }
+
+    /// Return the epoch at which a consolidation of `consolidation_balance` would complete,
+    /// without consuming any churn.
+    pub fn get_consolidation_epoch_for_balance(
+        &self,
+        consolidation_balance: u64,
+        spec: &ChainSpec,
+    ) -> Result<Epoch, Error> {
+        self.compute_consolidation_epoch_and_churn(consolidation_balance, spec)
+            .map(|(consolidation_epoch, _)| consolidation_epoch)
+    }
+
+    /// Compute the exit epoch for `exit_balance` and the exit churn remaining in that epoch.
+    fn compute_exit_epoch_and_churn(
+        &self,
+        exit_balance: u64,
+        spec: &ChainSpec,
+    ) -> Result<(Epoch, u64), Error> {
+        let mut earliest_exit_epoch = std::cmp::max(
+            self.earliest_exit_epoch()?,
+            self.compute_activation_exit_epoch(self.current_epoch(), spec)?,
+        );
+
+        let per_epoch_churn = self.get_activation_exit_churn_limit(spec)?;
+        // New epoch for exits
+        let mut exit_balance_to_consume = if self.earliest_exit_epoch()? < earliest_exit_epoch {
+            per_epoch_churn
+        } else {
+            self.exit_balance_to_consume()?
+        };
+
+        // Exit doesn't fit in the current earliest epoch
+        if exit_balance > exit_balance_to_consume {
+            let balance_to_process = exit_balance.safe_sub(exit_balance_to_consume)?;
+            let additional_epochs = balance_to_process
+                .safe_sub(1)?
+                .safe_div(per_epoch_churn)?
+                .safe_add(1)?;
+            earliest_exit_epoch.safe_add_assign(additional_epochs)?;
+            exit_balance_to_consume
+                .safe_add_assign(additional_epochs.safe_mul(per_epoch_churn)?)?;
+        }
+
+        Ok((
+            earliest_exit_epoch,
+            exit_balance_to_consume.safe_sub(exit_balance)?,
+        ))
+    }
+
+    /// Compute the consolidation epoch for `consolidation_balance` and the consolidation churn
+    /// remaining in that epoch.
+    fn compute_consolidation_epoch_and_churn(
+        &self,
+        consolidation_balance: u64,
+        spec: &ChainSpec,
+    ) -> Result<(Epoch, u64), Error> {
+        let mut earliest_consolidation_epoch = std::cmp::max(
+            self.earliest_consolidation_epoch()?,
+            self.compute_activation_exit_epoch(self.current_epoch(), spec)?,
+        );
+
+        let per_epoch_consolidation_churn = self.get_consolidation_churn_limit(spec)?;
+
+        // New epoch for consolidations
+        let mut consolidation_balance_to_consume =
+            if self.earliest_consolidation_epoch()? < earliest_consolidation_epoch {
+                per_epoch_consolidation_churn
+            } else {
+                self.consolidation_balance_to_consume()?
+            };
+        // Consolidation doesn't fit in the current earliest epoch
+        if consolidation_balance > consolidation_balance_to_consume {
+            let balance_to_process =
+                consolidation_balance.safe_sub(consolidation_balance_to_consume)?;
+            let additional_epochs = balance_to_process
+                .safe_sub(1)?
+                .safe_div(per_epoch_consolidation_churn)?
+                .safe_add(1)?;
+            earliest_consolidation_epoch.safe_add_assign(additional_epochs)?;
+            consolidation_balance_to_consume
+                .safe_add_assign(additional_epochs.safe_mul(per_epoch_consolidation_churn)?)?;
+        }
+
+        Ok((
+            earliest_consolidation_epoch,
+            consolidation_balance_to_consume.safe_sub(consolidation_balance)?,
+        ))
+    }
+
     // ******* Electra mutators *******
 
     pub fn queue_excess_active_balance(
@@ -2231,33 +2370,12 @@ impl<E: EthSpec> BeaconState<E> {
         exit_balance: u64,
         spec: &ChainSpec,
     ) -> Result<Epoch, Error> {
-        let mut earliest_exit_epoch = std::cmp::max(
-            self.earliest_exit_epoch()?,
-            self.compute_activation_exit_epoch(self.current_epoch(), spec)?,
-        );
-
-        let per_epoch_churn = self.get_activation_exit_churn_limit(spec)?;
-        // New epoch for exits
-        let mut exit_balance_to_consume = if self.earliest_exit_epoch()? < earliest_exit_epoch {
-            per_epoch_churn
-        } else {
-            self.exit_balance_to_consume()?
-        };
+        let (earliest_exit_epoch, exit_balance_to_consume) =
+            self.compute_exit_epoch_and_churn(exit_balance, spec)?;
 
-        // Exit doesn't fit in the current earliest epoch
-        if exit_balance > exit_balance_to_consume {
-            let balance_to_process = exit_balance.safe_sub(exit_balance_to_consume)?;
-            let additional_epochs = balance_to_process
-                .safe_sub(1)?
-                .safe_div(per_epoch_churn)?
-                .safe_add(1)?;
-            earliest_exit_epoch.safe_add_assign(additional_epochs)?;
-            exit_balance_to_consume
-                .safe_add_assign(additional_epochs.safe_mul(per_epoch_churn)?)?;
-        }
-        let state = self.as_electra_mut()?;
         // Consume the balance and update state variables
-        state.exit_balance_to_consume = exit_balance_to_consume.safe_sub(exit_balance)?;
+        let state = self.as_electra_mut()?;
+        state.exit_balance_to_consume = exit_balance_to_consume;
         state.earliest_exit_epoch = earliest_exit_epoch;
 
         Ok(state.earliest_exit_epoch)
@@ -2268,36 +2386,12 @@ impl<E: EthSpec> BeaconState<E> {
         consolidation_balance: u64,
         spec: &ChainSpec,
     ) -> Result<Epoch, Error> {
-        let mut earliest_consolidation_epoch = std::cmp::max(
-            self.earliest_consolidation_epoch()?,
-            self.compute_activation_exit_epoch(self.current_epoch(), spec)?,
-        );
-
-        let per_epoch_consolidation_churn = self.get_consolidation_churn_limit(spec)?;
+        let (earliest_consolidation_epoch, consolidation_balance_to_consume) =
+            self.compute_consolidation_epoch_and_churn(consolidation_balance, spec)?;
 
-        // New epoch for consolidations
-        let mut consolidation_balance_to_consume =
-            if self.earliest_consolidation_epoch()? < earliest_consolidation_epoch {
-                per_epoch_consolidation_churn
-            } else {
-                self.consolidation_balance_to_consume()?
-            };
-        // Consolidation doesn't fit in the current earliest epoch
-        if consolidation_balance > consolidation_balance_to_consume {
-            let balance_to_process =
-                consolidation_balance.safe_sub(consolidation_balance_to_consume)?;
-            let additional_epochs = balance_to_process
-                .safe_sub(1)?
-                .safe_div(per_epoch_consolidation_churn)?
-                .safe_add(1)?;
-            earliest_consolidation_epoch.safe_add_assign(additional_epochs)?;
-            consolidation_balance_to_consume
-                .safe_add_assign(additional_epochs.safe_mul(per_epoch_consolidation_churn)?)?;
-        }
         // Consume the balance and update state variables
         let state = self.as_electra_mut()?;
-        state.consolidation_balance_to_consume =
-            consolidation_balance_to_consume.safe_sub(consolidation_balance)?;
+        state.consolidation_balance_to_consume = consolidation_balance_to_consume;
         state.earliest_consolidation_epoch = earliest_consolidation_epoch;
 
         Ok(state.earliest_consolidation_epoch)
@@ -2447,12 +2541,7 @@ impl<E: EthSpec> BeaconState<E> {
 
         let slot = Slot::from_ssz_bytes(slot_bytes)?;
         let fork_at_slot = spec.fork_name_at_slot::<E>(slot);
-
-        Ok(map_fork_name!(
-            fork_at_slot,
-            Self,
-            <_>::from_ssz_bytes(bytes)?
-        ))
+        Self::from_ssz_bytes_by_fork(bytes, fork_at_slot)
     }
 
     #[allow(clippy::arithmetic_side_effects)]
@@ -2480,6 +2569,127 @@ impl<E: EthSpec> BeaconState<E> {
         Ok(())
     }
 
+    /// Returns the name and tree hash root of each field of the state, in order.
+    fn merkle_fields(&self) -> Vec<(&'static str, Hash256)> {
+        let mut fields = vec![];
+        #[allow(clippy::arithmetic_side_effects)]
+        match self {
+            BeaconState::Base(state) => {
+                map_beacon_state_base_fields!(state, |name, field| {
+                    fields.push((name, field.tree_hash_root()));
+                });
+            }
+            BeaconState::Altair(state) => {
+                map_beacon_state_altair_fields!(state, |name, field| {
+                    fields.push((name, field.tree_hash_root()));
+                });
+            }
+            BeaconState::Bellatrix(state) => {
+                map_beacon_state_bellatrix_fields!(state, |name, field| {
+                    fields.push((name, field.tree_hash_root()));
+                });
+            }
+            BeaconState::Capella(state) => {
+                map_beacon_state_capella_fields!(state, |name, field| {
+                    fields.push((name, field.tree_hash_root()));
+                });
+            }
+            BeaconState::Deneb(state) => {
+                map_beacon_state_deneb_fields!(state, |name, field| {
+                    fields.push((name, field.tree_hash_root()));
+                });
+            }
+            BeaconState::Electra(state) => {
+                map_beacon_state_electra_fields!(state, |name, field| {
+                    fields.push((name, field.tree_hash_root()));
+                });
+            }
+        }
+        fields
+    }
+
+    /// Returns the nodes at `generalized_indices` of the Merkle tree of the state.
+    ///
+    /// Nodes down to the fields of the state are supported. Within fields which are lists or
+    /// vectors, nodes down to their elements and the lengths of lists are also supported.
+    pub fn merkle_nodes(&self, generalized_indices: &[usize]) -> Result<Vec<Hash256>, Error> {
+        let fields = self.merkle_fields();
+        let depth = self.num_fields_pow2().ilog2() as usize;
+        let field_chunks = fields
+            .iter()
+            .flat_map(|(_, root)| root.as_bytes())
+            .copied()
+            .collect::<Vec<_>>();
+
+        // Group the indices within each field, so that the elements of each list or vector are
+        // only hashed once.
+        let mut nodes = vec![None; generalized_indices.len()];
+        let mut indices_by_field = BTreeMap::<usize, Vec<(usize, usize)>>::new();
+        for (position, &generalized_index) in generalized_indices.iter().enumerate() {
+            match split_generalized_index(generalized_index, depth) {
+                Some((field_index, field_generalized_index)) if field_generalized_index > 1 => {
+                    indices_by_field
+                        .entry(field_index)
+                        .or_default()
+                        .push((position, field_generalized_index));
+                }
+                _ => {
+                    if let Some(node) = nodes.get_mut(position) {
+                        *node = chunks_node(&field_chunks, depth, generalized_index);
+                    }
+                }
+            }
+        }
+
+        for (field_index, indices) in indices_by_field {
+            let Some((name, _)) = fields.get(field_index) else {
+                continue;
+            };
+            let i = indices.iter().map(|(_, index)| *index).collect::<Vec<_>>();
+            let field_nodes = match *name {
+                "block_roots" => vector_nodes(self.block_roots(), &i),
+                "state_roots" => vector_nodes(self.state_roots(), &i),
+                "historical_roots" => list_nodes(self.historical_roots(), &i),
+                "eth1_data_votes" => list_nodes(self.eth1_data_votes(), &i),
+                "validators" => list_nodes(self.validators(), &i),
+                "balances" => list_nodes(self.balances(), &i),
+                "randao_mixes" => vector_nodes(self.randao_mixes(), &i),
+                "slashings" => vector_nodes(self.slashings(), &i),
+                "previous_epoch_attestations" => {
+                    list_nodes(self.previous_epoch_attestations()?, &i)
+                }
+                "current_epoch_attestations" => list_nodes(self.current_epoch_attestations()?, &i),
+                "previous_epoch_participation" => {
+                    list_nodes(self.previous_epoch_participation()?, &i)
+                }
+                "current_epoch_participation" => {
+                    list_nodes(self.current_epoch_participation()?, &i)
+                }
+                "inactivity_scores" => list_nodes(self.inactivity_scores()?, &i),
+                "historical_summaries" => list_nodes(self.historical_summaries()?, &i),
+                "pending_balance_deposits" => list_nodes(self.pending_balance_deposits()?, &i),
+                "pending_partial_withdrawals" => {
+                    list_nodes(self.pending_partial_withdrawals()?, &i)
+                }
+                "pending_consolidations" => list_nodes(self.pending_consolidations()?, &i),
+                _ => continue,
+            };
+            for ((position, _), field_node) in indices.into_iter().zip(field_nodes) {
+                if let Some(node) = nodes.get_mut(position) {
+                    *node = field_node;
+                }
+            }
+        }
+
+        generalized_indices
+            .iter()
+            .zip(nodes)
+            .map(|(&generalized_index, node)| {
+                node.ok_or(Error::IndexNotSupported(generalized_index))
+            })
+            .collect()
+    }
+
     pub fn compute_merkle_proof(&self, generalized_index: usize) -> Result<Vec<Hash256>, Error> {
         // 1. Convert generalized index to field index.
         let field_index = match generalized_index {
@@ -2507,40 +2717,11 @@ impl<E: EthSpec> BeaconState<E> {
         };
 
         // 2. Get all `BeaconState` leaves.
-        let mut leaves = vec![];
-        #[allow(clippy::arithmetic_side_effects)]
-        match self {
-            BeaconState::Base(state) => {
-                map_beacon_state_base_fields!(state, |_, field| {
-                    leaves.push(field.tree_hash_root());
-                });
-            }
-            BeaconState::Altair(state) => {
-                map_beacon_state_altair_fields!(state, |_, field| {
-                    leaves.push(field.tree_hash_root());
-                });
-            }
-            BeaconState::Bellatrix(state) => {
-                map_beacon_state_bellatrix_fields!(state, |_, field| {
-                    leaves.push(field.tree_hash_root());
-                });
-            }
-            BeaconState::Capella(state) => {
-                map_beacon_state_capella_fields!(state, |_, field| {
-                    leaves.push(field.tree_hash_root());
-                });
-            }
-            BeaconState::Deneb(state) => {
-                map_beacon_state_deneb_fields!(state, |_, field| {
-                    leaves.push(field.tree_hash_root());
-                });
-            }
-            BeaconState::Electra(state) => {
-                map_beacon_state_electra_fields!(state, |_, field| {
-                    leaves.push(field.tree_hash_root());
-                });
-            }
-        };
+        let leaves = self
+            .merkle_fields()
+            .into_iter()
+            .map(|(_, root)| root)
+            .collect::<Vec<_>>();
 
         // 3. Make deposit tree.
         // Use the depth of the `BeaconState` fields (i.e. `log2(32) = 5`).
@@ -2557,6 +2738,27 @@ impl<E: EthSpec> BeaconState<E> {
     }
 }
 
+/// Compute the seed of `epoch` for `domain_type` from the RANDAO `mix` at the end of epoch
+/// `epoch - MIN_SEED_LOOKAHEAD - 1`.
+///
+/// Spec v0.12.1
+pub fn compute_seed(mix: &Hash256, epoch: Epoch, domain_type: Domain, spec: &ChainSpec) -> Hash256 {
+    let domain_bytes = int_to_bytes4(spec.get_domain_constant(domain_type));
+    let epoch_bytes = int_to_bytes8(epoch.as_u64());
+
+    const NUM_DOMAIN_BYTES: usize = 4;
+    const NUM_EPOCH_BYTES: usize = 8;
+    const MIX_OFFSET: usize = NUM_DOMAIN_BYTES + NUM_EPOCH_BYTES;
+    const NUM_MIX_BYTES: usize = 32;
+
+    let mut preimage = [0; NUM_DOMAIN_BYTES + NUM_EPOCH_BYTES + NUM_MIX_BYTES];
+    preimage[0..NUM_DOMAIN_BYTES].copy_from_slice(&domain_bytes);
+    preimage[NUM_DOMAIN_BYTES..MIX_OFFSET].copy_from_slice(&epoch_bytes);
+    preimage[MIX_OFFSET..].copy_from_slice(mix.as_bytes());
+
+    Hash256::from_slice(&hash(&preimage))
+}
+
 impl From<RelativeEpochError> for Error {
     fn from(e: RelativeEpochError) -> Error {
         Error::RelativeEpochError(e)
@@ -2613,6 +2815,12 @@ impl<E: EthSpec> CompareFields for BeaconState<E> {
     }
 }
 
+impl<E: EthSpec> ForkVersionDecode for BeaconState<E> {
+    fn from_ssz_bytes_by_fork(bytes: &[u8], fork_name: ForkName) -> Result<Self, DecodeError> {
+        Ok(map_fork_name!(fork_name, Self, <_>::from_ssz_bytes(bytes)?))
+    }
+}
+
 impl<E: EthSpec> ForkVersionDeserialize for BeaconState<E> {
     fn deserialize_by_fork<'de, D: serde::Deserializer<'de>>(
         value: serde_json::value::Value,
@@ -16,6 +16,20 @@ impl Balance {
         Self { raw: 0, minimum }
     }
 
+    /// Initialize the balance to `raw`, which may be below the given `minimum`.
+    pub fn from_raw(raw: u64, minimum: u64) -> Self {
+        Self { raw, minimum }
+    }
+
+    /// Returns the balance without respect to the `minimum`.
+    pub fn raw(&self) -> u64 {
+        self.raw
+    }
+
+    pub fn minimum(&self) -> u64 {
+        self.minimum
+    }
+
     /// Returns the balance with respect to the initialization `minimum`.
     pub fn get(&self) -> u64 {
         std::cmp::max(self.raw, self.minimum)
@@ -1,7 +1,7 @@
 #![allow(clippy::arithmetic_side_effects)]
 
 use crate::*;
-use core::num::NonZeroUsize;
+use core::num::{NonZeroU32, NonZeroUsize};
 use derivative::Derivative;
 use safe_arith::SafeArith;
 use serde::{Deserialize, Serialize};
@@ -20,14 +20,26 @@ four_byte_option_impl!(four_byte_option_non_zero_usize, NonZeroUsize);
 
 /// Computes and stores the shuffling for an epoch. Provides various getters to allow callers to
 /// read the committees for the given epoch.
+///
+/// The shuffling of each epoch is independent, so each cache owns its own allocation. Caches are
+/// shared by `Arc` between the states and the shuffling cache which use the same epoch.
 #[derive(Derivative, Debug, Default, Clone, Serialize, Deserialize, Encode, Decode)]
 #[derivative(PartialEq)]
 pub struct CommitteeCache {
     #[ssz(with = "four_byte_option_epoch")]
     initialized_epoch: Option<Epoch>,
-    shuffling: Vec<usize>,
+    /// The shuffled indices of the active validators.
+    ///
+    /// Indices are stored in four bytes rather than eight to halve the memory footprint, but are
+    /// still encoded in eight bytes so that the on-disk format is unchanged.
+    #[ssz(with = "eight_byte_validator_indices")]
+    shuffling: Vec<u32>,
+    /// The position of each validator in `shuffling` (plus one), indexed by validator index.
+    ///
+    /// This list has an entry for every validator in the state, so positions are stored in four
+    /// bytes rather than eight to halve its memory footprint.
     #[derivative(PartialEq(compare_with = "compare_shuffling_positions"))]
-    shuffling_positions: Vec<NonZeroUsizeOption>,
+    shuffling_positions: Vec<ShufflingPosition>,
     committees_per_slot: u64,
     slots_per_epoch: u64,
 }
@@ -42,7 +54,7 @@ pub struct CommitteeCache {
 ///
 /// In practice this is only used in tests.
 #[allow(clippy::indexing_slicing)]
-fn compare_shuffling_positions(xs: &Vec<NonZeroUsizeOption>, ys: &Vec<NonZeroUsizeOption>) -> bool {
+fn compare_shuffling_positions(xs: &Vec<ShufflingPosition>, ys: &Vec<ShufflingPosition>) -> bool {
     use std::cmp::Ordering;
 
     let (shorter, longer) = match xs.len().cmp(&ys.len()) {
@@ -55,7 +67,7 @@ fn compare_shuffling_positions(xs: &Vec<NonZeroUsizeOption>, ys: &Vec<NonZeroUsi
     shorter == &longer[..shorter.len()]
         && longer[shorter.len()..]
             .iter()
-            .all(|new| *new == NonZeroUsizeOption(None))
+            .all(|new| *new == ShufflingPosition(None))
 }
 
 impl CommitteeCache {
@@ -80,17 +92,33 @@ impl CommitteeCache {
             return Err(Error::EpochOutOfBounds);
         }
 
+        let seed = state.get_seed(epoch, Domain::BeaconAttester, spec)?;
+
+        Self::initialized_from_validators::<E>(state.validators(), epoch, seed, spec)
+    }
+
+    /// Return a new, fully initialized cache for `epoch` from the attester `seed` of `epoch` and
+    /// the `validators` of a state from `epoch` or later.
+    ///
+    /// This allows the committees of an epoch to be computed without a state from that epoch.
+    pub fn initialized_from_validators<E: EthSpec>(
+        validators: &Validators<E>,
+        epoch: Epoch,
+        seed: Hash256,
+        spec: &ChainSpec,
+    ) -> Result<Arc<CommitteeCache>, Error> {
         // May cause divide-by-zero errors.
         if E::slots_per_epoch() == 0 {
             return Err(Error::ZeroSlotsPerEpoch);
         }
 
-        // The use of `NonZeroUsize` reduces the maximum number of possible validators by one.
-        if state.validators().len() == usize::MAX {
+        // Validator indices are stored as a `u32` and shuffling positions as a `NonZeroU32`, which
+        // limits the number of validators to one less than `u32::MAX`.
+        if validators.len() >= u32::MAX as usize {
             return Err(Error::TooManyValidators);
         }
 
-        let active_validator_indices = get_active_validator_indices(state.validators(), epoch);
+        let active_validator_indices = get_active_validator_indices(validators, epoch);
 
         if active_validator_indices.is_empty() {
             return Err(Error::InsufficientValidators);
@@ -99,21 +127,24 @@ impl CommitteeCache {
         let committees_per_slot =
             E::get_committee_count_per_slot(active_validator_indices.len(), spec)? as u64;
 
-        let seed = state.get_seed(epoch, Domain::BeaconAttester, spec)?;
-
+        // The number of validators was checked above, so the indices fit in a `u32`.
         let shuffling = shuffle_list(
             active_validator_indices,
             spec.shuffle_round_count,
             &seed[..],
             false,
         )
-        .ok_or(Error::UnableToShuffle)?;
+        .ok_or(Error::UnableToShuffle)?
+        .into_iter()
+        .map(|v| v as u32)
+        .collect::<Vec<_>>();
 
-        let mut shuffling_positions = vec![<_>::default(); state.validators().len()];
+        let mut shuffling_positions = vec![<_>::default(); validators.len()];
         for (i, &v) in shuffling.iter().enumerate() {
             *shuffling_positions
-                .get_mut(v)
-                .ok_or(Error::ShuffleIndexOutOfBounds(v))? = NonZeroUsize::new(i + 1).into();
+                .get_mut(v as usize)
+                .ok_or(Error::ShuffleIndexOutOfBounds(v as usize))? =
+                NonZeroU32::new(i as u32 + 1).into();
         }
 
         Ok(Arc::new(CommitteeCache {
@@ -139,7 +170,7 @@ impl CommitteeCache {
     /// Always returns `&[]` for a non-initialized epoch.
     ///
     /// Spec v0.12.1
-    pub fn active_validator_indices(&self) -> &[usize] {
+    pub fn active_validator_indices(&self) -> &[u32] {
         &self.shuffling
     }
 
@@ -148,7 +179,7 @@ impl CommitteeCache {
     /// Always returns `&[]` for a non-initialized epoch.
     ///
     /// Spec v0.12.1
-    pub fn shuffling(&self) -> &[usize] {
+    pub fn shuffling(&self) -> &[u32] {
         &self.shuffling
     }
 
@@ -285,7 +316,7 @@ impl CommitteeCache {
     /// Returns a slice of `self.shuffling` that represents the `index`'th committee in the epoch.
     ///
     /// Spec v0.12.1
-    fn compute_committee(&self, index: usize) -> Option<&[usize]> {
+    fn compute_committee(&self, index: usize) -> Option<&[u32]> {
         self.shuffling.get(self.compute_committee_range(index)?)
     }
 
@@ -307,7 +338,7 @@ impl CommitteeCache {
         self.shuffling_positions
             .get(validator_index)?
             .0
-            .map(|p| p.get() - 1)
+            .map(|p| p.get() as usize - 1)
     }
 }
 
@@ -377,20 +408,31 @@ impl arbitrary::Arbitrary<'_> for CommitteeCache {
     }
 }
 
+/// The position of a validator in the shuffling (plus one), if it is active.
+///
 /// This is a shim struct to ensure that we can encode a `Vec<Option<NonZeroUsize>>` an SSZ union
 /// with a four-byte selector. The SSZ specification changed from four bytes to one byte during 2021
 /// and we use this shim to avoid breaking the Lighthouse database.
-#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+///
+/// In memory the value is stored as a `NonZeroU32`, however it is still encoded as a
+/// `NonZeroUsize` so that the on-disk format is unchanged.
+#[derive(Debug, Default, PartialEq, Clone, Copy, Serialize, Deserialize)]
 #[serde(transparent)]
-struct NonZeroUsizeOption(Option<NonZeroUsize>);
+struct ShufflingPosition(Option<NonZeroU32>);
 
-impl From<Option<NonZeroUsize>> for NonZeroUsizeOption {
-    fn from(opt: Option<NonZeroUsize>) -> Self {
+impl ShufflingPosition {
+    fn to_non_zero_usize(self) -> Option<NonZeroUsize> {
+        self.0.and_then(|p| NonZeroUsize::new(p.get() as usize))
+    }
+}
+
+impl From<Option<NonZeroU32>> for ShufflingPosition {
+    fn from(opt: Option<NonZeroU32>) -> Self {
         Self(opt)
     }
 }
 
-impl Encode for NonZeroUsizeOption {
+impl Encode for ShufflingPosition {
     fn is_ssz_fixed_len() -> bool {
         four_byte_option_non_zero_usize::encode::is_ssz_fixed_len()
     }
@@ -400,19 +442,19 @@ impl Encode for NonZeroUsizeOption {
     }
 
     fn ssz_bytes_len(&self) -> usize {
-        four_byte_option_non_zero_usize::encode::ssz_bytes_len(&self.0)
+        four_byte_option_non_zero_usize::encode::ssz_bytes_len(&self.to_non_zero_usize())
     }
 
     fn ssz_append(&self, buf: &mut Vec<u8>) {
-        four_byte_option_non_zero_usize::encode::ssz_append(&self.0, buf)
+        four_byte_option_non_zero_usize::encode::ssz_append(&self.to_non_zero_usize(), buf)
     }
 
     fn as_ssz_bytes(&self) -> Vec<u8> {
-        four_byte_option_non_zero_usize::encode::as_ssz_bytes(&self.0)
+        four_byte_option_non_zero_usize::encode::as_ssz_bytes(&self.to_non_zero_usize())
     }
 }
 
-impl Decode for NonZeroUsizeOption {
+impl Decode for ShufflingPosition {
     fn is_ssz_fixed_len() -> bool {
         four_byte_option_non_zero_usize::decode::is_ssz_fixed_len()
     }
@@ -422,6 +464,74 @@ impl Decode for NonZeroUsizeOption {
     }
 
     fn from_ssz_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
-        four_byte_option_non_zero_usize::decode::from_ssz_bytes(bytes).map(Self)
+        four_byte_option_non_zero_usize::decode::from_ssz_bytes(bytes)?
+            .map(NonZeroU32::try_from)
+            .transpose()
+            .map(Self)
+            .map_err(|_| DecodeError::BytesInvalid("shuffling position exceeds u32".into()))
+    }
+}
+
+/// Encodes a `Vec<u32>` of validator indices as a list of `u64`, as they were encoded when they
+/// were stored as `usize`.
+mod eight_byte_validator_indices {
+    pub mod encode {
+        use ssz::Encode;
+
+        pub fn is_ssz_fixed_len() -> bool {
+            <Vec<u64> as Encode>::is_ssz_fixed_len()
+        }
+
+        pub fn ssz_fixed_len() -> usize {
+            <Vec<u64> as Encode>::ssz_fixed_len()
+        }
+
+        pub fn ssz_bytes_len(indices: &[u32]) -> usize {
+            indices.len() * <u64 as Encode>::ssz_fixed_len()
+        }
+
+        pub fn ssz_append(indices: &[u32], buf: &mut Vec<u8>) {
+            buf.reserve(ssz_bytes_len(indices));
+            for &index in indices {
+                u64::from(index).ssz_append(buf);
+            }
+        }
+
+        pub fn as_ssz_bytes(indices: &[u32]) -> Vec<u8> {
+            let mut buf = vec![];
+            ssz_append(indices, &mut buf);
+            buf
+        }
+    }
+
+    pub mod decode {
+        use ssz::{Decode, DecodeError};
+
+        pub fn is_ssz_fixed_len() -> bool {
+            <Vec<u64> as Decode>::is_ssz_fixed_len()
+        }
+
+        pub fn ssz_fixed_len() -> usize {
+            <Vec<u64> as Decode>::ssz_fixed_len()
+        }
+
+        pub fn from_ssz_bytes(bytes: &[u8]) -> Result<Vec<u32>, DecodeError> {
+            let index_len = <u64 as Decode>::ssz_fixed_len();
+            if bytes.len() % index_len != 0 {
+                return Err(DecodeError::InvalidByteLength {
+                    len: bytes.len(),
+                    expected: bytes.len() / index_len * index_len,
+                });
+            }
+            bytes
+                .chunks(index_len)
+                .map(|chunk| {
+                    let index = u64::from_ssz_bytes(chunk)?;
+                    u32::try_from(index).map_err(|_| {
+                        DecodeError::BytesInvalid(format!("validator index {index} exceeds u32"))
+                    })
+                })
+                .collect()
+        }
     }
 }
@@ -115,12 +115,15 @@ async fn shuffles_for_the_right_epoch() {
             false,
         )
         .unwrap()
+        .into_iter()
+        .map(|v| v as u32)
+        .collect::<Vec<_>>()
     };
 
     let assert_shuffling_positions_accurate = |cache: &CommitteeCache| {
         for (i, v) in cache.shuffling().iter().enumerate() {
             assert_eq!(
-                cache.shuffled_position(*v).unwrap(),
+                cache.shuffled_position(*v as usize).unwrap(),
                 i,
                 "Shuffling position inaccurate"
             );
@@ -165,3 +168,50 @@ async fn min_randao_epoch_correct() {
     state.get_randao_mix(min_randao_epoch - 1).unwrap_err();
     state.get_randao_mix(min_randao_epoch + 1).unwrap();
 }
+
+#[test]
+fn shuffling_position_encoding_unchanged() {
+    use super::{four_byte_option_non_zero_usize, ShufflingPosition};
+    use ssz::{Decode, Encode};
+    use std::num::{NonZeroU32, NonZeroUsize};
+
+    for position in [None, Some(1), Some(u32::MAX)] {
+        let compact = ShufflingPosition(position.and_then(NonZeroU32::new));
+        let legacy = position.and_then(|p| NonZeroUsize::new(p as usize));
+
+        let bytes = compact.as_ssz_bytes();
+        assert_eq!(
+            bytes,
+            four_byte_option_non_zero_usize::encode::as_ssz_bytes(&legacy)
+        );
+        assert_eq!(ShufflingPosition::from_ssz_bytes(&bytes).unwrap(), compact);
+    }
+
+    // Positions which do not fit in a `u32` are rejected.
+    let too_large = NonZeroUsize::new(u32::MAX as usize + 1);
+    let bytes = four_byte_option_non_zero_usize::encode::as_ssz_bytes(&too_large);
+    assert!(ShufflingPosition::from_ssz_bytes(&bytes).is_err());
+}
+
+#[test]
+fn shuffling_encoding_unchanged() {
+    use super::eight_byte_validator_indices;
+    use ssz::Encode;
+
+    let compact: Vec<u32> = vec![0, 7, 3, u32::MAX];
+    let legacy: Vec<u64> = compact.iter().map(|&i| u64::from(i)).collect();
+
+    let bytes = eight_byte_validator_indices::encode::as_ssz_bytes(&compact);
+    assert_eq!(bytes, legacy.as_ssz_bytes());
+    assert_eq!(
+        eight_byte_validator_indices::decode::from_ssz_bytes(&bytes).unwrap(),
+        compact
+    );
+
+    // Indices which do not fit in a `u32` are rejected.
+    let too_large = vec![u32::MAX as u64 + 1].as_ssz_bytes();
+    assert!(eight_byte_validator_indices::decode::from_ssz_bytes(&too_large).is_err());
+
+    // Truncated indices are rejected.
+    assert!(eight_byte_validator_indices::decode::from_ssz_bytes(&bytes[..9]).is_err());
+}
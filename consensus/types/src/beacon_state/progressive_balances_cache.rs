@@ -8,6 +8,8 @@ use crate::{
 };
 use arbitrary::Arbitrary;
 use safe_arith::SafeArith;
+use ssz::{Decode, DecodeError, Encode};
+use ssz_derive::{Decode, Encode};
 
 /// This cache keeps track of the accumulated target attestation balance for the current & previous
 /// epochs. The cached values can be utilised by fork choice to calculate unrealized justification
@@ -25,6 +27,17 @@ struct Inner {
     pub current_epoch_cache: EpochTotalBalances,
 }
 
+/// The representation of an initialized `ProgressiveBalancesCache` which is stored alongside its
+/// state in the database, so that the cache doesn't need to be recomputed from the participation
+/// of every validator when the state is loaded.
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct PersistedInner {
+    current_epoch: Epoch,
+    minimum_balance: u64,
+    previous_epoch_flag_balances: Vec<u64>,
+    current_epoch_flag_balances: Vec<u64>,
+}
+
 /// Caches the participation values for one epoch (either the previous or current).
 #[derive(PartialEq, Debug, Clone, Arbitrary)]
 pub struct EpochTotalBalances {
@@ -270,6 +283,61 @@ impl ProgressiveBalancesCache {
         self.current_epoch_flag_attesting_balance(TIMELY_HEAD_FLAG_INDEX)
     }
 
+    /// Encode the cache for storage in the database, or return `None` if it isn't initialized.
+    pub fn as_persisted_bytes(&self) -> Option<Vec<u8>> {
+        let inner = self.inner.as_ref()?;
+        let raw_balances = |cache: &EpochTotalBalances| {
+            cache
+                .total_flag_balances
+                .iter()
+                .map(Balance::raw)
+                .collect::<Vec<_>>()
+        };
+        let persisted = PersistedInner {
+            current_epoch: inner.current_epoch,
+            minimum_balance: inner
+                .current_epoch_cache
+                .total_flag_balances
+                .first()
+                .map_or(0, Balance::minimum),
+            previous_epoch_flag_balances: raw_balances(&inner.previous_epoch_cache),
+            current_epoch_flag_balances: raw_balances(&inner.current_epoch_cache),
+        };
+        Some(persisted.as_ssz_bytes())
+    }
+
+    /// Decode a cache encoded by `as_persisted_bytes`.
+    pub fn from_persisted_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let persisted = PersistedInner::from_ssz_bytes(bytes)?;
+        let epoch_cache = |raw_balances: &[u64]| -> Result<EpochTotalBalances, DecodeError> {
+            let total_flag_balances = <[u64; NUM_FLAG_INDICES]>::try_from(raw_balances)
+                .map_err(|_| {
+                    DecodeError::BytesInvalid(format!(
+                        "Expected {} flag balances, got {}",
+                        NUM_FLAG_INDICES,
+                        raw_balances.len()
+                    ))
+                })?
+                .map(|raw| Balance::from_raw(raw, persisted.minimum_balance));
+            Ok(EpochTotalBalances {
+                total_flag_balances,
+            })
+        };
+
+        let mut cache = Self::default();
+        cache.initialize(
+            persisted.current_epoch,
+            epoch_cache(&persisted.previous_epoch_flag_balances)?,
+            epoch_cache(&persisted.current_epoch_flag_balances)?,
+        );
+        Ok(cache)
+    }
+
+    /// Returns the epoch of the state that the cache was initialized for, if any.
+    pub fn current_epoch(&self) -> Option<Epoch> {
+        self.inner.as_ref().map(|inner| inner.current_epoch)
+    }
+
     fn get_inner_mut(&mut self) -> Result<&mut Inner, BeaconStateError> {
         self.inner
             .as_mut()
@@ -294,3 +362,39 @@ pub fn is_progressive_balances_enabled<E: EthSpec>(state: &BeaconState<E>) -> bo
         | BeaconState::Electra(_) => true,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MainnetEthSpec;
+
+    #[test]
+    fn persisted_round_trip() {
+        let spec = MainnetEthSpec::default_spec();
+        let mut cache = ProgressiveBalancesCache::default();
+        assert_eq!(cache.as_persisted_bytes(), None);
+
+        let mut previous_epoch_cache = EpochTotalBalances::new(&spec);
+        previous_epoch_cache
+            .on_new_attestation(false, TIMELY_TARGET_FLAG_INDEX, 32_000_000_000)
+            .unwrap();
+        let current_epoch_cache = EpochTotalBalances::new(&spec);
+        cache.initialize(Epoch::new(7), previous_epoch_cache, current_epoch_cache);
+
+        let bytes = cache.as_persisted_bytes().unwrap();
+        let decoded = ProgressiveBalancesCache::from_persisted_bytes(&bytes).unwrap();
+        assert_eq!(decoded, cache);
+        assert_eq!(decoded.current_epoch(), Some(Epoch::new(7)));
+        assert_eq!(
+            decoded.previous_epoch_target_attesting_balance(),
+            Ok(32_000_000_000)
+        );
+        // An empty balance still reads as the minimum after decoding.
+        assert_eq!(
+            decoded.current_epoch_target_attesting_balance(),
+            Ok(spec.effective_balance_increment)
+        );
+
+        assert!(ProgressiveBalancesCache::from_persisted_bytes(&bytes[1..]).is_err());
+    }
+}
@@ -10,6 +10,7 @@ use lazy_static::lazy_static;
 use ssz::Encode;
 use std::ops::Mul;
 use swap_or_not_shuffle::compute_shuffled_index;
+use tree_hash::TreeHash;
 
 pub const MAX_VALIDATOR_COUNT: usize = 129;
 pub const SLOT_OFFSET: Slot = Slot::new(1);
@@ -178,7 +179,9 @@ mod committees {
         let mut ordered_indices = state
             .get_cached_active_validator_indices(relative_epoch)
             .unwrap()
-            .to_vec();
+            .iter()
+            .map(|&i| i as usize)
+            .collect::<Vec<_>>();
         ordered_indices.sort_unstable();
         assert_eq!(
             active_indices, ordered_indices,
@@ -215,14 +218,14 @@ mod committees {
                 for (committee_i, validator_i) in bc.committee.iter().enumerate() {
                     // Assert the validators are assigned contiguously across committees.
                     assert_eq!(
-                        *validator_i,
+                        *validator_i as usize,
                         *expected_indices_iter.next().unwrap(),
                         "Non-sequential validators."
                     );
                     // Assert a call to `get_attestation_duties` is consistent with a call to
                     // `get_beacon_committees_at_slot`
                     let attestation_duty = state
-                        .get_attestation_duties(*validator_i, relative_epoch)
+                        .get_attestation_duties(*validator_i as usize, relative_epoch)
                         .unwrap()
                         .unwrap();
                     assert_eq!(attestation_duty.slot, slot);
@@ -345,6 +348,226 @@ mod get_outstanding_deposit_len {
     }
 }
 
+mod electra_pending_queues {
+    use super::*;
+    use beacon_chain::types::{
+        ForkName, PendingBalanceDeposit, PendingConsolidation, PendingPartialWithdrawal,
+    };
+
+    type E = MinimalEthSpec;
+
+    fn state_and_spec() -> (BeaconState<E>, ChainSpec) {
+        let spec = ForkName::Electra.make_genesis_spec(E::default_spec());
+        let harness = BeaconChainHarness::builder(E::default())
+            .spec(spec.clone())
+            .keypairs(KEYPAIRS[0..16].to_vec())
+            .mock_execution_layer()
+            .fresh_ephemeral_store()
+            .build();
+        let mut state = harness.get_current_state();
+        state.build_total_active_balance_cache(&spec).unwrap();
+        (state, spec)
+    }
+
+    fn partial_withdrawal(index: u64) -> PendingPartialWithdrawal {
+        PendingPartialWithdrawal {
+            index,
+            amount: 1,
+            withdrawable_epoch: Epoch::new(0),
+        }
+    }
+
+    fn consolidation(source_index: u64, target_index: u64) -> PendingConsolidation {
+        PendingConsolidation {
+            source_index,
+            target_index,
+        }
+    }
+
+    #[test]
+    fn queue_positions() {
+        let (mut state, _) = state_and_spec();
+
+        for (index, amount) in [(0, 1), (1, 2), (0, 3)] {
+            state
+                .pending_balance_deposits_mut()
+                .unwrap()
+                .push(PendingBalanceDeposit { index, amount })
+                .unwrap();
+        }
+        assert_eq!(state.get_pending_balance_to_deposit(0), Ok(4));
+        assert_eq!(state.get_pending_balance_to_deposit(1), Ok(2));
+        assert_eq!(state.get_pending_balance_to_deposit(2), Ok(0));
+
+        for index in [1, 0, 1] {
+            state
+                .pending_partial_withdrawals_mut()
+                .unwrap()
+                .push(partial_withdrawal(index))
+                .unwrap();
+        }
+        assert_eq!(
+            state.get_pending_partial_withdrawal_positions(1),
+            Ok(vec![0, 2])
+        );
+        assert_eq!(
+            state.get_pending_partial_withdrawal_positions(0),
+            Ok(vec![1])
+        );
+        assert_eq!(
+            state.get_pending_partial_withdrawal_positions(2),
+            Ok(vec![])
+        );
+
+        for (source, target) in [(2, 3), (4, 2)] {
+            state
+                .pending_consolidations_mut()
+                .unwrap()
+                .push(consolidation(source, target))
+                .unwrap();
+        }
+        assert_eq!(state.get_pending_consolidation_position(2), Ok(Some(0)));
+        assert_eq!(state.get_pending_consolidation_position(4), Ok(Some(1)));
+        // Only the source of a consolidation is considered.
+        assert_eq!(state.get_pending_consolidation_position(3), Ok(None));
+    }
+
+    #[test]
+    fn queue_capacity() {
+        let (mut state, _) = state_and_spec();
+
+        let limit = E::pending_partial_withdrawals_limit();
+        for index in 0..limit as u64 - 1 {
+            state
+                .pending_partial_withdrawals_mut()
+                .unwrap()
+                .push(partial_withdrawal(index))
+                .unwrap();
+        }
+        assert_eq!(state.pending_partial_withdrawals_has_capacity(), Ok(true));
+        state
+            .pending_partial_withdrawals_mut()
+            .unwrap()
+            .push(partial_withdrawal(0))
+            .unwrap();
+        assert_eq!(state.pending_partial_withdrawals_has_capacity(), Ok(false));
+
+        let limit = E::pending_consolidations_limit();
+        for index in 0..limit as u64 - 1 {
+            state
+                .pending_consolidations_mut()
+                .unwrap()
+                .push(consolidation(index, index + 1))
+                .unwrap();
+        }
+        assert_eq!(state.pending_consolidations_has_capacity(), Ok(true));
+        state
+            .pending_consolidations_mut()
+            .unwrap()
+            .push(consolidation(0, 1))
+            .unwrap();
+        assert_eq!(state.pending_consolidations_has_capacity(), Ok(false));
+    }
+
+    #[tokio::test]
+    async fn queue_accessors_require_electra() {
+        let state = build_state::<E>(16).await;
+        assert!(state.get_pending_balance_to_deposit(0).is_err());
+        assert!(state.get_pending_partial_withdrawal_positions(0).is_err());
+        assert!(state.get_pending_consolidation_position(0).is_err());
+        assert!(state.pending_partial_withdrawals_has_capacity().is_err());
+        assert!(state.pending_consolidations_has_capacity().is_err());
+    }
+
+    #[test]
+    fn exit_epoch_for_balance() {
+        let (mut state, spec) = state_and_spec();
+        *state.earliest_exit_epoch_mut().unwrap() = Epoch::new(0);
+
+        let churn = state.get_activation_exit_churn_limit(&spec).unwrap();
+        let exit_epoch = state
+            .compute_activation_exit_epoch(state.current_epoch(), &spec)
+            .unwrap();
+        let exit_balance_to_consume = state.exit_balance_to_consume().unwrap();
+
+        // An exit which uses exactly the churn of an epoch fits in that epoch.
+        assert_eq!(
+            state.get_exit_epoch_for_balance(churn, &spec),
+            Ok(exit_epoch)
+        );
+        assert_eq!(
+            state.get_exit_epoch_for_balance(churn + 1, &spec),
+            Ok(exit_epoch + 1)
+        );
+        assert_eq!(
+            state.get_exit_epoch_for_balance(2 * churn + 1, &spec),
+            Ok(exit_epoch + 2)
+        );
+
+        // Querying the exit epoch consumes no churn.
+        assert_eq!(state.exit_balance_to_consume(), Ok(exit_balance_to_consume));
+        assert_eq!(state.earliest_exit_epoch(), Ok(Epoch::new(0)));
+
+        // Updating the churn yields the same epoch, and leaves the rest of that epoch's churn.
+        assert_eq!(
+            state.compute_exit_epoch_and_update_churn(churn + 1, &spec),
+            Ok(exit_epoch + 1)
+        );
+        assert_eq!(state.exit_balance_to_consume(), Ok(churn - 1));
+
+        // Further exits are queued behind the remaining churn.
+        assert_eq!(
+            state.get_exit_epoch_for_balance(churn - 1, &spec),
+            Ok(exit_epoch + 1)
+        );
+        assert_eq!(
+            state.get_exit_epoch_for_balance(churn, &spec),
+            Ok(exit_epoch + 2)
+        );
+    }
+
+    #[test]
+    fn consolidation_epoch_for_balance() {
+        let (mut state, mut spec) = state_and_spec();
+        *state.earliest_consolidation_epoch_mut().unwrap() = Epoch::new(0);
+
+        // With few validators all of the balance churn goes to activations and exits, so there is
+        // no churn left for consolidations.
+        assert_eq!(state.get_consolidation_churn_limit(&spec), Ok(0));
+        assert!(state.get_consolidation_epoch_for_balance(1, &spec).is_err());
+
+        // Halve the activation and exit churn to leave some for consolidations.
+        spec.max_per_epoch_activation_exit_churn_limit = spec.min_per_epoch_churn_limit_electra / 2;
+        let churn = state.get_consolidation_churn_limit(&spec).unwrap();
+        assert!(churn > 0);
+        let consolidation_epoch = state
+            .compute_activation_exit_epoch(state.current_epoch(), &spec)
+            .unwrap();
+
+        assert_eq!(
+            state.get_consolidation_epoch_for_balance(churn, &spec),
+            Ok(consolidation_epoch)
+        );
+        assert_eq!(
+            state.get_consolidation_epoch_for_balance(churn + 1, &spec),
+            Ok(consolidation_epoch + 1)
+        );
+
+        // Querying the consolidation epoch consumes no churn.
+        assert_eq!(state.earliest_consolidation_epoch(), Ok(Epoch::new(0)));
+
+        assert_eq!(
+            state.compute_consolidation_epoch_and_update_churn(churn, &spec),
+            Ok(consolidation_epoch)
+        );
+        assert_eq!(state.consolidation_balance_to_consume(), Ok(0));
+        assert_eq!(
+            state.get_consolidation_epoch_for_balance(1, &spec),
+            Ok(consolidation_epoch + 1)
+        );
+    }
+}
+
 #[test]
 fn decode_base_and_altair() {
     type E = MainnetEthSpec;
@@ -404,3 +627,41 @@ fn decode_base_and_altair() {
             .expect_err("bad altair state cannot be decoded");
     }
 }
+
+#[tokio::test]
+async fn merkle_multiproof_of_fields_and_list_elements() {
+    let mut state = build_state::<MinimalEthSpec>(16).await;
+    let root = state.update_tree_hash_cache().unwrap();
+    let first_leaf = state.num_fields_pow2();
+
+    // `slot` is the third field of the state and `balances` the thirteenth.
+    let slot_index = first_leaf + 2;
+    let balances_index = first_leaf + 12;
+
+    // The balances of validators 4 to 7 are packed into the second chunk of the list's data tree,
+    // whose depth is that of 2^40 balances packed 4 to a chunk.
+    let data_depth = 38;
+    let chunk_index = ((balances_index * 2) << data_depth) + 1;
+    let mut chunk = [0; 32];
+    for (i, balance) in state.balances().iter().skip(4).take(4).enumerate() {
+        chunk[i * 8..(i + 1) * 8].copy_from_slice(&balance.to_le_bytes());
+    }
+
+    let indices = [slot_index, chunk_index];
+    let leaves = state.merkle_nodes(&indices).unwrap();
+    assert_eq!(
+        leaves,
+        vec![state.slot().tree_hash_root(), Hash256::from(chunk)]
+    );
+    assert_eq!(
+        state.merkle_nodes(&[chunk_index * 2]),
+        Err(BeaconStateError::IndexNotSupported(chunk_index * 2))
+    );
+
+    let proof = state
+        .merkle_nodes(&merkle_proof::get_helper_indices(&indices))
+        .unwrap();
+    assert!(merkle_proof::verify_merkle_multiproof(
+        &leaves, &proof, &indices, root
+    ));
+}
@@ -1,4 +1,4 @@
-use crate::test_utils::TestRandom;
+use crate::test_utils::{random_block_with_blobs, TestRandom};
 use crate::{
     beacon_block_body::BLOB_KZG_COMMITMENTS_INDEX, BeaconBlockHeader, BeaconStateError, Blob,
     Epoch, EthSpec, FixedVector, Hash256, SignedBeaconBlockHeader, Slot, VariableList,
@@ -16,7 +16,6 @@ use ssz_derive::{Decode, Encode};
 use std::fmt::Debug;
 use std::hash::Hash;
 use std::sync::Arc;
-use test_random_derive::TestRandom;
 use tree_hash::TreeHash;
 use tree_hash_derive::TreeHash;
 
@@ -55,16 +54,7 @@ impl Ord for BlobIdentifier {
 }
 
 #[derive(
-    Debug,
-    Clone,
-    Serialize,
-    Deserialize,
-    Encode,
-    Decode,
-    TreeHash,
-    TestRandom,
-    Derivative,
-    arbitrary::Arbitrary,
+    Debug, Clone, Serialize, Deserialize, Encode, Decode, TreeHash, Derivative, arbitrary::Arbitrary,
 )]
 #[serde(bound = "E: EthSpec")]
 #[arbitrary(bound = "E: EthSpec")]
@@ -80,6 +70,27 @@ pub struct BlobSidecar<E: EthSpec> {
     pub kzg_commitment_inclusion_proof: FixedVector<Hash256, E::KzgCommitmentInclusionProofDepth>,
 }
 
+/// Random sidecars are generated from a random block, so that the index, commitment and inclusion
+/// proof of the sidecar are consistent with its block header.
+impl<E: EthSpec> TestRandom for BlobSidecar<E> {
+    fn random_for_test(rng: &mut impl rand::RngCore) -> Self {
+        let block = random_block_with_blobs::<E>(rng);
+        let num_blobs = block
+            .message()
+            .body()
+            .blob_kzg_commitments()
+            .map_or(0, |commitments| commitments.len());
+        let index = rng.gen_range(0..num_blobs);
+        Self::new(
+            index,
+            Blob::<E>::random_for_test(rng),
+            &block,
+            KzgProof::random_for_test(rng),
+        )
+        .expect("block has a commitment at index")
+    }
+}
+
 impl<E: EthSpec> PartialOrd for BlobSidecar<E> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         Some(self.cmp(other))
@@ -273,3 +284,20 @@ pub type BlobSidecarList<E> = VariableList<Arc<BlobSidecar<E>>, <E as EthSpec>::
 pub type FixedBlobSidecarList<E> =
     FixedVector<Option<Arc<BlobSidecar<E>>>, <E as EthSpec>::MaxBlobsPerBlock>;
 pub type BlobsList<E> = VariableList<Blob<E>, <E as EthSpec>::MaxBlobCommitmentsPerBlock>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{SeedableRng, XorShiftRng};
+    use crate::MainnetEthSpec;
+
+    #[test]
+    fn random_sidecar_is_consistent() {
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        for _ in 0..8 {
+            let sidecar = BlobSidecar::<MainnetEthSpec>::random_for_test(&mut rng);
+            assert!(sidecar.index < MainnetEthSpec::max_blobs_per_block() as u64);
+            assert!(sidecar.verify_blob_sidecar_inclusion_proof());
+        }
+    }
+}
@@ -2,25 +2,38 @@ use crate::beacon_block_body::KzgCommitments;
 use crate::{
     ChainSpec, EthSpec, ExecutionPayloadHeaderBellatrix, ExecutionPayloadHeaderCapella,
     ExecutionPayloadHeaderDeneb, ExecutionPayloadHeaderElectra, ExecutionPayloadHeaderRef,
-    ExecutionPayloadHeaderRefMut, ForkName, ForkVersionDeserialize, SignedRoot, Uint256,
+    ExecutionPayloadHeaderRefMut, ForkName, ForkVersionDecode, ForkVersionDeserialize, SignedRoot,
+    Uint256,
 };
 use bls::PublicKeyBytes;
 use bls::Signature;
 use serde::{Deserialize, Deserializer, Serialize};
+use ssz::Decode;
+use ssz_derive::{Decode, Encode};
 use superstruct::superstruct;
 use tree_hash_derive::TreeHash;
 
 #[superstruct(
     variants(Bellatrix, Capella, Deneb, Electra),
     variant_attributes(
-        derive(PartialEq, Debug, Serialize, Deserialize, TreeHash, Clone),
+        derive(
+            PartialEq,
+            Debug,
+            Encode,
+            Decode,
+            Serialize,
+            Deserialize,
+            TreeHash,
+            Clone
+        ),
         serde(bound = "E: EthSpec", deny_unknown_fields)
     ),
     map_ref_into(ExecutionPayloadHeaderRef),
     map_ref_mut_into(ExecutionPayloadHeaderRefMut)
 )]
-#[derive(PartialEq, Debug, Serialize, Deserialize, TreeHash, Clone)]
+#[derive(PartialEq, Debug, Encode, Serialize, Deserialize, TreeHash, Clone)]
 #[serde(bound = "E: EthSpec", deny_unknown_fields, untagged)]
+#[ssz(enum_behaviour = "transparent")]
 #[tree_hash(enum_behaviour = "transparent")]
 pub struct BuilderBid<E: EthSpec> {
     #[superstruct(only(Bellatrix), partial_getter(rename = "header_bellatrix"))]
@@ -44,6 +57,20 @@ impl<E: EthSpec> BuilderBid<E> {
     }
 }
 
+impl<E: EthSpec> ForkVersionDecode for BuilderBid<E> {
+    fn from_ssz_bytes_by_fork(bytes: &[u8], fork_name: ForkName) -> Result<Self, ssz::DecodeError> {
+        match fork_name {
+            ForkName::Bellatrix => BuilderBidBellatrix::from_ssz_bytes(bytes).map(Self::Bellatrix),
+            ForkName::Capella => BuilderBidCapella::from_ssz_bytes(bytes).map(Self::Capella),
+            ForkName::Deneb => BuilderBidDeneb::from_ssz_bytes(bytes).map(Self::Deneb),
+            ForkName::Electra => BuilderBidElectra::from_ssz_bytes(bytes).map(Self::Electra),
+            ForkName::Base | ForkName::Altair => Err(ssz::DecodeError::BytesInvalid(format!(
+                "unsupported fork for BuilderBid: {fork_name}",
+            ))),
+        }
+    }
+}
+
 impl<'a, E: EthSpec> BuilderBidRef<'a, E> {
     pub fn header(&self) -> ExecutionPayloadHeaderRef<'a, E> {
         map_builder_bid_ref_into_execution_payload_header_ref!(&'a _, self, |bid, cons| cons(
@@ -63,7 +90,7 @@ impl<'a, E: EthSpec> BuilderBidRefMut<'a, E> {
 impl<E: EthSpec> SignedRoot for BuilderBid<E> {}
 
 /// Validator registration, for use in interacting with servers implementing the builder API.
-#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+#[derive(PartialEq, Debug, Encode, Serialize, Deserialize, Clone)]
 #[serde(bound = "E: EthSpec")]
 pub struct SignedBuilderBid<E: EthSpec> {
     pub message: BuilderBid<E>,
@@ -114,6 +141,22 @@ impl<E: EthSpec> ForkVersionDeserialize for SignedBuilderBid<E> {
     }
 }
 
+impl<E: EthSpec> ForkVersionDecode for SignedBuilderBid<E> {
+    fn from_ssz_bytes_by_fork(bytes: &[u8], fork_name: ForkName) -> Result<Self, ssz::DecodeError> {
+        let mut builder = ssz::SszDecoderBuilder::new(bytes);
+
+        builder.register_anonymous_variable_length_item()?;
+        builder.register_type::<Signature>()?;
+
+        let mut decoder = builder.build()?;
+        let message = decoder
+            .decode_next_with(|bytes| BuilderBid::from_ssz_bytes_by_fork(bytes, fork_name))?;
+        let signature = decoder.decode_next()?;
+
+        Ok(Self { message, signature })
+    }
+}
+
 impl<E: EthSpec> SignedBuilderBid<E> {
     pub fn verify_signature(&self, spec: &ChainSpec) -> bool {
         self.message
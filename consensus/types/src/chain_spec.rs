@@ -2,7 +2,10 @@ use crate::application_domain::{ApplicationDomain, APPLICATION_DOMAIN_BUILDER};
 use crate::blob_sidecar::BlobIdentifier;
 use crate::data_column_sidecar::DataColumnIdentifier;
 use crate::*;
+use compare_fields::{CompareFields, Comparison, FieldComparison};
+use compare_fields_derive::CompareFields;
 use int_to_bytes::int_to_bytes4;
+use itertools::Itertools;
 use safe_arith::{ArithError, SafeArith};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_utils::quoted_u64::MaybeQuoted;
@@ -33,7 +36,7 @@ pub enum Domain {
 /// Lighthouse's internal configuration struct.
 ///
 /// Contains a mixture of "preset" and "config" values w.r.t to the EF definitions.
-#[derive(arbitrary::Arbitrary, PartialEq, Debug, Clone)]
+#[derive(arbitrary::Arbitrary, PartialEq, Debug, Clone, CompareFields)]
 pub struct ChainSpec {
     /*
      * Config name
@@ -606,6 +609,131 @@ impl ChainSpec {
             .expect("Subnet count must be greater than 0")
     }
 
+    /// Returns the fields which differ between `self` and `other`.
+    pub fn diff(&self, other: &ChainSpec) -> Vec<ChainSpecDiff> {
+        self.compare_fields(other)
+            .into_iter()
+            .filter_map(|comparison| match comparison {
+                Comparison::Child(FieldComparison {
+                    field_name,
+                    equal: false,
+                    a,
+                    b,
+                }) => Some(ChainSpecDiff {
+                    field_name,
+                    ours: a,
+                    theirs: b,
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Check that the spec is internally consistent, and consistent with the preset of `E`.
+    ///
+    /// Returns a description of every problem found.
+    pub fn validate<E: EthSpec>(&self) -> Result<(), Vec<String>> {
+        let mut errors = vec![];
+
+        // Forks must be scheduled in order, and only once their predecessor is scheduled.
+        for (prev, next) in ForkName::list_all().into_iter().tuple_windows() {
+            match (self.fork_epoch(prev), self.fork_epoch(next)) {
+                (None, Some(_)) => errors.push(format!("{next} is scheduled but {prev} is not")),
+                (Some(prev_epoch), Some(next_epoch)) if next_epoch < prev_epoch => {
+                    errors.push(format!(
+                        "{next} fork epoch {next_epoch} is before {prev} fork epoch {prev_epoch}"
+                    ))
+                }
+                _ => (),
+            }
+        }
+        if let Some(eip7594_fork_epoch) = self.eip7594_fork_epoch {
+            let after_deneb = self
+                .deneb_fork_epoch
+                .is_some_and(|deneb_fork_epoch| deneb_fork_epoch <= eip7594_fork_epoch);
+            if !after_deneb {
+                errors.push(format!(
+                    "eip7594 fork epoch {eip7594_fork_epoch} is not at or after the deneb fork epoch"
+                ));
+            }
+        }
+
+        let scheduled_forks = ForkName::list_all()
+            .into_iter()
+            .filter(|fork_name| self.fork_epoch(*fork_name).is_some())
+            .collect::<Vec<_>>();
+        for (i, fork_name) in scheduled_forks.iter().enumerate() {
+            let fork_version = self.fork_version_for_name(*fork_name);
+            if let Some(other) = scheduled_forks
+                .iter()
+                .take(i)
+                .find(|other| self.fork_version_for_name(**other) == fork_version)
+            {
+                errors.push(format!(
+                    "{fork_name} has the same fork version as {other}: 0x{}",
+                    hex::encode(fork_version)
+                ));
+            }
+        }
+
+        let mut check = |ok: bool, message: &str| {
+            if !ok {
+                errors.push(message.to_string());
+            }
+        };
+        check(self.seconds_per_slot > 0, "seconds_per_slot is zero");
+        check(
+            self.target_committee_size > 0,
+            "target_committee_size is zero",
+        );
+        check(self.shuffle_round_count > 0, "shuffle_round_count is zero");
+        check(
+            self.max_committees_per_slot > 0
+                && self.max_committees_per_slot <= E::MaxCommitteesPerSlot::to_usize(),
+            "max_committees_per_slot is zero or exceeds the preset",
+        );
+        check(
+            self.effective_balance_increment > 0
+                && self
+                    .max_effective_balance
+                    .safe_rem(self.effective_balance_increment)
+                    == Ok(0)
+                && self
+                    .max_effective_balance_electra
+                    .safe_rem(self.effective_balance_increment)
+                    == Ok(0),
+            "max effective balances are not multiples of effective_balance_increment",
+        );
+        check(
+            self.min_activation_balance <= self.max_effective_balance_electra,
+            "min_activation_balance exceeds max_effective_balance_electra",
+        );
+        check(
+            self.min_seed_lookahead <= self.max_seed_lookahead,
+            "min_seed_lookahead exceeds max_seed_lookahead",
+        );
+        check(
+            self.epochs_per_sync_committee_period > 0,
+            "epochs_per_sync_committee_period is zero",
+        );
+        check(
+            self.max_request_blocks_deneb <= self.max_request_blocks,
+            "max_request_blocks_deneb exceeds max_request_blocks",
+        );
+        check(
+            self.data_column_sidecar_subnet_count > 0
+                && (self.number_of_columns as u64).safe_rem(self.data_column_sidecar_subnet_count)
+                    == Ok(0),
+            "number_of_columns is not a multiple of data_column_sidecar_subnet_count",
+        );
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
     /// Returns a `ChainSpec` compatible with the Ethereum Foundation specification.
     pub fn mainnet() -> Self {
         Self {
@@ -1179,6 +1307,16 @@ impl ChainSpec {
     }
 }
 
+/// A field which differs between two `ChainSpec`s, as returned by `ChainSpec::diff`.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ChainSpecDiff {
+    pub field_name: String,
+    /// The `Debug` representation of the value in the spec on which `diff` was called.
+    pub ours: String,
+    /// The `Debug` representation of the value in the other spec.
+    pub theirs: String,
+}
+
 impl Default for ChainSpec {
     fn default() -> Self {
         Self::mainnet()
@@ -1890,7 +2028,6 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use itertools::Itertools;
 
     #[test]
     fn test_mainnet_spec_can_be_constructed() {
@@ -2006,6 +2143,66 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn diff_reports_differing_fields() {
+        let spec = ChainSpec::mainnet();
+        assert_eq!(spec.diff(&spec.clone()), vec![]);
+
+        let mut other = spec.clone();
+        other.seconds_per_slot = 6;
+        other.deneb_fork_epoch = None;
+        assert_eq!(
+            spec.diff(&other),
+            vec![
+                ChainSpecDiff {
+                    field_name: "seconds_per_slot".to_string(),
+                    ours: "12".to_string(),
+                    theirs: "6".to_string(),
+                },
+                ChainSpecDiff {
+                    field_name: "deneb_fork_epoch".to_string(),
+                    ours: format!("{:?}", spec.deneb_fork_epoch),
+                    theirs: "None".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn built_in_specs_are_valid() {
+        ChainSpec::mainnet().validate::<MainnetEthSpec>().unwrap();
+        ChainSpec::minimal().validate::<MinimalEthSpec>().unwrap();
+        ChainSpec::gnosis().validate::<GnosisEthSpec>().unwrap();
+    }
+
+    #[test]
+    fn validate_rejects_inconsistent_specs() {
+        let mut spec = ChainSpec::mainnet();
+        spec.capella_fork_epoch = Some(Epoch::new(10));
+        spec.deneb_fork_epoch = Some(Epoch::new(5));
+        spec.electra_fork_epoch = Some(Epoch::new(20));
+        spec.electra_fork_version = spec.deneb_fork_version;
+        spec.max_committees_per_slot = 128;
+
+        let errors = spec.validate::<MainnetEthSpec>().unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                "deneb fork epoch 5 is before capella fork epoch 10".to_string(),
+                "electra has the same fork version as deneb: 0x04000000".to_string(),
+                "max_committees_per_slot is zero or exceeds the preset".to_string(),
+            ]
+        );
+
+        let mut spec = ChainSpec::minimal();
+        spec.altair_fork_epoch = None;
+        spec.bellatrix_fork_epoch = Some(Epoch::new(1));
+        assert_eq!(
+            spec.validate::<MinimalEthSpec>().unwrap_err(),
+            vec!["bellatrix is scheduled but altair is not".to_string()]
+        );
+    }
 }
 
 #[cfg(test)]
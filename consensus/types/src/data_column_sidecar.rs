@@ -1,5 +1,5 @@
 use crate::beacon_block_body::{KzgCommitments, BLOB_KZG_COMMITMENTS_INDEX};
-use crate::test_utils::TestRandom;
+use crate::test_utils::{random_block_with_blobs, TestRandom};
 use crate::{
     BeaconBlockHeader, ChainSpec, EthSpec, Hash256, KzgProofs, SignedBeaconBlock,
     SignedBeaconBlockHeader, Slot,
@@ -11,6 +11,7 @@ use kzg::Kzg;
 use kzg::{Blob as KzgBlob, Cell as KzgCell, Error as KzgError};
 use kzg::{KzgCommitment, KzgProof};
 use merkle_proof::verify_merkle_proof;
+use rand::Rng;
 use rayon::prelude::*;
 use safe_arith::ArithError;
 use serde::{Deserialize, Serialize};
@@ -21,7 +22,6 @@ use ssz_types::Error as SszError;
 use ssz_types::{FixedVector, VariableList};
 use std::hash::Hash;
 use std::sync::Arc;
-use test_random_derive::TestRandom;
 use tree_hash::TreeHash;
 use tree_hash_derive::TreeHash;
 
@@ -41,16 +41,7 @@ pub struct DataColumnIdentifier {
 pub type DataColumnSidecarList<E> = Vec<Arc<DataColumnSidecar<E>>>;
 
 #[derive(
-    Debug,
-    Clone,
-    Serialize,
-    Deserialize,
-    Encode,
-    Decode,
-    TreeHash,
-    TestRandom,
-    Derivative,
-    arbitrary::Arbitrary,
+    Debug, Clone, Serialize, Deserialize, Encode, Decode, TreeHash, Derivative, arbitrary::Arbitrary,
 )]
 #[serde(bound = "E: EthSpec")]
 #[arbitrary(bound = "E: EthSpec")]
@@ -68,6 +59,37 @@ pub struct DataColumnSidecar<E: EthSpec> {
     pub kzg_commitments_inclusion_proof: FixedVector<Hash256, E::KzgCommitmentsInclusionProofDepth>,
 }
 
+/// Random sidecars are generated from a random block, so that the number of cells and proofs in
+/// the column match the block's commitments, and the inclusion proof is valid for its header.
+impl<E: EthSpec> TestRandom for DataColumnSidecar<E> {
+    fn random_for_test(rng: &mut impl rand::RngCore) -> Self {
+        let block = random_block_with_blobs::<E>(rng);
+        let body = block.message().body();
+        let kzg_commitments = body
+            .blob_kzg_commitments()
+            .cloned()
+            .expect("block is post-Deneb");
+        let num_blobs = kzg_commitments.len();
+
+        Self {
+            index: rng.gen_range(0..E::default_spec().number_of_columns as u64),
+            column: (0..num_blobs)
+                .map(|_| Cell::<E>::random_for_test(rng))
+                .collect::<Vec<_>>()
+                .into(),
+            kzg_commitments,
+            kzg_proofs: (0..num_blobs)
+                .map(|_| KzgProof::random_for_test(rng))
+                .collect::<Vec<_>>()
+                .into(),
+            signed_block_header: block.signed_block_header(),
+            kzg_commitments_inclusion_proof: body
+                .kzg_commitments_merkle_proof()
+                .expect("block is post-Deneb"),
+        }
+    }
+}
+
 impl<E: EthSpec> DataColumnSidecar<E> {
     pub fn slot(&self) -> Slot {
         self.signed_block_header.message.slot
@@ -392,3 +414,33 @@ impl From<SszError> for DataColumnSidecarError {
 fn ssz_cell_to_crypto_cell<E: EthSpec>(cell: &Cell<E>) -> Result<KzgCell, KzgError> {
     KzgCell::from_bytes(cell.as_ref()).map_err(Into::into)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{SeedableRng, XorShiftRng};
+    use crate::MainnetEthSpec;
+
+    ssz_and_tree_hash_tests!(DataColumnSidecar<MainnetEthSpec>);
+
+    #[test]
+    fn sidecar_size_bounds() {
+        type E = MainnetEthSpec;
+
+        let empty_len = DataColumnSidecar::<E>::empty().as_ssz_bytes().len();
+        assert!(empty_len < DataColumnSidecar::<E>::min_size());
+        assert!(DataColumnSidecar::<E>::min_size() < DataColumnSidecar::<E>::max_size());
+    }
+
+    #[test]
+    fn random_sidecar_is_consistent() {
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        for _ in 0..8 {
+            let sidecar = DataColumnSidecar::<MainnetEthSpec>::random_for_test(&mut rng);
+            assert!(!sidecar.kzg_commitments.is_empty());
+            assert_eq!(sidecar.column.len(), sidecar.kzg_commitments.len());
+            assert_eq!(sidecar.kzg_proofs.len(), sidecar.kzg_commitments.len());
+            assert!(sidecar.verify_inclusion_proof());
+        }
+    }
+}
@@ -113,20 +113,6 @@ impl<'a, E: EthSpec> ExecutionPayloadRef<'a, E> {
 }
 
 impl<E: EthSpec> ExecutionPayload<E> {
-    pub fn from_ssz_bytes(bytes: &[u8], fork_name: ForkName) -> Result<Self, ssz::DecodeError> {
-        match fork_name {
-            ForkName::Base | ForkName::Altair => Err(ssz::DecodeError::BytesInvalid(format!(
-                "unsupported fork for ExecutionPayload: {fork_name}",
-            ))),
-            ForkName::Bellatrix => {
-                ExecutionPayloadBellatrix::from_ssz_bytes(bytes).map(Self::Bellatrix)
-            }
-            ForkName::Capella => ExecutionPayloadCapella::from_ssz_bytes(bytes).map(Self::Capella),
-            ForkName::Deneb => ExecutionPayloadDeneb::from_ssz_bytes(bytes).map(Self::Deneb),
-            ForkName::Electra => ExecutionPayloadElectra::from_ssz_bytes(bytes).map(Self::Electra),
-        }
-    }
-
     #[allow(clippy::arithmetic_side_effects)]
     /// Returns the maximum size of an execution payload.
     pub fn max_execution_payload_bellatrix_size() -> usize {
@@ -178,6 +164,22 @@ impl<E: EthSpec> ExecutionPayload<E> {
     }
 }
 
+impl<E: EthSpec> ForkVersionDecode for ExecutionPayload<E> {
+    fn from_ssz_bytes_by_fork(bytes: &[u8], fork_name: ForkName) -> Result<Self, ssz::DecodeError> {
+        match fork_name {
+            ForkName::Base | ForkName::Altair => Err(ssz::DecodeError::BytesInvalid(format!(
+                "unsupported fork for ExecutionPayload: {fork_name}",
+            ))),
+            ForkName::Bellatrix => {
+                ExecutionPayloadBellatrix::from_ssz_bytes(bytes).map(Self::Bellatrix)
+            }
+            ForkName::Capella => ExecutionPayloadCapella::from_ssz_bytes(bytes).map(Self::Capella),
+            ForkName::Deneb => ExecutionPayloadDeneb::from_ssz_bytes(bytes).map(Self::Deneb),
+            ForkName::Electra => ExecutionPayloadElectra::from_ssz_bytes(bytes).map(Self::Electra),
+        }
+    }
+}
+
 impl<E: EthSpec> ForkVersionDeserialize for ExecutionPayload<E> {
     fn deserialize_by_fork<'de, D: serde::Deserializer<'de>>(
         value: serde_json::value::Value,
@@ -98,24 +98,6 @@ impl<E: EthSpec> ExecutionPayloadHeader<E> {
         None
     }
 
-    pub fn from_ssz_bytes(bytes: &[u8], fork_name: ForkName) -> Result<Self, ssz::DecodeError> {
-        match fork_name {
-            ForkName::Base | ForkName::Altair => Err(ssz::DecodeError::BytesInvalid(format!(
-                "unsupported fork for ExecutionPayloadHeader: {fork_name}",
-            ))),
-            ForkName::Bellatrix => {
-                ExecutionPayloadHeaderBellatrix::from_ssz_bytes(bytes).map(Self::Bellatrix)
-            }
-            ForkName::Capella => {
-                ExecutionPayloadHeaderCapella::from_ssz_bytes(bytes).map(Self::Capella)
-            }
-            ForkName::Deneb => ExecutionPayloadHeaderDeneb::from_ssz_bytes(bytes).map(Self::Deneb),
-            ForkName::Electra => {
-                ExecutionPayloadHeaderElectra::from_ssz_bytes(bytes).map(Self::Electra)
-            }
-        }
-    }
-
     #[allow(clippy::arithmetic_side_effects)]
     pub fn ssz_max_var_len_for_fork(fork_name: ForkName) -> usize {
         // Matching here in case variable fields are added in future forks.
@@ -404,6 +386,26 @@ impl<E: EthSpec> TryFrom<ExecutionPayloadHeader<E>> for ExecutionPayloadHeaderEl
     }
 }
 
+impl<E: EthSpec> ForkVersionDecode for ExecutionPayloadHeader<E> {
+    fn from_ssz_bytes_by_fork(bytes: &[u8], fork_name: ForkName) -> Result<Self, ssz::DecodeError> {
+        match fork_name {
+            ForkName::Base | ForkName::Altair => Err(ssz::DecodeError::BytesInvalid(format!(
+                "unsupported fork for ExecutionPayloadHeader: {fork_name}",
+            ))),
+            ForkName::Bellatrix => {
+                ExecutionPayloadHeaderBellatrix::from_ssz_bytes(bytes).map(Self::Bellatrix)
+            }
+            ForkName::Capella => {
+                ExecutionPayloadHeaderCapella::from_ssz_bytes(bytes).map(Self::Capella)
+            }
+            ForkName::Deneb => ExecutionPayloadHeaderDeneb::from_ssz_bytes(bytes).map(Self::Deneb),
+            ForkName::Electra => {
+                ExecutionPayloadHeaderElectra::from_ssz_bytes(bytes).map(Self::Electra)
+            }
+        }
+    }
+}
+
 impl<E: EthSpec> ForkVersionDeserialize for ExecutionPayloadHeader<E> {
     fn deserialize_by_fork<'de, D: serde::Deserializer<'de>>(
         value: serde_json::value::Value,
@@ -0,0 +1,600 @@
+use crate::test_utils::TestRandom;
+use crate::{
+    DepositRequest, EthSpec, ExecutionLayerWithdrawalRequest, ExecutionPayloadElectra, Unsigned,
+    VariableList,
+};
+use derivative::Derivative;
+use safe_arith::SafeArith;
+use serde::{Deserialize, Serialize};
+use ssz::{Decode, Encode};
+use ssz_derive::{Decode, Encode};
+use test_random_derive::TestRandom;
+use tree_hash_derive::TreeHash;
+
+/// The type of an execution layer request, identified by its prefix byte in EIP-7685 lists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RequestPrefix {
+    /// Deposit requests, as defined in EIP-6110.
+    Deposit,
+    /// Withdrawal requests, as defined in EIP-7002.
+    Withdrawal,
+}
+
+impl RequestPrefix {
+    /// Every request type, in increasing order of prefix.
+    pub const ALL: [RequestPrefix; 2] = [RequestPrefix::Deposit, RequestPrefix::Withdrawal];
+
+    pub fn from_u8(prefix: u8) -> Option<Self> {
+        match prefix {
+            0x00 => Some(RequestPrefix::Deposit),
+            0x01 => Some(RequestPrefix::Withdrawal),
+            _ => None,
+        }
+    }
+
+    pub fn to_u8(self) -> u8 {
+        match self {
+            RequestPrefix::Deposit => 0x00,
+            RequestPrefix::Withdrawal => 0x01,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RequestsError {
+    /// The element at `index` was empty, so has no request type.
+    MissingRequestType {
+        index: usize,
+    },
+    /// The element at `index` contained a request type but no requests. EIP-7685 requires such
+    /// elements to be omitted from the list.
+    EmptyRequestData {
+        index: usize,
+        request_type: u8,
+    },
+    UnknownRequestType {
+        index: usize,
+        request_type: u8,
+    },
+    /// Request types must be strictly increasing, so each type appears at most once.
+    RequestTypeNotIncreasing {
+        index: usize,
+        request_type: u8,
+        previous_request_type: u8,
+    },
+    /// The request data is not a whole number of requests.
+    InvalidLength {
+        request_type: u8,
+        length: usize,
+        request_size: usize,
+    },
+    TooManyRequests {
+        request_type: u8,
+        count: usize,
+        max: usize,
+    },
+    InvalidSsz {
+        request_type: u8,
+        error: ssz::DecodeError,
+    },
+}
+
+/// The requests from the execution layer to the consensus layer which are included in a block.
+#[derive(
+    Debug,
+    Clone,
+    Default,
+    Serialize,
+    Deserialize,
+    Encode,
+    Decode,
+    TreeHash,
+    TestRandom,
+    Derivative,
+    arbitrary::Arbitrary,
+)]
+#[derivative(PartialEq, Hash(bound = "E: EthSpec"))]
+#[serde(bound = "E: EthSpec")]
+#[arbitrary(bound = "E: EthSpec")]
+pub struct ExecutionRequests<E: EthSpec> {
+    pub deposits: VariableList<DepositRequest, E::MaxDepositRequestsPerPayload>,
+    pub withdrawals:
+        VariableList<ExecutionLayerWithdrawalRequest, E::MaxWithdrawalRequestsPerPayload>,
+}
+
+impl<E: EthSpec> ExecutionRequests<E> {
+    /// Decode the list of requests returned by the execution engine, as defined in EIP-7685.
+    ///
+    /// Each element is a single request type byte followed by the SSZ encoding of the requests of
+    /// that type, which are concatenated since every request type is fixed-length. Elements must
+    /// be ordered by strictly increasing request type and must not be empty.
+    pub fn try_from_requests_list<B: AsRef<[u8]>>(requests: &[B]) -> Result<Self, RequestsError> {
+        let mut execution_requests = Self::default();
+        let mut previous_request_type = None;
+
+        for (index, request) in requests.iter().enumerate() {
+            let (&request_type, data) = request
+                .as_ref()
+                .split_first()
+                .ok_or(RequestsError::MissingRequestType { index })?;
+
+            if let Some(previous_request_type) = previous_request_type {
+                if request_type <= previous_request_type {
+                    return Err(RequestsError::RequestTypeNotIncreasing {
+                        index,
+                        request_type,
+                        previous_request_type,
+                    });
+                }
+            }
+            previous_request_type = Some(request_type);
+
+            if data.is_empty() {
+                return Err(RequestsError::EmptyRequestData {
+                    index,
+                    request_type,
+                });
+            }
+
+            match RequestPrefix::from_u8(request_type) {
+                Some(RequestPrefix::Deposit) => {
+                    execution_requests.deposits = decode_requests(request_type, data)?;
+                }
+                Some(RequestPrefix::Withdrawal) => {
+                    execution_requests.withdrawals = decode_requests(request_type, data)?;
+                }
+                None => {
+                    return Err(RequestsError::UnknownRequestType {
+                        index,
+                        request_type,
+                    })
+                }
+            }
+        }
+
+        Ok(execution_requests)
+    }
+
+    /// Encode the list of requests to be sent to the execution engine, as defined in EIP-7685.
+    ///
+    /// This is the inverse of `try_from_requests_list`.
+    pub fn to_requests_list(&self) -> Vec<Vec<u8>> {
+        self.iter()
+            .map(|requests| {
+                // The capacity is only a hint, so skip preallocating if it would overflow.
+                let capacity = requests.ssz_bytes_len().safe_add(1).unwrap_or_default();
+                let mut request = Vec::with_capacity(capacity);
+                request.push(requests.prefix().to_u8());
+                requests.ssz_append(&mut request);
+                request
+            })
+            .collect()
+    }
+
+    /// Iterate over the requests of each type, in increasing order of prefix. Types without any
+    /// requests are skipped.
+    pub fn iter(&self) -> impl Iterator<Item = RequestsOfType<'_, E>> + '_ {
+        RequestPrefix::ALL
+            .into_iter()
+            .map(|prefix| self.requests_of_type(prefix))
+            .filter(|requests| !requests.is_empty())
+    }
+
+    /// The requests of a single type.
+    pub fn requests_of_type(&self, prefix: RequestPrefix) -> RequestsOfType<'_, E> {
+        match prefix {
+            RequestPrefix::Deposit => RequestsOfType::Deposit(&self.deposits),
+            RequestPrefix::Withdrawal => RequestsOfType::Withdrawal(&self.withdrawals),
+        }
+    }
+
+    /// The number of requests of a single type.
+    pub fn len_of_type(&self, prefix: RequestPrefix) -> usize {
+        self.requests_of_type(prefix).len()
+    }
+
+    /// The total number of requests of all types.
+    pub fn len(&self) -> usize {
+        RequestPrefix::ALL
+            .into_iter()
+            .map(|prefix| self.len_of_type(prefix))
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<E: EthSpec> From<&ExecutionPayloadElectra<E>> for ExecutionRequests<E> {
+    fn from(payload: &ExecutionPayloadElectra<E>) -> Self {
+        Self {
+            deposits: payload.deposit_requests.clone(),
+            withdrawals: payload.withdrawal_requests.clone(),
+        }
+    }
+}
+
+/// The requests of a single type, borrowed from `ExecutionRequests`.
+///
+/// The requests are encoded on demand, directly into the caller's buffer, since they are not
+/// stored in their encoded form.
+pub enum RequestsOfType<'a, E: EthSpec> {
+    Deposit(&'a VariableList<DepositRequest, E::MaxDepositRequestsPerPayload>),
+    Withdrawal(
+        &'a VariableList<ExecutionLayerWithdrawalRequest, E::MaxWithdrawalRequestsPerPayload>,
+    ),
+}
+
+impl<E: EthSpec> RequestsOfType<'_, E> {
+    pub fn prefix(&self) -> RequestPrefix {
+        match self {
+            RequestsOfType::Deposit(_) => RequestPrefix::Deposit,
+            RequestsOfType::Withdrawal(_) => RequestPrefix::Withdrawal,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            RequestsOfType::Deposit(requests) => requests.len(),
+            RequestsOfType::Withdrawal(requests) => requests.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The length of the concatenated SSZ encoding of the requests, without the prefix.
+    pub fn ssz_bytes_len(&self) -> usize {
+        match self {
+            RequestsOfType::Deposit(requests) => requests.ssz_bytes_len(),
+            RequestsOfType::Withdrawal(requests) => requests.ssz_bytes_len(),
+        }
+    }
+
+    /// Append the concatenated SSZ encoding of the requests, without the prefix, to `buf`.
+    pub fn ssz_append(&self, buf: &mut Vec<u8>) {
+        match self {
+            RequestsOfType::Deposit(requests) => requests.ssz_append(buf),
+            RequestsOfType::Withdrawal(requests) => requests.ssz_append(buf),
+        }
+    }
+
+    /// The concatenated SSZ encoding of the requests, without the prefix.
+    pub fn as_ssz_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(self.ssz_bytes_len());
+        self.ssz_append(&mut buf);
+        buf
+    }
+}
+
+/// A JSON view of `ExecutionRequests` in which the requests of each type are given as the
+/// 0x-prefixed hex encoding of their concatenated SSZ, without the request type byte.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionRequestsJson {
+    #[serde(with = "serde_utils::hex_vec")]
+    pub deposits: Vec<u8>,
+    #[serde(with = "serde_utils::hex_vec")]
+    pub withdrawals: Vec<u8>,
+}
+
+impl<E: EthSpec> From<&ExecutionRequests<E>> for ExecutionRequestsJson {
+    fn from(requests: &ExecutionRequests<E>) -> Self {
+        Self {
+            deposits: requests
+                .requests_of_type(RequestPrefix::Deposit)
+                .as_ssz_bytes(),
+            withdrawals: requests
+                .requests_of_type(RequestPrefix::Withdrawal)
+                .as_ssz_bytes(),
+        }
+    }
+}
+
+impl<E: EthSpec> TryFrom<ExecutionRequestsJson> for ExecutionRequests<E> {
+    type Error = RequestsError;
+
+    fn try_from(json: ExecutionRequestsJson) -> Result<Self, RequestsError> {
+        Ok(Self {
+            deposits: decode_requests(RequestPrefix::Deposit.to_u8(), &json.deposits)?,
+            withdrawals: decode_requests(RequestPrefix::Withdrawal.to_u8(), &json.withdrawals)?,
+        })
+    }
+}
+
+/// Serialize `ExecutionRequests` as an EIP-7685 requests list of 0x-prefixed hex strings, as used
+/// by the engine API.
+///
+/// Use with `#[serde(with = "types::execution_requests::hex_requests_list")]`.
+pub mod hex_requests_list {
+    use super::*;
+    use serde::de::Error;
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<E: EthSpec, S: Serializer>(
+        requests: &ExecutionRequests<E>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serde_utils::list_of_bytes_lists::serialize(&requests.to_requests_list(), serializer)
+    }
+
+    pub fn deserialize<'de, E: EthSpec, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<ExecutionRequests<E>, D::Error> {
+        let list = serde_utils::list_of_bytes_lists::deserialize(deserializer)?;
+        ExecutionRequests::try_from_requests_list(&list)
+            .map_err(|e| D::Error::custom(format!("invalid execution requests: {:?}", e)))
+    }
+}
+
+/// Decode the concatenated SSZ encoding of a list of fixed-length requests.
+fn decode_requests<T: Decode, N: Unsigned>(
+    request_type: u8,
+    data: &[u8],
+) -> Result<VariableList<T, N>, RequestsError> {
+    let request_size = T::ssz_fixed_len();
+    let invalid_length = || RequestsError::InvalidLength {
+        request_type,
+        length: data.len(),
+        request_size,
+    };
+    if data.len().safe_rem(request_size) != Ok(0) {
+        return Err(invalid_length());
+    }
+
+    let count = data
+        .len()
+        .safe_div(request_size)
+        .map_err(|_| invalid_length())?;
+    if count > N::to_usize() {
+        return Err(RequestsError::TooManyRequests {
+            request_type,
+            count,
+            max: N::to_usize(),
+        });
+    }
+
+    VariableList::from_ssz_bytes(data).map_err(|error| RequestsError::InvalidSsz {
+        request_type,
+        error,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{SeedableRng, XorShiftRng};
+    use crate::MainnetEthSpec;
+
+    type E = MainnetEthSpec;
+
+    ssz_and_tree_hash_tests!(ExecutionRequests<E>);
+
+    fn prefixed(request_type: u8, data: &[u8]) -> Vec<u8> {
+        let mut bytes = vec![request_type];
+        bytes.extend_from_slice(data);
+        bytes
+    }
+
+    fn random_requests() -> ExecutionRequests<E> {
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        let mut requests = ExecutionRequests::<E>::random_for_test(&mut rng);
+        requests
+            .deposits
+            .push(DepositRequest::random_for_test(&mut rng))
+            .unwrap();
+        requests
+            .withdrawals
+            .push(ExecutionLayerWithdrawalRequest::random_for_test(&mut rng))
+            .unwrap();
+        requests
+    }
+
+    #[test]
+    fn decode_requests_list() {
+        let requests = random_requests();
+        let list = vec![
+            prefixed(
+                RequestPrefix::Deposit.to_u8(),
+                &requests.deposits.as_ssz_bytes(),
+            ),
+            prefixed(
+                RequestPrefix::Withdrawal.to_u8(),
+                &requests.withdrawals.as_ssz_bytes(),
+            ),
+        ];
+        assert_eq!(
+            ExecutionRequests::<E>::try_from_requests_list(&list),
+            Ok(requests)
+        );
+
+        // Request types without any requests are omitted.
+        let empty: Vec<Vec<u8>> = vec![];
+        assert_eq!(
+            ExecutionRequests::<E>::try_from_requests_list(&empty),
+            Ok(ExecutionRequests::default())
+        );
+    }
+
+    #[test]
+    fn encode_requests_list() {
+        let mut requests = random_requests();
+        assert_eq!(
+            requests
+                .requests_of_type(RequestPrefix::Deposit)
+                .as_ssz_bytes(),
+            requests.deposits.as_ssz_bytes()
+        );
+        for requests_of_type in requests.iter() {
+            assert_eq!(
+                requests_of_type.as_ssz_bytes().len(),
+                requests_of_type.ssz_bytes_len()
+            );
+        }
+
+        let list = requests.to_requests_list();
+        assert_eq!(list.len(), 2);
+        assert_eq!(
+            ExecutionRequests::<E>::try_from_requests_list(&list),
+            Ok(requests.clone())
+        );
+
+        requests.deposits = VariableList::empty();
+        assert_eq!(requests.len(), requests.withdrawals.len());
+        assert_eq!(
+            requests
+                .iter()
+                .map(|requests| requests.prefix())
+                .collect::<Vec<_>>(),
+            vec![RequestPrefix::Withdrawal]
+        );
+        assert_eq!(requests.to_requests_list(), vec![list[1].clone()]);
+
+        let empty = ExecutionRequests::<E>::default();
+        assert!(empty.is_empty());
+        assert!(empty.to_requests_list().is_empty());
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let requests = random_requests();
+        let json = ExecutionRequestsJson::from(&requests);
+        let value = serde_json::to_value(&json).unwrap();
+        assert_eq!(
+            value["deposits"],
+            format!("0x{}", hex::encode(requests.deposits.as_ssz_bytes()))
+        );
+
+        let decoded: ExecutionRequestsJson = serde_json::from_value(value).unwrap();
+        assert_eq!(ExecutionRequests::<E>::try_from(decoded), Ok(requests));
+
+        let empty = ExecutionRequestsJson::from(&ExecutionRequests::<E>::default());
+        assert_eq!(
+            serde_json::to_string(&empty).unwrap(),
+            r#"{"deposits":"0x","withdrawals":"0x"}"#
+        );
+        assert_eq!(
+            ExecutionRequests::<E>::try_from(empty),
+            Ok(ExecutionRequests::default())
+        );
+
+        let truncated = ExecutionRequestsJson {
+            deposits: vec![0; 1],
+            withdrawals: vec![],
+        };
+        assert!(ExecutionRequests::<E>::try_from(truncated).is_err());
+    }
+
+    #[test]
+    fn hex_requests_list_round_trip() {
+        #[derive(Serialize, Deserialize)]
+        #[serde(bound = "E: EthSpec")]
+        struct Wrapper<E: EthSpec> {
+            #[serde(with = "hex_requests_list")]
+            requests: ExecutionRequests<E>,
+        }
+
+        let requests = random_requests();
+        let json = serde_json::to_value(Wrapper {
+            requests: requests.clone(),
+        })
+        .unwrap();
+        let expected = requests
+            .to_requests_list()
+            .iter()
+            .map(|request| format!("0x{}", hex::encode(request)))
+            .collect::<Vec<_>>();
+        assert_eq!(json["requests"], serde_json::json!(expected));
+
+        let decoded: Wrapper<E> = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded.requests, requests);
+
+        assert!(serde_json::from_str::<Wrapper<E>>(r#"{"requests":["0xff00"]}"#).is_err());
+    }
+
+    #[test]
+    fn request_prefixes() {
+        for prefix in RequestPrefix::ALL {
+            assert_eq!(RequestPrefix::from_u8(prefix.to_u8()), Some(prefix));
+        }
+        assert_eq!(RequestPrefix::from_u8(0xff), None);
+    }
+
+    #[test]
+    fn invalid_requests_lists() {
+        let requests = random_requests();
+        let deposits = prefixed(
+            RequestPrefix::Deposit.to_u8(),
+            &requests.deposits.as_ssz_bytes(),
+        );
+        let withdrawals = prefixed(
+            RequestPrefix::Withdrawal.to_u8(),
+            &requests.withdrawals.as_ssz_bytes(),
+        );
+
+        assert_eq!(
+            ExecutionRequests::<E>::try_from_requests_list(&[Vec::<u8>::new()]),
+            Err(RequestsError::MissingRequestType { index: 0 })
+        );
+        assert_eq!(
+            ExecutionRequests::<E>::try_from_requests_list(&[vec![
+                RequestPrefix::Withdrawal.to_u8()
+            ]]),
+            Err(RequestsError::EmptyRequestData {
+                index: 0,
+                request_type: RequestPrefix::Withdrawal.to_u8()
+            })
+        );
+        assert_eq!(
+            ExecutionRequests::<E>::try_from_requests_list(&[withdrawals.clone(), deposits]),
+            Err(RequestsError::RequestTypeNotIncreasing {
+                index: 1,
+                request_type: RequestPrefix::Deposit.to_u8(),
+                previous_request_type: RequestPrefix::Withdrawal.to_u8(),
+            })
+        );
+        assert_eq!(
+            ExecutionRequests::<E>::try_from_requests_list(&[
+                withdrawals.clone(),
+                withdrawals.clone()
+            ]),
+            Err(RequestsError::RequestTypeNotIncreasing {
+                index: 1,
+                request_type: RequestPrefix::Withdrawal.to_u8(),
+                previous_request_type: RequestPrefix::Withdrawal.to_u8(),
+            })
+        );
+        assert_eq!(
+            ExecutionRequests::<E>::try_from_requests_list(&[vec![0xff_u8, 0x00]]),
+            Err(RequestsError::UnknownRequestType {
+                index: 0,
+                request_type: 0xff
+            })
+        );
+        assert_eq!(
+            ExecutionRequests::<E>::try_from_requests_list(
+                &[&withdrawals[..withdrawals.len() - 1]]
+            ),
+            Err(RequestsError::InvalidLength {
+                request_type: RequestPrefix::Withdrawal.to_u8(),
+                length: withdrawals.len() - 2,
+                request_size: <ExecutionLayerWithdrawalRequest as Decode>::ssz_fixed_len(),
+            })
+        );
+
+        let max = <E as EthSpec>::MaxWithdrawalRequestsPerPayload::to_usize();
+        let too_many = prefixed(
+            RequestPrefix::Withdrawal.to_u8(),
+            &requests.withdrawals[0].as_ssz_bytes().repeat(max + 1),
+        );
+        assert_eq!(
+            ExecutionRequests::<E>::try_from_requests_list(&[too_many]),
+            Err(RequestsError::TooManyRequests {
+                request_type: RequestPrefix::Withdrawal.to_u8(),
+                count: max + 1,
+                max,
+            })
+        );
+    }
+}
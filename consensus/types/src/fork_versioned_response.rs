@@ -2,8 +2,24 @@ use crate::ForkName;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::value::Value;
+use ssz::DecodeError;
 use std::sync::Arc;
 
+/// SSZ decoding of a container whose variant depends on the fork.
+///
+/// This is for use where the fork is known from context rather than from the bytes themselves,
+/// e.g. from the RPC context bytes or the `Eth-Consensus-Version` header.
+pub trait ForkVersionDecode: Sized {
+    /// SSZ decode with explicit fork variant.
+    fn from_ssz_bytes_by_fork(bytes: &[u8], fork_name: ForkName) -> Result<Self, DecodeError>;
+}
+
+impl<T: ForkVersionDecode> ForkVersionDecode for Arc<T> {
+    fn from_ssz_bytes_by_fork(bytes: &[u8], fork_name: ForkName) -> Result<Self, DecodeError> {
+        T::from_ssz_bytes_by_fork(bytes, fork_name).map(Arc::new)
+    }
+}
+
 pub trait ForkVersionDeserialize: Sized + DeserializeOwned {
     fn deserialize_by_fork<'de, D: Deserializer<'de>>(
         value: Value,
@@ -2,6 +2,7 @@ use crate::{
     test_utils::{RngCore, TestRandom},
     Hash256,
 };
+use lazy_static::lazy_static;
 use regex::bytes::Regex;
 use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
 use ssz::{Decode, DecodeError, Encode};
@@ -11,6 +12,20 @@ use tree_hash::{PackedEncoding, TreeHash};
 
 pub const GRAFFITI_BYTES_LEN: usize = 32;
 
+lazy_static! {
+    /// Matches the client version encoding `<EL code><EL commit><CL code><CL commit>`, where the
+    /// codes are two upper-case letters and the commits are up to 4 lower-case hex characters,
+    /// optionally followed by a space-separated message.
+    static ref CLIENT_VERSION_RE: regex::Regex = regex::Regex::new(
+        r"^([A-Z]{2})([0-9a-f]{0,4})([A-Z]{2})([0-9a-f]{0,4})(?:\s+(.*))?$"
+    )
+    .expect("client version regex is valid");
+    /// Matches the graffiti wall syntax `graffitiwall:<x>:<y>:#<RRGGBB>`.
+    static ref WALL_PIXEL_RE: regex::Regex =
+        regex::Regex::new(r"^graffitiwall:([0-9]{1,3}):([0-9]{1,3}):#([0-9a-fA-F]{6})$")
+            .expect("graffiti wall regex is valid");
+}
+
 /// The 32-byte `graffiti` field on a beacon block.
 #[derive(Default, Debug, PartialEq, Hash, Clone, Copy, Serialize, Deserialize)]
 #[serde(transparent)]
@@ -25,6 +40,96 @@ impl Graffiti {
     }
 }
 
+/// The structured contents of a `Graffiti`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParsedGraffiti {
+    /// The graffiti is all zeros.
+    Empty,
+    /// The graffiti identifies the execution and consensus clients of the proposer.
+    ClientVersion(ClientVersionGraffiti),
+    /// The graffiti sets a pixel on the graffiti wall.
+    WallPixel(GraffitiWallPixel),
+    /// The graffiti is free-form text.
+    Text(String),
+}
+
+/// Client versions embedded in graffiti, e.g. `GE1234LH5678`.
+///
+/// See `ClientVersionV1` in the execution engine API for the client codes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClientVersionGraffiti {
+    pub execution_code: String,
+    pub execution_commit: String,
+    pub consensus_code: String,
+    pub consensus_commit: String,
+    /// Any text which follows the client versions.
+    pub message: Option<String>,
+}
+
+/// A pixel drawn on the graffiti wall, using the syntax `graffitiwall:<x>:<y>:#<RRGGBB>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraffitiWallPixel {
+    pub x: u16,
+    pub y: u16,
+    pub color: [u8; 3],
+}
+
+impl Graffiti {
+    /// Returns the graffiti as a string, ignoring trailing zero bytes.
+    ///
+    /// Returns `None` if the graffiti is not valid UTF-8.
+    pub fn as_str(&self) -> Option<&str> {
+        let len = self
+            .0
+            .iter()
+            .rposition(|byte| *byte != 0)
+            .map_or(0, |i| i.saturating_add(1));
+        std::str::from_utf8(self.0.get(..len)?).ok()
+    }
+
+    /// Parse the contents of the graffiti into one of the known formats.
+    pub fn parse(&self) -> ParsedGraffiti {
+        if self.0 == [0; GRAFFITI_BYTES_LEN] {
+            ParsedGraffiti::Empty
+        } else if let Some(client_version) = self.client_version() {
+            ParsedGraffiti::ClientVersion(client_version)
+        } else if let Some(pixel) = self.wall_pixel() {
+            ParsedGraffiti::WallPixel(pixel)
+        } else {
+            ParsedGraffiti::Text(self.as_utf8_lossy())
+        }
+    }
+
+    /// Extract the client versions from graffiti of the form `GE1234LH5678`, if present.
+    pub fn client_version(&self) -> Option<ClientVersionGraffiti> {
+        let captures = CLIENT_VERSION_RE.captures(self.as_str()?)?;
+        let group = |i| captures.get(i).map(|m| m.as_str().to_string());
+
+        Some(ClientVersionGraffiti {
+            execution_code: group(1)?,
+            execution_commit: group(2)?,
+            consensus_code: group(3)?,
+            consensus_commit: group(4)?,
+            message: group(5).filter(|message| !message.is_empty()),
+        })
+    }
+
+    /// Extract a graffiti wall pixel from graffiti of the form `graffitiwall:1:2:#ff0000`.
+    pub fn wall_pixel(&self) -> Option<GraffitiWallPixel> {
+        let captures = WALL_PIXEL_RE.captures(self.as_str()?)?;
+        let x = captures.get(1)?.as_str().parse().ok()?;
+        let y = captures.get(2)?.as_str().parse().ok()?;
+        let color = hex::decode(captures.get(3)?.as_str()).ok()?;
+
+        Some(GraffitiWallPixel {
+            x,
+            y,
+            color: color.try_into().ok()?,
+        })
+    }
+}
+
 impl fmt::Display for Graffiti {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", serde_utils::hex::encode(self.0))
@@ -183,3 +288,66 @@ impl TestRandom for Graffiti {
         Self::from(Hash256::random_for_test(rng).to_fixed_bytes())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graffiti(s: &str) -> Graffiti {
+        GraffitiString::from_str(s).unwrap().into()
+    }
+
+    #[test]
+    fn parse_client_version() {
+        assert_eq!(
+            graffiti("GE1234LH5678").parse(),
+            ParsedGraffiti::ClientVersion(ClientVersionGraffiti {
+                execution_code: "GE".into(),
+                execution_commit: "1234".into(),
+                consensus_code: "LH".into(),
+                consensus_commit: "5678".into(),
+                message: None,
+            })
+        );
+
+        let version = graffiti("NMLH hello world").client_version().unwrap();
+        assert_eq!(version.execution_code, "NM");
+        assert_eq!(version.execution_commit, "");
+        assert_eq!(version.consensus_code, "LH");
+        assert_eq!(version.message.as_deref(), Some("hello world"));
+
+        assert_eq!(graffiti("GE1234LH5678oops").client_version(), None);
+        assert_eq!(graffiti("Lighthouse").client_version(), None);
+    }
+
+    #[test]
+    fn parse_wall_pixel() {
+        assert_eq!(
+            graffiti("graffitiwall:12:345:#ff00A0").parse(),
+            ParsedGraffiti::WallPixel(GraffitiWallPixel {
+                x: 12,
+                y: 345,
+                color: [0xff, 0x00, 0xa0],
+            })
+        );
+        assert_eq!(graffiti("graffitiwall:1:2:#fff").wall_pixel(), None);
+        assert_eq!(graffiti("graffitiwall:1000:2:#ffffff").wall_pixel(), None);
+    }
+
+    #[test]
+    fn parse_other() {
+        assert_eq!(Graffiti::default().parse(), ParsedGraffiti::Empty);
+        assert_eq!(
+            graffiti("hello world").parse(),
+            ParsedGraffiti::Text("hello world".into())
+        );
+
+        let mut invalid_utf8 = [0; GRAFFITI_BYTES_LEN];
+        invalid_utf8[0] = 0xff;
+        assert_eq!(Graffiti::from(invalid_utf8).as_str(), None);
+        assert!(matches!(
+            Graffiti::from(invalid_utf8).parse(),
+            ParsedGraffiti::Text(_)
+        ));
+    }
+}
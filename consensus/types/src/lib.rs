@@ -42,6 +42,7 @@ pub mod execution_block_hash;
 pub mod execution_layer_withdrawal_request;
 pub mod execution_payload;
 pub mod execution_payload_header;
+pub mod execution_requests;
 pub mod fork;
 pub mod fork_data;
 pub mod fork_name;
@@ -54,6 +55,7 @@ pub mod light_client_bootstrap;
 pub mod light_client_finality_update;
 pub mod light_client_optimistic_update;
 pub mod light_client_update;
+pub mod merkle_node;
 pub mod pending_attestation;
 pub mod pending_balance_deposit;
 pub mod pending_consolidation;
@@ -66,11 +68,13 @@ pub mod shuffling_id;
 pub mod signed_aggregate_and_proof;
 pub mod signed_beacon_block;
 pub mod signed_beacon_block_header;
+pub mod signed_beacon_block_view;
 pub mod signed_bls_to_execution_change;
 pub mod signed_consolidation;
 pub mod signed_contribution_and_proof;
 pub mod signed_voluntary_exit;
 pub mod signing_data;
+pub mod stable_container;
 pub mod sync_committee_subscription;
 pub mod sync_duty;
 pub mod validator;
@@ -99,6 +103,8 @@ pub mod validator_registration_data;
 pub mod withdrawal;
 
 pub mod epoch_cache;
+#[cfg(feature = "runtime-preset")]
+pub mod runtime_preset;
 pub mod slot_data;
 #[cfg(feature = "sqlite")]
 pub mod sqlite;
@@ -141,7 +147,7 @@ pub use crate::beacon_committee::{BeaconCommittee, OwnedBeaconCommittee};
 pub use crate::beacon_state::{Error as BeaconStateError, *};
 pub use crate::blob_sidecar::{BlobSidecar, BlobSidecarList, BlobsList};
 pub use crate::bls_to_execution_change::BlsToExecutionChange;
-pub use crate::chain_spec::{ChainSpec, Config, Domain};
+pub use crate::chain_spec::{ChainSpec, ChainSpecDiff, Config, Domain};
 pub use crate::checkpoint::Checkpoint;
 pub use crate::config_and_preset::{
     ConfigAndPreset, ConfigAndPresetCapella, ConfigAndPresetDeneb, ConfigAndPresetElectra,
@@ -173,12 +179,19 @@ pub use crate::execution_payload_header::{
     ExecutionPayloadHeaderDeneb, ExecutionPayloadHeaderElectra, ExecutionPayloadHeaderRef,
     ExecutionPayloadHeaderRefMut,
 };
+pub use crate::execution_requests::{
+    ExecutionRequests, ExecutionRequestsJson, RequestPrefix, RequestsError, RequestsOfType,
+};
 pub use crate::fork::Fork;
 pub use crate::fork_context::ForkContext;
 pub use crate::fork_data::ForkData;
 pub use crate::fork_name::{ForkName, InconsistentFork};
-pub use crate::fork_versioned_response::{ForkVersionDeserialize, ForkVersionedResponse};
-pub use crate::graffiti::{Graffiti, GRAFFITI_BYTES_LEN};
+pub use crate::fork_versioned_response::{
+    ForkVersionDecode, ForkVersionDeserialize, ForkVersionedResponse,
+};
+pub use crate::graffiti::{
+    ClientVersionGraffiti, Graffiti, GraffitiWallPixel, ParsedGraffiti, GRAFFITI_BYTES_LEN,
+};
 pub use crate::historical_batch::HistoricalBatch;
 pub use crate::indexed_attestation::{
     IndexedAttestation, IndexedAttestationBase, IndexedAttestationElectra, IndexedAttestationRef,
@@ -216,11 +229,14 @@ pub use crate::pending_balance_deposit::PendingBalanceDeposit;
 pub use crate::pending_consolidation::PendingConsolidation;
 pub use crate::pending_partial_withdrawal::PendingPartialWithdrawal;
 pub use crate::preset::{
-    AltairPreset, BasePreset, BellatrixPreset, CapellaPreset, DenebPreset, ElectraPreset,
+    AltairPreset, BasePreset, BellatrixPreset, CapellaPreset, DenebPreset, Eip7594Preset,
+    ElectraPreset,
 };
 pub use crate::proposer_preparation_data::ProposerPreparationData;
 pub use crate::proposer_slashing::ProposerSlashing;
 pub use crate::relative_epoch::{Error as RelativeEpochError, RelativeEpoch};
+#[cfg(feature = "runtime-preset")]
+pub use crate::runtime_preset::FullPreset;
 pub use crate::runtime_var_list::RuntimeVariableList;
 pub use crate::selection_proof::SelectionProof;
 pub use crate::shuffling_id::AttestationShufflingId;
@@ -234,6 +250,7 @@ pub use crate::signed_beacon_block::{
     SignedBeaconBlockHash, SignedBlindedBeaconBlock,
 };
 pub use crate::signed_beacon_block_header::SignedBeaconBlockHeader;
+pub use crate::signed_beacon_block_view::{BeaconBlockView, SignedBeaconBlockView};
 pub use crate::signed_bls_to_execution_change::SignedBlsToExecutionChange;
 pub use crate::signed_consolidation::SignedConsolidation;
 pub use crate::signed_contribution_and_proof::SignedContributionAndProof;
@@ -1,8 +1,8 @@
 use crate::{
     light_client_update::*, test_utils::TestRandom, BeaconState, ChainSpec, EthSpec, FixedVector,
-    ForkName, ForkVersionDeserialize, Hash256, LightClientHeader, LightClientHeaderAltair,
-    LightClientHeaderCapella, LightClientHeaderDeneb, LightClientHeaderElectra, SignedBeaconBlock,
-    Slot, SyncCommittee,
+    ForkName, ForkVersionDecode, ForkVersionDeserialize, Hash256, LightClientHeader,
+    LightClientHeaderAltair, LightClientHeaderCapella, LightClientHeaderDeneb,
+    LightClientHeaderElectra, SignedBeaconBlock, Slot, SyncCommittee,
 };
 use derivative::Derivative;
 use serde::{Deserialize, Deserializer, Serialize};
@@ -80,24 +80,6 @@ impl<E: EthSpec> LightClientBootstrap<E> {
         })
     }
 
-    pub fn from_ssz_bytes(bytes: &[u8], fork_name: ForkName) -> Result<Self, ssz::DecodeError> {
-        let bootstrap = match fork_name {
-            ForkName::Altair | ForkName::Bellatrix => {
-                Self::Altair(LightClientBootstrapAltair::from_ssz_bytes(bytes)?)
-            }
-            ForkName::Capella => Self::Capella(LightClientBootstrapCapella::from_ssz_bytes(bytes)?),
-            ForkName::Deneb => Self::Deneb(LightClientBootstrapDeneb::from_ssz_bytes(bytes)?),
-            ForkName::Electra => Self::Electra(LightClientBootstrapElectra::from_ssz_bytes(bytes)?),
-            ForkName::Base => {
-                return Err(ssz::DecodeError::BytesInvalid(format!(
-                    "LightClientBootstrap decoding for {fork_name} not implemented"
-                )))
-            }
-        };
-
-        Ok(bootstrap)
-    }
-
     #[allow(clippy::arithmetic_side_effects)]
     pub fn ssz_max_len_for_fork(fork_name: ForkName) -> usize {
         let fixed_len = match fork_name {
@@ -155,6 +137,26 @@ impl<E: EthSpec> LightClientBootstrap<E> {
     }
 }
 
+impl<E: EthSpec> ForkVersionDecode for LightClientBootstrap<E> {
+    fn from_ssz_bytes_by_fork(bytes: &[u8], fork_name: ForkName) -> Result<Self, ssz::DecodeError> {
+        let bootstrap = match fork_name {
+            ForkName::Altair | ForkName::Bellatrix => {
+                Self::Altair(LightClientBootstrapAltair::from_ssz_bytes(bytes)?)
+            }
+            ForkName::Capella => Self::Capella(LightClientBootstrapCapella::from_ssz_bytes(bytes)?),
+            ForkName::Deneb => Self::Deneb(LightClientBootstrapDeneb::from_ssz_bytes(bytes)?),
+            ForkName::Electra => Self::Electra(LightClientBootstrapElectra::from_ssz_bytes(bytes)?),
+            ForkName::Base => {
+                return Err(ssz::DecodeError::BytesInvalid(format!(
+                    "LightClientBootstrap decoding for {fork_name} not implemented"
+                )))
+            }
+        };
+
+        Ok(bootstrap)
+    }
+}
+
 impl<E: EthSpec> ForkVersionDeserialize for LightClientBootstrap<E> {
     fn deserialize_by_fork<'de, D: Deserializer<'de>>(
         value: Value,
@@ -1,9 +1,9 @@
 use super::{EthSpec, FixedVector, Hash256, LightClientHeader, Slot, SyncAggregate};
 use crate::ChainSpec;
 use crate::{
-    light_client_update::*, test_utils::TestRandom, ForkName, ForkVersionDeserialize,
-    LightClientHeaderAltair, LightClientHeaderCapella, LightClientHeaderDeneb,
-    LightClientHeaderElectra, SignedBeaconBlock,
+    light_client_update::*, test_utils::TestRandom, ForkName, ForkVersionDecode,
+    ForkVersionDeserialize, LightClientHeaderAltair, LightClientHeaderCapella,
+    LightClientHeaderDeneb, LightClientHeaderElectra, SignedBeaconBlock,
 };
 use derivative::Derivative;
 use serde::{Deserialize, Deserializer, Serialize};
@@ -156,7 +156,24 @@ impl<E: EthSpec> LightClientFinalityUpdate<E> {
         })
     }
 
-    pub fn from_ssz_bytes(bytes: &[u8], fork_name: ForkName) -> Result<Self, ssz::DecodeError> {
+    #[allow(clippy::arithmetic_side_effects)]
+    pub fn ssz_max_len_for_fork(fork_name: ForkName) -> usize {
+        let fixed_size = match fork_name {
+            ForkName::Base => 0,
+            ForkName::Altair | ForkName::Bellatrix => {
+                <LightClientFinalityUpdateAltair<E> as Encode>::ssz_fixed_len()
+            }
+            ForkName::Capella => <LightClientFinalityUpdateCapella<E> as Encode>::ssz_fixed_len(),
+            ForkName::Deneb => <LightClientFinalityUpdateDeneb<E> as Encode>::ssz_fixed_len(),
+            ForkName::Electra => <LightClientFinalityUpdateElectra<E> as Encode>::ssz_fixed_len(),
+        };
+        // `2 *` because there are two headers in the update
+        fixed_size + 2 * LightClientHeader::<E>::ssz_max_var_len_for_fork(fork_name)
+    }
+}
+
+impl<E: EthSpec> ForkVersionDecode for LightClientFinalityUpdate<E> {
+    fn from_ssz_bytes_by_fork(bytes: &[u8], fork_name: ForkName) -> Result<Self, ssz::DecodeError> {
         let finality_update = match fork_name {
             ForkName::Altair | ForkName::Bellatrix => {
                 Self::Altair(LightClientFinalityUpdateAltair::from_ssz_bytes(bytes)?)
@@ -177,21 +194,6 @@ impl<E: EthSpec> LightClientFinalityUpdate<E> {
 
         Ok(finality_update)
     }
-
-    #[allow(clippy::arithmetic_side_effects)]
-    pub fn ssz_max_len_for_fork(fork_name: ForkName) -> usize {
-        let fixed_size = match fork_name {
-            ForkName::Base => 0,
-            ForkName::Altair | ForkName::Bellatrix => {
-                <LightClientFinalityUpdateAltair<E> as Encode>::ssz_fixed_len()
-            }
-            ForkName::Capella => <LightClientFinalityUpdateCapella<E> as Encode>::ssz_fixed_len(),
-            ForkName::Deneb => <LightClientFinalityUpdateDeneb<E> as Encode>::ssz_fixed_len(),
-            ForkName::Electra => <LightClientFinalityUpdateElectra<E> as Encode>::ssz_fixed_len(),
-        };
-        // `2 *` because there are two headers in the update
-        fixed_size + 2 * LightClientHeader::<E>::ssz_max_var_len_for_fork(fork_name)
-    }
 }
 
 impl<E: EthSpec> ForkVersionDeserialize for LightClientFinalityUpdate<E> {
@@ -1,12 +1,12 @@
 use crate::ChainSpec;
 use crate::ForkName;
-use crate::ForkVersionDeserialize;
 use crate::{light_client_update::*, BeaconBlockBody};
 use crate::{
     test_utils::TestRandom, EthSpec, ExecutionPayloadHeaderCapella, ExecutionPayloadHeaderDeneb,
     ExecutionPayloadHeaderElectra, FixedVector, Hash256, SignedBeaconBlock,
 };
 use crate::{BeaconBlockHeader, ExecutionPayloadHeader};
+use crate::{ForkVersionDecode, ForkVersionDeserialize};
 use derivative::Derivative;
 use serde::{Deserialize, Serialize};
 use ssz::Decode;
@@ -96,38 +96,6 @@ impl<E: EthSpec> LightClientHeader<E> {
         Ok(header)
     }
 
-    pub fn from_ssz_bytes(bytes: &[u8], fork_name: ForkName) -> Result<Self, ssz::DecodeError> {
-        let header = match fork_name {
-            ForkName::Altair | ForkName::Bellatrix => {
-                LightClientHeader::Altair(LightClientHeaderAltair::from_ssz_bytes(bytes)?)
-            }
-            ForkName::Capella => {
-                LightClientHeader::Capella(LightClientHeaderCapella::from_ssz_bytes(bytes)?)
-            }
-            ForkName::Deneb => {
-                LightClientHeader::Deneb(LightClientHeaderDeneb::from_ssz_bytes(bytes)?)
-            }
-            ForkName::Electra => {
-                LightClientHeader::Electra(LightClientHeaderElectra::from_ssz_bytes(bytes)?)
-            }
-            ForkName::Base => {
-                return Err(ssz::DecodeError::BytesInvalid(format!(
-                    "LightClientHeader decoding for {fork_name} not implemented"
-                )))
-            }
-        };
-
-        Ok(header)
-    }
-
-    /// Custom SSZ decoder that takes a `ForkName` as context.
-    pub fn from_ssz_bytes_for_fork(
-        bytes: &[u8],
-        fork_name: ForkName,
-    ) -> Result<Self, ssz::DecodeError> {
-        Self::from_ssz_bytes(bytes, fork_name)
-    }
-
     pub fn ssz_max_var_len_for_fork(fork_name: ForkName) -> usize {
         match fork_name {
             ForkName::Base | ForkName::Altair | ForkName::Bellatrix => 0,
@@ -231,6 +199,32 @@ impl<E: EthSpec> LightClientHeaderElectra<E> {
     }
 }
 
+impl<E: EthSpec> ForkVersionDecode for LightClientHeader<E> {
+    fn from_ssz_bytes_by_fork(bytes: &[u8], fork_name: ForkName) -> Result<Self, ssz::DecodeError> {
+        let header = match fork_name {
+            ForkName::Altair | ForkName::Bellatrix => {
+                LightClientHeader::Altair(LightClientHeaderAltair::from_ssz_bytes(bytes)?)
+            }
+            ForkName::Capella => {
+                LightClientHeader::Capella(LightClientHeaderCapella::from_ssz_bytes(bytes)?)
+            }
+            ForkName::Deneb => {
+                LightClientHeader::Deneb(LightClientHeaderDeneb::from_ssz_bytes(bytes)?)
+            }
+            ForkName::Electra => {
+                LightClientHeader::Electra(LightClientHeaderElectra::from_ssz_bytes(bytes)?)
+            }
+            ForkName::Base => {
+                return Err(ssz::DecodeError::BytesInvalid(format!(
+                    "LightClientHeader decoding for {fork_name} not implemented"
+                )))
+            }
+        };
+
+        Ok(header)
+    }
+}
+
 impl<E: EthSpec> ForkVersionDeserialize for LightClientHeader<E> {
     fn deserialize_by_fork<'de, D: serde::Deserializer<'de>>(
         value: serde_json::value::Value,
@@ -1,4 +1,7 @@
-use super::{EthSpec, ForkName, ForkVersionDeserialize, LightClientHeader, Slot, SyncAggregate};
+use super::{
+    EthSpec, ForkName, ForkVersionDecode, ForkVersionDeserialize, LightClientHeader, Slot,
+    SyncAggregate,
+};
 use crate::test_utils::TestRandom;
 use crate::{
     light_client_update::*, ChainSpec, LightClientHeaderAltair, LightClientHeaderCapella,
@@ -141,7 +144,23 @@ impl<E: EthSpec> LightClientOptimisticUpdate<E> {
         })
     }
 
-    pub fn from_ssz_bytes(bytes: &[u8], fork_name: ForkName) -> Result<Self, ssz::DecodeError> {
+    #[allow(clippy::arithmetic_side_effects)]
+    pub fn ssz_max_len_for_fork(fork_name: ForkName) -> usize {
+        let fixed_len = match fork_name {
+            ForkName::Base => 0,
+            ForkName::Altair | ForkName::Bellatrix => {
+                <LightClientOptimisticUpdateAltair<E> as Encode>::ssz_fixed_len()
+            }
+            ForkName::Capella => <LightClientOptimisticUpdateCapella<E> as Encode>::ssz_fixed_len(),
+            ForkName::Deneb => <LightClientOptimisticUpdateDeneb<E> as Encode>::ssz_fixed_len(),
+            ForkName::Electra => <LightClientOptimisticUpdateElectra<E> as Encode>::ssz_fixed_len(),
+        };
+        fixed_len + LightClientHeader::<E>::ssz_max_var_len_for_fork(fork_name)
+    }
+}
+
+impl<E: EthSpec> ForkVersionDecode for LightClientOptimisticUpdate<E> {
+    fn from_ssz_bytes_by_fork(bytes: &[u8], fork_name: ForkName) -> Result<Self, ssz::DecodeError> {
         let optimistic_update = match fork_name {
             ForkName::Altair | ForkName::Bellatrix => {
                 Self::Altair(LightClientOptimisticUpdateAltair::from_ssz_bytes(bytes)?)
@@ -164,20 +183,6 @@ impl<E: EthSpec> LightClientOptimisticUpdate<E> {
 
         Ok(optimistic_update)
     }
-
-    #[allow(clippy::arithmetic_side_effects)]
-    pub fn ssz_max_len_for_fork(fork_name: ForkName) -> usize {
-        let fixed_len = match fork_name {
-            ForkName::Base => 0,
-            ForkName::Altair | ForkName::Bellatrix => {
-                <LightClientOptimisticUpdateAltair<E> as Encode>::ssz_fixed_len()
-            }
-            ForkName::Capella => <LightClientOptimisticUpdateCapella<E> as Encode>::ssz_fixed_len(),
-            ForkName::Deneb => <LightClientOptimisticUpdateDeneb<E> as Encode>::ssz_fixed_len(),
-            ForkName::Electra => <LightClientOptimisticUpdateElectra<E> as Encode>::ssz_fixed_len(),
-        };
-        fixed_len + LightClientHeader::<E>::ssz_max_var_len_for_fork(fork_name)
-    }
 }
 
 impl<E: EthSpec> ForkVersionDeserialize for LightClientOptimisticUpdate<E> {
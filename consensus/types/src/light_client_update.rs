@@ -2,8 +2,8 @@ use super::{EthSpec, FixedVector, Hash256, Slot, SyncAggregate, SyncCommittee};
 use crate::light_client_header::LightClientHeaderElectra;
 use crate::{
     beacon_state, test_utils::TestRandom, BeaconBlock, BeaconBlockHeader, BeaconState, ChainSpec,
-    ForkName, ForkVersionDeserialize, LightClientHeaderAltair, LightClientHeaderCapella,
-    LightClientHeaderDeneb, SignedBeaconBlock,
+    ForkName, ForkVersionDecode, ForkVersionDeserialize, LightClientHeaderAltair,
+    LightClientHeaderCapella, LightClientHeaderDeneb, SignedBeaconBlock,
 };
 use derivative::Derivative;
 use safe_arith::ArithError;
@@ -135,6 +135,26 @@ pub struct LightClientUpdate<E: EthSpec> {
     pub signature_slot: Slot,
 }
 
+impl<E: EthSpec> ForkVersionDecode for LightClientUpdate<E> {
+    fn from_ssz_bytes_by_fork(bytes: &[u8], fork_name: ForkName) -> Result<Self, ssz::DecodeError> {
+        let update = match fork_name {
+            ForkName::Altair | ForkName::Bellatrix => {
+                Self::Altair(LightClientUpdateAltair::from_ssz_bytes(bytes)?)
+            }
+            ForkName::Capella => Self::Capella(LightClientUpdateCapella::from_ssz_bytes(bytes)?),
+            ForkName::Deneb => Self::Deneb(LightClientUpdateDeneb::from_ssz_bytes(bytes)?),
+            ForkName::Electra => Self::Electra(LightClientUpdateElectra::from_ssz_bytes(bytes)?),
+            ForkName::Base => {
+                return Err(ssz::DecodeError::BytesInvalid(format!(
+                    "LightClientUpdate decoding for {fork_name} not implemented"
+                )))
+            }
+        };
+
+        Ok(update)
+    }
+}
+
 impl<E: EthSpec> ForkVersionDeserialize for LightClientUpdate<E> {
     fn deserialize_by_fork<'de, D: Deserializer<'de>>(
         value: Value,
@@ -262,24 +282,6 @@ impl<E: EthSpec> LightClientUpdate<E> {
 
         Ok(light_client_update)
     }
-
-    pub fn from_ssz_bytes(bytes: &[u8], fork_name: ForkName) -> Result<Self, ssz::DecodeError> {
-        let update = match fork_name {
-            ForkName::Altair | ForkName::Bellatrix => {
-                Self::Altair(LightClientUpdateAltair::from_ssz_bytes(bytes)?)
-            }
-            ForkName::Capella => Self::Capella(LightClientUpdateCapella::from_ssz_bytes(bytes)?),
-            ForkName::Deneb => Self::Deneb(LightClientUpdateDeneb::from_ssz_bytes(bytes)?),
-            ForkName::Electra => Self::Electra(LightClientUpdateElectra::from_ssz_bytes(bytes)?),
-            ForkName::Base => {
-                return Err(ssz::DecodeError::BytesInvalid(format!(
-                    "LightClientUpdate decoding for {fork_name} not implemented"
-                )))
-            }
-        };
-
-        Ok(update)
-    }
 }
 
 #[cfg(test)]
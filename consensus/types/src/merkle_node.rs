@@ -0,0 +1,176 @@
+//! Computes the nodes of SSZ Merkle trees by generalized index, so that multiproofs can be
+//! produced for the fields of states and blocks.
+//!
+//! See the [SSZ Merkle proofs spec][spec] for the definition of generalized indices.
+//!
+//! [spec]: https://github.com/ethereum/consensus-specs/blob/dev/ssz/merkle-proofs.md
+#![allow(clippy::arithmetic_side_effects)]
+
+use crate::{Hash256, List, Vector};
+use ethereum_hashing::ZERO_HASHES;
+use milhouse::Value;
+use ssz_types::typenum::Unsigned;
+use std::cmp::min;
+use tree_hash::{TreeHash, TreeHashType, BYTES_PER_CHUNK};
+
+/// Splits a `generalized_index` at `depth` or deeper into the index of its ancestor at `depth`
+/// and its generalized index within the subtree rooted at that ancestor.
+///
+/// Returns `None` if `generalized_index` is shallower than `depth`.
+pub fn split_generalized_index(generalized_index: usize, depth: usize) -> Option<(usize, usize)> {
+    let index_depth = generalized_index.checked_ilog2()? as usize;
+    let subtree_depth = index_depth.checked_sub(depth)?;
+    let ancestor = generalized_index >> subtree_depth;
+    let subtree_mask = (1 << subtree_depth) - 1;
+    Some((
+        ancestor - (1 << depth),
+        (1 << subtree_depth) | (generalized_index & subtree_mask),
+    ))
+}
+
+/// Returns the node at `generalized_index` of the Merkle tree of `depth` whose leaves are the
+/// 32-byte `chunks`, padded with zero chunks.
+///
+/// Returns `None` if `generalized_index` is zero or deeper than the leaves.
+pub fn chunks_node(chunks: &[u8], depth: usize, generalized_index: usize) -> Option<Hash256> {
+    let index_depth = generalized_index.checked_ilog2()? as usize;
+    let height = depth.checked_sub(index_depth)?;
+    let position = generalized_index - (1 << index_depth);
+
+    let start = (position << height) * BYTES_PER_CHUNK;
+    let end = min(start + (BYTES_PER_CHUNK << height), chunks.len());
+    match chunks.get(start..end) {
+        Some(subtree_chunks) if !subtree_chunks.is_empty() => {
+            Some(tree_hash::merkle_root(subtree_chunks, 1 << height))
+        }
+        _ => ZERO_HASHES
+            .get(height)
+            .map(|zero| Hash256::from_slice(zero)),
+    }
+}
+
+/// Returns the chunks of the Merkle tree of a sequence of `items`.
+fn item_chunks<'a, T: TreeHash + 'a>(items: impl Iterator<Item = &'a T>) -> Vec<u8> {
+    let mut chunks = vec![];
+    if T::tree_hash_type() == TreeHashType::Basic {
+        for item in items {
+            chunks.extend_from_slice(&item.tree_hash_packed_encoding());
+        }
+    } else {
+        for item in items {
+            chunks.extend_from_slice(item.tree_hash_root().as_bytes());
+        }
+    }
+    chunks
+}
+
+/// Returns the depth of the Merkle tree of a sequence of up to `max_len` items.
+fn items_depth<T: TreeHash>(max_len: usize) -> usize {
+    let max_chunks = if T::tree_hash_type() == TreeHashType::Basic {
+        max_len.div_ceil(T::tree_hash_packing_factor())
+    } else {
+        max_len
+    };
+    max_chunks.next_power_of_two().ilog2() as usize
+}
+
+/// Returns the nodes at `generalized_indices` of the Merkle tree of `vector`, or `None` for those
+/// which aren't supported.
+///
+/// Nodes down to the elements of the vector are supported, or to the chunks of packed elements.
+/// The elements are only hashed once, however many nodes are requested.
+pub fn vector_nodes<T, N>(
+    vector: &Vector<T, N>,
+    generalized_indices: &[usize],
+) -> Vec<Option<Hash256>>
+where
+    T: TreeHash + Value,
+    N: Unsigned,
+{
+    let chunks = item_chunks(vector.iter());
+    let depth = items_depth::<T>(N::to_usize());
+    generalized_indices
+        .iter()
+        .map(|&generalized_index| chunks_node(&chunks, depth, generalized_index))
+        .collect()
+}
+
+/// Returns the nodes at `generalized_indices` of the Merkle tree of `list`, or `None` for those
+/// which aren't supported.
+///
+/// As well as the nodes supported by `vector_nodes`, the length of the list is at generalized
+/// index 3.
+pub fn list_nodes<T, N>(list: &List<T, N>, generalized_indices: &[usize]) -> Vec<Option<Hash256>>
+where
+    T: TreeHash + Value,
+    N: Unsigned,
+{
+    let chunks = item_chunks(list.iter());
+    let depth = items_depth::<T>(N::to_usize());
+    generalized_indices
+        .iter()
+        .map(|&generalized_index| {
+            if generalized_index == 1 {
+                return Some(list.tree_hash_root());
+            }
+            match split_generalized_index(generalized_index, 1)? {
+                (0, data_index) => chunks_node(&chunks, depth, data_index),
+                (_, 1) => Some((list.len() as u64).tree_hash_root()),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ssz_types::typenum::{U16, U32};
+
+    #[test]
+    fn split_generalized_index_into_subtree() {
+        assert_eq!(split_generalized_index(1, 1), None);
+        assert_eq!(split_generalized_index(2, 1), Some((0, 1)));
+        assert_eq!(split_generalized_index(3, 1), Some((1, 1)));
+        // The sixth node at depth 3, followed by the path left then right.
+        assert_eq!(split_generalized_index(0b1101_01, 3), Some((5, 0b1_01)));
+    }
+
+    #[test]
+    fn list_nodes_match_tree_hash() {
+        let list = List::<u64, U16>::new((0..5).collect()).unwrap();
+        let nodes = list_nodes(&list, &[2, 3, 8, 11, 16]);
+        let data_root = nodes[0].unwrap();
+        let length = nodes[1].unwrap();
+        assert_eq!(
+            Hash256::from_slice(&ethereum_hashing::hash32_concat(
+                data_root.as_bytes(),
+                length.as_bytes()
+            )),
+            list.tree_hash_root()
+        );
+
+        // 16 `u64`s pack into 4 chunks, at generalized indices 4 to 7 of the data tree and 8 to
+        // 11 of the list tree.
+        let mut first_chunk = [0; 32];
+        for (i, value) in (0..4u64).enumerate() {
+            first_chunk[i * 8..(i + 1) * 8].copy_from_slice(&value.to_le_bytes());
+        }
+        assert_eq!(nodes[2], Some(Hash256::from(first_chunk)));
+        assert_eq!(nodes[3], Some(Hash256::from_slice(&ZERO_HASHES[0])));
+        assert_eq!(nodes[4], None);
+    }
+
+    #[test]
+    fn vector_root_matches_tree_hash() {
+        let vector =
+            Vector::<Hash256, U32>::new((0..32).map(Hash256::from_low_u64_be).collect()).unwrap();
+        assert_eq!(
+            vector_nodes(&vector, &[1, 32 + 7]),
+            vec![
+                Some(vector.tree_hash_root()),
+                Some(Hash256::from_low_u64_be(7))
+            ]
+        );
+    }
+}
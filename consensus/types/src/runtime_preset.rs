@@ -0,0 +1,229 @@
+//! Presets loaded from YAML at runtime, rather than compiled into an `EthSpec`.
+//!
+//! Only the values of a preset which are held in the `ChainSpec` can vary at runtime. Values
+//! which determine the shape of SSZ types (e.g. `SLOTS_PER_EPOCH`) are associated types of the
+//! `EthSpec`, so they are checked against the compiled `EthSpec` and a preset which changes any
+//! of them is rejected. Running with different SSZ limits still requires a new `EthSpec`.
+use crate::*;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::path::Path;
+
+/// The files which make up a preset directory, as published in the consensus specs.
+const PRESET_FILES: [&str; 7] = [
+    "phase0.yaml",
+    "altair.yaml",
+    "bellatrix.yaml",
+    "capella.yaml",
+    "deneb.yaml",
+    "electra.yaml",
+    "eip7594.yaml",
+];
+
+/// The values of a preset for every fork.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct FullPreset {
+    #[serde(flatten)]
+    pub base: BasePreset,
+    #[serde(flatten)]
+    pub altair: AltairPreset,
+    #[serde(flatten)]
+    pub bellatrix: BellatrixPreset,
+    #[serde(flatten)]
+    pub capella: CapellaPreset,
+    #[serde(flatten)]
+    pub deneb: DenebPreset,
+    #[serde(flatten)]
+    pub electra: ElectraPreset,
+    #[serde(flatten)]
+    pub eip7594: Eip7594Preset,
+}
+
+impl FullPreset {
+    pub fn from_chain_spec<E: EthSpec>(spec: &ChainSpec) -> Self {
+        Self {
+            base: BasePreset::from_chain_spec::<E>(spec),
+            altair: AltairPreset::from_chain_spec::<E>(spec),
+            bellatrix: BellatrixPreset::from_chain_spec::<E>(spec),
+            capella: CapellaPreset::from_chain_spec::<E>(spec),
+            deneb: DenebPreset::from_chain_spec::<E>(spec),
+            electra: ElectraPreset::from_chain_spec::<E>(spec),
+            eip7594: Eip7594Preset::from_chain_spec::<E>(spec),
+        }
+    }
+
+    /// Load a preset from a single YAML file containing the values for every fork.
+    pub fn load_from_file(path: &Path) -> Result<Self, String> {
+        let file = File::open(path)
+            .map_err(|e| format!("Unable to open preset file {}: {:?}", path.display(), e))?;
+        serde_yaml::from_reader(file)
+            .map_err(|e| format!("Unable to parse preset file {}: {:?}", path.display(), e))
+    }
+
+    /// Load a preset from a directory laid out like those in the consensus specs, with one YAML
+    /// file per fork.
+    pub fn load_from_dir(dir: &Path) -> Result<Self, String> {
+        let mut values = serde_yaml::Mapping::new();
+        for filename in PRESET_FILES {
+            let path = dir.join(filename);
+            let file = File::open(&path)
+                .map_err(|e| format!("Unable to open preset file {}: {:?}", path.display(), e))?;
+            let fork_values: serde_yaml::Mapping = serde_yaml::from_reader(file)
+                .map_err(|e| format!("Unable to parse preset file {}: {:?}", path.display(), e))?;
+            values.extend(fork_values);
+        }
+        serde_yaml::from_value(serde_yaml::Value::Mapping(values))
+            .map_err(|e| format!("Invalid preset in {}: {:?}", dir.display(), e))
+    }
+
+    /// Set the values of the preset which are held in the `ChainSpec`.
+    ///
+    /// Fails without modifying `spec` if any value which is fixed by `E` differs from the
+    /// compiled value.
+    pub fn apply_to_chain_spec<E: EthSpec>(&self, spec: &mut ChainSpec) -> Result<(), String> {
+        self.check_compile_time_values::<E>()
+            .map_err(|mismatched| {
+                format!(
+                    "Preset values cannot be changed without recompiling {}: {}",
+                    E::spec_name(),
+                    mismatched.join(", ")
+                )
+            })?;
+        self.set_runtime_values(spec);
+        Ok(())
+    }
+
+    /// Check that the values of the preset which are fixed at compile time match those of `E`.
+    ///
+    /// Returns the names of the mismatched values on failure.
+    pub fn check_compile_time_values<E: EthSpec>(&self) -> Result<(), Vec<String>> {
+        // Every runtime value is taken from `self`, so only compile-time values can differ.
+        let mut spec = E::default_spec();
+        self.set_runtime_values(&mut spec);
+        let expected = Self::from_chain_spec::<E>(&spec);
+
+        let to_map = |preset: &Self| match serde_json::to_value(preset) {
+            Ok(serde_json::Value::Object(map)) => map,
+            _ => serde_json::Map::new(),
+        };
+        let expected = to_map(&expected);
+        let mismatched = to_map(self)
+            .into_iter()
+            .filter(|(name, value)| expected.get(name) != Some(value))
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>();
+
+        if mismatched.is_empty() {
+            Ok(())
+        } else {
+            Err(mismatched)
+        }
+    }
+
+    fn set_runtime_values(&self, spec: &mut ChainSpec) {
+        let base = &self.base;
+        spec.max_committees_per_slot = base.max_committees_per_slot as usize;
+        spec.target_committee_size = base.target_committee_size as usize;
+        spec.shuffle_round_count = base.shuffle_round_count;
+        spec.hysteresis_quotient = base.hysteresis_quotient;
+        spec.hysteresis_downward_multiplier = base.hysteresis_downward_multiplier;
+        spec.hysteresis_upward_multiplier = base.hysteresis_upward_multiplier;
+        spec.safe_slots_to_update_justified = base.safe_slots_to_update_justified;
+        spec.min_deposit_amount = base.min_deposit_amount;
+        spec.max_effective_balance = base.max_effective_balance;
+        spec.effective_balance_increment = base.effective_balance_increment;
+        spec.min_attestation_inclusion_delay = base.min_attestation_inclusion_delay;
+        spec.min_seed_lookahead = base.min_seed_lookahead;
+        spec.max_seed_lookahead = base.max_seed_lookahead;
+        spec.min_epochs_to_inactivity_penalty = base.min_epochs_to_inactivity_penalty;
+        spec.base_reward_factor = base.base_reward_factor;
+        spec.whistleblower_reward_quotient = base.whistleblower_reward_quotient;
+        spec.proposer_reward_quotient = base.proposer_reward_quotient;
+        spec.inactivity_penalty_quotient = base.inactivity_penalty_quotient;
+        spec.min_slashing_penalty_quotient = base.min_slashing_penalty_quotient;
+        spec.proportional_slashing_multiplier = base.proportional_slashing_multiplier;
+
+        let altair = &self.altair;
+        spec.inactivity_penalty_quotient_altair = altair.inactivity_penalty_quotient_altair;
+        spec.min_slashing_penalty_quotient_altair = altair.min_slashing_penalty_quotient_altair;
+        spec.proportional_slashing_multiplier_altair =
+            altair.proportional_slashing_multiplier_altair;
+        spec.epochs_per_sync_committee_period = altair.epochs_per_sync_committee_period;
+        spec.min_sync_committee_participants = altair.min_sync_committee_participants;
+
+        let bellatrix = &self.bellatrix;
+        spec.inactivity_penalty_quotient_bellatrix =
+            bellatrix.inactivity_penalty_quotient_bellatrix;
+        spec.min_slashing_penalty_quotient_bellatrix =
+            bellatrix.min_slashing_penalty_quotient_bellatrix;
+        spec.proportional_slashing_multiplier_bellatrix =
+            bellatrix.proportional_slashing_multiplier_bellatrix;
+
+        spec.max_validators_per_withdrawals_sweep =
+            self.capella.max_validators_per_withdrawals_sweep;
+
+        let electra = &self.electra;
+        spec.min_activation_balance = electra.min_activation_balance;
+        spec.max_effective_balance_electra = electra.max_effective_balance_electra;
+        spec.min_slashing_penalty_quotient_electra = electra.min_slashing_penalty_quotient_electra;
+        spec.whistleblower_reward_quotient_electra = electra.whistleblower_reward_quotient_electra;
+        spec.max_pending_partials_per_withdrawals_sweep =
+            electra.max_pending_partials_per_withdrawals_sweep;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn preset_dir(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("presets")
+            .join(name)
+    }
+
+    #[test]
+    fn load_compiled_presets() {
+        let preset = FullPreset::load_from_dir(&preset_dir("mainnet")).unwrap();
+        assert_eq!(
+            preset,
+            FullPreset::from_chain_spec::<MainnetEthSpec>(&ChainSpec::mainnet())
+        );
+        assert_eq!(preset.check_compile_time_values::<MainnetEthSpec>(), Ok(()));
+
+        let preset = FullPreset::load_from_dir(&preset_dir("minimal")).unwrap();
+        assert_eq!(preset.check_compile_time_values::<MinimalEthSpec>(), Ok(()));
+    }
+
+    #[test]
+    fn runtime_values_are_applied() {
+        let mut preset = FullPreset::load_from_dir(&preset_dir("mainnet")).unwrap();
+        preset.base.shuffle_round_count = 10;
+        preset.electra.max_effective_balance_electra = 4_096_000_000_000;
+
+        let mut spec = ChainSpec::mainnet();
+        preset
+            .apply_to_chain_spec::<MainnetEthSpec>(&mut spec)
+            .unwrap();
+        assert_eq!(spec.shuffle_round_count, 10);
+        assert_eq!(spec.max_effective_balance_electra, 4_096_000_000_000);
+        assert_eq!(FullPreset::from_chain_spec::<MainnetEthSpec>(&spec), preset);
+    }
+
+    #[test]
+    fn compile_time_values_are_checked() {
+        let preset = FullPreset::load_from_dir(&preset_dir("minimal")).unwrap();
+        let mismatched = preset
+            .check_compile_time_values::<MainnetEthSpec>()
+            .unwrap_err();
+        assert!(mismatched.contains(&"SLOTS_PER_EPOCH".to_string()));
+        assert!(!mismatched.contains(&"SHUFFLE_ROUND_COUNT".to_string()));
+
+        let mut spec = ChainSpec::mainnet();
+        assert!(preset
+            .apply_to_chain_spec::<MainnetEthSpec>(&mut spec)
+            .is_err());
+        assert_eq!(spec, ChainSpec::mainnet());
+    }
+}
@@ -105,16 +105,6 @@ impl<E: EthSpec, Payload: AbstractExecPayload<E>> SignedBeaconBlock<E, Payload>
         Self::from_ssz_bytes_with(bytes, |bytes| BeaconBlock::from_ssz_bytes(bytes, spec))
     }
 
-    /// SSZ decode with explicit fork variant.
-    pub fn from_ssz_bytes_for_fork(
-        bytes: &[u8],
-        fork_name: ForkName,
-    ) -> Result<Self, ssz::DecodeError> {
-        Self::from_ssz_bytes_with(bytes, |bytes| {
-            BeaconBlock::from_ssz_bytes_for_fork(bytes, fork_name)
-        })
-    }
-
     /// SSZ decode which attempts to decode all variants (slow).
     pub fn any_from_ssz_bytes(bytes: &[u8]) -> Result<Self, ssz::DecodeError> {
         Self::from_ssz_bytes_with(bytes, BeaconBlock::any_from_ssz_bytes)
@@ -592,6 +582,16 @@ impl<E: EthSpec> SignedBeaconBlock<E> {
     }
 }
 
+impl<E: EthSpec, Payload: AbstractExecPayload<E>> ForkVersionDecode
+    for SignedBeaconBlock<E, Payload>
+{
+    fn from_ssz_bytes_by_fork(bytes: &[u8], fork_name: ForkName) -> Result<Self, ssz::DecodeError> {
+        Self::from_ssz_bytes_with(bytes, |bytes| {
+            BeaconBlock::from_ssz_bytes_by_fork(bytes, fork_name)
+        })
+    }
+}
+
 impl<E: EthSpec, Payload: AbstractExecPayload<E>> ForkVersionDeserialize
     for SignedBeaconBlock<E, Payload>
 {
@@ -765,6 +765,12 @@ mod test {
                         .unwrap()
                         .tree_hash_root()
                 );
+
+                let body = block.message().body();
+                assert_eq!(
+                    body.tree_hash_root_with_payload_root(payload.tree_hash_root()),
+                    body.tree_hash_root()
+                );
             }
 
             let reconstructed = blinded_block.try_into_full_block(payload).unwrap();
@@ -0,0 +1,262 @@
+//! Read-only views of SSZ-encoded blocks.
+//!
+//! Decoding an entire `SignedBeaconBlock` is expensive, particularly for blocks with a full
+//! execution payload. These views read the fixed-length fields of a block directly from its SSZ
+//! bytes, which is useful when only the slot or roots of the block are required (e.g. to
+//! determine its fork, or to look up its parent).
+use crate::*;
+use bls::SIGNATURE_BYTES_LEN;
+use ssz::{Decode, DecodeError, BYTES_PER_LENGTH_OFFSET};
+use tree_hash::TreeHash;
+
+/// Length of the fixed portion of an SSZ-encoded `SignedBeaconBlock`.
+const SIGNED_BLOCK_FIXED_LEN: usize = BYTES_PER_LENGTH_OFFSET + SIGNATURE_BYTES_LEN;
+/// Length of the fixed portion of an SSZ-encoded `BeaconBlock`.
+const BLOCK_FIXED_LEN: usize = 8 + 8 + 32 + 32 + BYTES_PER_LENGTH_OFFSET;
+
+/// A view of an SSZ-encoded `SignedBeaconBlock` of any fork.
+///
+/// Only the structure of the fixed-length fields is validated on construction, the body is not
+/// decoded unless explicitly requested.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SignedBeaconBlockView<'a> {
+    message: BeaconBlockView<'a>,
+    signature: &'a [u8; SIGNATURE_BYTES_LEN],
+}
+
+/// A view of an SSZ-encoded `BeaconBlock` of any fork.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BeaconBlockView<'a> {
+    slot: &'a [u8; 8],
+    proposer_index: &'a [u8; 8],
+    parent_root: &'a [u8; 32],
+    state_root: &'a [u8; 32],
+    body: &'a [u8],
+}
+
+impl<'a> SignedBeaconBlockView<'a> {
+    /// Create a view of the SSZ-encoded `SignedBeaconBlock` in `bytes`.
+    pub fn from_ssz_bytes(bytes: &'a [u8]) -> Result<Self, DecodeError> {
+        let mut remaining = bytes;
+        check_offset(take(&mut remaining)?, SIGNED_BLOCK_FIXED_LEN)?;
+        let signature = take(&mut remaining)?;
+        let message = BeaconBlockView::from_ssz_bytes(remaining)?;
+
+        Ok(Self { message, signature })
+    }
+
+    /// A view of the `message` of the block.
+    pub fn message(&self) -> BeaconBlockView<'a> {
+        self.message
+    }
+
+    /// The compressed bytes of the block's signature.
+    pub fn signature_bytes(&self) -> &'a [u8] {
+        self.signature
+    }
+
+    /// Convenience accessor for the block's slot.
+    pub fn slot(&self) -> Slot {
+        self.message.slot()
+    }
+
+    /// Convenience accessor for the block's parent root.
+    pub fn parent_root(&self) -> Hash256 {
+        self.message.parent_root()
+    }
+
+    /// Returns the `tree_hash_root` of the block, decoding the body in order to hash it.
+    pub fn canonical_root<E: EthSpec, Payload: AbstractExecPayload<E>>(
+        &self,
+        spec: &ChainSpec,
+    ) -> Result<Hash256, DecodeError> {
+        self.message.canonical_root::<E, Payload>(spec)
+    }
+}
+
+impl<'a> BeaconBlockView<'a> {
+    /// Create a view of the SSZ-encoded `BeaconBlock` in `bytes`.
+    pub fn from_ssz_bytes(bytes: &'a [u8]) -> Result<Self, DecodeError> {
+        let mut remaining = bytes;
+        let slot = take(&mut remaining)?;
+        let proposer_index = take(&mut remaining)?;
+        let parent_root = take(&mut remaining)?;
+        let state_root = take(&mut remaining)?;
+        check_offset(take(&mut remaining)?, BLOCK_FIXED_LEN)?;
+
+        Ok(Self {
+            slot,
+            proposer_index,
+            parent_root,
+            state_root,
+            body: remaining,
+        })
+    }
+
+    pub fn slot(&self) -> Slot {
+        Slot::new(u64::from_le_bytes(*self.slot))
+    }
+
+    pub fn proposer_index(&self) -> u64 {
+        u64::from_le_bytes(*self.proposer_index)
+    }
+
+    pub fn parent_root(&self) -> Hash256 {
+        Hash256::from(*self.parent_root)
+    }
+
+    pub fn state_root(&self) -> Hash256 {
+        Hash256::from(*self.state_root)
+    }
+
+    /// The SSZ bytes of the block's body.
+    pub fn body_bytes(&self) -> &'a [u8] {
+        self.body
+    }
+
+    /// Returns the name of the fork at the block's slot.
+    pub fn fork_name<E: EthSpec>(&self, spec: &ChainSpec) -> ForkName {
+        spec.fork_name_at_slot::<E>(self.slot())
+    }
+
+    /// Decode the body of the block according to the fork at its slot.
+    pub fn decode_body<E: EthSpec, Payload: AbstractExecPayload<E>>(
+        &self,
+        spec: &ChainSpec,
+    ) -> Result<BeaconBlockBody<E, Payload>, DecodeError> {
+        Ok(map_fork_name!(
+            self.fork_name::<E>(spec),
+            BeaconBlockBody,
+            <_>::from_ssz_bytes(self.body)?
+        ))
+    }
+
+    /// Return the tree hash root of the block's body.
+    ///
+    /// The root is the same regardless of whether the body has a full or blinded payload, so
+    /// `Payload` must only match the encoding of the bytes.
+    pub fn body_root<E: EthSpec, Payload: AbstractExecPayload<E>>(
+        &self,
+        spec: &ChainSpec,
+    ) -> Result<Hash256, DecodeError> {
+        self.decode_body::<E, Payload>(spec)
+            .map(|body| body.tree_hash_root())
+    }
+
+    /// Returns the header of the block, decoding the body in order to compute its root.
+    pub fn block_header<E: EthSpec, Payload: AbstractExecPayload<E>>(
+        &self,
+        spec: &ChainSpec,
+    ) -> Result<BeaconBlockHeader, DecodeError> {
+        Ok(BeaconBlockHeader {
+            slot: self.slot(),
+            proposer_index: self.proposer_index(),
+            parent_root: self.parent_root(),
+            state_root: self.state_root(),
+            body_root: self.body_root::<E, Payload>(spec)?,
+        })
+    }
+
+    /// Returns the `tree_hash_root` of the block, which is equal to the root of its header.
+    pub fn canonical_root<E: EthSpec, Payload: AbstractExecPayload<E>>(
+        &self,
+        spec: &ChainSpec,
+    ) -> Result<Hash256, DecodeError> {
+        self.block_header::<E, Payload>(spec)
+            .map(|header| header.tree_hash_root())
+    }
+}
+
+/// Split the first `N` bytes off the front of `bytes`.
+fn take<'a, const N: usize>(bytes: &mut &'a [u8]) -> Result<&'a [u8; N], DecodeError> {
+    let len = bytes.len();
+    let (head, tail) = bytes
+        .get(..N)
+        .zip(bytes.get(N..))
+        .ok_or(DecodeError::InvalidByteLength { len, expected: N })?;
+    *bytes = tail;
+    head.try_into()
+        .map_err(|_| DecodeError::InvalidByteLength { len, expected: N })
+}
+
+/// Check that an offset points to the end of the fixed portion of a container with a single
+/// variable-length field.
+fn check_offset(
+    offset: &[u8; BYTES_PER_LENGTH_OFFSET],
+    fixed_len: usize,
+) -> Result<(), DecodeError> {
+    let offset = u32::from_le_bytes(*offset) as usize;
+    match offset.cmp(&fixed_len) {
+        std::cmp::Ordering::Equal => Ok(()),
+        std::cmp::Ordering::Less => Err(DecodeError::OffsetIntoFixedPortion(offset)),
+        std::cmp::Ordering::Greater => Err(DecodeError::OffsetSkipsVariableBytes(offset)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ssz::Encode;
+
+    type E = MainnetEthSpec;
+
+    fn blocks(spec: &ChainSpec) -> Vec<SignedBeaconBlock<E>> {
+        let mut block = BeaconBlock::Deneb(BeaconBlockDeneb::empty(spec));
+        *block.slot_mut() = spec
+            .deneb_fork_epoch
+            .unwrap()
+            .start_slot(E::slots_per_epoch());
+        *block.proposer_index_mut() = 42;
+        *block.parent_root_mut() = Hash256::repeat_byte(1);
+        *block.state_root_mut() = Hash256::repeat_byte(2);
+
+        vec![
+            SignedBeaconBlock::from_block(
+                BeaconBlock::Base(BeaconBlockBase::empty(spec)),
+                Signature::empty(),
+            ),
+            SignedBeaconBlock::from_block(block, Signature::empty()),
+        ]
+    }
+
+    #[test]
+    fn view_matches_decoded_block() {
+        let spec = &E::default_spec();
+
+        for block in blocks(spec) {
+            let bytes = block.as_ssz_bytes();
+            let view = SignedBeaconBlockView::from_ssz_bytes(&bytes).unwrap();
+            let message = view.message();
+
+            assert_eq!(message.slot(), block.slot());
+            assert_eq!(message.proposer_index(), block.message().proposer_index());
+            assert_eq!(message.parent_root(), block.parent_root());
+            assert_eq!(message.state_root(), block.state_root());
+            assert_eq!(view.signature_bytes(), &block.signature().serialize()[..]);
+            assert_eq!(
+                message.body_root::<E, FullPayload<E>>(spec).unwrap(),
+                block.message().body_root()
+            );
+            assert_eq!(
+                view.canonical_root::<E, FullPayload<E>>(spec).unwrap(),
+                block.canonical_root()
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_bytes() {
+        let spec = &E::default_spec();
+        let bytes = blocks(spec)[0].as_ssz_bytes();
+
+        assert!(SignedBeaconBlockView::from_ssz_bytes(&[]).is_err());
+        assert!(SignedBeaconBlockView::from_ssz_bytes(&bytes[..SIGNED_BLOCK_FIXED_LEN]).is_err());
+
+        let mut bad_offset = bytes.clone();
+        bad_offset[0] = 0;
+        assert_eq!(
+            SignedBeaconBlockView::from_ssz_bytes(&bad_offset),
+            Err(DecodeError::OffsetIntoFixedPortion(0))
+        );
+    }
+}
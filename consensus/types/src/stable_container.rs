@@ -0,0 +1,535 @@
+//! Building blocks for the `StableContainer` and `Profile` types defined in EIP-7495.
+//!
+//! A `StableContainer[N]` has a fixed capacity of `N` fields, each of which is optional. It is
+//! serialized as a `Bitvector[N]` of the fields which are present, followed by the present fields
+//! encoded as a regular container. Its tree hash root is that of a container padded to `N` fields
+//! (with absent fields as zero chunks), mixed in with the root of the bitvector. This keeps the
+//! generalized indices of each field stable as fields are added in future forks.
+//!
+//! A `Profile[B]` is a view of the `StableContainer` `B` in which some fields are required and the
+//! rest are optional. It is serialized as a bitvector of only its optional fields (omitted if there
+//! are none), followed by the present fields. It has the same tree hash root as `B`.
+//!
+//! The `ssz_derive` and `tree_hash_derive` macros come from external crates and do not support
+//! these types, so the encoders, decoders and `stable_container_tree_hash_root` here are used to
+//! implement `Encode`, `Decode` and `TreeHash` by hand. Derive support belongs in those crates, and
+//! can replace these helpers once it lands there. Fields must be appended or registered in the
+//! order in which they are defined.
+use crate::{BitVector, Hash256, Unsigned};
+use ethereum_hashing::hash32_concat;
+use ssz::{Decode, DecodeError, Encode, SszDecoder, SszDecoderBuilder, BYTES_PER_LENGTH_OFFSET};
+use tree_hash::{TreeHash, BYTES_PER_CHUNK};
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    /// More fields were appended than the container has capacity for.
+    TooManyFields { max: usize },
+}
+
+/// A field which has been SSZ encoded, prior to being placed in its container.
+struct EncodedField {
+    is_fixed_len: bool,
+    bytes: Vec<u8>,
+}
+
+impl EncodedField {
+    fn new<T: Encode>(field: &T) -> Self {
+        Self {
+            is_fixed_len: T::is_ssz_fixed_len(),
+            bytes: field.as_ssz_bytes(),
+        }
+    }
+}
+
+/// Append `fields` to `buf` as a regular SSZ container.
+fn encode_container(fields: &[EncodedField], buf: &mut Vec<u8>) {
+    let fixed_len: usize = fields
+        .iter()
+        .map(|field| {
+            if field.is_fixed_len {
+                field.bytes.len()
+            } else {
+                BYTES_PER_LENGTH_OFFSET
+            }
+        })
+        .sum();
+
+    let mut offset = fixed_len;
+    for field in fields {
+        if field.is_fixed_len {
+            buf.extend_from_slice(&field.bytes);
+        } else {
+            buf.extend_from_slice(&ssz::encode_length(offset));
+            offset = offset.saturating_add(field.bytes.len());
+        }
+    }
+    for field in fields.iter().filter(|field| !field.is_fixed_len) {
+        buf.extend_from_slice(&field.bytes);
+    }
+}
+
+/// Pack `bits` into bytes, least significant bit first, as for an SSZ `Bitvector`.
+fn pack_bits(bits: &[bool]) -> Vec<u8> {
+    bits.chunks(8)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .rev()
+                .fold(0u8, |byte, bit| byte.wrapping_shl(1) | u8::from(*bit))
+        })
+        .collect()
+}
+
+/// Unpack `len` bits from `bytes`, rejecting any set padding bits.
+fn unpack_bits(bytes: &[u8], len: usize) -> Result<Vec<bool>, DecodeError> {
+    let mut bits = bytes
+        .iter()
+        .flat_map(|byte| (0..8).map(move |i| byte.wrapping_shr(i) & 1 == 1))
+        .collect::<Vec<_>>();
+    if bits.iter().skip(len).any(|bit| *bit) {
+        return Err(DecodeError::BytesInvalid(
+            "Bitvector has set padding bits".to_string(),
+        ));
+    }
+    bits.truncate(len);
+    Ok(bits)
+}
+
+/// Split the bitvector of `len` bits from the front of `bytes`.
+fn split_bits(bytes: &[u8], len: usize) -> Result<(Vec<bool>, &[u8]), DecodeError> {
+    let bitvector_len = len.div_ceil(8);
+    if bytes.len() < bitvector_len {
+        return Err(DecodeError::InvalidByteLength {
+            len: bytes.len(),
+            expected: bitvector_len,
+        });
+    }
+    let (bitvector, fields) = bytes.split_at(bitvector_len);
+    Ok((unpack_bits(bitvector, len)?, fields))
+}
+
+/// Encodes a `StableContainer[N]`.
+pub struct StableContainerEncoder<N: Unsigned> {
+    active_fields: Vec<bool>,
+    fields: Vec<EncodedField>,
+    _phantom: std::marker::PhantomData<N>,
+}
+
+impl<N: Unsigned> Default for StableContainerEncoder<N> {
+    fn default() -> Self {
+        Self {
+            active_fields: vec![],
+            fields: vec![],
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<N: Unsigned> StableContainerEncoder<N> {
+    /// Append the next field, which is absent if `None`.
+    pub fn append<T: Encode>(&mut self, field: Option<&T>) -> Result<(), Error> {
+        if self.active_fields.len() >= N::to_usize() {
+            return Err(Error::TooManyFields { max: N::to_usize() });
+        }
+        self.active_fields.push(field.is_some());
+        self.fields.extend(field.map(EncodedField::new));
+        Ok(())
+    }
+
+    pub fn finalize(mut self, buf: &mut Vec<u8>) {
+        self.active_fields.resize(N::to_usize(), false);
+        buf.extend_from_slice(&pack_bits(&self.active_fields));
+        encode_container(&self.fields, buf);
+    }
+}
+
+/// Encodes a `Profile` of a `StableContainer`.
+#[derive(Default)]
+pub struct ProfileEncoder {
+    optional_fields: Vec<bool>,
+    fields: Vec<EncodedField>,
+}
+
+impl ProfileEncoder {
+    pub fn append_required<T: Encode>(&mut self, field: &T) {
+        self.fields.push(EncodedField::new(field));
+    }
+
+    pub fn append_optional<T: Encode>(&mut self, field: Option<&T>) {
+        self.optional_fields.push(field.is_some());
+        self.fields.extend(field.map(EncodedField::new));
+    }
+
+    pub fn finalize(self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&pack_bits(&self.optional_fields));
+        encode_container(&self.fields, buf);
+    }
+}
+
+/// Builds a decoder for a `StableContainer[N]`.
+pub struct StableContainerDecoderBuilder<'a> {
+    active_fields: Vec<bool>,
+    builder: SszDecoderBuilder<'a>,
+    index: usize,
+}
+
+impl<'a> StableContainerDecoderBuilder<'a> {
+    pub fn new<N: Unsigned>(bytes: &'a [u8]) -> Result<Self, DecodeError> {
+        let (active_fields, fields) = split_bits(bytes, N::to_usize())?;
+        Ok(Self {
+            active_fields,
+            builder: SszDecoderBuilder::new(fields),
+            index: 0,
+        })
+    }
+
+    /// Register the type of the next field, which will only be decoded if it is present.
+    pub fn register_type<T: Decode>(&mut self) -> Result<(), DecodeError> {
+        let active = self.active_fields.get(self.index).copied().ok_or_else(|| {
+            DecodeError::BytesInvalid("More fields than the StableContainer capacity".to_string())
+        })?;
+        if active {
+            self.builder.register_type::<T>()?;
+        }
+        self.index = self.index.saturating_add(1);
+        Ok(())
+    }
+
+    /// Build the decoder, failing if a field is present which is unknown to this version of the
+    /// container.
+    pub fn build(self) -> Result<StableContainerDecoder<'a>, DecodeError> {
+        if self
+            .active_fields
+            .iter()
+            .skip(self.index)
+            .any(|active| *active)
+        {
+            return Err(DecodeError::BytesInvalid(
+                "StableContainer has an unknown field".to_string(),
+            ));
+        }
+        Ok(StableContainerDecoder {
+            active_fields: self.active_fields,
+            decoder: self.builder.build()?,
+            index: 0,
+        })
+    }
+}
+
+/// Decodes the fields of a `StableContainer` in order.
+pub struct StableContainerDecoder<'a> {
+    active_fields: Vec<bool>,
+    decoder: SszDecoder<'a>,
+    index: usize,
+}
+
+impl<'a> StableContainerDecoder<'a> {
+    /// Decode the next field, returning `None` if it is absent.
+    pub fn decode_next<T: Decode>(&mut self) -> Result<Option<T>, DecodeError> {
+        let active = self.active_fields.get(self.index).copied().unwrap_or(false);
+        self.index = self.index.saturating_add(1);
+        if active {
+            self.decoder.decode_next().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// Builds a decoder for a `Profile` with `optional_field_count` optional fields.
+pub struct ProfileDecoderBuilder<'a> {
+    optional_fields: Vec<bool>,
+    builder: SszDecoderBuilder<'a>,
+    optional_index: usize,
+}
+
+impl<'a> ProfileDecoderBuilder<'a> {
+    pub fn new(bytes: &'a [u8], optional_field_count: usize) -> Result<Self, DecodeError> {
+        let (optional_fields, fields) = split_bits(bytes, optional_field_count)?;
+        Ok(Self {
+            optional_fields,
+            builder: SszDecoderBuilder::new(fields),
+            optional_index: 0,
+        })
+    }
+
+    pub fn register_required<T: Decode>(&mut self) -> Result<(), DecodeError> {
+        self.builder.register_type::<T>()
+    }
+
+    pub fn register_optional<T: Decode>(&mut self) -> Result<(), DecodeError> {
+        let present = self
+            .optional_fields
+            .get(self.optional_index)
+            .copied()
+            .ok_or_else(|| DecodeError::BytesInvalid("Too many optional fields".to_string()))?;
+        if present {
+            self.builder.register_type::<T>()?;
+        }
+        self.optional_index = self.optional_index.saturating_add(1);
+        Ok(())
+    }
+
+    pub fn build(self) -> Result<ProfileDecoder<'a>, DecodeError> {
+        Ok(ProfileDecoder {
+            optional_fields: self.optional_fields,
+            decoder: self.builder.build()?,
+            optional_index: 0,
+        })
+    }
+}
+
+/// Decodes the fields of a `Profile` in order.
+pub struct ProfileDecoder<'a> {
+    optional_fields: Vec<bool>,
+    decoder: SszDecoder<'a>,
+    optional_index: usize,
+}
+
+impl<'a> ProfileDecoder<'a> {
+    pub fn decode_required<T: Decode>(&mut self) -> Result<T, DecodeError> {
+        self.decoder.decode_next()
+    }
+
+    pub fn decode_optional<T: Decode>(&mut self) -> Result<Option<T>, DecodeError> {
+        let present = self
+            .optional_fields
+            .get(self.optional_index)
+            .copied()
+            .unwrap_or(false);
+        self.optional_index = self.optional_index.saturating_add(1);
+        if present {
+            self.decoder.decode_next().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// Compute the tree hash root of a `StableContainer[N]`, or of any `Profile` of it, from the roots
+/// of its fields. `field_roots` is indexed by the position of each field in the `StableContainer`,
+/// with `None` for absent fields.
+pub fn stable_container_tree_hash_root<N: Unsigned>(
+    field_roots: &[Option<Hash256>],
+) -> Result<Hash256, Error> {
+    let max = N::to_usize();
+    if field_roots.len() > max {
+        return Err(Error::TooManyFields { max });
+    }
+
+    let mut active_fields = BitVector::<N>::new();
+    let mut leaves = Vec::with_capacity(field_roots.len().saturating_mul(BYTES_PER_CHUNK));
+    for (i, root) in field_roots.iter().enumerate() {
+        if root.is_some() {
+            active_fields
+                .set(i, true)
+                .map_err(|_| Error::TooManyFields { max })?;
+        }
+        leaves.extend_from_slice(root.unwrap_or_default().as_bytes());
+    }
+
+    let fields_root = tree_hash::merkle_root(&leaves, max);
+    Ok(Hash256::from(hash32_concat(
+        fields_root.as_bytes(),
+        active_fields.tree_hash_root().as_bytes(),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::typenum::{U16, U4};
+    use crate::VariableList;
+
+    /// The `Shape` example from EIP-7495, with an additional variable-length field.
+    #[derive(Debug, PartialEq)]
+    struct Shape {
+        side: Option<u16>,
+        color: Option<u8>,
+        radius: Option<u16>,
+        label: Option<VariableList<u8, U16>>,
+    }
+
+    impl Shape {
+        fn as_ssz_bytes(&self) -> Vec<u8> {
+            let mut encoder = StableContainerEncoder::<U4>::default();
+            encoder.append(self.side.as_ref()).unwrap();
+            encoder.append(self.color.as_ref()).unwrap();
+            encoder.append(self.radius.as_ref()).unwrap();
+            encoder.append(self.label.as_ref()).unwrap();
+            let mut buf = vec![];
+            encoder.finalize(&mut buf);
+            buf
+        }
+
+        fn from_ssz_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+            let mut builder = StableContainerDecoderBuilder::new::<U4>(bytes)?;
+            builder.register_type::<u16>()?;
+            builder.register_type::<u8>()?;
+            builder.register_type::<u16>()?;
+            builder.register_type::<VariableList<u8, U16>>()?;
+            let mut decoder = builder.build()?;
+            Ok(Self {
+                side: decoder.decode_next()?,
+                color: decoder.decode_next()?,
+                radius: decoder.decode_next()?,
+                label: decoder.decode_next()?,
+            })
+        }
+
+        fn tree_hash_root(&self) -> Hash256 {
+            stable_container_tree_hash_root::<U4>(&[
+                self.side.map(|side| side.tree_hash_root()),
+                self.color.map(|color| color.tree_hash_root()),
+                self.radius.map(|radius| radius.tree_hash_root()),
+                self.label.as_ref().map(|label| label.tree_hash_root()),
+            ])
+            .unwrap()
+        }
+    }
+
+    /// A `Profile[Shape]` with a required `color` and an optional `side`.
+    #[derive(Debug, PartialEq)]
+    struct Square {
+        side: Option<u16>,
+        color: u8,
+    }
+
+    impl Square {
+        fn as_ssz_bytes(&self) -> Vec<u8> {
+            let mut encoder = ProfileEncoder::default();
+            encoder.append_optional(self.side.as_ref());
+            encoder.append_required(&self.color);
+            let mut buf = vec![];
+            encoder.finalize(&mut buf);
+            buf
+        }
+
+        fn from_ssz_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+            let mut builder = ProfileDecoderBuilder::new(bytes, 1)?;
+            builder.register_optional::<u16>()?;
+            builder.register_required::<u8>()?;
+            let mut decoder = builder.build()?;
+            Ok(Self {
+                side: decoder.decode_optional()?,
+                color: decoder.decode_required()?,
+            })
+        }
+    }
+
+    #[test]
+    fn stable_container_round_trip() {
+        let square = Shape {
+            side: Some(0x42),
+            color: Some(1),
+            radius: None,
+            label: None,
+        };
+        assert_eq!(square.as_ssz_bytes(), hex::decode("03420001").unwrap());
+        assert_eq!(Shape::from_ssz_bytes(&square.as_ssz_bytes()), Ok(square));
+
+        let circle = Shape {
+            side: None,
+            color: Some(1),
+            radius: Some(0x42),
+            label: None,
+        };
+        assert_eq!(circle.as_ssz_bytes(), hex::decode("06014200").unwrap());
+        assert_eq!(Shape::from_ssz_bytes(&circle.as_ssz_bytes()), Ok(circle));
+
+        let labelled = Shape {
+            side: None,
+            color: Some(1),
+            radius: None,
+            label: Some(vec![0xaa, 0xbb].into()),
+        };
+        assert_eq!(
+            labelled.as_ssz_bytes(),
+            hex::decode("0a0105000000aabb").unwrap()
+        );
+        assert_eq!(
+            Shape::from_ssz_bytes(&labelled.as_ssz_bytes()),
+            Ok(labelled)
+        );
+    }
+
+    #[test]
+    fn stable_container_unknown_field() {
+        // A field from a future version of the container.
+        assert!(
+            StableContainerDecoderBuilder::new::<U4>(&hex::decode("0801").unwrap())
+                .and_then(|mut builder| {
+                    builder.register_type::<u16>()?;
+                    builder.register_type::<u8>()?;
+                    builder.register_type::<u16>()?;
+                    builder.build()
+                })
+                .is_err()
+        );
+
+        // Padding bits beyond the capacity of the container.
+        assert!(Shape::from_ssz_bytes(&hex::decode("13420001").unwrap()).is_err());
+    }
+
+    #[test]
+    fn profile_round_trip() {
+        let square = Square {
+            side: Some(0x42),
+            color: 1,
+        };
+        assert_eq!(square.as_ssz_bytes(), hex::decode("01420001").unwrap());
+        assert_eq!(Square::from_ssz_bytes(&square.as_ssz_bytes()), Ok(square));
+
+        let sideless = Square {
+            side: None,
+            color: 1,
+        };
+        assert_eq!(sideless.as_ssz_bytes(), hex::decode("0001").unwrap());
+        assert_eq!(
+            Square::from_ssz_bytes(&sideless.as_ssz_bytes()),
+            Ok(sideless)
+        );
+    }
+
+    #[test]
+    fn stable_container_tree_hash() {
+        let square = Shape {
+            side: Some(0x42),
+            color: Some(1),
+            radius: None,
+            label: None,
+        };
+
+        let chunk = |bytes: &[u8]| {
+            let mut chunk = [0; 32];
+            chunk[..bytes.len()].copy_from_slice(bytes);
+            chunk
+        };
+        let fields_root = hash32_concat(
+            &hash32_concat(&chunk(&[0x42, 0x00]), &chunk(&[0x01])),
+            &hash32_concat(&[0; 32], &[0; 32]),
+        );
+        let expected = hash32_concat(&fields_root, &chunk(&[0x03]));
+
+        assert_eq!(square.tree_hash_root(), Hash256::from(expected));
+
+        let labelled = Shape {
+            side: None,
+            color: Some(1),
+            radius: None,
+            label: Some(vec![0xaa, 0xbb].into()),
+        };
+        let label_root = hash32_concat(&chunk(&[0xaa, 0xbb]), &chunk(&[0x02]));
+        let fields_root = hash32_concat(
+            &hash32_concat(&[0; 32], &chunk(&[0x01])),
+            &hash32_concat(&[0; 32], &label_root),
+        );
+        let expected = hash32_concat(&fields_root, &chunk(&[0x0a]));
+
+        assert_eq!(labelled.tree_hash_root(), Hash256::from(expected));
+        assert_eq!(
+            stable_container_tree_hash_root::<U4>(&[None; 5]),
+            Err(Error::TooManyFields { max: 4 })
+        );
+    }
+}
@@ -0,0 +1,207 @@
+use super::{SeedableRng, TestRandom, XorShiftRng};
+use crate::*;
+use arbitrary::Unstructured;
+use std::marker::PhantomData;
+
+/// The number of epochs after the start of the last scheduled fork within which slots are chosen.
+const LAST_FORK_EPOCHS: u64 = 1024;
+
+/// Builds signed blocks with arbitrary contents, for use by fuzz targets.
+///
+/// The fork and slot of the block are drawn from an `Unstructured` seed, and are consistent with
+/// `spec` so that the block has the shape of one which could appear on that chain and survives an
+/// SSZ round trip. The slot, proposer index and parent root may be fixed rather than generated.
+///
+/// The contents of the block are generated by `TestRandom` from a seed drawn from `Unstructured`,
+/// rather than by `Arbitrary`, since almost no arbitrary byte strings are valid BLS signatures.
+/// This means that every input produces a block.
+pub struct BeaconBlockBuilder<'a, E: EthSpec> {
+    spec: &'a ChainSpec,
+    fork_name: Option<ForkName>,
+    slot: Option<Slot>,
+    proposer_index: Option<u64>,
+    parent_root: Option<Hash256>,
+    _phantom: PhantomData<E>,
+}
+
+impl<'a, E: EthSpec> BeaconBlockBuilder<'a, E> {
+    pub fn new(spec: &'a ChainSpec) -> Self {
+        Self {
+            spec,
+            fork_name: None,
+            slot: None,
+            proposer_index: None,
+            parent_root: None,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Only build blocks for `fork_name`, which must be scheduled in the spec.
+    pub fn fork_name(mut self, fork_name: ForkName) -> Self {
+        self.fork_name = Some(fork_name);
+        self
+    }
+
+    /// Only build blocks at `slot`. The fork is determined by the slot.
+    pub fn slot(mut self, slot: Slot) -> Self {
+        self.slot = Some(slot);
+        self
+    }
+
+    pub fn proposer_index(mut self, proposer_index: u64) -> Self {
+        self.proposer_index = Some(proposer_index);
+        self
+    }
+
+    pub fn parent_root(mut self, parent_root: Hash256) -> Self {
+        self.parent_root = Some(parent_root);
+        self
+    }
+
+    /// Build a block from the bytes in `u`.
+    ///
+    /// Returns `IncorrectFormat` if the requested fork is not reachable under the spec, or does
+    /// not match the requested slot.
+    pub fn build(&self, u: &mut Unstructured<'_>) -> arbitrary::Result<SignedBeaconBlock<E>> {
+        let (fork_name, slot) = match (self.fork_name, self.slot) {
+            (fork_name, Some(slot)) => {
+                let slot_fork_name = self.spec.fork_name_at_slot::<E>(slot);
+                if fork_name.is_some_and(|fork_name| fork_name != slot_fork_name) {
+                    return Err(arbitrary::Error::IncorrectFormat);
+                }
+                (slot_fork_name, slot)
+            }
+            (Some(fork_name), None) => {
+                let (start, end) = self
+                    .fork_slots(fork_name)
+                    .ok_or(arbitrary::Error::IncorrectFormat)?;
+                (fork_name, Slot::new(u.int_in_range(start..=end)?))
+            }
+            (None, None) => {
+                let reachable = ForkName::list_all()
+                    .into_iter()
+                    .filter_map(|fork_name| Some((fork_name, self.fork_slots(fork_name)?)))
+                    .collect::<Vec<_>>();
+                let (fork_name, (start, end)) = *u.choose(&reachable)?;
+                (fork_name, Slot::new(u.int_in_range(start..=end)?))
+            }
+        };
+
+        let mut rng = XorShiftRng::from_seed(u.arbitrary()?);
+        let mut block = map_fork_name!(fork_name, BeaconBlock, <_>::random_for_test(&mut rng));
+        *block.slot_mut() = slot;
+        if let Some(proposer_index) = self.proposer_index {
+            *block.proposer_index_mut() = proposer_index;
+        }
+        if let Some(parent_root) = self.parent_root {
+            *block.parent_root_mut() = parent_root;
+        }
+
+        Ok(SignedBeaconBlock::from_block(
+            block,
+            Signature::random_for_test(&mut rng),
+        ))
+    }
+
+    /// Returns the first and last slots at which `fork_name` is the active fork, or `None` if it
+    /// is never active.
+    fn fork_slots(&self, fork_name: ForkName) -> Option<(u64, u64)> {
+        let slots_per_epoch = E::slots_per_epoch();
+        let fork_epoch = self.spec.fork_epoch(fork_name)?;
+        if self.spec.fork_name_at_epoch(fork_epoch) != fork_name {
+            // A later fork is scheduled at the same epoch.
+            return None;
+        }
+
+        let next_fork_epoch =
+            std::iter::successors(fork_name.next_fork(), |fork_name| fork_name.next_fork())
+                .find_map(|fork_name| self.spec.fork_epoch(fork_name));
+        let end_epoch = next_fork_epoch.unwrap_or(fork_epoch + LAST_FORK_EPOCHS);
+
+        Some((
+            fork_epoch.start_slot(slots_per_epoch).as_u64(),
+            end_epoch.start_slot(slots_per_epoch).as_u64() - 1,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type E = MinimalEthSpec;
+
+    /// A spec in which each fork is scheduled one epoch after the previous one.
+    fn spec() -> ChainSpec {
+        let mut spec = E::default_spec();
+        spec.altair_fork_epoch = Some(Epoch::new(1));
+        spec.bellatrix_fork_epoch = Some(Epoch::new(2));
+        spec.capella_fork_epoch = Some(Epoch::new(3));
+        spec.deneb_fork_epoch = Some(Epoch::new(4));
+        spec.electra_fork_epoch = Some(Epoch::new(5));
+        spec
+    }
+
+    fn random_bytes(seed: u8) -> Vec<u8> {
+        (0..64)
+            .map(|i| seed.wrapping_mul(31).wrapping_add(i))
+            .collect()
+    }
+
+    #[test]
+    fn builds_blocks_for_every_fork() {
+        let spec = spec();
+        for (i, fork_name) in ForkName::list_all().into_iter().enumerate() {
+            let bytes = random_bytes(i as u8);
+            let block = BeaconBlockBuilder::<E>::new(&spec)
+                .fork_name(fork_name)
+                .proposer_index(7)
+                .build(&mut Unstructured::new(&bytes))
+                .unwrap();
+
+            assert_eq!(block.fork_name(&spec), Ok(fork_name));
+            assert_eq!(block.message().proposer_index(), 7);
+
+            let decoded =
+                SignedBeaconBlock::<E>::from_ssz_bytes(&block.as_ssz_bytes(), &spec).unwrap();
+            assert_eq!(decoded, block);
+        }
+    }
+
+    #[test]
+    fn slot_determines_fork() {
+        let spec = spec();
+        let bytes = random_bytes(42);
+        let slot = Slot::new(4 * E::slots_per_epoch() + 1);
+
+        let block = BeaconBlockBuilder::<E>::new(&spec)
+            .slot(slot)
+            .build(&mut Unstructured::new(&bytes))
+            .unwrap();
+        assert_eq!(block.slot(), slot);
+        assert_eq!(block.fork_name(&spec), Ok(ForkName::Deneb));
+
+        assert!(BeaconBlockBuilder::<E>::new(&spec)
+            .slot(slot)
+            .fork_name(ForkName::Electra)
+            .build(&mut Unstructured::new(&bytes))
+            .is_err());
+    }
+
+    #[test]
+    fn unreachable_forks_are_rejected() {
+        // Every fork up to Electra is active from genesis.
+        let spec = ForkName::Electra.make_genesis_spec(E::default_spec());
+        let bytes = random_bytes(1);
+
+        assert!(BeaconBlockBuilder::<E>::new(&spec)
+            .fork_name(ForkName::Deneb)
+            .build(&mut Unstructured::new(&bytes))
+            .is_err());
+
+        let block = BeaconBlockBuilder::<E>::new(&spec)
+            .build(&mut Unstructured::new(&bytes))
+            .unwrap();
+        assert_eq!(block.fork_name(&spec), Ok(ForkName::Electra));
+    }
+}
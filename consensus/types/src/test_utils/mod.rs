@@ -5,15 +5,18 @@ use std::fmt::Debug;
 pub use rand::{RngCore, SeedableRng};
 pub use rand_xorshift::XorShiftRng;
 
+pub use beacon_block_builder::BeaconBlockBuilder;
 pub use generate_deterministic_keypairs::generate_deterministic_keypair;
 pub use generate_deterministic_keypairs::generate_deterministic_keypairs;
 pub use generate_deterministic_keypairs::load_keypairs_from_yaml;
 use ssz::{ssz_encode, Decode, Encode};
+pub(crate) use test_random::random_block_with_blobs;
 pub use test_random::{test_random_instance, TestRandom};
 use tree_hash::TreeHash;
 
 #[macro_use]
 mod macros;
+mod beacon_block_builder;
 mod generate_deterministic_keypairs;
 #[cfg(test)]
 mod generate_random_block_and_blobs;
@@ -1,4 +1,5 @@
 use crate::*;
+use rand::seq::SliceRandom;
 use rand::RngCore;
 use rand::SeedableRng;
 use rand_xorshift::XorShiftRng;
@@ -28,6 +29,26 @@ pub trait TestRandom {
     fn random_for_test(rng: &mut impl RngCore) -> Self;
 }
 
+/// Returns a random block of a random fork from Deneb onwards, with at least one blob KZG
+/// commitment.
+///
+/// Used to generate sidecars which are consistent with the header and commitments of a block.
+pub(crate) fn random_block_with_blobs<E: EthSpec>(rng: &mut impl RngCore) -> SignedBeaconBlock<E> {
+    let forks = ForkName::list_all()
+        .into_iter()
+        .filter(|fork| fork.deneb_enabled())
+        .collect::<Vec<_>>();
+    let fork_name = forks.choose(rng).copied().unwrap_or_else(ForkName::latest);
+    let mut block: BeaconBlock<E> =
+        map_fork_name!(fork_name, BeaconBlock, <_>::random_for_test(rng));
+    if let Ok(commitments) = block.body_mut().blob_kzg_commitments_mut() {
+        if commitments.is_empty() {
+            *commitments = vec![KzgCommitment::random_for_test(rng)].into();
+        }
+    }
+    SignedBeaconBlock::from_block(block, Signature::random_for_test(rng))
+}
+
 impl<T> TestRandom for PhantomData<T> {
     fn random_for_test(_rng: &mut impl RngCore) -> Self {
         PhantomData
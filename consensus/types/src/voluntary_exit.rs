@@ -1,6 +1,6 @@
 use crate::{
-    test_utils::TestRandom, ChainSpec, Domain, Epoch, ForkName, Hash256, SecretKey, SignedRoot,
-    SignedVoluntaryExit,
+    test_utils::TestRandom, ChainSpec, Domain, Epoch, Fork, ForkName, Hash256, SecretKey,
+    SignedRoot, SignedVoluntaryExit,
 };
 
 use serde::{Deserialize, Serialize};
@@ -34,29 +34,61 @@ pub struct VoluntaryExit {
 impl SignedRoot for VoluntaryExit {}
 
 impl VoluntaryExit {
+    /// Sign the exit using the fork at which `self.epoch` falls under the schedule in `spec`.
     pub fn sign(
         self,
         secret_key: &SecretKey,
         genesis_validators_root: Hash256,
         spec: &ChainSpec,
     ) -> SignedVoluntaryExit {
-        let fork_name = spec.fork_name_at_epoch(self.epoch);
-        let fork_version = match fork_name {
-            ForkName::Base | ForkName::Altair | ForkName::Bellatrix | ForkName::Capella => {
-                spec.fork_version_for_name(fork_name)
-            }
-            // EIP-7044
-            ForkName::Deneb | ForkName::Electra => spec.fork_version_for_name(ForkName::Capella),
-        };
-        let domain =
-            spec.compute_domain(Domain::VoluntaryExit, fork_version, genesis_validators_root);
+        let fork = spec.fork_at_epoch(self.epoch);
+        self.sign_with_fork(secret_key, &fork, genesis_validators_root, spec)
+    }
 
+    /// Sign the exit on a chain which is currently at `fork`.
+    pub fn sign_with_fork(
+        self,
+        secret_key: &SecretKey,
+        fork: &Fork,
+        genesis_validators_root: Hash256,
+        spec: &ChainSpec,
+    ) -> SignedVoluntaryExit {
+        let domain = self.signing_domain(fork, genesis_validators_root, spec);
         let message = self.signing_root(domain);
         SignedVoluntaryExit {
             message: self,
             signature: secret_key.sign(message),
         }
     }
+
+    /// Returns the domain with which the exit is signed on a chain which is currently at `fork`.
+    pub fn signing_domain(
+        &self,
+        fork: &Fork,
+        genesis_validators_root: Hash256,
+        spec: &ChainSpec,
+    ) -> Hash256 {
+        spec.get_domain(
+            self.epoch,
+            Domain::VoluntaryExit,
+            &Self::signing_fork(fork, spec),
+            genesis_validators_root,
+        )
+    }
+
+    /// Returns the fork with which exits are signed on a chain which is currently at `fork`.
+    ///
+    /// From Deneb onwards exits are always signed with the Capella fork version (EIP-7044).
+    pub fn signing_fork(fork: &Fork, spec: &ChainSpec) -> Fork {
+        match spec.fork_name_at_epoch(fork.epoch) {
+            ForkName::Base | ForkName::Altair | ForkName::Bellatrix | ForkName::Capella => *fork,
+            ForkName::Deneb | ForkName::Electra => Fork {
+                previous_version: spec.capella_fork_version,
+                current_version: spec.capella_fork_version,
+                epoch: fork.epoch,
+            },
+        }
+    }
 }
 
 #[cfg(test)]
@@ -64,4 +96,37 @@ mod tests {
     use super::*;
 
     ssz_and_tree_hash_tests!(VoluntaryExit);
+
+    #[test]
+    fn signing_fork_uses_capella_from_deneb() {
+        let spec = ForkName::Deneb.make_genesis_spec(ChainSpec::mainnet());
+        let capella_fork = Fork {
+            previous_version: spec.bellatrix_fork_version,
+            current_version: spec.capella_fork_version,
+            epoch: Epoch::new(0),
+        };
+        let deneb_fork = spec.fork_at_epoch(Epoch::new(0));
+        assert_eq!(deneb_fork.current_version, spec.deneb_fork_version);
+
+        let exit = VoluntaryExit {
+            epoch: Epoch::new(1),
+            validator_index: 0,
+        };
+        let genesis_validators_root = Hash256::repeat_byte(1);
+        assert_eq!(
+            exit.signing_domain(&deneb_fork, genesis_validators_root, &spec),
+            spec.compute_domain(
+                Domain::VoluntaryExit,
+                spec.capella_fork_version,
+                genesis_validators_root
+            )
+        );
+
+        // Before Deneb the fork is used as-is.
+        let spec = ForkName::Capella.make_genesis_spec(ChainSpec::mainnet());
+        assert_eq!(
+            VoluntaryExit::signing_fork(&capella_fork, &spec),
+            capella_fork
+        );
+    }
 }
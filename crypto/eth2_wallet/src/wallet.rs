@@ -186,25 +186,13 @@ impl Wallet {
         voting_keystore_password: &[u8],
         withdrawal_keystore_password: &[u8],
     ) -> Result<ValidatorKeystores, Error> {
-        // Helper closure to reduce code duplication when generating keys.
-        //
-        // It is not a function on `self` to help protect against generating keys without
-        // incrementing `nextaccount`.
-        let derive = |key_type: KeyType, password: &[u8]| -> Result<Keystore, Error> {
-            let (secret, path) =
-                recover_validator_secret(self, wallet_password, self.json.nextaccount, key_type)?;
-
-            let keypair = keypair_from_secret(secret.as_bytes())?;
-
-            KeystoreBuilder::new(&keypair, password, format!("{}", path))?
-                .build()
-                .map_err(Into::into)
-        };
-
-        let keystores = ValidatorKeystores {
-            voting: derive(KeyType::Voting, voting_keystore_password)?,
-            withdrawal: derive(KeyType::Withdrawal, withdrawal_keystore_password)?,
-        };
+        let seed = self.decrypt_seed(wallet_password)?;
+        let keystores = validator_keystores_from_seed(
+            seed.as_bytes(),
+            self.json.nextaccount,
+            voting_keystore_password,
+            withdrawal_keystore_password,
+        )?;
 
         self.json.nextaccount = self
             .json
@@ -313,6 +301,34 @@ pub fn recover_validator_secret(
     Ok((destination.secret().to_vec().into(), path))
 }
 
+/// Produces the voting and withdrawal `Keystore`s (encrypted with the given passwords) for the
+/// validator at `index`, deriving the keys from the decrypted `seed` of a wallet.
+///
+/// This function does not update `wallet.nextaccount`, so the caller must ensure that keys are
+/// not generated for the same `index` twice. Since the seed only needs to be decrypted once, it
+/// allows the keys of many validators to be generated in parallel.
+pub fn validator_keystores_from_seed(
+    seed: &[u8],
+    index: u32,
+    voting_keystore_password: &[u8],
+    withdrawal_keystore_password: &[u8],
+) -> Result<ValidatorKeystores, Error> {
+    let derive = |key_type: KeyType, password: &[u8]| -> Result<Keystore, Error> {
+        let (secret, path) = recover_validator_secret_from_mnemonic(seed, index, key_type)?;
+
+        let keypair = keypair_from_secret(secret.as_bytes())?;
+
+        KeystoreBuilder::new(&keypair, password, format!("{}", path))?
+            .build()
+            .map_err(Into::into)
+    };
+
+    Ok(ValidatorKeystores {
+        voting: derive(KeyType::Voting, voting_keystore_password)?,
+        withdrawal: derive(KeyType::Withdrawal, withdrawal_keystore_password)?,
+    })
+}
+
 /// Returns `(secret, path)` for the `key_type` for the validator at `index`.
 ///
 /// This function should only be used for key recovery since it can easily lead to key duplication.
@@ -0,0 +1,563 @@
+//! # API Self-Test
+//!
+//! Use this tool to check that the HTTP API of a beacon node conforms to the beacon-API OpenAPI
+//! spec. A suite of requests which don't modify the node is made, and for each response:
+//!
+//! - The status code and `Content-Type` must be documented for the route.
+//! - The headers which are documented as required must be present.
+//! - A JSON body must match the documented schema.
+//!
+//! The spec is the bundled `beacon-node-oapi.json` or `beacon-node-oapi.yaml` published with each
+//! release of <https://github.com/ethereum/beacon-APIs>. Only references within the spec file are
+//! supported.
+//!
+//! The schemas of different forks overlap, since they don't forbid additional fields, so `oneOf`
+//! schemas are checked as though they were `anyOf`.
+//!
+//! ## Examples
+//!
+//! Check a local beacon node against the spec of a beacon-API release.
+//!
+//! ```ignore
+//! lcli api-selftest \
+//!     --beacon-url http://localhost:5052 \
+//!     --openapi beacon-node-oapi.json
+//! ```
+use clap::ArgMatches;
+use clap_utils::parse_required;
+use environment::Environment;
+use eth2::reqwest::{
+    header::{HeaderMap, CONTENT_TYPE},
+    Client,
+};
+use eth2::{types::BlockId, BeaconNodeHttpClient, SensitiveUrl, Timeouts};
+use log::{info, warn};
+use regex::Regex;
+use serde_json::Value;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use types::EthSpec;
+
+const HTTP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The number of deviations reported for each response, so that a deviation in every element of a
+/// list isn't reported thousands of times.
+const MAX_REPORTED_DEVIATIONS: usize = 10;
+
+/// The requests made, with `{epoch}` replaced by the epoch of the head.
+///
+/// Requests whose responses are very large on mainnet, such as all validators, are avoided.
+const REQUESTS: &[&str] = &[
+    "/eth/v1/beacon/genesis",
+    "/eth/v1/beacon/states/head/root",
+    "/eth/v1/beacon/states/head/fork",
+    "/eth/v1/beacon/states/head/finality_checkpoints",
+    "/eth/v1/beacon/states/head/validators/0",
+    "/eth/v1/beacon/states/head/validator_balances?id=0",
+    "/eth/v1/beacon/states/head/committees?index=0",
+    "/eth/v1/beacon/states/head/sync_committees",
+    "/eth/v1/beacon/states/head/randao",
+    "/eth/v1/beacon/headers",
+    "/eth/v1/beacon/headers/head",
+    "/eth/v2/beacon/blocks/head",
+    "/eth/v1/beacon/blocks/head/root",
+    "/eth/v1/beacon/blocks/head/attestations",
+    "/eth/v1/beacon/blinded_blocks/head",
+    "/eth/v1/beacon/blob_sidecars/head",
+    "/eth/v1/beacon/rewards/blocks/head",
+    "/eth/v1/beacon/pool/attester_slashings",
+    "/eth/v1/beacon/pool/proposer_slashings",
+    "/eth/v1/beacon/pool/voluntary_exits",
+    "/eth/v1/beacon/pool/bls_to_execution_changes",
+    "/eth/v1/config/fork_schedule",
+    "/eth/v1/config/spec",
+    "/eth/v1/config/deposit_contract",
+    "/eth/v2/debug/beacon/heads",
+    "/eth/v1/node/identity",
+    "/eth/v1/node/peers",
+    "/eth/v1/node/peer_count",
+    "/eth/v1/node/version",
+    "/eth/v1/node/syncing",
+    "/eth/v1/node/health",
+    "/eth/v1/validator/duties/proposer/{epoch}",
+];
+
+pub fn run<E: EthSpec>(env: Environment<E>, matches: &ArgMatches) -> Result<(), String> {
+    let executor = env.core_context().executor;
+
+    let beacon_url: SensitiveUrl = parse_required(matches, "beacon-url")?;
+    let openapi_path: PathBuf = parse_required(matches, "openapi")?;
+
+    let api_spec = ApiSpec::from_file(&openapi_path)?;
+    let client = BeaconNodeHttpClient::new(beacon_url.clone(), Timeouts::set_all(HTTP_TIMEOUT));
+    // The checked requests use a plain client, which returns responses of any status rather than
+    // converting error statuses into errors, so that every documented status is validated.
+    let http_client = Client::builder()
+        .timeout(HTTP_TIMEOUT)
+        .build()
+        .map_err(|e| format!("Unable to build HTTP client: {}", e))?;
+
+    let deviations = executor
+        .handle()
+        .ok_or("shutdown in progress")?
+        .block_on(async move {
+            let head_slot = client
+                .get_beacon_headers_block_id(BlockId::Head)
+                .await
+                .map_err(|e| format!("Failed to download head header: {:?}", e))?
+                .ok_or("head header not found")?
+                .data
+                .header
+                .message
+                .slot;
+            let epoch = head_slot.epoch(E::slots_per_epoch()).to_string();
+
+            let mut deviations = 0;
+            for request in REQUESTS {
+                let request = request.replace("{epoch}", &epoch);
+                let errors = check_request(&http_client, &beacon_url, &api_spec, &request).await;
+                if errors.is_empty() {
+                    info!("{}: conforms to the spec", request);
+                }
+                for error in errors.iter().take(MAX_REPORTED_DEVIATIONS) {
+                    warn!("{}: {}", request, error);
+                }
+                if errors.len() > MAX_REPORTED_DEVIATIONS {
+                    warn!(
+                        "{}: {} more deviations",
+                        request,
+                        errors.len() - MAX_REPORTED_DEVIATIONS
+                    );
+                }
+                deviations += errors.len();
+            }
+            Ok::<_, String>(deviations)
+        })?;
+
+    if deviations == 0 {
+        info!("All {} responses conform to the spec", REQUESTS.len());
+        Ok(())
+    } else {
+        Err(format!("found {} deviations from the spec", deviations))
+    }
+}
+
+/// Makes a GET `request` and returns the ways in which its response deviates from the spec.
+async fn check_request(
+    client: &Client,
+    beacon_url: &SensitiveUrl,
+    api_spec: &ApiSpec,
+    request: &str,
+) -> Vec<String> {
+    let (path, query) = request.split_once('?').unwrap_or((request, ""));
+    let Some(operation) = api_spec.operation(path, "get") else {
+        return vec!["route isn't in the spec".to_string()];
+    };
+
+    let mut url = beacon_url.full.clone();
+    match url.path_segments_mut() {
+        Ok(mut segments) => {
+            segments
+                .pop_if_empty()
+                .extend(path.trim_start_matches('/').split('/'));
+        }
+        Err(()) => return vec!["beacon URL can't have a path".to_string()],
+    }
+    if !query.is_empty() {
+        url.set_query(Some(query));
+    }
+
+    let response = match client.get(url).send().await {
+        Ok(response) => response,
+        Err(e) => return vec![format!("request failed: {}", e)],
+    };
+    let status = response.status();
+    let headers = response.headers().clone();
+    let body = match response.bytes().await {
+        Ok(body) => body,
+        Err(e) => return vec![format!("failed to read body: {}", e)],
+    };
+
+    let responses = operation.get("responses").and_then(Value::as_object);
+    let Some(documented) = responses.and_then(|responses| responses.get(status.as_str())) else {
+        return vec![format!("status {} isn't documented", status)];
+    };
+    let documented = match api_spec.resolve(documented) {
+        Ok(documented) => documented,
+        Err(e) => return vec![e],
+    };
+
+    let mut errors = vec![];
+    check_headers(api_spec, documented, &headers, &mut errors);
+
+    let content_type = headers
+        .get(CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .and_then(|content_type| content_type.split(';').next())
+        .map(str::trim);
+    let Some(content) = documented.get("content").and_then(Value::as_object) else {
+        if !body.is_empty() {
+            errors.push(format!("status {} is documented without a body", status));
+        }
+        return errors;
+    };
+    let Some(content_type) = content_type else {
+        errors.push("missing Content-Type header".to_string());
+        return errors;
+    };
+    let Some(media_type) = content.get(content_type) else {
+        errors.push(format!("Content-Type {} isn't documented", content_type));
+        return errors;
+    };
+
+    if content_type == "application/json" {
+        match serde_json::from_slice::<Value>(&body) {
+            Ok(value) => {
+                if let Some(schema) = media_type.get("schema") {
+                    api_spec.validate(schema, &value, "body", &mut errors);
+                }
+            }
+            Err(e) => errors.push(format!("invalid JSON: {}", e)),
+        }
+    }
+    errors
+}
+
+/// Checks that the headers documented as required for a response are present.
+fn check_headers(
+    api_spec: &ApiSpec,
+    documented: &Value,
+    headers: &HeaderMap,
+    errors: &mut Vec<String>,
+) {
+    let Some(documented_headers) = documented.get("headers").and_then(Value::as_object) else {
+        return;
+    };
+    for (name, header) in documented_headers {
+        match api_spec.resolve(header) {
+            Ok(header) => {
+                let required = header.get("required").and_then(Value::as_bool) == Some(true);
+                if required && !headers.contains_key(name.as_str()) {
+                    errors.push(format!("missing required header {}", name));
+                }
+            }
+            Err(e) => errors.push(e),
+        }
+    }
+}
+
+/// A beacon-API OpenAPI spec.
+struct ApiSpec {
+    root: Value,
+    regexes: RefCell<HashMap<String, Result<Regex, String>>>,
+}
+
+impl ApiSpec {
+    fn from_file(path: &Path) -> Result<Self, String> {
+        let file =
+            File::open(path).map_err(|e| format!("Unable to open {}: {}", path.display(), e))?;
+        let root = if path
+            .extension()
+            .map_or(false, |extension| extension == "json")
+        {
+            serde_json::from_reader(file).map_err(|e| format!("Invalid JSON spec: {}", e))?
+        } else {
+            serde_yaml::from_reader(file).map_err(|e| format!("Invalid YAML spec: {}", e))?
+        };
+        Ok(Self::new(root))
+    }
+
+    fn new(root: Value) -> Self {
+        Self {
+            root,
+            regexes: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the operation of `method` for the route matching `path`, such as
+    /// `/eth/v1/beacon/states/{state_id}/root` for `/eth/v1/beacon/states/head/root`.
+    fn operation(&self, path: &str, method: &str) -> Option<&Value> {
+        let segments = path.split('/').collect::<Vec<_>>();
+        // Prefer routes with fewer parameters, so that literal segments take precedence.
+        let (_, route) = self
+            .root
+            .get("paths")?
+            .as_object()?
+            .iter()
+            .filter(|(route, _)| {
+                let route_segments = route.split('/').collect::<Vec<_>>();
+                route_segments.len() == segments.len()
+                    && route_segments
+                        .iter()
+                        .zip(&segments)
+                        .all(|(route, segment)| {
+                            route == segment || (route.starts_with('{') && !segment.is_empty())
+                        })
+            })
+            .min_by_key(|(route, _)| route.matches('{').count())?;
+        self.resolve(route).ok()?.get(method)
+    }
+
+    /// Follows the `$ref` of `value` to the value it refers to, if any.
+    fn resolve<'a>(&'a self, mut value: &'a Value) -> Result<&'a Value, String> {
+        while let Some(reference) = value.get("$ref").and_then(Value::as_str) {
+            value = reference
+                .strip_prefix('#')
+                .and_then(|pointer| self.root.pointer(pointer))
+                .ok_or_else(|| format!("unable to resolve reference {}", reference))?;
+        }
+        Ok(value)
+    }
+
+    /// Checks `value` against `schema`, describing each mismatch with its `location` in `errors`.
+    fn validate(&self, schema: &Value, value: &Value, location: &str, errors: &mut Vec<String>) {
+        let schema = match self.resolve(schema) {
+            Ok(schema) => schema,
+            Err(e) => {
+                errors.push(format!("{}: {}", location, e));
+                return;
+            }
+        };
+        if value.is_null() && schema.get("nullable").and_then(Value::as_bool) == Some(true) {
+            return;
+        }
+
+        if let Some(schemas) = schema.get("allOf").and_then(Value::as_array) {
+            for schema in schemas {
+                self.validate(schema, value, location, errors);
+            }
+        }
+        for keyword in ["oneOf", "anyOf"] {
+            if let Some(schemas) = schema.get(keyword).and_then(Value::as_array) {
+                let matches_any = schemas.iter().any(|schema| {
+                    let mut schema_errors = vec![];
+                    self.validate(schema, value, location, &mut schema_errors);
+                    schema_errors.is_empty()
+                });
+                if !matches_any {
+                    errors.push(format!(
+                        "{}: doesn't match any {} schema",
+                        location, keyword
+                    ));
+                }
+            }
+        }
+
+        if let Some(expected) = schema.get("type").and_then(Value::as_str) {
+            let matches = match expected {
+                "object" => value.is_object(),
+                "array" => value.is_array(),
+                "string" => value.is_string(),
+                "boolean" => value.is_boolean(),
+                "integer" => value.is_i64() || value.is_u64(),
+                "number" => value.is_number(),
+                "null" => value.is_null(),
+                _ => true,
+            };
+            if !matches {
+                errors.push(format!(
+                    "{}: expected {}, found {}",
+                    location,
+                    expected,
+                    json_type(value)
+                ));
+                return;
+            }
+        }
+        if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+            if !allowed.contains(value) {
+                errors.push(format!(
+                    "{}: {} isn't one of {:?}",
+                    location, value, allowed
+                ));
+            }
+        }
+
+        match value {
+            Value::Object(object) => {
+                let required = schema.get("required").and_then(Value::as_array);
+                for name in required.into_iter().flatten().filter_map(Value::as_str) {
+                    if !object.contains_key(name) {
+                        errors.push(format!("{}: missing required field {}", location, name));
+                    }
+                }
+                let properties = schema.get("properties").and_then(Value::as_object);
+                for (name, property) in properties.into_iter().flatten() {
+                    if let Some(field) = object.get(name) {
+                        let location = format!("{}.{}", location, name);
+                        self.validate(property, field, &location, errors);
+                    }
+                }
+            }
+            Value::Array(items) => {
+                let min_items = schema.get("minItems").and_then(Value::as_u64);
+                let max_items = schema.get("maxItems").and_then(Value::as_u64);
+                let len = items.len() as u64;
+                if min_items.map_or(false, |min| len < min)
+                    || max_items.map_or(false, |max| len > max)
+                {
+                    errors.push(format!("{}: {} items is out of bounds", location, len));
+                }
+                if let Some(item_schema) = schema.get("items") {
+                    for (i, item) in items.iter().enumerate() {
+                        let location = format!("{}[{}]", location, i);
+                        self.validate(item_schema, item, &location, errors);
+                    }
+                }
+            }
+            Value::String(string) => {
+                if let Some(pattern) = schema.get("pattern").and_then(Value::as_str) {
+                    match self.matches_pattern(pattern, string) {
+                        Ok(true) => {}
+                        Ok(false) => errors.push(format!(
+                            "{}: {:?} doesn't match {}",
+                            location, string, pattern
+                        )),
+                        Err(e) => errors.push(format!("{}: {}", location, e)),
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn matches_pattern(&self, pattern: &str, string: &str) -> Result<bool, String> {
+        let mut regexes = self.regexes.borrow_mut();
+        let regex = regexes.entry(pattern.to_string()).or_insert_with(|| {
+            Regex::new(pattern).map_err(|e| format!("unsupported pattern {}: {}", pattern, e))
+        });
+        regex
+            .as_ref()
+            .map(|regex| regex.is_match(string))
+            .map_err(Clone::clone)
+    }
+}
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn spec() -> ApiSpec {
+        ApiSpec::new(json!({
+            "components": {
+                "schemas": {
+                    "Root": {
+                        "type": "string",
+                        "pattern": "^0x[a-fA-F0-9]{64}$"
+                    },
+                    "Checkpoint": {
+                        "type": "object",
+                        "required": ["epoch", "root"],
+                        "properties": {
+                            "epoch": { "type": "string", "pattern": "^[0-9]+$" },
+                            "root": { "$ref": "#/components/schemas/Root" }
+                        }
+                    },
+                    "EpochOrRoot": {
+                        "oneOf": [
+                            { "type": "string", "pattern": "^[0-9]+$" },
+                            { "$ref": "#/components/schemas/Root" }
+                        ]
+                    }
+                }
+            }
+        }))
+    }
+
+    fn errors(schema: &str, value: Value) -> Vec<String> {
+        let spec = spec();
+        let mut errors = vec![];
+        spec.validate(
+            &json!({ "$ref": format!("#/components/schemas/{schema}") }),
+            &value,
+            "body",
+            &mut errors,
+        );
+        errors
+    }
+
+    fn root() -> String {
+        format!("0x{}", "ab".repeat(32))
+    }
+
+    #[test]
+    fn references_are_followed() {
+        assert!(errors("Root", json!(root())).is_empty());
+        assert_eq!(
+            errors("Checkpoint", json!({ "epoch": "1", "root": 1 })),
+            vec!["body.root: expected string, found number"]
+        );
+
+        let mut errors = vec![];
+        spec().validate(
+            &json!({ "$ref": "#/components/schemas/Missing" }),
+            &json!(1),
+            "body",
+            &mut errors,
+        );
+        assert_eq!(
+            errors,
+            vec!["body: unable to resolve reference #/components/schemas/Missing"]
+        );
+    }
+
+    #[test]
+    fn one_of_matches_any_schema() {
+        assert!(errors("EpochOrRoot", json!("12")).is_empty());
+        assert!(errors("EpochOrRoot", json!(root())).is_empty());
+        assert_eq!(
+            errors("EpochOrRoot", json!("head")),
+            vec!["body: doesn't match any oneOf schema"]
+        );
+    }
+
+    #[test]
+    fn required_fields_must_be_present() {
+        assert!(errors("Checkpoint", json!({ "epoch": "1", "root": root() })).is_empty());
+        // Additional fields are allowed.
+        assert!(errors(
+            "Checkpoint",
+            json!({ "epoch": "1", "root": root(), "x": 0 })
+        )
+        .is_empty());
+        assert_eq!(
+            errors("Checkpoint", json!({ "epoch": "1" })),
+            vec!["body: missing required field root"]
+        );
+    }
+
+    #[test]
+    fn strings_must_match_pattern() {
+        assert_eq!(
+            errors("Checkpoint", json!({ "epoch": "-1", "root": "0x00" })),
+            vec![
+                r#"body.epoch: "-1" doesn't match ^[0-9]+$"#.to_string(),
+                r#"body.root: "0x00" doesn't match ^0x[a-fA-F0-9]{64}$"#.to_string(),
+            ]
+        );
+
+        let spec = ApiSpec::new(json!({}));
+        let mut errors = vec![];
+        spec.validate(&json!({ "pattern": "(" }), &json!("x"), "body", &mut errors);
+        assert_eq!(errors.len(), 1);
+        assert!(
+            errors[0].starts_with("body: unsupported pattern ("),
+            "{errors:?}"
+        );
+    }
+}
@@ -0,0 +1,104 @@
+//! # Check Spec
+//!
+//! Use this tool to check that the spec of a network is internally consistent, and optionally to
+//! list the fields in which it differs from another config. This is useful for debugging peers
+//! which fail to agree on the fork digest or reject each other's messages.
+//!
+//! The other config can be loaded from a `config.yaml` file, or downloaded from the
+//! `/eth/v1/config/spec` endpoint of a beacon node.
+//!
+//! ## Examples
+//!
+//! Compare the config of a local testnet against that of a running beacon node.
+//!
+//! ```ignore
+//! lcli check-spec \
+//!     --testnet-dir ~/.lighthouse/local-testnet/testnet \
+//!     --beacon-url http://localhost:5052
+//! ```
+use clap::ArgMatches;
+use clap_utils::parse_optional;
+use environment::Environment;
+use eth2::{BeaconNodeHttpClient, SensitiveUrl, Timeouts};
+use eth2_network_config::Eth2NetworkConfig;
+use log::{info, warn};
+use std::path::PathBuf;
+use std::time::Duration;
+use types::{ChainSpec, Config, EthSpec};
+
+const HTTP_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub fn run<E: EthSpec>(
+    env: Environment<E>,
+    network_config: Eth2NetworkConfig,
+    matches: &ArgMatches,
+) -> Result<(), String> {
+    let spec = network_config.chain_spec::<E>()?;
+    let executor = env.core_context().executor;
+
+    let config_path: Option<PathBuf> = parse_optional(matches, "config")?;
+    let beacon_url: Option<SensitiveUrl> = parse_optional(matches, "beacon-url")?;
+
+    info!("Using {} spec", E::spec_name());
+
+    let mut valid = check_valid::<E>("Network", &spec);
+
+    let other_config = match (config_path, beacon_url) {
+        (Some(config_path), None) => Some(Config::from_file(&config_path)?),
+        (None, Some(beacon_url)) => {
+            let client = BeaconNodeHttpClient::new(beacon_url, Timeouts::set_all(HTTP_TIMEOUT));
+            let config = executor
+                .handle()
+                .ok_or("shutdown in progress")?
+                .block_on(async move {
+                    client
+                        .get_config_spec::<Config>()
+                        .await
+                        .map(|response| response.data)
+                        .map_err(|e| format!("Failed to download config: {:?}", e))
+                })?;
+            Some(config)
+        }
+        (None, None) => None,
+        _ => return Err("must supply at most one of --config or --beacon-url".into()),
+    };
+
+    if let Some(other_config) = other_config {
+        let other_spec = other_config
+            .apply_to_chain_spec::<E>(&spec)
+            .ok_or_else(|| format!("Other config is not for the {} preset", E::spec_name()))?;
+        valid &= check_valid::<E>("Other", &other_spec);
+
+        let differences = spec.diff(&other_spec);
+        if differences.is_empty() {
+            info!("Specs are identical");
+        }
+        for difference in differences {
+            warn!(
+                "{}: network has {}, other has {}",
+                difference.field_name, difference.ours, difference.theirs
+            );
+        }
+    }
+
+    if valid {
+        Ok(())
+    } else {
+        Err("spec is invalid".into())
+    }
+}
+
+fn check_valid<E: EthSpec>(name: &str, spec: &ChainSpec) -> bool {
+    match spec.validate::<E>() {
+        Ok(()) => {
+            info!("{} spec is valid", name);
+            true
+        }
+        Err(errors) => {
+            for error in errors {
+                warn!("{} spec is invalid: {}", name, error);
+            }
+            false
+        }
+    }
+}
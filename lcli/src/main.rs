@@ -1,5 +1,7 @@
+mod api_selftest;
 mod block_root;
 mod check_deposit_data;
+mod check_spec;
 mod generate_bootnode_enr;
 mod indexed_attestations;
 mod mnemonic_validators;
@@ -482,6 +484,51 @@ fn main() {
                         .display_order(0)
                 )
         )
+        .subcommand(
+            Command::new("check-spec")
+                .about("Validates the spec of the network, optionally listing differences with \
+                        another config.")
+                .arg(
+                    Arg::new("config")
+                        .long("config")
+                        .value_name("PATH")
+                        .action(ArgAction::Set)
+                        .conflicts_with("beacon-url")
+                        .help("Path to a config.yaml to compare against.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("beacon-url")
+                        .long("beacon-url")
+                        .value_name("URL")
+                        .action(ArgAction::Set)
+                        .help("URL to a beacon-API provider whose config is compared against.")
+                        .display_order(0)
+                )
+        )
+        .subcommand(
+            Command::new("api-selftest")
+                .about("Checks the responses of a beacon node's HTTP API against the beacon-API \
+                        OpenAPI spec, reporting any deviations.")
+                .arg(
+                    Arg::new("beacon-url")
+                        .long("beacon-url")
+                        .value_name("URL")
+                        .action(ArgAction::Set)
+                        .default_value("http://localhost:5052")
+                        .help("URL of the beacon node to check.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("openapi")
+                        .long("openapi")
+                        .value_name("PATH")
+                        .action(ArgAction::Set)
+                        .required(true)
+                        .help("Path to the bundled beacon-API OpenAPI spec, as JSON or YAML.")
+                        .display_order(0)
+                )
+        )
         .subcommand(
             Command::new("mock-el")
                 .about("Creates a mock execution layer server. This is NOT SAFE and should only \
@@ -551,6 +598,45 @@ fn main() {
                                 until Prague is triggered on mainnet.")
                         .display_order(0)
                 )
+                .arg(
+                    Arg::new("response-delay-ms")
+                        .long("response-delay-ms")
+                        .value_name("MILLISECONDS")
+                        .action(ArgAction::Set)
+                        .help("Delay every response by this many milliseconds.")
+                        .default_value("0")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("method-delay-ms")
+                        .long("method-delay-ms")
+                        .value_name("METHOD=MILLISECONDS")
+                        .action(ArgAction::Append)
+                        .help("Delay responses to a single JSON-RPC method by this many \
+                            milliseconds, overriding --response-delay-ms. May be supplied \
+                            multiple times, e.g. --method-delay-ms engine_newPayloadV3=500.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("error-probability")
+                        .long("error-probability")
+                        .value_name("PROBABILITY")
+                        .action(ArgAction::Set)
+                        .help("The probability, between 0 and 1, that any request will fail with \
+                            a JSON-RPC error.")
+                        .default_value("0")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("invalid-status-probability")
+                        .long("invalid-status-probability")
+                        .value_name("PROBABILITY")
+                        .action(ArgAction::Set)
+                        .help("The probability, between 0 and 1, that a payload passed to \
+                            engine_newPayload will be reported as INVALID.")
+                        .default_value("0")
+                        .display_order(0)
+                )
         )
         .get_matches();
 
@@ -654,6 +740,13 @@ fn run<E: EthSpec>(env_builder: EnvironmentBuilder<E>, matches: &ArgMatches) ->
             state_root::run::<E>(env, network_config, matches)
                 .map_err(|e| format!("Failed to run state-root command: {}", e))
         }
+        Some(("check-spec", matches)) => {
+            let network_config = get_network_config()?;
+            check_spec::run::<E>(env, network_config, matches)
+                .map_err(|e| format!("Failed to run check-spec command: {}", e))
+        }
+        Some(("api-selftest", matches)) => api_selftest::run::<E>(env, matches)
+            .map_err(|e| format!("Failed to run api-selftest command: {}", e)),
         Some(("mock-el", matches)) => mock_el::run::<E>(env, matches)
             .map_err(|e| format!("Failed to run mock-el command: {}", e)),
         Some((other, _)) => Err(format!("Unknown subcommand {}. See --help.", other)),
@@ -4,11 +4,14 @@ use environment::Environment;
 use execution_layer::{
     auth::JwtKey,
     test_utils::{
-        Config, MockExecutionConfig, MockServer, DEFAULT_JWT_SECRET, DEFAULT_TERMINAL_BLOCK,
+        Config, FaultInjection, MockExecutionConfig, MockServer, DEFAULT_JWT_SECRET,
+        DEFAULT_TERMINAL_BLOCK,
     },
 };
+use std::collections::HashMap;
 use std::net::Ipv4Addr;
 use std::path::PathBuf;
+use std::time::Duration;
 use types::*;
 
 pub fn run<E: EthSpec>(mut env: Environment<E>, matches: &ArgMatches) -> Result<(), String> {
@@ -19,6 +22,22 @@ pub fn run<E: EthSpec>(mut env: Environment<E>, matches: &ArgMatches) -> Result<
     let shanghai_time = parse_required(matches, "shanghai-time")?;
     let cancun_time = parse_optional(matches, "cancun-time")?;
     let prague_time = parse_optional(matches, "prague-time")?;
+    let response_delay_ms: u64 = parse_required(matches, "response-delay-ms")?;
+    let error_probability = parse_probability(matches, "error-probability")?;
+    let invalid_status_probability = parse_probability(matches, "invalid-status-probability")?;
+    let method_delays = matches
+        .get_many::<String>("method-delay-ms")
+        .unwrap_or_default()
+        .map(|value| {
+            let (method, delay_ms) = value
+                .split_once('=')
+                .ok_or_else(|| format!("Expected METHOD=MILLISECONDS, got {}", value))?;
+            let delay_ms = delay_ms
+                .parse::<u64>()
+                .map_err(|e| format!("Invalid delay for {}: {:?}", method, e))?;
+            Ok((method.to_string(), Duration::from_millis(delay_ms)))
+        })
+        .collect::<Result<HashMap<_, _>, String>>()?;
 
     let handle = env.core_context().executor.handle().unwrap();
     let spec = &E::default_spec();
@@ -37,6 +56,12 @@ pub fn run<E: EthSpec>(mut env: Environment<E>, matches: &ArgMatches) -> Result<
         shanghai_time: Some(shanghai_time),
         cancun_time,
         prague_time,
+        fault_injection: FaultInjection {
+            response_delay: Duration::from_millis(response_delay_ms),
+            method_delays,
+            error_probability,
+            invalid_status_probability,
+        },
     };
     let kzg = None;
     let server: MockServer<E> = MockServer::new_with_config(&handle, config, kzg);
@@ -62,3 +87,14 @@ pub fn run<E: EthSpec>(mut env: Environment<E>, matches: &ArgMatches) -> Result<
 
     Ok(())
 }
+
+fn parse_probability(matches: &ArgMatches, name: &'static str) -> Result<f64, String> {
+    let probability: f64 = parse_required(matches, name)?;
+    if !(0.0..=1.0).contains(&probability) {
+        return Err(format!(
+            "--{} must be between 0 and 1, got {}",
+            name, probability
+        ));
+    }
+    Ok(probability)
+}
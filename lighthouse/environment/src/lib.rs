@@ -12,9 +12,9 @@ use eth2_network_config::Eth2NetworkConfig;
 use futures::channel::mpsc::{channel, Receiver, Sender};
 use futures::{future, StreamExt};
 
-use logging::SSELoggingComponents;
+use logging::{LogFilterHandle, RecentLogs, RuntimeLevelFilter, SSELoggingComponents};
 use serde::{Deserialize, Serialize};
-use slog::{error, info, o, warn, Drain, Duplicate, Level, Logger};
+use slog::{error, info, o, warn, Drain, Duplicate, Logger};
 use sloggers::{file::FileLoggerBuilder, types::Format, types::Severity, Build};
 use std::fs::create_dir_all;
 use std::io::{Result as IOResult, Write};
@@ -38,6 +38,8 @@ pub use task_executor::test_utils::null_logger;
 
 const LOG_CHANNEL_SIZE: usize = 16384;
 const SSE_LOG_CHANNEL_SIZE: usize = 2048;
+/// The number of recent terminal log records kept in memory for the HTTP API.
+const RECENT_LOGS_CAPACITY: usize = 1024;
 /// The maximum time in seconds the client will wait for all internal tasks to shutdown.
 const MAXIMUM_SHUTDOWN_TIME: u64 = 15;
 
@@ -91,6 +93,8 @@ pub struct RuntimeContext<E: EthSpec> {
     pub eth2_config: Eth2Config,
     pub eth2_network_config: Option<Arc<Eth2NetworkConfig>>,
     pub sse_logging_components: Option<SSELoggingComponents>,
+    pub log_filter: Option<LogFilterHandle>,
+    pub recent_logs: Option<RecentLogs>,
 }
 
 impl<E: EthSpec> RuntimeContext<E> {
@@ -104,6 +108,8 @@ impl<E: EthSpec> RuntimeContext<E> {
             eth2_config: self.eth2_config.clone(),
             eth2_network_config: self.eth2_network_config.clone(),
             sse_logging_components: self.sse_logging_components.clone(),
+            log_filter: self.log_filter.clone(),
+            recent_logs: self.recent_logs.clone(),
         }
     }
 
@@ -123,6 +129,8 @@ pub struct EnvironmentBuilder<E: EthSpec> {
     runtime: Option<Arc<Runtime>>,
     log: Option<Logger>,
     sse_logging_components: Option<SSELoggingComponents>,
+    log_filter: Option<LogFilterHandle>,
+    recent_logs: Option<RecentLogs>,
     eth_spec_instance: E,
     eth2_config: Eth2Config,
     eth2_network_config: Option<Eth2NetworkConfig>,
@@ -135,6 +143,8 @@ impl EnvironmentBuilder<MinimalEthSpec> {
             runtime: None,
             log: None,
             sse_logging_components: None,
+            log_filter: None,
+            recent_logs: None,
             eth_spec_instance: MinimalEthSpec,
             eth2_config: Eth2Config::minimal(),
             eth2_network_config: None,
@@ -149,6 +159,8 @@ impl EnvironmentBuilder<MainnetEthSpec> {
             runtime: None,
             log: None,
             sse_logging_components: None,
+            log_filter: None,
+            recent_logs: None,
             eth_spec_instance: MainnetEthSpec,
             eth2_config: Eth2Config::mainnet(),
             eth2_network_config: None,
@@ -163,6 +175,8 @@ impl EnvironmentBuilder<GnosisEthSpec> {
             runtime: None,
             log: None,
             sse_logging_components: None,
+            log_filter: None,
+            recent_logs: None,
             eth_spec_instance: GnosisEthSpec,
             eth2_config: Eth2Config::gnosis(),
             eth2_network_config: None,
@@ -235,15 +249,17 @@ impl<E: EthSpec> EnvironmentBuilder<E> {
                 .build()
         };
 
-        let stdout_drain = match config.debug_level.as_str() {
-            "info" => stdout_drain.filter_level(Level::Info),
-            "debug" => stdout_drain.filter_level(Level::Debug),
-            "trace" => stdout_drain.filter_level(Level::Trace),
-            "warn" => stdout_drain.filter_level(Level::Warning),
-            "error" => stdout_drain.filter_level(Level::Error),
-            "crit" => stdout_drain.filter_level(Level::Critical),
-            unknown => return Err(format!("Unknown debug-level: {}", unknown)),
-        };
+        // The most recent stdout logs are also kept in memory.
+        let recent_logs = RecentLogs::new(RECENT_LOGS_CAPACITY);
+        self.recent_logs = Some(recent_logs.clone());
+        let stdout_drain = Duplicate::new(stdout_drain, recent_logs);
+
+        // The level of stdout logs may be changed at runtime via the `log_filter`.
+        let level = logging::parse_level(&config.debug_level)
+            .map_err(|_| format!("Unknown debug-level: {}", config.debug_level))?;
+        let log_filter = LogFilterHandle::new(level);
+        self.log_filter = Some(log_filter.clone());
+        let stdout_drain = RuntimeLevelFilter::new(stdout_drain, log_filter);
 
         let stdout_logger = Logger::root(stdout_drain.fuse(), o!());
 
@@ -355,6 +371,8 @@ impl<E: EthSpec> EnvironmentBuilder<E> {
             exit,
             log: self.log.ok_or("Cannot build environment without log")?,
             sse_logging_components: self.sse_logging_components,
+            log_filter: self.log_filter,
+            recent_logs: self.recent_logs,
             eth_spec_instance: self.eth_spec_instance,
             eth2_config: self.eth2_config,
             eth2_network_config: self.eth2_network_config.map(Arc::new),
@@ -374,6 +392,8 @@ pub struct Environment<E: EthSpec> {
     exit: async_channel::Receiver<()>,
     log: Logger,
     sse_logging_components: Option<SSELoggingComponents>,
+    log_filter: Option<LogFilterHandle>,
+    recent_logs: Option<RecentLogs>,
     eth_spec_instance: E,
     pub eth2_config: Eth2Config,
     pub eth2_network_config: Option<Arc<Eth2NetworkConfig>>,
@@ -401,6 +421,8 @@ impl<E: EthSpec> Environment<E> {
             eth2_config: self.eth2_config.clone(),
             eth2_network_config: self.eth2_network_config.clone(),
             sse_logging_components: self.sse_logging_components.clone(),
+            log_filter: self.log_filter.clone(),
+            recent_logs: self.recent_logs.clone(),
         }
     }
 
@@ -417,6 +439,8 @@ impl<E: EthSpec> Environment<E> {
             eth2_config: self.eth2_config.clone(),
             eth2_network_config: self.eth2_network_config.clone(),
             sse_logging_components: self.sse_logging_components.clone(),
+            log_filter: self.log_filter.clone(),
+            recent_logs: self.recent_logs.clone(),
         }
     }
 
@@ -3,6 +3,7 @@ use account_manager::{
         create::*,
         import::{self, CMD as IMPORT_CMD},
         modify::{ALL, CMD as MODIFY_CMD, DISABLE, ENABLE, PUBKEY_FLAG},
+        rebind_network::{CMD as REBIND_NETWORK_CMD, FORCE_FLAG},
         CMD as VALIDATOR_CMD,
     },
     wallet::{
@@ -14,6 +15,7 @@ use account_manager::{
 };
 use account_utils::{
     eth2_keystore::KeystoreBuilder,
+    network_binding::NetworkBinding,
     validator_definitions::{SigningDefinition, ValidatorDefinition, ValidatorDefinitions},
     ZeroizeString,
 };
@@ -25,7 +27,7 @@ use std::path::{Path, PathBuf};
 use std::process::{Child, Command, Output, Stdio};
 use std::str::from_utf8;
 use tempfile::{tempdir, TempDir};
-use types::{Keypair, PublicKey};
+use types::{Hash256, Keypair, PublicKey};
 use validator_dir::ValidatorDir;
 
 /// Returns the `lighthouse account` command.
@@ -399,6 +401,102 @@ fn validator_create() {
     assert_eq!(dir_validator_count(validator_dir.path()), 6);
 }
 
+/// Creates two validators with `quantity_flag`, then simulates a run which was interrupted whilst
+/// creating the second validator, before the wallet was updated.
+///
+/// Returns the pubkeys of the two validators.
+fn interrupt_validator_create(
+    base_dir: &Path,
+    validator_dir: &Path,
+    validator: &TestValidator,
+    quantity_flag: &str,
+) -> Vec<String> {
+    let created = validator.create(quantity_flag, 2, false).unwrap();
+    assert_eq!(created.len(), 2);
+
+    let wallet_dir = fs::read_dir(base_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .find(|path| path.is_dir())
+        .expect("wallet dir should exist");
+    let wallet_path = wallet_dir.join(wallet_dir.file_name().unwrap());
+    let wallet_json = fs::read_to_string(&wallet_path).unwrap();
+    assert!(wallet_json.contains("\"nextaccount\":2"));
+    fs::write(
+        &wallet_path,
+        wallet_json.replace("\"nextaccount\":2", "\"nextaccount\":0"),
+    )
+    .unwrap();
+    fs::remove_file(
+        validator_dir
+            .join(&created[1])
+            .join(validator_dir::VOTING_KEYSTORE_FILE),
+    )
+    .unwrap();
+
+    created
+}
+
+#[test]
+fn validator_create_resumes_interrupted_run_with_count() {
+    let base_dir = tempdir().unwrap();
+    let validator_dir = tempdir().unwrap();
+    let secrets_dir = tempdir().unwrap();
+
+    let wallet = TestWallet::new(base_dir.path(), "wally");
+    wallet.create_expect_success();
+
+    let validator = TestValidator::new(validator_dir.path(), secrets_dir.path(), wallet);
+    let created = interrupt_validator_create(
+        base_dir.path(),
+        validator_dir.path(),
+        &validator,
+        COUNT_FLAG,
+    );
+
+    // The first validator counts towards the total and the second is created again, with the
+    // same key.
+    let resumed = validator.create_expect_success(COUNT_FLAG, 2, false);
+    let resumed_pubkeys = resumed
+        .iter()
+        .map(|v| v.voting_keypair(&secrets_dir).unwrap().pk.as_hex_string())
+        .collect::<Vec<_>>();
+    assert_eq!(resumed_pubkeys, created);
+    drop(resumed);
+
+    assert_eq!(dir_validator_count(validator_dir.path()), 2);
+
+    // The wallet was updated, so another run creates new validators.
+    let more = validator.create(COUNT_FLAG, 1, false).unwrap();
+    assert_eq!(more.len(), 1);
+    assert!(!created.contains(&more[0]));
+    assert_eq!(dir_validator_count(validator_dir.path()), 3);
+}
+
+#[test]
+fn validator_create_resumes_interrupted_run_with_at_most() {
+    let base_dir = tempdir().unwrap();
+    let validator_dir = tempdir().unwrap();
+    let secrets_dir = tempdir().unwrap();
+
+    let wallet = TestWallet::new(base_dir.path(), "wally");
+    wallet.create_expect_success();
+
+    let validator = TestValidator::new(validator_dir.path(), secrets_dir.path(), wallet);
+    let created = interrupt_validator_create(
+        base_dir.path(),
+        validator_dir.path(),
+        &validator,
+        AT_MOST_FLAG,
+    );
+
+    // The first validator was already counted, so only the second is created again, with the
+    // same key.
+    let resumed = validator.create(AT_MOST_FLAG, 2, false).unwrap();
+    assert_eq!(resumed, vec![created[1].clone()]);
+    assert_eq!(dir_validator_count(validator_dir.path()), 2);
+}
+
 #[test]
 fn validator_import_launchpad() {
     const PASSWORD: &str = "cats";
@@ -494,6 +592,10 @@ fn validator_import_launchpad() {
         builder_proposals: None,
         builder_boost_factor: None,
         prefer_builder_proposals: None,
+        scheduled_exit: None,
+        bls_to_execution_change: None,
+        handover_epoch: None,
+        suspended: false,
         voting_public_key: keystore.public_key().unwrap(),
         signing_definition: SigningDefinition::LocalKeystore {
             voting_keystore_path,
@@ -618,6 +720,10 @@ fn validator_import_launchpad_no_password_then_add_password() {
         builder_proposals: None,
         builder_boost_factor: None,
         prefer_builder_proposals: None,
+        scheduled_exit: None,
+        bls_to_execution_change: None,
+        handover_epoch: None,
+        suspended: false,
         voting_public_key: keystore.public_key().unwrap(),
         signing_definition: SigningDefinition::LocalKeystore {
             voting_keystore_path,
@@ -646,6 +752,10 @@ fn validator_import_launchpad_no_password_then_add_password() {
         builder_proposals: None,
         builder_boost_factor: None,
         prefer_builder_proposals: None,
+        scheduled_exit: None,
+        bls_to_execution_change: None,
+        handover_epoch: None,
+        suspended: false,
         voting_public_key: keystore.public_key().unwrap(),
         signing_definition: SigningDefinition::LocalKeystore {
             voting_keystore_path: dst_keystore_dir.join(KEYSTORE_NAME),
@@ -750,6 +860,10 @@ fn validator_import_launchpad_password_file() {
         builder_proposals: None,
         builder_boost_factor: None,
         prefer_builder_proposals: None,
+        scheduled_exit: None,
+        bls_to_execution_change: None,
+        handover_epoch: None,
+        suspended: false,
         signing_definition: SigningDefinition::LocalKeystore {
             voting_keystore_path,
             voting_keystore_password_path: None,
@@ -773,3 +887,41 @@ fn check_slashing_protection(validator_dir: &TempDir, pubkeys: impl Iterator<Ite
             .unwrap();
     }
 }
+
+#[test]
+fn validator_rebind_network() {
+    let validator_dir = tempdir().unwrap();
+    let rebind = |force: bool| {
+        let mut cmd = validator_cmd();
+        cmd.arg(format!("--{}", VALIDATOR_DIR_FLAG))
+            .arg(validator_dir.path().as_os_str())
+            .arg(REBIND_NETWORK_CMD);
+        if force {
+            cmd.arg(format!("--{}", FORCE_FLAG));
+        }
+        output_result(&mut cmd)
+    };
+
+    // An unbound directory is bound to the network.
+    rebind(false).unwrap();
+    let binding = NetworkBinding::open(validator_dir.path())
+        .unwrap()
+        .expect("directory should be bound");
+
+    // A directory bound to another network is only moved with `--force`.
+    let other_binding = NetworkBinding {
+        genesis_validators_root: Hash256::repeat_byte(42),
+    };
+    other_binding.save(validator_dir.path()).unwrap();
+    rebind(false).unwrap_err();
+    assert_eq!(
+        NetworkBinding::open(validator_dir.path()).unwrap(),
+        Some(other_binding)
+    );
+
+    rebind(true).unwrap();
+    assert_eq!(
+        NetworkBinding::open(validator_dir.path()).unwrap(),
+        Some(binding)
+    );
+}
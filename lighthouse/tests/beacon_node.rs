@@ -1,4 +1,4 @@
-use beacon_node::ClientConfig as Config;
+use beacon_node::{ClientConfig as Config, ClientGenesis};
 
 use crate::exec::{CommandLineTestExec, CompletedTest};
 use beacon_node::beacon_chain::chain_config::{
@@ -186,6 +186,26 @@ fn shuffling_cache_set() {
         .with_config(|config| assert_eq!(config.chain.shuffling_cache_size, 500));
 }
 
+#[test]
+fn payload_prefetch_window_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.payload_prefetch_window,
+                beacon_node::beacon_chain::payload_prefetch::DEFAULT_PAYLOAD_PREFETCH_WINDOW
+            )
+        });
+}
+
+#[test]
+fn payload_prefetch_window_set() {
+    CommandLineTest::new()
+        .flag("payload-prefetch-window", Some("4"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.payload_prefetch_window, 4));
+}
+
 #[test]
 fn fork_choice_before_proposal_timeout_default() {
     CommandLineTest::new()
@@ -225,6 +245,60 @@ fn checkpoint_sync_url_timeout_default() {
         });
 }
 
+#[test]
+fn checkpoint_deposit_snapshot_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let snapshot_path = dir.path().join("deposit_snapshot.ssz");
+    File::create(&snapshot_path)
+        .and_then(|mut file| file.write_all(&[1, 2, 3]))
+        .expect("Unable to write deposit snapshot");
+
+    CommandLineTest::new()
+        .flag("checkpoint-sync-url", Some("http://localhost:5052"))
+        .flag("checkpoint-deposit-snapshot", snapshot_path.to_str())
+        .run_with_zero_port_and_no_genesis_sync()
+        .with_config(|config| match &config.genesis {
+            ClientGenesis::CheckpointSyncUrl {
+                deposit_snapshot_bytes,
+                ..
+            } => assert_eq!(deposit_snapshot_bytes.as_deref(), Some(&[1, 2, 3][..])),
+            genesis => panic!("unexpected genesis {:?}", genesis),
+        });
+}
+
+#[test]
+fn deposit_snapshot_file_alias() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let snapshot_path = dir.path().join("deposit_snapshot.ssz");
+    File::create(&snapshot_path)
+        .and_then(|mut file| file.write_all(&[1, 2, 3]))
+        .expect("Unable to write deposit snapshot");
+
+    CommandLineTest::new()
+        .flag("checkpoint-sync-url", Some("http://localhost:5052"))
+        .flag("deposit-snapshot-file", snapshot_path.to_str())
+        .run_with_zero_port_and_no_genesis_sync()
+        .with_config(|config| match &config.genesis {
+            ClientGenesis::CheckpointSyncUrl {
+                deposit_snapshot_bytes,
+                ..
+            } => assert_eq!(deposit_snapshot_bytes.as_deref(), Some(&[1, 2, 3][..])),
+            genesis => panic!("unexpected genesis {:?}", genesis),
+        });
+}
+
+#[test]
+#[should_panic]
+fn checkpoint_deposit_snapshot_requires_checkpoint_sync() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let snapshot_path = dir.path().join("deposit_snapshot.ssz");
+    File::create(&snapshot_path).expect("Unable to write deposit snapshot");
+
+    CommandLineTest::new()
+        .flag("checkpoint-deposit-snapshot", snapshot_path.to_str())
+        .run_with_zero_port();
+}
+
 #[test]
 fn prepare_payload_lookahead_default() {
     CommandLineTest::new()
@@ -585,9 +659,8 @@ fn run_payload_builder_flag_test(flag: &str, builders: &str) {
         .collect();
     run_payload_builder_flag_test_with_config(flag, builders, None, None, |config| {
         let config = config.execution_layer.as_ref().unwrap();
-        // Only first provided endpoint is parsed as we don't support
-        // redundancy.
-        assert_eq!(config.builder_url, all_builders.get(0).cloned());
+        // All provided builders are raced against each other.
+        assert_eq!(config.builder_urls, all_builders);
     })
 }
 fn run_payload_builder_flag_test_with_config<F: Fn(&Config)>(
@@ -638,6 +711,15 @@ fn builder_fallback_flags() {
             assert_eq!(config.chain.builder_fallback_skips_per_epoch, 11);
         },
     );
+    run_payload_builder_flag_test_with_config(
+        "builder",
+        "http://meow.cats",
+        Some("builder-fallback-skips-window"),
+        Some("16"),
+        |config| {
+            assert_eq!(config.chain.builder_fallback_skips_window, Some(16));
+        },
+    );
     run_payload_builder_flag_test_with_config(
         "builder",
         "http://meow.cats",
@@ -647,6 +729,15 @@ fn builder_fallback_flags() {
             assert_eq!(config.chain.builder_fallback_epochs_since_finalization, 4);
         },
     );
+    run_payload_builder_flag_test_with_config(
+        "builder",
+        "http://meow.cats",
+        Some("builder-fallback-head-delay-ms"),
+        Some("4000"),
+        |config| {
+            assert_eq!(config.chain.builder_fallback_head_delay_ms, Some(4000));
+        },
+    );
     run_payload_builder_flag_test_with_config(
         "builder",
         "http://meow.cats",
@@ -658,6 +749,30 @@ fn builder_fallback_flags() {
     );
 }
 
+#[test]
+#[should_panic]
+fn builder_fallback_skips_window_longer_than_epoch() {
+    run_payload_builder_flag_test_with_config(
+        "builder",
+        "http://meow.cats",
+        Some("builder-fallback-skips-window"),
+        Some("64"),
+        |_| {},
+    );
+}
+
+#[test]
+#[should_panic]
+fn builder_fallback_skips_window_zero() {
+    run_payload_builder_flag_test_with_config(
+        "builder",
+        "http://meow.cats",
+        Some("builder-fallback-skips-window"),
+        Some("0"),
+        |_| {},
+    );
+}
+
 #[test]
 fn builder_get_header_timeout() {
     run_payload_builder_flag_test_with_config(
@@ -678,6 +793,74 @@ fn builder_get_header_timeout() {
     );
 }
 
+#[test]
+fn builder_disable_ssz() {
+    run_payload_builder_flag_test_with_config(
+        "builder",
+        "http://meow.cats",
+        None,
+        None,
+        |config| assert!(!config.execution_layer.as_ref().unwrap().builder_disable_ssz),
+    );
+    run_payload_builder_flag_test_with_config(
+        "builder",
+        "http://meow.cats",
+        Some("builder-disable-ssz"),
+        None,
+        |config| assert!(config.execution_layer.as_ref().unwrap().builder_disable_ssz),
+    );
+}
+
+#[test]
+fn builder_registration_batch_flags() {
+    run_payload_builder_flag_test_with_config(
+        "builder",
+        "http://meow.cats",
+        None,
+        None,
+        |config| {
+            let el_config = config.execution_layer.as_ref().unwrap();
+            assert_eq!(el_config.builder_registration_batch_size, Some(500));
+            assert_eq!(
+                el_config.builder_registration_batch_delay,
+                Some(Duration::from_millis(100))
+            );
+        },
+    );
+    run_payload_builder_flag_test_with_config(
+        "builder",
+        "http://meow.cats",
+        Some("builder-registration-batch-size"),
+        Some("64"),
+        |config| {
+            assert_eq!(
+                config
+                    .execution_layer
+                    .as_ref()
+                    .unwrap()
+                    .builder_registration_batch_size,
+                Some(64)
+            );
+        },
+    );
+    run_payload_builder_flag_test_with_config(
+        "builder",
+        "http://meow.cats",
+        Some("builder-registration-batch-delay"),
+        Some("250"),
+        |config| {
+            assert_eq!(
+                config
+                    .execution_layer
+                    .as_ref()
+                    .unwrap()
+                    .builder_registration_batch_delay,
+                Some(Duration::from_millis(250))
+            );
+        },
+    );
+}
+
 #[test]
 fn builder_user_agent() {
     run_payload_builder_flag_test_with_config(
@@ -1690,6 +1873,28 @@ fn http_tls_flags() {
                 .expect("tls_config was empty.");
             assert_eq!(tls_config.cert, Path::new("tests/tls/cert.pem"));
             assert_eq!(tls_config.key, Path::new("tests/tls/key.rsa"));
+            assert_eq!(tls_config.client_ca, None);
+        });
+}
+#[test]
+fn http_tls_client_ca_flag() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-enable-tls", None)
+        .flag("http-tls-cert", Some("tests/tls/cert.pem"))
+        .flag("http-tls-key", Some("tests/tls/key.rsa"))
+        .flag("http-tls-client-ca", Some("tests/tls/cert.pem"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            let tls_config = config
+                .http_api
+                .tls_config
+                .as_ref()
+                .expect("tls_config was empty.");
+            assert_eq!(
+                tls_config.client_ca.as_deref(),
+                Some(Path::new("tests/tls/cert.pem"))
+            );
         });
 }
 
@@ -2665,6 +2870,79 @@ fn http_sse_capacity_multiplier_override() {
         .with_config(|config| assert_eq!(config.http_api.sse_capacity_multiplier, 10));
 }
 
+#[test]
+fn http_historic_state_replay_limit_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.http_api.historic_state_replay_limit, 0));
+}
+
+#[test]
+fn http_historic_state_replay_limit_override() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-historic-state-replay-limit", Some("8192"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.http_api.historic_state_replay_limit, 8192));
+}
+
+#[test]
+fn http_api_tokens_file_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.http_api.api_tokens_file, None));
+}
+
+#[test]
+fn http_api_tokens_file_flag() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-api-tokens-file", Some("/tmp/api-tokens.txt"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.http_api.api_tokens_file,
+                Some(PathBuf::from("/tmp/api-tokens.txt"))
+            )
+        });
+}
+
+#[test]
+fn http_rate_limit_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.http_api.rate_limit, None));
+}
+
+#[test]
+fn http_rate_limit_override() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-rate-limit", Some("default:100/10;beacon/states:5/10"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            let expected = "default:100/10;beacon/states:5/10".parse().unwrap();
+            assert_eq!(config.http_api.rate_limit, Some(expected));
+        });
+}
+
+#[test]
+fn http_response_cache_size_default() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.http_api.response_cache_size_mb, 64));
+}
+
+#[test]
+fn http_response_cache_size_override() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-response-cache-size", Some("0"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.http_api.response_cache_size_mb, 0));
+}
+
 #[test]
 fn http_duplicate_block_status_default() {
     CommandLineTest::new()
@@ -1,4 +1,7 @@
-use validator_client::{config::DEFAULT_WEB3SIGNER_KEEP_ALIVE, ApiTopic, Config};
+use validator_client::{
+    config::DEFAULT_WEB3SIGNER_KEEP_ALIVE, ApiTopic, BeaconNodeRoutes, BeaconNodeSelection, Config,
+    RequestClass, SigningTimeouts, ValidatorMetricsLabels,
+};
 
 use crate::exec::CommandLineTestExec;
 use bls::{Keypair, PublicKeyBytes};
@@ -154,6 +157,57 @@ fn beacon_nodes_tls_certs_flag() {
         });
 }
 
+#[test]
+fn beacon_nodes_tls_client_identity_flags() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    CommandLineTest::new()
+        .flag(
+            "beacon-nodes-tls-client-cert",
+            dir.path().join("client.crt").to_str(),
+        )
+        .flag(
+            "beacon-nodes-tls-client-key",
+            dir.path().join("client.key").to_str(),
+        )
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.beacon_nodes_tls_client_identity,
+                Some((dir.path().join("client.crt"), dir.path().join("client.key")))
+            )
+        });
+}
+
+#[test]
+#[should_panic]
+fn beacon_nodes_tls_client_cert_without_key() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    CommandLineTest::new()
+        .flag(
+            "beacon-nodes-tls-client-cert",
+            dir.path().join("client.crt").to_str(),
+        )
+        .run()
+        .with_config(|config| assert!(config.beacon_nodes_tls_client_identity.is_some()));
+}
+
+#[test]
+fn beacon_nodes_api_token_file_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    CommandLineTest::new()
+        .flag(
+            "beacon-nodes-api-token-file",
+            dir.path().join("api-token.txt").to_str(),
+        )
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.beacon_nodes_api_token_file,
+                Some(dir.path().join("api-token.txt"))
+            )
+        });
+}
+
 // Tests for Graffiti flags.
 #[test]
 fn graffiti_flag() {
@@ -243,6 +297,34 @@ fn fee_recipient_flag() {
             )
         });
 }
+#[test]
+fn fee_recipient_file_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let mut file =
+        File::create(dir.path().join("fee_recipient.txt")).expect("Unable to create file");
+    let new_key = Keypair::random();
+    let pubkeybytes = PublicKeyBytes::from(new_key.pk);
+    let contents = "default: 0x00000000219ab540356cbb839cbe05303d7705fa";
+    file.write_all(contents.as_bytes())
+        .expect("Unable to write to file");
+    CommandLineTest::new()
+        .flag(
+            "suggested-fee-recipient-file",
+            dir.path().join("fee_recipient.txt").as_os_str().to_str(),
+        )
+        .run()
+        .with_config(|config| {
+            // Public key not present so load default.
+            assert_eq!(
+                config
+                    .fee_recipient_file
+                    .as_ref()
+                    .unwrap()
+                    .get_fee_recipient(&pubkeybytes),
+                Some(Address::from_str("0x00000000219ab540356cbb839cbe05303d7705fa").unwrap())
+            )
+        });
+}
 
 // Tests for HTTP flags.
 #[test]
@@ -423,6 +505,19 @@ fn no_doppelganger_protection_flag() {
         .run()
         .with_config(|config| assert!(!config.enable_doppelganger_protection));
 }
+#[test]
+fn runtime_snapshot_flag() {
+    CommandLineTest::new()
+        .flag("enable-runtime-snapshot", None)
+        .run()
+        .with_config(|config| assert!(config.enable_runtime_snapshot));
+}
+#[test]
+fn no_runtime_snapshot_flag() {
+    CommandLineTest::new()
+        .run()
+        .with_config(|config| assert!(!config.enable_runtime_snapshot));
+}
 
 #[test]
 fn produce_block_v3_flag() {
@@ -485,6 +580,28 @@ fn no_prefer_builder_proposals_flag() {
         .with_config(|config| assert!(!config.prefer_builder_proposals));
 }
 #[test]
+fn proposer_only_flag() {
+    CommandLineTest::new()
+        .flag("proposer-only", None)
+        .run()
+        .with_config(|config| assert!(config.proposer_only));
+}
+#[test]
+fn no_proposer_only_flag() {
+    CommandLineTest::new()
+        .run()
+        .with_config(|config| assert!(!config.proposer_only));
+}
+#[test]
+#[should_panic]
+fn proposer_only_conflicts_with_doppelganger_protection() {
+    CommandLineTest::new()
+        .flag("proposer-only", None)
+        .flag("enable-doppelganger-protection", None)
+        .run()
+        .with_config(|config| assert!(config.proposer_only));
+}
+#[test]
 fn no_builder_registration_timestamp_override_flag() {
     CommandLineTest::new()
         .run()
@@ -572,6 +689,139 @@ fn broadcast_flag() {
         });
 }
 
+#[test]
+fn beacon_node_selection_default() {
+    CommandLineTest::new().run().with_config(|config| {
+        assert_eq!(config.beacon_node_selection, BeaconNodeSelection::Ordered);
+    });
+}
+
+#[test]
+fn beacon_node_selection_flag() {
+    CommandLineTest::new()
+        .flag("beacon-node-selection", Some("latency-weighted"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.beacon_node_selection,
+                BeaconNodeSelection::LatencyWeighted
+            );
+        });
+    CommandLineTest::new()
+        .flag("beacon-node-selection", Some("round-robin-reads"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.beacon_node_selection,
+                BeaconNodeSelection::RoundRobinReads
+            );
+        });
+}
+
+#[test]
+#[should_panic]
+fn beacon_node_selection_invalid() {
+    CommandLineTest::new()
+        .flag("beacon-node-selection", Some("random"))
+        .run();
+}
+
+#[test]
+fn beacon_node_routes_default() {
+    CommandLineTest::new().run().with_config(|config| {
+        assert_eq!(config.beacon_node_routes, BeaconNodeRoutes::default());
+    });
+}
+
+#[test]
+fn beacon_node_routes_flag() {
+    CommandLineTest::new()
+        .flag(
+            "beacon-nodes",
+            Some("http://localhost:1001,http://localhost:1002"),
+        )
+        .flag("beacon-node-routes", Some("blocks=0,duties=0:1"))
+        .run()
+        .with_config(|config| {
+            let routes = &config.beacon_node_routes;
+            assert_eq!(routes.route(RequestClass::Blocks), Some(&[0][..]));
+            assert_eq!(routes.route(RequestClass::Duties), Some(&[0, 1][..]));
+            assert_eq!(routes.route(RequestClass::Attestations), None);
+        });
+}
+
+#[test]
+#[should_panic]
+fn beacon_node_routes_unknown_index() {
+    CommandLineTest::new()
+        .flag("beacon-node-routes", Some("blocks=1"))
+        .run();
+}
+
+#[test]
+fn validator_metrics_labels_default() {
+    CommandLineTest::new().run().with_config(|config| {
+        assert_eq!(config.validator_metrics_labels, None);
+        assert!(config.validator_metrics_pubkeys.is_empty());
+    });
+}
+
+#[test]
+fn validator_metrics_labels_flag() {
+    CommandLineTest::new()
+        .flag("validator-metrics-labels", Some("none"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.validator_metrics_labels,
+                Some(ValidatorMetricsLabels::None)
+            );
+        });
+}
+
+#[test]
+fn validator_metrics_pubkeys_flag() {
+    let pubkeys = [
+        PublicKeyBytes::from(Keypair::random().pk),
+        PublicKeyBytes::from(Keypair::random().pk),
+    ];
+    CommandLineTest::new()
+        .flag("validator-metrics-labels", Some("monitored"))
+        .flag(
+            "validator-metrics-pubkeys",
+            Some(&format!(
+                "{},{}",
+                pubkeys[0].as_hex_string(),
+                pubkeys[1].as_hex_string()
+            )),
+        )
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.validator_metrics_labels,
+                Some(ValidatorMetricsLabels::Monitored)
+            );
+            assert_eq!(config.validator_metrics_pubkeys, pubkeys);
+        });
+}
+
+#[test]
+#[should_panic]
+fn validator_metrics_labels_monitored_without_pubkeys() {
+    CommandLineTest::new()
+        .flag("validator-metrics-labels", Some("monitored"))
+        .run();
+}
+
+#[test]
+#[should_panic]
+fn validator_metrics_labels_conflicts_with_high_validator_count_metrics() {
+    CommandLineTest::new()
+        .flag("validator-metrics-labels", Some("all"))
+        .flag("enable-high-validator-count-metrics", None)
+        .run();
+}
+
 #[test]
 #[should_panic(expected = "Unknown API topic")]
 fn wrong_broadcast_flag() {
@@ -606,6 +856,32 @@ fn latency_measurement_service() {
         });
 }
 
+#[test]
+fn performance_tracking_epochs() {
+    CommandLineTest::new().run().with_config(|config| {
+        assert_eq!(config.performance_tracking_epochs, None);
+    });
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("performance-tracking-epochs", Some("64"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(config.performance_tracking_epochs, Some(64));
+        });
+}
+
+#[test]
+#[should_panic]
+fn performance_tracking_epochs_zero_value() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("performance-tracking-epochs", Some("0"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(config.performance_tracking_epochs, Some(0));
+        });
+}
+
 #[test]
 fn validator_registration_batch_size() {
     CommandLineTest::new().run().with_config(|config| {
@@ -627,6 +903,93 @@ fn validator_registration_batch_size_zero_value() {
         .run();
 }
 
+#[test]
+fn proposer_duties_prefetch_slots() {
+    CommandLineTest::new().run().with_config(|config| {
+        assert_eq!(config.proposer_prefetch_slots, 2);
+    });
+    CommandLineTest::new()
+        .flag("proposer-duties-prefetch-slots", Some("0"))
+        .run()
+        .with_config(|config| assert_eq!(config.proposer_prefetch_slots, 0));
+}
+
+#[test]
+fn distributed_duty_delay() {
+    CommandLineTest::new().run().with_config(|config| {
+        assert_eq!(config.distributed_duty_delay, None);
+    });
+    CommandLineTest::new()
+        .flag("distributed", None)
+        .flag("distributed-duty-delay", Some("250"))
+        .run()
+        .with_config(|config| {
+            assert!(config.distributed);
+            assert_eq!(
+                config.distributed_duty_delay,
+                Some(Duration::from_millis(250))
+            );
+        });
+}
+
+#[test]
+fn sync_committee_jitter() {
+    CommandLineTest::new().run().with_config(|config| {
+        assert_eq!(config.sync_committee_jitter, None);
+    });
+    CommandLineTest::new()
+        .flag("sync-committee-jitter", Some("500"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.sync_committee_jitter,
+                Some(Duration::from_millis(500))
+            );
+        });
+}
+
+#[test]
+#[should_panic]
+fn distributed_duty_delay_requires_distributed() {
+    CommandLineTest::new()
+        .flag("distributed-duty-delay", Some("250"))
+        .run();
+}
+
+#[test]
+fn early_attestations() {
+    CommandLineTest::new().run().with_config(|config| {
+        assert_eq!(config.early_attestation_min_delay, None);
+    });
+    CommandLineTest::new()
+        .flag("early-attestations", None)
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.early_attestation_min_delay,
+                Some(Duration::from_millis(1000))
+            );
+        });
+    CommandLineTest::new()
+        .flag("early-attestations", None)
+        .flag("early-attestation-min-delay", Some("500"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.early_attestation_min_delay,
+                Some(Duration::from_millis(500))
+            );
+        });
+}
+
+#[test]
+#[should_panic]
+fn early_attestation_min_delay_requires_early_attestations() {
+    CommandLineTest::new()
+        .flag("early-attestation-min-delay", Some("500"))
+        .run();
+}
+
 #[test]
 fn validator_disable_web3_signer_slashing_protection_default() {
     CommandLineTest::new().run().with_config(|config| {
@@ -667,6 +1030,138 @@ fn validator_web3_signer_keep_alive_override() {
         });
 }
 
+#[test]
+fn validator_web3_signer_signing_timeouts_default() {
+    CommandLineTest::new().run().with_config(|config| {
+        assert_eq!(
+            config.web3_signer_signing_timeouts,
+            SigningTimeouts::default()
+        );
+    });
+}
+
+#[test]
+fn validator_web3_signer_signing_timeouts() {
+    CommandLineTest::new()
+        .flag("web3-signer-block-timeout", Some("2000"))
+        .flag("web3-signer-attestation-timeout", Some("1000"))
+        .flag("web3-signer-aggregate-timeout", Some("500"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.web3_signer_signing_timeouts,
+                SigningTimeouts {
+                    block: Some(Duration::from_secs(2)),
+                    attestation: Some(Duration::from_secs(1)),
+                    aggregate: Some(Duration::from_millis(500)),
+                }
+            );
+        });
+}
+
+#[test]
+fn validator_web3_signer_health_check_interval_default() {
+    CommandLineTest::new().run().with_config(|config| {
+        assert_eq!(config.web3_signer_health_check_interval, None);
+    });
+}
+
+#[test]
+fn validator_web3_signer_health_check_interval() {
+    CommandLineTest::new()
+        .flag("web3-signer-health-check-interval", Some("12"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.web3_signer_health_check_interval,
+                Some(Duration::from_secs(12))
+            );
+        });
+}
+
+#[test]
+#[should_panic]
+fn validator_web3_signer_health_check_interval_zero() {
+    CommandLineTest::new()
+        .flag("web3-signer-health-check-interval", Some("0"))
+        .run();
+}
+
+#[test]
+fn slashing_protection_export_default() {
+    CommandLineTest::new().run().with_config(|config| {
+        assert_eq!(config.slashing_protection_export_path, None);
+        assert_eq!(
+            config.slashing_protection_export_interval,
+            Duration::from_secs(3600)
+        );
+    });
+}
+
+#[test]
+fn slashing_protection_export_flags() {
+    CommandLineTest::new()
+        .flag(
+            "slashing-protection-export-path",
+            Some("/tmp/interchange.json"),
+        )
+        .flag("slashing-protection-export-interval", Some("600"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.slashing_protection_export_path,
+                Some(PathBuf::from("/tmp/interchange.json"))
+            );
+            assert_eq!(
+                config.slashing_protection_export_interval,
+                Duration::from_secs(600)
+            );
+        });
+}
+
+#[test]
+#[should_panic]
+fn slashing_protection_export_interval_zero() {
+    CommandLineTest::new()
+        .flag("slashing-protection-export-interval", Some("0"))
+        .run();
+}
+
+#[test]
+fn signing_lease_default() {
+    CommandLineTest::new().run().with_config(|config| {
+        assert_eq!(config.signing_lease_file, None);
+        assert_eq!(config.signing_lease_timeout, Duration::from_secs(60));
+        assert_eq!(config.signing_lease_clock_skew, Duration::from_secs(5));
+    });
+}
+
+#[test]
+fn signing_lease_flags() {
+    CommandLineTest::new()
+        .flag("signing-lease-file", Some("/tmp/lease.json"))
+        .flag("signing-lease-timeout", Some("30"))
+        .flag("signing-lease-clock-skew", Some("2"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.signing_lease_file,
+                Some(PathBuf::from("/tmp/lease.json"))
+            );
+            assert_eq!(config.signing_lease_timeout, Duration::from_secs(30));
+            assert_eq!(config.signing_lease_clock_skew, Duration::from_secs(2));
+        });
+}
+
+#[test]
+#[should_panic]
+fn signing_lease_timeout_too_short() {
+    CommandLineTest::new()
+        .flag("signing-lease-file", Some("/tmp/lease.json"))
+        .flag("signing-lease-timeout", Some("5"))
+        .run();
+}
+
 #[test]
 fn validator_proposer_nodes_default_empty() {
     CommandLineTest::new().run().with_config(|config| {
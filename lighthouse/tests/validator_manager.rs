@@ -9,6 +9,7 @@ use tempfile::{tempdir, TempDir};
 use types::*;
 use validator_manager::{
     create_validators::CreateConfig,
+    exit_validators::ExitConfig,
     import_validators::ImportConfig,
     move_validators::{MoveConfig, PasswordSource, Validators},
 };
@@ -105,6 +106,12 @@ impl CommandLineTest<MoveConfig> {
     }
 }
 
+impl CommandLineTest<ExitConfig> {
+    fn validators_exit() -> Self {
+        Self::default().flag("exit", None)
+    }
+}
+
 #[test]
 pub fn validator_create_without_output_path() {
     CommandLineTest::validators_create().assert_failed();
@@ -199,7 +206,9 @@ pub fn validator_import_defaults() {
         .flag("--vc-token", Some("./token.json"))
         .assert_success(|config| {
             let expected = ImportConfig {
-                validators_file_path: PathBuf::from("./vals.json"),
+                validators_file_path: Some(PathBuf::from("./vals.json")),
+                web3signer_url: None,
+                web3signer_overrides_path: None,
                 vc_url: SensitiveUrl::parse("http://localhost:5062").unwrap(),
                 vc_token_path: PathBuf::from("./token.json"),
                 ignore_duplicates: false,
@@ -216,7 +225,9 @@ pub fn validator_import_misc_flags() {
         .flag("--ignore-duplicates", None)
         .assert_success(|config| {
             let expected = ImportConfig {
-                validators_file_path: PathBuf::from("./vals.json"),
+                validators_file_path: Some(PathBuf::from("./vals.json")),
+                web3signer_url: None,
+                web3signer_overrides_path: None,
                 vc_url: SensitiveUrl::parse("http://localhost:5062").unwrap(),
                 vc_token_path: PathBuf::from("./token.json"),
                 ignore_duplicates: true,
@@ -239,6 +250,43 @@ pub fn validator_import_missing_validators_file() {
         .assert_failed();
 }
 
+#[test]
+pub fn validator_import_from_web3signer() {
+    CommandLineTest::validators_import()
+        .flag("--from-web3signer", Some("http://localhost:9000"))
+        .flag("--web3signer-overrides", Some("./overrides.json"))
+        .flag("--vc-token", Some("./token.json"))
+        .assert_success(|config| {
+            let expected = ImportConfig {
+                validators_file_path: None,
+                web3signer_url: Some(SensitiveUrl::parse("http://localhost:9000").unwrap()),
+                web3signer_overrides_path: Some(PathBuf::from("./overrides.json")),
+                vc_url: SensitiveUrl::parse("http://localhost:5062").unwrap(),
+                vc_token_path: PathBuf::from("./token.json"),
+                ignore_duplicates: false,
+            };
+            assert_eq!(expected, config);
+        });
+}
+
+#[test]
+pub fn validator_import_from_web3signer_and_validators_file() {
+    CommandLineTest::validators_import()
+        .flag("--validators-file", Some("./vals.json"))
+        .flag("--from-web3signer", Some("http://localhost:9000"))
+        .flag("--vc-token", Some("./token.json"))
+        .assert_failed();
+}
+
+#[test]
+pub fn validator_import_overrides_without_web3signer() {
+    CommandLineTest::validators_import()
+        .flag("--validators-file", Some("./vals.json"))
+        .flag("--web3signer-overrides", Some("./overrides.json"))
+        .flag("--vc-token", Some("./token.json"))
+        .assert_failed();
+}
+
 #[test]
 pub fn validator_move_defaults() {
     CommandLineTest::validators_move()
@@ -394,3 +442,52 @@ pub fn validator_move_count() {
             assert_eq!(expected, config);
         });
 }
+
+#[test]
+pub fn validator_exit_defaults() {
+    CommandLineTest::validators_exit()
+        .flag("--vc-token", Some("./token.json"))
+        .flag("--validators", Some(EXAMPLE_PUBKEY_0))
+        .assert_success(|config| {
+            let expected = ExitConfig {
+                vc_url: SensitiveUrl::parse("http://localhost:5062").unwrap(),
+                vc_token_path: PathBuf::from("./token.json"),
+                bn_url: SensitiveUrl::parse("http://localhost:5052").unwrap(),
+                validators: Validators::Specific(vec![
+                    PublicKeyBytes::from_str(EXAMPLE_PUBKEY_0).unwrap()
+                ]),
+                stagger_epochs: 0,
+                dry_run: false,
+            };
+            assert_eq!(expected, config);
+        });
+}
+
+#[test]
+pub fn validator_exit_count_staggered_dry_run() {
+    CommandLineTest::validators_exit()
+        .flag("--vc-url", Some("http://localhost:1"))
+        .flag("--vc-token", Some("./token.json"))
+        .flag("--beacon-node", Some("http://localhost:2"))
+        .flag("--count", Some("42"))
+        .flag("--stagger", Some("3"))
+        .flag("--dry-run", None)
+        .assert_success(|config| {
+            let expected = ExitConfig {
+                vc_url: SensitiveUrl::parse("http://localhost:1").unwrap(),
+                vc_token_path: PathBuf::from("./token.json"),
+                bn_url: SensitiveUrl::parse("http://localhost:2").unwrap(),
+                validators: Validators::Count(42),
+                stagger_epochs: 3,
+                dry_run: true,
+            };
+            assert_eq!(expected, config);
+        });
+}
+
+#[test]
+pub fn validator_exit_without_validators() {
+    CommandLineTest::validators_exit()
+        .flag("--vc-token", Some("./token.json"))
+        .assert_failed();
+}
@@ -24,6 +24,8 @@ pub struct Slasher<E: EthSpec> {
     block_queue: BlockQueue,
     attester_slashings: Mutex<HashSet<AttesterSlashing<E>>>,
     proposer_slashings: Mutex<HashSet<ProposerSlashing>>,
+    /// The current epoch of the most recent batch which was processed successfully.
+    last_processed_epoch: Mutex<Option<Epoch>>,
     config: Arc<Config>,
     log: Logger,
 }
@@ -43,6 +45,7 @@ impl<E: EthSpec> Slasher<E> {
             block_queue,
             attester_slashings,
             proposer_slashings,
+            last_processed_epoch: Mutex::new(None),
             config,
             log,
         })
@@ -58,6 +61,11 @@ impl<E: EthSpec> Slasher<E> {
         std::mem::take(&mut self.proposer_slashings.lock())
     }
 
+    /// Returns the current epoch of the most recent batch which was processed successfully.
+    pub fn last_processed_epoch(&self) -> Option<Epoch> {
+        *self.last_processed_epoch.lock()
+    }
+
     pub fn config(&self) -> &Config {
         &self.config
     }
@@ -82,6 +90,7 @@ impl<E: EthSpec> Slasher<E> {
         let block_stats = self.process_blocks(&mut txn)?;
         let attestation_stats = self.process_attestations(current_epoch, &mut txn)?;
         txn.commit()?;
+        *self.last_processed_epoch.lock() = Some(current_epoch);
         Ok(BatchStats {
             block_stats,
             attestation_stats,
@@ -3,7 +3,7 @@ use crate::case_result::compare_beacon_state_results_without_caches;
 use crate::decode::{ssz_decode_file, ssz_decode_file_with, ssz_decode_state, yaml_decode_file};
 use serde::Deserialize;
 use state_processing::initialize_beacon_state_from_eth1;
-use types::{BeaconState, Deposit, ExecutionPayloadHeader, Hash256};
+use types::{BeaconState, Deposit, ExecutionPayloadHeader, ForkVersionDecode, Hash256};
 
 #[derive(Debug, Clone, Deserialize)]
 struct Metadata {
@@ -39,7 +39,7 @@ impl<E: EthSpec> LoadCase for GenesisInitialization<E> {
             if meta.execution_payload_header.unwrap_or(false) {
                 Some(ssz_decode_file_with(
                     &path.join("execution_payload_header.ssz_snappy"),
-                    |bytes| ExecutionPayloadHeader::from_ssz_bytes(bytes, fork_name),
+                    |bytes| ExecutionPayloadHeader::from_ssz_bytes_by_fork(bytes, fork_name),
                 )?)
             } else {
                 None
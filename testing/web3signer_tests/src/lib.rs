@@ -424,6 +424,10 @@ mod tests {
                     builder_proposals: None,
                     builder_boost_factor: None,
                     prefer_builder_proposals: None,
+                    scheduled_exit: None,
+                    bls_to_execution_change: None,
+                    handover_epoch: None,
+                    suspended: false,
                     description: String::default(),
                     signing_definition: SigningDefinition::LocalKeystore {
                         voting_keystore_path: signer_rig.keystore_path.clone(),
@@ -450,6 +454,10 @@ mod tests {
                     builder_proposals: None,
                     builder_boost_factor: None,
                     prefer_builder_proposals: None,
+                    scheduled_exit: None,
+                    bls_to_execution_change: None,
+                    handover_epoch: None,
+                    suspended: false,
                     description: String::default(),
                     signing_definition: SigningDefinition::Web3Signer(Web3SignerDefinition {
                         url: signer_rig.url.to_string(),
@@ -457,6 +465,8 @@ mod tests {
                         request_timeout_ms: None,
                         client_identity_path: Some(client_identity_path()),
                         client_identity_password: Some(client_identity_password()),
+                        fallback_keystore_path: None,
+                        fallback_keystore_password_path: None,
                     }),
                 };
                 ValidatorStoreRig::new(
@@ -122,3 +122,33 @@ fn invalid_unregistered_validator() {
     }
     .run()
 }
+
+#[test]
+fn check_without_insert() {
+    let dir = tempfile::tempdir().unwrap();
+    let slashing_db =
+        SlashingDatabase::create(&dir.path().join("slashing_protection.sqlite")).unwrap();
+    let pubkey = pubkey(DEFAULT_VALIDATOR_INDEX);
+    slashing_db.register_validator(pubkey).unwrap();
+
+    let first_block = block(1);
+    let check = |block: &BeaconBlockHeader| {
+        slashing_db.check_block_proposal_without_insert(&pubkey, block, DEFAULT_DOMAIN)
+    };
+
+    // Checking a block doesn't record it, so it can be checked repeatedly.
+    assert_eq!(check(&first_block), Ok(Safe::Valid));
+    assert_eq!(check(&first_block), Ok(Safe::Valid));
+
+    slashing_db
+        .check_and_insert_block_proposal(&pubkey, &first_block, DEFAULT_DOMAIN)
+        .unwrap();
+    assert_eq!(check(&first_block), Ok(Safe::SameData));
+    assert_eq!(
+        check(&block(1)),
+        Err(NotSafe::InvalidBlock(InvalidBlock::DoubleBlockProposal(
+            SignedBlock::from_header(&first_block, DEFAULT_DOMAIN)
+        )))
+    );
+    assert_eq!(check(&block(2)), Ok(Safe::Valid));
+}
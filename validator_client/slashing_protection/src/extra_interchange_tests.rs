@@ -3,6 +3,7 @@
 use crate::test_utils::pubkey;
 use crate::*;
 use tempfile::tempdir;
+use types::{Epoch, Slot};
 
 #[test]
 fn export_non_existent_key() {
@@ -73,3 +74,85 @@ fn export_same_key_twice() {
         export_double.minify().unwrap()
     );
 }
+
+#[test]
+fn prune_all_preserves_minimal_interchange() {
+    let dir = tempdir().unwrap();
+    let slashing_db_file = dir.path().join("slashing_protection.sqlite");
+    let slashing_db = SlashingDatabase::create(&slashing_db_file).unwrap();
+
+    let slots_per_epoch = 32;
+    let key1 = pubkey(1);
+    let key2 = pubkey(2);
+    slashing_db.register_validator(key1).unwrap();
+    slashing_db.register_validator(key2).unwrap();
+
+    // Validator 1 signs a block and attestation in each of the first 10 epochs, while validator 2
+    // stopped signing early on and is now disabled.
+    for epoch in 0..10 {
+        let root = Hash256::from_low_u64_be(epoch + 1).into();
+        let slot = Epoch::new(epoch).start_slot(slots_per_epoch);
+        slashing_db
+            .check_and_insert_block_signing_root(&key1, slot, root)
+            .unwrap();
+        slashing_db
+            .check_and_insert_attestation_signing_root(
+                &key1,
+                Epoch::new(epoch.saturating_sub(1)),
+                Epoch::new(epoch),
+                root,
+            )
+            .unwrap();
+    }
+    for i in 0..2 {
+        slashing_db
+            .check_and_insert_block_signing_root(&key2, Slot::new(i + 1), SigningRoot::default())
+            .unwrap();
+        slashing_db
+            .check_and_insert_attestation_signing_root(
+                &key2,
+                Epoch::new(0),
+                Epoch::new(i),
+                SigningRoot::default(),
+            )
+            .unwrap();
+    }
+    let key2_id = slashing_db.get_validator_id(&key2).unwrap();
+    slashing_db
+        .with_transaction(|txn| slashing_db.update_validator_status(txn, key2_id, false))
+        .unwrap();
+
+    assert_eq!(
+        slashing_db.max_attestation_target().unwrap(),
+        Some(Epoch::new(9))
+    );
+    let before = slashing_db
+        .export_interchange_info(Hash256::zero(), None)
+        .unwrap();
+
+    let summary = slashing_db
+        .prune_all(Epoch::new(10), 4, slots_per_epoch)
+        .unwrap();
+    assert_eq!(
+        summary,
+        PruneSummary {
+            blocks: 7,
+            attestations: 7,
+        }
+    );
+
+    let after = slashing_db
+        .export_interchange_info(Hash256::zero(), None)
+        .unwrap();
+    assert_eq!(after.data[0].signed_blocks.len(), 4);
+    assert_eq!(after.data[0].signed_attestations.len(), 4);
+    assert_eq!(after.data[1].signed_blocks.len(), 1);
+    assert_eq!(after.data[1].signed_attestations.len(), 1);
+    assert_eq!(before.minify().unwrap(), after.minify().unwrap());
+
+    // Pruning again is a no-op.
+    let summary = slashing_db
+        .prune_all(Epoch::new(10), 4, slots_per_epoch)
+        .unwrap();
+    assert_eq!(summary, PruneSummary::default());
+}
@@ -7,6 +7,7 @@ use crate::test_utils::*;
 use crate::*;
 use rayon::prelude::*;
 use tempfile::tempdir;
+use types::AttestationData;
 
 #[test]
 fn block_same_slot() {
@@ -80,3 +81,45 @@ fn attestation_surround_fest() {
     let num_successes = results.iter().filter(|res| res.is_ok()).count();
     assert_eq!(num_successes, 1);
 }
+
+#[test]
+fn attestation_batch_same_slot() {
+    let dir = tempdir().unwrap();
+    let slashing_db_file = dir.path().join("slashing_protection.sqlite");
+    let slashing_db = SlashingDatabase::create(&slashing_db_file).unwrap();
+
+    let num_validators = 10;
+    let pubkeys = (0..num_validators).map(pubkey).collect::<Vec<_>>();
+    slashing_db.register_validators(pubkeys.iter()).unwrap();
+
+    // One attestation from each validator, followed by a double vote from the first validator.
+    let att = attestation_data_builder(0, 1);
+    let double_vote = AttestationData {
+        beacon_block_root: Hash256::repeat_byte(1),
+        ..att.clone()
+    };
+    let batch = pubkeys
+        .iter()
+        .map(|pk| (pk, &att, DEFAULT_DOMAIN))
+        .chain(std::iter::once((&pubkeys[0], &double_vote, DEFAULT_DOMAIN)));
+    let results = slashing_db.check_and_insert_attestations(batch).unwrap();
+
+    assert_eq!(results.len(), num_validators + 1);
+    assert!(results[..num_validators]
+        .iter()
+        .all(|res| *res == Ok(Safe::Valid)));
+    assert!(matches!(
+        results[num_validators],
+        Err(NotSafe::InvalidAttestation(InvalidAttestation::DoubleVote(
+            _
+        )))
+    ));
+
+    // The safe attestations were committed, so signing them again is a no-op.
+    for pk in &pubkeys {
+        assert_eq!(
+            slashing_db.check_and_insert_attestation(pk, &att, DEFAULT_DOMAIN),
+            Ok(Safe::SameData)
+        );
+    }
+}
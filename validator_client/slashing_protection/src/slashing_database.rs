@@ -10,6 +10,7 @@ use r2d2_sqlite::SqliteConnectionManager;
 use rusqlite::{params, OptionalExtension, Transaction, TransactionBehavior};
 use std::fs::File;
 use std::path::Path;
+use std::sync::Arc;
 use std::time::Duration;
 use types::{AttestationData, BeaconBlockHeader, Epoch, Hash256, PublicKeyBytes, SignedRoot, Slot};
 
@@ -25,15 +26,72 @@ pub const CONNECTION_TIMEOUT: Duration = Duration::from_secs(5);
 #[cfg(test)]
 pub const CONNECTION_TIMEOUT: Duration = Duration::from_secs(1);
 
+/// How long a connection waits for a lock held by another connection before giving up.
+///
+/// Because of `locking_mode=EXCLUSIVE`, a process holds the lock from its first transaction until
+/// it closes the database, so other processes can't use the database while the validator client
+/// is running. This only lets a process which is starting up wait for another process which is
+/// about to finish with the database (e.g. an export), rather than failing immediately.
+#[cfg(not(test))]
+pub const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+#[cfg(test)]
+pub const BUSY_TIMEOUT: Duration = Duration::from_millis(100);
+
 /// Supported version of the interchange format.
 pub const SUPPORTED_INTERCHANGE_FORMAT_VERSION: u64 = 5;
 
 /// Column ID of the `validators.enabled` column.
 pub const VALIDATORS_ENABLED_CID: i64 = 2;
 
+/// The number of epochs of signing history kept by `SlashingDatabase::prune_all`.
+///
+/// Pruning is always safe because the latest block and attestation of each validator are kept, and
+/// act as low watermarks for anything signed in future. The validator client keeps only 512 epochs
+/// for the validators it is running, while this longer period (a little more than the weak
+/// subjectivity period) applies to validators which have been removed from it, whose history may
+/// still be wanted if they are exported or re-imported.
+pub const DEFAULT_RETENTION_EPOCHS: u64 = 4096;
+
+/// The number of records removed by `SlashingDatabase::prune_all`.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct PruneSummary {
+    pub blocks: usize,
+    pub attestations: usize,
+}
+
 #[derive(Debug, Clone)]
 pub struct SlashingDatabase {
     conn_pool: Pool,
+    /// Checkpoints the write-ahead log once the last clone of the database is dropped.
+    _checkpoint_on_drop: Arc<CheckpointOnDrop>,
+}
+
+/// Merges the write-ahead log into the main database file when dropped, so that the main file
+/// holds every record once the validator client has shut down.
+#[derive(Debug)]
+struct CheckpointOnDrop {
+    conn_pool: Pool,
+}
+
+impl Drop for CheckpointOnDrop {
+    fn drop(&mut self) {
+        if let Ok(conn) = self.conn_pool.get() {
+            let _ = wal_checkpoint(&conn);
+        }
+    }
+}
+
+/// Merge the write-ahead log into the main database file and truncate the log.
+fn wal_checkpoint(conn: &rusqlite::Connection) -> Result<(), NotSafe> {
+    let busy: i64 = conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", params![], |row| {
+        row.get(0)
+    })?;
+    if busy != 0 {
+        return Err(NotSafe::SQLError(
+            "Unable to checkpoint the write-ahead log, database is busy".to_string(),
+        ));
+    }
+    Ok(())
 }
 
 impl SlashingDatabase {
@@ -96,20 +154,40 @@ impl SlashingDatabase {
         let txn = conn.transaction()?;
         Self::apply_schema_migrations(&txn)?;
         txn.commit()?;
+        drop(conn);
 
-        Ok(Self { conn_pool })
+        Ok(Self::from_conn_pool(conn_pool))
     }
 
     /// Open an existing `SlashingDatabase` from disk.
     ///
-    /// This will automatically check for and apply the latest schema migrations.
+    /// This will automatically check for and apply the latest schema migrations, and merge any
+    /// records left in the write-ahead log by an unclean shutdown into the main database file.
     pub fn open(path: &Path) -> Result<Self, NotSafe> {
         let conn_pool = Self::open_conn_pool(path)?;
-        let db = Self { conn_pool };
+        let db = Self::from_conn_pool(conn_pool);
+        db.checkpoint()?;
         db.with_transaction(Self::apply_schema_migrations)?;
         Ok(db)
     }
 
+    fn from_conn_pool(conn_pool: Pool) -> Self {
+        Self {
+            _checkpoint_on_drop: Arc::new(CheckpointOnDrop {
+                conn_pool: conn_pool.clone(),
+            }),
+            conn_pool,
+        }
+    }
+
+    /// Merge the write-ahead log into the main database file and truncate the log.
+    ///
+    /// This happens automatically when the database is opened and when it is closed.
+    pub fn checkpoint(&self) -> Result<(), NotSafe> {
+        let conn = self.conn_pool.get()?;
+        wal_checkpoint(&conn)
+    }
+
     fn apply_schema_migrations(txn: &Transaction) -> Result<(), NotSafe> {
         // Add the `enabled` column to the `validators` table if it does not already exist.
         let enabled_col_exists = txn
@@ -161,9 +239,18 @@ impl SlashingDatabase {
     /// to serialise all DB access (to prevent slashable data being checked and signed in parallel).
     /// The exclusive locking mode also has the benefit of applying to other processes, so multiple
     /// Lighthouse processes trying to access the same database will also be blocked.
+    ///
+    /// The database is written through a write-ahead log, which makes each commit a single
+    /// append (and `fsync`) rather than a rewrite of the modified pages via a rollback journal.
+    /// Because the locking mode is set first, SQLite keeps the WAL index in heap memory and never
+    /// creates a shared memory file.
     fn apply_pragmas(conn: &mut rusqlite::Connection) -> Result<(), rusqlite::Error> {
         conn.pragma_update(None, "foreign_keys", true)?;
         conn.pragma_update(None, "locking_mode", "EXCLUSIVE")?;
+        conn.busy_timeout(BUSY_TIMEOUT)?;
+        conn.pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get::<_, String>(0))?;
+        // Every commit must be durable before the message it protects is signed.
+        conn.pragma_update(None, "synchronous", "FULL")?;
         Ok(())
     }
 
@@ -641,6 +728,37 @@ impl SlashingDatabase {
         Ok(safe)
     }
 
+    /// Check a batch of attestations for slash safety, recording those which are safe.
+    ///
+    /// All attestations are checked and inserted within a single exclusive transaction, so signing
+    /// the attestations of many validators in the same slot only takes the write lock and syncs
+    /// to disk once. Attestations are processed in order, so a later attestation is checked
+    /// against any earlier attestation from the same batch.
+    ///
+    /// Returns one result per attestation, or an error if the batch couldn't be committed, in
+    /// which case none of the attestations are safe to sign.
+    pub fn check_and_insert_attestations<'a>(
+        &self,
+        attestations: impl IntoIterator<Item = (&'a PublicKeyBytes, &'a AttestationData, Hash256)>,
+    ) -> Result<Vec<Result<Safe, NotSafe>>, NotSafe> {
+        let mut conn = self.conn_pool.get()?;
+        let txn = conn.transaction_with_behavior(TransactionBehavior::Exclusive)?;
+        let results = attestations
+            .into_iter()
+            .map(|(validator_pubkey, attestation, domain)| {
+                self.check_and_insert_attestation_signing_root_txn(
+                    validator_pubkey,
+                    attestation.source.epoch,
+                    attestation.target.epoch,
+                    attestation.signing_root(domain).into(),
+                    &txn,
+                )
+            })
+            .collect();
+        txn.commit()?;
+        Ok(results)
+    }
+
     /// Transactional variant of `check_and_insert_attestation_signing_root`.
     fn check_and_insert_attestation_signing_root_txn(
         &self,
@@ -670,6 +788,46 @@ impl SlashingDatabase {
         Ok(safe)
     }
 
+    /// Check a block proposal for slash safety *without* recording it in the database.
+    ///
+    /// The verdict is advisory only: it may be invalidated by a block signed after the check.
+    pub fn check_block_proposal_without_insert(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        block_header: &BeaconBlockHeader,
+        domain: Hash256,
+    ) -> Result<Safe, NotSafe> {
+        let mut conn = self.conn_pool.get()?;
+        let txn = conn.transaction()?;
+        self.check_block_proposal(
+            &txn,
+            validator_pubkey,
+            block_header.slot,
+            block_header.signing_root(domain).into(),
+        )
+    }
+
+    /// Check an attestation for slash safety *without* recording it in the database.
+    ///
+    /// The verdict is advisory only: it may be invalidated by an attestation signed after the
+    /// check.
+    pub fn check_attestation_without_insert(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        attestation: &AttestationData,
+        domain: Hash256,
+    ) -> Result<Safe, NotSafe> {
+        let mut conn = self.conn_pool.get()?;
+        let txn = conn.transaction()?;
+        self.check_attestation(
+            &txn,
+            validator_pubkey,
+            attestation.source.epoch,
+            attestation.target.epoch,
+            attestation.signing_root(domain).into(),
+        )
+    }
+
     /// Import slashing protection from another client in the interchange format.
     ///
     /// This function will atomically import the entire interchange, failing if *any*
@@ -1012,6 +1170,59 @@ impl SlashingDatabase {
         Ok(())
     }
 
+    /// Return the greatest attestation target epoch signed by any validator in the database.
+    pub fn max_attestation_target(&self) -> Result<Option<Epoch>, NotSafe> {
+        let mut conn = self.conn_pool.get()?;
+        let txn = conn.transaction()?;
+        let max_target = txn
+            .prepare("SELECT MAX(target_epoch) FROM signed_attestations")?
+            .query_row(params![], |row| row.get(0))?;
+        Ok(max_target)
+    }
+
+    /// Remove the blocks and attestations of every validator in the database (including disabled
+    /// validators) which are more than `retention_epochs` older than `current_epoch`.
+    ///
+    /// Like the other pruning functions this never removes a validator's latest block or
+    /// attestation, so a minimal interchange export remains complete and the database continues
+    /// to protect against signing anything slashable.
+    pub fn prune_all(
+        &self,
+        current_epoch: Epoch,
+        retention_epochs: u64,
+        slots_per_epoch: u64,
+    ) -> Result<PruneSummary, NotSafe> {
+        let new_min_target = current_epoch.saturating_sub(retention_epochs);
+        let new_min_slot = new_min_target.start_slot(slots_per_epoch);
+
+        let mut conn = self.conn_pool.get()?;
+        let txn = conn.transaction_with_behavior(TransactionBehavior::Exclusive)?;
+        let blocks = txn.execute(
+            "DELETE FROM signed_blocks
+             WHERE
+                slot < ?1 AND
+                slot < (SELECT MAX(b.slot)
+                        FROM signed_blocks AS b
+                        WHERE b.validator_id = signed_blocks.validator_id)",
+            params![new_min_slot],
+        )?;
+        let attestations = txn.execute(
+            "DELETE FROM signed_attestations
+             WHERE
+                target_epoch < ?1 AND
+                target_epoch < (SELECT MAX(a.target_epoch)
+                                FROM signed_attestations AS a
+                                WHERE a.validator_id = signed_attestations.validator_id)",
+            params![new_min_target],
+        )?;
+        txn.commit()?;
+
+        Ok(PruneSummary {
+            blocks,
+            attestations,
+        })
+    }
+
     pub fn num_validator_rows(&self) -> Result<u32, NotSafe> {
         let mut conn = self.conn_pool.get()?;
         let txn = conn.transaction()?;
@@ -1229,6 +1440,12 @@ mod tests {
                     .to_uppercase(),
                 "EXCLUSIVE"
             );
+            assert_eq!(
+                conn.pragma_query_value(None, "journal_mode", |row| { row.get::<_, String>(0) })
+                    .unwrap()
+                    .to_uppercase(),
+                "WAL"
+            );
         };
 
         let db1 = SlashingDatabase::create(&file).unwrap();
@@ -1238,6 +1455,28 @@ mod tests {
         check(&db2);
     }
 
+    // The write-ahead log is merged into the main file once the last clone is dropped.
+    #[test]
+    fn wal_checkpointed_on_drop() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("db.sqlite");
+        let wal_file = dir.path().join("db.sqlite-wal");
+        let wal_len = || std::fs::metadata(&wal_file).map_or(0, |metadata| metadata.len());
+
+        let db = SlashingDatabase::create(&file).unwrap();
+        db.register_validator(PublicKeyBytes::empty()).unwrap();
+        assert!(wal_len() > 0);
+
+        let db_clone = db.clone();
+        drop(db);
+        assert!(wal_len() > 0);
+        drop(db_clone);
+        assert_eq!(wal_len(), 0);
+
+        let db = SlashingDatabase::open(&file).unwrap();
+        assert_eq!(db.num_validator_rows().unwrap(), 1);
+    }
+
     #[test]
     fn test_transaction_failure() {
         let dir = tempdir().unwrap();
@@ -1,4 +1,4 @@
-use crate::beacon_node_fallback::{ApiTopic, BeaconNodeFallback, RequireSynced};
+use crate::beacon_node_fallback::{ApiTopic, BeaconNodeFallback, RequestClass, RequireSynced};
 use crate::{
     duties_service::{DutiesService, DutyAndProof},
     http_metrics::metrics,
@@ -6,13 +6,16 @@ use crate::{
     OfflineOnFailure,
 };
 use environment::RuntimeContext;
+use eth2::types::{EventKind, EventTopic};
 use futures::future::join_all;
+use futures::StreamExt;
 use slog::{crit, debug, error, info, trace, warn};
 use slot_clock::SlotClock;
 use std::collections::HashMap;
 use std::ops::Deref;
 use std::sync::Arc;
-use tokio::time::{sleep, sleep_until, Duration, Instant};
+use tokio::sync::watch;
+use tokio::time::{sleep, sleep_until, timeout_at, Duration, Instant};
 use tree_hash::TreeHash;
 use types::{Attestation, AttestationData, ChainSpec, CommitteeIndex, EthSpec, Slot};
 
@@ -23,6 +26,7 @@ pub struct AttestationServiceBuilder<T: SlotClock + 'static, E: EthSpec> {
     slot_clock: Option<T>,
     beacon_nodes: Option<Arc<BeaconNodeFallback<T, E>>>,
     context: Option<RuntimeContext<E>>,
+    early_attestation_min_delay: Option<Duration>,
 }
 
 impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
@@ -33,6 +37,7 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
             slot_clock: None,
             beacon_nodes: None,
             context: None,
+            early_attestation_min_delay: None,
         }
     }
 
@@ -61,6 +66,13 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
         self
     }
 
+    /// Attest as soon as the head block of the slot is observed, but no earlier than `min_delay`
+    /// into the slot.
+    pub fn early_attestations(mut self, min_delay: Option<Duration>) -> Self {
+        self.early_attestation_min_delay = min_delay;
+        self
+    }
+
     pub fn build(self) -> Result<AttestationService<T, E>, String> {
         Ok(AttestationService {
             inner: Arc::new(Inner {
@@ -79,6 +91,7 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
                 context: self
                     .context
                     .ok_or("Cannot build AttestationService without runtime_context")?,
+                early_attestation_min_delay: self.early_attestation_min_delay,
             }),
         })
     }
@@ -91,6 +104,7 @@ pub struct Inner<T, E: EthSpec> {
     slot_clock: T,
     beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
     context: RuntimeContext<E>,
+    early_attestation_min_delay: Option<Duration>,
 }
 
 /// Attempts to produce attestations for all known validators 1/3rd of the way through each slot.
@@ -98,6 +112,10 @@ pub struct Inner<T, E: EthSpec> {
 /// If any validators are on the same committee, a single attestation will be downloaded and
 /// returned to the beacon node. This attestation will have a signature from each of the
 /// validators.
+///
+/// If early attestations are enabled, attestations are produced as soon as a beacon node reports
+/// a head block for the slot instead, provided the configured minimum delay into the slot has
+/// passed.
 pub struct AttestationService<T, E: EthSpec> {
     inner: Arc<Inner<T, E>>,
 }
@@ -137,10 +155,19 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
 
         let executor = self.context.executor.clone();
 
+        let mut head_slot_rx = self
+            .early_attestation_min_delay
+            .map(|_| self.spawn_head_monitor(slot_duration));
+
         let interval_fut = async move {
             loop {
                 if let Some(duration_to_next_slot) = self.slot_clock.duration_to_next_slot() {
-                    sleep(duration_to_next_slot + slot_duration / 3).await;
+                    self.wait_for_attestation_time(
+                        duration_to_next_slot,
+                        slot_duration,
+                        head_slot_rx.as_mut(),
+                    )
+                    .await;
                     let log = self.context.log();
 
                     if let Err(e) = self.spawn_attestation_tasks(slot_duration) {
@@ -168,6 +195,119 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
         Ok(())
     }
 
+    /// Waits until attestations for the next slot should be produced, which is 1/3rd of the way
+    /// through the slot plus any distributed duty delay.
+    ///
+    /// If early attestations are enabled, this returns as soon as the head block of the slot has
+    /// been observed on `head_slot_rx`, but never before the minimum delay into the slot.
+    async fn wait_for_attestation_time(
+        &self,
+        duration_to_next_slot: Duration,
+        slot_duration: Duration,
+        head_slot_rx: Option<&mut watch::Receiver<Slot>>,
+    ) {
+        let attestation_delay = slot_duration / 3 + self.duties_service.distributed_duty_delay;
+        let deadline = Instant::now() + duration_to_next_slot + attestation_delay;
+
+        let (Some(min_delay), Some(head_slot_rx), Some(current_slot)) = (
+            self.early_attestation_min_delay,
+            head_slot_rx,
+            self.slot_clock.now(),
+        ) else {
+            sleep_until(deadline).await;
+            return;
+        };
+        let next_slot = current_slot + 1;
+
+        sleep(duration_to_next_slot + min_delay.min(attestation_delay)).await;
+        let head_observed = timeout_at(deadline, async {
+            head_slot_rx
+                .wait_for(|head_slot| *head_slot >= next_slot)
+                .await
+                .is_ok()
+        })
+        .await;
+
+        if matches!(head_observed, Ok(true)) {
+            debug!(
+                self.context.log(),
+                "Head block observed, attesting early";
+                "slot" => next_slot,
+            );
+        } else {
+            // Either the deadline has been reached or the head monitor has stopped.
+            sleep_until(deadline).await;
+        }
+    }
+
+    /// Spawns a task which follows the head events of the beacon nodes and publishes the slot of
+    /// the latest head block on the returned channel.
+    ///
+    /// If the event stream fails it is re-established after a slot, in the meantime attestations
+    /// are produced at the usual time.
+    fn spawn_head_monitor(&self, slot_duration: Duration) -> watch::Receiver<Slot> {
+        let (head_slot_tx, head_slot_rx) = watch::channel(Slot::new(0));
+        let beacon_nodes = self.beacon_nodes.clone();
+        let log = self.context.log().clone();
+
+        let future = async move {
+            loop {
+                let stream_result = beacon_nodes
+                    .first_success(
+                        RequireSynced::Yes,
+                        OfflineOnFailure::No,
+                        |beacon_node| async move {
+                            beacon_node.get_events::<E>(&[EventTopic::Head]).await
+                        },
+                    )
+                    .await;
+
+                match stream_result {
+                    Ok(stream) => {
+                        tokio::pin!(stream);
+                        while let Some(event) = stream.next().await {
+                            match event {
+                                Ok(EventKind::Head(head)) => {
+                                    head_slot_tx.send_if_modified(|head_slot| {
+                                        let is_newer = head.slot > *head_slot;
+                                        if is_newer {
+                                            *head_slot = head.slot;
+                                        }
+                                        is_newer
+                                    });
+                                }
+                                Ok(_) => (),
+                                Err(e) => {
+                                    warn!(
+                                        log,
+                                        "Head event stream failed";
+                                        "error" => ?e,
+                                    );
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        warn!(
+                            log,
+                            "Unable to subscribe to head events";
+                            "msg" => "attestations will not be produced early",
+                            "error" => %e,
+                        );
+                    }
+                }
+
+                sleep(slot_duration).await;
+            }
+        };
+
+        self.context
+            .executor
+            .spawn(future, "attestation_head_monitor");
+        head_slot_rx
+    }
+
     /// For each each required attestation, spawn a new task that downloads, signs and uploads the
     /// attestation to the beacon node.
     fn spawn_attestation_tasks(&self, slot_duration: Duration) -> Result<(), String> {
@@ -357,58 +497,75 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
             .await
             .map_err(|e| e.to_string())?;
 
-        // Create futures to produce signed `Attestation` objects.
-        let attestation_data_ref = &attestation_data;
-        let signing_futures = validator_duties.iter().map(|duty_and_proof| async move {
-            let duty = &duty_and_proof.duty;
-            let attestation_data = attestation_data_ref;
-
-            // Ensure that the attestation matches the duties.
-            if !duty.match_attestation_data::<E>(attestation_data, &self.context.eth2_config.spec) {
-                crit!(
-                    log,
-                    "Inconsistent validator duties during signing";
-                    "validator" => ?duty.pubkey,
-                    "duty_slot" => duty.slot,
-                    "attestation_slot" => attestation_data.slot,
-                    "duty_index" => duty.committee_index,
-                    "attestation_index" => attestation_data.index,
-                );
-                return None;
-            }
+        // Create an unsigned `Attestation` for each validator.
+        let mut unsigned_attestations = validator_duties
+            .iter()
+            .filter_map(|duty_and_proof| {
+                let duty = &duty_and_proof.duty;
 
-            let mut attestation = match Attestation::<E>::empty_for_signing(
-                duty.committee_index,
-                duty.committee_length as usize,
-                attestation_data.slot,
-                attestation_data.beacon_block_root,
-                attestation_data.source,
-                attestation_data.target,
-                &self.context.eth2_config.spec,
-            ) {
-                Ok(attestation) => attestation,
-                Err(err) => {
+                // Ensure that the attestation matches the duties.
+                if !duty
+                    .match_attestation_data::<E>(&attestation_data, &self.context.eth2_config.spec)
+                {
                     crit!(
                         log,
-                        "Invalid validator duties during signing";
+                        "Inconsistent validator duties during signing";
                         "validator" => ?duty.pubkey,
-                        "duty" => ?duty,
-                        "err" => ?err,
+                        "duty_slot" => duty.slot,
+                        "attestation_slot" => attestation_data.slot,
+                        "duty_index" => duty.committee_index,
+                        "attestation_index" => attestation_data.index,
                     );
                     return None;
                 }
-            };
 
-            match self
-                .validator_store
-                .sign_attestation(
-                    duty.pubkey,
-                    duty.validator_committee_index as usize,
-                    &mut attestation,
-                    current_epoch,
-                )
-                .await
-            {
+                let attestation = match Attestation::<E>::empty_for_signing(
+                    duty.committee_index,
+                    duty.committee_length as usize,
+                    attestation_data.slot,
+                    attestation_data.beacon_block_root,
+                    attestation_data.source,
+                    attestation_data.target,
+                    &self.context.eth2_config.spec,
+                ) {
+                    Ok(attestation) => attestation,
+                    Err(err) => {
+                        crit!(
+                            log,
+                            "Invalid validator duties during signing";
+                            "validator" => ?duty.pubkey,
+                            "duty" => ?duty,
+                            "err" => ?err,
+                        );
+                        return None;
+                    }
+                };
+
+                Some((duty, attestation))
+            })
+            .collect::<Vec<_>>();
+
+        // Sign all of the attestations together, collecting any successful results.
+        let results = self
+            .validator_store
+            .sign_attestations(
+                unsigned_attestations
+                    .iter_mut()
+                    .map(|(duty, attestation)| {
+                        (
+                            duty.pubkey,
+                            duty.validator_committee_index as usize,
+                            attestation,
+                        )
+                    })
+                    .collect(),
+                current_epoch,
+            )
+            .await;
+        let (ref attestations, ref validator_indices): (Vec<_>, Vec<_>) = unsigned_attestations
+            .into_iter()
+            .zip(results)
+            .filter_map(|((duty, attestation), result)| match result {
                 Ok(()) => Some((attestation, duty.validator_index)),
                 Err(ValidatorStoreError::UnknownPubkey(pubkey)) => {
                     // A pubkey can be missing when a validator was recently
@@ -424,6 +581,64 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
                     );
                     None
                 }
+                Err(ValidatorStoreError::SignerQuarantined(pubkey)) => {
+                    // The quarantine is already alerted by the signer health service.
+                    debug!(
+                        log,
+                        "Not signing attestation";
+                        "reason" => "remote signer quarantined",
+                        "validator" => ?pubkey,
+                        "committee_index" => committee_index,
+                        "slot" => slot.as_u64(),
+                    );
+                    None
+                }
+                Err(ValidatorStoreError::HandedOver(pubkey)) => {
+                    debug!(
+                        log,
+                        "Not signing attestation";
+                        "reason" => "validator handed over to another validator client",
+                        "validator" => ?pubkey,
+                        "committee_index" => committee_index,
+                        "slot" => slot.as_u64(),
+                    );
+                    None
+                }
+                Err(ValidatorStoreError::Suspended(pubkey)) => {
+                    debug!(
+                        log,
+                        "Not signing attestation";
+                        "reason" => "validator suspended",
+                        "validator" => ?pubkey,
+                        "committee_index" => committee_index,
+                        "slot" => slot.as_u64(),
+                    );
+                    None
+                }
+                Err(ValidatorStoreError::RemoteSlashingProtection(reason)) => {
+                    warn!(
+                        log,
+                        "Remote signer refused to sign attestation";
+                        "reason" => reason,
+                        "validator" => ?duty.pubkey,
+                        "committee_index" => committee_index,
+                        "slot" => slot.as_u64(),
+                    );
+                    None
+                }
+                // In a distributed validator cluster the signer may fail to produce some
+                // signatures, e.g. if a threshold of the cluster could not be reached.
+                Err(e) if self.duties_service.distributed => {
+                    warn!(
+                        log,
+                        "Failed to sign attestation";
+                        "error" => ?e,
+                        "validator" => ?duty.pubkey,
+                        "committee_index" => committee_index,
+                        "slot" => slot.as_u64(),
+                    );
+                    None
+                }
                 Err(e) => {
                     crit!(
                         log,
@@ -435,14 +650,7 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
                     );
                     None
                 }
-            }
-        });
-
-        // Execute all the futures in parallel, collecting any successful results.
-        let (ref attestations, ref validator_indices): (Vec<_>, Vec<_>) = join_all(signing_futures)
-            .await
-            .into_iter()
-            .flatten()
+            })
             .unzip();
 
         if attestations.is_empty() {
@@ -637,7 +845,8 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
             let signed_aggregate_and_proofs_slice = signed_aggregate_and_proofs.as_slice();
             match self
                 .beacon_nodes
-                .first_success(
+                .first_success_for(
+                    RequestClass::Attestations,
                     RequireSynced::No,
                     OfflineOnFailure::Yes,
                     |beacon_node| async move {
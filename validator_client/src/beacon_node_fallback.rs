@@ -3,22 +3,30 @@
 //! succeed.
 
 use crate::check_synced::check_synced;
-use crate::http_metrics::metrics::{inc_counter_vec, ENDPOINT_ERRORS, ENDPOINT_REQUESTS};
+use crate::http_metrics::metrics::{
+    inc_counter_vec, set_float_gauge_vec, set_gauge, BEACON_NODES_DIVERGENT, ENDPOINT_ERRORS,
+    ENDPOINT_HEALTH, ENDPOINT_REQUESTS,
+};
 use environment::RuntimeContext;
+use eth2::types::GenesisData;
 use eth2::BeaconNodeHttpClient;
 use futures::future;
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
-use slog::{debug, error, info, warn, Logger};
+use slog::{crit, debug, error, info, warn, Logger};
 use slot_clock::SlotClock;
+use std::collections::HashMap;
 use std::fmt;
 use std::fmt::Debug;
 use std::future::Future;
 use std::marker::PhantomData;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use strum::{EnumString, EnumVariantNames};
 use tokio::{sync::RwLock, time::sleep};
-use types::{ChainSpec, Config, EthSpec};
+use types::{ChainSpec, Config, Epoch, EthSpec, ForkName, Hash256};
 
 /// Message emitted when the VC detects the BN is using a different spec.
 const UPDATE_REQUIRED_LOG_HINT: &str = "this VC or the remote BN may need updating";
@@ -32,6 +40,13 @@ const UPDATE_REQUIRED_LOG_HINT: &str = "this VC or the remote BN may need updati
 /// having the correct nodes up and running prior to the start of the slot.
 const SLOT_LOOKAHEAD: Duration = Duration::from_secs(2);
 
+/// The weight given to the outcome of the most recent request when updating the health of a
+/// candidate.
+const HEALTH_SMOOTHING_FACTOR: f64 = 0.2;
+
+/// The lowest health score used when weighting candidates, to avoid dividing by zero.
+const MIN_HEALTH_SCORE: f64 = 0.01;
+
 /// Indicates a measurement of latency between the VC and a BN.
 pub struct LatencyMeasurement {
     /// An identifier for the beacon node (e.g. the URL).
@@ -140,6 +155,158 @@ impl<T> Errors<T> {
     }
 }
 
+/// Strategies for choosing the order in which candidates are tried by `BeaconNodeFallback`.
+///
+/// Regardless of the strategy, synced candidates are always tried before unsynced or offline ones.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Deserialize, Serialize, EnumString, EnumVariantNames,
+)]
+#[strum(serialize_all = "kebab-case")]
+pub enum BeaconNodeSelection {
+    /// Try candidates in the order they were configured.
+    #[default]
+    Ordered,
+    /// Prefer candidates with the lowest recent latency, weighted by their health score.
+    LatencyWeighted,
+    /// Keep using the last candidate to succeed until it fails, rather than returning to the
+    /// first configured candidate.
+    StickyPrimary,
+    /// Rotate the first candidate tried for each request made via `first_success`. Publications
+    /// made via `request` use the configured order.
+    RoundRobinReads,
+}
+
+/// The classes of request which may be routed to a subset of the configured beacon nodes.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize, EnumString, EnumVariantNames,
+)]
+#[strum(serialize_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub enum RequestClass {
+    /// Producing and publishing blocks.
+    Blocks,
+    /// Publishing attestations and aggregates.
+    Attestations,
+    /// Publishing sync committee messages and contributions.
+    SyncCommittee,
+    /// Subscribing to subnets and preparing proposers.
+    Subscriptions,
+    /// Querying attester, proposer and sync committee duties.
+    Duties,
+}
+
+impl From<ApiTopic> for RequestClass {
+    fn from(topic: ApiTopic) -> Self {
+        match topic {
+            ApiTopic::Attestations => RequestClass::Attestations,
+            ApiTopic::Blocks => RequestClass::Blocks,
+            ApiTopic::Subscriptions => RequestClass::Subscriptions,
+            ApiTopic::SyncCommittee => RequestClass::SyncCommittee,
+        }
+    }
+}
+
+/// Restricts classes of request to the beacon nodes at the given indices, in the order the beacon
+/// nodes were configured. Classes without a route may be sent to any beacon node.
+///
+/// Parsed from a comma-separated list of `CLASS=INDEX[:INDEX...]` entries, e.g.
+/// `blocks=0,duties=1:2`.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct BeaconNodeRoutes(HashMap<RequestClass, Vec<usize>>);
+
+impl BeaconNodeRoutes {
+    /// Returns the indices of the beacon nodes which may serve `class`, or `None` if any may.
+    pub fn route(&self, class: RequestClass) -> Option<&[usize]> {
+        self.0.get(&class).map(Vec::as_slice)
+    }
+
+    /// Returns the highest beacon node index used by any route.
+    pub fn max_index(&self) -> Option<usize> {
+        self.0.values().flatten().copied().max()
+    }
+}
+
+impl FromStr for BeaconNodeRoutes {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut routes = HashMap::new();
+        for entry in s
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+        {
+            let (class, indices) = entry
+                .split_once('=')
+                .ok_or_else(|| format!("Beacon node route must be CLASS=INDICES: {entry}"))?;
+            let class = class
+                .trim()
+                .parse::<RequestClass>()
+                .map_err(|_| format!("Unknown request class to route: {class}"))?;
+            let indices = indices
+                .split(':')
+                .map(|index| {
+                    index
+                        .trim()
+                        .parse::<usize>()
+                        .map_err(|_| format!("Invalid beacon node index in route: {index}"))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            if routes.insert(class, indices).is_some() {
+                return Err(format!("Duplicate beacon node route for {entry}"));
+            }
+        }
+        Ok(Self(routes))
+    }
+}
+
+/// A summary of the outcomes of recent requests to a candidate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CandidateHealth {
+    /// Exponentially weighted moving average of request successes, between 0 and 1.
+    pub score: f64,
+    /// Exponentially weighted moving average of the latency of successful requests.
+    pub latency: Option<Duration>,
+}
+
+impl Default for CandidateHealth {
+    fn default() -> Self {
+        Self {
+            score: 1.0,
+            latency: None,
+        }
+    }
+}
+
+impl CandidateHealth {
+    fn record_success(&mut self, latency: Duration) {
+        self.score = smooth(self.score, 1.0);
+        self.latency = Some(match self.latency {
+            Some(previous) => {
+                Duration::from_secs_f64(smooth(previous.as_secs_f64(), latency.as_secs_f64()))
+            }
+            None => latency,
+        });
+    }
+
+    fn record_failure(&mut self) {
+        self.score = smooth(self.score, 0.0);
+    }
+
+    /// The expected cost of sending a request to the candidate, where lower is better.
+    ///
+    /// Candidates without a latency measurement are the most expensive.
+    fn cost(&self) -> f64 {
+        self.latency.map_or(f64::INFINITY, |latency| {
+            latency.as_secs_f64() / self.score.max(MIN_HEALTH_SCORE)
+        })
+    }
+}
+
+fn smooth(previous: f64, latest: f64) -> f64 {
+    previous * (1.0 - HEALTH_SMOOTHING_FACTOR) + latest * HEALTH_SMOOTHING_FACTOR
+}
+
 /// Reasons why a candidate might not be ready.
 #[derive(Debug, Clone, Copy)]
 pub enum CandidateError {
@@ -149,11 +316,35 @@ pub enum CandidateError {
     NotSynced,
 }
 
+/// A response from a beacon node, with the `ETag` used to revalidate it.
+#[derive(Debug, Clone)]
+struct CachedResponse<T> {
+    etag: Option<String>,
+    data: T,
+}
+
+/// The network and fork schedule reported by a beacon node.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkView {
+    pub genesis_fork_version: [u8; 4],
+    pub fork_epochs: Vec<(ForkName, Option<Epoch>)>,
+    pub genesis_validators_root: Option<Hash256>,
+}
+
 /// Represents a `BeaconNodeHttpClient` inside a `BeaconNodeFallback` that may or may not be used
 /// for a query.
 pub struct CandidateBeaconNode<E> {
     beacon_node: BeaconNodeHttpClient,
     status: RwLock<Result<(), CandidateError>>,
+    health: Mutex<CandidateHealth>,
+    /// The most recent `config/spec` response, which is revalidated rather than re-downloaded.
+    config_cache: Mutex<Option<CachedResponse<Config>>>,
+    /// The most recent `beacon/genesis` response, which is revalidated rather than re-downloaded.
+    genesis_cache: Mutex<Option<CachedResponse<GenesisData>>>,
+    network_view: Mutex<Option<NetworkView>>,
+    /// The number of times the node has been found to be online after being offline or unchecked,
+    /// e.g. after a restart which lost the state provided by the validator client.
+    connections: AtomicU64,
     _phantom: PhantomData<E>,
 }
 
@@ -163,10 +354,37 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
         Self {
             beacon_node,
             status: RwLock::new(Err(CandidateError::Uninitialized)),
+            health: Mutex::new(CandidateHealth::default()),
+            config_cache: Mutex::new(None),
+            genesis_cache: Mutex::new(None),
+            network_view: Mutex::new(None),
+            connections: AtomicU64::new(0),
             _phantom: PhantomData,
         }
     }
 
+    /// Returns the health of `self`, based on the outcomes of recent requests.
+    pub fn health(&self) -> CandidateHealth {
+        *self.health.lock()
+    }
+
+    /// Returns the network and fork schedule most recently reported by `self`.
+    pub fn network_view(&self) -> Option<NetworkView> {
+        self.network_view.lock().clone()
+    }
+
+    fn record_success(&self, latency: Duration) {
+        let mut health = self.health.lock();
+        health.record_success(latency);
+        set_float_gauge_vec(&ENDPOINT_HEALTH, &[self.beacon_node.as_ref()], health.score);
+    }
+
+    fn record_failure(&self) {
+        let mut health = self.health.lock();
+        health.record_failure();
+        set_float_gauge_vec(&ENDPOINT_HEALTH, &[self.beacon_node.as_ref()], health.score);
+    }
+
     /// Returns the status of `self`.
     ///
     /// If `RequiredSynced::No`, any `NotSynced` node will be ignored and mapped to `Ok(())`.
@@ -188,19 +406,26 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
         &self,
         slot_clock: Option<&T>,
         spec: &ChainSpec,
+        genesis_validators_root: Option<Hash256>,
         log: &Logger,
     ) -> Result<(), CandidateError> {
         let previous_status = self.status(RequireSynced::Yes).await;
         let was_offline = matches!(previous_status, Err(CandidateError::Offline));
+        let was_unchecked = matches!(previous_status, Err(CandidateError::Uninitialized));
 
         let new_status = if let Err(e) = self.is_online(was_offline, log).await {
             Err(e)
-        } else if let Err(e) = self.is_compatible(spec, log).await {
-            Err(e)
-        } else if let Err(e) = self.is_synced(slot_clock, log).await {
-            Err(e)
         } else {
-            Ok(())
+            if was_offline || was_unchecked {
+                self.connections.fetch_add(1, Ordering::Relaxed);
+            }
+            if let Err(e) = self.is_compatible(spec, genesis_validators_root, log).await {
+                Err(e)
+            } else if let Err(e) = self.is_synced(slot_clock, log).await {
+                Err(e)
+            } else {
+                Ok(())
+            }
         };
 
         // In case of concurrent use, the latest value will always be used. It's possible that a
@@ -244,22 +469,78 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
         }
     }
 
-    /// Checks if the node has the correct specification.
-    async fn is_compatible(&self, spec: &ChainSpec, log: &Logger) -> Result<(), CandidateError> {
-        let config = self
+    /// Returns the node's `config/spec`, revalidating any cached response.
+    async fn config_spec(&self) -> Result<Config, eth2::Error> {
+        let etag = self
+            .config_cache
+            .lock()
+            .as_ref()
+            .and_then(|cached| cached.etag.clone());
+        match self
             .beacon_node
-            .get_config_spec::<Config>()
-            .await
-            .map_err(|e| {
-                error!(
-                    log,
-                    "Unable to read spec from beacon node";
-                    "error" => %e,
-                    "endpoint" => %self.beacon_node,
-                );
-                CandidateError::Offline
-            })?
-            .data;
+            .get_config_spec_if_none_match::<Config>(etag.as_deref())
+            .await?
+        {
+            Some((response, etag)) => {
+                *self.config_cache.lock() = Some(CachedResponse {
+                    etag,
+                    data: response.data.clone(),
+                });
+                Ok(response.data)
+            }
+            None => self
+                .config_cache
+                .lock()
+                .as_ref()
+                .map(|cached| cached.data.clone())
+                .ok_or(eth2::Error::StatusCode(eth2::StatusCode::NOT_MODIFIED)),
+        }
+    }
+
+    /// Returns the node's `beacon/genesis`, revalidating any cached response.
+    async fn genesis(&self) -> Result<GenesisData, eth2::Error> {
+        let etag = self
+            .genesis_cache
+            .lock()
+            .as_ref()
+            .and_then(|cached| cached.etag.clone());
+        match self
+            .beacon_node
+            .get_beacon_genesis_if_none_match(etag.as_deref())
+            .await?
+        {
+            Some((response, etag)) => {
+                *self.genesis_cache.lock() = Some(CachedResponse {
+                    etag,
+                    data: response.data.clone(),
+                });
+                Ok(response.data)
+            }
+            None => self
+                .genesis_cache
+                .lock()
+                .as_ref()
+                .map(|cached| cached.data.clone())
+                .ok_or(eth2::Error::StatusCode(eth2::StatusCode::NOT_MODIFIED)),
+        }
+    }
+
+    /// Checks if the node has the correct specification and, once it is known, genesis.
+    async fn is_compatible(
+        &self,
+        spec: &ChainSpec,
+        genesis_validators_root: Option<Hash256>,
+        log: &Logger,
+    ) -> Result<(), CandidateError> {
+        let config = self.config_spec().await.map_err(|e| {
+            error!(
+                log,
+                "Unable to read spec from beacon node";
+                "error" => %e,
+                "endpoint" => %self.beacon_node,
+            );
+            CandidateError::Offline
+        })?;
 
         let beacon_node_spec = ChainSpec::from_config::<E>(&config).ok_or_else(|| {
             error!(
@@ -271,7 +552,50 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
             CandidateError::Incompatible
         })?;
 
-        if beacon_node_spec.genesis_fork_version != spec.genesis_fork_version {
+        let bn_genesis_validators_root = if genesis_validators_root.is_some() {
+            let genesis = self.genesis().await.map_err(|e| {
+                error!(
+                    log,
+                    "Unable to read genesis from beacon node";
+                    "error" => %e,
+                    "endpoint" => %self.beacon_node,
+                );
+                CandidateError::Offline
+            })?;
+            Some(genesis.genesis_validators_root)
+        } else {
+            None
+        };
+
+        let network_view = NetworkView {
+            genesis_fork_version: beacon_node_spec.genesis_fork_version,
+            fork_epochs: ForkName::list_all_fork_epochs(&beacon_node_spec),
+            genesis_validators_root: bn_genesis_validators_root,
+        };
+        let previous_view = self.network_view.lock().replace(network_view.clone());
+        if let Some(previous_view) = previous_view.filter(|view| {
+            view.fork_epochs != network_view.fork_epochs
+                || view.genesis_fork_version != network_view.genesis_fork_version
+        }) {
+            warn!(
+                log,
+                "Beacon node fork schedule changed";
+                "endpoint" => %self.beacon_node,
+                "previous" => ?previous_view.fork_epochs,
+                "current" => ?network_view.fork_epochs,
+            );
+        }
+
+        if bn_genesis_validators_root != genesis_validators_root {
+            error!(
+                log,
+                "Beacon node has a different genesis";
+                "endpoint" => %self.beacon_node,
+                "bn_genesis_validators_root" => ?bn_genesis_validators_root,
+                "our_genesis_validators_root" => ?genesis_validators_root,
+            );
+            return Err(CandidateError::Incompatible);
+        } else if beacon_node_spec.genesis_fork_version != spec.genesis_fork_version {
             error!(
                 log,
                 "Beacon node is configured for a different network";
@@ -346,7 +670,17 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
 pub struct BeaconNodeFallback<T, E> {
     candidates: Vec<CandidateBeaconNode<E>>,
     slot_clock: Option<T>,
+    genesis_validators_root: Option<Hash256>,
+    /// Whether the candidates were found to report different networks or fork schedules.
+    divergent: AtomicBool,
     broadcast_topics: Vec<ApiTopic>,
+    selection: BeaconNodeSelection,
+    routes: BeaconNodeRoutes,
+    /// The index of the last candidate to succeed, used by `BeaconNodeSelection::StickyPrimary`.
+    sticky_index: AtomicUsize,
+    /// The index of the next candidate to try first, used by
+    /// `BeaconNodeSelection::RoundRobinReads`.
+    round_robin_index: AtomicUsize,
     spec: ChainSpec,
     log: Logger,
 }
@@ -355,13 +689,21 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
     pub fn new(
         candidates: Vec<CandidateBeaconNode<E>>,
         broadcast_topics: Vec<ApiTopic>,
+        selection: BeaconNodeSelection,
+        routes: BeaconNodeRoutes,
         spec: ChainSpec,
         log: Logger,
     ) -> Self {
         Self {
             candidates,
             slot_clock: None,
+            genesis_validators_root: None,
+            divergent: AtomicBool::new(false),
             broadcast_topics,
+            selection,
+            routes,
+            sticky_index: AtomicUsize::new(0),
+            round_robin_index: AtomicUsize::new(0),
             spec,
             log,
         }
@@ -376,11 +718,30 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
         self.slot_clock = Some(slot_clock);
     }
 
+    /// Sets the genesis validators root which each candidate must report, once it is known.
+    pub fn set_genesis_validators_root(&mut self, genesis_validators_root: Hash256) {
+        self.genesis_validators_root = Some(genesis_validators_root);
+    }
+
     /// The count of candidates, regardless of their state.
     pub fn num_total(&self) -> usize {
         self.candidates.len()
     }
 
+    /// Returns `true` if requests for `topic` are sent to all candidates.
+    pub fn broadcasts(&self, topic: ApiTopic) -> bool {
+        self.broadcast_topics.contains(&topic)
+    }
+
+    /// Returns the total number of times that candidates have come online. A change means that
+    /// some candidate may have restarted and lost any state previously sent to it.
+    pub fn connections(&self) -> u64 {
+        self.candidates
+            .iter()
+            .map(|candidate| candidate.connections.load(Ordering::Relaxed))
+            .sum()
+    }
+
     /// The count of synced and ready candidates.
     pub async fn num_synced(&self) -> usize {
         let mut n = 0;
@@ -424,12 +785,55 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
             .candidates
             .iter()
             .map(|candidate| {
-                candidate.refresh_status(self.slot_clock.as_ref(), &self.spec, &self.log)
+                candidate.refresh_status(
+                    self.slot_clock.as_ref(),
+                    &self.spec,
+                    self.genesis_validators_root,
+                    &self.log,
+                )
             })
             .collect::<Vec<_>>();
 
         // run all updates concurrently and ignore errors
         let _ = future::join_all(futures).await;
+
+        self.check_divergence();
+    }
+
+    /// Raises an alert if the candidates report different networks or fork schedules, since
+    /// requests may be served by any of them.
+    fn check_divergence(&self) {
+        let mut views: Vec<(NetworkView, Vec<String>)> = vec![];
+        for candidate in &self.candidates {
+            let Some(view) = candidate.network_view() else {
+                continue;
+            };
+            let endpoint = candidate.beacon_node.to_string();
+            match views.iter_mut().find(|(other, _)| *other == view) {
+                Some((_, endpoints)) => endpoints.push(endpoint),
+                None => views.push((view, vec![endpoint])),
+            }
+        }
+
+        let divergent = views.len() > 1;
+        set_gauge(&BEACON_NODES_DIVERGENT, i64::from(divergent));
+        let was_divergent = self.divergent.swap(divergent, Ordering::Relaxed);
+        if divergent && !was_divergent {
+            for (view, endpoints) in &views {
+                crit!(
+                    self.log,
+                    "Beacon nodes report different networks or fork schedules";
+                    "msg" => "check that all beacon nodes are configured for the same network \
+                              and are running releases which agree on upcoming forks",
+                    "endpoints" => ?endpoints,
+                    "genesis_fork_version" => ?view.genesis_fork_version,
+                    "fork_epochs" => ?view.fork_epochs,
+                    "genesis_validators_root" => ?view.genesis_validators_root,
+                );
+            }
+        } else if !divergent && was_divergent {
+            info!(self.log, "Beacon nodes agree on network and fork schedule");
+        }
     }
 
     /// Concurrently send a request to all candidates (regardless of
@@ -465,26 +869,129 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
         future::join_all(futures)
             .await
             .into_iter()
-            .map(|(beacon_node_id, response_instant)| LatencyMeasurement {
-                beacon_node_id,
-                latency: response_instant
-                    .and_then(|response| response.checked_duration_since(request_instant)),
+            .zip(&self.candidates)
+            .map(|((beacon_node_id, response_instant), candidate)| {
+                let latency = response_instant
+                    .and_then(|response| response.checked_duration_since(request_instant));
+                match latency {
+                    Some(latency) => candidate.record_success(latency),
+                    None => candidate.record_failure(),
+                }
+                LatencyMeasurement {
+                    beacon_node_id,
+                    latency,
+                }
             })
             .collect()
     }
 
+    /// Returns the indices of `self.candidates` in the order they should be tried, according to
+    /// `self.selection`.
+    ///
+    /// If `is_read` is `false` the round-robin strategy uses the configured order, so that
+    /// publications are sent to the preferred candidate first.
+    ///
+    /// If `class` is routed to a subset of the candidates, only those candidates are returned.
+    fn candidate_order(&self, is_read: bool, class: Option<RequestClass>) -> Vec<usize> {
+        let mut order: Vec<usize> = match class.and_then(|class| self.routes.route(class)) {
+            Some(route) => (0..self.candidates.len())
+                .filter(|index| route.contains(index))
+                .collect(),
+            None => (0..self.candidates.len()).collect(),
+        };
+        match self.selection {
+            BeaconNodeSelection::Ordered => {}
+            BeaconNodeSelection::LatencyWeighted => {
+                let costs: Vec<f64> = self
+                    .candidates
+                    .iter()
+                    .map(|candidate| candidate.health().cost())
+                    .collect();
+                // The sort is stable, so candidates with equal costs keep the configured order.
+                order.sort_by(|a, b| costs[*a].total_cmp(&costs[*b]));
+            }
+            BeaconNodeSelection::StickyPrimary => {
+                let sticky = self.sticky_index.load(Ordering::Relaxed);
+                if let Some(position) = order.iter().position(|index| *index == sticky) {
+                    order.remove(position);
+                    order.insert(0, sticky);
+                }
+            }
+            BeaconNodeSelection::RoundRobinReads => {
+                if is_read && !order.is_empty() {
+                    let first =
+                        self.round_robin_index.fetch_add(1, Ordering::Relaxed) % order.len();
+                    order.rotate_left(first);
+                }
+            }
+        }
+        order
+    }
+
     /// Run `func` against each candidate in `self`, returning immediately if a result is found.
     /// Otherwise, return all the errors encountered along the way.
     ///
     /// First this function will try all nodes with a suitable status. If no candidates are suitable
     /// or all the requests fail, it will try updating the status of all unsuitable nodes and
     /// re-running `func` again.
+    ///
+    /// Candidates are tried in the order given by the configured `BeaconNodeSelection`.
     pub async fn first_success<'a, F, O, Err, R>(
         &'a self,
         require_synced: RequireSynced,
         offline_on_failure: OfflineOnFailure,
         func: F,
     ) -> Result<O, Errors<Err>>
+    where
+        F: Fn(&'a BeaconNodeHttpClient) -> R,
+        R: Future<Output = Result<O, Err>>,
+        Err: Debug,
+    {
+        self.first_success_in_order(
+            self.candidate_order(true, None),
+            require_synced,
+            offline_on_failure,
+            func,
+        )
+        .await
+    }
+
+    /// Run `func` against each candidate which may serve requests of `class`, returning
+    /// immediately if a result is found.
+    ///
+    /// See `Self::first_success` for more information.
+    pub async fn first_success_for<'a, F, O, Err, R>(
+        &'a self,
+        class: RequestClass,
+        require_synced: RequireSynced,
+        offline_on_failure: OfflineOnFailure,
+        func: F,
+    ) -> Result<O, Errors<Err>>
+    where
+        F: Fn(&'a BeaconNodeHttpClient) -> R,
+        R: Future<Output = Result<O, Err>>,
+        Err: Debug,
+    {
+        self.first_success_in_order(
+            self.candidate_order(true, Some(class)),
+            require_synced,
+            offline_on_failure,
+            func,
+        )
+        .await
+    }
+
+    /// Run `func` against each candidate in `self`, in the given `order`, returning immediately
+    /// if a result is found.
+    ///
+    /// See `Self::first_success` for more information.
+    async fn first_success_in_order<'a, F, O, Err, R>(
+        &'a self,
+        order: Vec<usize>,
+        require_synced: RequireSynced,
+        offline_on_failure: OfflineOnFailure,
+        func: F,
+    ) -> Result<O, Errors<Err>>
     where
         F: Fn(&'a BeaconNodeHttpClient) -> R,
         R: Future<Output = Result<O, Err>>,
@@ -494,19 +1001,25 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
         let mut to_retry = vec![];
         let mut retry_unsynced = vec![];
         let log = &self.log.clone();
+        let sticky_index = &self.sticky_index;
 
         // Run `func` using a `candidate`, returning the value or capturing errors.
         //
         // We use a macro instead of a closure here since it is not trivial to move `func` into a
         // closure.
         macro_rules! try_func {
-            ($candidate: ident) => {{
+            ($index: ident, $candidate: ident) => {{
                 inc_counter_vec(&ENDPOINT_REQUESTS, &[$candidate.beacon_node.as_ref()]);
 
                 // There exists a race condition where `func` may be called when the candidate is
                 // actually not ready. We deem this an acceptable inefficiency.
+                let request_instant = Instant::now();
                 match func(&$candidate.beacon_node).await {
-                    Ok(val) => return Ok(val),
+                    Ok(val) => {
+                        $candidate.record_success(request_instant.elapsed());
+                        sticky_index.store($index, Ordering::Relaxed);
+                        return Ok(val);
+                    }
                     Err(e) => {
                         debug!(
                             log,
@@ -522,6 +1035,7 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
                         if matches!(offline_on_failure, OfflineOnFailure::Yes) {
                             $candidate.set_offline().await;
                         }
+                        $candidate.record_failure();
                         errors.push(($candidate.beacon_node.to_string(), Error::RequestFailed(e)));
                         inc_counter_vec(&ENDPOINT_ERRORS, &[$candidate.beacon_node.as_ref()]);
                     }
@@ -532,19 +1046,20 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
         // First pass: try `func` on all synced and ready candidates.
         //
         // This ensures that we always choose a synced node if it is available.
-        for candidate in &self.candidates {
+        for index in order {
+            let candidate = &self.candidates[index];
             match candidate.status(RequireSynced::Yes).await {
                 Err(e @ CandidateError::NotSynced) if require_synced == false => {
                     // This client is unsynced we will try it after trying all synced clients
-                    retry_unsynced.push(candidate);
+                    retry_unsynced.push((index, candidate));
                     errors.push((candidate.beacon_node.to_string(), Error::Unavailable(e)));
                 }
                 Err(e) => {
                     // This client was not ready on the first pass, we might try it again later.
-                    to_retry.push(candidate);
+                    to_retry.push((index, candidate));
                     errors.push((candidate.beacon_node.to_string(), Error::Unavailable(e)));
                 }
-                _ => try_func!(candidate),
+                _ => try_func!(index, candidate),
             }
         }
 
@@ -554,27 +1069,34 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
         // Due to async race-conditions, it is possible that we will send a request to a candidate
         // that has been set to an offline/unready status. This is acceptable.
         if require_synced == false {
-            for candidate in retry_unsynced {
-                try_func!(candidate);
+            for (index, candidate) in retry_unsynced {
+                try_func!(index, candidate);
             }
         }
 
         // Third pass: try again, attempting to make non-ready clients become ready.
-        for candidate in to_retry {
+        for (index, candidate) in to_retry {
             // If the candidate hasn't luckily transferred into the correct state in the meantime,
             // force an update of the state.
             let new_status = match candidate.status(require_synced).await {
                 Ok(()) => Ok(()),
                 Err(_) => {
                     candidate
-                        .refresh_status(self.slot_clock.as_ref(), &self.spec, &self.log)
+                        .refresh_status(
+                            self.slot_clock.as_ref(),
+                            &self.spec,
+                            self.genesis_validators_root,
+                            &self.log,
+                        )
                         .await
                 }
             };
 
             match new_status {
-                Ok(()) => try_func!(candidate),
-                Err(CandidateError::NotSynced) if require_synced == false => try_func!(candidate),
+                Ok(()) => try_func!(index, candidate),
+                Err(CandidateError::NotSynced) if require_synced == false => {
+                    try_func!(index, candidate)
+                }
                 Err(e) => {
                     errors.push((candidate.beacon_node.to_string(), Error::Unavailable(e)));
                 }
@@ -601,6 +1123,29 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
         offline_on_failure: OfflineOnFailure,
         func: F,
     ) -> Result<(), Errors<Err>>
+    where
+        F: Fn(&'a BeaconNodeHttpClient) -> R,
+        R: Future<Output = Result<O, Err>>,
+    {
+        self.broadcast_to(
+            (0..self.candidates.len()).collect(),
+            require_synced,
+            offline_on_failure,
+            func,
+        )
+        .await
+    }
+
+    /// Run `func` against the candidates at the indices in `order`.
+    ///
+    /// See `Self::broadcast` for more information.
+    async fn broadcast_to<'a, F, O, Err, R>(
+        &'a self,
+        order: Vec<usize>,
+        require_synced: RequireSynced,
+        offline_on_failure: OfflineOnFailure,
+        func: F,
+    ) -> Result<(), Errors<Err>>
     where
         F: Fn(&'a BeaconNodeHttpClient) -> R,
         R: Future<Output = Result<O, Err>>,
@@ -614,8 +1159,12 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
 
             // There exists a race condition where `func` may be called when the candidate is
             // actually not ready. We deem this an acceptable inefficiency.
+            let request_instant = Instant::now();
             match func(&candidate.beacon_node).await {
-                Ok(val) => Ok(val),
+                Ok(val) => {
+                    candidate.record_success(request_instant.elapsed());
+                    Ok(val)
+                }
                 Err(e) => {
                     // If we have an error on this function, mark the client as not-ready.
                     //
@@ -625,6 +1174,7 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
                     if matches!(offline_on_failure, OfflineOnFailure::Yes) {
                         candidate.set_offline().await;
                     }
+                    candidate.record_failure();
                     inc_counter_vec(&ENDPOINT_ERRORS, &[candidate.beacon_node.as_ref()]);
                     Err((candidate.beacon_node.to_string(), Error::RequestFailed(e)))
                 }
@@ -635,7 +1185,7 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
         //
         // This ensures that we always choose a synced node if it is available.
         let mut first_batch_futures = vec![];
-        for candidate in &self.candidates {
+        for candidate in order.into_iter().map(|index| &self.candidates[index]) {
             match candidate.status(RequireSynced::Yes).await {
                 Ok(_) => {
                     first_batch_futures.push(run_on_candidate(candidate));
@@ -673,7 +1223,12 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
                 Ok(()) => Ok(()),
                 Err(_) => {
                     candidate
-                        .refresh_status(self.slot_clock.as_ref(), &self.spec, &self.log)
+                        .refresh_status(
+                            self.slot_clock.as_ref(),
+                            &self.spec,
+                            self.genesis_validators_root,
+                            &self.log,
+                        )
                         .await
                 }
             };
@@ -706,6 +1261,8 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
 
     /// Call `func` on first beacon node that returns success or on all beacon nodes
     /// depending on the `topic` and configuration.
+    ///
+    /// Only the beacon nodes to which the `topic` is routed are used.
     pub async fn request<'a, F, Err, R>(
         &'a self,
         require_synced: RequireSynced,
@@ -718,12 +1275,23 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
         R: Future<Output = Result<(), Err>>,
         Err: Debug,
     {
-        if self.broadcast_topics.contains(&topic) {
-            self.broadcast(require_synced, offline_on_failure, func)
-                .await
+        let class = Some(topic.into());
+        if self.broadcasts(topic) {
+            self.broadcast_to(
+                self.candidate_order(false, class),
+                require_synced,
+                offline_on_failure,
+                func,
+            )
+            .await
         } else {
-            self.first_success(require_synced, offline_on_failure, func)
-                .await?;
+            self.first_success_in_order(
+                self.candidate_order(false, class),
+                require_synced,
+                offline_on_failure,
+                func,
+            )
+            .await?;
             Ok(())
         }
     }
@@ -761,4 +1329,53 @@ mod test {
             .map(|topic| ApiTopic::from_str(topic).unwrap())
             .eq(all.into_iter()));
     }
+
+    #[test]
+    fn beacon_node_routes_parse() {
+        let routes = BeaconNodeRoutes::from_str("blocks=0, duties=1:2").unwrap();
+        assert_eq!(routes.route(RequestClass::Blocks), Some(&[0][..]));
+        assert_eq!(routes.route(RequestClass::Duties), Some(&[1, 2][..]));
+        assert_eq!(routes.route(RequestClass::Attestations), None);
+        assert_eq!(routes.max_index(), Some(2));
+
+        assert_eq!(
+            BeaconNodeRoutes::from_str("").unwrap(),
+            BeaconNodeRoutes::default()
+        );
+        assert!(BeaconNodeRoutes::from_str("blocks").is_err());
+        assert!(BeaconNodeRoutes::from_str("blocks=").is_err());
+        assert!(BeaconNodeRoutes::from_str("exits=0").is_err());
+        assert!(BeaconNodeRoutes::from_str("blocks=0,blocks=1").is_err());
+    }
+
+    #[test]
+    fn health_tracks_recent_outcomes() {
+        let mut health = CandidateHealth::default();
+        assert_eq!(health.cost(), f64::INFINITY);
+
+        health.record_success(Duration::from_millis(100));
+        assert_eq!(health.latency, Some(Duration::from_millis(100)));
+        let healthy_cost = health.cost();
+
+        health.record_failure();
+        assert!(health.score < 1.0);
+        assert!(health.cost() > healthy_cost);
+
+        // Repeated successes restore the score.
+        for _ in 0..100 {
+            health.record_success(Duration::from_millis(100));
+        }
+        assert!(health.score > 0.99);
+    }
+
+    #[test]
+    fn beacon_node_selection_from_str() {
+        for selection in BeaconNodeSelection::VARIANTS {
+            BeaconNodeSelection::from_str(selection).unwrap();
+        }
+        assert_eq!(
+            BeaconNodeSelection::from_str("latency-weighted").unwrap(),
+            BeaconNodeSelection::LatencyWeighted
+        );
+    }
 }
@@ -1,6 +1,6 @@
 use crate::beacon_node_fallback::{Error as FallbackError, Errors};
 use crate::{
-    beacon_node_fallback::{ApiTopic, BeaconNodeFallback, RequireSynced},
+    beacon_node_fallback::{ApiTopic, BeaconNodeFallback, RequestClass, RequireSynced},
     determine_graffiti,
     graffiti_file::GraffitiFile,
     OfflineOnFailure,
@@ -186,10 +186,15 @@ impl<T: SlotClock, E: EthSpec> ProposerFallback<T, E> {
         R: Future<Output = Result<O, Err>>,
         Err: Debug,
     {
-        // Try running `func` on the non-proposer beacon nodes.
+        // Try running `func` on the non-proposer beacon nodes to which blocks are routed.
         let beacon_nodes_result = self
             .beacon_nodes
-            .first_success(require_synced, offline_on_failure, func.clone())
+            .first_success_for(
+                RequestClass::Blocks,
+                require_synced,
+                offline_on_failure,
+                func.clone(),
+            )
             .await;
 
         match (beacon_nodes_result, &self.proposer_nodes) {
@@ -394,6 +399,26 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
                 );
                 return Ok(());
             }
+            Err(ValidatorStoreError::HandedOver(pubkey)) => {
+                info!(
+                    log,
+                    "Not signing block";
+                    "reason" => "validator handed over to another validator client",
+                    "pubkey" => ?pubkey,
+                    "slot" => ?slot
+                );
+                return Ok(());
+            }
+            Err(ValidatorStoreError::Suspended(pubkey)) => {
+                info!(
+                    log,
+                    "Not signing block";
+                    "reason" => "validator suspended",
+                    "pubkey" => ?pubkey,
+                    "slot" => ?slot
+                );
+                return Ok(());
+            }
             Err(e) => {
                 return Err(BlockError::Recoverable(format!(
                     "Unable to sign block: {:?}",
@@ -468,6 +493,16 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
                 );
                 return Ok(());
             }
+            Err(ValidatorStoreError::Suspended(pubkey)) => {
+                info!(
+                    log,
+                    "Not proposing block";
+                    "reason" => "validator suspended",
+                    "pubkey" => ?pubkey,
+                    "slot" => ?slot
+                );
+                return Ok(());
+            }
             Err(e) => {
                 return Err(BlockError::Recoverable(format!(
                     "Unable to produce randao reveal signature: {:?}",
@@ -585,7 +620,7 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
         builder_boost_factor: Option<u64>,
         log: &Logger,
     ) -> Result<UnsignedBlock<E>, BlockError> {
-        let (block_response, _) = beacon_node
+        let (block_response, metadata) = beacon_node
             .get_validator_blocks_v3::<E>(
                 slot,
                 randao_reveal_ref,
@@ -609,6 +644,10 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
             log,
             "Received unsigned block";
             "slot" => slot.as_u64(),
+            "blinded" => metadata.execution_payload_blinded,
+            "execution_payload_value" => %metadata.execution_payload_value,
+            "local_payload_value" => metadata.local_payload_value.map(|value| value.to_string()),
+            "builder_bid_value" => metadata.builder_bid_value.map(|value| value.to_string()),
         );
         if proposer_index != Some(unsigned_block.proposer_index()) {
             return Err(BlockError::Recoverable(
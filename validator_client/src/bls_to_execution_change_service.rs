@@ -0,0 +1,108 @@
+use crate::beacon_node_fallback::{BeaconNodeFallback, OfflineOnFailure, RequireSynced};
+use crate::validator_store::ValidatorStore;
+use account_utils::validator_definitions::BlsToExecutionChangeRecord;
+use environment::RuntimeContext;
+use slog::{error, info, Logger};
+use slot_clock::SlotClock;
+use std::sync::Arc;
+use tokio::time::sleep;
+use types::{EthSpec, PublicKey, PublicKeyBytes, SignedBlsToExecutionChange};
+
+/// Starts a service which broadcasts the withdrawal credentials changes that have been signed via
+/// the keymanager API.
+///
+/// Queued changes are checked at the start of each epoch. Changes which fail to be broadcast
+/// remain queued and are retried in the following epoch.
+pub fn start_bls_to_execution_change_service<T: SlotClock + 'static, E: EthSpec>(
+    context: RuntimeContext<E>,
+    slot_clock: T,
+    validator_store: Arc<ValidatorStore<T, E>>,
+    beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
+) {
+    let log = context.log().clone();
+
+    let future = async move {
+        loop {
+            let sleep_time = slot_clock
+                .duration_to_next_epoch(E::slots_per_epoch())
+                .unwrap_or_else(|| slot_clock.slot_duration());
+            sleep(sleep_time).await;
+
+            let pending_changes = validator_store
+                .initialized_validators()
+                .read()
+                .pending_bls_to_execution_changes();
+
+            for (pubkey, signed_change) in pending_changes {
+                publish_bls_to_execution_change(
+                    &validator_store,
+                    &beacon_nodes,
+                    &pubkey,
+                    signed_change,
+                    &log,
+                )
+                .await;
+            }
+        }
+    };
+
+    context.executor.spawn(future, "bls_to_execution_changes");
+}
+
+/// Broadcast the withdrawal credentials change for `pubkey`, recording it as submitted if a
+/// beacon node accepts it.
+async fn publish_bls_to_execution_change<T: SlotClock + 'static, E: EthSpec>(
+    validator_store: &ValidatorStore<T, E>,
+    beacon_nodes: &BeaconNodeFallback<T, E>,
+    pubkey: &PublicKey,
+    signed_change: SignedBlsToExecutionChange,
+    log: &Logger,
+) {
+    let pubkey_bytes = PublicKeyBytes::from(pubkey);
+    let changes = &[signed_change.clone()];
+    if let Err(e) = beacon_nodes
+        .first_success(
+            RequireSynced::Yes,
+            OfflineOnFailure::Yes,
+            |beacon_node| async move {
+                beacon_node
+                    .post_beacon_pool_bls_to_execution_changes(changes)
+                    .await
+            },
+        )
+        .await
+    {
+        error!(
+            log,
+            "Failed to publish withdrawal credentials change";
+            "error" => %e,
+            "validator" => ?pubkey_bytes,
+        );
+        return;
+    }
+
+    info!(
+        log,
+        "Published withdrawal credentials change";
+        "to_execution_address" => ?signed_change.message.to_execution_address,
+        "validator_index" => signed_change.message.validator_index,
+        "validator" => ?pubkey_bytes,
+    );
+
+    let record = BlsToExecutionChangeRecord {
+        signed_change,
+        submitted: true,
+    };
+    if let Err(e) = validator_store
+        .initialized_validators()
+        .write()
+        .set_bls_to_execution_change(pubkey, Some(record))
+    {
+        error!(
+            log,
+            "Failed to record withdrawal credentials change as submitted";
+            "error" => ?e,
+            "validator" => ?pubkey_bytes,
+        );
+    }
+}
@@ -65,6 +65,35 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("beacon-node-selection")
+                .long("beacon-node-selection")
+                .value_name("STRATEGY")
+                .help("The strategy used to choose which beacon node to send a request to first. \
+                       \"ordered\" tries beacon nodes in the order they are configured. \
+                       \"latency-weighted\" prefers beacon nodes with low recent latency and few \
+                       recent errors. \"sticky-primary\" keeps using the last beacon node to \
+                       succeed until it fails. \"round-robin-reads\" rotates the first beacon \
+                       node tried for each request, except for publications which use the \
+                       configured order. Synced beacon nodes are always preferred.")
+                .default_value("ordered")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("beacon-node-routes")
+                .long("beacon-node-routes")
+                .value_name("ROUTES")
+                .help("Comma-separated list of routes restricting classes of request to some of \
+                       the beacon nodes given by --beacon-nodes, e.g. \"blocks=0,duties=1:2\". \
+                       Each route is a request class followed by the colon-separated indices \
+                       of the beacon nodes to use, counting from 0 in the order they are \
+                       configured. Possible classes are: blocks, attestations, \
+                       sync-committee, subscriptions, duties. Classes without a route may use \
+                       any beacon node. Routed requests never fall back to other beacon nodes.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("validators-dir")
                 .long("validators-dir")
@@ -93,6 +122,64 @@ pub fn cli_app() -> Command {
                 .conflicts_with("datadir")
                 .display_order(0)
         )
+        .arg(
+            Arg::new("slashing-protection-export-path")
+                .long("slashing-protection-export-path")
+                .value_name("PATH")
+                .help("If present, the slashing protection database is exported to this path as \
+                       an EIP-3076 interchange file periodically and when the validator client \
+                       shuts down. The previous exports are kept alongside it, with the suffixes \
+                       .1, .2 and .3.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("slashing-protection-export-interval")
+                .long("slashing-protection-export-interval")
+                .value_name("SECONDS")
+                .help("The number of seconds between periodic exports of the slashing protection \
+                       database. Only used with --slashing-protection-export-path.")
+                .default_value("3600")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("signing-lease-file")
+                .long("signing-lease-file")
+                .value_name("PATH")
+                .help("If present, validators only sign whilst this validator client holds the \
+                       lease recorded in this file. Point an active and a standby validator \
+                       client at the same file, e.g. on a shared filesystem, to ensure that only \
+                       one of them signs at a time. The standby takes over once the active \
+                       instance stops renewing the lease.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("signing-lease-timeout")
+                .long("signing-lease-timeout")
+                .value_name("SECONDS")
+                .help("The number of seconds after which a signing lease which isn't renewed may \
+                       be taken over by another validator client. Only used with \
+                       --signing-lease-file.")
+                .default_value("60")
+                .requires("signing-lease-file")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("signing-lease-clock-skew")
+                .long("signing-lease-clock-skew")
+                .value_name("SECONDS")
+                .help("The number of seconds to wait after a signing lease expires before taking \
+                       it over, allowing for the difference between the clocks of the two \
+                       validator clients. Their clocks must be kept within this margin of each \
+                       other, e.g. using NTP. Only used with --signing-lease-file.")
+                .default_value("5")
+                .requires("signing-lease-file")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("init-slashing-protection")
                 .long("init-slashing-protection")
@@ -139,6 +226,36 @@ pub fn cli_app() -> Command {
                         delimiter, and must not be part of the certificate path.")
                 .display_order(0)
         )
+        .arg(
+            Arg::new("beacon-nodes-tls-client-cert")
+                .long("beacon-nodes-tls-client-cert")
+                .value_name("CERTIFICATE-FILE")
+                .action(ArgAction::Set)
+                .requires("beacon-nodes-tls-client-key")
+                .help("Path to a PEM certificate which the validator client presents to beacon \
+                        nodes (and/or proposer nodes) which require TLS client authentication.")
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("beacon-nodes-tls-client-key")
+                .long("beacon-nodes-tls-client-key")
+                .value_name("KEY-FILE")
+                .action(ArgAction::Set)
+                .requires("beacon-nodes-tls-client-cert")
+                .help("Path to the private key of --beacon-nodes-tls-client-cert. The key must \
+                        be in PKCS#8 PEM format and must not be password-protected.")
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("beacon-nodes-api-token-file")
+                .long("beacon-nodes-api-token-file")
+                .value_name("PATH")
+                .action(ArgAction::Set)
+                .help("Path to a file containing a bearer token which the validator client \
+                        presents to beacon nodes (and/or proposer nodes) which require API \
+                        tokens. The token must have the validator or admin scope.")
+                .display_order(0)
+        )
         // This overwrites the graffiti configured in the beacon node.
         .arg(
             Arg::new("graffiti")
@@ -167,6 +284,17 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("suggested-fee-recipient-file")
+                .long("suggested-fee-recipient-file")
+                .help("Specify a file to load per-validator fee recipients from. Entries in this \
+                       file take priority over --suggested-fee-recipient, but not over fee \
+                       recipients in the validator definitions. The file is checked for changes \
+                       every slot and reloaded without restarting the validator client.")
+                .value_name("FEE-RECIPIENT-FILE")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("produce-block-v3")
                 .long("produce-block-v3")
@@ -183,6 +311,49 @@ pub fn cli_app() -> Command {
                 .help_heading(FLAG_HEADER)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("distributed-duty-delay")
+                .long("distributed-duty-delay")
+                .value_name("MILLIS")
+                .help("Additional delay to apply before producing attestations and sync committee \
+                       messages, allowing distributed validator middleware to coordinate. \
+                       Requires --distributed.")
+                .requires("distributed")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("early-attestations")
+                .long("early-attestations")
+                .help("Produce attestations as soon as a beacon node reports the head block of the \
+                       slot, instead of always waiting until 1/3rd of the way through the slot. \
+                       Attestations are still produced at the usual time if the block is late.")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("early-attestation-min-delay")
+                .long("early-attestation-min-delay")
+                .value_name("MILLIS")
+                .help("The minimum delay into the slot before an attestation is produced early. \
+                       Requires --early-attestations.")
+                .requires("early-attestations")
+                .default_value("1000")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("sync-committee-jitter")
+                .long("sync-committee-jitter")
+                .value_name("MILLIS")
+                .help("Spread sync committee messages, contributions and subnet subscriptions \
+                       randomly over this many milliseconds after they would otherwise be sent, \
+                       to avoid bursts of requests to the beacon node when managing many \
+                       validators. Capped at 1/6th of a slot.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         /* REST API related arguments */
         .arg(
             Arg::new("http")
@@ -321,6 +492,30 @@ pub fn cli_app() -> Command {
                 .help_heading(FLAG_HEADER)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("validator-metrics-labels")
+                .long("validator-metrics-labels")
+                .value_name("VALIDATORS")
+                .help("The validators which have their own label in per validator metrics. \
+                    \"all\" labels every validator, \"monitored\" only labels those given by \
+                    --validator-metrics-pubkeys and \"none\" labels no validators. The metrics \
+                    of unlabelled validators are aggregated. If not set, validators are only \
+                    labelled when there are 64 or fewer of them.")
+                .value_parser(["all", "monitored", "none"])
+                .conflicts_with("enable-high-validator-count-metrics")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("validator-metrics-pubkeys")
+                .long("validator-metrics-pubkeys")
+                .value_name("PUBKEYS")
+                .help("Comma-separated list of the public keys of the validators which have their \
+                    own label in per validator metrics when --validator-metrics-labels is \
+                    \"monitored\".")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         /*
          * Explorer metrics
          */
@@ -364,6 +559,18 @@ pub fn cli_app() -> Command {
                 .help_heading(FLAG_HEADER)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("enable-runtime-snapshot")
+                .long("enable-runtime-snapshot")
+                .help("If present, the validator client writes its duties, doppelganger detection \
+                    progress and builder registrations to the validator directory every slot and \
+                    when it shuts down, and restores them on start-up if they are less than an \
+                    epoch old. This allows a quick restart to resume duties immediately. \
+                    Doppelganger protection still checks the epochs since the snapshot.")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+        )
         .arg(
             Arg::new("builder-proposals")
                 .long("builder-proposals")
@@ -412,6 +619,18 @@ pub fn cli_app() -> Command {
                 .display_order(0)
                 .hide(true)
         )
+        .arg(
+            Arg::new("performance-tracking-epochs")
+                .long("performance-tracking-epochs")
+                .value_name("EPOCHS")
+                .help("If set, the validator client tracks the attestation correctness, inclusion \
+                    distance, proposals and rewards of each validator over this many recent \
+                    epochs, using data fetched from the beacon node once per epoch. The results \
+                    are served at the lighthouse/ui/performance HTTP API endpoint.")
+                .requires("http")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("validator-registration-batch-size")
                 .long("validator-registration-batch-size")
@@ -444,6 +663,32 @@ pub fn cli_app() -> Command {
                 .help_heading(FLAG_HEADER)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("proposer-duties-prefetch-slots")
+                .long("proposer-duties-prefetch-slots")
+                .value_name("SLOTS")
+                .help("The number of slots at the end of each epoch during which the proposer \
+                    duties of the next epoch are downloaded, so that proposals at the start of \
+                    the next epoch don't depend on the beacon node being reachable at the epoch \
+                    boundary. Setting it to 0 disables prefetching. Duties are never downloaded \
+                    more than one epoch in advance, as beacon nodes don't serve them.")
+                .default_value("2")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("proposer-only")
+                .long("proposer-only")
+                .help("If this flag is set, Lighthouse will only propose blocks and will not \
+                    perform attestation or sync committee duties. This is intended for dedicated \
+                    proposal infrastructure where other validator clients using the same keys \
+                    perform all other duties. Cannot be used with doppelganger protection, since \
+                    the other validator clients would be detected as doppelgangers.")
+                .action(ArgAction::SetTrue)
+                .conflicts_with("enable-doppelganger-protection")
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+        )
         .arg(
             Arg::new("disable-slashing-protection-web3signer")
                 .long("disable-slashing-protection-web3signer")
@@ -460,6 +705,50 @@ pub fn cli_app() -> Command {
         /*
          * Experimental/development options.
          */
+        .arg(
+            Arg::new("web3-signer-aggregate-timeout")
+                .long("web3-signer-aggregate-timeout")
+                .value_name("MILLIS")
+                .help("Timeout for web3signer requests to sign aggregates. If a request times \
+                       out and the validator has a fallback_keystore_path, the aggregate is signed \
+                       with the fallback keystore instead. Defaults to the request timeout of \
+                       the web3signer.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("web3-signer-attestation-timeout")
+                .long("web3-signer-attestation-timeout")
+                .value_name("MILLIS")
+                .help("Timeout for web3signer requests to sign attestations. If a request \
+                       times out and the validator has a fallback_keystore_path, the attestation \
+                       is signed with the fallback keystore instead. Defaults to the request \
+                       timeout of the web3signer.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("web3-signer-block-timeout")
+                .long("web3-signer-block-timeout")
+                .value_name("MILLIS")
+                .help("Timeout for web3signer requests to sign blocks. If a request times \
+                       out and the validator has a fallback_keystore_path, the block is signed \
+                       with the fallback keystore instead. Defaults to the request timeout of \
+                       the web3signer.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("web3-signer-health-check-interval")
+                .long("web3-signer-health-check-interval")
+                .value_name("SECONDS")
+                .help("When provided, each web3signer instance is checked at this interval by \
+                       querying its upcheck endpoint and requesting a harmless test signature. \
+                       Validators using an instance which fails consecutive checks are \
+                       quarantined (they will not sign) until it passes a check.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("web3-signer-keep-alive-timeout")
                 .long("web3-signer-keep-alive-timeout")
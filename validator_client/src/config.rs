@@ -1,5 +1,11 @@
-use crate::beacon_node_fallback::ApiTopic;
+use crate::beacon_node_fallback::{ApiTopic, BeaconNodeRoutes, BeaconNodeSelection};
+use crate::duties_service::{ValidatorMetricsLabels, DEFAULT_PROPOSER_PREFETCH_SLOTS};
+use crate::fee_recipient_file::FeeRecipientFile;
 use crate::graffiti_file::GraffitiFile;
+use crate::signing_lease::{
+    DEFAULT_SIGNING_LEASE_CLOCK_SKEW, DEFAULT_SIGNING_LEASE_TIMEOUT, MIN_SIGNING_LEASE_TIMEOUT,
+};
+use crate::signing_method::SigningTimeouts;
 use crate::{http_api, http_metrics};
 use clap::ArgMatches;
 use clap_utils::{flags::DISABLE_MALLOC_TUNING_FLAG, parse_optional, parse_required};
@@ -14,11 +20,13 @@ use slog::{info, warn, Logger};
 use std::fs;
 use std::net::IpAddr;
 use std::path::PathBuf;
+use std::str::FromStr;
 use std::time::Duration;
-use types::{Address, GRAFFITI_BYTES_LEN};
+use types::{Address, PublicKeyBytes, GRAFFITI_BYTES_LEN};
 
 pub const DEFAULT_BEACON_NODE: &str = "http://localhost:5052/";
 pub const DEFAULT_WEB3SIGNER_KEEP_ALIVE: Option<Duration> = Some(Duration::from_secs(20));
+pub const DEFAULT_SLASHING_PROTECTION_EXPORT_INTERVAL: Duration = Duration::from_secs(3600);
 
 /// Stores the core configuration for this validator instance.
 #[derive(Clone, Serialize, Deserialize)]
@@ -48,6 +56,8 @@ pub struct Config {
     pub graffiti_file: Option<GraffitiFile>,
     /// Fallback fallback address.
     pub fee_recipient: Option<Address>,
+    /// Fee recipient file to load per validator fee recipients.
+    pub fee_recipient_file: Option<FeeRecipientFile>,
     /// Configuration for the HTTP REST API.
     pub http_api: http_api::Config,
     /// Configuration for the HTTP REST API.
@@ -57,11 +67,18 @@ pub struct Config {
     /// If true, enable functionality that monitors the network for attestations or proposals from
     /// any of the validators managed by this client before starting up.
     pub enable_doppelganger_protection: bool,
+    /// If true, persist runtime state to disk and restore it on start-up.
+    pub enable_runtime_snapshot: bool,
     /// If true, then we publish validator specific metrics (e.g next attestation duty slot)
     /// for all our managed validators.
     /// Note: We publish validator specific metrics for low validator counts without this flag
     /// (<= 64 validators)
     pub enable_high_validator_count_metrics: bool,
+    /// The validators with their own label in per-validator metrics, overriding
+    /// `enable_high_validator_count_metrics`.
+    pub validator_metrics_labels: Option<ValidatorMetricsLabels>,
+    /// The validators with their own label under `ValidatorMetricsLabels::Monitored`.
+    pub validator_metrics_pubkeys: Vec<PublicKeyBytes>,
     /// Enable use of the blinded block endpoints during proposals.
     pub builder_proposals: bool,
     /// Overrides the timestamp field in builder api ValidatorRegistrationV1
@@ -71,10 +88,20 @@ pub struct Config {
     /// A list of custom certificates that the validator client will additionally use when
     /// connecting to a beacon node over SSL/TLS.
     pub beacon_nodes_tls_certs: Option<Vec<PathBuf>>,
+    /// Certificate and private key presented to beacon nodes requiring TLS client authentication.
+    pub beacon_nodes_tls_client_identity: Option<(PathBuf, PathBuf)>,
+    /// File containing the bearer token presented to beacon nodes which require API tokens.
+    pub beacon_nodes_api_token_file: Option<PathBuf>,
     /// Enables broadcasting of various requests (by topic) to all beacon nodes.
     pub broadcast_topics: Vec<ApiTopic>,
+    /// The strategy used to choose the order in which beacon nodes are tried.
+    pub beacon_node_selection: BeaconNodeSelection,
+    /// Restricts classes of request to a subset of `beacon_nodes`.
+    pub beacon_node_routes: BeaconNodeRoutes,
     /// Enables a service which attempts to measure latency between the VC and BNs.
     pub enable_latency_measurement_service: bool,
+    /// If set, the performance of each validator is tracked over this many epochs.
+    pub performance_tracking_epochs: Option<u64>,
     /// Defines the number of validators per `validator/register_validator` request sent to the BN.
     pub validator_registration_batch_size: usize,
     /// Enable slashing protection even while using web3signer keys.
@@ -83,10 +110,39 @@ pub struct Config {
     pub builder_boost_factor: Option<u64>,
     /// If true, Lighthouse will prefer builder proposals, if available.
     pub prefer_builder_proposals: bool,
+    /// If true, only block proposals are performed. Attestation and sync committee duties are
+    /// left to other validator clients using the same keys.
+    pub proposer_only: bool,
+    /// The number of slots at the end of each epoch during which the proposer duties of the next
+    /// epoch are prefetched.
+    pub proposer_prefetch_slots: u64,
     /// Whether we are running with distributed network support.
     pub distributed: bool,
+    /// Additional delay applied before producing attestations and sync committee messages, to
+    /// give distributed validator middleware time to coordinate.
+    pub distributed_duty_delay: Option<Duration>,
+    /// If set, attestations are produced as soon as the head block of the slot is observed, but
+    /// no earlier than this delay into the slot.
+    pub early_attestation_min_delay: Option<Duration>,
+    /// The maximum random delay applied to sync committee messages and subscriptions.
+    pub sync_committee_jitter: Option<Duration>,
     pub web3_signer_keep_alive_timeout: Option<Duration>,
     pub web3_signer_max_idle_connections: Option<usize>,
+    /// Timeouts for Web3Signer requests to sign blocks, attestations and aggregates.
+    pub web3_signer_signing_timeouts: SigningTimeouts,
+    /// If set, the health of each Web3Signer instance is checked at this interval.
+    pub web3_signer_health_check_interval: Option<Duration>,
+    /// If set, the slashing protection database is exported to this path periodically and on
+    /// shutdown.
+    pub slashing_protection_export_path: Option<PathBuf>,
+    /// The interval between periodic exports of the slashing protection database.
+    pub slashing_protection_export_interval: Duration,
+    /// If set, validators only sign whilst this instance holds the lease in this file.
+    pub signing_lease_file: Option<PathBuf>,
+    /// The time after which a lease which isn't renewed may be taken over by another instance.
+    pub signing_lease_timeout: Duration,
+    /// The time to wait after a lease expires before taking it over, allowing for clock skew.
+    pub signing_lease_clock_skew: Duration,
 }
 
 impl Default for Config {
@@ -115,24 +171,45 @@ impl Default for Config {
             graffiti: None,
             graffiti_file: None,
             fee_recipient: None,
+            fee_recipient_file: None,
             http_api: <_>::default(),
             http_metrics: <_>::default(),
             monitoring_api: None,
             enable_doppelganger_protection: false,
+            enable_runtime_snapshot: false,
             enable_high_validator_count_metrics: false,
+            validator_metrics_labels: None,
+            validator_metrics_pubkeys: vec![],
             beacon_nodes_tls_certs: None,
+            beacon_nodes_tls_client_identity: None,
+            beacon_nodes_api_token_file: None,
             builder_proposals: false,
             builder_registration_timestamp_override: None,
             gas_limit: None,
             broadcast_topics: vec![ApiTopic::Subscriptions],
+            beacon_node_selection: BeaconNodeSelection::default(),
+            beacon_node_routes: BeaconNodeRoutes::default(),
             enable_latency_measurement_service: true,
+            performance_tracking_epochs: None,
             validator_registration_batch_size: 500,
             enable_web3signer_slashing_protection: true,
             builder_boost_factor: None,
             prefer_builder_proposals: false,
+            proposer_only: false,
+            proposer_prefetch_slots: DEFAULT_PROPOSER_PREFETCH_SLOTS,
             distributed: false,
+            distributed_duty_delay: None,
+            early_attestation_min_delay: None,
+            sync_committee_jitter: None,
             web3_signer_keep_alive_timeout: DEFAULT_WEB3SIGNER_KEEP_ALIVE,
             web3_signer_max_idle_connections: None,
+            web3_signer_signing_timeouts: SigningTimeouts::default(),
+            web3_signer_health_check_interval: None,
+            slashing_protection_export_path: None,
+            slashing_protection_export_interval: DEFAULT_SLASHING_PROTECTION_EXPORT_INTERVAL,
+            signing_lease_file: None,
+            signing_lease_timeout: DEFAULT_SIGNING_LEASE_TIMEOUT,
+            signing_lease_clock_skew: DEFAULT_SIGNING_LEASE_CLOCK_SKEW,
         }
     }
 }
@@ -196,6 +273,31 @@ impl Config {
         config.init_slashing_protection = cli_args.get_flag("init-slashing-protection");
         config.use_long_timeouts = cli_args.get_flag("use-long-timeouts");
 
+        config.slashing_protection_export_path =
+            parse_optional(cli_args, "slashing-protection-export-path")?;
+        if let Some(secs) = parse_optional::<u64>(cli_args, "slashing-protection-export-interval")?
+        {
+            if secs == 0 {
+                return Err("slashing-protection-export-interval must be greater than 0".into());
+            }
+            config.slashing_protection_export_interval = Duration::from_secs(secs);
+        }
+
+        config.signing_lease_file = parse_optional(cli_args, "signing-lease-file")?;
+        if let Some(secs) = parse_optional::<u64>(cli_args, "signing-lease-timeout")? {
+            let timeout = Duration::from_secs(secs);
+            if timeout < MIN_SIGNING_LEASE_TIMEOUT {
+                return Err(format!(
+                    "signing-lease-timeout must be at least {} seconds",
+                    MIN_SIGNING_LEASE_TIMEOUT.as_secs()
+                ));
+            }
+            config.signing_lease_timeout = timeout;
+        }
+        if let Some(secs) = parse_optional::<u64>(cli_args, "signing-lease-clock-skew")? {
+            config.signing_lease_clock_skew = Duration::from_secs(secs);
+        }
+
         if let Some(graffiti_file_path) = cli_args.get_one::<String>("graffiti-file") {
             let mut graffiti_file = GraffitiFile::new(graffiti_file_path.into());
             graffiti_file
@@ -230,14 +332,46 @@ impl Config {
             config.fee_recipient = Some(input_fee_recipient);
         }
 
+        if let Some(fee_recipient_file_path) =
+            cli_args.get_one::<String>("suggested-fee-recipient-file")
+        {
+            let mut fee_recipient_file = FeeRecipientFile::new(fee_recipient_file_path.into());
+            fee_recipient_file
+                .read_fee_recipient_file()
+                .map_err(|e| format!("Error reading fee recipient file: {:?}", e))?;
+            config.fee_recipient_file = Some(fee_recipient_file);
+            info!(log, "Successfully loaded fee recipient file"; "path" => fee_recipient_file_path);
+        }
+
         if let Some(tls_certs) = parse_optional::<String>(cli_args, "beacon-nodes-tls-certs")? {
             config.beacon_nodes_tls_certs = Some(tls_certs.split(',').map(PathBuf::from).collect());
         }
 
+        if let (Some(cert), Some(key)) = (
+            parse_optional::<PathBuf>(cli_args, "beacon-nodes-tls-client-cert")?,
+            parse_optional::<PathBuf>(cli_args, "beacon-nodes-tls-client-key")?,
+        ) {
+            config.beacon_nodes_tls_client_identity = Some((cert, key));
+        }
+
+        config.beacon_nodes_api_token_file =
+            parse_optional(cli_args, "beacon-nodes-api-token-file")?;
+
         if cli_args.get_flag("distributed") {
             config.distributed = true;
         }
 
+        config.distributed_duty_delay =
+            parse_optional::<u64>(cli_args, "distributed-duty-delay")?.map(Duration::from_millis);
+
+        if cli_args.get_flag("early-attestations") {
+            let min_delay: u64 = parse_required(cli_args, "early-attestation-min-delay")?;
+            config.early_attestation_min_delay = Some(Duration::from_millis(min_delay));
+        }
+
+        config.sync_committee_jitter =
+            parse_optional::<u64>(cli_args, "sync-committee-jitter")?.map(Duration::from_millis);
+
         if cli_args.get_flag("disable-run-on-all") {
             warn!(
                 log,
@@ -258,6 +392,25 @@ impl Config {
                 .collect::<Result<_, _>>()?;
         }
 
+        if let Some(selection) =
+            parse_optional::<BeaconNodeSelection>(cli_args, "beacon-node-selection")?
+        {
+            config.beacon_node_selection = selection;
+        }
+
+        if let Some(routes) = parse_optional::<BeaconNodeRoutes>(cli_args, "beacon-node-routes")? {
+            if let Some(max_index) = routes.max_index() {
+                if max_index >= config.beacon_nodes.len() {
+                    return Err(format!(
+                        "Beacon node route refers to index {max_index}, but only {} beacon \
+                         nodes are configured",
+                        config.beacon_nodes.len()
+                    ));
+                }
+            }
+            config.beacon_node_routes = routes;
+        }
+
         /*
          * Web3 signer
          */
@@ -273,6 +426,20 @@ impl Config {
         if let Some(n) = parse_optional::<usize>(cli_args, "web3-signer-max-idle-connections")? {
             config.web3_signer_max_idle_connections = Some(n);
         }
+        config.web3_signer_signing_timeouts = SigningTimeouts {
+            block: parse_optional::<u64>(cli_args, "web3-signer-block-timeout")?
+                .map(Duration::from_millis),
+            attestation: parse_optional::<u64>(cli_args, "web3-signer-attestation-timeout")?
+                .map(Duration::from_millis),
+            aggregate: parse_optional::<u64>(cli_args, "web3-signer-aggregate-timeout")?
+                .map(Duration::from_millis),
+        };
+        if let Some(secs) = parse_optional::<u64>(cli_args, "web3-signer-health-check-interval")? {
+            if secs == 0 {
+                return Err("web3-signer-health-check-interval must be greater than 0".into());
+            }
+            config.web3_signer_health_check_interval = Some(Duration::from_secs(secs));
+        }
 
         /*
          * Http API server
@@ -330,6 +497,28 @@ impl Config {
             config.enable_high_validator_count_metrics = true;
         }
 
+        config.validator_metrics_labels =
+            parse_optional::<ValidatorMetricsLabels>(cli_args, "validator-metrics-labels")?;
+
+        if let Some(pubkeys) = cli_args.get_one::<String>("validator-metrics-pubkeys") {
+            if config.validator_metrics_labels != Some(ValidatorMetricsLabels::Monitored) {
+                return Err("--validator-metrics-pubkeys requires \
+                    --validator-metrics-labels monitored"
+                    .to_string());
+            }
+            config.validator_metrics_pubkeys = pubkeys
+                .split(',')
+                .map(|pubkey| {
+                    PublicKeyBytes::from_str(pubkey.trim())
+                        .map_err(|e| format!("Invalid validator metrics pubkey: {}", e))
+                })
+                .collect::<Result<_, _>>()?;
+        } else if config.validator_metrics_labels == Some(ValidatorMetricsLabels::Monitored) {
+            return Err("--validator-metrics-labels monitored requires \
+                --validator-metrics-pubkeys"
+                .to_string());
+        }
+
         if let Some(address) = cli_args.get_one::<String>("metrics-address") {
             config.http_metrics.listen_addr = address
                 .parse::<IpAddr>()
@@ -373,6 +562,8 @@ impl Config {
             config.enable_doppelganger_protection = true;
         }
 
+        config.enable_runtime_snapshot = cli_args.get_flag("enable-runtime-snapshot");
+
         if cli_args.get_flag("builder-proposals") {
             config.builder_proposals = true;
         }
@@ -381,6 +572,10 @@ impl Config {
             config.prefer_builder_proposals = true;
         }
 
+        config.proposer_only = cli_args.get_flag("proposer-only");
+        config.proposer_prefetch_slots =
+            parse_required(cli_args, "proposer-duties-prefetch-slots")?;
+
         if cli_args.get_flag("produce-block-v3") {
             warn!(
                 log,
@@ -424,6 +619,12 @@ impl Config {
             );
         }
 
+        config.performance_tracking_epochs =
+            parse_optional(cli_args, "performance-tracking-epochs")?;
+        if config.performance_tracking_epochs == Some(0) {
+            return Err("performance-tracking-epochs cannot be 0".to_string());
+        }
+
         config.validator_registration_batch_size =
             parse_required(cli_args, "validator-registration-batch-size")?;
         if config.validator_registration_batch_size == 0 {
@@ -11,6 +11,10 @@
 //!
 //! If a doppelganger is detected, the entire validator client will exit.
 //!
+//! Operators may override the service for individual validators via the HTTP API, either to
+//! release a validator before detection completes or to re-arm detection (e.g. after migrating a
+//! validator from another client).
+//!
 //! For validators started during the genesis epoch, there is **no doppelganger protection!**. This
 //! prevents a stale-mate where all validators will cease to function for a few epochs and then all
 //! start at the same time.
@@ -33,6 +37,7 @@ use crate::beacon_node_fallback::{BeaconNodeFallback, RequireSynced};
 use crate::validator_store::ValidatorStore;
 use crate::OfflineOnFailure;
 use environment::RuntimeContext;
+use eth2::lighthouse_vc::types::DoppelgangerStatusData;
 use eth2::types::LivenessResponseData;
 use parking_lot::RwLock;
 use slog::{crit, error, info, Logger};
@@ -151,6 +156,24 @@ impl DoppelgangerState {
     }
 }
 
+/// The most recent liveness data observed for a validator during doppelganger detection.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LivenessObservation {
+    /// The latest epoch for which liveness data has been received.
+    pub latest_epoch: Option<Epoch>,
+    /// The latest epoch in which the validator was observed to be live, if any.
+    pub latest_live_epoch: Option<Epoch>,
+}
+
+impl LivenessObservation {
+    fn observe(&mut self, response: &LivenessResponseData) {
+        self.latest_epoch = self.latest_epoch.max(Some(response.epoch));
+        if response.is_live {
+            self.latest_live_epoch = self.latest_live_epoch.max(Some(response.epoch));
+        }
+    }
+}
+
 /// Perform two requests to the BN to obtain the liveness data for `validator_indices`. One
 /// request will pertain to the `current_epoch`, the other to the `previous_epoch`.
 ///
@@ -270,6 +293,10 @@ async fn beacon_node_liveness<'a, T: 'static + SlotClock, E: EthSpec>(
 
 pub struct DoppelgangerService {
     doppelganger_states: RwLock<HashMap<PublicKeyBytes, DoppelgangerState>>,
+    /// The liveness data observed for each validator, used for reporting only.
+    ///
+    /// This lock must never be held at the same time as `doppelganger_states`.
+    liveness_observations: RwLock<HashMap<PublicKeyBytes, LivenessObservation>>,
     log: Logger,
 }
 
@@ -277,6 +304,7 @@ impl DoppelgangerService {
     pub fn new(log: Logger) -> Self {
         Self {
             doppelganger_states: <_>::default(),
+            liveness_observations: <_>::default(),
             log,
         }
     }
@@ -388,6 +416,133 @@ impl DoppelgangerService {
             })
     }
 
+    /// Restores the detection progress of `validator` from a snapshot taken in `snapshot_epoch` by
+    /// a previous run of the validator client.
+    ///
+    /// A validator for which a doppelganger was detected remains blocked from signing. Any other
+    /// validator must still pass at least `DEFAULT_REMAINING_DETECTION_EPOCHS` checks, since a
+    /// duplicate may have started whilst the validator client wasn't running. The checks start
+    /// from the epoch after the snapshot, so that they cover that downtime.
+    ///
+    /// Returns `false` if the validator is not registered with the service, in which case nothing
+    /// is changed.
+    pub fn restore_progress(
+        &self,
+        validator: PublicKeyBytes,
+        snapshot_epoch: Epoch,
+        remaining_epochs: u64,
+    ) -> bool {
+        let mut states = self.doppelganger_states.write();
+        let Some(state) = states.get_mut(&validator) else {
+            return false;
+        };
+        // A validator registered in the genesis epoch has no checks to restore.
+        if state.remaining_epochs == 0 {
+            return true;
+        }
+        *state = DoppelgangerState {
+            next_check_epoch: std::cmp::min(
+                state.next_check_epoch,
+                snapshot_epoch.saturating_add(1_u64),
+            ),
+            remaining_epochs: std::cmp::max(remaining_epochs, DEFAULT_REMAINING_DETECTION_EPOCHS),
+        };
+        true
+    }
+
+    /// Returns the detection state of all validators known to the doppelganger service.
+    pub fn status_data(&self) -> Vec<DoppelgangerStatusData> {
+        let mut status_data = self
+            .doppelganger_states
+            .read()
+            .iter()
+            .map(|(pubkey, state)| DoppelgangerStatusData {
+                pubkey: *pubkey,
+                signing_enabled: !state.requires_further_checks(),
+                detected: state.remaining_epochs == u64::MAX,
+                remaining_epochs: state.remaining_epochs,
+                next_check_epoch: state.next_check_epoch,
+                latest_observed_epoch: None,
+                latest_live_epoch: None,
+            })
+            .collect::<Vec<_>>();
+
+        // The states lock is dropped before reading the observations.
+        let observations = self.liveness_observations.read();
+        for data in &mut status_data {
+            if let Some(observation) = observations.get(&data.pubkey) {
+                data.latest_observed_epoch = observation.latest_epoch;
+                data.latest_live_epoch = observation.latest_live_epoch;
+            }
+        }
+
+        status_data
+    }
+
+    /// Allow `validator` to start signing immediately, skipping any remaining detection epochs.
+    ///
+    /// Returns an error if the validator is unknown or if a doppelganger has been detected.
+    pub fn release_validator(&self, validator: PublicKeyBytes) -> Result<(), String> {
+        let mut states = self.doppelganger_states.write();
+        let state = states
+            .get_mut(&validator)
+            .ok_or_else(|| format!("validator {:?} is unknown to doppelganger", validator))?;
+
+        if state.remaining_epochs == u64::MAX {
+            return Err("a doppelganger has been detected, refusing to release".to_string());
+        }
+        state.remaining_epochs = 0;
+
+        info!(
+            self.log,
+            "Doppelganger detection released by operator";
+            "msg" => "starting validator",
+            "validator" => ?validator,
+        );
+
+        Ok(())
+    }
+
+    /// Restart doppelganger detection for `validator`, preventing it from signing until
+    /// `DEFAULT_REMAINING_DETECTION_EPOCHS` further epochs have been checked.
+    ///
+    /// Unlike `Self::register_new_validator`, detection is applied even during the genesis epoch.
+    pub fn rearm_validator<E: EthSpec, T: SlotClock>(
+        &self,
+        validator: PublicKeyBytes,
+        slot_clock: &T,
+    ) -> Result<(), String> {
+        let current_epoch = slot_clock
+            .now_or_genesis()
+            .ok_or_else(|| "Unable to read slot clock when re-arming validator".to_string())?
+            .epoch(E::slots_per_epoch());
+
+        {
+            let mut states = self.doppelganger_states.write();
+            let state = states
+                .get_mut(&validator)
+                .ok_or_else(|| format!("validator {:?} is unknown to doppelganger", validator))?;
+
+            if state.remaining_epochs == u64::MAX {
+                return Err("a doppelganger has been detected, refusing to re-arm".to_string());
+            }
+            *state = DoppelgangerState {
+                next_check_epoch: current_epoch.saturating_add(1_u64),
+                remaining_epochs: DEFAULT_REMAINING_DETECTION_EPOCHS,
+            };
+        }
+        self.liveness_observations.write().remove(&validator);
+
+        info!(
+            self.log,
+            "Doppelganger detection re-armed by operator";
+            "remaining_epochs" => DEFAULT_REMAINING_DETECTION_EPOCHS,
+            "validator" => ?validator,
+        );
+
+        Ok(())
+    }
+
     /// Register a new validator with the doppelganger service.
     ///
     /// Validators added during the genesis epoch will not have doppelganger protection applied to
@@ -571,6 +726,19 @@ impl DoppelgangerService {
             }
         }
 
+        // Record the observations for reporting via the API.
+        {
+            let mut observations = self.liveness_observations.write();
+            for response in previous_epoch_responses
+                .iter()
+                .chain(current_epoch_responses.iter())
+            {
+                if let Some(pubkey) = indices_map.get(&response.index) {
+                    observations.entry(*pubkey).or_default().observe(response);
+                }
+            }
+        }
+
         let violators_exist = !violators.is_empty();
         if violators_exist {
             crit!(
@@ -977,6 +1145,60 @@ mod test {
             .assert_unregistered(2);
     }
 
+    #[test]
+    fn restore_progress() {
+        let epoch = genesis_epoch() + 4;
+        let scenario = TestBuilder::default()
+            .build()
+            .set_slot(epoch.start_slot(E::slots_per_epoch()))
+            .register_all_in_doppelganger_protection_if_enabled();
+        let validator = |index: usize| scenario.validators[index];
+
+        // A validator which had completed its checks must be checked again, from the epoch after
+        // the snapshot so that the downtime is covered.
+        assert!(scenario
+            .doppelganger
+            .restore_progress(validator(0), epoch - 2, 0));
+        // A validator which was part-way through its checks must still complete them.
+        assert!(scenario.doppelganger.restore_progress(
+            validator(1),
+            epoch,
+            DEFAULT_REMAINING_DETECTION_EPOCHS + 1
+        ));
+        // A validator for which a doppelganger was detected remains blocked.
+        assert!(scenario
+            .doppelganger
+            .restore_progress(validator(2), epoch, u64::MAX));
+        // Unregistered validators are not restored.
+        assert!(!scenario
+            .doppelganger
+            .restore_progress(PublicKeyBytes::empty(), epoch, 0));
+
+        scenario
+            .assert_all_disabled()
+            .assert_state(
+                0,
+                &DoppelgangerState {
+                    next_check_epoch: epoch - 1,
+                    remaining_epochs: DEFAULT_REMAINING_DETECTION_EPOCHS,
+                },
+            )
+            .assert_state(
+                1,
+                &DoppelgangerState {
+                    next_check_epoch: epoch + 1,
+                    remaining_epochs: DEFAULT_REMAINING_DETECTION_EPOCHS + 1,
+                },
+            )
+            .assert_state(
+                2,
+                &DoppelgangerState {
+                    next_check_epoch: epoch + 1,
+                    remaining_epochs: u64::MAX,
+                },
+            );
+    }
+
     enum ShouldShutdown {
         Yes,
         No,
@@ -1184,6 +1406,95 @@ mod test {
             });
     }
 
+    #[test]
+    fn operator_release_and_rearm() {
+        let epoch = genesis_epoch() + 1;
+        let slot = epoch.start_slot(E::slots_per_epoch());
+
+        let scenario = TestBuilder::default()
+            .build()
+            .set_slot(slot)
+            .register_all_in_doppelganger_protection_if_enabled()
+            .assert_all_disabled();
+
+        for validator in &scenario.validators {
+            scenario.doppelganger.release_validator(*validator).unwrap();
+        }
+        let scenario = scenario.assert_all_enabled().set_slot(slot + 1);
+
+        for validator in &scenario.validators {
+            scenario
+                .doppelganger
+                .rearm_validator::<E, _>(*validator, &scenario.slot_clock)
+                .unwrap();
+        }
+        scenario
+            .assert_all_disabled()
+            .assert_all_states(&DoppelgangerState {
+                next_check_epoch: epoch + 1,
+                remaining_epochs: DEFAULT_REMAINING_DETECTION_EPOCHS,
+            });
+    }
+
+    #[test]
+    fn operator_override_refused_after_detection() {
+        let epoch = genesis_epoch() + 1;
+        let slot = epoch.start_slot(E::slots_per_epoch());
+        let unknown = PublicKeyBytes::empty();
+
+        let scenario = TestBuilder::default()
+            .build()
+            .set_slot(slot)
+            .register_all_in_doppelganger_protection_if_enabled();
+        assert!(scenario.doppelganger.release_validator(unknown).is_err());
+
+        for state in scenario
+            .doppelganger
+            .doppelganger_states
+            .write()
+            .values_mut()
+        {
+            state.remaining_epochs = u64::MAX;
+        }
+        let validator = scenario.validators[0];
+        assert!(scenario.doppelganger.release_validator(validator).is_err());
+        assert!(scenario
+            .doppelganger
+            .rearm_validator::<E, _>(validator, &scenario.slot_clock)
+            .is_err());
+        assert!(scenario
+            .doppelganger
+            .status_data()
+            .iter()
+            .all(|status| status.detected && !status.signing_enabled));
+    }
+
+    #[test]
+    fn status_data_includes_observations() {
+        let epoch = genesis_epoch() + 1;
+        let slot = epoch.start_slot(E::slots_per_epoch());
+
+        let scenario = TestBuilder::default()
+            .build()
+            .set_slot(slot)
+            .register_all_in_doppelganger_protection_if_enabled()
+            .simulate_detect_doppelgangers(
+                slot,
+                ShouldShutdown::No,
+                |current_epoch, detection_indices: Vec<_>| {
+                    future::ready(get_false_responses(current_epoch, &detection_indices))
+                },
+            );
+
+        let status_data = scenario.doppelganger.status_data();
+        assert_eq!(status_data.len(), DEFAULT_VALIDATORS);
+        for status in status_data {
+            assert!(!status.signing_enabled);
+            assert_eq!(status.latest_observed_epoch, Some(epoch));
+            assert_eq!(status.latest_live_epoch, None);
+        }
+    }
+
     #[test]
     fn no_doppelgangers_for_adequate_time() {
         let initial_epoch = genesis_epoch() + 42;
@@ -6,10 +6,15 @@
 //! The `DutiesService` is also responsible for sending events to the `BlockService` which trigger
 //! block production.
 
+pub mod selection_proofs;
 pub mod sync;
 
-use crate::beacon_node_fallback::{ApiTopic, BeaconNodeFallback, OfflineOnFailure, RequireSynced};
-use crate::http_metrics::metrics::{get_int_gauge, set_int_gauge, ATTESTATION_DUTY};
+use crate::beacon_node_fallback::{
+    ApiTopic, BeaconNodeFallback, OfflineOnFailure, RequestClass, RequireSynced,
+};
+use crate::http_metrics::metrics::{
+    get_int_gauge, set_int_gauge, ATTESTATION_DUTIES_PER_SLOT, ATTESTATION_DUTY,
+};
 use crate::{
     block_service::BlockServiceNotification,
     http_metrics::metrics,
@@ -22,13 +27,16 @@ use eth2::types::{
 use futures::{stream, StreamExt};
 use parking_lot::RwLock;
 use safe_arith::{ArithError, SafeArith};
+use selection_proofs::{process_selection_proof_queue, SelectionProofQueue};
+use serde::{Deserialize, Serialize};
 use slog::{debug, error, info, warn, Logger};
 use slot_clock::SlotClock;
 use std::cmp::min;
-use std::collections::{hash_map, BTreeMap, HashMap, HashSet};
+use std::collections::{hash_map, HashMap, HashSet};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
+use strum::{EnumString, EnumVariantNames};
 use sync::poll_sync_committee_duties;
 use sync::SyncDutiesMap;
 use tokio::{sync::mpsc::Sender, time::sleep};
@@ -37,6 +45,24 @@ use types::{ChainSpec, Epoch, EthSpec, Hash256, PublicKeyBytes, SelectionProof,
 /// Only retain `HISTORICAL_DUTIES_EPOCHS` duties prior to the current epoch.
 const HISTORICAL_DUTIES_EPOCHS: u64 = 2;
 
+/// By default, download the proposer duties for the next epoch during the last
+/// `DEFAULT_PROPOSER_PREFETCH_SLOTS` slots of the current epoch.
+///
+/// Proposer duties for the next epoch are only tentative until the last block of the current epoch
+/// is known, but they rarely change. Holding them in advance means proposals at the start of the
+/// next epoch don't depend on the beacon node being reachable at the epoch boundary. Prefetched
+/// duties are replaced by the duties downloaded once their epoch starts, and any differences are
+/// reported.
+///
+/// Attester duties don't need this, they are always polled one epoch in advance and re-downloaded
+/// whenever their dependent root changes. Beacon nodes don't serve duties any further ahead than
+/// the next epoch, as they depend on blocks which don't exist yet, so neither kind of duty can be
+/// prefetched for later epochs.
+pub const DEFAULT_PROPOSER_PREFETCH_SLOTS: u64 = 2;
+
+/// The maximum number of validators whose indices are resolved by each request to the beacon node.
+const VALIDATOR_INDEX_BATCH_SIZE: usize = 1024;
+
 /// Compute attestation selection proofs this many slots before they are required.
 ///
 /// At start-up selection proofs will be computed with less lookahead out of necessity.
@@ -53,6 +79,19 @@ const SELECTION_PROOF_SCHEDULE_DENOM: u32 = 2;
 /// flag in the cli to enable collection of per validator metrics.
 const VALIDATOR_METRICS_MIN_COUNT: usize = 64;
 
+/// The validators which are given their own label in per-validator metrics. The metrics of all
+/// other validators are only collected as aggregates, keeping the number of series bounded.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize, EnumString, EnumVariantNames)]
+#[strum(serialize_all = "kebab-case")]
+pub enum ValidatorMetricsLabels {
+    /// Every validator has its own label.
+    All,
+    /// Only the validators in `DutiesService::metrics_monitored_validators` have their own label.
+    Monitored,
+    /// No validator has its own label.
+    None,
+}
+
 /// The number of validators to request duty information for in the initial request.
 /// The initial request is used to determine if further requests are required, so that it
 /// reduces the amount of data that needs to be transferred.
@@ -207,7 +246,17 @@ impl SubscriptionSlots {
 type DependentRoot = Hash256;
 
 type AttesterMap = HashMap<PublicKeyBytes, HashMap<Epoch, (DependentRoot, DutyAndProof)>>;
-type ProposerMap = HashMap<Epoch, (DependentRoot, Vec<ProposerData>)>;
+type ProposerMap = HashMap<Epoch, ProposerDuties>;
+
+/// The proposer duties of all local validators in some epoch.
+pub struct ProposerDuties {
+    pub dependent_root: DependentRoot,
+    pub duties: Vec<ProposerData>,
+    /// Set if the duties were downloaded before the start of their epoch, in which case the
+    /// `dependent_root` is the head of the beacon node at the time (rather than the actual
+    /// dependent root) and the duties may still change.
+    pub prefetched: bool,
+}
 
 /// See the module-level documentation.
 pub struct DutiesService<T, E: EthSpec> {
@@ -218,6 +267,8 @@ pub struct DutiesService<T, E: EthSpec> {
     pub proposers: RwLock<ProposerMap>,
     /// Map from validator index to sync committee duties.
     pub sync_duties: SyncDutiesMap<E>,
+    /// Duties which are waiting for their selection proofs to be signed.
+    pub selection_proof_queue: SelectionProofQueue,
     /// Provides the canonical list of locally-managed validators.
     pub validator_store: Arc<ValidatorStore<T, E>>,
     /// Maps unknown validator pubkeys to the next slot time when a poll should be conducted again.
@@ -230,10 +281,21 @@ pub struct DutiesService<T, E: EthSpec> {
     pub context: RuntimeContext<E>,
     /// The current chain spec.
     pub spec: ChainSpec,
-    //// Whether we permit large validator counts in the metrics.
-    pub enable_high_validator_count_metrics: bool,
+    /// The validators with their own label in per-validator metrics. If `None`, every validator
+    /// has its own label as long as there are at most `VALIDATOR_METRICS_MIN_COUNT` validators.
+    pub validator_metrics_labels: Option<ValidatorMetricsLabels>,
+    /// The validators with their own label under `ValidatorMetricsLabels::Monitored`.
+    pub metrics_monitored_validators: HashSet<PublicKeyBytes>,
     /// If this validator is running in distributed mode.
     pub distributed: bool,
+    /// Additional delay applied before producing attestations and sync committee messages.
+    pub distributed_duty_delay: Duration,
+    /// If set, only proposer duties are tracked. Attestation and sync committee duties are
+    /// performed by other validator clients.
+    pub proposer_only: bool,
+    /// The number of slots at the end of each epoch during which the proposer duties of the next
+    /// epoch are prefetched, see `DEFAULT_PROPOSER_PREFETCH_SLOTS`.
+    pub proposer_prefetch_slots: u64,
 }
 
 impl<T: SlotClock + 'static, E: EthSpec> DutiesService<T, E> {
@@ -249,15 +311,13 @@ impl<T: SlotClock + 'static, E: EthSpec> DutiesService<T, E> {
             .validator_store
             .voting_pubkeys(DoppelgangerStatus::only_safe);
 
-        self.proposers
-            .read()
-            .get(&epoch)
-            .map_or(0, |(_, proposers)| {
-                proposers
-                    .iter()
-                    .filter(|proposer_data| signing_pubkeys.contains(&proposer_data.pubkey))
-                    .count()
-            })
+        self.proposers.read().get(&epoch).map_or(0, |proposers| {
+            proposers
+                .duties
+                .iter()
+                .filter(|proposer_data| signing_pubkeys.contains(&proposer_data.pubkey))
+                .count()
+        })
     }
 
     /// Returns the total number of validators that should attest in the given epoch.
@@ -277,9 +337,16 @@ impl<T: SlotClock + 'static, E: EthSpec> DutiesService<T, E> {
 
     /// Returns the total number of validators that are in a doppelganger detection period.
     pub fn doppelganger_detecting_count(&self) -> usize {
+        // Without the signing lease no validator signs, regardless of doppelganger protection.
+        if !self.validator_store.signing_lease().allows_signing() {
+            return 0;
+        }
         self.validator_store
             .voting_pubkeys::<HashSet<_>, _>(DoppelgangerStatus::only_unsafe)
-            .len()
+            .into_iter()
+            // Watch-only validators are never safe to sign, but aren't detecting doppelgangers.
+            .filter(|pubkey| !self.validator_store.is_watch_only(pubkey))
+            .count()
     }
 
     /// Returns the pubkeys of the validators which are assigned to propose in the given slot.
@@ -297,8 +364,9 @@ impl<T: SlotClock + 'static, E: EthSpec> DutiesService<T, E> {
         self.proposers
             .read()
             .get(&epoch)
-            .map(|(_, proposers)| {
+            .map(|proposers| {
                 proposers
+                    .duties
                     .iter()
                     .filter(|proposer_data| {
                         proposer_data.slot == slot
@@ -332,10 +400,17 @@ impl<T: SlotClock + 'static, E: EthSpec> DutiesService<T, E> {
             .collect()
     }
 
-    /// Returns `true` if we should collect per validator metrics and `false` otherwise.
-    pub fn per_validator_metrics(&self) -> bool {
-        self.enable_high_validator_count_metrics
-            || self.total_validator_count() <= VALIDATOR_METRICS_MIN_COUNT
+    /// Returns `true` if we should collect per validator metrics for `pubkey` and `false`
+    /// otherwise.
+    pub fn per_validator_metrics(&self, pubkey: &PublicKeyBytes) -> bool {
+        match self.validator_metrics_labels {
+            Some(ValidatorMetricsLabels::All) => true,
+            Some(ValidatorMetricsLabels::Monitored) => {
+                self.metrics_monitored_validators.contains(pubkey)
+            }
+            Some(ValidatorMetricsLabels::None) => false,
+            None => self.total_validator_count() <= VALIDATOR_METRICS_MIN_COUNT,
+        }
     }
 }
 
@@ -407,6 +482,11 @@ pub fn start_update_service<T: SlotClock + 'static, E: EthSpec>(
         "duties_service_proposers",
     );
 
+    // Attestation and sync committee duties are performed elsewhere in proposer-only mode.
+    if core_duties_service.proposer_only {
+        return;
+    }
+
     /*
      * Spawn the task which keeps track of local attestation duties.
      */
@@ -466,6 +546,34 @@ pub fn start_update_service<T: SlotClock + 'static, E: EthSpec>(
         },
         "duties_service_sync_committee",
     );
+
+    // Spawn the task which signs the selection proofs waiting in the queue.
+    let duties_service = core_duties_service.clone();
+    core_duties_service.context.executor.spawn(
+        async move {
+            // At halfway through each slot when nothing else is likely to be getting signed, sign
+            // the selection proofs which are due.
+            let slot_duration = duties_service.slot_clock.slot_duration();
+            let slot_offset = slot_duration / SELECTION_PROOF_SCHEDULE_DENOM;
+            loop {
+                if let Some(duration) = duties_service.slot_clock.duration_to_next_slot() {
+                    if duration > slot_offset {
+                        sleep(duration - slot_offset).await;
+                    } else {
+                        sleep(duration + slot_duration - slot_offset).await;
+                    }
+                } else {
+                    // Just sleep for one slot if we are unable to read the system clock, this gives
+                    // us an opportunity for the clock to eventually come good.
+                    sleep(slot_duration).await;
+                    continue;
+                }
+
+                process_selection_proof_queue(&duties_service).await;
+            }
+        },
+        "duties_service_selection_proofs",
+    );
 }
 
 /// Iterate through all the voting pubkeys in the `ValidatorStore` and attempt to learn any unknown
@@ -486,93 +594,123 @@ async fn poll_validator_indices<T: SlotClock + 'static, E: EthSpec>(
         .validator_store
         .voting_pubkeys(DoppelgangerStatus::ignored);
 
-    for pubkey in all_pubkeys {
-        // This is on its own line to avoid some weirdness with locks and if statements.
-        let is_known = duties_service
-            .validator_store
-            .initialized_validators()
-            .read()
-            .get_index(&pubkey)
-            .is_some();
+    let current_slot_opt = duties_service.slot_clock.now();
+    let unknown_pubkeys = all_pubkeys
+        .into_iter()
+        .filter(|pubkey| {
+            // This is on its own line to avoid some weirdness with locks and if statements.
+            let is_known = duties_service
+                .validator_store
+                .initialized_validators()
+                .read()
+                .get_index(pubkey)
+                .is_some();
+            if is_known {
+                return false;
+            }
 
-        if !is_known {
-            let current_slot_opt = duties_service.slot_clock.now();
+            let Some(current_slot) = current_slot_opt else {
+                return true;
+            };
+            let is_first_slot_of_epoch = current_slot % E::slots_per_epoch() == 0;
 
-            if let Some(current_slot) = current_slot_opt {
-                let is_first_slot_of_epoch = current_slot % E::slots_per_epoch() == 0;
+            // Query an unknown validator later if it was queried within the last epoch, or if
+            // the current slot is the first slot of an epoch.
+            let poll_later = duties_service
+                .unknown_validator_next_poll_slots
+                .read()
+                .get(pubkey)
+                .map(|&poll_slot| poll_slot > current_slot || is_first_slot_of_epoch)
+                .unwrap_or(false);
+            !poll_later
+        })
+        .collect::<Vec<_>>();
 
-                // Query an unknown validator later if it was queried within the last epoch, or if
-                // the current slot is the first slot of an epoch.
-                let poll_later = duties_service
-                    .unknown_validator_next_poll_slots
-                    .read()
-                    .get(&pubkey)
-                    .map(|&poll_slot| poll_slot > current_slot || is_first_slot_of_epoch)
-                    .unwrap_or(false);
-                if poll_later {
-                    continue;
-                }
-            }
+    // Resolve the indices of many validators with each request, rather than one request per
+    // validator.
+    for batch in unknown_pubkeys.chunks(VALIDATOR_INDEX_BATCH_SIZE) {
+        let ids = batch
+            .iter()
+            .map(|pubkey| ValidatorId::PublicKey(*pubkey))
+            .collect::<Vec<_>>();
+        let ids = &ids;
 
-            // Query the remote BN to resolve a pubkey to a validator index.
-            let download_result = duties_service
-                .beacon_nodes
-                .first_success(
-                    RequireSynced::No,
-                    OfflineOnFailure::Yes,
-                    |beacon_node| async move {
-                        let _timer = metrics::start_timer_vec(
-                            &metrics::DUTIES_SERVICE_TIMES,
-                            &[metrics::VALIDATOR_ID_HTTP_GET],
-                        );
-                        beacon_node
-                            .get_beacon_states_validator_id(
-                                StateId::Head,
-                                &ValidatorId::PublicKey(pubkey),
-                            )
-                            .await
-                    },
-                )
-                .await;
+        // Query the remote BN to resolve the pubkeys to validator indices.
+        let download_result = duties_service
+            .beacon_nodes
+            .first_success(
+                RequireSynced::No,
+                OfflineOnFailure::Yes,
+                |beacon_node| async move {
+                    let _timer = metrics::start_timer_vec(
+                        &metrics::DUTIES_SERVICE_TIMES,
+                        &[metrics::VALIDATOR_ID_HTTP_POST],
+                    );
+                    beacon_node
+                        .post_beacon_states_validators(StateId::Head, Some(ids.clone()), None)
+                        .await
+                },
+            )
+            .await;
+
+        let indices = match download_result {
+            Ok(response) => response
+                .map(|response| response.data)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|validator| (validator.validator.pubkey, validator.index))
+                .collect::<HashMap<_, _>>(),
+            // Don't exit early on an error, keep attempting to resolve other indices.
+            Err(e) => {
+                error!(
+                    log,
+                    "Failed to resolve pubkeys to indices";
+                    "error" => %e,
+                    "count" => batch.len(),
+                );
+                continue;
+            }
+        };
 
+        for pubkey in batch {
             let fee_recipient = duties_service
                 .validator_store
-                .get_fee_recipient(&pubkey)
+                .get_fee_recipient(pubkey)
                 .map(|fr| fr.to_string())
                 .unwrap_or_else(|| {
                     "Fee recipient for validator not set in validator_definitions.yml \
                     or provided with the `--suggested-fee-recipient` flag"
                         .to_string()
                 });
-            match download_result {
-                Ok(Some(response)) => {
+            match indices.get(pubkey) {
+                Some(&index) => {
                     info!(
                         log,
                         "Validator exists in beacon chain";
                         "pubkey" => ?pubkey,
-                        "validator_index" => response.data.index,
+                        "validator_index" => index,
                         "fee_recipient" => fee_recipient
                     );
                     duties_service
                         .validator_store
                         .initialized_validators()
                         .write()
-                        .set_index(&pubkey, response.data.index);
+                        .set_index(pubkey, index);
 
                     duties_service
                         .unknown_validator_next_poll_slots
                         .write()
-                        .remove(&pubkey);
+                        .remove(pubkey);
                 }
                 // This is not necessarily an error, it just means the validator is not yet known to
                 // the beacon chain.
-                Ok(None) => {
+                None => {
                     if let Some(current_slot) = current_slot_opt {
                         let next_poll_slot = current_slot.saturating_add(E::slots_per_epoch());
                         duties_service
                             .unknown_validator_next_poll_slots
                             .write()
-                            .insert(pubkey, next_poll_slot);
+                            .insert(*pubkey, next_poll_slot);
                     }
 
                     debug!(
@@ -582,16 +720,6 @@ async fn poll_validator_indices<T: SlotClock + 'static, E: EthSpec>(
                         "fee_recipient" => fee_recipient
                     )
                 }
-                // Don't exit early on an error, keep attempting to resolve other indices.
-                Err(e) => {
-                    error!(
-                        log,
-                        "Failed to resolve pubkey to index";
-                        "error" => %e,
-                        "pubkey" => ?pubkey,
-                        "fee_recipient" => fee_recipient
-                    )
-                }
             }
         }
     }
@@ -959,14 +1087,10 @@ async fn poll_beacon_attesters_for_epoch<T: SlotClock + 'static, E: EthSpec>(
     }
     drop(attesters);
 
-    // Spawn the background task to compute selection proofs.
-    let subservice = duties_service.clone();
-    duties_service.context.executor.spawn(
-        async move {
-            fill_in_selection_proofs(subservice, new_duties, dependent_root).await;
-        },
-        "duties_service_selection_proofs_background",
-    );
+    // Queue the selection proofs to be computed in the background.
+    duties_service
+        .selection_proof_queue
+        .push_attestation_duties(dependent_root, new_duties);
 
     Ok(())
 }
@@ -989,44 +1113,61 @@ fn get_uninitialized_validators<T: SlotClock + 'static, E: EthSpec>(
         .collect::<Vec<_>>()
 }
 
+/// Updates the attestation duty slot of each validator with its own label, and the number of
+/// duties in each slot of `epoch` for the remaining validators.
 fn update_per_validator_duty_metrics<T: SlotClock + 'static, E: EthSpec>(
     duties_service: &Arc<DutiesService<T, E>>,
     epoch: Epoch,
     current_slot: Slot,
 ) {
-    if duties_service.per_validator_metrics() {
-        let attesters = duties_service.attesters.read();
-        attesters.values().for_each(|attester_duties_by_epoch| {
-            if let Some((_, duty_and_proof)) = attester_duties_by_epoch.get(&epoch) {
-                let duty = &duty_and_proof.duty;
-                let validator_index = duty.validator_index;
-                let duty_slot = duty.slot;
-                if let Some(existing_slot_gauge) =
-                    get_int_gauge(&ATTESTATION_DUTY, &[&validator_index.to_string()])
+    let mut unlabelled_duties_per_slot = vec![0; E::slots_per_epoch() as usize];
+    let attesters = duties_service.attesters.read();
+    attesters.values().for_each(|attester_duties_by_epoch| {
+        if let Some((_, duty_and_proof)) = attester_duties_by_epoch.get(&epoch) {
+            let duty = &duty_and_proof.duty;
+            let validator_index = duty.validator_index;
+            let duty_slot = duty.slot;
+            if !duties_service.per_validator_metrics(&duty.pubkey) {
+                let slot_index = duty_slot.as_usize() % unlabelled_duties_per_slot.len();
+                unlabelled_duties_per_slot[slot_index] += 1;
+            } else if let Some(existing_slot_gauge) =
+                get_int_gauge(&ATTESTATION_DUTY, &[&validator_index.to_string()])
+            {
+                let existing_slot = Slot::new(existing_slot_gauge.get() as u64);
+                let existing_epoch = existing_slot.epoch(E::slots_per_epoch());
+
+                // First condition ensures that we switch to the next epoch duty slot
+                // once the current epoch duty slot passes.
+                // Second condition is to ensure that next epoch duties don't override
+                // current epoch duties.
+                if existing_slot < current_slot
+                    || (duty_slot.epoch(E::slots_per_epoch()) <= existing_epoch
+                        && duty_slot > current_slot
+                        && duty_slot != existing_slot)
                 {
-                    let existing_slot = Slot::new(existing_slot_gauge.get() as u64);
-                    let existing_epoch = existing_slot.epoch(E::slots_per_epoch());
-
-                    // First condition ensures that we switch to the next epoch duty slot
-                    // once the current epoch duty slot passes.
-                    // Second condition is to ensure that next epoch duties don't override
-                    // current epoch duties.
-                    if existing_slot < current_slot
-                        || (duty_slot.epoch(E::slots_per_epoch()) <= existing_epoch
-                            && duty_slot > current_slot
-                            && duty_slot != existing_slot)
-                    {
-                        existing_slot_gauge.set(duty_slot.as_u64() as i64);
-                    }
-                } else {
-                    set_int_gauge(
-                        &ATTESTATION_DUTY,
-                        &[&validator_index.to_string()],
-                        duty_slot.as_u64() as i64,
-                    );
+                    existing_slot_gauge.set(duty_slot.as_u64() as i64);
                 }
+            } else {
+                set_int_gauge(
+                    &ATTESTATION_DUTY,
+                    &[&validator_index.to_string()],
+                    duty_slot.as_u64() as i64,
+                );
             }
-        });
+        }
+    });
+
+    let relative_epoch = if epoch == current_slot.epoch(E::slots_per_epoch()) {
+        "current"
+    } else {
+        "next"
+    };
+    for (slot_index, count) in unlabelled_duties_per_slot.into_iter().enumerate() {
+        set_int_gauge(
+            &ATTESTATION_DUTIES_PER_SLOT,
+            &[relative_epoch, &slot_index.to_string()],
+            count,
+        );
     }
 }
 
@@ -1037,7 +1178,8 @@ async fn post_validator_duties_attester<T: SlotClock + 'static, E: EthSpec>(
 ) -> Result<DutiesResponse<Vec<AttesterData>>, Error> {
     duties_service
         .beacon_nodes
-        .first_success(
+        .first_success_for(
+            RequestClass::Duties,
             RequireSynced::No,
             OfflineOnFailure::Yes,
             |beacon_node| async move {
@@ -1056,157 +1198,143 @@ async fn post_validator_duties_attester<T: SlotClock + 'static, E: EthSpec>(
 
 /// Compute the attestation selection proofs for the `duties` and add them to the `attesters` map.
 ///
-/// Duties are computed in batches each slot. If a re-org is detected then the process will
-/// terminate early as it is assumed the selection proofs from `duties` are no longer relevant.
+/// Each duty is paired with the dependent root of the duties it was downloaded with. Duties which
+/// have since been replaced due to a re-org are skipped, as their selection proofs are no longer
+/// relevant.
 async fn fill_in_selection_proofs<T: SlotClock + 'static, E: EthSpec>(
-    duties_service: Arc<DutiesService<T, E>>,
-    duties: Vec<AttesterData>,
-    dependent_root: Hash256,
+    duties_service: &DutiesService<T, E>,
+    duties: Vec<(DependentRoot, AttesterData)>,
 ) {
     let log = duties_service.context.log();
 
-    // Sort duties by slot in a BTreeMap.
-    let mut duties_by_slot: BTreeMap<Slot, Vec<_>> = BTreeMap::new();
+    // Avoid signing selection proofs for duties which have been re-orged out.
+    let duties = {
+        let attesters = duties_service.attesters.read();
+        duties
+            .into_iter()
+            .filter(|(dependent_root, duty)| {
+                let epoch = duty.slot.epoch(E::slots_per_epoch());
+                attesters
+                    .get(&duty.pubkey)
+                    .and_then(|attester_map| attester_map.get(&epoch))
+                    .map_or(true, |(existing_dependent_root, _)| {
+                        existing_dependent_root == dependent_root
+                    })
+            })
+            .collect::<Vec<_>>()
+    };
 
-    for duty in duties {
-        duties_by_slot.entry(duty.slot).or_default().push(duty);
+    if duties.is_empty() {
+        return;
     }
 
-    // At halfway through each slot when nothing else is likely to be getting signed, sign a batch
-    // of selection proofs and insert them into the duties service `attesters` map.
-    let slot_clock = &duties_service.slot_clock;
-    let slot_offset = duties_service.slot_clock.slot_duration() / SELECTION_PROOF_SCHEDULE_DENOM;
+    let batch_size = duties.len();
+    let timer = metrics::start_timer_vec(
+        &metrics::DUTIES_SERVICE_TIMES,
+        &[metrics::ATTESTATION_SELECTION_PROOFS],
+    );
 
-    while !duties_by_slot.is_empty() {
-        if let Some(duration) = slot_clock.duration_to_next_slot() {
-            sleep(duration.saturating_sub(slot_offset)).await;
+    // Sign selection proofs (serially).
+    let duty_and_proof_results = stream::iter(duties)
+        .then(|(dependent_root, duty)| async move {
+            let opt_selection_proof =
+                make_selection_proof(&duty, &duties_service.validator_store, &duties_service.spec)
+                    .await?;
+            Ok((dependent_root, duty, opt_selection_proof))
+        })
+        .collect::<Vec<_>>()
+        .await;
 
-            let Some(current_slot) = slot_clock.now() else {
+    // Add to attesters store.
+    let current_slot = duties_service
+        .slot_clock
+        .now_or_genesis()
+        .unwrap_or_default();
+    let mut attesters = duties_service.attesters.write();
+    for result in duty_and_proof_results {
+        let (dependent_root, duty, selection_proof) = match result {
+            Ok(duty_and_proof) => duty_and_proof,
+            Err(Error::FailedToProduceSelectionProof(ValidatorStoreError::UnknownPubkey(
+                pubkey,
+            ))) => {
+                // A pubkey can be missing when a validator was recently
+                // removed via the API.
+                warn!(
+                    log,
+                    "Missing pubkey for duty and proof";
+                    "info" => "a validator may have recently been removed from this VC",
+                    "pubkey" => ?pubkey,
+                );
+                // Do not abort the entire batch for a single failure.
                 continue;
-            };
-
-            let selection_lookahead = if duties_service.distributed {
-                SELECTION_PROOF_SLOT_LOOKAHEAD_DVT
-            } else {
-                SELECTION_PROOF_SLOT_LOOKAHEAD
-            };
-
-            let lookahead_slot = current_slot + selection_lookahead;
-
-            let mut relevant_duties = duties_by_slot.split_off(&lookahead_slot);
-            std::mem::swap(&mut relevant_duties, &mut duties_by_slot);
-
-            let batch_size = relevant_duties.values().map(Vec::len).sum::<usize>();
-
-            if batch_size == 0 {
+            }
+            // Watch-only validators never aggregate, and without the signing lease another
+            // instance aggregates, so neither needs selection proofs.
+            Err(Error::FailedToProduceSelectionProof(
+                ValidatorStoreError::WatchOnly(_) | ValidatorStoreError::SigningLeaseNotHeld(_),
+            )) => {
                 continue;
             }
+            Err(e) => {
+                error!(
+                    log,
+                    "Failed to produce duty and proof";
+                    "error" => ?e,
+                    "msg" => "may impair attestation duties"
+                );
+                // Do not abort the entire batch for a single failure.
+                continue;
+            }
+        };
 
-            let timer = metrics::start_timer_vec(
-                &metrics::DUTIES_SERVICE_TIMES,
-                &[metrics::ATTESTATION_SELECTION_PROOFS],
-            );
-
-            // Sign selection proofs (serially).
-            let duty_and_proof_results = stream::iter(relevant_duties.into_values().flatten())
-                .then(|duty| async {
-                    let opt_selection_proof = make_selection_proof(
-                        &duty,
-                        &duties_service.validator_store,
-                        &duties_service.spec,
-                    )
-                    .await?;
-                    Ok((duty, opt_selection_proof))
-                })
-                .collect::<Vec<_>>()
-                .await;
-
-            // Add to attesters store.
-            let mut attesters = duties_service.attesters.write();
-            for result in duty_and_proof_results {
-                let (duty, selection_proof) = match result {
-                    Ok(duty_and_proof) => duty_and_proof,
-                    Err(Error::FailedToProduceSelectionProof(
-                        ValidatorStoreError::UnknownPubkey(pubkey),
-                    )) => {
-                        // A pubkey can be missing when a validator was recently
-                        // removed via the API.
-                        warn!(
-                            log,
-                            "Missing pubkey for duty and proof";
-                            "info" => "a validator may have recently been removed from this VC",
-                            "pubkey" => ?pubkey,
-                        );
-                        // Do not abort the entire batch for a single failure.
-                        continue;
-                    }
-                    Err(e) => {
-                        error!(
-                            log,
-                            "Failed to produce duty and proof";
-                            "error" => ?e,
-                            "msg" => "may impair attestation duties"
-                        );
-                        // Do not abort the entire batch for a single failure.
-                        continue;
-                    }
+        let attester_map = attesters.entry(duty.pubkey).or_default();
+        let epoch = duty.slot.epoch(E::slots_per_epoch());
+        match attester_map.entry(epoch) {
+            hash_map::Entry::Occupied(mut entry) => {
+                // No need to update duties for which no proof was computed.
+                let Some(selection_proof) = selection_proof else {
+                    continue;
                 };
 
-                let attester_map = attesters.entry(duty.pubkey).or_default();
-                let epoch = duty.slot.epoch(E::slots_per_epoch());
-                match attester_map.entry(epoch) {
-                    hash_map::Entry::Occupied(mut entry) => {
-                        // No need to update duties for which no proof was computed.
-                        let Some(selection_proof) = selection_proof else {
-                            continue;
-                        };
-
-                        let (existing_dependent_root, existing_duty) = entry.get_mut();
-
-                        if *existing_dependent_root == dependent_root {
-                            // Replace existing proof.
-                            existing_duty.selection_proof = Some(selection_proof);
-                        } else {
-                            // Our selection proofs are no longer relevant due to a reorg, abandon
-                            // this entire background process.
-                            debug!(
-                                log,
-                                "Stopping selection proof background task";
-                                "reason" => "re-org"
-                            );
-                            return;
-                        }
-                    }
-                    hash_map::Entry::Vacant(entry) => {
-                        // This probably shouldn't happen, but we have enough info to fill in the
-                        // entry so we may as well.
-                        let subscription_slots = SubscriptionSlots::new(duty.slot, current_slot);
-                        let duty_and_proof = DutyAndProof {
-                            duty,
-                            selection_proof,
-                            subscription_slots,
-                        };
-                        entry.insert((dependent_root, duty_and_proof));
-                    }
+                let (existing_dependent_root, existing_duty) = entry.get_mut();
+
+                if *existing_dependent_root == dependent_root {
+                    // Replace existing proof.
+                    existing_duty.selection_proof = Some(selection_proof);
+                } else {
+                    // Our selection proof is no longer relevant due to a re-org that happened
+                    // whilst it was being signed.
+                    debug!(
+                        log,
+                        "Discarding selection proof";
+                        "reason" => "re-org",
+                        "validator_index" => duty.validator_index,
+                    );
                 }
             }
-            drop(attesters);
-
-            let time_taken_ms =
-                Duration::from_secs_f64(timer.map_or(0.0, |t| t.stop_and_record())).as_millis();
-            debug!(
-                log,
-                "Computed attestation selection proofs";
-                "batch_size" => batch_size,
-                "lookahead_slot" => lookahead_slot,
-                "time_taken_ms" => time_taken_ms
-            );
-        } else {
-            // Just sleep for one slot if we are unable to read the system clock, this gives
-            // us an opportunity for the clock to eventually come good.
-            sleep(duties_service.slot_clock.slot_duration()).await;
+            hash_map::Entry::Vacant(entry) => {
+                // This probably shouldn't happen, but we have enough info to fill in the
+                // entry so we may as well.
+                let subscription_slots = SubscriptionSlots::new(duty.slot, current_slot);
+                let duty_and_proof = DutyAndProof {
+                    duty,
+                    selection_proof,
+                    subscription_slots,
+                };
+                entry.insert((dependent_root, duty_and_proof));
+            }
         }
     }
+    drop(attesters);
+
+    let time_taken_ms =
+        Duration::from_secs_f64(timer.map_or(0.0, |t| t.stop_and_record())).as_millis();
+    debug!(
+        log,
+        "Computed attestation selection proofs";
+        "batch_size" => batch_size,
+        "time_taken_ms" => time_taken_ms
+    );
 }
 
 /// Download the proposer duties for the current epoch and store them in `duties_service.proposers`.
@@ -1227,11 +1355,12 @@ async fn fill_in_selection_proofs<T: SlotClock + 'static, E: EthSpec>(
 /// However, we also have the slashing protection as a second line of defence. These two factors
 /// provide an acceptable level of safety.
 ///
-/// It's important to note that since there is a 0-epoch look-ahead (i.e., no look-ahead) for block
-/// proposers then it's very likely that a proposal for the first slot of the epoch will need go
-/// through the slow path every time. I.e., the proposal will only happen after we've been able to
-/// download and process the duties from the BN. This means it is very important to ensure this
-/// function is as fast as possible.
+/// The proposer duties for the next epoch are prefetched near the end of each epoch (see
+/// `PROPOSER_PREFETCH_SLOTS`), so a proposal for the first slot of an epoch can usually take the
+/// fast path. Beacon nodes are not required to serve next-epoch proposer duties though, so when
+/// prefetching fails the proposal will only happen after we've been able to download and process
+/// the duties from the BN. This means it is very important to ensure this function is as fast as
+/// possible.
 async fn poll_beacon_proposers<T: SlotClock + 'static, E: EthSpec>(
     duties_service: &DutiesService<T, E>,
     block_service_tx: &mut Sender<BlockServiceNotification>,
@@ -1273,7 +1402,8 @@ async fn poll_beacon_proposers<T: SlotClock + 'static, E: EthSpec>(
     if !local_pubkeys.is_empty() {
         let download_result = duties_service
             .beacon_nodes
-            .first_success(
+            .first_success_for(
+                RequestClass::Duties,
                 RequireSynced::No,
                 OfflineOnFailure::Yes,
                 |beacon_node| async move {
@@ -1305,12 +1435,45 @@ async fn poll_beacon_proposers<T: SlotClock + 'static, E: EthSpec>(
                     "num_relevant_duties" => relevant_duties.len(),
                 );
 
-                if let Some((prior_dependent_root, _)) = duties_service
+                // The last block of the previous epoch may have changed the duties since they were
+                // prefetched.
+                let prefetched_duties_changed = duties_service
+                    .proposers
+                    .read()
+                    .get(&current_epoch)
+                    .is_some_and(|prior| {
+                        prior.prefetched && proposer_duties_changed(&prior.duties, &relevant_duties)
+                    });
+
+                let duties = ProposerDuties {
+                    dependent_root,
+                    duties: relevant_duties,
+                    prefetched: false,
+                };
+                if let Some(prior) = duties_service
                     .proposers
                     .write()
-                    .insert(current_epoch, (dependent_root, relevant_duties))
+                    .insert(current_epoch, duties)
                 {
-                    if dependent_root != prior_dependent_root {
+                    let prior_dependent_root = prior.dependent_root;
+                    if prefetched_duties_changed {
+                        warn!(
+                            log,
+                            "Prefetched proposer duties changed";
+                            "prior_head_root" => %prior_dependent_root,
+                            "dependent_root" => %dependent_root,
+                            "msg" => "this may happen from time to time"
+                        );
+                        metrics::inc_counter(&metrics::PREFETCHED_PROPOSER_DUTIES_CHANGED);
+                    } else if prior.prefetched {
+                        // The dependent root of prefetched duties is only a placeholder.
+                        debug!(
+                            log,
+                            "Replaced prefetched proposer duties";
+                            "prior_head_root" => %prior_dependent_root,
+                            "dependent_root" => %dependent_root,
+                        )
+                    } else if dependent_root != prior_dependent_root {
                         warn!(
                             log,
                             "Proposer duties re-org";
@@ -1360,6 +1523,10 @@ async fn poll_beacon_proposers<T: SlotClock + 'static, E: EthSpec>(
             );
             metrics::inc_counter(&metrics::PROPOSAL_CHANGED);
         }
+
+        if should_prefetch_proposers::<E>(current_slot, duties_service.proposer_prefetch_slots) {
+            prefetch_beacon_proposers(duties_service, current_epoch + 1, &local_pubkeys).await;
+        }
     }
 
     // Prune old duties.
@@ -1371,6 +1538,107 @@ async fn poll_beacon_proposers<T: SlotClock + 'static, E: EthSpec>(
     Ok(())
 }
 
+/// Returns `true` if the proposer duties for the next epoch should be downloaded at `slot`, when
+/// they are prefetched during the last `prefetch_slots` slots of each epoch.
+fn should_prefetch_proposers<E: EthSpec>(slot: Slot, prefetch_slots: u64) -> bool {
+    let slots_per_epoch = E::slots_per_epoch();
+    prefetch_slots > 0 && slot.as_u64() % slots_per_epoch + prefetch_slots >= slots_per_epoch
+}
+
+/// Returns `true` if `new` doesn't assign the same proposals to the same validators as `prior`.
+fn proposer_duties_changed(prior: &[ProposerData], new: &[ProposerData]) -> bool {
+    let proposals = |duties: &[ProposerData]| {
+        duties
+            .iter()
+            .map(|duty| (duty.slot, duty.pubkey))
+            .collect::<HashSet<_>>()
+    };
+    proposals(prior) != proposals(new)
+}
+
+/// Download the tentative proposer duties for `next_epoch` and store them in
+/// `duties_service.proposers`, unless duties downloaded during `next_epoch` are already known.
+///
+/// Failures are not an error, the duties will be downloaded again once `next_epoch` starts.
+async fn prefetch_beacon_proposers<T: SlotClock + 'static, E: EthSpec>(
+    duties_service: &DutiesService<T, E>,
+    next_epoch: Epoch,
+    local_pubkeys: &HashSet<PublicKeyBytes>,
+) {
+    let log = duties_service.context.log();
+
+    // Not all beacon nodes serve next-epoch proposer duties, so don't mark them offline if they
+    // refuse.
+    let download_result = duties_service
+        .beacon_nodes
+        .first_success_for(
+            RequestClass::Duties,
+            RequireSynced::No,
+            OfflineOnFailure::No,
+            |beacon_node| async move {
+                let _timer = metrics::start_timer_vec(
+                    &metrics::DUTIES_SERVICE_TIMES,
+                    &[metrics::PROPOSER_DUTIES_PREFETCH_HTTP_GET],
+                );
+                beacon_node.get_validator_duties_proposer(next_epoch).await
+            },
+        )
+        .await;
+
+    let response = match download_result {
+        Ok(response) => response,
+        Err(e) => {
+            debug!(
+                log,
+                "Unable to prefetch proposer duties";
+                "epoch" => next_epoch,
+                "err" => %e,
+            );
+            return;
+        }
+    };
+
+    let relevant_duties = response
+        .data
+        .into_iter()
+        .filter(|proposer_duty| local_pubkeys.contains(&proposer_duty.pubkey))
+        .collect::<Vec<_>>();
+
+    debug!(
+        log,
+        "Prefetched proposer duties";
+        "epoch" => next_epoch,
+        "head_root" => %response.dependent_root,
+        "num_relevant_duties" => relevant_duties.len(),
+    );
+
+    let mut proposers = duties_service.proposers.write();
+    let prior = proposers.get(&next_epoch);
+    if let Some(prior) = prior.filter(|prior| prior.prefetched) {
+        // A re-org of the current epoch can change the duties of the next epoch. The prefetched
+        // duties are replaced either way, so that they match the head of the beacon node.
+        if proposer_duties_changed(&prior.duties, &relevant_duties) {
+            debug!(
+                log,
+                "Prefetched proposer duties re-org";
+                "epoch" => next_epoch,
+                "prior_head_root" => %prior.dependent_root,
+                "head_root" => %response.dependent_root,
+            );
+        }
+    }
+    if prior.map_or(true, |duties| duties.prefetched) {
+        proposers.insert(
+            next_epoch,
+            ProposerDuties {
+                dependent_root: response.dependent_root,
+                duties: relevant_duties,
+                prefetched: true,
+            },
+        );
+    }
+}
+
 /// Notify the block service if it should produce a block.
 async fn notify_block_production_service<T: SlotClock + 'static, E: EthSpec>(
     current_slot: Slot,
@@ -1407,6 +1675,64 @@ async fn notify_block_production_service<T: SlotClock + 'static, E: EthSpec>(
 mod test {
     use super::*;
 
+    #[test]
+    fn prefetch_proposers_at_end_of_epoch() {
+        type E = types::MainnetEthSpec;
+        let slots_per_epoch = E::slots_per_epoch();
+
+        let prefetch_slots = |epoch: Epoch, prefetch_slots| {
+            let start_slot = epoch.start_slot(slots_per_epoch);
+            (0..slots_per_epoch)
+                .filter(|&i| should_prefetch_proposers::<E>(start_slot + i, prefetch_slots))
+                .collect::<Vec<_>>()
+        };
+
+        for epoch in [Epoch::new(0), Epoch::new(7)] {
+            let default_slots = slots_per_epoch - DEFAULT_PROPOSER_PREFETCH_SLOTS..slots_per_epoch;
+            assert_eq!(
+                prefetch_slots(epoch, DEFAULT_PROPOSER_PREFETCH_SLOTS),
+                default_slots.collect::<Vec<_>>()
+            );
+            assert_eq!(prefetch_slots(epoch, 1), vec![slots_per_epoch - 1]);
+            // Prefetching can be disabled, or last for the whole epoch.
+            assert!(prefetch_slots(epoch, 0).is_empty());
+            for whole_epoch in [slots_per_epoch, slots_per_epoch * 2] {
+                assert_eq!(
+                    prefetch_slots(epoch, whole_epoch),
+                    (0..slots_per_epoch).collect::<Vec<_>>()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn prefetched_proposer_duties_changes() {
+        let duty_of = |validator: u8, slot: u64| ProposerData {
+            pubkey: PublicKeyBytes::deserialize(&[validator; 48]).unwrap(),
+            validator_index: validator.into(),
+            slot: Slot::new(slot),
+        };
+
+        let prior = vec![duty_of(1, 33), duty_of(2, 40)];
+        // The order of the duties doesn't matter.
+        assert!(!proposer_duties_changed(
+            &prior,
+            &[duty_of(2, 40), duty_of(1, 33)]
+        ));
+        // A proposal moved to another slot, or another validator.
+        assert!(proposer_duties_changed(
+            &prior,
+            &[duty_of(1, 34), duty_of(2, 40)]
+        ));
+        assert!(proposer_duties_changed(
+            &prior,
+            &[duty_of(3, 33), duty_of(2, 40)]
+        ));
+        // A proposal was gained or lost.
+        assert!(proposer_duties_changed(&prior, &[duty_of(1, 33)]));
+        assert!(proposer_duties_changed(&[], &[duty_of(0, 32)]));
+    }
+
     #[test]
     fn subscription_slots_exact() {
         // Set current slot in the past so no duties are considered expired.
@@ -0,0 +1,250 @@
+//! A queue of duties which require selection proofs.
+//!
+//! Signing selection proofs can be slow for a VC with many validators or a remote signer. Rather
+//! than signing them whilst processing duties, the duties are pushed to this queue and signed by a
+//! single background task. Using a single task (rather than one per batch of duties) ensures the
+//! signer isn't flooded with concurrent requests, and that the most urgent proofs are signed first.
+
+use super::sync::fill_in_aggregation_proofs;
+use super::{
+    fill_in_selection_proofs, DependentRoot, DutiesService, SELECTION_PROOF_SLOT_LOOKAHEAD,
+    SELECTION_PROOF_SLOT_LOOKAHEAD_DVT,
+};
+use crate::http_metrics::metrics;
+use eth2::types::AttesterData;
+use parking_lot::Mutex;
+use slog::debug;
+use slot_clock::SlotClock;
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::Instant;
+use types::{EthSpec, Slot, SyncDuty};
+
+/// Attestation duties and the dependent root of the duties they were downloaded with.
+type AttestationJobs = BTreeMap<Slot, Vec<(DependentRoot, AttesterData)>>;
+/// Sync duties and their sync committee period.
+type SyncJobs = BTreeMap<Slot, Vec<(u64, SyncDuty)>>;
+
+/// Duties waiting for their selection proofs to be signed, keyed by the slot at which the proofs
+/// are required.
+#[derive(Default)]
+pub struct SelectionProofQueue {
+    attestations: Mutex<AttestationJobs>,
+    sync: Mutex<SyncJobs>,
+}
+
+impl SelectionProofQueue {
+    /// Queue selection proofs for attestation `duties`.
+    ///
+    /// Each proof is signed once its duty is within the selection proof lookahead.
+    pub fn push_attestation_duties(
+        &self,
+        dependent_root: DependentRoot,
+        duties: Vec<AttesterData>,
+    ) {
+        let mut attestations = self.attestations.lock();
+        for duty in duties {
+            attestations
+                .entry(duty.slot)
+                .or_default()
+                .push((dependent_root, duty));
+        }
+        drop(attestations);
+        self.update_metrics();
+    }
+
+    /// Queue sync selection proofs for all slots from `current_slot` to `pre_compute_slot`
+    /// (inclusive).
+    ///
+    /// Each entry of `duties` is paired with the slot from which its proofs are not yet known.
+    pub fn push_sync_duties(
+        &self,
+        sync_committee_period: u64,
+        current_slot: Slot,
+        pre_compute_slot: Slot,
+        duties: &[(Slot, SyncDuty)],
+    ) {
+        let mut sync = self.sync.lock();
+        for slot in (current_slot.as_u64()..=pre_compute_slot.as_u64()).map(Slot::new) {
+            for (validator_start_slot, duty) in duties {
+                // Proofs are already known at this slot for this validator.
+                if slot < *validator_start_slot {
+                    continue;
+                }
+                sync.entry(slot)
+                    .or_default()
+                    .push((sync_committee_period, duty.clone()));
+            }
+        }
+        drop(sync);
+        self.update_metrics();
+    }
+
+    /// Returns the number of selection proofs waiting to be signed.
+    ///
+    /// A sync duty is counted once per slot, regardless of its number of subnets.
+    pub fn len(&self) -> usize {
+        let attestations = self
+            .attestations
+            .lock()
+            .values()
+            .map(Vec::len)
+            .sum::<usize>();
+        let sync = self.sync.lock().values().map(Vec::len).sum::<usize>();
+        attestations + sync
+    }
+
+    /// Returns `true` if there are no selection proofs waiting to be signed.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove the attestation duties prior to `lookahead_slot` and all of the sync duties from
+    /// the queue.
+    fn pop_due(&self, lookahead_slot: Slot) -> (AttestationJobs, SyncJobs) {
+        let attestations = {
+            let mut attestations = self.attestations.lock();
+            let later = attestations.split_off(&lookahead_slot);
+            std::mem::replace(&mut *attestations, later)
+        };
+        let sync = std::mem::take(&mut *self.sync.lock());
+        self.update_metrics();
+        (attestations, sync)
+    }
+
+    fn update_metrics(&self) {
+        metrics::set_gauge(&metrics::SELECTION_PROOF_QUEUE_LENGTH, self.len() as i64);
+    }
+}
+
+/// Sign the selection proofs which are due and store them in the duties service.
+///
+/// Proofs are signed in order of the slot at which they're required, so that a backlog of proofs
+/// for later slots can't delay those which are needed sooner.
+pub async fn process_selection_proof_queue<T: SlotClock + 'static, E: EthSpec>(
+    duties_service: &DutiesService<T, E>,
+) {
+    let log = duties_service.context.log();
+
+    let Some(current_slot) = duties_service.slot_clock.now() else {
+        return;
+    };
+
+    let selection_lookahead = if duties_service.distributed {
+        SELECTION_PROOF_SLOT_LOOKAHEAD_DVT
+    } else {
+        SELECTION_PROOF_SLOT_LOOKAHEAD
+    };
+    let lookahead_slot = current_slot + selection_lookahead;
+
+    let (mut attestations, mut sync) = duties_service.selection_proof_queue.pop_due(lookahead_slot);
+    if attestations.is_empty() && sync.is_empty() {
+        return;
+    }
+
+    let start = Instant::now();
+    let slots = attestations
+        .keys()
+        .chain(sync.keys())
+        .copied()
+        .collect::<BTreeSet<_>>();
+
+    for slot in slots {
+        if let Some(duties) = attestations.remove(&slot) {
+            fill_in_selection_proofs(duties_service, duties).await;
+        }
+
+        if let Some(duties) = sync.remove(&slot) {
+            let mut duties_by_period: BTreeMap<u64, Vec<SyncDuty>> = BTreeMap::new();
+            for (sync_committee_period, duty) in duties {
+                duties_by_period
+                    .entry(sync_committee_period)
+                    .or_default()
+                    .push(duty);
+            }
+            for (sync_committee_period, duties) in duties_by_period {
+                fill_in_aggregation_proofs(duties_service, &duties, sync_committee_period, slot)
+                    .await;
+            }
+        }
+    }
+
+    debug!(
+        log,
+        "Processed selection proof queue";
+        "current_slot" => current_slot,
+        "lookahead_slot" => lookahead_slot,
+        "time_taken_ms" => start.elapsed().as_millis(),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{Hash256, PublicKeyBytes};
+
+    fn attester_duty(slot: u64) -> AttesterData {
+        AttesterData {
+            pubkey: PublicKeyBytes::empty(),
+            validator_index: 0,
+            committees_at_slot: 1,
+            committee_index: 0,
+            committee_length: 1,
+            validator_committee_index: 0,
+            slot: Slot::new(slot),
+        }
+    }
+
+    fn sync_duty(validator_index: u64) -> SyncDuty {
+        SyncDuty {
+            pubkey: PublicKeyBytes::empty(),
+            validator_index,
+            validator_sync_committee_indices: vec![0],
+        }
+    }
+
+    #[test]
+    fn pop_due_attestations() {
+        let queue = SelectionProofQueue::default();
+        let root = Hash256::repeat_byte(1);
+        queue.push_attestation_duties(root, (0..8).map(attester_duty).collect());
+        assert_eq!(queue.len(), 8);
+
+        let (attestations, sync) = queue.pop_due(Slot::new(5));
+        assert!(sync.is_empty());
+        assert_eq!(
+            attestations.keys().copied().collect::<Vec<_>>(),
+            (0..5).map(Slot::new).collect::<Vec<_>>()
+        );
+        assert_eq!(queue.len(), 3);
+
+        let (attestations, _) = queue.pop_due(Slot::new(5));
+        assert!(attestations.is_empty());
+        assert_eq!(queue.len(), 3);
+    }
+
+    #[test]
+    fn push_sync_duties_from_start_slot() {
+        let queue = SelectionProofQueue::default();
+        let duties = [(Slot::new(10), sync_duty(0)), (Slot::new(12), sync_duty(1))];
+        queue.push_sync_duties(1, Slot::new(10), Slot::new(13), &duties);
+
+        let (attestations, sync) = queue.pop_due(Slot::new(0));
+        assert!(attestations.is_empty());
+        assert!(queue.is_empty());
+
+        let indices_at = |slot: u64| {
+            sync[&Slot::new(slot)]
+                .iter()
+                .map(|(period, duty)| {
+                    assert_eq!(*period, 1);
+                    duty.validator_index
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(sync.len(), 4);
+        assert_eq!(indices_at(10), vec![0]);
+        assert_eq!(indices_at(11), vec![0]);
+        assert_eq!(indices_at(12), vec![0, 1]);
+        assert_eq!(indices_at(13), vec![0, 1]);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::beacon_node_fallback::{OfflineOnFailure, RequireSynced};
+use crate::beacon_node_fallback::{OfflineOnFailure, RequestClass, RequireSynced};
 use crate::{
     doppelganger_service::DoppelgangerStatus,
     duties_service::{DutiesService, Error},
@@ -349,19 +349,11 @@ pub async fn poll_sync_committee_duties<T: SlotClock + 'static, E: EthSpec>(
         .prepare_for_aggregator_pre_compute(current_sync_committee_period, current_slot, spec);
 
     if !new_pre_compute_duties.is_empty() {
-        let sub_duties_service = duties_service.clone();
-        duties_service.context.executor.spawn(
-            async move {
-                fill_in_aggregation_proofs(
-                    sub_duties_service,
-                    &new_pre_compute_duties,
-                    current_sync_committee_period,
-                    current_slot,
-                    current_pre_compute_slot,
-                )
-                .await
-            },
-            "duties_service_sync_selection_proofs",
+        duties_service.selection_proof_queue.push_sync_duties(
+            current_sync_committee_period,
+            current_slot,
+            current_pre_compute_slot,
+            &new_pre_compute_duties,
         );
     }
 
@@ -393,19 +385,11 @@ pub async fn poll_sync_committee_duties<T: SlotClock + 'static, E: EthSpec>(
             .prepare_for_aggregator_pre_compute(next_sync_committee_period, current_slot, spec);
 
         if !new_pre_compute_duties.is_empty() {
-            let sub_duties_service = duties_service.clone();
-            duties_service.context.executor.spawn(
-                async move {
-                    fill_in_aggregation_proofs(
-                        sub_duties_service,
-                        &new_pre_compute_duties,
-                        next_sync_committee_period,
-                        current_slot,
-                        pre_compute_slot,
-                    )
-                    .await
-                },
-                "duties_service_sync_selection_proofs",
+            duties_service.selection_proof_queue.push_sync_duties(
+                next_sync_committee_period,
+                current_slot,
+                pre_compute_slot,
+                &new_pre_compute_duties,
             );
         }
     }
@@ -442,7 +426,8 @@ pub async fn poll_sync_committee_duties_for_period<T: SlotClock + 'static, E: Et
 
     let duties_response = duties_service
         .beacon_nodes
-        .first_success(
+        .first_success_for(
+            RequestClass::Duties,
             RequireSynced::No,
             OfflineOnFailure::Yes,
             |beacon_node| async move {
@@ -511,148 +496,139 @@ pub async fn poll_sync_committee_duties_for_period<T: SlotClock + 'static, E: Et
     Ok(())
 }
 
+/// Compute the sync selection proofs of `duties` for `slot` and add them to the sync duties map.
+///
+/// Proofs are signed for the slot prior to `slot`, which is the slot at which the signed
+/// contribution and proof should be broadcast.
 pub async fn fill_in_aggregation_proofs<T: SlotClock + 'static, E: EthSpec>(
-    duties_service: Arc<DutiesService<T, E>>,
-    pre_compute_duties: &[(Slot, SyncDuty)],
+    duties_service: &DutiesService<T, E>,
+    duties: &[SyncDuty],
     sync_committee_period: u64,
-    current_slot: Slot,
-    pre_compute_slot: Slot,
+    slot: Slot,
 ) {
     let log = duties_service.context.log();
 
-    debug!(
-        log,
-        "Calculating sync selection proofs";
-        "period" => sync_committee_period,
-        "current_slot" => current_slot,
-        "pre_compute_slot" => pre_compute_slot
-    );
-
-    // Generate selection proofs for each validator at each slot, one slot at a time.
-    for slot in (current_slot.as_u64()..=pre_compute_slot.as_u64()).map(Slot::new) {
-        let mut validator_proofs = vec![];
-        for (validator_start_slot, duty) in pre_compute_duties {
-            // Proofs are already known at this slot for this validator.
-            if slot < *validator_start_slot {
+    let mut validator_proofs = vec![];
+    for duty in duties {
+        let subnet_ids = match duty.subnet_ids::<E>() {
+            Ok(subnet_ids) => subnet_ids,
+            Err(e) => {
+                crit!(
+                    log,
+                    "Arithmetic error computing subnet IDs";
+                    "error" => ?e,
+                );
                 continue;
             }
+        };
 
-            let subnet_ids = match duty.subnet_ids::<E>() {
-                Ok(subnet_ids) => subnet_ids,
+        // Create futures to produce proofs.
+        let futures = subnet_ids.iter().map(|subnet_id| async move {
+            // Construct proof for prior slot.
+            let proof_slot = slot - 1;
+
+            let proof = match duties_service
+                .validator_store
+                .produce_sync_selection_proof(&duty.pubkey, proof_slot, *subnet_id)
+                .await
+            {
+                Ok(proof) => proof,
+                Err(ValidatorStoreError::UnknownPubkey(pubkey)) => {
+                    // A pubkey can be missing when a validator was recently
+                    // removed via the API.
+                    debug!(
+                        log,
+                        "Missing pubkey for sync selection proof";
+                        "pubkey" => ?pubkey,
+                        "pubkey" => ?duty.pubkey,
+                        "slot" => proof_slot,
+                    );
+                    return None;
+                }
+                // Watch-only validators never aggregate, nor do validators without the signing
+                // lease.
+                Err(
+                    ValidatorStoreError::WatchOnly(_) | ValidatorStoreError::SigningLeaseNotHeld(_),
+                ) => return None,
                 Err(e) => {
-                    crit!(
+                    warn!(
                         log,
-                        "Arithmetic error computing subnet IDs";
+                        "Unable to sign selection proof";
                         "error" => ?e,
+                        "pubkey" => ?duty.pubkey,
+                        "slot" => proof_slot,
                     );
-                    continue;
+                    return None;
                 }
             };
 
-            // Create futures to produce proofs.
-            let duties_service_ref = &duties_service;
-            let futures = subnet_ids.iter().map(|subnet_id| async move {
-                // Construct proof for prior slot.
-                let proof_slot = slot - 1;
-
-                let proof = match duties_service_ref
-                    .validator_store
-                    .produce_sync_selection_proof(&duty.pubkey, proof_slot, *subnet_id)
-                    .await
-                {
-                    Ok(proof) => proof,
-                    Err(ValidatorStoreError::UnknownPubkey(pubkey)) => {
-                        // A pubkey can be missing when a validator was recently
-                        // removed via the API.
-                        debug!(
-                            log,
-                            "Missing pubkey for sync selection proof";
-                            "pubkey" => ?pubkey,
-                            "pubkey" => ?duty.pubkey,
-                            "slot" => proof_slot,
-                        );
-                        return None;
-                    }
-                    Err(e) => {
-                        warn!(
-                            log,
-                            "Unable to sign selection proof";
-                            "error" => ?e,
-                            "pubkey" => ?duty.pubkey,
-                            "slot" => proof_slot,
-                        );
-                        return None;
-                    }
-                };
-
-                match proof.is_aggregator::<E>() {
-                    Ok(true) => {
-                        debug!(
-                            log,
-                            "Validator is sync aggregator";
-                            "validator_index" => duty.validator_index,
-                            "slot" => proof_slot,
-                            "subnet_id" => %subnet_id,
-                        );
-                        Some(((proof_slot, *subnet_id), proof))
-                    }
-                    Ok(false) => None,
-                    Err(e) => {
-                        warn!(
-                            log,
-                            "Error determining is_aggregator";
-                            "pubkey" => ?duty.pubkey,
-                            "slot" => proof_slot,
-                            "error" => ?e,
-                        );
-                        None
-                    }
+            match proof.is_aggregator::<E>() {
+                Ok(true) => {
+                    debug!(
+                        log,
+                        "Validator is sync aggregator";
+                        "validator_index" => duty.validator_index,
+                        "slot" => proof_slot,
+                        "subnet_id" => %subnet_id,
+                    );
+                    Some(((proof_slot, *subnet_id), proof))
                 }
-            });
+                Ok(false) => None,
+                Err(e) => {
+                    warn!(
+                        log,
+                        "Error determining is_aggregator";
+                        "pubkey" => ?duty.pubkey,
+                        "slot" => proof_slot,
+                        "error" => ?e,
+                    );
+                    None
+                }
+            }
+        });
 
-            // Execute all the futures in parallel, collecting any successful results.
-            let proofs = join_all(futures)
-                .await
-                .into_iter()
-                .flatten()
-                .collect::<Vec<_>>();
+        // Execute all the futures in parallel, collecting any successful results.
+        let proofs = join_all(futures)
+            .await
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
 
-            validator_proofs.push((duty.validator_index, proofs));
-        }
+        validator_proofs.push((duty.validator_index, proofs));
+    }
 
-        // Add to global storage (we add regularly so the proofs can be used ASAP).
-        let sync_map = duties_service.sync_duties.committees.read();
-        let Some(committee_duties) = sync_map.get(&sync_committee_period) else {
+    // Add to global storage (we add regularly so the proofs can be used ASAP).
+    let sync_map = duties_service.sync_duties.committees.read();
+    let Some(committee_duties) = sync_map.get(&sync_committee_period) else {
+        debug!(
+            log,
+            "Missing sync duties";
+            "period" => sync_committee_period,
+        );
+        return;
+    };
+    let validators = committee_duties.validators.read();
+    let num_validators_updated = validator_proofs.len();
+
+    for (validator_index, proofs) in validator_proofs {
+        if let Some(Some(duty)) = validators.get(&validator_index) {
+            duty.aggregation_duties.proofs.write().extend(proofs);
+        } else {
             debug!(
                 log,
-                "Missing sync duties";
+                "Missing sync duty to update";
+                "validator_index" => validator_index,
                 "period" => sync_committee_period,
             );
-            continue;
-        };
-        let validators = committee_duties.validators.read();
-        let num_validators_updated = validator_proofs.len();
-
-        for (validator_index, proofs) in validator_proofs {
-            if let Some(Some(duty)) = validators.get(&validator_index) {
-                duty.aggregation_duties.proofs.write().extend(proofs);
-            } else {
-                debug!(
-                    log,
-                    "Missing sync duty to update";
-                    "validator_index" => validator_index,
-                    "period" => sync_committee_period,
-                );
-            }
         }
+    }
 
-        if num_validators_updated > 0 {
-            debug!(
-                log,
-                "Finished computing sync selection proofs";
-                "slot" => slot,
-                "updated_validators" => num_validators_updated,
-            );
-        }
+    if num_validators_updated > 0 {
+        debug!(
+            log,
+            "Finished computing sync selection proofs";
+            "slot" => slot,
+            "updated_validators" => num_validators_updated,
+        );
     }
 }
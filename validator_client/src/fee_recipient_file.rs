@@ -0,0 +1,313 @@
+use crate::http_metrics::metrics;
+use crate::validator_store::ValidatorStore;
+use bls::PublicKeyBytes;
+use environment::RuntimeContext;
+use serde::{Deserialize, Serialize};
+use slog::{debug, error, info};
+use slot_clock::SlotClock;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{prelude::*, BufReader};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::SystemTime;
+use tokio::time::sleep;
+use types::{Address, EthSpec};
+
+#[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
+pub enum Error {
+    InvalidFile(std::io::Error),
+    InvalidLine(String),
+    InvalidPublicKey(String),
+    InvalidFeeRecipient(String),
+    DuplicatePublicKey(PublicKeyBytes),
+}
+
+/// Struct to load validator fee recipients from file.
+/// The fee recipient file is expected to have the following structure
+///
+/// default: 0x00000000219ab540356cbb839cbe05303d7705fa
+/// public_key1: fee_recipient1
+/// public_key2: fee_recipient2
+/// ...
+///
+/// Empty lines are ignored.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeeRecipientFile {
+    fee_recipient_path: PathBuf,
+    fee_recipients: HashMap<PublicKeyBytes, Address>,
+    default: Option<Address>,
+    /// The modification time of the file when it was last read.
+    modified: Option<SystemTime>,
+}
+
+impl FeeRecipientFile {
+    pub fn new(fee_recipient_path: PathBuf) -> Self {
+        Self {
+            fee_recipient_path,
+            fee_recipients: HashMap::new(),
+            default: None,
+            modified: None,
+        }
+    }
+
+    /// Returns the fee recipient corresponding to the given public key if present, else returns
+    /// the default fee recipient.
+    pub fn get_fee_recipient(&self, public_key: &PublicKeyBytes) -> Option<Address> {
+        self.fee_recipients
+            .get(public_key)
+            .copied()
+            .or(self.default)
+    }
+
+    /// Reads from a fee recipient file with the specified format and replaces the default value
+    /// and the hashmap.
+    ///
+    /// Returns an error if the file does not exist, or if the format is invalid. In that case
+    /// `self` is left unchanged.
+    pub fn read_fee_recipient_file(&mut self) -> Result<(), Error> {
+        let file = File::open(self.fee_recipient_path.as_path()).map_err(Error::InvalidFile)?;
+        let modified = file.metadata().and_then(|m| m.modified()).ok();
+        let reader = BufReader::new(file);
+
+        let mut fee_recipients = HashMap::new();
+        let mut default = None;
+        for line in reader.lines() {
+            let line = line.map_err(|e| Error::InvalidLine(e.to_string()))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match read_line(&line)? {
+                (Some(pk), fee_recipient) => {
+                    if fee_recipients.insert(pk, fee_recipient).is_some() {
+                        return Err(Error::DuplicatePublicKey(pk));
+                    }
+                }
+                (None, fee_recipient) => default = Some(fee_recipient),
+            }
+        }
+
+        self.fee_recipients = fee_recipients;
+        self.default = default;
+        self.modified = modified;
+        Ok(())
+    }
+
+    /// Returns `true` if the file on disk has been modified since it was last read.
+    ///
+    /// If the modification time cannot be read the file is assumed to have changed.
+    pub fn is_modified(&self) -> bool {
+        let modified = std::fs::metadata(&self.fee_recipient_path)
+            .and_then(|m| m.modified())
+            .ok();
+        modified.is_none() || modified != self.modified
+    }
+
+    /// Returns the number of entries (including the default) which differ between `self` and
+    /// `other`.
+    pub fn num_changed_entries(&self, other: &Self) -> usize {
+        let changed_keys = self
+            .fee_recipients
+            .iter()
+            .filter(|(pk, fee_recipient)| other.fee_recipients.get(pk) != Some(fee_recipient))
+            .count();
+        let new_keys = other
+            .fee_recipients
+            .keys()
+            .filter(|pk| !self.fee_recipients.contains_key(pk))
+            .count();
+        let default_changed = usize::from(self.default != other.default);
+
+        changed_keys + new_keys + default_changed
+    }
+}
+
+/// Parses a line from the fee recipient file.
+///
+/// `Ok((None, fee_recipient))` represents the fee recipient for the default key.
+/// `Ok((Some(pk), fee_recipient))` represents the fee recipient for the public key `pk`.
+/// Returns an error if the line is in the wrong format or does not contain a valid public key or
+/// address.
+fn read_line(line: &str) -> Result<(Option<PublicKeyBytes>, Address), Error> {
+    if let Some(i) = line.find(':') {
+        let (key, value) = line.split_at(i);
+        // Note: `value.len() >=1` so `value[1..]` is safe
+        let fee_recipient = Address::from_str(value[1..].trim())
+            .map_err(|e| Error::InvalidFeeRecipient(format!("{}: {:?}", line, e)))?;
+        let key = key.trim();
+        if key == "default" {
+            Ok((None, fee_recipient))
+        } else {
+            let pk = PublicKeyBytes::from_str(key).map_err(Error::InvalidPublicKey)?;
+            Ok((Some(pk), fee_recipient))
+        }
+    } else {
+        Err(Error::InvalidLine(format!("Missing delimiter: {}", line)))
+    }
+}
+
+/// Starts a service which checks the fee recipient file for changes once per slot.
+///
+/// When the file is modified it is re-read and, if valid, atomically replaces the fee recipients
+/// held by the `validator_store`, so the new values apply to the next proposal. An invalid file is
+/// logged and ignored, leaving the previous values in place.
+pub fn start_fee_recipient_file_service<T: SlotClock + 'static, E: EthSpec>(
+    context: RuntimeContext<E>,
+    slot_clock: T,
+    validator_store: Arc<ValidatorStore<T, E>>,
+) {
+    let log = context.log().clone();
+
+    let future = async move {
+        loop {
+            let sleep_time = slot_clock
+                .duration_to_next_slot()
+                .unwrap_or_else(|| slot_clock.slot_duration());
+            sleep(sleep_time).await;
+
+            let Some(mut fee_recipient_file) = validator_store.fee_recipient_file() else {
+                return;
+            };
+            if !fee_recipient_file.is_modified() {
+                continue;
+            }
+
+            if let Err(e) = fee_recipient_file.read_fee_recipient_file() {
+                metrics::inc_counter(&metrics::FEE_RECIPIENT_FILE_RELOAD_ERRORS);
+                error!(
+                    log,
+                    "Unable to reload fee recipient file";
+                    "error" => ?e,
+                    "path" => ?fee_recipient_file.fee_recipient_path,
+                );
+                continue;
+            }
+
+            let changed = validator_store.update_fee_recipient_file(fee_recipient_file);
+            if changed > 0 {
+                metrics::inc_counter_by(
+                    &metrics::FEE_RECIPIENT_FILE_CHANGED_ENTRIES,
+                    changed as u64,
+                );
+                info!(
+                    log,
+                    "Reloaded fee recipient file";
+                    "changed_entries" => changed,
+                );
+            } else {
+                debug!(log, "Fee recipient file modified without changes");
+            }
+        }
+    };
+
+    context.executor.spawn(future, "fee_recipient_file");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bls::Keypair;
+    use std::io::LineWriter;
+    use tempfile::TempDir;
+
+    const DEFAULT_FEE_RECIPIENT: &str = "0x00000000219ab540356cbb839cbe05303d7705fa";
+    const FEE_RECIPIENT1: &str = "0x4242424242424242424242424242424242424242";
+    const FEE_RECIPIENT2: &str = "0x1111111111111111111111111111111111111111";
+    const PK1: &str = "0x800012708dc03f611751aad7a43a082142832b5c1aceed07ff9b543cf836381861352aa923c70eeb02018b638aa306aa";
+    const PK2: &str = "0x80001866ce324de7d80ec73be15e2d064dcf121adf1b34a0d679f2b9ecbab40ce021e03bb877e1a2fe72eaaf475e6e21";
+
+    fn write_file(path: &PathBuf, lines: &[String]) {
+        let file = File::create(path).unwrap();
+        let mut writer = LineWriter::new(file);
+        for line in lines {
+            writer.write_all(line.as_bytes()).unwrap();
+            writer.write_all(b"\n").unwrap();
+        }
+        writer.flush().unwrap();
+    }
+
+    fn address(s: &str) -> Address {
+        Address::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn test_load_fee_recipient() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("fee_recipients.txt");
+        write_file(
+            &path,
+            &[
+                format!("default: {}", DEFAULT_FEE_RECIPIENT),
+                String::new(),
+                format!("{}: {}", PK1, FEE_RECIPIENT1),
+            ],
+        );
+
+        let mut file = FeeRecipientFile::new(path);
+        file.read_fee_recipient_file().unwrap();
+
+        let pk1 = PublicKeyBytes::from_str(PK1).unwrap();
+        let pk2 = PublicKeyBytes::from_str(PK2).unwrap();
+        let unknown = PublicKeyBytes::from(Keypair::random().pk);
+
+        assert_eq!(file.get_fee_recipient(&pk1), Some(address(FEE_RECIPIENT1)));
+        assert_eq!(
+            file.get_fee_recipient(&pk2),
+            Some(address(DEFAULT_FEE_RECIPIENT))
+        );
+        assert_eq!(
+            file.get_fee_recipient(&unknown),
+            Some(address(DEFAULT_FEE_RECIPIENT))
+        );
+    }
+
+    #[test]
+    fn test_reload_fee_recipient() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("fee_recipients.txt");
+        write_file(&path, &[format!("{}: {}", PK1, FEE_RECIPIENT1)]);
+
+        let mut file = FeeRecipientFile::new(path.clone());
+        file.read_fee_recipient_file().unwrap();
+        assert!(!file.is_modified());
+
+        // Entries removed from the file must not persist after a reload.
+        let old = file.clone();
+        write_file(&path, &[format!("{}: {}", PK2, FEE_RECIPIENT2)]);
+        file.read_fee_recipient_file().unwrap();
+
+        let pk1 = PublicKeyBytes::from_str(PK1).unwrap();
+        let pk2 = PublicKeyBytes::from_str(PK2).unwrap();
+        assert_eq!(file.get_fee_recipient(&pk1), None);
+        assert_eq!(file.get_fee_recipient(&pk2), Some(address(FEE_RECIPIENT2)));
+        assert_eq!(old.num_changed_entries(&file), 2);
+        assert_eq!(file.num_changed_entries(&file.clone()), 0);
+    }
+
+    #[test]
+    fn test_invalid_file_leaves_entries_unchanged() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("fee_recipients.txt");
+        write_file(&path, &[format!("{}: {}", PK1, FEE_RECIPIENT1)]);
+
+        let mut file = FeeRecipientFile::new(path.clone());
+        file.read_fee_recipient_file().unwrap();
+        let old = file.clone();
+
+        for lines in [
+            vec![format!("{} {}", PK1, FEE_RECIPIENT1)],
+            vec![format!("{}: 0x1234", PK1)],
+            vec![format!("0x1234: {}", FEE_RECIPIENT1)],
+            vec![
+                format!("{}: {}", PK1, FEE_RECIPIENT1),
+                format!("{}: {}", PK1, FEE_RECIPIENT2),
+            ],
+        ] {
+            write_file(&path, &lines);
+            assert!(file.read_fee_recipient_file().is_err());
+            assert_eq!(file, old);
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use crate::validator_store::ValidatorStore;
+use account_utils::validator_definitions::{BlsToExecutionChangeRecord, SigningDefinition};
+use bls::PublicKey;
+use eth2::lighthouse_vc::types::{BlsToExecutionChangeStatus, GetBlsToExecutionChangeResponse};
+use slot_clock::SlotClock;
+use std::path::PathBuf;
+use std::sync::Arc;
+use types::{Address, EthSpec, SignedBlsToExecutionChange};
+use validator_dir::{unlock_keypair, WITHDRAWAL_KEYSTORE_FILE};
+
+pub fn get_bls_to_execution_change<T: 'static + SlotClock + Clone, E: EthSpec>(
+    validator_pubkey: PublicKey,
+    validator_store: Arc<ValidatorStore<T, E>>,
+) -> Result<GetBlsToExecutionChangeResponse, warp::Rejection> {
+    let initialized_validators_rw_lock = validator_store.initialized_validators();
+    let initialized_validators = initialized_validators_rw_lock.read();
+    if initialized_validators
+        .validator(&validator_pubkey.compress())
+        .is_none()
+    {
+        return Err(warp_utils::reject::custom_not_found(
+            "The key was not found on the server".to_string(),
+        ));
+    }
+
+    let record = initialized_validators
+        .bls_to_execution_change(&validator_pubkey)
+        .ok_or_else(|| {
+            warp_utils::reject::custom_not_found(
+                "No withdrawal credentials change exists for this validator".to_string(),
+            )
+        })?;
+
+    Ok(GetBlsToExecutionChangeResponse {
+        pubkey: validator_pubkey.into(),
+        signed_change: record.signed_change,
+        status: if record.submitted {
+            BlsToExecutionChangeStatus::Submitted
+        } else {
+            BlsToExecutionChangeStatus::Queued
+        },
+    })
+}
+
+/// Signs a change of the validator's withdrawal credentials to `to_execution_address` using the
+/// withdrawal keystore stored alongside its voting keystore, and queues it for broadcast.
+pub fn set_bls_to_execution_change<T: 'static + SlotClock + Clone, E: EthSpec>(
+    validator_pubkey: PublicKey,
+    to_execution_address: Address,
+    secrets_dir: Option<PathBuf>,
+    validator_store: Arc<ValidatorStore<T, E>>,
+) -> Result<SignedBlsToExecutionChange, warp::Rejection> {
+    let withdrawal_keystore_path = {
+        let initialized_validators_rw_lock = validator_store.initialized_validators();
+        let initialized_validators = initialized_validators_rw_lock.read();
+        let definition = initialized_validators
+            .validator_definitions()
+            .iter()
+            .find(|def| def.voting_public_key == validator_pubkey)
+            .ok_or_else(|| {
+                warp_utils::reject::custom_not_found(
+                    "The key was not found on the server".to_string(),
+                )
+            })?;
+
+        if definition
+            .bls_to_execution_change
+            .as_ref()
+            .map_or(false, |change| change.submitted)
+        {
+            return Err(warp_utils::reject::custom_bad_request(
+                "A withdrawal credentials change has already been submitted for this validator"
+                    .to_string(),
+            ));
+        }
+
+        match &definition.signing_definition {
+            SigningDefinition::LocalKeystore {
+                voting_keystore_path,
+                ..
+            } => voting_keystore_path
+                .parent()
+                .map(|dir| dir.join(WITHDRAWAL_KEYSTORE_FILE))
+                .filter(|path| path.exists()),
+            _ => None,
+        }
+        .ok_or_else(|| {
+            warp_utils::reject::custom_bad_request(
+                "The withdrawal keystore for this validator is not available".to_string(),
+            )
+        })?
+    };
+
+    let secrets_dir = secrets_dir.ok_or_else(|| {
+        warp_utils::reject::custom_bad_request(
+            "The withdrawal keystore password cannot be read without a secrets directory"
+                .to_string(),
+        )
+    })?;
+    let withdrawal_keypair =
+        unlock_keypair(&withdrawal_keystore_path, secrets_dir).map_err(|e| {
+            warp_utils::reject::custom_bad_request(format!(
+                "Unable to unlock the withdrawal keystore: {:?}",
+                e
+            ))
+        })?;
+
+    let validator_index = validator_store
+        .validator_index(&validator_pubkey.compress())
+        .ok_or_else(|| {
+            warp_utils::reject::custom_bad_request(
+                "The validator index is not yet known, the validator may not be deposited"
+                    .to_string(),
+            )
+        })?;
+
+    let signed_change = validator_store.sign_bls_to_execution_change(
+        &withdrawal_keypair,
+        validator_index,
+        to_execution_address,
+    );
+
+    let record = BlsToExecutionChangeRecord {
+        signed_change: signed_change.clone(),
+        submitted: false,
+    };
+    validator_store
+        .initialized_validators()
+        .write()
+        .set_bls_to_execution_change(&validator_pubkey, Some(record))
+        .map_err(|e| {
+            warp_utils::reject::custom_server_error(format!(
+                "Unable to queue withdrawal credentials change: {:?}",
+                e
+            ))
+        })?;
+
+    Ok(signed_change)
+}
+
+pub fn delete_bls_to_execution_change<T: 'static + SlotClock + Clone, E: EthSpec>(
+    validator_pubkey: PublicKey,
+    validator_store: Arc<ValidatorStore<T, E>>,
+) -> Result<(), warp::Rejection> {
+    let initialized_validators_rw_lock = validator_store.initialized_validators();
+    let mut initialized_validators = initialized_validators_rw_lock.write();
+    if initialized_validators
+        .validator(&validator_pubkey.compress())
+        .is_none()
+    {
+        return Err(warp_utils::reject::custom_not_found(
+            "The key was not found on the server, nothing to delete".to_string(),
+        ));
+    }
+
+    match initialized_validators.bls_to_execution_change(&validator_pubkey) {
+        None => Ok(()),
+        Some(change) if change.submitted => Err(warp_utils::reject::custom_bad_request(
+            "The change has already been submitted and cannot be cancelled".to_string(),
+        )),
+        Some(_) => initialized_validators
+            .set_bls_to_execution_change(&validator_pubkey, None)
+            .map_err(|e| {
+                warp_utils::reject::custom_server_error(format!(
+                    "Unable to cancel withdrawal credentials change: {:?}",
+                    e
+                ))
+            }),
+    }
+}
@@ -0,0 +1,103 @@
+use crate::validator_store::ValidatorStore;
+use bls::{PublicKey, PublicKeyBytes};
+use eth2::lighthouse_vc::types::{GetBuilderSettingsResponse, UpdateBuilderSettingsRequest};
+use slot_clock::SlotClock;
+use std::sync::Arc;
+use types::EthSpec;
+
+pub fn get_builder_settings<T: 'static + SlotClock + Clone, E: EthSpec>(
+    validator_pubkey: PublicKey,
+    validator_store: Arc<ValidatorStore<T, E>>,
+) -> Result<GetBuilderSettingsResponse, warp::Rejection> {
+    let pubkey = PublicKeyBytes::from(&validator_pubkey);
+    if validator_store
+        .initialized_validators()
+        .read()
+        .validator(&pubkey)
+        .is_none()
+    {
+        return Err(warp_utils::reject::custom_not_found(
+            "The key was not found on the server".to_string(),
+        ));
+    }
+
+    Ok(GetBuilderSettingsResponse {
+        pubkey,
+        builder_proposals: validator_store.get_builder_proposals(&pubkey),
+        builder_boost_factor: validator_store.get_builder_boost_factor(&pubkey),
+    })
+}
+
+pub fn set_builder_settings<T: 'static + SlotClock + Clone, E: EthSpec>(
+    validator_pubkey: PublicKey,
+    request: UpdateBuilderSettingsRequest,
+    validator_store: Arc<ValidatorStore<T, E>>,
+) -> Result<(), warp::Rejection> {
+    {
+        let initialized_validators_rw_lock = validator_store.initialized_validators();
+        let mut initialized_validators = initialized_validators_rw_lock.write();
+        match initialized_validators.validator(&validator_pubkey.compress()) {
+            None => {
+                return Err(warp_utils::reject::custom_not_found(
+                    "The key was not found on the server, nothing to update".to_string(),
+                ))
+            }
+            Some(initialized_validator)
+                if request.builder_proposals.map_or(true, |v| {
+                    initialized_validator.get_builder_proposals() == Some(v)
+                }) && request.builder_boost_factor.map_or(true, |v| {
+                    initialized_validator.get_builder_boost_factor() == Some(v)
+                }) =>
+            {
+                return Ok(());
+            }
+            Some(_) => initialized_validators
+                .set_validator_builder_settings(
+                    &validator_pubkey,
+                    request.builder_proposals,
+                    request.builder_boost_factor,
+                )
+                .map_err(|e| {
+                    warp_utils::reject::custom_server_error(format!(
+                        "Error persisting builder settings: {:?}",
+                        e
+                    ))
+                })?,
+        }
+    }
+    validator_store.notify_proposal_data_changed();
+    Ok(())
+}
+
+pub fn delete_builder_settings<T: 'static + SlotClock + Clone, E: EthSpec>(
+    validator_pubkey: PublicKey,
+    validator_store: Arc<ValidatorStore<T, E>>,
+) -> Result<(), warp::Rejection> {
+    {
+        let initialized_validators_rw_lock = validator_store.initialized_validators();
+        let mut initialized_validators = initialized_validators_rw_lock.write();
+        match initialized_validators.validator(&validator_pubkey.compress()) {
+            None => {
+                return Err(warp_utils::reject::custom_not_found(
+                    "The key was not found on the server, nothing to delete".to_string(),
+                ))
+            }
+            Some(initialized_validator)
+                if initialized_validator.get_builder_proposals().is_none()
+                    && initialized_validator.get_builder_boost_factor().is_none() =>
+            {
+                return Ok(());
+            }
+            Some(_) => initialized_validators
+                .delete_validator_builder_settings(&validator_pubkey)
+                .map_err(|e| {
+                    warp_utils::reject::custom_server_error(format!(
+                        "Error persisting builder settings removal: {:?}",
+                        e
+                    ))
+                })?,
+        }
+    }
+    validator_store.notify_proposal_data_changed();
+    Ok(())
+}
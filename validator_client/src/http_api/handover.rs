@@ -0,0 +1,146 @@
+//! Hand validators over to another validator client without a long downtime window.
+//!
+//! The new validator client schedules the handover at an epoch, from which this validator
+//! client stops signing blocks and attestations for the keys. Once that epoch is reached the
+//! handover is confirmed, which disables the keys in the slashing protection database and returns
+//! their slashing protection data, including the last messages signed here, for import by the new
+//! validator client.
+
+use crate::validator_store::ValidatorStore;
+use eth2::lighthouse_vc::types::{
+    ConfirmHandoverRequest, ConfirmHandoverResponse, HandoverRequest, HandoverStatus,
+};
+use slog::{info, Logger};
+use slot_clock::SlotClock;
+use std::sync::Arc;
+use types::{Epoch, EthSpec};
+
+fn current_epoch<T: SlotClock, E: EthSpec>(slot_clock: &T) -> Result<Epoch, warp::Rejection> {
+    slot_clock
+        .now()
+        .map(|slot| slot.epoch(E::slots_per_epoch()))
+        .ok_or_else(|| {
+            warp_utils::reject::custom_server_error("Unable to read slot clock".to_string())
+        })
+}
+
+pub fn get_handover<T: 'static + SlotClock + Clone, E: EthSpec>(
+    validator_store: Arc<ValidatorStore<T, E>>,
+    slot_clock: T,
+) -> Result<Vec<HandoverStatus>, warp::Rejection> {
+    let current_epoch = current_epoch::<T, E>(&slot_clock)?;
+    let mut statuses = validator_store
+        .initialized_validators()
+        .read()
+        .handover_epochs()
+        .into_iter()
+        .map(|(pubkey, epoch)| HandoverStatus {
+            pubkey,
+            epoch,
+            complete: current_epoch >= epoch,
+        })
+        .collect::<Vec<_>>();
+    statuses.sort_by_key(|status| status.pubkey);
+    Ok(statuses)
+}
+
+pub fn set_handover<T: 'static + SlotClock + Clone, E: EthSpec>(
+    request: HandoverRequest,
+    validator_store: Arc<ValidatorStore<T, E>>,
+    slot_clock: T,
+    log: Logger,
+) -> Result<(), warp::Rejection> {
+    let current_epoch = current_epoch::<T, E>(&slot_clock)?;
+    if request.epoch < current_epoch {
+        return Err(warp_utils::reject::custom_bad_request(format!(
+            "Handover epoch {} is prior to the current epoch {}",
+            request.epoch, current_epoch
+        )));
+    }
+
+    let initialized_validators_rw_lock = validator_store.initialized_validators();
+    let mut initialized_validators = initialized_validators_rw_lock.write();
+
+    // Check every key before changing any, so that a request is applied entirely or not at all.
+    let mut pubkeys = Vec::with_capacity(request.pubkeys.len());
+    for pubkey_bytes in &request.pubkeys {
+        if initialized_validators.validator(pubkey_bytes).is_none() {
+            return Err(warp_utils::reject::custom_not_found(format!(
+                "The key {:?} was not found on the server",
+                pubkey_bytes
+            )));
+        }
+        let pubkey = pubkey_bytes.decompress().map_err(|e| {
+            warp_utils::reject::custom_bad_request(format!("Invalid pubkey: {:?}", e))
+        })?;
+        pubkeys.push(pubkey);
+    }
+
+    for pubkey in &pubkeys {
+        initialized_validators
+            .set_handover_epoch(pubkey, Some(request.epoch))
+            .map_err(|e| {
+                warp_utils::reject::custom_server_error(format!(
+                    "Unable to schedule handover: {:?}",
+                    e
+                ))
+            })?;
+    }
+
+    info!(
+        log,
+        "Validator handover scheduled";
+        "msg" => "blocks and attestations will not be signed from the handover epoch",
+        "epoch" => request.epoch,
+        "count" => pubkeys.len(),
+    );
+    Ok(())
+}
+
+pub fn confirm_handover<T: 'static + SlotClock + Clone, E: EthSpec>(
+    request: ConfirmHandoverRequest,
+    validator_store: Arc<ValidatorStore<T, E>>,
+    slot_clock: T,
+    log: Logger,
+) -> Result<ConfirmHandoverResponse, warp::Rejection> {
+    let current_epoch = current_epoch::<T, E>(&slot_clock)?;
+    {
+        let initialized_validators = validator_store.initialized_validators();
+        let initialized_validators = initialized_validators.read();
+        for pubkey in &request.pubkeys {
+            match initialized_validators.handover_epoch(pubkey) {
+                Some(epoch) if epoch <= current_epoch => (),
+                Some(epoch) => {
+                    return Err(warp_utils::reject::custom_bad_request(format!(
+                        "The handover of {:?} is not complete until epoch {}",
+                        pubkey, epoch
+                    )))
+                }
+                None => {
+                    return Err(warp_utils::reject::custom_not_found(format!(
+                        "No handover is scheduled for {:?}",
+                        pubkey
+                    )))
+                }
+            }
+        }
+    }
+
+    let slashing_protection = validator_store
+        .export_slashing_protection_for_keys(&request.pubkeys)
+        .map_err(|e| {
+            warp_utils::reject::custom_server_error(format!(
+                "Unable to export slashing protection data: {:?}",
+                e
+            ))
+        })?;
+
+    info!(
+        log,
+        "Validator handover confirmed";
+        "count" => request.pubkeys.len(),
+    );
+    Ok(ConfirmHandoverResponse {
+        slashing_protection,
+    })
+}
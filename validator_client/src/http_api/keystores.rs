@@ -3,7 +3,9 @@ use crate::{
     initialized_validators::Error, signing_method::SigningMethod, InitializedValidators,
     ValidatorStore,
 };
-use account_utils::{validator_definitions::PasswordStorage, ZeroizeString};
+use account_utils::{
+    parallel_decryption::decrypt_in_parallel, validator_definitions::PasswordStorage, ZeroizeString,
+};
 use eth2::lighthouse_vc::{
     std_types::{
         DeleteKeystoreStatus, DeleteKeystoresRequest, DeleteKeystoresResponse,
@@ -13,17 +15,32 @@ use eth2::lighthouse_vc::{
     types::{ExportKeystoresResponse, SingleExportKeystoresResponse},
 };
 use eth2_keystore::Keystore;
-use slog::{info, warn, Logger};
+use futures::{stream, Stream, StreamExt};
+use slog::{debug, info, warn, Logger};
 use slot_clock::SlotClock;
+use std::io::{self, Read};
 use std::path::PathBuf;
+use std::pin::pin;
 use std::sync::Arc;
 use task_executor::TaskExecutor;
 use tokio::runtime::Handle;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::UnboundedReceiverStream;
 use types::{EthSpec, PublicKeyBytes};
 use validator_dir::{keystore_password_path, Builder as ValidatorDirBuilder};
+use warp::http::header::CONTENT_TYPE;
+use warp::hyper::body::{Buf, Bytes};
+use warp::hyper::Body;
+use warp::reply::Response;
 use warp::Rejection;
 use warp_utils::reject::{custom_bad_request, custom_server_error};
 
+/// The maximum number of threads used to decrypt keystores during an import.
+const MAX_IMPORT_DECRYPTION_THREADS: usize = 8;
+
+/// The number of chunks of an import request body which may be received ahead of the parser.
+const IMPORT_BODY_CHUNK_BUFFER: usize = 16;
+
 pub fn list<T: SlotClock + 'static, E: EthSpec>(
     validator_store: Arc<ValidatorStore<T, E>>,
 ) -> ListKeystoresResponse {
@@ -33,7 +50,8 @@ pub fn list<T: SlotClock + 'static, E: EthSpec>(
     let keystores = initialized_validators
         .validator_definitions()
         .iter()
-        .filter(|def| def.enabled)
+        // Watch-only validators don't have any keys.
+        .filter(|def| def.enabled && !def.signing_definition.is_watch_only())
         .map(|def| {
             let validating_pubkey = def.voting_public_key.compress();
 
@@ -44,7 +62,9 @@ pub fn list<T: SlotClock + 'static, E: EthSpec>(
                         ref voting_keystore,
                         ..
                     } => (voting_keystore.path(), Some(false)),
-                    SigningMethod::Web3Signer { .. } => (None, Some(true)),
+                    SigningMethod::Web3Signer { .. } | SigningMethod::WatchOnly { .. } => {
+                        (None, Some(true))
+                    }
                 });
 
             SingleKeystoreResponse {
@@ -58,14 +78,25 @@ pub fn list<T: SlotClock + 'static, E: EthSpec>(
     ListKeystoresResponse { data: keystores }
 }
 
-pub fn import<T: SlotClock + 'static, E: EthSpec>(
-    request: ImportKeystoresRequest,
+/// Import the keystores in the `ImportKeystoresRequest` given by `body`.
+///
+/// The request is parsed as the body arrives, one keystore at a time, rather than being buffered
+/// in full first.
+///
+/// The import runs in a background task, which streams the status of each keystore back in the
+/// response body as soon as it is known. The body is a standard `ImportKeystoresResponse` once
+/// complete, but clients importing thousands of keys receive data throughout the import rather
+/// than waiting for a single monolithic response (and potentially timing out).
+pub async fn import<T: SlotClock + 'static, E: EthSpec>(
+    body: impl Stream<Item = Result<impl Buf, warp::Error>>,
     validator_dir: PathBuf,
     secrets_dir: Option<PathBuf>,
     validator_store: Arc<ValidatorStore<T, E>>,
     task_executor: TaskExecutor,
     log: Logger,
-) -> Result<ImportKeystoresResponse, Rejection> {
+) -> Result<Response, Rejection> {
+    let request = parse_import_request(body, &task_executor).await?;
+
     // Check request validity. This is the only cases in which we should return a 4xx code.
     if request.keystores.len() != request.passwords.len() {
         return Err(custom_bad_request(format!(
@@ -75,12 +106,127 @@ pub fn import<T: SlotClock + 'static, E: EthSpec>(
         )));
     }
 
+    let (status_tx, status_rx) = mpsc::unbounded_channel();
+    let handle = task_executor.handle();
+    task_executor.spawn_blocking(
+        move || {
+            import_keystores(
+                request,
+                validator_dir,
+                secrets_dir,
+                &validator_store,
+                handle,
+                status_tx,
+                &log,
+            )
+        },
+        "keystore_import",
+    );
+
+    let statuses = UnboundedReceiverStream::new(status_rx)
+        .enumerate()
+        .map(|(i, status)| {
+            let separator = if i == 0 { "" } else { "," };
+            serde_json::to_string(&status).map(|json| format!("{}{}", separator, json))
+        });
+    let body = stream::once(async { Ok(r#"{"data":["#.to_string()) })
+        .chain(statuses)
+        .chain(stream::once(async { Ok("]}".to_string()) }));
+
+    warp::http::Response::builder()
+        .header(CONTENT_TYPE, "application/json")
+        .body(Body::wrap_stream(body))
+        .map_err(|e| custom_server_error(format!("unable to build response: {:?}", e)))
+}
+
+/// Parse an `ImportKeystoresRequest` from `body` on a blocking thread, whilst the body is still
+/// being received.
+async fn parse_import_request(
+    body: impl Stream<Item = Result<impl Buf, warp::Error>>,
+    task_executor: &TaskExecutor,
+) -> Result<ImportKeystoresRequest, Rejection> {
+    let (chunk_tx, chunk_rx) = mpsc::channel(IMPORT_BODY_CHUNK_BUFFER);
+    let parse_handle = task_executor
+        .spawn_blocking_handle(
+            move || serde_json::from_reader::<_, ImportKeystoresRequest>(BodyReader::new(chunk_rx)),
+            "keystore_import_parse",
+        )
+        .ok_or_else(|| custom_server_error("validator client shutdown".into()))?;
+
+    let mut body = pin!(body);
+    while let Some(chunk) = body.next().await {
+        let chunk = chunk
+            .map(|mut chunk| chunk.copy_to_bytes(chunk.remaining()))
+            .map_err(|e| e.to_string());
+        // The parser stops reading early if the body is invalid.
+        if chunk_tx.send(chunk).await.is_err() {
+            break;
+        }
+    }
+    drop(chunk_tx);
+
+    parse_handle
+        .await
+        .map_err(|e| custom_server_error(format!("unable to parse request: {:?}", e)))?
+        .map_err(|e| custom_bad_request(format!("invalid request body: {}", e)))
+}
+
+/// A blocking `Read` over the chunks of a request body, which are received from an async task.
+struct BodyReader {
+    chunks: mpsc::Receiver<Result<Bytes, String>>,
+    current: Bytes,
+}
+
+impl BodyReader {
+    fn new(chunks: mpsc::Receiver<Result<Bytes, String>>) -> Self {
+        Self {
+            chunks,
+            current: Bytes::new(),
+        }
+    }
+}
+
+impl Read for BodyReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.current.is_empty() {
+            match self.chunks.blocking_recv() {
+                Some(Ok(chunk)) => self.current = chunk,
+                Some(Err(e)) => return Err(io::Error::new(io::ErrorKind::Other, e)),
+                None => return Ok(0),
+            }
+        }
+        let len = buf.len().min(self.current.len());
+        self.current.copy_to_slice(&mut buf[..len]);
+        Ok(len)
+    }
+}
+
+/// Import each of the keystores in `request`, sending their statuses to `status_tx` in the order
+/// of the request.
+///
+/// Keystore passwords are checked by a bounded pool of threads, whilst the keystores are imported
+/// one at a time by the calling thread.
+fn import_keystores<T: SlotClock + 'static, E: EthSpec>(
+    request: ImportKeystoresRequest,
+    validator_dir: PathBuf,
+    secrets_dir: Option<PathBuf>,
+    validator_store: &ValidatorStore<T, E>,
+    handle: Option<Handle>,
+    status_tx: mpsc::UnboundedSender<Status<ImportKeystoreStatus>>,
+    log: &Logger,
+) {
+    let count = request.keystores.len();
     info!(
         log,
         "Importing keystores via standard HTTP API";
-        "count" => request.keystores.len(),
+        "count" => count,
     );
 
+    // The client may have disconnected, in which case the import continues regardless.
+    let send_status = |status| {
+        let _ = status_tx.send(status);
+    };
+
     // Import slashing protection data before keystores, so that new keystores don't start signing
     // without it. Do not return early on failure, propagate the failure to each key.
     let slashing_protection_status =
@@ -109,59 +255,99 @@ pub fn import<T: SlotClock + 'static, E: EthSpec>(
             Ok(())
         };
 
-    // Import each keystore. Some keystores may fail to be imported, so we record a status for each.
-    let mut statuses = Vec::with_capacity(request.keystores.len());
+    if let Err(e) = slashing_protection_status {
+        // Slashing protection import failed, do not attempt to import any keys. Record an error
+        // status for each.
+        for _ in 0..count {
+            send_status(Status::error(
+                ImportKeystoreStatus::Error,
+                format!("slashing protection import failed: {:?}", e),
+            ));
+        }
+        return;
+    }
+
+    let Some(handle) = handle else {
+        for _ in 0..count {
+            send_status(Status::error(
+                ImportKeystoreStatus::Error,
+                "validator client shutdown".into(),
+            ));
+        }
+        return;
+    };
 
-    for (KeystoreJsonStr(keystore), password) in request
+    let keystores = request
         .keystores
         .into_iter()
-        .zip(request.passwords.into_iter())
-    {
-        let pubkey_str = keystore.pubkey().to_string();
+        .zip(request.passwords)
+        .map(|(KeystoreJsonStr(keystore), password)| (keystore, password))
+        .collect::<Vec<_>>();
+    let (mut num_imported, mut num_duplicate, mut num_error) = (0, 0, 0);
+    decrypt_in_parallel(
+        &keystores,
+        MAX_IMPORT_DECRYPTION_THREADS,
+        |(keystore, password)| {
+            keystore
+                .decrypt_keypair(password.as_ref())
+                .map(drop)
+                .map_err(|e| format!("incorrect password: {:?}", e))
+        },
+        |i, (keystore, password), password_check| {
+            let pubkey_str = keystore.pubkey().to_string();
+            let password_check =
+                password_check.unwrap_or_else(|| Err("unable to check password".to_string()));
 
-        let status = if let Err(e) = &slashing_protection_status {
-            // Slashing protection import failed, do not attempt to import the key. Record an
-            // error status.
-            Status::error(
-                ImportKeystoreStatus::Error,
-                format!("slashing protection import failed: {:?}", e),
-            )
-        } else if let Some(handle) = task_executor.handle() {
-            // Import the keystore.
-            match import_single_keystore(
-                keystore,
-                password,
+            let status = match import_single_keystore(
+                keystore.clone(),
+                password.clone(),
+                password_check,
                 validator_dir.clone(),
                 secrets_dir.clone(),
-                &validator_store,
-                handle,
+                validator_store,
+                handle.clone(),
             ) {
                 Ok(status) => Status::ok(status),
                 Err(e) => {
                     warn!(
                         log,
                         "Error importing keystore, skipped";
-                        "pubkey" => pubkey_str,
+                        "pubkey" => &pubkey_str,
                         "error" => ?e,
                     );
                     Status::error(ImportKeystoreStatus::Error, e)
                 }
+            };
+
+            match status.status {
+                ImportKeystoreStatus::Imported => num_imported += 1,
+                ImportKeystoreStatus::Duplicate => num_duplicate += 1,
+                ImportKeystoreStatus::Error => num_error += 1,
             }
-        } else {
-            Status::error(
-                ImportKeystoreStatus::Error,
-                "validator client shutdown".into(),
-            )
-        };
-        statuses.push(status);
-    }
+            debug!(
+                log,
+                "Processed keystore";
+                "pubkey" => pubkey_str,
+                "status" => ?status.status,
+                "progress" => format!("{}/{}", i + 1, count),
+            );
+            send_status(status);
+        },
+    );
 
-    Ok(ImportKeystoresResponse { data: statuses })
+    info!(
+        log,
+        "Finished importing keystores";
+        "imported" => num_imported,
+        "duplicate" => num_duplicate,
+        "error" => num_error,
+    );
 }
 
 fn import_single_keystore<T: SlotClock + 'static, E: EthSpec>(
     keystore: Keystore,
     password: ZeroizeString,
+    password_check: Result<(), String>,
     validator_dir_path: PathBuf,
     secrets_dir: Option<PathBuf>,
     validator_store: &ValidatorStore<T, E>,
@@ -195,14 +381,12 @@ fn import_single_keystore<T: SlotClock + 'static, E: EthSpec>(
         PasswordStorage::ValidatorDefinitions(password.clone())
     };
 
-    // Check that the password is correct.
+    // Check that the password is correct (the keystore was decrypted by the caller).
     // In future we should re-structure to avoid the double decryption here. It's not as simple
     // as removing this check because `add_validator_keystore` will break if provided with an
     // invalid validator definition (`update_validators` will get stuck trying to decrypt with the
     // wrong password indefinitely).
-    keystore
-        .decrypt_keypair(password.as_ref())
-        .map_err(|e| format!("incorrect password: {:?}", e))?;
+    password_check?;
 
     let validator_dir = ValidatorDirBuilder::new(validator_dir_path)
         .password_dir_opt(secrets_dir)
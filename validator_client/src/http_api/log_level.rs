@@ -0,0 +1,51 @@
+use eth2::lighthouse_vc::types::LogFilterData;
+use logging::{level_name, parse_level, LogFilter, LogFilterHandle};
+use slog::{info, Logger};
+
+fn log_filter_handle(
+    log_filter: Option<LogFilterHandle>,
+) -> Result<LogFilterHandle, warp::Rejection> {
+    log_filter.ok_or_else(|| {
+        warp_utils::reject::custom_server_error(
+            "The log level cannot be changed at runtime".to_string(),
+        )
+    })
+}
+
+pub fn get_log_level(
+    log_filter: Option<LogFilterHandle>,
+) -> Result<LogFilterData, warp::Rejection> {
+    let filter = log_filter_handle(log_filter)?.get();
+    Ok(LogFilterData {
+        level: level_name(filter.level).to_string(),
+        targets: filter
+            .targets
+            .into_iter()
+            .map(|(target, level)| (target, level_name(level).to_string()))
+            .collect(),
+    })
+}
+
+pub fn set_log_level(
+    request: LogFilterData,
+    log_filter: Option<LogFilterHandle>,
+    log: Logger,
+) -> Result<(), warp::Rejection> {
+    let log_filter = log_filter_handle(log_filter)?;
+    let level = parse_level(&request.level).map_err(warp_utils::reject::custom_bad_request)?;
+    let targets = request
+        .targets
+        .into_iter()
+        .map(|(target, level)| Ok((target, parse_level(&level)?)))
+        .collect::<Result<_, String>>()
+        .map_err(warp_utils::reject::custom_bad_request)?;
+
+    log_filter.set(LogFilter { level, targets });
+    info!(
+        log,
+        "Updated log level";
+        "level" => request.level,
+        "targets" => ?log_filter.get().targets,
+    );
+    Ok(())
+}
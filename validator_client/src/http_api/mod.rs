@@ -1,16 +1,34 @@
 mod api_secret;
+mod bls_to_execution_change;
+mod builder_settings;
 mod create_signed_voluntary_exit;
 mod create_validator;
 mod graffiti;
+mod handover;
 mod keystores;
+mod log_level;
 mod remotekeys;
+mod scheduled_exit;
+mod slashing_protection_check;
+mod suspension;
 mod tests;
 
 pub mod test_utils;
 
+use crate::http_api::bls_to_execution_change::{
+    delete_bls_to_execution_change, get_bls_to_execution_change, set_bls_to_execution_change,
+};
+use crate::http_api::builder_settings::{
+    delete_builder_settings, get_builder_settings, set_builder_settings,
+};
 use crate::http_api::graffiti::{delete_graffiti, get_graffiti, set_graffiti};
+use crate::http_api::scheduled_exit::{
+    delete_scheduled_exit, get_scheduled_exit, set_scheduled_exit,
+};
+use crate::http_api::suspension::{get_suspension, set_suspension};
 
 use crate::http_api::create_signed_voluntary_exit::create_signed_voluntary_exit;
+use crate::performance_service::PerformanceTracker;
 use crate::{determine_graffiti, GraffitiFile, ValidatorStore};
 use account_utils::{
     mnemonic_from_phrase,
@@ -23,12 +41,13 @@ use create_validator::{
 use eth2::lighthouse_vc::{
     std_types::{AuthResponse, GetFeeRecipientResponse, GetGasLimitResponse},
     types::{
-        self as api_types, GenericResponse, GetGraffitiResponse, Graffiti, PublicKey,
-        PublicKeyBytes, SetGraffitiRequest,
+        self as api_types, BlsToExecutionChangeRequest, GenericResponse, GetGraffitiResponse,
+        Graffiti, PublicKey, PublicKeyBytes, ScheduleExitRequest, SetGraffitiRequest,
+        SetSuspensionRequest,
     },
 };
 use lighthouse_version::version_with_platform;
-use logging::SSELoggingComponents;
+use logging::{LogFilterHandle, SSELoggingComponents};
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use slog::{crit, info, warn, Logger};
@@ -46,6 +65,7 @@ use tokio_stream::{wrappers::BroadcastStream, StreamExt};
 use types::{ChainSpec, ConfigAndPreset, EthSpec};
 use validator_dir::Builder as ValidatorDirBuilder;
 use warp::{sse::Event, Filter};
+use warp_utils::reject::convert_rejection;
 use warp_utils::task::blocking_json_task;
 
 #[derive(Debug)]
@@ -81,6 +101,8 @@ pub struct Context<T: SlotClock, E: EthSpec> {
     pub config: Config,
     pub log: Logger,
     pub sse_logging_components: Option<SSELoggingComponents>,
+    pub log_filter: Option<LogFilterHandle>,
+    pub performance_tracker: Option<Arc<PerformanceTracker>>,
     pub slot_clock: T,
     pub _phantom: PhantomData<E>,
 }
@@ -227,6 +249,12 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
     let inner_components = ctx.sse_logging_components.clone();
     let sse_component_filter = warp::any().map(move || inner_components.clone());
 
+    let inner_log_filter = ctx.log_filter.clone();
+    let log_filter_handle_filter = warp::any().map(move || inner_log_filter.clone());
+
+    let inner_performance_tracker = ctx.performance_tracker.clone();
+    let performance_tracker_filter = warp::any().map(move || inner_performance_tracker.clone());
+
     // Create a `warp` filter that provides access to local system information.
     let system_info = Arc::new(RwLock::new(sysinfo::System::new()));
     {
@@ -347,6 +375,146 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             },
         );
 
+    // GET lighthouse/doppelganger
+    let get_lighthouse_doppelganger = warp::path("lighthouse")
+        .and(warp::path("doppelganger"))
+        .and(warp::path::end())
+        .and(validator_store_filter.clone())
+        .then(|validator_store: Arc<ValidatorStore<T, E>>| {
+            blocking_json_task(move || {
+                validator_store
+                    .doppelganger_status_data()
+                    .map(api_types::GenericResponse::from)
+                    .ok_or_else(|| {
+                        warp_utils::reject::custom_bad_request(
+                            "doppelganger protection is not enabled".to_string(),
+                        )
+                    })
+            })
+        });
+
+    // POST lighthouse/doppelganger/{validator_pubkey}
+    let post_lighthouse_doppelganger = warp::path("lighthouse")
+        .and(warp::path("doppelganger"))
+        .and(warp::path::param::<PublicKey>())
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(validator_store_filter.clone())
+        .then(
+            |validator_pubkey: PublicKey,
+             body: api_types::DoppelgangerOverrideRequest,
+             validator_store: Arc<ValidatorStore<T, E>>| {
+                blocking_json_task(move || {
+                    validator_store
+                        .override_doppelganger_protection(
+                            PublicKeyBytes::from(&validator_pubkey),
+                            body.action,
+                        )
+                        .map_err(|e| {
+                            warp_utils::reject::custom_bad_request(format!(
+                                "unable to override doppelganger protection: {}",
+                                e
+                            ))
+                        })
+                })
+            },
+        );
+
+    // GET lighthouse/logging
+    let get_lighthouse_logging = warp::path("lighthouse")
+        .and(warp::path("logging"))
+        .and(warp::path::end())
+        .and(log_filter_handle_filter.clone())
+        .then(|log_filter_handle: Option<LogFilterHandle>| {
+            blocking_json_task(move || {
+                log_level::get_log_level(log_filter_handle).map(GenericResponse::from)
+            })
+        });
+
+    // POST lighthouse/logging
+    let post_lighthouse_logging = warp::path("lighthouse")
+        .and(warp::path("logging"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(log_filter_handle_filter)
+        .and(log_filter.clone())
+        .then(
+            |request: api_types::LogFilterData, log_filter_handle: Option<LogFilterHandle>, log| {
+                blocking_json_task(move || {
+                    log_level::set_log_level(request, log_filter_handle, log)
+                })
+            },
+        );
+
+    // GET lighthouse/handover
+    let get_lighthouse_handover = warp::path("lighthouse")
+        .and(warp::path("handover"))
+        .and(warp::path::end())
+        .and(validator_store_filter.clone())
+        .and(slot_clock_filter.clone())
+        .then(
+            |validator_store: Arc<ValidatorStore<T, E>>, slot_clock: T| {
+                blocking_json_task(move || {
+                    handover::get_handover(validator_store, slot_clock).map(GenericResponse::from)
+                })
+            },
+        );
+
+    // POST lighthouse/handover
+    let post_lighthouse_handover = warp::path("lighthouse")
+        .and(warp::path("handover"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(validator_store_filter.clone())
+        .and(slot_clock_filter.clone())
+        .and(log_filter.clone())
+        .then(
+            |request: api_types::HandoverRequest,
+             validator_store: Arc<ValidatorStore<T, E>>,
+             slot_clock: T,
+             log| {
+                blocking_json_task(move || {
+                    handover::set_handover(request, validator_store, slot_clock, log)
+                })
+            },
+        );
+
+    // POST lighthouse/handover/confirm
+    let post_lighthouse_handover_confirm = warp::path("lighthouse")
+        .and(warp::path("handover"))
+        .and(warp::path("confirm"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(validator_store_filter.clone())
+        .and(slot_clock_filter.clone())
+        .and(log_filter.clone())
+        .then(
+            |request: api_types::ConfirmHandoverRequest,
+             validator_store: Arc<ValidatorStore<T, E>>,
+             slot_clock: T,
+             log| {
+                blocking_json_task(move || {
+                    handover::confirm_handover(request, validator_store, slot_clock, log)
+                })
+            },
+        );
+
+    // POST lighthouse/slashing_protection/check
+    let post_lighthouse_slashing_protection_check = warp::path("lighthouse")
+        .and(warp::path("slashing_protection"))
+        .and(warp::path("check"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(validator_store_filter.clone())
+        .then(
+            |request: api_types::SlashingProtectionCheckRequest,
+             validator_store: Arc<ValidatorStore<T, E>>| {
+                blocking_json_task(move || {
+                    slashing_protection_check::check_slashing_protection(request, validator_store)
+                })
+            },
+        );
+
     // GET lighthouse/ui/health
     let get_lighthouse_ui_health = warp::path("lighthouse")
         .and(warp::path("ui"))
@@ -364,6 +532,30 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             })
         });
 
+    // GET lighthouse/ui/performance
+    let get_lighthouse_ui_performance = warp::path("lighthouse")
+        .and(warp::path("ui"))
+        .and(warp::path("performance"))
+        .and(warp::path::end())
+        .and(warp::query::<api_types::PerformanceQuery>())
+        .and(performance_tracker_filter)
+        .then(
+            |query: api_types::PerformanceQuery,
+             performance_tracker: Option<Arc<PerformanceTracker>>| {
+                blocking_json_task(move || {
+                    performance_tracker
+                        .map(|tracker| {
+                            api_types::GenericResponse::from(tracker.summary(query.epochs))
+                        })
+                        .ok_or_else(|| {
+                            warp_utils::reject::custom_bad_request(
+                                "performance tracking is not enabled".to_string(),
+                            )
+                        })
+                })
+            },
+        );
+
     let get_lighthouse_ui_graffiti = warp::path("lighthouse")
         .and(warp::path("ui"))
         .and(warp::path("graffiti"))
@@ -605,6 +797,10 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                                 builder_proposals: web3signer.builder_proposals,
                                 builder_boost_factor: web3signer.builder_boost_factor,
                                 prefer_builder_proposals: web3signer.prefer_builder_proposals,
+                                scheduled_exit: None,
+                                bls_to_execution_change: None,
+                                handover_epoch: None,
+                                suspended: false,
                                 description: web3signer.description,
                                 signing_definition: SigningDefinition::Web3Signer(
                                     Web3SignerDefinition {
@@ -614,6 +810,8 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                                         client_identity_path: web3signer.client_identity_path,
                                         client_identity_password: web3signer
                                             .client_identity_password,
+                                        fallback_keystore_path: None,
+                                        fallback_keystore_password_path: None,
                                     },
                                 ),
                             })
@@ -632,6 +830,52 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             },
         );
 
+    // POST lighthouse/validators/watch_only
+    let post_validators_watch_only = warp::path("lighthouse")
+        .and(warp::path("validators"))
+        .and(warp::path("watch_only"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(validator_store_filter.clone())
+        .and(task_executor_filter.clone())
+        .then(
+            |body: Vec<api_types::WatchOnlyValidatorRequest>,
+             validator_store: Arc<ValidatorStore<T, E>>,
+             task_executor: TaskExecutor| {
+                blocking_json_task(move || {
+                    if let Some(handle) = task_executor.handle() {
+                        let definitions: Vec<ValidatorDefinition> = body
+                            .into_iter()
+                            .map(|request| {
+                                let mut def = ValidatorDefinition::new_watch_only(
+                                    request.voting_public_key,
+                                    request.description,
+                                );
+                                def.enabled = request.enable;
+                                def
+                            })
+                            .collect();
+                        handle.block_on(async {
+                            for def in definitions {
+                                validator_store.add_validator(def).await.map_err(|e| {
+                                    warp_utils::reject::custom_server_error(format!(
+                                        "failed to initialize validator: {:?}",
+                                        e
+                                    ))
+                                })?;
+                            }
+                            Ok::<_, warp::Rejection>(())
+                        })?;
+                        Ok(())
+                    } else {
+                        Err(warp_utils::reject::custom_server_error(
+                            "Lighthouse shutting down".into(),
+                        ))
+                    }
+                })
+            },
+        );
+
     // PATCH lighthouse/validators/{validator_pubkey}
     let patch_validators = warp::path("lighthouse")
         .and(warp::path("validators"))
@@ -741,6 +985,7 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                                             e
                                         ))
                                     })?;
+                                validator_store.notify_proposal_data_changed();
                                 Ok(())
                             } else {
                                 Err(warp_utils::reject::custom_server_error(
@@ -861,7 +1106,9 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                                 "Error persisting fee recipient: {:?}",
                                 e
                             ))
-                        })
+                        })?;
+                    validator_store.notify_proposal_data_changed();
+                    Ok(())
                 })
             },
         )
@@ -897,7 +1144,9 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                                 "Error persisting fee recipient removal: {:?}",
                                 e
                             ))
-                        })
+                        })?;
+                    validator_store.notify_proposal_data_changed();
+                    Ok(())
                 })
             },
         )
@@ -966,7 +1215,9 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                                 "Error persisting gas limit: {:?}",
                                 e
                             ))
-                        })
+                        })?;
+                    validator_store.notify_proposal_data_changed();
+                    Ok(())
                 })
             },
         )
@@ -1002,12 +1253,60 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                                 "Error persisting gas limit removal: {:?}",
                                 e
                             ))
-                        })
+                        })?;
+                    validator_store.notify_proposal_data_changed();
+                    Ok(())
                 })
             },
         )
         .map(|reply| warp::reply::with_status(reply, warp::http::StatusCode::NO_CONTENT));
 
+    // GET /eth/v1/validator/{pubkey}/builder
+    let get_builder = eth_v1
+        .and(warp::path("validator"))
+        .and(warp::path::param::<PublicKey>())
+        .and(warp::path("builder"))
+        .and(warp::path::end())
+        .and(validator_store_filter.clone())
+        .then(
+            |pubkey: PublicKey, validator_store: Arc<ValidatorStore<T, E>>| {
+                blocking_json_task(move || {
+                    get_builder_settings(pubkey, validator_store).map(GenericResponse::from)
+                })
+            },
+        );
+
+    // POST /eth/v1/validator/{pubkey}/builder
+    let post_builder = eth_v1
+        .and(warp::path("validator"))
+        .and(warp::path::param::<PublicKey>())
+        .and(warp::path("builder"))
+        .and(warp::body::json())
+        .and(warp::path::end())
+        .and(validator_store_filter.clone())
+        .then(
+            |pubkey: PublicKey,
+             request: api_types::UpdateBuilderSettingsRequest,
+             validator_store: Arc<ValidatorStore<T, E>>| {
+                blocking_json_task(move || set_builder_settings(pubkey, request, validator_store))
+            },
+        )
+        .map(|reply| warp::reply::with_status(reply, warp::http::StatusCode::ACCEPTED));
+
+    // DELETE /eth/v1/validator/{pubkey}/builder
+    let delete_builder = eth_v1
+        .and(warp::path("validator"))
+        .and(warp::path::param::<PublicKey>())
+        .and(warp::path("builder"))
+        .and(warp::path::end())
+        .and(validator_store_filter.clone())
+        .then(
+            |pubkey: PublicKey, validator_store: Arc<ValidatorStore<T, E>>| {
+                blocking_json_task(move || delete_builder_settings(pubkey, validator_store))
+            },
+        )
+        .map(|reply| warp::reply::with_status(reply, warp::http::StatusCode::NO_CONTENT));
+
     // POST /eth/v1/validator/{pubkey}/voluntary_exit
     let post_validators_voluntary_exits = eth_v1
         .and(warp::path("validator"))
@@ -1016,7 +1315,7 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
         .and(warp::query::<api_types::VoluntaryExitQuery>())
         .and(warp::path::end())
         .and(validator_store_filter.clone())
-        .and(slot_clock_filter)
+        .and(slot_clock_filter.clone())
         .and(log_filter.clone())
         .and(task_executor_filter.clone())
         .then(
@@ -1120,6 +1419,146 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
         )
         .map(|reply| warp::reply::with_status(reply, warp::http::StatusCode::NO_CONTENT));
 
+    // GET /eth/v1/validator/{pubkey}/exit
+    let get_scheduled_exit = eth_v1
+        .and(warp::path("validator"))
+        .and(warp::path::param::<PublicKey>())
+        .and(warp::path("exit"))
+        .and(warp::path::end())
+        .and(validator_store_filter.clone())
+        .then(
+            |pubkey: PublicKey, validator_store: Arc<ValidatorStore<T, E>>| {
+                blocking_json_task(move || {
+                    get_scheduled_exit(pubkey, validator_store).map(GenericResponse::from)
+                })
+            },
+        );
+
+    // POST /eth/v1/validator/{pubkey}/exit
+    let post_scheduled_exit = eth_v1
+        .and(warp::path("validator"))
+        .and(warp::path::param::<PublicKey>())
+        .and(warp::path("exit"))
+        .and(warp::body::json())
+        .and(warp::path::end())
+        .and(validator_store_filter.clone())
+        .then(
+            |pubkey: PublicKey,
+             request: ScheduleExitRequest,
+             validator_store: Arc<ValidatorStore<T, E>>| {
+                blocking_json_task(move || {
+                    set_scheduled_exit(pubkey, request.epoch, validator_store)
+                })
+            },
+        )
+        .map(|reply| warp::reply::with_status(reply, warp::http::StatusCode::ACCEPTED));
+
+    // DELETE /eth/v1/validator/{pubkey}/exit
+    let delete_scheduled_exit = eth_v1
+        .and(warp::path("validator"))
+        .and(warp::path::param::<PublicKey>())
+        .and(warp::path("exit"))
+        .and(warp::path::end())
+        .and(validator_store_filter.clone())
+        .then(
+            |pubkey: PublicKey, validator_store: Arc<ValidatorStore<T, E>>| {
+                blocking_json_task(move || delete_scheduled_exit(pubkey, validator_store))
+            },
+        )
+        .map(|reply| warp::reply::with_status(reply, warp::http::StatusCode::NO_CONTENT));
+
+    // GET /eth/v1/validator/{pubkey}/bls_to_execution_change
+    let get_bls_to_execution_change = eth_v1
+        .and(warp::path("validator"))
+        .and(warp::path::param::<PublicKey>())
+        .and(warp::path("bls_to_execution_change"))
+        .and(warp::path::end())
+        .and(validator_store_filter.clone())
+        .then(
+            |pubkey: PublicKey, validator_store: Arc<ValidatorStore<T, E>>| {
+                blocking_json_task(move || {
+                    get_bls_to_execution_change(pubkey, validator_store).map(GenericResponse::from)
+                })
+            },
+        );
+
+    // POST /eth/v1/validator/{pubkey}/bls_to_execution_change
+    let withdrawal_secrets_dir = ctx.secrets_dir.clone();
+    let post_bls_to_execution_change = eth_v1
+        .and(warp::path("validator"))
+        .and(warp::path::param::<PublicKey>())
+        .and(warp::path("bls_to_execution_change"))
+        .and(warp::body::json())
+        .and(warp::path::end())
+        .and(validator_store_filter.clone())
+        .then(
+            move |pubkey: PublicKey,
+                  request: BlsToExecutionChangeRequest,
+                  validator_store: Arc<ValidatorStore<T, E>>| {
+                let secrets_dir = withdrawal_secrets_dir.clone();
+                blocking_json_task(move || {
+                    set_bls_to_execution_change(
+                        pubkey,
+                        request.to_execution_address,
+                        secrets_dir,
+                        validator_store,
+                    )
+                    .map(GenericResponse::from)
+                })
+            },
+        )
+        .map(|reply| warp::reply::with_status(reply, warp::http::StatusCode::ACCEPTED));
+
+    // DELETE /eth/v1/validator/{pubkey}/bls_to_execution_change
+    let delete_bls_to_execution_change = eth_v1
+        .and(warp::path("validator"))
+        .and(warp::path::param::<PublicKey>())
+        .and(warp::path("bls_to_execution_change"))
+        .and(warp::path::end())
+        .and(validator_store_filter.clone())
+        .then(
+            |pubkey: PublicKey, validator_store: Arc<ValidatorStore<T, E>>| {
+                blocking_json_task(move || delete_bls_to_execution_change(pubkey, validator_store))
+            },
+        )
+        .map(|reply| warp::reply::with_status(reply, warp::http::StatusCode::NO_CONTENT));
+
+    // GET /eth/v1/validator/{pubkey}/suspend
+    let get_validator_suspension = eth_v1
+        .and(warp::path("validator"))
+        .and(warp::path::param::<PublicKey>())
+        .and(warp::path("suspend"))
+        .and(warp::path::end())
+        .and(validator_store_filter.clone())
+        .then(
+            |pubkey: PublicKey, validator_store: Arc<ValidatorStore<T, E>>| {
+                blocking_json_task(move || {
+                    get_suspension(pubkey, validator_store).map(GenericResponse::from)
+                })
+            },
+        );
+
+    // POST /eth/v1/validator/{pubkey}/suspend
+    let post_validator_suspension = eth_v1
+        .and(warp::path("validator"))
+        .and(warp::path::param::<PublicKey>())
+        .and(warp::path("suspend"))
+        .and(warp::body::json())
+        .and(warp::path::end())
+        .and(validator_store_filter.clone())
+        .and(log_filter.clone())
+        .then(
+            |pubkey: PublicKey,
+             request: SetSuspensionRequest,
+             validator_store: Arc<ValidatorStore<T, E>>,
+             log: Logger| {
+                blocking_json_task(move || {
+                    set_suspension(pubkey, request.suspended, validator_store, log)
+                })
+            },
+        )
+        .map(|reply| warp::reply::with_status(reply, warp::http::StatusCode::ACCEPTED));
+
     // GET /eth/v1/keystores
     let get_std_keystores = std_keystores.and(validator_store_filter.clone()).then(
         |validator_store: Arc<ValidatorStore<T, E>>| {
@@ -1129,25 +1568,27 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
 
     // POST /eth/v1/keystores
     let post_std_keystores = std_keystores
-        .and(warp::body::json())
+        .and(warp::body::stream())
         .and(validator_dir_filter)
         .and(secrets_dir_filter)
         .and(validator_store_filter.clone())
         .and(task_executor_filter.clone())
         .and(log_filter.clone())
         .then(
-            move |request, validator_dir, secrets_dir, validator_store, task_executor, log| {
+            move |body, validator_dir, secrets_dir, validator_store, task_executor, log| {
                 let secrets_dir = store_passwords_in_secrets_dir.then_some(secrets_dir);
-                blocking_json_task(move || {
-                    keystores::import(
-                        request,
+                async move {
+                    let result = keystores::import(
+                        body,
                         validator_dir,
                         secrets_dir,
                         validator_store,
                         task_executor,
                         log,
                     )
-                })
+                    .await;
+                    convert_rejection(result).await
+                }
             },
         );
 
@@ -1253,9 +1694,17 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                         .or(get_lighthouse_validators_pubkey)
                         .or(get_lighthouse_ui_health)
                         .or(get_lighthouse_ui_graffiti)
+                        .or(get_lighthouse_ui_performance)
+                        .or(get_lighthouse_doppelganger)
+                        .or(get_lighthouse_handover)
+                        .or(get_lighthouse_logging)
                         .or(get_fee_recipient)
                         .or(get_gas_limit)
+                        .or(get_builder)
                         .or(get_graffiti)
+                        .or(get_scheduled_exit)
+                        .or(get_bls_to_execution_change)
+                        .or(get_validator_suspension)
                         .or(get_std_keystores)
                         .or(get_std_remotekeys)
                         .recover(warp_utils::reject::handle_rejection),
@@ -1265,12 +1714,22 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                         .or(post_validators_keystore)
                         .or(post_validators_mnemonic)
                         .or(post_validators_web3signer)
+                        .or(post_validators_watch_only)
                         .or(post_validators_voluntary_exits)
+                        .or(post_lighthouse_doppelganger)
+                        .or(post_lighthouse_handover)
+                        .or(post_lighthouse_handover_confirm)
+                        .or(post_lighthouse_slashing_protection_check)
+                        .or(post_lighthouse_logging)
                         .or(post_fee_recipient)
                         .or(post_gas_limit)
+                        .or(post_builder)
                         .or(post_std_keystores)
                         .or(post_std_remotekeys)
                         .or(post_graffiti)
+                        .or(post_scheduled_exit)
+                        .or(post_bls_to_execution_change)
+                        .or(post_validator_suspension)
                         .recover(warp_utils::reject::handle_rejection),
                 ))
                 .or(warp::patch()
@@ -1279,9 +1738,12 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                     delete_lighthouse_keystores
                         .or(delete_fee_recipient)
                         .or(delete_gas_limit)
+                        .or(delete_builder)
                         .or(delete_std_keystores)
                         .or(delete_std_remotekeys)
                         .or(delete_graffiti)
+                        .or(delete_scheduled_exit)
+                        .or(delete_bls_to_execution_change)
                         .recover(warp_utils::reject::handle_rejection),
                 )),
         )
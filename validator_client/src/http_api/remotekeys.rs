@@ -32,7 +32,7 @@ pub fn list<T: SlotClock + 'static, E: EthSpec>(
             let validating_pubkey = def.voting_public_key.compress();
 
             match &def.signing_definition {
-                SigningDefinition::LocalKeystore { .. } => None,
+                SigningDefinition::LocalKeystore { .. } | SigningDefinition::WatchOnly => None,
                 SigningDefinition::Web3Signer(Web3SignerDefinition { url, .. }) => {
                     Some(SingleListRemotekeysResponse {
                         pubkey: validating_pubkey,
@@ -127,6 +127,10 @@ fn import_single_remotekey<T: SlotClock + 'static, E: EthSpec>(
         builder_proposals: None,
         builder_boost_factor: None,
         prefer_builder_proposals: None,
+        scheduled_exit: None,
+        bls_to_execution_change: None,
+        handover_epoch: None,
+        suspended: false,
         description: String::from("Added by remotekey API"),
         signing_definition: SigningDefinition::Web3Signer(Web3SignerDefinition {
             url,
@@ -134,6 +138,8 @@ fn import_single_remotekey<T: SlotClock + 'static, E: EthSpec>(
             request_timeout_ms: None,
             client_identity_path: None,
             client_identity_password: None,
+            fallback_keystore_path: None,
+            fallback_keystore_password_path: None,
         }),
     };
     handle
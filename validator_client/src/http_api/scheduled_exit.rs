@@ -0,0 +1,108 @@
+use crate::validator_store::ValidatorStore;
+use account_utils::validator_definitions::ScheduledExit;
+use bls::PublicKey;
+use eth2::lighthouse_vc::types::{GetScheduledExitResponse, ScheduledExitStatus};
+use slot_clock::SlotClock;
+use std::sync::Arc;
+use types::{Epoch, EthSpec};
+
+pub fn get_scheduled_exit<T: 'static + SlotClock + Clone, E: EthSpec>(
+    validator_pubkey: PublicKey,
+    validator_store: Arc<ValidatorStore<T, E>>,
+) -> Result<GetScheduledExitResponse, warp::Rejection> {
+    let initialized_validators_rw_lock = validator_store.initialized_validators();
+    let initialized_validators = initialized_validators_rw_lock.read();
+    if initialized_validators
+        .validator(&validator_pubkey.compress())
+        .is_none()
+    {
+        return Err(warp_utils::reject::custom_not_found(
+            "The key was not found on the server".to_string(),
+        ));
+    }
+
+    let scheduled_exit = initialized_validators
+        .scheduled_exit(&validator_pubkey)
+        .ok_or_else(|| {
+            warp_utils::reject::custom_not_found(
+                "No exit is scheduled for this validator".to_string(),
+            )
+        })?;
+
+    Ok(GetScheduledExitResponse {
+        pubkey: validator_pubkey.into(),
+        epoch: scheduled_exit.epoch,
+        status: if scheduled_exit.submitted {
+            ScheduledExitStatus::Submitted
+        } else {
+            ScheduledExitStatus::Scheduled
+        },
+    })
+}
+
+pub fn set_scheduled_exit<T: 'static + SlotClock + Clone, E: EthSpec>(
+    validator_pubkey: PublicKey,
+    epoch: Epoch,
+    validator_store: Arc<ValidatorStore<T, E>>,
+) -> Result<(), warp::Rejection> {
+    let initialized_validators_rw_lock = validator_store.initialized_validators();
+    let mut initialized_validators = initialized_validators_rw_lock.write();
+    if initialized_validators
+        .validator(&validator_pubkey.compress())
+        .is_none()
+    {
+        return Err(warp_utils::reject::custom_not_found(
+            "The key was not found on the server, nothing to update".to_string(),
+        ));
+    }
+
+    if initialized_validators
+        .scheduled_exit(&validator_pubkey)
+        .map_or(false, |exit| exit.submitted)
+    {
+        return Err(warp_utils::reject::custom_bad_request(
+            "An exit has already been submitted for this validator".to_string(),
+        ));
+    }
+
+    let scheduled_exit = ScheduledExit {
+        epoch,
+        submitted: false,
+    };
+    initialized_validators
+        .set_scheduled_exit(&validator_pubkey, Some(scheduled_exit))
+        .map_err(|e| {
+            warp_utils::reject::custom_server_error(format!("Unable to schedule exit: {:?}", e))
+        })
+}
+
+pub fn delete_scheduled_exit<T: 'static + SlotClock + Clone, E: EthSpec>(
+    validator_pubkey: PublicKey,
+    validator_store: Arc<ValidatorStore<T, E>>,
+) -> Result<(), warp::Rejection> {
+    let initialized_validators_rw_lock = validator_store.initialized_validators();
+    let mut initialized_validators = initialized_validators_rw_lock.write();
+    if initialized_validators
+        .validator(&validator_pubkey.compress())
+        .is_none()
+    {
+        return Err(warp_utils::reject::custom_not_found(
+            "The key was not found on the server, nothing to delete".to_string(),
+        ));
+    }
+
+    match initialized_validators.scheduled_exit(&validator_pubkey) {
+        None => Ok(()),
+        Some(exit) if exit.submitted => Err(warp_utils::reject::custom_bad_request(
+            "The exit has already been submitted and cannot be cancelled".to_string(),
+        )),
+        Some(_) => initialized_validators
+            .set_scheduled_exit(&validator_pubkey, None)
+            .map_err(|e| {
+                warp_utils::reject::custom_server_error(format!(
+                    "Unable to cancel scheduled exit: {:?}",
+                    e
+                ))
+            }),
+    }
+}
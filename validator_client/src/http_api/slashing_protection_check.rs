@@ -0,0 +1,55 @@
+//! Check messages against the slashing protection database without signing them, so that
+//! external signing pipelines can pre-validate them against the history of this validator client.
+//!
+//! The verdict is advisory: a conflicting message may be signed after the check.
+
+use crate::validator_store::ValidatorStore;
+use eth2::lighthouse_vc::types::{
+    SlashingProtectionCheckMessage, SlashingProtectionCheckRequest,
+    SlashingProtectionCheckResponse, SlashingProtectionVerdict,
+};
+use slashing_protection::{NotSafe, Safe};
+use slot_clock::SlotClock;
+use std::sync::Arc;
+use types::EthSpec;
+
+pub fn check_slashing_protection<T: 'static + SlotClock + Clone, E: EthSpec>(
+    request: SlashingProtectionCheckRequest,
+    validator_store: Arc<ValidatorStore<T, E>>,
+) -> Result<SlashingProtectionCheckResponse, warp::Rejection> {
+    let pubkey = request.pubkey;
+    if !validator_store.has_validator(&pubkey) {
+        return Err(warp_utils::reject::custom_not_found(format!(
+            "no validator for {:?}",
+            pubkey
+        )));
+    }
+
+    let result = match &request.message {
+        SlashingProtectionCheckMessage::BlockHeader(block_header) => {
+            validator_store.check_block_slashing_protection(&pubkey, block_header)
+        }
+        SlashingProtectionCheckMessage::AttestationData(attestation_data) => {
+            validator_store.check_attestation_slashing_protection(&pubkey, attestation_data)
+        }
+    };
+
+    let (verdict, reason) = match result {
+        Ok(Safe::Valid) => (SlashingProtectionVerdict::Safe, None),
+        Ok(Safe::SameData) => (SlashingProtectionVerdict::SameData, None),
+        Err(
+            e @ (NotSafe::UnregisteredValidator(_)
+            | NotSafe::DisabledValidator(_)
+            | NotSafe::InvalidBlock(_)
+            | NotSafe::InvalidAttestation(_)),
+        ) => (SlashingProtectionVerdict::NotSafe, Some(format!("{:?}", e))),
+        Err(e) => {
+            return Err(warp_utils::reject::custom_server_error(format!(
+                "unable to check slashing protection: {:?}",
+                e
+            )))
+        }
+    };
+
+    Ok(SlashingProtectionCheckResponse { verdict, reason })
+}
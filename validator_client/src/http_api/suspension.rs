@@ -0,0 +1,63 @@
+use crate::validator_store::ValidatorStore;
+use bls::PublicKey;
+use eth2::lighthouse_vc::types::GetSuspensionResponse;
+use slog::{info, Logger};
+use slot_clock::SlotClock;
+use std::sync::Arc;
+use types::EthSpec;
+
+pub fn get_suspension<T: 'static + SlotClock + Clone, E: EthSpec>(
+    validator_pubkey: PublicKey,
+    validator_store: Arc<ValidatorStore<T, E>>,
+) -> Result<GetSuspensionResponse, warp::Rejection> {
+    let initialized_validators_rw_lock = validator_store.initialized_validators();
+    let initialized_validators = initialized_validators_rw_lock.read();
+    let pubkey = validator_pubkey.compress();
+    if initialized_validators.validator(&pubkey).is_none() {
+        return Err(warp_utils::reject::custom_not_found(
+            "The key was not found on the server".to_string(),
+        ));
+    }
+
+    Ok(GetSuspensionResponse {
+        pubkey,
+        suspended: initialized_validators.is_suspended(&pubkey),
+    })
+}
+
+pub fn set_suspension<T: 'static + SlotClock + Clone, E: EthSpec>(
+    validator_pubkey: PublicKey,
+    suspended: bool,
+    validator_store: Arc<ValidatorStore<T, E>>,
+    log: Logger,
+) -> Result<(), warp::Rejection> {
+    let initialized_validators_rw_lock = validator_store.initialized_validators();
+    let mut initialized_validators = initialized_validators_rw_lock.write();
+    let pubkey = validator_pubkey.compress();
+    if initialized_validators.validator(&pubkey).is_none() {
+        return Err(warp_utils::reject::custom_not_found(
+            "The key was not found on the server, nothing to update".to_string(),
+        ));
+    }
+    if initialized_validators.is_suspended(&pubkey) == suspended {
+        return Ok(());
+    }
+
+    initialized_validators
+        .set_suspended(&validator_pubkey, suspended)
+        .map_err(|e| {
+            warp_utils::reject::custom_server_error(format!("Unable to update suspension: {:?}", e))
+        })?;
+
+    if suspended {
+        info!(
+            log,
+            "Validator suspended";
+            "msg" => "no duties will be performed until it is resumed",
+            "pubkey" => ?pubkey,
+        );
+    } else {
+        info!(log, "Validator resumed"; "pubkey" => ?pubkey);
+    }
+    Ok(())
+}
@@ -16,7 +16,7 @@ use eth2::{
     Error as ApiError,
 };
 use eth2_keystore::KeystoreBuilder;
-use logging::test_logger;
+use logging::{test_logger, LogFilterHandle};
 use parking_lot::RwLock;
 use sensitive_url::SensitiveUrl;
 use slashing_protection::{SlashingDatabase, SLASHING_PROTECTION_FILENAME};
@@ -136,6 +136,8 @@ impl ApiTester {
             config: http_config,
             log,
             sse_logging_components: None,
+            log_filter: Some(LogFilterHandle::new(slog::Level::Info)),
+            performance_tracker: None,
             slot_clock,
             _phantom: PhantomData,
         });
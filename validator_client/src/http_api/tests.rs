@@ -3,12 +3,20 @@
 
 mod keystores;
 
-use crate::doppelganger_service::DoppelgangerService;
+use crate::doppelganger_service::{
+    DoppelgangerService, DoppelgangerStatus, DEFAULT_REMAINING_DETECTION_EPOCHS,
+};
 use crate::{
     http_api::{ApiSecret, Config as HttpConfig, Context},
     initialized_validators::InitializedValidators,
+    performance_service::{AttestationPerformance, EpochPerformance, PerformanceTracker},
+    signer_health_service::QUARANTINE_THRESHOLD,
+    validator_store::Error as ValidatorStoreError,
     Config, ValidatorDefinitions, ValidatorStore,
 };
+use account_utils::validator_definitions::{
+    SigningDefinition, ValidatorDefinition, Web3SignerDefinition,
+};
 use account_utils::{
     eth2_wallet::WalletBuilder, mnemonic_from_phrase, random_mnemonic, random_password,
     random_password_string, ZeroizeString,
@@ -20,7 +28,7 @@ use eth2::{
     Error as ApiError,
 };
 use eth2_keystore::KeystoreBuilder;
-use logging::test_logger;
+use logging::{test_logger, LogFilterHandle};
 use parking_lot::RwLock;
 use sensitive_url::SensitiveUrl;
 use slashing_protection::{SlashingDatabase, SLASHING_PROTECTION_FILENAME};
@@ -36,6 +44,8 @@ use tempfile::{tempdir, TempDir};
 use types::graffiti::GraffitiString;
 
 const PASSWORD_BYTES: &[u8] = &[42, 50, 37];
+const UNREACHABLE_WEB3SIGNER_URL: &str = "http://127.0.0.1:1/";
+const UNREACHABLE_WEB3SIGNER_ORIGIN: &str = "http://127.0.0.1:1";
 pub const TEST_DEFAULT_FEE_RECIPIENT: Address = Address::repeat_byte(42);
 
 type E = MainnetEthSpec;
@@ -44,6 +54,7 @@ struct ApiTester {
     client: ValidatorClientHttpClient,
     initialized_validators: Arc<RwLock<InitializedValidators>>,
     validator_store: Arc<ValidatorStore<TestingSlotClock, E>>,
+    performance_tracker: Arc<PerformanceTracker>,
     url: SensitiveUrl,
     slot_clock: TestingSlotClock,
     _validator_dir: TempDir,
@@ -112,6 +123,8 @@ impl ApiTester {
 
         let initialized_validators = validator_store.initialized_validators();
 
+        let performance_tracker = Arc::new(PerformanceTracker::new(2));
+
         let context = Arc::new(Context {
             task_executor: test_runtime.task_executor.clone(),
             api_secret,
@@ -130,6 +143,8 @@ impl ApiTester {
                 store_passwords_in_secrets_dir: false,
             },
             sse_logging_components: None,
+            log_filter: Some(LogFilterHandle::new(slog::Level::Info)),
+            performance_tracker: Some(performance_tracker.clone()),
             log,
             slot_clock: slot_clock.clone(),
             _phantom: PhantomData,
@@ -153,6 +168,7 @@ impl ApiTester {
             client,
             initialized_validators,
             validator_store,
+            performance_tracker,
             url,
             slot_clock,
             _validator_dir: validator_dir,
@@ -500,6 +516,51 @@ impl ApiTester {
         self
     }
 
+    pub async fn create_watch_only_validator(self) -> Self {
+        let initial_vals = self.vals_total();
+        let initial_enabled_vals = self.vals_enabled();
+
+        let pubkey: PublicKeyBytes = Keypair::random().pk.into();
+        let request = WatchOnlyValidatorRequest {
+            enable: true,
+            description: "watch-only".into(),
+            voting_public_key: pubkey.decompress().unwrap(),
+        };
+        self.client
+            .post_lighthouse_validators_watch_only(&[request])
+            .await
+            .unwrap();
+
+        assert_eq!(self.vals_total(), initial_vals + 1);
+        assert_eq!(self.vals_enabled(), initial_enabled_vals + 1);
+
+        // Duties are collected for a watch-only validator, but it never signs.
+        assert!(self
+            .validator_store
+            .voting_pubkeys::<Vec<_>, _>(DoppelgangerStatus::ignored)
+            .contains(&pubkey));
+        assert!(!self
+            .validator_store
+            .voting_pubkeys::<Vec<_>, _>(DoppelgangerStatus::only_safe)
+            .contains(&pubkey));
+        assert!(matches!(
+            self.validator_store
+                .randao_reveal(pubkey, Epoch::new(0))
+                .await,
+            Err(ValidatorStoreError::WatchOnly(_))
+        ));
+        assert!(!self
+            .client
+            .get_keystores()
+            .await
+            .unwrap()
+            .data
+            .iter()
+            .any(|keystore| keystore.validating_pubkey == pubkey));
+
+        self
+    }
+
     pub async fn test_sign_voluntary_exits(self, index: usize, maybe_epoch: Option<Epoch>) -> Self {
         let validator = &self.client.get_lighthouse_validators().await.unwrap().data[index];
         // manually setting validator index in `ValidatorStore`
@@ -810,6 +871,559 @@ impl ApiTester {
 
         self
     }
+
+    pub async fn test_doppelganger_override(self, index: usize) -> Self {
+        let validator = &self.client.get_lighthouse_validators().await.unwrap().data[index];
+        let pubkey = validator.voting_pubkey;
+        let get_status = || async {
+            self.client
+                .get_lighthouse_doppelganger()
+                .await
+                .unwrap()
+                .data
+                .into_iter()
+                .find(|status| status.pubkey == pubkey)
+                .unwrap()
+        };
+
+        // Validators registered in the genesis epoch are not subject to detection.
+        let status = get_status().await;
+        assert!(status.signing_enabled);
+        assert!(!status.detected);
+        assert_eq!(status.remaining_epochs, 0);
+
+        self.client
+            .post_lighthouse_doppelganger(&pubkey, DoppelgangerOverride::Rearm)
+            .await
+            .unwrap();
+        let status = get_status().await;
+        assert!(!status.signing_enabled);
+        assert_eq!(status.remaining_epochs, DEFAULT_REMAINING_DETECTION_EPOCHS);
+        assert_eq!(status.latest_observed_epoch, None);
+        assert!(!self
+            .validator_store
+            .doppelganger_protection_allows_signing(pubkey));
+
+        self.client
+            .post_lighthouse_doppelganger(&pubkey, DoppelgangerOverride::Release)
+            .await
+            .unwrap();
+        let status = get_status().await;
+        assert!(status.signing_enabled);
+        assert_eq!(status.remaining_epochs, 0);
+
+        // Overrides for unknown validators are rejected.
+        assert!(self
+            .client
+            .post_lighthouse_doppelganger(&PublicKeyBytes::empty(), DoppelgangerOverride::Release)
+            .await
+            .is_err());
+
+        self
+    }
+
+    pub async fn test_scheduled_exit(self, index: usize, epoch: Epoch) -> Self {
+        let validator = &self.client.get_lighthouse_validators().await.unwrap().data[index];
+        let pubkey = &validator.voting_pubkey;
+
+        // No exit is scheduled initially.
+        assert!(self.client.get_scheduled_exit(pubkey).await.is_err());
+
+        self.client
+            .post_scheduled_exit(pubkey, epoch)
+            .await
+            .unwrap();
+        let resp = self.client.get_scheduled_exit(pubkey).await.unwrap();
+        assert_eq!(resp.pubkey, *pubkey);
+        assert_eq!(resp.epoch, epoch);
+        assert_eq!(resp.status, ScheduledExitStatus::Scheduled);
+
+        self.client.delete_scheduled_exit(pubkey).await.unwrap();
+        assert!(self.client.get_scheduled_exit(pubkey).await.is_err());
+
+        self
+    }
+
+    pub async fn test_bls_to_execution_change_without_withdrawal_keystore(
+        self,
+        index: usize,
+    ) -> Self {
+        let validator = &self.client.get_lighthouse_validators().await.unwrap().data[index];
+        let pubkey = &validator.voting_pubkey;
+
+        // No change is queued initially, and cancelling a non-existent change succeeds.
+        assert!(self
+            .client
+            .get_bls_to_execution_change(pubkey)
+            .await
+            .is_err());
+        self.client
+            .delete_bls_to_execution_change(pubkey)
+            .await
+            .unwrap();
+
+        // Validators created via the API don't store their withdrawal keystore, so no change can
+        // be signed for them.
+        assert!(self
+            .client
+            .post_bls_to_execution_change(pubkey, Address::repeat_byte(1))
+            .await
+            .is_err());
+        assert!(self
+            .client
+            .get_bls_to_execution_change(pubkey)
+            .await
+            .is_err());
+
+        self
+    }
+
+    pub async fn test_validator_suspension(self, index: usize) -> Self {
+        let validator = &self.client.get_lighthouse_validators().await.unwrap().data[index];
+        let pubkey = validator.voting_pubkey;
+        let is_voting = || {
+            self.validator_store
+                .voting_pubkeys::<Vec<_>, _>(DoppelgangerStatus::ignored)
+                .contains(&pubkey)
+        };
+
+        let resp = self.client.get_validator_suspension(&pubkey).await.unwrap();
+        assert_eq!(resp.pubkey, pubkey);
+        assert!(!resp.suspended);
+        assert!(is_voting());
+
+        // A suspended validator keeps its key, but performs no duties and signs nothing.
+        self.client
+            .post_validator_suspension(&pubkey, true)
+            .await
+            .unwrap();
+        assert!(
+            self.client
+                .get_validator_suspension(&pubkey)
+                .await
+                .unwrap()
+                .suspended
+        );
+        assert!(!is_voting());
+        assert!(self.validator_store.has_validator(&pubkey));
+        assert!(matches!(
+            self.validator_store
+                .randao_reveal(pubkey, Epoch::new(0))
+                .await,
+            Err(ValidatorStoreError::Suspended(_))
+        ));
+
+        self.client
+            .post_validator_suspension(&pubkey, false)
+            .await
+            .unwrap();
+        assert!(
+            !self
+                .client
+                .get_validator_suspension(&pubkey)
+                .await
+                .unwrap()
+                .suspended
+        );
+        assert!(is_voting());
+        assert!(self
+            .validator_store
+            .randao_reveal(pubkey, Epoch::new(0))
+            .await
+            .is_ok());
+
+        // Unknown validators are rejected.
+        assert!(self
+            .client
+            .post_validator_suspension(&PublicKeyBytes::empty(), true)
+            .await
+            .is_err());
+
+        self
+    }
+
+    pub async fn test_log_level(self) -> Self {
+        let default = self.client.get_lighthouse_logging().await.unwrap().data;
+        assert_eq!(
+            default,
+            LogFilterData {
+                level: "info".to_string(),
+                targets: <_>::default(),
+            }
+        );
+
+        let log_filter = LogFilterData {
+            level: "warn".to_string(),
+            targets: [(
+                "validator_client::duties_service".to_string(),
+                "debug".to_string(),
+            )]
+            .into_iter()
+            .collect(),
+        };
+        self.client
+            .post_lighthouse_logging(&log_filter)
+            .await
+            .unwrap();
+        assert_eq!(
+            self.client.get_lighthouse_logging().await.unwrap().data,
+            log_filter
+        );
+
+        // Unknown levels are rejected without changing the filter.
+        assert!(self
+            .client
+            .post_lighthouse_logging(&LogFilterData {
+                level: "verbose".to_string(),
+                targets: <_>::default(),
+            })
+            .await
+            .is_err());
+        assert_eq!(
+            self.client.get_lighthouse_logging().await.unwrap().data,
+            log_filter
+        );
+
+        self
+    }
+
+    pub async fn test_handover(self, index: usize) -> Self {
+        let validator = &self.client.get_lighthouse_validators().await.unwrap().data[index];
+        let pubkey = validator.voting_pubkey;
+        let handover_epoch = Epoch::new(1);
+        let confirm_request = ConfirmHandoverRequest {
+            pubkeys: vec![pubkey],
+        };
+
+        // Handovers of unknown keys are rejected.
+        assert!(self
+            .client
+            .post_lighthouse_handover(&HandoverRequest {
+                epoch: handover_epoch,
+                pubkeys: vec![PublicKeyBytes::empty()],
+            })
+            .await
+            .is_err());
+        assert!(self
+            .client
+            .post_lighthouse_handover_confirm(&confirm_request)
+            .await
+            .is_err());
+
+        self.client
+            .post_lighthouse_handover(&HandoverRequest {
+                epoch: handover_epoch,
+                pubkeys: vec![pubkey],
+            })
+            .await
+            .unwrap();
+        let statuses = self.client.get_lighthouse_handover().await.unwrap().data;
+        assert_eq!(
+            statuses,
+            vec![HandoverStatus {
+                pubkey,
+                epoch: handover_epoch,
+                complete: false,
+            }]
+        );
+
+        // The handover cannot be confirmed until its epoch is reached.
+        assert!(self
+            .client
+            .post_lighthouse_handover_confirm(&confirm_request)
+            .await
+            .is_err());
+
+        self.slot_clock
+            .set_slot(handover_epoch.start_slot(E::slots_per_epoch()).as_u64());
+        let statuses = self.client.get_lighthouse_handover().await.unwrap().data;
+        assert!(statuses[0].complete);
+
+        let resp = self
+            .client
+            .post_lighthouse_handover_confirm(&confirm_request)
+            .await
+            .unwrap();
+        assert_eq!(resp.slashing_protection.data.len(), 1);
+        assert_eq!(resp.slashing_protection.data[0].pubkey, pubkey);
+
+        self
+    }
+
+    /// Adds a validator using a Web3Signer instance which is down, with a fallback keystore if
+    /// `fallback` is set, and returns its public key.
+    async fn add_unreachable_web3signer_validator(&self, fallback: bool) -> PublicKeyBytes {
+        let keypair = Keypair::random();
+        // The fallback keystore is decrypted when the validator is added, so its files only need
+        // to exist until then.
+        let fallback_dir = tempdir().unwrap();
+        let (fallback_keystore_path, fallback_keystore_password_path) = if fallback {
+            let password = random_password();
+            let keystore = KeystoreBuilder::new(&keypair, password.as_bytes(), String::new())
+                .unwrap()
+                .build()
+                .unwrap();
+            let keystore_path = fallback_dir.path().join("keystore.json");
+            let password_path = fallback_dir.path().join("password.txt");
+            keystore
+                .to_json_writer(std::fs::File::create(&keystore_path).unwrap())
+                .unwrap();
+            std::fs::write(&password_path, password.as_bytes()).unwrap();
+            (Some(keystore_path), Some(password_path))
+        } else {
+            (None, None)
+        };
+
+        let definition = ValidatorDefinition {
+            enabled: true,
+            voting_public_key: keypair.pk.clone(),
+            graffiti: None,
+            suggested_fee_recipient: None,
+            gas_limit: None,
+            builder_proposals: None,
+            builder_boost_factor: None,
+            prefer_builder_proposals: None,
+            scheduled_exit: None,
+            bls_to_execution_change: None,
+            handover_epoch: None,
+            suspended: false,
+            description: String::new(),
+            signing_definition: SigningDefinition::Web3Signer(Web3SignerDefinition {
+                // Nothing listens on port 1, so requests to the signer fail immediately.
+                url: UNREACHABLE_WEB3SIGNER_URL.into(),
+                root_certificate_path: None,
+                request_timeout_ms: None,
+                client_identity_path: None,
+                client_identity_password: None,
+                fallback_keystore_path,
+                fallback_keystore_password_path,
+            }),
+        };
+        self.validator_store
+            .add_validator(definition)
+            .await
+            .unwrap();
+        keypair.pk.into()
+    }
+
+    pub async fn test_web3signer_fallback_when_signer_down(self) -> Self {
+        let with_fallback = self.add_unreachable_web3signer_validator(true).await;
+        let without_fallback = self.add_unreachable_web3signer_validator(false).await;
+        let exit = VoluntaryExit {
+            epoch: Epoch::new(0),
+            validator_index: 0,
+        };
+
+        // The fallback keystore signs when the request to the signer fails.
+        self.validator_store
+            .sign_voluntary_exit(with_fallback, exit.clone())
+            .await
+            .unwrap();
+        assert!(self
+            .validator_store
+            .sign_voluntary_exit(without_fallback, exit.clone())
+            .await
+            .is_err());
+
+        // Once the signer fails its health checks it is quarantined, but the fallback keystore
+        // still signs for the validator which has one.
+        for _ in 0..QUARANTINE_THRESHOLD {
+            self.validator_store
+                .signer_health()
+                .record_failure(UNREACHABLE_WEB3SIGNER_ORIGIN);
+        }
+        assert!(self.validator_store.signer_quarantined(&with_fallback));
+        self.validator_store
+            .sign_voluntary_exit(with_fallback, exit.clone())
+            .await
+            .unwrap();
+        assert!(matches!(
+            self.validator_store
+                .sign_voluntary_exit(without_fallback, exit)
+                .await,
+            Err(ValidatorStoreError::SignerQuarantined(_))
+        ));
+
+        self
+    }
+
+    pub async fn test_ui_performance(self) -> Self {
+        let pubkey = PublicKeyBytes::empty();
+        let attestation = |inclusion_distance| AttestationPerformance {
+            head: true,
+            target: true,
+            source: true,
+            inclusion_distance: Some(inclusion_distance),
+            rewards: 10,
+        };
+        let record = |attestation, blocks_proposed| {
+            [(
+                pubkey,
+                EpochPerformance {
+                    validator_index: 7,
+                    attestation,
+                    blocks_proposed,
+                    blocks_missed: 0,
+                    proposal_rewards: 100 * blocks_proposed,
+                },
+            )]
+            .into()
+        };
+
+        let performance = self
+            .client
+            .get_lighthouse_ui_performance(None)
+            .await
+            .unwrap();
+        assert_eq!(performance.data.start_epoch, None);
+        assert!(performance.data.validators.is_empty());
+
+        // Only the most recent two epochs are kept.
+        self.performance_tracker
+            .insert(Epoch::new(1), record(None, 0));
+        self.performance_tracker
+            .insert(Epoch::new(2), record(Some(attestation(1)), 1));
+        let missed = AttestationPerformance {
+            head: false,
+            target: false,
+            source: false,
+            inclusion_distance: None,
+            rewards: -10,
+        };
+        self.performance_tracker
+            .insert(Epoch::new(3), record(Some(missed), 0));
+
+        let performance = self
+            .client
+            .get_lighthouse_ui_performance(None)
+            .await
+            .unwrap();
+        assert_eq!(performance.data.start_epoch, Some(Epoch::new(2)));
+        assert_eq!(performance.data.end_epoch, Some(Epoch::new(3)));
+        assert_eq!(
+            performance.data.validators,
+            vec![ValidatorPerformanceData {
+                pubkey,
+                validator_index: 7,
+                attestations_expected: 2,
+                attestations_included: 1,
+                head_hits: 1,
+                target_hits: 1,
+                source_hits: 1,
+                average_inclusion_distance: Some(1.0),
+                blocks_proposed: 1,
+                blocks_missed: 0,
+                attestation_rewards: 0,
+                proposal_rewards: 100,
+            }]
+        );
+
+        let performance = self
+            .client
+            .get_lighthouse_ui_performance(Some(1))
+            .await
+            .unwrap();
+        assert_eq!(performance.data.start_epoch, Some(Epoch::new(3)));
+        assert_eq!(performance.data.validators[0].attestations_included, 0);
+        assert_eq!(performance.data.validators[0].attestation_rewards, -10);
+
+        self
+    }
+
+    pub async fn test_slashing_protection_check(self, index: usize) -> Self {
+        let validator = &self.client.get_lighthouse_validators().await.unwrap().data[index];
+        let pubkey = validator.voting_pubkey;
+        let attestation_data = |beacon_block_root: u64| AttestationData {
+            beacon_block_root: Hash256::from_low_u64_be(beacon_block_root),
+            source: Checkpoint {
+                epoch: Epoch::new(0),
+                root: Hash256::zero(),
+            },
+            target: Checkpoint {
+                epoch: Epoch::new(1),
+                root: Hash256::zero(),
+            },
+            ..AttestationData::default()
+        };
+        let check = |pubkey, message| {
+            let client = &self.client;
+            async move {
+                client
+                    .post_lighthouse_slashing_protection_check(&SlashingProtectionCheckRequest {
+                        pubkey,
+                        message,
+                    })
+                    .await
+            }
+        };
+
+        // Checks for unknown keys are rejected.
+        assert!(check(
+            PublicKeyBytes::empty(),
+            SlashingProtectionCheckMessage::AttestationData(attestation_data(1))
+        )
+        .await
+        .is_err());
+
+        let block_header = BeaconBlockHeader {
+            slot: Slot::new(1),
+            proposer_index: 0,
+            parent_root: Hash256::zero(),
+            state_root: Hash256::zero(),
+            body_root: Hash256::zero(),
+        };
+        let resp = check(
+            pubkey,
+            SlashingProtectionCheckMessage::BlockHeader(block_header),
+        )
+        .await
+        .unwrap();
+        assert_eq!(resp.verdict, SlashingProtectionVerdict::Safe);
+
+        // Checking an attestation doesn't record it.
+        for _ in 0..2 {
+            let resp = check(
+                pubkey,
+                SlashingProtectionCheckMessage::AttestationData(attestation_data(1)),
+            )
+            .await
+            .unwrap();
+            assert_eq!(resp.verdict, SlashingProtectionVerdict::Safe);
+        }
+
+        let mut attestation = Attestation::Base(AttestationBase {
+            aggregation_bits: BitList::with_capacity(
+                <E as EthSpec>::MaxValidatorsPerCommittee::to_usize(),
+            )
+            .unwrap(),
+            data: attestation_data(1),
+            signature: AggregateSignature::empty(),
+        });
+        self.validator_store
+            .sign_attestation(pubkey, 0, &mut attestation, Epoch::new(1))
+            .await
+            .unwrap();
+
+        let resp = check(
+            pubkey,
+            SlashingProtectionCheckMessage::AttestationData(attestation_data(1)),
+        )
+        .await
+        .unwrap();
+        assert_eq!(resp.verdict, SlashingProtectionVerdict::SameData);
+
+        // A different attestation with the same target is a double vote.
+        let resp = check(
+            pubkey,
+            SlashingProtectionCheckMessage::AttestationData(attestation_data(2)),
+        )
+        .await
+        .unwrap();
+        assert_eq!(resp.verdict, SlashingProtectionVerdict::NotSafe);
+        assert!(resp.reason.unwrap().contains("DoubleVote"));
+
+        self
+    }
 }
 
 struct HdValidatorScenario {
@@ -961,6 +1575,89 @@ async fn routes_with_invalid_auth() {
                 .set_graffiti(&PublicKeyBytes::empty(), GraffitiString::default())
                 .await
         })
+        .await
+        .test_with_invalid_auth(|client| async move {
+            client.get_scheduled_exit(&PublicKeyBytes::empty()).await
+        })
+        .await
+        .test_with_invalid_auth(|client| async move {
+            client
+                .post_scheduled_exit(&PublicKeyBytes::empty(), Epoch::new(0))
+                .await
+        })
+        .await
+        .test_with_invalid_auth(|client| async move {
+            client.delete_scheduled_exit(&PublicKeyBytes::empty()).await
+        })
+        .await
+        .test_with_invalid_auth(|client| async move {
+            client
+                .get_bls_to_execution_change(&PublicKeyBytes::empty())
+                .await
+        })
+        .await
+        .test_with_invalid_auth(|client| async move {
+            client
+                .post_bls_to_execution_change(&PublicKeyBytes::empty(), Address::zero())
+                .await
+        })
+        .await
+        .test_with_invalid_auth(|client| async move {
+            client
+                .delete_bls_to_execution_change(&PublicKeyBytes::empty())
+                .await
+        })
+        .await
+        .test_with_invalid_auth(|client| async move {
+            client
+                .get_validator_suspension(&PublicKeyBytes::empty())
+                .await
+        })
+        .await
+        .test_with_invalid_auth(|client| async move {
+            client
+                .post_validator_suspension(&PublicKeyBytes::empty(), true)
+                .await
+        })
+        .await
+        .test_with_invalid_auth(|client| async move { client.get_lighthouse_doppelganger().await })
+        .await
+        .test_with_invalid_auth(|client| async move { client.get_lighthouse_handover().await })
+        .await
+        .test_with_invalid_auth(|client| async move { client.get_lighthouse_logging().await })
+        .await
+        .test_with_invalid_auth(|client| async move {
+            client
+                .post_lighthouse_logging(&LogFilterData {
+                    level: "debug".to_string(),
+                    targets: <_>::default(),
+                })
+                .await
+        })
+        .await
+        .test_with_invalid_auth(|client| async move {
+            client
+                .post_lighthouse_handover(&HandoverRequest {
+                    epoch: Epoch::new(0),
+                    pubkeys: vec![],
+                })
+                .await
+        })
+        .await
+        .test_with_invalid_auth(|client| async move {
+            client
+                .post_lighthouse_handover_confirm(&ConfirmHandoverRequest { pubkeys: vec![] })
+                .await
+        })
+        .await
+        .test_with_invalid_auth(|client| async move {
+            client
+                .post_lighthouse_doppelganger(
+                    &PublicKeyBytes::empty(),
+                    DoppelgangerOverride::Release,
+                )
+                .await
+        })
         .await;
 }
 
@@ -1315,6 +2012,120 @@ async fn validator_graffiti_api() {
         .await;
 }
 
+#[tokio::test]
+async fn doppelganger_override_api() {
+    ApiTester::new()
+        .await
+        .create_hd_validators(HdValidatorScenario {
+            count: 2,
+            specify_mnemonic: false,
+            key_derivation_path_offset: 0,
+            disabled: vec![],
+        })
+        .await
+        .assert_validators_count(2)
+        .test_doppelganger_override(0)
+        .await;
+}
+
+#[tokio::test]
+async fn validator_scheduled_exit_api() {
+    ApiTester::new()
+        .await
+        .create_hd_validators(HdValidatorScenario {
+            count: 2,
+            specify_mnemonic: false,
+            key_derivation_path_offset: 0,
+            disabled: vec![],
+        })
+        .await
+        .assert_validators_count(2)
+        .test_scheduled_exit(1, Epoch::new(1024))
+        .await;
+}
+
+#[tokio::test]
+async fn validator_bls_to_execution_change_api() {
+    ApiTester::new()
+        .await
+        .create_hd_validators(HdValidatorScenario {
+            count: 2,
+            specify_mnemonic: false,
+            key_derivation_path_offset: 0,
+            disabled: vec![],
+        })
+        .await
+        .assert_validators_count(2)
+        .test_bls_to_execution_change_without_withdrawal_keystore(1)
+        .await;
+}
+
+#[tokio::test]
+async fn validator_suspension_api() {
+    ApiTester::new()
+        .await
+        .create_hd_validators(HdValidatorScenario {
+            count: 2,
+            specify_mnemonic: false,
+            key_derivation_path_offset: 0,
+            disabled: vec![],
+        })
+        .await
+        .assert_validators_count(2)
+        .test_validator_suspension(1)
+        .await;
+}
+
+#[tokio::test]
+async fn validator_handover_api() {
+    ApiTester::new()
+        .await
+        .create_hd_validators(HdValidatorScenario {
+            count: 2,
+            specify_mnemonic: false,
+            key_derivation_path_offset: 0,
+            disabled: vec![],
+        })
+        .await
+        .assert_validators_count(2)
+        .test_handover(1)
+        .await;
+}
+
+#[tokio::test]
+async fn web3signer_fallback_when_signer_down() {
+    ApiTester::new()
+        .await
+        .test_web3signer_fallback_when_signer_down()
+        .await;
+}
+
+#[tokio::test]
+async fn slashing_protection_check_api() {
+    ApiTester::new()
+        .await
+        .create_hd_validators(HdValidatorScenario {
+            count: 2,
+            specify_mnemonic: false,
+            key_derivation_path_offset: 0,
+            disabled: vec![],
+        })
+        .await
+        .assert_validators_count(2)
+        .test_slashing_protection_check(1)
+        .await;
+}
+
+#[tokio::test]
+async fn ui_performance_api() {
+    ApiTester::new().await.test_ui_performance().await;
+}
+
+#[tokio::test]
+async fn log_level_api() {
+    ApiTester::new().await.test_log_level().await;
+}
+
 #[tokio::test]
 async fn keystore_validator_creation() {
     ApiTester::new()
@@ -1344,6 +2155,18 @@ async fn keystore_validator_creation() {
         .assert_validators_count(2);
 }
 
+#[tokio::test]
+async fn watch_only_validator_creation() {
+    ApiTester::new()
+        .await
+        .assert_enabled_validators_count(0)
+        .assert_validators_count(0)
+        .create_watch_only_validator()
+        .await
+        .assert_enabled_validators_count(1)
+        .assert_validators_count(1);
+}
+
 #[tokio::test]
 async fn web3signer_validator_creation() {
     ApiTester::new()
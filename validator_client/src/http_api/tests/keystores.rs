@@ -558,6 +558,53 @@ async fn import_keystores_wrong_password() {
     .await;
 }
 
+#[tokio::test]
+async fn import_many_keystores_preserves_order() {
+    run_test(|tester| async move {
+        let num_keystores = 12;
+        let (mut keystores, mut passwords): (Vec<_>, Vec<_>) = (0..num_keystores)
+            .map(|_| {
+                let password = random_password_string();
+                (new_keystore(password.clone()), password)
+            })
+            .unzip();
+
+        // Use an incorrect password for every third keystore.
+        for password in passwords.iter_mut().step_by(3) {
+            *password = random_password_string();
+        }
+
+        // Repeat the second keystore at the end of the request.
+        keystores.push(keystores[1].clone());
+        passwords.push(passwords[1].clone());
+
+        let import_res = tester
+            .client
+            .post_keystores(&ImportKeystoresRequest {
+                keystores: keystores.clone(),
+                passwords,
+                slashing_protection: None,
+            })
+            .await
+            .unwrap();
+
+        let expected_statuses = (0..num_keystores)
+            .map(|i| {
+                if i % 3 == 0 {
+                    ImportKeystoreStatus::Error
+                } else {
+                    ImportKeystoreStatus::Imported
+                }
+            })
+            .chain(all_duplicate(1));
+        check_keystore_import_response(&import_res, expected_statuses);
+
+        let get_res = tester.client.get_keystores().await.unwrap();
+        assert_eq!(get_res.data.len(), num_keystores - num_keystores / 3);
+    })
+    .await;
+}
+
 #[tokio::test]
 async fn import_invalid_slashing_protection() {
     run_test(|tester| async move {
@@ -658,6 +705,13 @@ async fn check_get_set_fee_recipient() {
             )
             .await
             .expect("should update fee recipient");
+        // the update should trigger a refresh of builder registrations
+        tokio::time::timeout(
+            Duration::from_secs(1),
+            tester.validator_store.proposal_data_changed(),
+        )
+        .await
+        .expect("should notify of the fee recipient change");
         // now everything but pubkey[1] should be TEST_DEFAULT_FEE_RECIPIENT
         for (i, pubkey) in all_pubkeys.iter().enumerate() {
             let get_res = tester
@@ -952,6 +1006,120 @@ async fn check_get_set_gas_limit() {
     .await
 }
 
+#[tokio::test]
+async fn check_get_set_builder_settings() {
+    run_test(|tester: ApiTester| async move {
+        let password = random_password_string();
+        let keystores = (0..2)
+            .map(|_| new_keystore(password.clone()))
+            .collect::<Vec<_>>();
+        let all_pubkeys = keystores.iter().map(keystore_pubkey).collect::<Vec<_>>();
+
+        let import_res = tester
+            .client
+            .post_keystores(&ImportKeystoresRequest {
+                keystores: keystores.clone(),
+                passwords: vec![password.clone(); keystores.len()],
+                slashing_protection: None,
+            })
+            .await
+            .unwrap();
+        check_keystore_import_response(&import_res, all_imported(keystores.len()));
+
+        let default_settings = |pubkey: &PublicKeyBytes| GetBuilderSettingsResponse {
+            pubkey: *pubkey,
+            builder_proposals: false,
+            builder_boost_factor: None,
+        };
+
+        // Before setting anything, every validator uses the process-wide defaults.
+        for pubkey in &all_pubkeys {
+            assert_eq!(
+                tester.client.get_builder_settings(pubkey).await.unwrap(),
+                default_settings(pubkey)
+            );
+        }
+
+        // Set both settings for pubkey[0], then update only the boost factor.
+        tester
+            .client
+            .post_builder_settings(
+                &all_pubkeys[0],
+                &UpdateBuilderSettingsRequest {
+                    builder_proposals: Some(true),
+                    builder_boost_factor: Some(50),
+                },
+            )
+            .await
+            .unwrap();
+        tester
+            .client
+            .post_builder_settings(
+                &all_pubkeys[0],
+                &UpdateBuilderSettingsRequest {
+                    builder_proposals: None,
+                    builder_boost_factor: Some(80),
+                },
+            )
+            .await
+            .unwrap();
+
+        let expected = GetBuilderSettingsResponse {
+            pubkey: all_pubkeys[0],
+            builder_proposals: true,
+            builder_boost_factor: Some(80),
+        };
+        assert_eq!(
+            tester
+                .client
+                .get_builder_settings(&all_pubkeys[0])
+                .await
+                .unwrap(),
+            expected
+        );
+        assert_eq!(
+            tester
+                .client
+                .get_builder_settings(&all_pubkeys[1])
+                .await
+                .unwrap(),
+            default_settings(&all_pubkeys[1])
+        );
+
+        // The settings are recorded in the validator definitions.
+        {
+            let initialized_validators = tester.initialized_validators.read();
+            let definition = initialized_validators
+                .validator_definitions()
+                .iter()
+                .find(|def| def.voting_public_key.compress() == all_pubkeys[0])
+                .unwrap();
+            assert_eq!(definition.builder_proposals, Some(true));
+            assert_eq!(definition.builder_boost_factor, Some(80));
+        }
+
+        // Deleting the settings restores the process-wide defaults.
+        tester
+            .client
+            .delete_builder_settings(&all_pubkeys[0])
+            .await
+            .unwrap();
+        assert_eq!(
+            tester
+                .client
+                .get_builder_settings(&all_pubkeys[0])
+                .await
+                .unwrap(),
+            default_settings(&all_pubkeys[0])
+        );
+
+        // Unknown validators are rejected.
+        let unknown = Keypair::random().pk.compress();
+        assert!(tester.client.get_builder_settings(&unknown).await.is_err());
+    })
+    .await
+}
+
 fn all_indices(count: usize) -> Vec<usize> {
     (0..count).collect()
 }
@@ -28,8 +28,9 @@ pub const UPDATE_ATTESTERS_FETCH: &str = "update_attesters_fetch";
 pub const UPDATE_ATTESTERS_STORE: &str = "update_attesters_store";
 pub const ATTESTER_DUTIES_HTTP_POST: &str = "attester_duties_http_post";
 pub const PROPOSER_DUTIES_HTTP_GET: &str = "proposer_duties_http_get";
+pub const PROPOSER_DUTIES_PREFETCH_HTTP_GET: &str = "proposer_duties_prefetch_http_get";
 pub const VALIDATOR_DUTIES_SYNC_HTTP_POST: &str = "validator_duties_sync_http_post";
-pub const VALIDATOR_ID_HTTP_GET: &str = "validator_id_http_get";
+pub const VALIDATOR_ID_HTTP_POST: &str = "validator_id_http_post";
 pub const SUBSCRIPTIONS_HTTP_POST: &str = "subscriptions_http_post";
 pub const UPDATE_PROPOSERS: &str = "update_proposers";
 pub const ATTESTATION_SELECTION_PROOFS: &str = "attestation_selection_proofs";
@@ -53,6 +54,11 @@ lazy_static::lazy_static! {
         "Number of total validators (enabled and disabled)"
     );
 
+    pub static ref SELECTION_PROOF_QUEUE_LENGTH: Result<IntGauge> = try_create_int_gauge(
+        "vc_selection_proof_queue_length",
+        "Number of duties waiting for selection proofs to be signed"
+    );
+
     pub static ref SIGNED_BLOCKS_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
         "vc_signed_beacon_blocks_total",
         "Total count of attempted block signings",
@@ -112,6 +118,14 @@ lazy_static::lazy_static! {
         "vc_slashing_protection_prune_times_seconds",
         "Time required to prune the slashing protection DB",
     );
+    pub static ref SLASHING_PROTECTION_EXPORT_TIMES: Result<Histogram> = try_create_histogram(
+        "vc_slashing_protection_export_times_seconds",
+        "Time required to export the slashing protection DB to an interchange file",
+    );
+    pub static ref SLASHING_PROTECTION_EXPORT_ERRORS: Result<IntCounter> = try_create_int_counter(
+        "vc_slashing_protection_export_errors_total",
+        "Total count of failures to export the slashing protection DB to an interchange file",
+    );
     pub static ref BLOCK_SERVICE_TIMES: Result<HistogramVec> = try_create_histogram_vec(
         "vc_beacon_block_service_task_times_seconds",
         "Duration to perform beacon block service tasks",
@@ -131,6 +145,10 @@ lazy_static::lazy_static! {
         "vc_beacon_block_proposal_changed",
         "A duties update discovered a new block proposer for the current slot",
     );
+    pub static ref PREFETCHED_PROPOSER_DUTIES_CHANGED: Result<IntCounter> = try_create_int_counter(
+        "vc_prefetched_proposer_duties_changed",
+        "Proposer duties prefetched during the previous epoch differed from the final duties",
+    );
     /*
      * Endpoint metrics
      */
@@ -144,6 +162,11 @@ lazy_static::lazy_static! {
         "The number of beacon node requests for each endpoint",
         &["endpoint"]
     );
+    pub static ref ENDPOINT_HEALTH: Result<GaugeVec> = try_create_float_gauge_vec(
+        "bn_endpoint_health_score",
+        "The health score of each beacon node endpoint, based on recent request outcomes",
+        &["endpoint"]
+    );
 
     /*
     * Beacon node availability metrics
@@ -170,6 +193,11 @@ lazy_static::lazy_static! {
         "sync_eth2_fallback_connected",
         "Set to 1 if connected to atleast one synced eth2 fallback node, otherwise set to 0",
     );
+
+    pub static ref BEACON_NODES_DIVERGENT: Result<IntGauge> = try_create_int_gauge(
+        "vc_beacon_nodes_divergent",
+        "Set to 1 if the beacon nodes report different networks or fork schedules",
+    );
     /*
      * Signing Metrics
      */
@@ -182,12 +210,75 @@ lazy_static::lazy_static! {
         "vc_block_signing_times_seconds",
         "Duration to obtain a signature for a block",
     );
+    pub static ref WEB3SIGNER_REQUEST_TIMES: Result<HistogramVec> = try_create_histogram_vec(
+        "vc_web3signer_request_times_seconds",
+        "Duration of signing requests to each Web3Signer instance",
+        &["signer"]
+    );
+    pub static ref WEB3SIGNER_REQUEST_ERRORS: Result<IntCounterVec> = try_create_int_counter_vec(
+        "vc_web3signer_request_errors_total",
+        "Total count of failed signing requests to each Web3Signer instance",
+        &["signer"]
+    );
+    pub static ref WEB3SIGNER_SIGNING_TIMEOUTS: Result<IntCounterVec> = try_create_int_counter_vec(
+        "vc_web3signer_signing_timeouts_total",
+        "Total count of signing requests to each Web3Signer instance which exceeded the signing \
+        timeout of their operation",
+        &["signer", "operation"]
+    );
+    pub static ref WEB3SIGNER_FALLBACK_SIGNATURES: Result<IntCounterVec> =
+        try_create_int_counter_vec(
+            "vc_web3signer_fallback_signatures_total",
+            "Total count of signatures produced by a local fallback keystore after a signing \
+            request to a Web3Signer instance timed out or failed",
+            &["signer", "operation"]
+        );
+    pub static ref WEB3SIGNER_HEALTH_CHECK_FAILURES: Result<IntCounterVec> =
+        try_create_int_counter_vec(
+            "vc_web3signer_health_check_failures_total",
+            "Total count of failed health checks of each Web3Signer instance",
+            &["signer"]
+        );
+    pub static ref WEB3SIGNER_QUARANTINED: Result<IntGaugeVec> = try_create_int_gauge_vec(
+        "vc_web3signer_quarantined",
+        "Set to 1 if a Web3Signer instance is quarantined due to failed health checks",
+        &["signer"]
+    );
+
+    /*
+     * Signing lease
+     */
+    pub static ref SIGNING_LEASE_HELD: Result<IntGauge> = try_create_int_gauge(
+        "vc_signing_lease_held",
+        "Set to 1 if this instance holds the signing lease and may sign"
+    );
 
+    /*
+     * Fee recipient file
+     */
+    pub static ref FEE_RECIPIENT_FILE_CHANGED_ENTRIES: Result<IntCounter> = try_create_int_counter(
+        "vc_fee_recipient_file_changed_entries_total",
+        "Total count of fee recipient entries changed by reloading the fee recipient file"
+    );
+    pub static ref FEE_RECIPIENT_FILE_RELOAD_ERRORS: Result<IntCounter> = try_create_int_counter(
+        "vc_fee_recipient_file_reload_errors_total",
+        "Total count of failures to reload the fee recipient file"
+    );
     pub static ref ATTESTATION_DUTY: Result<IntGaugeVec> = try_create_int_gauge_vec(
         "vc_attestation_duty_slot",
         "Attestation duty slot for all managed validators",
         &["validator"]
     );
+    pub static ref PROPOSAL_READINESS_ISSUES: Result<IntGaugeVec> = try_create_int_gauge_vec(
+        "vc_proposal_readiness_issues",
+        "Number of block proposals in the next epoch affected by each readiness issue",
+        &["issue"]
+    );
+    pub static ref ATTESTATION_DUTIES_PER_SLOT: Result<IntGaugeVec> = try_create_int_gauge_vec(
+        "vc_attestation_duties_per_slot",
+        "Attestation duties in each slot of the epoch for validators without per-validator metrics",
+        &["epoch", "slot_index"]
+    );
     /*
      * BN latency
      */
@@ -8,14 +8,15 @@
 
 use crate::signing_method::SigningMethod;
 use account_utils::{
+    parallel_decryption::decrypt_in_parallel,
     read_password, read_password_from_user, read_password_string,
     validator_definitions::{
-        self, SigningDefinition, ValidatorDefinition, ValidatorDefinitions, Web3SignerDefinition,
-        CONFIG_FILENAME,
+        self, BlsToExecutionChangeRecord, ScheduledExit, SigningDefinition, ValidatorDefinition,
+        ValidatorDefinitions, Web3SignerDefinition, CONFIG_FILENAME,
     },
     ZeroizeString,
 };
-use eth2_keystore::Keystore;
+use eth2_keystore::{Keystore, PlainText, Uuid};
 use lighthouse_metrics::set_gauge;
 use lockfile::{Lockfile, LockfileError};
 use parking_lot::{MappedMutexGuard, Mutex, MutexGuard};
@@ -28,7 +29,9 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 use types::graffiti::GraffitiString;
-use types::{Address, Graffiti, Keypair, PublicKey, PublicKeyBytes};
+use types::{
+    Address, Epoch, Graffiti, Keypair, PublicKey, PublicKeyBytes, SignedBlsToExecutionChange,
+};
 use url::{ParseError, Url};
 use validator_dir::Builder as ValidatorDirBuilder;
 
@@ -36,6 +39,9 @@ use crate::key_cache;
 use crate::key_cache::KeyCache;
 use crate::Config;
 
+/// The maximum number of threads used to decrypt keystores when validators are initialized.
+const MAX_DECRYPTION_THREADS: usize = 16;
+
 /// Default timeout for a request to a remote signer for a signature.
 ///
 /// Set to 12 seconds since that's the duration of a slot. A remote signer that cannot sign within
@@ -110,6 +116,8 @@ pub enum Error {
     InvalidWeb3SignerClientIdentityCertificateFile(io::Error),
     InvalidWeb3SignerClientIdentityCertificate(ReqwestError),
     UnableToBuildWeb3SignerClient(ReqwestError),
+    /// A fallback keystore for the remote signer was supplied without a password path.
+    MissingWeb3SignerFallbackKeystorePassword,
     /// Unable to apply an action to a validator.
     InvalidActionOnValidator,
     UnableToReadValidatorPassword(String),
@@ -134,6 +142,10 @@ pub struct InitializedValidator {
     builder_proposals: Option<bool>,
     builder_boost_factor: Option<u64>,
     prefer_builder_proposals: Option<bool>,
+    /// The epoch from which this validator no longer signs blocks or attestations.
+    handover_epoch: Option<Epoch>,
+    /// Whether the validator is suspended, in which case it must not perform duties or sign.
+    suspended: bool,
     /// The validators index in `state.validators`, to be updated by an external service.
     index: Option<u64>,
 }
@@ -149,8 +161,8 @@ impl InitializedValidator {
                 option_lockfile.as_mut()
             })
             .ok(),
-            // Web3Signer validators do not have any lockfiles.
-            SigningMethod::Web3Signer { .. } => None,
+            // Web3Signer and watch-only validators do not have any lockfiles.
+            SigningMethod::Web3Signer { .. } | SigningMethod::WatchOnly { .. } => None,
         }
     }
 
@@ -188,6 +200,63 @@ fn open_keystore(path: &Path) -> Result<Keystore, Error> {
     Keystore::from_json_reader(keystore_file).map_err(Error::UnableToParseVotingKeystore)
 }
 
+/// Decrypts the fallback keystore of a remote signer validator and checks that it holds the key of
+/// the validator.
+///
+/// This is a blocking function.
+fn load_fallback_keypair(
+    keystore_path: &Path,
+    password_path: Option<&Path>,
+    voting_public_key: &PublicKey,
+) -> Result<Keypair, Error> {
+    let password_path = password_path.ok_or(Error::MissingWeb3SignerFallbackKeystorePassword)?;
+    let password =
+        read_password(password_path).map_err(Error::UnableToReadVotingKeystorePassword)?;
+    let keypair = open_keystore(keystore_path)?
+        .decrypt_keypair(password.as_bytes())
+        .map_err(Error::UnableToDecryptKeystore)?;
+
+    if keypair.pk != *voting_public_key {
+        return Err(Error::VotingPublicKeyMismatch {
+            definition: Box::new(voting_public_key.clone()),
+            keystore: Box::new(keypair.pk),
+        });
+    }
+    Ok(keypair)
+}
+
+/// A local keystore which is decrypted before its validator is initialized.
+struct PendingDecryption {
+    keystore: Keystore,
+    password: Option<ZeroizeString>,
+    password_path: Option<PathBuf>,
+}
+
+impl PendingDecryption {
+    /// Decrypts the keystore, preferring the `password` over the `password_path` as in
+    /// `InitializedValidator::from_definition`.
+    ///
+    /// This is a blocking function.
+    fn decrypt(&self) -> Result<(PlainText, Keypair), Error> {
+        let password = match (&self.password, &self.password_path) {
+            (Some(password), _) => password.as_ref().to_vec().into(),
+            (None, Some(path)) => {
+                read_password(path).map_err(Error::UnableToReadVotingKeystorePassword)?
+            }
+            (None, None) => {
+                return Err(Error::UnableToDecryptKeystore(
+                    eth2_keystore::Error::EmptyPassword,
+                ))
+            }
+        };
+        let keypair = self
+            .keystore
+            .decrypt_keypair(password.as_bytes())
+            .map_err(Error::UnableToDecryptKeystore)?;
+        Ok((password, keypair))
+    }
+}
+
 fn get_lockfile_path(file_path: &Path) -> Option<PathBuf> {
     file_path
         .file_name()
@@ -297,12 +366,41 @@ impl InitializedValidator {
             SigningDefinition::Web3Signer(web3_signer) => {
                 let signing_url = build_web3_signer_url(&web3_signer.url, &def.voting_public_key)
                     .map_err(|e| Error::InvalidWeb3SignerUrl(e.to_string()))?;
+                let upcheck_url = build_web3_signer_upcheck_url(&web3_signer.url)
+                    .map_err(|e| Error::InvalidWeb3SignerUrl(e.to_string()))?;
 
                 let request_timeout = web3_signer
                     .request_timeout_ms
                     .map(Duration::from_millis)
                     .unwrap_or(DEFAULT_REMOTE_SIGNER_REQUEST_TIMEOUT);
 
+                let fallback_keypair = match web3_signer.fallback_keystore_path.clone() {
+                    Some(keystore_path) => {
+                        let password_path = web3_signer.fallback_keystore_password_path.clone();
+                        let voting_public_key = def.voting_public_key.clone();
+                        // Decrypting a keystore is slow, so keep it off the core executor.
+                        let keypair = tokio::task::spawn_blocking(move || {
+                            load_fallback_keypair(
+                                &keystore_path,
+                                password_path.as_deref(),
+                                &voting_public_key,
+                            )
+                        })
+                        .await
+                        .map_err(Error::TokioJoin)??;
+                        Some(Arc::new(keypair))
+                    }
+                    None => None,
+                };
+
+                // The fallback keystore does not affect the client, so it is excluded from the key
+                // to share clients between validators using the same Web3Signer instance.
+                let web3_signer = Web3SignerDefinition {
+                    fallback_keystore_path: None,
+                    fallback_keystore_password_path: None,
+                    ..web3_signer
+                };
+
                 // Check if a client has already been initialized for this remote signer url.
                 let http_client = if let Some(client_map) = web3_signer_client_map {
                     match client_map.get(&web3_signer) {
@@ -339,10 +437,16 @@ impl InitializedValidator {
 
                 SigningMethod::Web3Signer {
                     signing_url,
+                    upcheck_url,
                     http_client,
                     voting_public_key: def.voting_public_key,
+                    signing_timeouts: config.web3_signer_signing_timeouts,
+                    fallback_keypair,
                 }
             }
+            SigningDefinition::WatchOnly => SigningMethod::WatchOnly {
+                voting_public_key: def.voting_public_key,
+            },
         };
 
         Ok(Self {
@@ -353,6 +457,8 @@ impl InitializedValidator {
             builder_proposals: def.builder_proposals,
             builder_boost_factor: def.builder_boost_factor,
             prefer_builder_proposals: def.prefer_builder_proposals,
+            handover_epoch: def.handover_epoch,
+            suspended: def.suspended,
             index: None,
         })
     }
@@ -363,7 +469,8 @@ impl InitializedValidator {
             SigningMethod::LocalKeystore { voting_keypair, .. } => &voting_keypair.pk,
             SigningMethod::Web3Signer {
                 voting_public_key, ..
-            } => voting_public_key,
+            }
+            | SigningMethod::WatchOnly { voting_public_key } => voting_public_key,
         }
     }
 }
@@ -394,6 +501,12 @@ fn build_web3_signer_url(base_url: &str, voting_public_key: &PublicKey) -> Resul
     Url::parse(base_url)?.join(&format!("api/v1/eth2/sign/{}", voting_public_key))
 }
 
+/// Resolves `upcheck` relative to `base_url`, like the signing URL, so that a Web3Signer served
+/// under a path prefix is checked under the same prefix.
+fn build_web3_signer_upcheck_url(base_url: &str) -> Result<Url, ParseError> {
+    Url::parse(base_url)?.join("upcheck")
+}
+
 fn build_web3_signer_client(
     root_certificate_path: Option<PathBuf>,
     client_identity_path: Option<PathBuf>,
@@ -810,6 +923,206 @@ impl InitializedValidators {
         Ok(())
     }
 
+    /// Returns the voluntary exit scheduled for the validator with `voting_public_key`, if any.
+    pub fn scheduled_exit(&self, voting_public_key: &PublicKey) -> Option<ScheduledExit> {
+        self.definitions
+            .as_slice()
+            .iter()
+            .find(|def| def.voting_public_key == *voting_public_key)
+            .and_then(|def| def.scheduled_exit)
+    }
+
+    /// Sets or removes the voluntary exit scheduled for the validator with `voting_public_key`.
+    ///
+    /// ## Notes
+    ///
+    /// Saves the `ValidatorDefinitions` to file, even if no definitions were changed.
+    pub fn set_scheduled_exit(
+        &mut self,
+        voting_public_key: &PublicKey,
+        scheduled_exit: Option<ScheduledExit>,
+    ) -> Result<(), Error> {
+        if let Some(def) = self
+            .definitions
+            .as_mut_slice()
+            .iter_mut()
+            .find(|def| def.voting_public_key == *voting_public_key)
+        {
+            def.scheduled_exit = scheduled_exit;
+        }
+
+        self.definitions
+            .save(&self.validators_dir)
+            .map_err(Error::UnableToSaveDefinitions)?;
+        Ok(())
+    }
+
+    /// Returns the public key and epoch of all enabled validators with a scheduled voluntary exit
+    /// which has not yet been submitted.
+    pub fn pending_scheduled_exits(&self) -> Vec<(PublicKey, Epoch)> {
+        self.definitions
+            .as_slice()
+            .iter()
+            .filter(|def| def.enabled)
+            .filter_map(|def| {
+                def.scheduled_exit
+                    .filter(|exit| !exit.submitted)
+                    .map(|exit| (def.voting_public_key.clone(), exit.epoch))
+            })
+            .collect()
+    }
+
+    /// Returns the change of withdrawal credentials recorded for the validator with
+    /// `voting_public_key`, if any.
+    pub fn bls_to_execution_change(
+        &self,
+        voting_public_key: &PublicKey,
+    ) -> Option<BlsToExecutionChangeRecord> {
+        self.definitions
+            .as_slice()
+            .iter()
+            .find(|def| def.voting_public_key == *voting_public_key)
+            .and_then(|def| def.bls_to_execution_change.clone())
+    }
+
+    /// Sets or removes the change of withdrawal credentials recorded for the validator with
+    /// `voting_public_key`.
+    ///
+    /// ## Notes
+    ///
+    /// Saves the `ValidatorDefinitions` to file, even if no definitions were changed.
+    pub fn set_bls_to_execution_change(
+        &mut self,
+        voting_public_key: &PublicKey,
+        bls_to_execution_change: Option<BlsToExecutionChangeRecord>,
+    ) -> Result<(), Error> {
+        if let Some(def) = self
+            .definitions
+            .as_mut_slice()
+            .iter_mut()
+            .find(|def| def.voting_public_key == *voting_public_key)
+        {
+            def.bls_to_execution_change = bls_to_execution_change;
+        }
+
+        self.definitions
+            .save(&self.validators_dir)
+            .map_err(Error::UnableToSaveDefinitions)?;
+        Ok(())
+    }
+
+    /// Returns the public key and signed change of all validators with a change of withdrawal
+    /// credentials which has not yet been submitted.
+    pub fn pending_bls_to_execution_changes(&self) -> Vec<(PublicKey, SignedBlsToExecutionChange)> {
+        self.definitions
+            .as_slice()
+            .iter()
+            .filter_map(|def| {
+                def.bls_to_execution_change
+                    .as_ref()
+                    .filter(|change| !change.submitted)
+                    .map(|change| (def.voting_public_key.clone(), change.signed_change.clone()))
+            })
+            .collect()
+    }
+
+    /// Returns the epoch from which the validator with `public_key` has been handed over to
+    /// another validator client, if any.
+    pub fn handover_epoch(&self, public_key: &PublicKeyBytes) -> Option<Epoch> {
+        self.validators
+            .get(public_key)
+            .and_then(|v| v.handover_epoch)
+    }
+
+    /// Returns the public key and handover epoch of all initialized validators with a handover.
+    pub fn handover_epochs(&self) -> Vec<(PublicKeyBytes, Epoch)> {
+        self.validators
+            .iter()
+            .filter_map(|(pubkey, v)| v.handover_epoch.map(|epoch| (*pubkey, epoch)))
+            .collect()
+    }
+
+    /// Sets or removes the `InitializedValidator` and `ValidatorDefinition` `handover_epoch`
+    /// values.
+    ///
+    /// ## Notes
+    ///
+    /// Saves the `ValidatorDefinitions` to file, even if no definitions were changed.
+    pub fn set_handover_epoch(
+        &mut self,
+        voting_public_key: &PublicKey,
+        handover_epoch: Option<Epoch>,
+    ) -> Result<(), Error> {
+        if let Some(def) = self
+            .definitions
+            .as_mut_slice()
+            .iter_mut()
+            .find(|def| def.voting_public_key == *voting_public_key)
+        {
+            def.handover_epoch = handover_epoch;
+        }
+
+        if let Some(val) = self
+            .validators
+            .get_mut(&PublicKeyBytes::from(voting_public_key))
+        {
+            val.handover_epoch = handover_epoch;
+        }
+
+        self.definitions
+            .save(&self.validators_dir)
+            .map_err(Error::UnableToSaveDefinitions)?;
+
+        Ok(())
+    }
+
+    /// Returns `true` if the validator with `public_key` is suspended.
+    pub fn is_suspended(&self, public_key: &PublicKeyBytes) -> bool {
+        self.validators
+            .get(public_key)
+            .map_or(false, |v| v.suspended)
+    }
+
+    /// Returns `true` if the validator with `public_key` is watch-only, and so must never sign.
+    pub fn is_watch_only(&self, public_key: &PublicKeyBytes) -> bool {
+        self.validators.get(public_key).map_or(false, |v| {
+            matches!(v.signing_method.as_ref(), SigningMethod::WatchOnly { .. })
+        })
+    }
+
+    /// Sets the `InitializedValidator` and `ValidatorDefinition` `suspended` values.
+    ///
+    /// ## Notes
+    ///
+    /// Saves the `ValidatorDefinitions` to file, even if no definitions were changed.
+    pub fn set_suspended(
+        &mut self,
+        voting_public_key: &PublicKey,
+        suspended: bool,
+    ) -> Result<(), Error> {
+        if let Some(def) = self
+            .definitions
+            .as_mut_slice()
+            .iter_mut()
+            .find(|def| def.voting_public_key == *voting_public_key)
+        {
+            def.suspended = suspended;
+        }
+
+        if let Some(val) = self
+            .validators
+            .get_mut(&PublicKeyBytes::from(voting_public_key))
+        {
+            val.suspended = suspended;
+        }
+
+        self.definitions
+            .save(&self.validators_dir)
+            .map_err(Error::UnableToSaveDefinitions)?;
+
+        Ok(())
+    }
+
     /// Returns a `HashMap` of `public_key` -> `graffiti` for all initialized validators.
     pub fn get_all_validators_graffiti(&self) -> HashMap<&PublicKeyBytes, Option<Graffiti>> {
         let mut result = HashMap::new();
@@ -1090,6 +1403,78 @@ impl InitializedValidators {
         Ok(())
     }
 
+    /// Sets the `InitializedValidator` and `ValidatorDefinition` `builder_proposals` and
+    /// `builder_boost_factor` values. Values which are `None` are left unchanged.
+    ///
+    /// ## Notes
+    ///
+    /// Saves the `ValidatorDefinitions` to file, even if no definitions were changed.
+    pub fn set_validator_builder_settings(
+        &mut self,
+        voting_public_key: &PublicKey,
+        builder_proposals: Option<bool>,
+        builder_boost_factor: Option<u64>,
+    ) -> Result<(), Error> {
+        if let Some(def) = self
+            .definitions
+            .as_mut_slice()
+            .iter_mut()
+            .find(|def| def.voting_public_key == *voting_public_key)
+        {
+            def.builder_proposals = builder_proposals.or(def.builder_proposals);
+            def.builder_boost_factor = builder_boost_factor.or(def.builder_boost_factor);
+        }
+
+        if let Some(val) = self
+            .validators
+            .get_mut(&PublicKeyBytes::from(voting_public_key))
+        {
+            val.builder_proposals = builder_proposals.or(val.builder_proposals);
+            val.builder_boost_factor = builder_boost_factor.or(val.builder_boost_factor);
+        }
+
+        self.definitions
+            .save(&self.validators_dir)
+            .map_err(Error::UnableToSaveDefinitions)?;
+
+        Ok(())
+    }
+
+    /// Removes the `InitializedValidator` and `ValidatorDefinition` `builder_proposals` and
+    /// `builder_boost_factor` values, so that the process level defaults apply.
+    ///
+    /// ## Notes
+    ///
+    /// Saves the `ValidatorDefinitions` to file, even if no definitions were changed.
+    pub fn delete_validator_builder_settings(
+        &mut self,
+        voting_public_key: &PublicKey,
+    ) -> Result<(), Error> {
+        if let Some(def) = self
+            .definitions
+            .as_mut_slice()
+            .iter_mut()
+            .find(|def| def.voting_public_key == *voting_public_key)
+        {
+            def.builder_proposals = None;
+            def.builder_boost_factor = None;
+        }
+
+        if let Some(val) = self
+            .validators
+            .get_mut(&PublicKeyBytes::from(voting_public_key))
+        {
+            val.builder_proposals = None;
+            val.builder_boost_factor = None;
+        }
+
+        self.definitions
+            .save(&self.validators_dir)
+            .map_err(Error::UnableToSaveDefinitions)?;
+
+        Ok(())
+    }
+
     /// Tries to decrypt the key cache.
     ///
     /// Returns the decrypted cache if decryption was successful, or an error if a required password
@@ -1123,8 +1508,8 @@ impl InitializedValidators {
                     };
                     definitions_map.insert(*key_store.uuid(), def);
                 }
-                // Remote signer validators don't interact with the key cache.
-                SigningDefinition::Web3Signer { .. } => (),
+                // Remote signer and watch-only validators don't interact with the key cache.
+                SigningDefinition::Web3Signer { .. } | SigningDefinition::WatchOnly => (),
             }
         }
 
@@ -1167,8 +1552,8 @@ impl InitializedValidators {
                     passwords.push(pw);
                     public_keys.push(def.voting_public_key.clone());
                 }
-                // Remote signer validators don't interact with the key cache.
-                SigningDefinition::Web3Signer { .. } => (),
+                // Remote signer and watch-only validators don't interact with the key cache.
+                SigningDefinition::Web3Signer { .. } | SigningDefinition::WatchOnly => (),
             };
         }
 
@@ -1183,6 +1568,98 @@ impl InitializedValidators {
         .map_err(Error::UnableToDecryptKeyCache)
     }
 
+    /// Decrypts the keystores of the local validators which are about to be initialized in
+    /// parallel, adding their keypairs to the `key_cache` so that they are not decrypted one at a
+    /// time during initialization.
+    ///
+    /// Keystores which are unable to be opened or decrypted are skipped, leaving the error to be
+    /// reported when their validators are initialized. Keystores without a password are skipped
+    /// too, since their passwords are read from stdin.
+    async fn decrypt_keystores_in_parallel(
+        &self,
+        key_cache: &mut KeyCache,
+        key_stores: &mut HashMap<PathBuf, Keystore>,
+    ) -> Result<(), Error> {
+        let mut pending_uuids = HashSet::new();
+        let mut pending = vec![];
+        for def in self.definitions.as_slice() {
+            let SigningDefinition::LocalKeystore {
+                voting_keystore_path,
+                voting_keystore_password_path,
+                voting_keystore_password,
+            } = &def.signing_definition
+            else {
+                continue;
+            };
+            if !def.enabled
+                || self
+                    .validators
+                    .contains_key(&def.voting_public_key.compress())
+                || (voting_keystore_password.is_none() && voting_keystore_password_path.is_none())
+            {
+                continue;
+            }
+
+            use std::collections::hash_map::Entry::*;
+            let keystore = match key_stores.entry(voting_keystore_path.clone()) {
+                Vacant(entry) => match open_keystore(voting_keystore_path) {
+                    Ok(keystore) => entry.insert(keystore),
+                    Err(_) => continue,
+                },
+                Occupied(entry) => entry.into_mut(),
+            };
+            if key_cache.get(keystore.uuid()).is_some() || !pending_uuids.insert(*keystore.uuid()) {
+                continue;
+            }
+
+            pending.push(PendingDecryption {
+                keystore: keystore.clone(),
+                password: voting_keystore_password.clone(),
+                password_path: voting_keystore_password_path.clone(),
+            });
+        }
+
+        if pending.is_empty() {
+            return Ok(());
+        }
+
+        info!(
+            self.log,
+            "Decrypting validator keystores";
+            "count" => pending.len(),
+        );
+        let results = tokio::task::spawn_blocking(move || {
+            let mut results = Vec::with_capacity(pending.len());
+            decrypt_in_parallel(
+                &pending,
+                MAX_DECRYPTION_THREADS,
+                PendingDecryption::decrypt,
+                |_, pending, result| results.push((*pending.keystore.uuid(), result)),
+            );
+            results
+        })
+        .await
+        .map_err(Error::TokioJoin)?;
+        for (uuid, result) in results {
+            match result {
+                Some(Ok((password, keypair))) => key_cache.add(keypair, &uuid, password),
+                Some(Err(e)) => debug!(
+                    self.log,
+                    "Failed to decrypt keystore in parallel";
+                    "error" => ?e,
+                    "keystore_uuid" => %uuid,
+                ),
+                // The keystore is decrypted again when its validator is initialized.
+                None => debug!(
+                    self.log,
+                    "Panicked while decrypting keystore in parallel";
+                    "keystore_uuid" => %uuid,
+                ),
+            }
+        }
+        Ok(())
+    }
+
     /// Scans `self.definitions` and attempts to initialize and validators which are not already
     /// initialized.
     ///
@@ -1225,6 +1702,11 @@ impl InitializedValidators {
             KeyCache::new()
         };
 
+        if has_local_definitions {
+            self.decrypt_keystores_in_parallel(&mut key_cache, &mut key_stores)
+                .await?;
+        }
+
         let mut disabled_uuids = HashSet::new();
         for def in self.definitions.as_slice() {
             if def.enabled {
@@ -1328,6 +1810,25 @@ impl InitializedValidators {
                             }
                         }
                     }
+                    SigningDefinition::WatchOnly => {
+                        let init = InitializedValidator::from_definition(
+                            def.clone(),
+                            &mut key_cache,
+                            &mut key_stores,
+                            &mut None,
+                            &self.config,
+                        )
+                        .await?;
+                        self.validators
+                            .insert(init.voting_public_key().compress(), init);
+
+                        info!(
+                            self.log,
+                            "Enabled validator";
+                            "signing_method" => "watch_only",
+                            "voting_pubkey" => format!("{:?}", def.voting_public_key),
+                        );
+                    }
                 }
             } else {
                 self.validators.remove(&def.voting_public_key.compress());
@@ -1340,8 +1841,9 @@ impl InitializedValidators {
                             disabled_uuids.insert(*key_store.uuid());
                         }
                     }
-                    // Remote signers do not interact with the key cache.
-                    SigningDefinition::Web3Signer { .. } => (),
+                    // Remote signers and watch-only validators do not interact with the key
+                    // cache.
+                    SigningDefinition::Web3Signer { .. } | SigningDefinition::WatchOnly => (),
                 }
 
                 info!(
@@ -1419,8 +1921,8 @@ impl InitializedValidators {
                         passwords.insert(def.voting_public_key.clone(), password);
                     }
                 }
-                // Remote signers don't have passwords.
-                SigningDefinition::Web3Signer { .. } => (),
+                // Remote signers and watch-only validators don't have passwords.
+                SigningDefinition::Web3Signer { .. } | SigningDefinition::WatchOnly => (),
             };
         }
 
@@ -1,16 +1,25 @@
 mod attestation_service;
 mod beacon_node_fallback;
 mod block_service;
+mod bls_to_execution_change_service;
 mod check_synced;
 mod cli;
 mod duties_service;
+mod fee_recipient_file;
 mod graffiti_file;
 mod http_metrics;
 mod key_cache;
 mod latency;
 mod notifier;
+mod performance_service;
 mod preparation_service;
+mod proposal_readiness_service;
+mod runtime_snapshot;
+mod scheduled_exit_service;
+mod signer_health_service;
+mod signing_lease;
 mod signing_method;
+mod slashing_protection_export;
 mod sync_committee_service;
 
 pub mod config;
@@ -19,13 +28,15 @@ pub mod http_api;
 pub mod initialized_validators;
 pub mod validator_store;
 
-pub use beacon_node_fallback::ApiTopic;
+pub use beacon_node_fallback::{ApiTopic, BeaconNodeRoutes, BeaconNodeSelection, RequestClass};
 pub use cli::cli_app;
 pub use config::Config;
+pub use duties_service::ValidatorMetricsLabels;
 use initialized_validators::InitializedValidators;
 use lighthouse_metrics::set_gauge;
 use monitoring_api::{MonitoringHttpClient, ProcessType};
 use sensitive_url::SensitiveUrl;
+pub use signing_method::SigningTimeouts;
 pub use slashing_protection::{SlashingDatabase, SLASHING_PROTECTION_FILENAME};
 
 use crate::beacon_node_fallback::{
@@ -35,6 +46,7 @@ use crate::beacon_node_fallback::{
 use crate::doppelganger_service::DoppelgangerService;
 use crate::graffiti_file::GraffitiFile;
 use crate::initialized_validators::Error::UnableToOpenVotingKeystore;
+use account_utils::network_binding::{Error as NetworkBindingError, NetworkBinding};
 use account_utils::validator_definitions::ValidatorDefinitions;
 use attestation_service::{AttestationService, AttestationServiceBuilder};
 use block_service::{BlockService, BlockServiceBuilder};
@@ -45,12 +57,15 @@ use eth2::{reqwest::ClientBuilder, types::Graffiti, BeaconNodeHttpClient, Status
 use http_api::ApiSecret;
 use notifier::spawn_notifier;
 use parking_lot::RwLock;
+use performance_service::PerformanceTracker;
 use preparation_service::{PreparationService, PreparationServiceBuilder};
-use reqwest::Certificate;
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
+use reqwest::{Certificate, Identity};
+use runtime_snapshot::RUNTIME_SNAPSHOT_FILENAME;
 use slog::{debug, error, info, warn, Logger};
 use slot_clock::SlotClock;
 use slot_clock::SystemTimeSlotClock;
-use std::fs::File;
+use std::fs::{self, File};
 use std::io::Read;
 use std::marker::PhantomData;
 use std::net::SocketAddr;
@@ -103,6 +118,7 @@ pub struct ProductionValidatorClient<E: EthSpec> {
     config: Config,
     beacon_nodes: Arc<BeaconNodeFallback<SystemTimeSlotClock, E>>,
     genesis_time: u64,
+    genesis_validators_root: Hash256,
 }
 
 impl<E: EthSpec> ProductionValidatorClient<E> {
@@ -309,6 +325,20 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
                 }
             }
 
+            // Present a client certificate to beacon nodes which require one.
+            if let Some((cert, key)) = &config.beacon_nodes_tls_client_identity {
+                beacon_node_http_client_builder =
+                    beacon_node_http_client_builder.identity(load_pem_identity(cert, key)?);
+            }
+
+            // Present a bearer token to beacon nodes which require one.
+            if let Some(token_path) = &config.beacon_nodes_api_token_file {
+                let mut headers = HeaderMap::new();
+                headers.insert(AUTHORIZATION, load_api_token_header(token_path)?);
+                beacon_node_http_client_builder =
+                    beacon_node_http_client_builder.default_headers(headers);
+            }
+
             let beacon_node_http_client = beacon_node_http_client_builder
                 // Set default timeout to be the full slot duration.
                 .timeout(slot_duration)
@@ -395,6 +425,8 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
         let mut beacon_nodes: BeaconNodeFallback<_, E> = BeaconNodeFallback::new(
             candidates,
             config.broadcast_topics.clone(),
+            config.beacon_node_selection,
+            config.beacon_node_routes.clone(),
             context.eth2_config.spec.clone(),
             log.clone(),
         );
@@ -402,6 +434,9 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
         let mut proposer_nodes: BeaconNodeFallback<_, E> = BeaconNodeFallback::new(
             proposer_candidates,
             config.broadcast_topics.clone(),
+            config.beacon_node_selection,
+            // Routes refer to the indices of `--beacon-nodes`, so they don't apply to proposer nodes.
+            BeaconNodeRoutes::default(),
             context.eth2_config.spec.clone(),
             log.clone(),
         );
@@ -412,6 +447,27 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             () = context.executor.exit() => return Err("Shutting down".to_string())
         };
 
+        // Refuse to reuse keys and slashing protection history from another network.
+        match NetworkBinding::check_or_bind(&config.validator_dir, genesis_validators_root) {
+            Ok(true) => info!(
+                log,
+                "Bound validator directory to network";
+                "genesis_validators_root" => ?genesis_validators_root,
+            ),
+            Ok(false) => (),
+            Err(NetworkBindingError::NetworkMismatch { bound, actual }) => {
+                return Err(format!(
+                    "The validator directory {:?} belongs to the network with genesis validators \
+                     root {:?}, but the beacon node is on the network with genesis validators \
+                     root {:?}. Refusing to reuse its keys and slashing protection history. To \
+                     deliberately move the directory to this network, run `lighthouse account \
+                     validator rebind-network`",
+                    config.validator_dir, bound, actual
+                ))
+            }
+            Err(e) => return Err(format!("Unable to check network binding: {:?}", e)),
+        }
+
         // Update the metrics server.
         if let Some(ctx) = &http_metrics_ctx {
             ctx.shared.write().genesis_time = Some(genesis_time);
@@ -425,6 +481,8 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
 
         beacon_nodes.set_slot_clock(slot_clock.clone());
         proposer_nodes.set_slot_clock(slot_clock.clone());
+        beacon_nodes.set_genesis_validators_root(genesis_validators_root);
+        proposer_nodes.set_genesis_validators_root(genesis_validators_root);
 
         let beacon_nodes = Arc::new(beacon_nodes);
         start_fallback_updater_service(context.clone(), beacon_nodes.clone())?;
@@ -476,14 +534,25 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             attesters: <_>::default(),
             proposers: <_>::default(),
             sync_duties: SyncDutiesMap::new(config.distributed),
+            selection_proof_queue: <_>::default(),
             slot_clock: slot_clock.clone(),
             beacon_nodes: beacon_nodes.clone(),
             validator_store: validator_store.clone(),
             unknown_validator_next_poll_slots: <_>::default(),
             spec: context.eth2_config.spec.clone(),
             context: duties_context,
-            enable_high_validator_count_metrics: config.enable_high_validator_count_metrics,
+            validator_metrics_labels: config.validator_metrics_labels.or(config
+                .enable_high_validator_count_metrics
+                .then_some(ValidatorMetricsLabels::All)),
+            metrics_monitored_validators: config
+                .validator_metrics_pubkeys
+                .iter()
+                .copied()
+                .collect(),
             distributed: config.distributed,
+            distributed_duty_delay: config.distributed_duty_delay.unwrap_or_default(),
+            proposer_only: config.proposer_only,
+            proposer_prefetch_slots: config.proposer_prefetch_slots,
         });
 
         // Update the metrics server.
@@ -513,6 +582,7 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             .validator_store(validator_store.clone())
             .beacon_nodes(beacon_nodes.clone())
             .runtime_context(context.service_context("attestation".into()))
+            .early_attestations(config.early_attestation_min_delay)
             .build()?;
 
         let preparation_service = PreparationServiceBuilder::new()
@@ -530,8 +600,20 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             slot_clock.clone(),
             beacon_nodes.clone(),
             context.service_context("sync_committee".into()),
+            config.sync_committee_jitter,
         );
 
+        if config.enable_runtime_snapshot {
+            runtime_snapshot::restore_runtime_snapshot(
+                &config.validator_dir.join(RUNTIME_SNAPSHOT_FILENAME),
+                genesis_validators_root,
+                &duties_service,
+                &preparation_service,
+                doppelganger_service.as_deref(),
+                &log,
+            );
+        }
+
         Ok(Self {
             context,
             duties_service,
@@ -545,6 +627,7 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             slot_clock,
             http_api_listen_addr: None,
             genesis_time,
+            genesis_validators_root,
             beacon_nodes,
         })
     }
@@ -559,6 +642,11 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
 
         let api_secret = ApiSecret::create_or_open(&self.config.validator_dir)?;
 
+        let performance_tracker = self
+            .config
+            .performance_tracking_epochs
+            .map(|epochs| Arc::new(PerformanceTracker::new(epochs)));
+
         self.http_api_listen_addr = if self.config.http_api.enabled {
             let ctx = Arc::new(http_api::Context {
                 task_executor: self.context.executor.clone(),
@@ -571,6 +659,8 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
                 spec: self.context.eth2_config.spec.clone(),
                 config: self.config.http_api.clone(),
                 sse_logging_components: self.context.sse_logging_components.clone(),
+                log_filter: self.context.log_filter.clone(),
+                performance_tracker: performance_tracker.clone(),
                 slot_clock: self.slot_clock.clone(),
                 log: log.clone(),
                 _phantom: PhantomData,
@@ -602,15 +692,23 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             .start_update_service(block_service_rx)
             .map_err(|e| format!("Unable to start block service: {}", e))?;
 
-        self.attestation_service
-            .clone()
-            .start_update_service(&self.context.eth2_config.spec)
-            .map_err(|e| format!("Unable to start attestation service: {}", e))?;
+        if self.config.proposer_only {
+            info!(
+                log,
+                "Proposer-only mode enabled";
+                "info" => "attestation and sync committee duties will not be performed"
+            );
+        } else {
+            self.attestation_service
+                .clone()
+                .start_update_service(&self.context.eth2_config.spec)
+                .map_err(|e| format!("Unable to start attestation service: {}", e))?;
 
-        self.sync_committee_service
-            .clone()
-            .start_update_service(&self.context.eth2_config.spec)
-            .map_err(|e| format!("Unable to start sync committee service: {}", e))?;
+            self.sync_committee_service
+                .clone()
+                .start_update_service(&self.context.eth2_config.spec)
+                .map_err(|e| format!("Unable to start sync committee service: {}", e))?;
+        }
 
         self.preparation_service
             .clone()
@@ -641,6 +739,82 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             );
         }
 
+        if self.config.fee_recipient_file.is_some() {
+            fee_recipient_file::start_fee_recipient_file_service(
+                self.context.service_context("fee_recipient_file".into()),
+                self.duties_service.slot_clock.clone(),
+                self.validator_store.clone(),
+            );
+        }
+
+        if let Some(interval) = self.config.web3_signer_health_check_interval {
+            signer_health_service::start_signer_health_service(
+                self.context.service_context("signer_health".into()),
+                self.validator_store.clone(),
+                interval,
+            );
+        }
+
+        if let Some(path) = &self.config.signing_lease_file {
+            signing_lease::start_signing_lease_service(
+                self.context.service_context("signing_lease".into()),
+                self.validator_store.clone(),
+                path.clone(),
+                self.config.signing_lease_timeout,
+                self.config.signing_lease_clock_skew,
+            );
+        }
+
+        if let Some(path) = &self.config.slashing_protection_export_path {
+            slashing_protection_export::start_slashing_protection_export_service(
+                self.context
+                    .service_context("slashing_protection_export".into()),
+                self.validator_store.clone(),
+                path.clone(),
+                self.config.slashing_protection_export_interval,
+            );
+        }
+
+        if let Some(tracker) = performance_tracker {
+            performance_service::start_performance_service(
+                self.context.service_context("performance".into()),
+                self.duties_service.clone(),
+                tracker,
+            );
+        }
+
+        if self.config.enable_runtime_snapshot {
+            runtime_snapshot::start_runtime_snapshot_service(
+                self.context.service_context("runtime_snapshot".into()),
+                self.config.validator_dir.join(RUNTIME_SNAPSHOT_FILENAME),
+                self.genesis_validators_root,
+                self.duties_service.clone(),
+                self.preparation_service.clone(),
+                self.doppelganger_service.clone(),
+            );
+        }
+
+        proposal_readiness_service::start_proposal_readiness_service(
+            self.context.service_context("proposal_readiness".into()),
+            self.duties_service.clone(),
+            self.preparation_service.clone(),
+            self.duties_service.beacon_nodes.clone(),
+        );
+
+        scheduled_exit_service::start_scheduled_exit_service(
+            self.context.clone(),
+            self.duties_service.slot_clock.clone(),
+            self.validator_store.clone(),
+            self.duties_service.beacon_nodes.clone(),
+        );
+
+        bls_to_execution_change_service::start_bls_to_execution_change_service(
+            self.context.clone(),
+            self.duties_service.slot_clock.clone(),
+            self.validator_store.clone(),
+            self.duties_service.beacon_nodes.clone(),
+        );
+
         Ok(())
     }
 }
@@ -849,6 +1023,24 @@ pub fn load_pem_certificate<P: AsRef<Path>>(pem_path: P) -> Result<Certificate,
     Certificate::from_pem(&buf).map_err(|e| format!("Unable to parse certificate: {}", e))
 }
 
+pub fn load_pem_identity<P: AsRef<Path>>(cert_path: P, key_path: P) -> Result<Identity, String> {
+    let cert = fs::read(&cert_path)
+        .map_err(|e| format!("Unable to read client certificate file: {}", e))?;
+    let key = fs::read(&key_path).map_err(|e| format!("Unable to read client key file: {}", e))?;
+    Identity::from_pkcs8_pem(&cert, &key)
+        .map_err(|e| format!("Unable to parse client certificate or key: {}", e))
+}
+
+/// Reads a bearer token from `token_path`, returning it as the value of an `Authorization` header.
+pub fn load_api_token_header<P: AsRef<Path>>(token_path: P) -> Result<HeaderValue, String> {
+    let token = fs::read_to_string(&token_path)
+        .map_err(|e| format!("Unable to read API token file: {}", e))?;
+    let mut header = HeaderValue::from_str(&format!("Bearer {}", token.trim()))
+        .map_err(|e| format!("Invalid API token: {}", e))?;
+    header.set_sensitive(true);
+    Ok(header)
+}
+
 // Given the various graffiti control methods, determine the graffiti that will be used for
 // the next block produced by the validator with the given public key.
 pub fn determine_graffiti(
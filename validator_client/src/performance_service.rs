@@ -0,0 +1,515 @@
+//! Tracks the performance of the validators managed by this validator client.
+//!
+//! At the start of each epoch the service looks at the epoch before the previous one, which can
+//! no longer receive attestations, and fetches from the beacon node:
+//!
+//! - The attestation rewards of each validator, which tell whether its head, target and source
+//!   votes were correct and timely.
+//! - The blocks of that epoch and the next, to find the first inclusion of each attestation and
+//!   whether each proposal made it on chain.
+//! - The consensus rewards of each proposed block.
+//!
+//! The results of the last `history_epochs` epochs are kept in memory and served by the
+//! `lighthouse/ui/performance` endpoint.
+
+use crate::beacon_node_fallback::{BeaconNodeFallback, OfflineOnFailure, RequireSynced};
+use crate::duties_service::DutiesService;
+use crate::validator_store::DoppelgangerStatus;
+use environment::RuntimeContext;
+use eth2::lighthouse_vc::types::{PerformanceData, ValidatorPerformanceData};
+use eth2::types::{AttesterData, BlockId, StateId, ValidatorId};
+use parking_lot::RwLock;
+use slog::{debug, warn};
+use slot_clock::SlotClock;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use tokio::time::sleep;
+use types::{AttestationRef, Epoch, EthSpec, PublicKeyBytes, Slot};
+
+/// The performance of a single validator in a single epoch.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EpochPerformance {
+    pub validator_index: u64,
+    /// Set if the validator was expected to attest in the epoch.
+    pub attestation: Option<AttestationPerformance>,
+    pub blocks_proposed: u64,
+    pub blocks_missed: u64,
+    pub proposal_rewards: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AttestationPerformance {
+    pub head: bool,
+    pub target: bool,
+    pub source: bool,
+    /// The number of slots between the attestation's slot and its first inclusion, if it was
+    /// included within the following epoch.
+    pub inclusion_distance: Option<u64>,
+    /// Net rewards in gwei, after penalties.
+    pub rewards: i64,
+}
+
+impl AttestationPerformance {
+    fn included(&self) -> bool {
+        self.head || self.target || self.source || self.inclusion_distance.is_some()
+    }
+}
+
+/// The tracked performance of the last `history_epochs` epochs.
+pub struct PerformanceTracker {
+    history_epochs: u64,
+    epochs: RwLock<BTreeMap<Epoch, HashMap<PublicKeyBytes, EpochPerformance>>>,
+}
+
+impl PerformanceTracker {
+    pub fn new(history_epochs: u64) -> Self {
+        Self {
+            history_epochs,
+            epochs: <_>::default(),
+        }
+    }
+
+    /// Returns the most recent epoch which has been tracked.
+    pub fn latest_epoch(&self) -> Option<Epoch> {
+        self.epochs.read().keys().next_back().copied()
+    }
+
+    /// Records the performance of `epoch`, dropping epochs which are no longer in the history.
+    pub fn insert(&self, epoch: Epoch, performance: HashMap<PublicKeyBytes, EpochPerformance>) {
+        let mut epochs = self.epochs.write();
+        epochs.insert(epoch, performance);
+        while epochs.len() as u64 > self.history_epochs {
+            epochs.pop_first();
+        }
+    }
+
+    /// Aggregates the performance of each validator over the most recent `epochs` tracked epochs,
+    /// or all tracked epochs if `epochs` is `None`.
+    pub fn summary(&self, epochs: Option<u64>) -> PerformanceData {
+        let tracked = self.epochs.read();
+        let count = epochs.map_or(tracked.len(), |epochs| epochs as usize);
+        let selected = tracked.iter().rev().take(count).collect::<Vec<_>>();
+
+        let mut validators = HashMap::<PublicKeyBytes, (ValidatorPerformanceData, u64, u64)>::new();
+        for (_, performance) in &selected {
+            for (pubkey, epoch_performance) in performance.iter() {
+                let (data, distance_sum, distance_count) =
+                    validators.entry(*pubkey).or_insert_with(|| {
+                        (
+                            ValidatorPerformanceData {
+                                pubkey: *pubkey,
+                                validator_index: epoch_performance.validator_index,
+                                ..<_>::default()
+                            },
+                            0,
+                            0,
+                        )
+                    });
+
+                if let Some(attestation) = epoch_performance.attestation {
+                    data.attestations_expected += 1;
+                    data.attestations_included += u64::from(attestation.included());
+                    data.head_hits += u64::from(attestation.head);
+                    data.target_hits += u64::from(attestation.target);
+                    data.source_hits += u64::from(attestation.source);
+                    data.attestation_rewards += attestation.rewards;
+                    if let Some(distance) = attestation.inclusion_distance {
+                        *distance_sum += distance;
+                        *distance_count += 1;
+                    }
+                }
+                data.blocks_proposed += epoch_performance.blocks_proposed;
+                data.blocks_missed += epoch_performance.blocks_missed;
+                data.proposal_rewards += epoch_performance.proposal_rewards;
+            }
+        }
+
+        let mut validators = validators
+            .into_values()
+            .map(|(mut data, distance_sum, distance_count)| {
+                if distance_count > 0 {
+                    data.average_inclusion_distance =
+                        Some(distance_sum as f64 / distance_count as f64);
+                }
+                data
+            })
+            .collect::<Vec<_>>();
+        validators.sort_by_key(|data| data.validator_index);
+
+        PerformanceData {
+            start_epoch: selected.last().map(|(epoch, _)| **epoch),
+            end_epoch: selected.first().map(|(epoch, _)| **epoch),
+            validators,
+        }
+    }
+}
+
+/// Starts a service which records the performance of each epoch in `tracker`.
+pub fn start_performance_service<T: SlotClock + 'static, E: EthSpec>(
+    context: RuntimeContext<E>,
+    duties_service: Arc<DutiesService<T, E>>,
+    tracker: Arc<PerformanceTracker>,
+) {
+    let log = context.log().clone();
+    let slot_clock = duties_service.slot_clock.clone();
+
+    let future = async move {
+        loop {
+            // Wait until the first slot of the next epoch has passed, giving the beacon node
+            // time to import its block.
+            let sleep_time = slot_clock
+                .duration_to_next_epoch(E::slots_per_epoch())
+                .map(|duration| duration + slot_clock.slot_duration())
+                .unwrap_or_else(|| slot_clock.slot_duration());
+            sleep(sleep_time).await;
+
+            let Some(current_epoch) = slot_clock
+                .now()
+                .map(|slot| slot.epoch(E::slots_per_epoch()))
+            else {
+                continue;
+            };
+            // Attestations of an epoch can be included until the end of the following epoch.
+            let Some(epoch) = current_epoch.as_u64().checked_sub(2).map(Epoch::new) else {
+                continue;
+            };
+            if tracker.latest_epoch() >= Some(epoch) {
+                continue;
+            }
+
+            match epoch_performance(&duties_service, epoch).await {
+                Ok(performance) => {
+                    debug!(
+                        log,
+                        "Recorded validator performance";
+                        "epoch" => epoch,
+                        "validators" => performance.len(),
+                    );
+                    tracker.insert(epoch, performance);
+                }
+                Err(e) => warn!(
+                    log,
+                    "Unable to record validator performance";
+                    "epoch" => epoch,
+                    "error" => e,
+                ),
+            }
+        }
+    };
+
+    context.executor.spawn(future, "performance_service");
+}
+
+/// Computes the performance of all local validators with a known index in `epoch`.
+///
+/// Relies on the duties of `epoch` still being held by the `duties_service`.
+async fn epoch_performance<T: SlotClock + 'static, E: EthSpec>(
+    duties_service: &DutiesService<T, E>,
+    epoch: Epoch,
+) -> Result<HashMap<PublicKeyBytes, EpochPerformance>, String> {
+    let beacon_nodes = &duties_service.beacon_nodes;
+    let validator_store = &duties_service.validator_store;
+
+    let mut performance = validator_store
+        .voting_pubkeys::<Vec<_>, _>(DoppelgangerStatus::ignored)
+        .into_iter()
+        .filter_map(|pubkey| {
+            let validator_index = validator_store.validator_index(&pubkey)?;
+            Some((
+                pubkey,
+                EpochPerformance {
+                    validator_index,
+                    ..<_>::default()
+                },
+            ))
+        })
+        .collect::<HashMap<_, _>>();
+    if performance.is_empty() {
+        return Ok(performance);
+    }
+    let pubkeys_by_index = performance
+        .iter()
+        .map(|(pubkey, performance)| (performance.validator_index, *pubkey))
+        .collect::<HashMap<_, _>>();
+
+    // Attestation rewards are only returned for validators which were expected to attest.
+    let validator_ids = pubkeys_by_index
+        .keys()
+        .map(|index| ValidatorId::Index(*index))
+        .collect::<Vec<_>>();
+    let validator_ids = &validator_ids;
+    let rewards = beacon_nodes
+        .first_success(
+            RequireSynced::Yes,
+            OfflineOnFailure::Yes,
+            |beacon_node| async move {
+                beacon_node
+                    .post_beacon_rewards_attestations(epoch, validator_ids)
+                    .await
+            },
+        )
+        .await
+        .map_err(|e| format!("Failed to download attestation rewards: {}", e))?
+        .data;
+    for reward in rewards.total_rewards {
+        let Some(performance) = pubkeys_by_index
+            .get(&reward.validator_index)
+            .and_then(|pubkey| performance.get_mut(pubkey))
+        else {
+            continue;
+        };
+        performance.attestation = Some(AttestationPerformance {
+            head: reward.head > 0,
+            target: reward.target > 0,
+            source: reward.source > 0,
+            inclusion_distance: None,
+            rewards: reward.head
+                + reward.target
+                + reward.source
+                + reward.inclusion_delay.map_or(0, |delay| delay.value as i64)
+                + reward.inactivity,
+        });
+    }
+
+    let attester_duties = duties_service
+        .attesters
+        .read()
+        .values()
+        .filter_map(|duties| duties.get(&epoch))
+        .map(|(_, duty_and_proof)| duty_and_proof.duty.clone())
+        .collect::<Vec<_>>();
+    let proposer_duties = duties_service
+        .proposers
+        .read()
+        .get(&epoch)
+        .map(|proposers| proposers.duties.clone())
+        .unwrap_or_default();
+
+    let mut blocks = BlockCache::<E>::default();
+
+    for duty in proposer_duties {
+        let Some(performance) = performance.get_mut(&duty.pubkey) else {
+            continue;
+        };
+        let proposed = blocks
+            .block(beacon_nodes, duty.slot)
+            .await?
+            .is_some_and(|block| block.proposer_index == duty.validator_index);
+        if !proposed {
+            performance.blocks_missed += 1;
+            continue;
+        }
+        performance.blocks_proposed += 1;
+
+        let reward = beacon_nodes
+            .first_success(
+                RequireSynced::Yes,
+                OfflineOnFailure::Yes,
+                |beacon_node| async move {
+                    beacon_node
+                        .get_beacon_rewards_blocks(BlockId::Slot(duty.slot))
+                        .await
+                },
+            )
+            .await
+            .map_err(|e| format!("Failed to download block rewards: {}", e))?;
+        performance.proposal_rewards += reward.map_or(0, |reward| reward.data.total);
+    }
+
+    for duty in attester_duties {
+        let Some(attestation) = performance
+            .get_mut(&duty.pubkey)
+            .and_then(|performance| performance.attestation.as_mut())
+        else {
+            continue;
+        };
+        attestation.inclusion_distance = blocks.inclusion_distance(beacon_nodes, &duty).await?;
+    }
+
+    Ok(performance)
+}
+
+/// The parts of a canonical block needed to track performance.
+struct BlockSummary {
+    proposer_index: u64,
+    attestations: Vec<AttestationSummary>,
+}
+
+struct AttestationSummary {
+    slot: Slot,
+    /// The committee index of the attestation data, which is always zero since Electra.
+    index: u64,
+    /// The committees covered by the attestation, since Electra.
+    committee_bits: Option<Vec<bool>>,
+    aggregation_bits: Vec<bool>,
+}
+
+/// Downloads each block and committee at most once while an epoch is processed.
+#[derive(Default)]
+struct BlockCache<E: EthSpec> {
+    blocks: HashMap<Slot, Option<BlockSummary>>,
+    /// The committee lengths of each slot, indexed by committee index.
+    committee_lengths: HashMap<Slot, Vec<usize>>,
+    _phantom: std::marker::PhantomData<E>,
+}
+
+impl<E: EthSpec> BlockCache<E> {
+    /// Returns the canonical block at `slot`, or `None` if the slot was skipped.
+    async fn block<T: SlotClock>(
+        &mut self,
+        beacon_nodes: &BeaconNodeFallback<T, E>,
+        slot: Slot,
+    ) -> Result<Option<&BlockSummary>, String> {
+        if !self.blocks.contains_key(&slot) {
+            let block = beacon_nodes
+                .first_success(
+                    RequireSynced::Yes,
+                    OfflineOnFailure::Yes,
+                    |beacon_node| async move {
+                        beacon_node
+                            .get_beacon_blinded_blocks::<E>(BlockId::Slot(slot))
+                            .await
+                    },
+                )
+                .await
+                .map_err(|e| format!("Failed to download block at slot {}: {}", slot, e))?
+                .map(|response| response.data)
+                // Guard against beacon nodes which return an earlier block for a skipped slot.
+                .filter(|block| block.slot() == slot)
+                .map(|block| {
+                    let message = block.message();
+                    BlockSummary {
+                        proposer_index: message.proposer_index(),
+                        attestations: message
+                            .body()
+                            .attestations()
+                            .map(|attestation| AttestationSummary {
+                                slot: attestation.data().slot,
+                                index: attestation.data().index,
+                                committee_bits: match attestation {
+                                    AttestationRef::Base(_) => None,
+                                    AttestationRef::Electra(att) => {
+                                        Some(att.committee_bits.iter().collect())
+                                    }
+                                },
+                                aggregation_bits: match attestation {
+                                    AttestationRef::Base(att) => {
+                                        att.aggregation_bits.iter().collect()
+                                    }
+                                    AttestationRef::Electra(att) => {
+                                        att.aggregation_bits.iter().collect()
+                                    }
+                                },
+                            })
+                            .collect(),
+                    }
+                });
+            self.blocks.insert(slot, block);
+        }
+        Ok(self.blocks.get(&slot).and_then(Option::as_ref))
+    }
+
+    /// Returns the lengths of the committees at `slot`, indexed by committee index.
+    async fn committee_lengths<T: SlotClock>(
+        &mut self,
+        beacon_nodes: &BeaconNodeFallback<T, E>,
+        slot: Slot,
+    ) -> Result<&[usize], String> {
+        if !self.committee_lengths.contains_key(&slot) {
+            let mut committees = beacon_nodes
+                .first_success(
+                    RequireSynced::Yes,
+                    OfflineOnFailure::Yes,
+                    |beacon_node| async move {
+                        beacon_node
+                            .get_beacon_states_committees(
+                                StateId::Slot(slot),
+                                Some(slot),
+                                None,
+                                None,
+                            )
+                            .await
+                    },
+                )
+                .await
+                .map_err(|e| format!("Failed to download committees at slot {}: {}", slot, e))?
+                .ok_or_else(|| format!("No committees at slot {}", slot))?
+                .data;
+            committees.sort_by_key(|committee| committee.index);
+            let lengths = committees
+                .into_iter()
+                .map(|committee| committee.validators.len())
+                .collect();
+            self.committee_lengths.insert(slot, lengths);
+        }
+        Ok(self
+            .committee_lengths
+            .get(&slot)
+            .map(Vec::as_slice)
+            .unwrap_or_default())
+    }
+
+    /// Returns the number of slots between the slot of `duty` and the first block which includes
+    /// an attestation by its validator, searching up to one epoch ahead.
+    async fn inclusion_distance<T: SlotClock>(
+        &mut self,
+        beacon_nodes: &BeaconNodeFallback<T, E>,
+        duty: &AttesterData,
+    ) -> Result<Option<u64>, String> {
+        for distance in 1..=E::slots_per_epoch() {
+            let slot = duty.slot + distance;
+            let Some(block) = self.block(beacon_nodes, slot).await? else {
+                continue;
+            };
+
+            let mut electra_attestations = vec![];
+            for attestation in &block.attestations {
+                if attestation.slot != duty.slot {
+                    continue;
+                }
+                match &attestation.committee_bits {
+                    // Before Electra, each attestation is for a single committee.
+                    None => {
+                        if attestation.index == duty.committee_index
+                            && attestation
+                                .aggregation_bits
+                                .get(duty.validator_committee_index as usize)
+                                == Some(&true)
+                        {
+                            return Ok(Some(distance));
+                        }
+                    }
+                    Some(committee_bits) => {
+                        if committee_bits.get(duty.committee_index as usize) == Some(&true) {
+                            electra_attestations.push((
+                                committee_bits.clone(),
+                                attestation.aggregation_bits.clone(),
+                            ));
+                        }
+                    }
+                }
+            }
+
+            if electra_attestations.is_empty() {
+                continue;
+            }
+            // Since Electra, an attestation spans several committees, with the aggregation bits
+            // of each committee following those of the committees with a lower index.
+            let lengths = self.committee_lengths(beacon_nodes, duty.slot).await?;
+            for (committee_bits, aggregation_bits) in electra_attestations {
+                let offset = committee_bits
+                    .iter()
+                    .zip(lengths)
+                    .take(duty.committee_index as usize)
+                    .filter(|(bit, _)| **bit)
+                    .map(|(_, length)| length)
+                    .sum::<usize>();
+                let position = offset + duty.validator_committee_index as usize;
+                if aggregation_bits.get(position) == Some(&true) {
+                    return Ok(Some(distance));
+                }
+            }
+        }
+        Ok(None)
+    }
+}
@@ -13,15 +13,15 @@ use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::time::{sleep, Duration};
 use types::{
-    Address, ChainSpec, EthSpec, ProposerPreparationData, SignedValidatorRegistrationData,
-    ValidatorRegistrationData,
+    Address, ChainSpec, Epoch, EthSpec, ProposerPreparationData, SignedValidatorRegistrationData,
+    Slot, ValidatorRegistrationData,
 };
 
 /// Number of epochs before the Bellatrix hard fork to begin posting proposer preparations.
 const PROPOSER_PREPARATION_LOOKAHEAD_EPOCHS: u64 = 2;
 
 /// Number of epochs to wait before re-submitting validator registration.
-const EPOCHS_PER_VALIDATOR_REGISTRATION_SUBMISSION: u64 = 1;
+pub const EPOCHS_PER_VALIDATOR_REGISTRATION_SUBMISSION: u64 = 1;
 
 /// Builds an `PreparationService`.
 pub struct PreparationServiceBuilder<T: SlotClock + 'static, E: EthSpec> {
@@ -102,6 +102,8 @@ impl<T: SlotClock + 'static, E: EthSpec> PreparationServiceBuilder<T, E> {
                     "Cannot build PreparationService without validator_registration_batch_size",
                 )?,
                 validator_registration_cache: RwLock::new(HashMap::new()),
+                validator_registrations_published: RwLock::new(HashMap::new()),
+                proposer_preparations_published: RwLock::new(None),
             }),
         })
     }
@@ -118,6 +120,34 @@ pub struct Inner<T, E: EthSpec> {
     validator_registration_cache:
         RwLock<HashMap<ValidatorRegistrationKey, SignedValidatorRegistrationData>>,
     validator_registration_batch_size: usize,
+    // The slot at which a registration of each validator was last published to all beacon nodes.
+    validator_registrations_published: RwLock<HashMap<PublicKeyBytes, Slot>>,
+    // The last proposer preparation published to all beacon nodes.
+    proposer_preparations_published: RwLock<Option<PublishedPreparations>>,
+}
+
+/// Proposer preparations which were published to all beacon nodes.
+#[derive(Debug, PartialEq)]
+struct PublishedPreparations {
+    epoch: Epoch,
+    /// The `BeaconNodeFallback::connections` when the preparations were published.
+    connections: u64,
+    preparation_data: Vec<ProposerPreparationData>,
+}
+
+impl PublishedPreparations {
+    /// Returns `true` if `self` must be published again, because they were published in an earlier
+    /// epoch, a beacon node has reconnected since, or the preparations have changed.
+    fn is_stale(
+        &self,
+        epoch: Epoch,
+        connections: u64,
+        preparation_data: &[ProposerPreparationData],
+    ) -> bool {
+        self.epoch != epoch
+            || self.connections != connections
+            || self.preparation_data != preparation_data
+    }
 }
 
 #[derive(Hash, Eq, PartialEq, Debug, Clone)]
@@ -170,6 +200,51 @@ impl<T: SlotClock + 'static, E: EthSpec> PreparationService<T, E> {
         self.start_proposer_prepare_service(spec)
     }
 
+    /// Returns the slot at which a registration of `pubkey` was last published to all beacon
+    /// nodes, if any.
+    pub fn last_validator_registration_slot(&self, pubkey: &PublicKeyBytes) -> Option<Slot> {
+        self.validator_registrations_published
+            .read()
+            .get(pubkey)
+            .copied()
+    }
+
+    /// Returns all signed validator registrations, with the slot at which each was last published
+    /// to all beacon nodes.
+    pub fn validator_registrations(&self) -> Vec<(SignedValidatorRegistrationData, Option<Slot>)> {
+        let registrations = self
+            .validator_registration_cache
+            .read()
+            .values()
+            .cloned()
+            .collect::<Vec<_>>();
+        registrations
+            .into_iter()
+            .map(|registration| {
+                let slot = self.last_validator_registration_slot(&registration.message.pubkey);
+                (registration, slot)
+            })
+            .collect()
+    }
+
+    /// Restores validator registrations from a previous run of the validator client, so that they
+    /// aren't re-signed with a new timestamp.
+    pub fn restore_validator_registrations(
+        &self,
+        registrations: Vec<(SignedValidatorRegistrationData, Option<Slot>)>,
+    ) {
+        for (registration, slot) in registrations {
+            if let Some(slot) = slot {
+                self.validator_registrations_published
+                    .write()
+                    .insert(registration.message.pubkey, slot);
+            }
+            self.validator_registration_cache
+                .write()
+                .insert(registration.message.clone().into(), registration);
+        }
+    }
+
     /// Starts the service which periodically produces proposer preparations.
     pub fn start_proposer_prepare_service(self, spec: &ChainSpec) -> Result<(), String> {
         let log = self.context.log().clone();
@@ -235,12 +310,19 @@ impl<T: SlotClock + 'static, E: EthSpec> PreparationService<T, E> {
                 }
 
                 // Wait one slot if the register validator request fails or if we should not publish at the current slot.
-                if let Some(duration_to_next_slot) = self.slot_clock.duration_to_next_slot() {
-                    sleep(duration_to_next_slot).await;
-                } else {
+                let sleep_time = self.slot_clock.duration_to_next_slot().unwrap_or_else(|| {
                     error!(log, "Failed to read slot clock");
                     // If we can't read the slot clock, just wait another slot.
-                    sleep(slot_duration).await;
+                    slot_duration
+                });
+
+                // Re-register straight away if a fee recipient or gas limit changes, rather than
+                // waiting for the next slot.
+                tokio::select! {
+                    _ = sleep(sleep_time) => (),
+                    _ = self.validator_store.proposal_data_changed() => {
+                        debug!(log, "Validator proposal data changed, refreshing registrations");
+                    }
                 }
             }
         };
@@ -263,12 +345,45 @@ impl<T: SlotClock + 'static, E: EthSpec> PreparationService<T, E> {
     }
 
     /// Prepare proposer preparations and send to beacon node
+    ///
+    /// The preparations of all validators are sent in a single request. The beacon node retains
+    /// them for 2 epochs, so when they are broadcast they are only re-sent once per epoch, unless
+    /// they change, a beacon node comes back online (possibly after a restart which lost them) or
+    /// the last request failed on any beacon node. Without broadcast they are sent every slot,
+    /// since the request may reach a different beacon node each time.
     async fn prepare_proposers_and_publish(&self, spec: &ChainSpec) -> Result<(), String> {
+        let current_epoch = self
+            .slot_clock
+            .now()
+            .map_or(E::genesis_epoch(), |slot| slot.epoch(E::slots_per_epoch()));
         let preparation_data = self.collect_preparation_data(spec);
-        if !preparation_data.is_empty() {
-            self.publish_preparation_data(preparation_data).await?;
+        if preparation_data.is_empty() {
+            return Ok(());
+        }
+
+        // Read before publishing, so that a beacon node which reconnects meanwhile is sent the
+        // preparations again.
+        let connections = self.beacon_nodes.connections();
+        let stale = self
+            .proposer_preparations_published
+            .read()
+            .as_ref()
+            .map_or(true, |published| {
+                published.is_stale(current_epoch, connections, &preparation_data)
+            });
+        if !stale {
+            return Ok(());
         }
 
+        let published = self.publish_preparation_data(&preparation_data).await
+            && self.beacon_nodes.broadcasts(ApiTopic::Subscriptions);
+        *self.proposer_preparations_published.write() =
+            published.then_some(PublishedPreparations {
+                epoch: current_epoch,
+                connections,
+                preparation_data,
+            });
+
         Ok(())
     }
 
@@ -331,15 +446,12 @@ impl<T: SlotClock + 'static, E: EthSpec> PreparationService<T, E> {
             .collect()
     }
 
-    async fn publish_preparation_data(
-        &self,
-        preparation_data: Vec<ProposerPreparationData>,
-    ) -> Result<(), String> {
+    /// Returns `true` if the preparations were published to all beacon nodes.
+    async fn publish_preparation_data(&self, preparation_data: &[ProposerPreparationData]) -> bool {
         let log = self.context.log();
 
         // Post the proposer preparations to the BN.
         let preparation_data_len = preparation_data.len();
-        let preparation_entries = preparation_data.as_slice();
         match self
             .beacon_nodes
             .request(
@@ -348,24 +460,29 @@ impl<T: SlotClock + 'static, E: EthSpec> PreparationService<T, E> {
                 ApiTopic::Subscriptions,
                 |beacon_node| async move {
                     beacon_node
-                        .post_validator_prepare_beacon_proposer(preparation_entries)
+                        .post_validator_prepare_beacon_proposer(preparation_data)
                         .await
                 },
             )
             .await
         {
-            Ok(()) => debug!(
-                log,
-                "Published proposer preparation";
-                "count" => preparation_data_len,
-            ),
-            Err(e) => error!(
-                log,
-                "Unable to publish proposer preparation to all beacon nodes";
-                "error" => %e,
-            ),
+            Ok(()) => {
+                debug!(
+                    log,
+                    "Published proposer preparation";
+                    "count" => preparation_data_len,
+                );
+                true
+            }
+            Err(e) => {
+                error!(
+                    log,
+                    "Unable to publish proposer preparation to all beacon nodes";
+                    "error" => %e,
+                );
+                false
+            }
         }
-        Ok(())
     }
 
     /// Register validators with builders, used in the blinded block proposal flow.
@@ -453,6 +570,12 @@ impl<T: SlotClock + 'static, E: EthSpec> PreparationService<T, E> {
                         );
                         continue;
                     }
+                    // Watch-only validators are registered by whichever system holds their keys,
+                    // and validators without the signing lease by the instance which holds it.
+                    Err(
+                        ValidatorStoreError::WatchOnly(_)
+                        | ValidatorStoreError::SigningLeaseNotHeld(_),
+                    ) => continue,
                     Err(e) => {
                         error!(
                             log,
@@ -486,11 +609,19 @@ impl<T: SlotClock + 'static, E: EthSpec> PreparationService<T, E> {
                     )
                     .await
                 {
-                    Ok(()) => info!(
-                        log,
-                        "Published validator registrations to the builder network";
-                        "count" => batch.len(),
-                    ),
+                    Ok(()) => {
+                        if let Some(slot) = self.slot_clock.now() {
+                            let mut published = self.validator_registrations_published.write();
+                            for registration in batch {
+                                published.insert(registration.message.pubkey, slot);
+                            }
+                        }
+                        info!(
+                            log,
+                            "Published validator registrations to the builder network";
+                            "count" => batch.len(),
+                        )
+                    }
                     Err(e) => warn!(
                         log,
                         "Unable to publish validator registrations to the builder network";
@@ -510,3 +641,28 @@ pub struct ProposalData {
     pub(crate) gas_limit: u64,
     pub(crate) builder_proposals: bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn published_preparations_become_stale() {
+        let preparation = |validator_index| ProposerPreparationData {
+            validator_index,
+            fee_recipient: Address::repeat_byte(1),
+        };
+        let epoch = Epoch::new(3);
+        let published = PublishedPreparations {
+            epoch,
+            connections: 2,
+            preparation_data: vec![preparation(0), preparation(1)],
+        };
+
+        assert!(!published.is_stale(epoch, 2, &[preparation(0), preparation(1)]));
+        // Resent in the next epoch, after a beacon node reconnects or when they change.
+        assert!(published.is_stale(epoch + 1, 2, &[preparation(0), preparation(1)]));
+        assert!(published.is_stale(epoch, 3, &[preparation(0), preparation(1)]));
+        assert!(published.is_stale(epoch, 2, &[preparation(0)]));
+    }
+}
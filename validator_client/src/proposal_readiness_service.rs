@@ -0,0 +1,229 @@
+//! Checks that each upcoming block proposal of a local validator is likely to succeed, early enough
+//! for an operator to fix any issues before the proposal is missed.
+
+use crate::beacon_node_fallback::BeaconNodeFallback;
+use crate::duties_service::DutiesService;
+use crate::http_metrics::metrics;
+use crate::preparation_service::{
+    PreparationService, ProposalData, EPOCHS_PER_VALIDATOR_REGISTRATION_SUBMISSION,
+};
+use environment::RuntimeContext;
+use slog::{error, info, warn};
+use slot_clock::SlotClock;
+use std::collections::HashMap;
+use std::sync::Arc;
+use strum::{EnumIter, IntoEnumIterator, IntoStaticStr};
+use tokio::time::sleep;
+use types::{EthSpec, PublicKeyBytes, Slot};
+
+/// The age in epochs after which a builder registration is considered stale.
+///
+/// Registrations are re-published every `EPOCHS_PER_VALIDATOR_REGISTRATION_SUBMISSION` epochs,
+/// so an older registration indicates that publishing has been failing.
+const MAX_REGISTRATION_AGE_EPOCHS: u64 = EPOCHS_PER_VALIDATOR_REGISTRATION_SUBMISSION + 1;
+
+/// An issue which is likely to cause a block proposal to fail or to be suboptimal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum ReadinessIssue {
+    /// The validator has no fee recipient, so a block cannot be produced.
+    MissingFeeRecipient,
+    /// The validator uses the builder network but has no recently published registration.
+    StaleBuilderRegistration,
+    /// The remote signer of the validator is quarantined.
+    SignerUnavailable,
+    /// None of the beacon nodes are synced.
+    NoSyncedBeaconNode,
+}
+
+/// Returns the issues which affect a proposal at the `current_slot` by a validator with the given
+/// `proposal_data`.
+fn readiness_issues<E: EthSpec>(
+    proposal_data: &ProposalData,
+    last_registration_slot: Option<Slot>,
+    signer_quarantined: bool,
+    num_synced_beacon_nodes: usize,
+    current_slot: Slot,
+) -> Vec<ReadinessIssue> {
+    let registration_stale = || {
+        last_registration_slot.map_or(true, |slot| {
+            current_slot.saturating_sub(slot) > MAX_REGISTRATION_AGE_EPOCHS * E::slots_per_epoch()
+        })
+    };
+
+    let mut issues = vec![];
+    if proposal_data.fee_recipient.is_none() {
+        issues.push(ReadinessIssue::MissingFeeRecipient);
+    } else if proposal_data.builder_proposals && registration_stale() {
+        // Registrations are only published for validators with a fee recipient.
+        issues.push(ReadinessIssue::StaleBuilderRegistration);
+    }
+    if signer_quarantined {
+        issues.push(ReadinessIssue::SignerUnavailable);
+    }
+    if num_synced_beacon_nodes == 0 {
+        issues.push(ReadinessIssue::NoSyncedBeaconNode);
+    }
+    issues
+}
+
+/// Starts a service which checks the readiness of each block proposal by a local validator in the
+/// next epoch, once per slot.
+///
+/// A warning is logged whenever the issues affecting a proposal change, and the number of upcoming
+/// proposals affected by each issue is published as a metric.
+pub fn start_proposal_readiness_service<T: SlotClock + 'static, E: EthSpec>(
+    context: RuntimeContext<E>,
+    duties_service: Arc<DutiesService<T, E>>,
+    preparation_service: PreparationService<T, E>,
+    beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
+) {
+    let log = context.log().clone();
+    let slot_clock = duties_service.slot_clock.clone();
+    let validator_store = duties_service.validator_store.clone();
+
+    let future = async move {
+        // The issues last reported for each upcoming proposal.
+        let mut reported_issues = HashMap::<(Slot, PublicKeyBytes), Vec<ReadinessIssue>>::new();
+
+        loop {
+            let sleep_time = slot_clock
+                .duration_to_next_slot()
+                .unwrap_or_else(|| slot_clock.slot_duration());
+            sleep(sleep_time).await;
+
+            let Some(current_slot) = slot_clock.now() else {
+                error!(log, "Failed to read slot clock");
+                continue;
+            };
+
+            let upcoming_proposals = (1..=E::slots_per_epoch())
+                .map(|offset| current_slot + offset)
+                .flat_map(|slot| {
+                    duties_service
+                        .block_proposers(slot)
+                        .into_iter()
+                        .map(move |pubkey| (slot, pubkey))
+                })
+                .collect::<Vec<_>>();
+
+            let num_synced_beacon_nodes = if upcoming_proposals.is_empty() {
+                0
+            } else {
+                beacon_nodes.num_synced().await
+            };
+
+            let mut issue_counts = HashMap::<ReadinessIssue, i64>::new();
+            let mut new_reported_issues = HashMap::new();
+            for (slot, pubkey) in upcoming_proposals {
+                // The validator may have been removed since its duties were downloaded.
+                let Some(proposal_data) = validator_store.proposal_data(&pubkey) else {
+                    continue;
+                };
+                let issues = readiness_issues::<E>(
+                    &proposal_data,
+                    preparation_service.last_validator_registration_slot(&pubkey),
+                    validator_store.signer_quarantined(&pubkey),
+                    num_synced_beacon_nodes,
+                    current_slot,
+                );
+                for issue in &issues {
+                    *issue_counts.entry(*issue).or_default() += 1;
+                }
+
+                let previous_issues = reported_issues.remove(&(slot, pubkey));
+                if previous_issues.as_ref() != Some(&issues) {
+                    let issue_names = issues
+                        .iter()
+                        .map(|issue| <&'static str>::from(*issue))
+                        .collect::<Vec<_>>();
+                    if !issues.is_empty() {
+                        warn!(
+                            log,
+                            "Validator is not ready to propose";
+                            "issues" => ?issue_names,
+                            "slots_until_proposal" => slot - current_slot,
+                            "proposal_slot" => slot,
+                            "validator" => ?pubkey,
+                        );
+                    } else if previous_issues.is_some() {
+                        info!(
+                            log,
+                            "Validator is ready to propose";
+                            "proposal_slot" => slot,
+                            "validator" => ?pubkey,
+                        );
+                    }
+                }
+                new_reported_issues.insert((slot, pubkey), issues);
+            }
+            reported_issues = new_reported_issues;
+
+            for issue in ReadinessIssue::iter() {
+                metrics::set_int_gauge(
+                    &metrics::PROPOSAL_READINESS_ISSUES,
+                    &[issue.into()],
+                    issue_counts.get(&issue).copied().unwrap_or_default(),
+                );
+            }
+        }
+    };
+
+    context.executor.spawn(future, "proposal_readiness");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{Address, MainnetEthSpec};
+
+    type E = MainnetEthSpec;
+
+    fn proposal_data(fee_recipient: Option<Address>, builder_proposals: bool) -> ProposalData {
+        ProposalData {
+            validator_index: Some(0),
+            fee_recipient,
+            gas_limit: 30_000_000,
+            builder_proposals,
+        }
+    }
+
+    #[test]
+    fn readiness_issues_are_detected() {
+        let current_slot = Slot::new(1024);
+        let ready = proposal_data(Some(Address::repeat_byte(1)), true);
+
+        assert!(
+            readiness_issues::<E>(&ready, Some(current_slot - 1), false, 1, current_slot)
+                .is_empty()
+        );
+        assert_eq!(
+            readiness_issues::<E>(&ready, None, true, 0, current_slot),
+            vec![
+                ReadinessIssue::StaleBuilderRegistration,
+                ReadinessIssue::SignerUnavailable,
+                ReadinessIssue::NoSyncedBeaconNode,
+            ]
+        );
+
+        // Registrations are only checked for validators using the builder network.
+        let stale_slot = current_slot - (MAX_REGISTRATION_AGE_EPOCHS * E::slots_per_epoch() + 1);
+        assert_eq!(
+            readiness_issues::<E>(&ready, Some(stale_slot), false, 1, current_slot),
+            vec![ReadinessIssue::StaleBuilderRegistration]
+        );
+        assert!(readiness_issues::<E>(
+            &proposal_data(Some(Address::repeat_byte(1)), false),
+            None,
+            false,
+            1,
+            current_slot
+        )
+        .is_empty());
+
+        assert_eq!(
+            readiness_issues::<E>(&proposal_data(None, true), None, false, 1, current_slot),
+            vec![ReadinessIssue::MissingFeeRecipient]
+        );
+    }
+}
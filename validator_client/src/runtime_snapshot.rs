@@ -0,0 +1,444 @@
+//! Persists state which the validator client derives at runtime (duties, doppelganger detection
+//! progress and builder registrations) to disk, so that a quick restart can resume duties
+//! immediately rather than re-deriving everything.
+//!
+//! The snapshot is written every slot and when the validator client shuts down. It is only
+//! restored if it was taken on the same network within the last epoch. Restored duties are
+//! verified against the beacon node's dependent roots as usual. A validator for which a
+//! doppelganger was detected remains blocked from signing, and any other validator must still pass
+//! doppelganger detection covering the epochs since the snapshot.
+
+use crate::doppelganger_service::DoppelgangerService;
+use crate::duties_service::{DutiesService, DutyAndProof, ProposerDuties};
+use crate::preparation_service::PreparationService;
+use account_utils::write_file_via_temporary;
+use bls::PublicKeyBytes;
+use environment::RuntimeContext;
+use eth2::types::{AttesterData, ProposerData};
+use serde::{Deserialize, Serialize};
+use slog::{debug, error, info, warn, Logger};
+use slot_clock::SlotClock;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::sleep;
+use types::{Epoch, EthSpec, Hash256, SignedValidatorRegistrationData, Slot};
+
+/// The name of the snapshot file within the validator directory.
+pub const RUNTIME_SNAPSHOT_FILENAME: &str = "runtime_snapshot.json";
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RuntimeSnapshot {
+    pub genesis_validators_root: Hash256,
+    /// The slot at which the snapshot was taken.
+    pub slot: Slot,
+    pub attesters: Vec<AttesterDutySnapshot>,
+    pub proposers: Vec<ProposerDutiesSnapshot>,
+    pub doppelganger: Vec<DoppelgangerSnapshot>,
+    pub registrations: Vec<RegistrationSnapshot>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct AttesterDutySnapshot {
+    pub dependent_root: Hash256,
+    pub duty: AttesterData,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct ProposerDutiesSnapshot {
+    pub epoch: Epoch,
+    pub dependent_root: Hash256,
+    pub prefetched: bool,
+    pub duties: Vec<ProposerData>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct DoppelgangerSnapshot {
+    pub pubkey: PublicKeyBytes,
+    pub remaining_epochs: u64,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RegistrationSnapshot {
+    pub registration: SignedValidatorRegistrationData,
+    /// The slot at which the registration was last published to all beacon nodes.
+    pub published_slot: Option<Slot>,
+}
+
+impl RuntimeSnapshot {
+    /// Takes a snapshot of the runtime state of the services at `slot`.
+    pub fn take<T: SlotClock + 'static, E: EthSpec>(
+        genesis_validators_root: Hash256,
+        slot: Slot,
+        duties_service: &DutiesService<T, E>,
+        preparation_service: &PreparationService<T, E>,
+        doppelganger_service: Option<&DoppelgangerService>,
+    ) -> Self {
+        let current_epoch = slot.epoch(E::slots_per_epoch());
+
+        let attesters = duties_service
+            .attesters
+            .read()
+            .values()
+            .flat_map(|duties| duties.iter())
+            .filter(|(epoch, _)| **epoch >= current_epoch)
+            .map(
+                |(_, (dependent_root, duty_and_proof))| AttesterDutySnapshot {
+                    dependent_root: *dependent_root,
+                    duty: duty_and_proof.duty.clone(),
+                },
+            )
+            .collect();
+
+        let proposers = duties_service
+            .proposers
+            .read()
+            .iter()
+            .filter(|(epoch, _)| **epoch >= current_epoch)
+            .map(|(epoch, duties)| ProposerDutiesSnapshot {
+                epoch: *epoch,
+                dependent_root: duties.dependent_root,
+                prefetched: duties.prefetched,
+                duties: duties.duties.clone(),
+            })
+            .collect();
+
+        let doppelganger = doppelganger_service
+            .map(|service| {
+                service
+                    .status_data()
+                    .into_iter()
+                    .map(|data| DoppelgangerSnapshot {
+                        pubkey: data.pubkey,
+                        remaining_epochs: data.remaining_epochs,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let registrations = preparation_service
+            .validator_registrations()
+            .into_iter()
+            .map(|(registration, published_slot)| RegistrationSnapshot {
+                registration,
+                published_slot,
+            })
+            .collect();
+
+        Self {
+            genesis_validators_root,
+            slot,
+            attesters,
+            proposers,
+            doppelganger,
+            registrations,
+        }
+    }
+
+    /// Returns `true` if the snapshot may be restored at `current_slot`.
+    pub fn is_fresh<E: EthSpec>(
+        &self,
+        genesis_validators_root: Hash256,
+        current_slot: Slot,
+    ) -> bool {
+        self.genesis_validators_root == genesis_validators_root
+            && self.slot <= current_slot
+            && current_slot <= self.slot + E::slots_per_epoch()
+    }
+
+    /// Restores the snapshot into the services, ignoring validators which are no longer managed by
+    /// the validator client and duties which have already passed.
+    pub fn restore<T: SlotClock + 'static, E: EthSpec>(
+        self,
+        current_slot: Slot,
+        duties_service: &DutiesService<T, E>,
+        preparation_service: &PreparationService<T, E>,
+        doppelganger_service: Option<&DoppelgangerService>,
+        log: &Logger,
+    ) {
+        let current_epoch = current_slot.epoch(E::slots_per_epoch());
+        let snapshot_epoch = self.slot.epoch(E::slots_per_epoch());
+        let validator_store = &duties_service.validator_store;
+
+        let mut attesters = duties_service.attesters.write();
+        let mut queued: HashMap<Hash256, Vec<AttesterData>> = HashMap::new();
+        for AttesterDutySnapshot {
+            dependent_root,
+            duty,
+        } in self.attesters
+        {
+            let epoch = duty.slot.epoch(E::slots_per_epoch());
+            if epoch < current_epoch || !validator_store.has_validator(&duty.pubkey) {
+                continue;
+            }
+            queued.entry(dependent_root).or_default().push(duty.clone());
+            let duty_and_proof = DutyAndProof::new_without_selection_proof(duty, current_slot);
+            attesters
+                .entry(duty_and_proof.duty.pubkey)
+                .or_default()
+                .insert(epoch, (dependent_root, duty_and_proof));
+        }
+        drop(attesters);
+
+        // The selection proofs weren't persisted, so they must be signed again.
+        for (dependent_root, duties) in queued {
+            duties_service
+                .selection_proof_queue
+                .push_attestation_duties(dependent_root, duties);
+        }
+
+        let mut proposers = duties_service.proposers.write();
+        for snapshot in self.proposers {
+            if snapshot.epoch < current_epoch {
+                continue;
+            }
+            let duties = snapshot
+                .duties
+                .into_iter()
+                .filter(|duty| validator_store.has_validator(&duty.pubkey))
+                .collect();
+            proposers.insert(
+                snapshot.epoch,
+                ProposerDuties {
+                    dependent_root: snapshot.dependent_root,
+                    duties,
+                    prefetched: snapshot.prefetched,
+                },
+            );
+        }
+        drop(proposers);
+
+        if let Some(doppelganger_service) = doppelganger_service {
+            for snapshot in self.doppelganger {
+                let restored = doppelganger_service.restore_progress(
+                    snapshot.pubkey,
+                    snapshot_epoch,
+                    snapshot.remaining_epochs,
+                );
+                if !restored && validator_store.has_validator(&snapshot.pubkey) {
+                    warn!(
+                        log,
+                        "Unable to restore doppelganger progress";
+                        "msg" => "validator is not registered for doppelganger protection",
+                        "pubkey" => ?snapshot.pubkey,
+                    );
+                }
+            }
+        }
+
+        let registrations = self
+            .registrations
+            .into_iter()
+            .filter(|snapshot| validator_store.has_validator(&snapshot.registration.message.pubkey))
+            .map(|snapshot| (snapshot.registration, snapshot.published_slot))
+            .collect();
+        preparation_service.restore_validator_registrations(registrations);
+    }
+
+    pub fn read_from_file(path: &Path) -> Result<Self, String> {
+        let file = File::open(path).map_err(|e| format!("Unable to open snapshot: {:?}", e))?;
+        serde_json::from_reader(file).map_err(|e| format!("Unable to parse snapshot: {:?}", e))
+    }
+
+    /// Writes the snapshot to `path`, via a temporary file so that `path` never holds a partial
+    /// snapshot.
+    pub fn write_to_file(&self, path: &Path) -> Result<(), String> {
+        let bytes =
+            serde_json::to_vec(self).map_err(|e| format!("Unable to encode snapshot: {:?}", e))?;
+        write_file_via_temporary(path, &path.with_extension("json.tmp"), &bytes)
+            .map_err(|e| format!("Unable to write snapshot: {:?}", e))
+    }
+}
+
+/// Restores the snapshot at `path` into the services, if it exists and is fresh.
+///
+/// The snapshot is removed once read, so that it can't be restored a second time if the validator
+/// client fails to start.
+pub fn restore_runtime_snapshot<T: SlotClock + 'static, E: EthSpec>(
+    path: &Path,
+    genesis_validators_root: Hash256,
+    duties_service: &DutiesService<T, E>,
+    preparation_service: &PreparationService<T, E>,
+    doppelganger_service: Option<&DoppelgangerService>,
+    log: &Logger,
+) {
+    if !path.exists() {
+        return;
+    }
+
+    let snapshot = RuntimeSnapshot::read_from_file(path);
+    if let Err(e) = fs::remove_file(path) {
+        error!(
+            log,
+            "Unable to remove runtime snapshot";
+            "error" => ?e,
+            "path" => %path.display(),
+        );
+    }
+    let snapshot = match snapshot {
+        Ok(snapshot) => snapshot,
+        Err(e) => {
+            error!(
+                log,
+                "Unable to read runtime snapshot";
+                "error" => e,
+                "path" => %path.display(),
+            );
+            return;
+        }
+    };
+
+    let Some(current_slot) = duties_service.slot_clock.now() else {
+        return;
+    };
+    if !snapshot.is_fresh::<E>(genesis_validators_root, current_slot) {
+        info!(
+            log,
+            "Ignoring stale runtime snapshot";
+            "snapshot_slot" => snapshot.slot,
+            "current_slot" => current_slot,
+        );
+        return;
+    }
+
+    info!(
+        log,
+        "Restoring runtime snapshot";
+        "snapshot_slot" => snapshot.slot,
+        "attester_duties" => snapshot.attesters.len(),
+        "doppelganger_validators" => snapshot.doppelganger.len(),
+        "registrations" => snapshot.registrations.len(),
+    );
+    snapshot.restore(
+        current_slot,
+        duties_service,
+        preparation_service,
+        doppelganger_service,
+        log,
+    );
+}
+
+/// Starts a service which writes a snapshot to `path` every slot, and once more when the
+/// validator client shuts down.
+pub fn start_runtime_snapshot_service<T: SlotClock + 'static, E: EthSpec>(
+    context: RuntimeContext<E>,
+    path: PathBuf,
+    genesis_validators_root: Hash256,
+    duties_service: Arc<DutiesService<T, E>>,
+    preparation_service: PreparationService<T, E>,
+    doppelganger_service: Option<Arc<DoppelgangerService>>,
+) {
+    let log = context.log().clone();
+    let slot_clock = duties_service.slot_clock.clone();
+
+    let write = move || {
+        let Some(slot) = slot_clock.now() else {
+            return;
+        };
+        let snapshot = RuntimeSnapshot::take(
+            genesis_validators_root,
+            slot,
+            &duties_service,
+            &preparation_service,
+            doppelganger_service.as_deref(),
+        );
+        match snapshot.write_to_file(&path) {
+            Ok(()) => debug!(
+                log,
+                "Wrote runtime snapshot";
+                "slot" => slot,
+                "path" => %path.display(),
+            ),
+            Err(e) => error!(
+                log,
+                "Failed to write runtime snapshot";
+                "error" => e,
+                "path" => %path.display(),
+            ),
+        }
+    };
+
+    let write = Arc::new(write);
+    let slot_duration = Duration::from_secs(context.eth2_config.spec.seconds_per_slot);
+    let executor = context.executor.clone();
+    let future = async move {
+        let exit = executor.exit();
+        tokio::pin!(exit);
+
+        loop {
+            tokio::select! {
+                () = sleep(slot_duration) => {
+                    let write = write.clone();
+                    if let Some(handle) =
+                        executor.spawn_blocking_handle(move || write(), "runtime_snapshot")
+                    {
+                        let _ = handle.await;
+                    }
+                }
+                () = &mut exit => {
+                    // Write without yielding, so the runtime shutting down can't interrupt it.
+                    write();
+                    return;
+                }
+            }
+        }
+    };
+
+    // The exit signal is handled above, so that the final snapshot isn't cancelled.
+    context
+        .executor
+        .spawn_without_exit(future, "runtime_snapshot");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+    use types::MainnetEthSpec;
+
+    type E = MainnetEthSpec;
+
+    fn snapshot(slot: u64) -> RuntimeSnapshot {
+        RuntimeSnapshot {
+            genesis_validators_root: Hash256::repeat_byte(1),
+            slot: Slot::new(slot),
+            attesters: vec![],
+            proposers: vec![ProposerDutiesSnapshot {
+                epoch: Epoch::new(1),
+                dependent_root: Hash256::repeat_byte(2),
+                prefetched: false,
+                duties: vec![],
+            }],
+            doppelganger: vec![DoppelgangerSnapshot {
+                pubkey: PublicKeyBytes::empty(),
+                remaining_epochs: u64::MAX,
+            }],
+            registrations: vec![],
+        }
+    }
+
+    #[test]
+    fn snapshot_round_trip() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(RUNTIME_SNAPSHOT_FILENAME);
+
+        let snapshot = snapshot(32);
+        snapshot.write_to_file(&path).unwrap();
+        assert_eq!(RuntimeSnapshot::read_from_file(&path).unwrap(), snapshot);
+    }
+
+    #[test]
+    fn snapshot_freshness() {
+        let root = Hash256::repeat_byte(1);
+        let snapshot = snapshot(32);
+        let slots_per_epoch = E::slots_per_epoch();
+
+        assert!(snapshot.is_fresh::<E>(root, Slot::new(32)));
+        assert!(snapshot.is_fresh::<E>(root, Slot::new(32 + slots_per_epoch)));
+        assert!(!snapshot.is_fresh::<E>(root, Slot::new(33 + slots_per_epoch)));
+        assert!(!snapshot.is_fresh::<E>(root, Slot::new(31)));
+        assert!(!snapshot.is_fresh::<E>(Hash256::repeat_byte(2), Slot::new(32)));
+    }
+}
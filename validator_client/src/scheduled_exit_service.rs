@@ -0,0 +1,142 @@
+use crate::beacon_node_fallback::{BeaconNodeFallback, OfflineOnFailure, RequireSynced};
+use crate::validator_store::ValidatorStore;
+use account_utils::validator_definitions::ScheduledExit;
+use environment::RuntimeContext;
+use slog::{error, info, warn, Logger};
+use slot_clock::SlotClock;
+use std::sync::Arc;
+use tokio::time::sleep;
+use types::{Epoch, EthSpec, PublicKey, PublicKeyBytes, VoluntaryExit};
+
+/// Starts a service which signs and broadcasts voluntary exits that have been scheduled via the
+/// keymanager API, once the epoch of the exit is reached.
+///
+/// Exits are checked at the start of each epoch. Exits which fail to be signed or broadcast remain
+/// pending and are retried in the following epoch.
+pub fn start_scheduled_exit_service<T: SlotClock + 'static, E: EthSpec>(
+    context: RuntimeContext<E>,
+    slot_clock: T,
+    validator_store: Arc<ValidatorStore<T, E>>,
+    beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
+) {
+    let log = context.log().clone();
+
+    let future = async move {
+        loop {
+            let sleep_time = slot_clock
+                .duration_to_next_epoch(E::slots_per_epoch())
+                .unwrap_or_else(|| slot_clock.slot_duration());
+            sleep(sleep_time).await;
+
+            let Some(current_epoch) = slot_clock
+                .now()
+                .map(|slot| slot.epoch(E::slots_per_epoch()))
+            else {
+                error!(log, "Failed to read slot clock");
+                continue;
+            };
+
+            let pending_exits = validator_store
+                .initialized_validators()
+                .read()
+                .pending_scheduled_exits();
+
+            for (pubkey, epoch) in pending_exits {
+                if epoch > current_epoch {
+                    continue;
+                }
+                publish_scheduled_exit(&validator_store, &beacon_nodes, &pubkey, epoch, &log).await;
+            }
+        }
+    };
+
+    context.executor.spawn(future, "scheduled_exits");
+}
+
+/// Sign and broadcast the voluntary exit for `pubkey` at `epoch`, recording it as submitted if
+/// a beacon node accepts it.
+async fn publish_scheduled_exit<T: SlotClock + 'static, E: EthSpec>(
+    validator_store: &ValidatorStore<T, E>,
+    beacon_nodes: &BeaconNodeFallback<T, E>,
+    pubkey: &PublicKey,
+    epoch: Epoch,
+    log: &Logger,
+) {
+    let pubkey_bytes = PublicKeyBytes::from(pubkey);
+    let Some(validator_index) = validator_store.validator_index(&pubkey_bytes) else {
+        warn!(
+            log,
+            "Unable to publish scheduled exit";
+            "error" => "validator index is not known",
+            "validator" => ?pubkey_bytes,
+        );
+        return;
+    };
+
+    let voluntary_exit = VoluntaryExit {
+        epoch,
+        validator_index,
+    };
+    let signed_voluntary_exit = match validator_store
+        .sign_voluntary_exit(pubkey_bytes, voluntary_exit)
+        .await
+    {
+        Ok(exit) => exit,
+        Err(e) => {
+            error!(
+                log,
+                "Failed to sign scheduled exit";
+                "error" => ?e,
+                "validator" => ?pubkey_bytes,
+            );
+            return;
+        }
+    };
+
+    let signed_voluntary_exit = &signed_voluntary_exit;
+    if let Err(e) = beacon_nodes
+        .first_success(
+            RequireSynced::Yes,
+            OfflineOnFailure::Yes,
+            |beacon_node| async move {
+                beacon_node
+                    .post_beacon_pool_voluntary_exits(signed_voluntary_exit)
+                    .await
+            },
+        )
+        .await
+    {
+        error!(
+            log,
+            "Failed to publish scheduled exit";
+            "error" => %e,
+            "validator" => ?pubkey_bytes,
+        );
+        return;
+    }
+
+    info!(
+        log,
+        "Published scheduled exit";
+        "epoch" => epoch,
+        "validator_index" => validator_index,
+        "validator" => ?pubkey_bytes,
+    );
+
+    let scheduled_exit = ScheduledExit {
+        epoch,
+        submitted: true,
+    };
+    if let Err(e) = validator_store
+        .initialized_validators()
+        .write()
+        .set_scheduled_exit(pubkey, Some(scheduled_exit))
+    {
+        error!(
+            log,
+            "Failed to record scheduled exit as submitted";
+            "error" => ?e,
+            "validator" => ?pubkey_bytes,
+        );
+    }
+}
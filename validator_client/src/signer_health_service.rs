@@ -0,0 +1,176 @@
+//! Monitors the health of the Web3Signer instances used by local validators.
+//!
+//! Each instance is periodically sent an `upcheck` request, followed by a request to sign a
+//! harmless message with one of its keys. An instance which fails `QUARANTINE_THRESHOLD`
+//! consecutive checks is quarantined: signing requests for all of its validators fail immediately,
+//! rather than each duty waiting on (and failing against) the unhealthy signer. The quarantine is
+//! lifted as soon as the instance passes a check.
+
+use crate::http_metrics::metrics;
+use crate::validator_store::ValidatorStore;
+use environment::RuntimeContext;
+use parking_lot::RwLock;
+use slog::{crit, debug, info, warn};
+use slot_clock::SlotClock;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::sleep;
+use types::{EthSpec, PublicKeyBytes};
+
+/// The number of consecutive failed health checks after which a signer is quarantined.
+pub const QUARANTINE_THRESHOLD: usize = 2;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct SignerState {
+    consecutive_failures: usize,
+    quarantined: bool,
+}
+
+/// The health of each Web3Signer instance, keyed by the origin of its URL.
+#[derive(Debug, Default)]
+pub struct SignerHealth {
+    signers: RwLock<HashMap<String, SignerState>>,
+}
+
+impl SignerHealth {
+    /// Returns `true` if the signer at `origin` is quarantined.
+    pub fn is_quarantined(&self, origin: &str) -> bool {
+        self.signers
+            .read()
+            .get(origin)
+            .map_or(false, |state| state.quarantined)
+    }
+
+    /// Record a successful health check of the signer at `origin`.
+    ///
+    /// Returns `true` if the signer was quarantined and has now been released.
+    pub fn record_success(&self, origin: &str) -> bool {
+        let previous = self
+            .signers
+            .write()
+            .insert(origin.to_string(), SignerState::default());
+        previous.map_or(false, |state| state.quarantined)
+    }
+
+    /// Record a failed health check of the signer at `origin`.
+    ///
+    /// Returns `true` if the signer has now been quarantined as a result.
+    pub fn record_failure(&self, origin: &str) -> bool {
+        let mut signers = self.signers.write();
+        let state = signers.entry(origin.to_string()).or_default();
+        state.consecutive_failures += 1;
+        let newly_quarantined =
+            !state.quarantined && state.consecutive_failures >= QUARANTINE_THRESHOLD;
+        state.quarantined |= newly_quarantined;
+        newly_quarantined
+    }
+
+    /// Forget all signers which are not in `origins`, e.g. after their validators were removed.
+    pub fn retain(&self, origins: &HashMap<String, PublicKeyBytes>) {
+        self.signers
+            .write()
+            .retain(|origin, _| origins.contains_key(origin));
+    }
+}
+
+/// Starts a service which checks the health of each Web3Signer instance once every `interval`.
+pub fn start_signer_health_service<T: SlotClock + 'static, E: EthSpec>(
+    context: RuntimeContext<E>,
+    validator_store: Arc<ValidatorStore<T, E>>,
+    interval: Duration,
+) {
+    let log = context.log().clone();
+
+    let future = async move {
+        loop {
+            sleep(interval).await;
+
+            // Check each signer using the first validator found for it.
+            let signers = validator_store.web3signer_validators();
+            let signer_health = validator_store.signer_health();
+            signer_health.retain(&signers);
+
+            for (origin, validator_pubkey) in signers {
+                match validator_store.check_remote_signer(validator_pubkey).await {
+                    Ok(()) => {
+                        metrics::set_int_gauge(&metrics::WEB3SIGNER_QUARANTINED, &[&origin], 0);
+                        if signer_health.record_success(&origin) {
+                            info!(
+                                log,
+                                "Remote signer recovered";
+                                "msg" => "its validators will resume signing",
+                                "signer" => origin,
+                            );
+                        } else {
+                            debug!(log, "Remote signer is healthy"; "signer" => origin);
+                        }
+                    }
+                    Err(e) => {
+                        metrics::inc_counter_vec(
+                            &metrics::WEB3SIGNER_HEALTH_CHECK_FAILURES,
+                            &[&origin],
+                        );
+                        if signer_health.record_failure(&origin) {
+                            metrics::set_int_gauge(&metrics::WEB3SIGNER_QUARANTINED, &[&origin], 1);
+                            crit!(
+                                log,
+                                "Remote signer quarantined";
+                                "msg" => "its validators will not sign until it recovers",
+                                "error" => e,
+                                "signer" => origin,
+                            );
+                        } else {
+                            warn!(
+                                log,
+                                "Remote signer health check failed";
+                                "error" => e,
+                                "signer" => origin,
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    };
+
+    context.executor.spawn(future, "signer_health");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIGNER: &str = "http://localhost:9000";
+
+    #[test]
+    fn quarantine_after_consecutive_failures() {
+        let health = SignerHealth::default();
+
+        for _ in 1..QUARANTINE_THRESHOLD {
+            assert!(!health.record_failure(SIGNER));
+            assert!(!health.is_quarantined(SIGNER));
+        }
+        assert!(health.record_failure(SIGNER));
+        assert!(health.is_quarantined(SIGNER));
+
+        // Further failures don't re-quarantine the signer.
+        assert!(!health.record_failure(SIGNER));
+        assert!(health.is_quarantined(SIGNER));
+
+        assert!(health.record_success(SIGNER));
+        assert!(!health.is_quarantined(SIGNER));
+        assert!(!health.record_success(SIGNER));
+    }
+
+    #[test]
+    fn success_resets_failures() {
+        let health = SignerHealth::default();
+
+        for _ in 0..QUARANTINE_THRESHOLD * 2 {
+            assert!(!health.record_failure(SIGNER));
+            assert!(!health.record_success(SIGNER));
+        }
+        assert!(!health.is_quarantined(SIGNER));
+    }
+}
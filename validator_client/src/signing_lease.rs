@@ -0,0 +1,304 @@
+//! Ensures that only one of a pair of validator clients signs for a set of keys at a time.
+//!
+//! The validator clients of an active/standby pair are pointed at the same lease file, typically on
+//! a shared filesystem. The file records which instance holds the lease and when the lease expires.
+//! The holder renews the lease several times per timeout, while the other instance waits for it to
+//! expire before taking it over. An instance only signs whilst it holds the lease, and it stops
+//! signing a renewal interval *before* the lease expires, so that a holder which can no longer
+//! renew the lease has stopped signing by the time the other instance takes over.
+//!
+//! Expiry times are compared using the wall clocks of both hosts, so the other instance waits for
+//! an additional clock skew margin after the lease expires. The clocks of both hosts must be kept
+//! within this margin of each other, e.g. using NTP.
+
+use crate::http_metrics::metrics;
+use crate::validator_store::ValidatorStore;
+use account_utils::write_file_via_temporary;
+use environment::RuntimeContext;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use slog::{crit, info, warn};
+use slot_clock::SlotClock;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::time::sleep;
+use types::EthSpec;
+
+/// The default time after which a lease which isn't renewed may be taken over.
+pub const DEFAULT_SIGNING_LEASE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The shortest timeout permitted, leaving time for a renewal to be written each interval.
+pub const MIN_SIGNING_LEASE_TIMEOUT: Duration = Duration::from_secs(12);
+
+/// The default margin allowed for the difference between the clocks of the two instances.
+pub const DEFAULT_SIGNING_LEASE_CLOCK_SKEW: Duration = Duration::from_secs(5);
+
+/// The number of times the lease is renewed within each timeout.
+const RENEWALS_PER_TIMEOUT: u32 = 4;
+
+/// How long to wait after acquiring or renewing the lease before checking that no other instance
+/// has overwritten it.
+const CONFIRMATION_DELAY: Duration = Duration::from_millis(500);
+
+/// The contents of the lease file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct LeaseRecord {
+    /// The randomly generated identifier of the instance holding the lease.
+    holder: String,
+    /// The UNIX timestamp, in seconds, at which the lease expires.
+    expires_at: u64,
+}
+
+/// Whether this instance currently holds the signing lease.
+#[derive(Debug)]
+pub struct SigningLease {
+    required: bool,
+    /// The UNIX timestamp, in seconds, until which this instance may sign.
+    signing_deadline: AtomicU64,
+}
+
+impl SigningLease {
+    /// Returns a lease which must be acquired before signing if `required`, and otherwise always
+    /// allows signing.
+    pub fn new(required: bool) -> Self {
+        Self {
+            required,
+            signing_deadline: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns `true` if this instance may sign.
+    pub fn allows_signing(&self) -> bool {
+        !self.required || unix_now() < self.signing_deadline.load(Ordering::Relaxed)
+    }
+
+    fn hold_until(&self, deadline: u64) {
+        self.signing_deadline.store(deadline, Ordering::Relaxed);
+    }
+
+    fn release(&self) {
+        self.signing_deadline.store(0, Ordering::Relaxed);
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+fn read_lease(path: &Path) -> Result<Option<LeaseRecord>, String> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let bytes = fs::read(path).map_err(|e| format!("unable to read lease file: {:?}", e))?;
+    // A malformed lease is treated as absent, so that a partially written file can't deadlock
+    // the pair.
+    Ok(serde_json::from_slice(&bytes).ok())
+}
+
+fn write_lease(path: &Path, temp_path: &Path, record: &LeaseRecord) -> Result<(), String> {
+    let bytes =
+        serde_json::to_vec(record).map_err(|e| format!("unable to encode lease: {:?}", e))?;
+    write_file_via_temporary(path, temp_path, &bytes)
+        .map_err(|e| format!("unable to write lease file: {:?}", e))
+}
+
+/// Attempts to acquire or renew the lease at `path` on behalf of `holder`.
+///
+/// Returns the expiry of the lease if it is held by `holder`, or `None` if it is held by another
+/// instance. A lease held by another instance is only taken over once it has been expired for
+/// `clock_skew`.
+async fn try_acquire(
+    path: &Path,
+    temp_path: &Path,
+    holder: &str,
+    timeout: Duration,
+    clock_skew: Duration,
+) -> Result<Option<u64>, String> {
+    let now = unix_now();
+    match read_lease(path)? {
+        Some(record)
+            if record.holder != holder
+                && record.expires_at.saturating_add(clock_skew.as_secs()) > now =>
+        {
+            return Ok(None)
+        }
+        _ => (),
+    }
+
+    let record = LeaseRecord {
+        holder: holder.to_string(),
+        expires_at: now + timeout.as_secs(),
+    };
+    write_lease(path, temp_path, &record)?;
+
+    // Another instance may have taken over the lease at the same time, whether we were acquiring
+    // or renewing it. Only the last write stands, so confirm that it was ours.
+    sleep(CONFIRMATION_DELAY).await;
+    if read_lease(path)?.as_ref() != Some(&record) {
+        return Ok(None);
+    }
+
+    Ok(Some(record.expires_at))
+}
+
+/// Starts a service which acquires and renews the signing lease at `path`.
+pub fn start_signing_lease_service<T: SlotClock + 'static, E: EthSpec>(
+    context: RuntimeContext<E>,
+    validator_store: Arc<ValidatorStore<T, E>>,
+    path: PathBuf,
+    timeout: Duration,
+    clock_skew: Duration,
+) {
+    let log = context.log().clone();
+    let holder = format!("{:016x}", rand::thread_rng().gen::<u64>());
+    let temp_path = path.with_extension(format!("{}.tmp", holder));
+    let renewal_interval = timeout / RENEWALS_PER_TIMEOUT;
+
+    info!(
+        log,
+        "Signing lease enabled";
+        "msg" => "validators will only sign whilst this instance holds the lease",
+        "path" => ?path,
+        "holder" => &holder,
+        "clock_skew" => ?clock_skew,
+    );
+
+    let future = async move {
+        let lease = validator_store.signing_lease();
+        loop {
+            let was_held = lease.allows_signing();
+            match try_acquire(&path, &temp_path, &holder, timeout, clock_skew).await {
+                Ok(Some(expires_at)) => {
+                    lease.hold_until(expires_at.saturating_sub(renewal_interval.as_secs()));
+                    if !was_held {
+                        info!(
+                            log,
+                            "Acquired signing lease";
+                            "msg" => "this instance will now sign",
+                            "path" => ?path,
+                        );
+                    }
+                }
+                Ok(None) => {
+                    lease.release();
+                    if was_held {
+                        crit!(
+                            log,
+                            "Signing lease taken over by another instance";
+                            "msg" => "this instance will not sign until it regains the lease",
+                            "path" => ?path,
+                        );
+                    }
+                }
+                // Keep signing until the local deadline passes, in case the failure is transient.
+                Err(e) => warn!(
+                    log,
+                    "Unable to renew signing lease";
+                    "error" => e,
+                    "path" => ?path,
+                ),
+            }
+            metrics::set_gauge(
+                &metrics::SIGNING_LEASE_HELD,
+                i64::from(lease.allows_signing()),
+            );
+
+            sleep(renewal_interval).await;
+        }
+    };
+
+    context.executor.spawn(future, "signing_lease");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    const TIMEOUT: Duration = Duration::from_secs(60);
+    const SKEW: Duration = Duration::from_secs(5);
+
+    #[tokio::test]
+    async fn only_one_holder_at_a_time() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("lease.json");
+        let temp_a = dir.path().join("a.tmp");
+        let temp_b = dir.path().join("b.tmp");
+
+        let expires_at = try_acquire(&path, &temp_a, "a", TIMEOUT, SKEW)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(expires_at >= unix_now() + TIMEOUT.as_secs() - 1);
+
+        // The lease is held by "a", so "b" can't take it, but "a" can renew it.
+        assert_eq!(
+            try_acquire(&path, &temp_b, "b", TIMEOUT, SKEW)
+                .await
+                .unwrap(),
+            None
+        );
+        assert!(try_acquire(&path, &temp_a, "a", TIMEOUT, SKEW)
+            .await
+            .unwrap()
+            .is_some());
+
+        // Within the clock skew margin after the lease expires, "b" still can't take it.
+        write_lease(
+            &path,
+            &temp_a,
+            &LeaseRecord {
+                holder: "a".into(),
+                expires_at: unix_now() - 1,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            try_acquire(&path, &temp_b, "b", TIMEOUT, SKEW)
+                .await
+                .unwrap(),
+            None
+        );
+
+        // Once the margin has passed, "b" takes it over and "a" can't renew it.
+        write_lease(
+            &path,
+            &temp_a,
+            &LeaseRecord {
+                holder: "a".into(),
+                expires_at: unix_now() - SKEW.as_secs() - 1,
+            },
+        )
+        .unwrap();
+        assert!(try_acquire(&path, &temp_b, "b", TIMEOUT, SKEW)
+            .await
+            .unwrap()
+            .is_some());
+        assert_eq!(
+            try_acquire(&path, &temp_a, "a", TIMEOUT, SKEW)
+                .await
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn signing_requires_unexpired_lease() {
+        assert!(SigningLease::new(false).allows_signing());
+
+        let lease = SigningLease::new(true);
+        assert!(!lease.allows_signing());
+        lease.hold_until(unix_now() + 10);
+        assert!(lease.allows_signing());
+        lease.hold_until(unix_now() - 1);
+        assert!(!lease.allows_signing());
+        lease.hold_until(unix_now() + 10);
+        lease.release();
+        assert!(!lease.allows_signing());
+    }
+}
@@ -7,9 +7,10 @@ use crate::http_metrics::metrics;
 use eth2_keystore::Keystore;
 use lockfile::Lockfile;
 use parking_lot::Mutex;
-use reqwest::{header::ACCEPT, Client};
+use reqwest::{header::ACCEPT, Client, StatusCode};
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 use task_executor::TaskExecutor;
 use types::*;
 use url::Url;
@@ -26,11 +27,17 @@ pub enum Error {
         domain: Domain,
     },
     Web3SignerRequestFailed(String),
+    /// The remote signer refused to sign the message due to its own slashing protection.
+    Web3SignerSlashingProtection(String),
     Web3SignerJsonParsingFailed(String),
+    /// The remote signer did not respond within the signing timeout of the message.
+    Web3SignerRequestTimeout(Duration),
     ShuttingDown,
     TokioJoin(String),
     MergeForkNotSupported,
     GenesisForkVersionRequired,
+    /// The validator is watch-only and has no key to sign with.
+    WatchOnly,
 }
 
 /// Enumerates all messages that can be signed by a validator.
@@ -73,6 +80,36 @@ impl<'a, E: EthSpec, Payload: AbstractExecPayload<E>> SignableMessage<'a, E, Pay
     }
 }
 
+/// Timeouts for signing requests to a remote signer, for each type of message with a deadline.
+///
+/// These are separate from, and should be shorter than, the request timeout of the remote signer
+/// client. A timeout of `None` falls back to the request timeout.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SigningTimeouts {
+    pub block: Option<Duration>,
+    pub attestation: Option<Duration>,
+    pub aggregate: Option<Duration>,
+}
+
+impl SigningTimeouts {
+    /// Returns the name of the operation which signs `message` and its timeout, if there is one.
+    fn for_message<E: EthSpec, Payload: AbstractExecPayload<E>>(
+        &self,
+        message: &SignableMessage<E, Payload>,
+    ) -> Option<(&'static str, Duration)> {
+        match message {
+            SignableMessage::BeaconBlock(_) => self.block.map(|t| (metrics::BEACON_BLOCK, t)),
+            SignableMessage::AttestationData(_) => {
+                self.attestation.map(|t| (metrics::ATTESTATIONS, t))
+            }
+            SignableMessage::SignedAggregateAndProof(_) => {
+                self.aggregate.map(|t| (metrics::AGGREGATES, t))
+            }
+            _ => None,
+        }
+    }
+}
+
 /// A method used by a validator to sign messages.
 ///
 /// Presently there is only a single variant, however we expect more variants to arise (e.g.,
@@ -90,9 +127,16 @@ pub enum SigningMethod {
     /// See: https://docs.web3signer.consensys.net/en/latest/
     Web3Signer {
         signing_url: Url,
+        upcheck_url: Url,
         http_client: Client,
         voting_public_key: PublicKey,
+        signing_timeouts: SigningTimeouts,
+        /// A local copy of the voting keypair, used to sign messages when a request to the
+        /// Web3Signer server exceeds its signing timeout.
+        fallback_keypair: Option<Arc<Keypair>>,
     },
+    /// A validator without any keys, which is only used to track duties and performance.
+    WatchOnly { voting_public_key: PublicKey },
 }
 
 /// The additional information used to construct a signature. Mostly used for protection from replay
@@ -128,6 +172,52 @@ impl SigningMethod {
             // Slashing protection is only required for remote signer keys when the configuration
             // dictates that it is desired.
             SigningMethod::Web3Signer { .. } => enable_web3signer_slashing_protection,
+            // Watch-only validators never sign, so they have nothing to protect.
+            SigningMethod::WatchOnly { .. } => false,
+        }
+    }
+
+    /// Returns the origin of the Web3Signer instance used by this signing method, which identifies
+    /// the instance in metrics and health checks.
+    ///
+    /// Returns `None` for local keystores and watch-only validators.
+    pub fn web3signer_origin(&self) -> Option<String> {
+        match self {
+            SigningMethod::LocalKeystore { .. } | SigningMethod::WatchOnly { .. } => None,
+            SigningMethod::Web3Signer { signing_url, .. } => {
+                Some(signing_url.origin().ascii_serialization())
+            }
+        }
+    }
+
+    /// Returns `true` if this is a remote signer with a local fallback keystore.
+    pub fn has_fallback_keypair(&self) -> bool {
+        matches!(
+            self,
+            SigningMethod::Web3Signer {
+                fallback_keypair: Some(_),
+                ..
+            }
+        )
+    }
+
+    /// Query the `upcheck` endpoint of the Web3Signer instance used by this signing method.
+    ///
+    /// Always succeeds for local keystores and watch-only validators.
+    pub async fn upcheck(&self) -> Result<(), Error> {
+        match self {
+            SigningMethod::LocalKeystore { .. } | SigningMethod::WatchOnly { .. } => Ok(()),
+            SigningMethod::Web3Signer {
+                upcheck_url,
+                http_client,
+                ..
+            } => http_client
+                .get(upcheck_url.clone())
+                .send()
+                .await
+                .and_then(|response| response.error_for_status())
+                .map(drop)
+                .map_err(|e| Error::Web3SignerRequestFailed(e.to_string())),
         }
     }
 
@@ -169,27 +259,20 @@ impl SigningMethod {
                 let _timer =
                     metrics::start_timer_vec(&metrics::SIGNING_TIMES, &[metrics::LOCAL_KEYSTORE]);
 
-                let voting_keypair = voting_keypair.clone();
-                // Spawn a blocking task to produce the signature. This avoids blocking the core
-                // tokio executor.
-                let signature = executor
-                    .spawn_blocking_handle(
-                        move || voting_keypair.sk.sign(signing_root),
-                        "local_keystore_signer",
-                    )
-                    .ok_or(Error::ShuttingDown)?
-                    .await
-                    .map_err(|e| Error::TokioJoin(e.to_string()))?;
-                Ok(signature)
+                sign_with_keypair(voting_keypair.clone(), signing_root, executor).await
             }
             SigningMethod::Web3Signer {
                 signing_url,
                 http_client,
+                signing_timeouts,
+                fallback_keypair,
                 ..
             } => {
                 let _timer =
                     metrics::start_timer_vec(&metrics::SIGNING_TIMES, &[metrics::WEB3SIGNER]);
 
+                let signing_timeout = signing_timeouts.for_message(&signable_message);
+
                 // Map the message into a Web3Signer type.
                 let object = match signable_message {
                     SignableMessage::RandaoReveal(epoch) => {
@@ -240,22 +323,95 @@ impl SigningMethod {
                     object,
                 };
 
+                // Requests are tracked per Web3Signer instance, identified by the origin of its
+                // URL. The HTTP client is shared between all validators using the same instance,
+                // so its connections are kept alive and reused across validators.
+                let signer = signing_url.origin().ascii_serialization();
+                let request_timer =
+                    metrics::start_timer_vec(&metrics::WEB3SIGNER_REQUEST_TIMES, &[&signer]);
+
                 // Request a signature from the Web3Signer instance via HTTP(S).
-                let response: SigningResponse = http_client
-                    .post(signing_url.clone())
-                    .header(ACCEPT, "application/json")
-                    .json(&request)
-                    .send()
-                    .await
-                    .map_err(|e| Error::Web3SignerRequestFailed(e.to_string()))?
-                    .error_for_status()
-                    .map_err(|e| Error::Web3SignerRequestFailed(e.to_string()))?
-                    .json()
-                    .await
-                    .map_err(|e| Error::Web3SignerJsonParsingFailed(e.to_string()))?;
-
-                Ok(response.signature)
+                let request_future = async {
+                    let response = http_client
+                        .post(signing_url.clone())
+                        .header(ACCEPT, "application/json")
+                        .json(&request)
+                        .send()
+                        .await
+                        .map_err(|e| Error::Web3SignerRequestFailed(e.to_string()))?;
+
+                    // Web3Signer (and distributed validator middleware implementing its API)
+                    // responds with 412 when its slashing protection prevents signing.
+                    if response.status() == StatusCode::PRECONDITION_FAILED {
+                        let message = response.text().await.unwrap_or_default();
+                        return Err(Error::Web3SignerSlashingProtection(message));
+                    }
+
+                    response
+                        .error_for_status()
+                        .map_err(|e| Error::Web3SignerRequestFailed(e.to_string()))?
+                        .json()
+                        .await
+                        .map_err(|e| Error::Web3SignerJsonParsingFailed(e.to_string()))
+                };
+                let response: Result<SigningResponse, Error> = match signing_timeout {
+                    Some((_, timeout)) => tokio::time::timeout(timeout, request_future)
+                        .await
+                        .unwrap_or(Err(Error::Web3SignerRequestTimeout(timeout))),
+                    None => request_future.await,
+                };
+
+                drop(request_timer);
+                if response.is_err() {
+                    metrics::inc_counter_vec(&metrics::WEB3SIGNER_REQUEST_ERRORS, &[&signer]);
+                }
+
+                let operation = signing_timeout.map_or("other", |(operation, _)| operation);
+                if let Err(Error::Web3SignerRequestTimeout(_)) = &response {
+                    metrics::inc_counter_vec(
+                        &metrics::WEB3SIGNER_SIGNING_TIMEOUTS,
+                        &[&signer, operation],
+                    );
+                }
+
+                // Fall back to the local keystore if the Web3Signer server is unreachable or too
+                // slow, but not if it refused to sign. The signature is deterministic, so signing
+                // the same root locally cannot conflict with a signature which the Web3Signer
+                // server may still produce.
+                if let (
+                    Err(Error::Web3SignerRequestTimeout(_) | Error::Web3SignerRequestFailed(_)),
+                    Some(fallback_keypair),
+                ) = (&response, fallback_keypair)
+                {
+                    metrics::inc_counter_vec(
+                        &metrics::WEB3SIGNER_FALLBACK_SIGNATURES,
+                        &[&signer, operation],
+                    );
+                    return sign_with_keypair(fallback_keypair.clone(), signing_root, executor)
+                        .await;
+                }
+
+                Ok(response?.signature)
             }
+            SigningMethod::WatchOnly { .. } => Err(Error::WatchOnly),
         }
     }
 }
+
+/// Signs `signing_root` with `keypair`.
+async fn sign_with_keypair(
+    keypair: Arc<Keypair>,
+    signing_root: Hash256,
+    executor: &TaskExecutor,
+) -> Result<Signature, Error> {
+    // Spawn a blocking task to produce the signature. This avoids blocking the core tokio
+    // executor.
+    executor
+        .spawn_blocking_handle(
+            move || keypair.sk.sign(signing_root),
+            "local_keystore_signer",
+        )
+        .ok_or(Error::ShuttingDown)?
+        .await
+        .map_err(|e| Error::TokioJoin(e.to_string()))
+}
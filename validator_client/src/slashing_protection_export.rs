@@ -0,0 +1,157 @@
+//! Writes the slashing protection database to an EIP-3076 interchange file periodically and when
+//! the validator client shuts down, so that a recent portable copy is always available for
+//! migrations and disaster recovery.
+
+use crate::http_metrics::metrics;
+use crate::validator_store::ValidatorStore;
+use account_utils::write_file_via_temporary;
+use environment::RuntimeContext;
+use slog::{debug, error, Logger};
+use slot_clock::SlotClock;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::sleep;
+use types::EthSpec;
+
+/// The number of previous exports kept alongside the latest, as `<path>.1`, `<path>.2`, etc.
+pub const EXPORT_ROTATION_COUNT: usize = 3;
+
+/// Starts a service which exports the slashing protection database to `path` once every
+/// `interval`, and once more when the validator client shuts down.
+pub fn start_slashing_protection_export_service<T: SlotClock + 'static, E: EthSpec>(
+    context: RuntimeContext<E>,
+    validator_store: Arc<ValidatorStore<T, E>>,
+    path: PathBuf,
+    interval: Duration,
+) {
+    let log = context.log().clone();
+    let executor = context.executor.clone();
+
+    let future = async move {
+        let exit = executor.exit();
+        tokio::pin!(exit);
+
+        loop {
+            tokio::select! {
+                () = sleep(interval) => {
+                    let validator_store = validator_store.clone();
+                    let path = path.clone();
+                    let log = log.clone();
+                    let export = move || export_and_log(&validator_store, &path, &log);
+                    if let Some(handle) =
+                        executor.spawn_blocking_handle(export, "slashing_protection_export")
+                    {
+                        let _ = handle.await;
+                    }
+                }
+                () = &mut exit => {
+                    // Export without yielding, so the runtime shutting down can't interrupt it.
+                    export_and_log(&validator_store, &path, &log);
+                    return;
+                }
+            }
+        }
+    };
+
+    // The exit signal is handled above, so that the final export isn't cancelled.
+    context
+        .executor
+        .spawn_without_exit(future, "slashing_protection_export");
+}
+
+fn export_and_log<T: SlotClock + 'static, E: EthSpec>(
+    validator_store: &ValidatorStore<T, E>,
+    path: &Path,
+    log: &Logger,
+) {
+    let _timer = metrics::start_timer(&metrics::SLASHING_PROTECTION_EXPORT_TIMES);
+    match export_slashing_protection(validator_store, path) {
+        Ok(()) => debug!(
+            log,
+            "Exported slashing protection database";
+            "path" => %path.display(),
+        ),
+        Err(e) => {
+            metrics::inc_counter(&metrics::SLASHING_PROTECTION_EXPORT_ERRORS);
+            error!(
+                log,
+                "Failed to export slashing protection database";
+                "error" => e,
+                "path" => %path.display(),
+            );
+        }
+    }
+}
+
+/// Export the slashing protection database to `path`, rotating previous exports.
+///
+/// The export is written to a temporary file first so that `path` never holds a partial export,
+/// and is only readable by the user running the validator client.
+fn export_slashing_protection<T: SlotClock + 'static, E: EthSpec>(
+    validator_store: &ValidatorStore<T, E>,
+    path: &Path,
+) -> Result<(), String> {
+    let interchange = validator_store
+        .export_slashing_protection()
+        .map_err(|e| format!("Unable to export interchange: {:?}", e))?;
+    let mut bytes = vec![];
+    interchange
+        .write_to(&mut bytes)
+        .map_err(|e| format!("Unable to encode export: {:?}", e))?;
+
+    rotate_exports(path, EXPORT_ROTATION_COUNT)
+        .map_err(|e| format!("Unable to rotate previous exports: {:?}", e))?;
+    write_file_via_temporary(path, &suffixed_path(path, "tmp"), &bytes)
+        .map_err(|e| format!("Unable to write export file: {:?}", e))
+}
+
+/// Shift the export at `path` and up to `count` previous exports along by one, discarding the
+/// oldest.
+fn rotate_exports(path: &Path, count: usize) -> std::io::Result<()> {
+    for i in (1..=count).rev() {
+        let from = if i == 1 {
+            path.to_path_buf()
+        } else {
+            suffixed_path(path, &(i - 1).to_string())
+        };
+        if from.exists() {
+            fs::rename(from, suffixed_path(path, &i.to_string()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns `path` with `.{suffix}` appended to its file name.
+fn suffixed_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = OsString::from(path);
+    path.push(".");
+    path.push(suffix);
+    path.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn rotate_exports_discards_oldest() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("interchange.json");
+        let count = 2;
+
+        for i in 0..4 {
+            rotate_exports(&path, count).unwrap();
+            fs::write(&path, i.to_string()).unwrap();
+        }
+
+        let read = |path: &Path| fs::read_to_string(path).unwrap();
+        assert_eq!(read(&path), "3");
+        assert_eq!(read(&suffixed_path(&path, "1")), "2");
+        assert_eq!(read(&suffixed_path(&path, "2")), "1");
+        assert!(!suffixed_path(&path, "3").exists());
+    }
+}
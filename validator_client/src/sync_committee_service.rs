@@ -1,4 +1,4 @@
-use crate::beacon_node_fallback::{ApiTopic, BeaconNodeFallback, RequireSynced};
+use crate::beacon_node_fallback::{ApiTopic, BeaconNodeFallback, RequestClass, RequireSynced};
 use crate::{
     duties_service::DutiesService,
     validator_store::{Error as ValidatorStoreError, ValidatorStore},
@@ -8,6 +8,7 @@ use environment::RuntimeContext;
 use eth2::types::BlockId;
 use futures::future::join_all;
 use futures::future::FutureExt;
+use rand::Rng;
 use slog::{crit, debug, error, info, trace, warn};
 use slot_clock::SlotClock;
 use std::collections::HashMap;
@@ -22,6 +23,13 @@ use types::{
 
 pub const SUBSCRIPTION_LOOKAHEAD_EPOCHS: u64 = 4;
 
+/// The number of batches that sync committee messages are split into when they are jittered.
+const JITTER_BATCHES: usize = 4;
+
+/// The jitter is capped at this fraction of a slot, so that jittered messages still reach the
+/// aggregators before they aggregate at 2/3 of the way through the slot.
+const MAX_JITTER_SLOT_FRACTION: u32 = 6;
+
 pub struct SyncCommitteeService<T: SlotClock + 'static, E: EthSpec> {
     inner: Arc<Inner<T, E>>,
 }
@@ -52,6 +60,9 @@ pub struct Inner<T: SlotClock + 'static, E: EthSpec> {
     ///
     /// This acts as a latch that fires once upon start-up, and then never again.
     first_subscription_done: AtomicBool,
+    /// The maximum random delay applied to sync committee messages, contributions and
+    /// subscriptions, to avoid bursts of requests to the beacon node.
+    jitter: Option<Duration>,
 }
 
 impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
@@ -61,7 +72,13 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
         slot_clock: T,
         beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
         context: RuntimeContext<E>,
+        jitter: Option<Duration>,
     ) -> Self {
+        let max_jitter =
+            Duration::from_secs(duties_service.spec.seconds_per_slot) / MAX_JITTER_SLOT_FRACTION;
+        let jitter = jitter
+            .filter(|jitter| !jitter.is_zero())
+            .map(|jitter| jitter.min(max_jitter));
         Self {
             inner: Arc::new(Inner {
                 duties_service,
@@ -70,10 +87,18 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
                 beacon_nodes,
                 context,
                 first_subscription_done: AtomicBool::new(false),
+                jitter,
             }),
         }
     }
 
+    /// Returns a random delay of up to the configured jitter, or zero if jitter is disabled.
+    fn random_jitter(&self) -> Duration {
+        self.jitter.map_or(Duration::ZERO, |jitter| {
+            rand::thread_rng().gen_range(Duration::ZERO..jitter)
+        })
+    }
+
     /// Check if the Altair fork has been activated and therefore sync duties should be performed.
     ///
     /// Slot clock errors are mapped to `false`.
@@ -109,7 +134,12 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
                 if let Some(duration_to_next_slot) = self.slot_clock.duration_to_next_slot() {
                     // Wait for contribution broadcast interval 1/3 of the way through the slot.
                     let log = self.context.log();
-                    sleep(duration_to_next_slot + slot_duration / 3).await;
+                    sleep(
+                        duration_to_next_slot
+                            + slot_duration / 3
+                            + self.duties_service.distributed_duty_delay,
+                    )
+                    .await;
 
                     // Do nothing if the Altair fork has not yet occurred.
                     if !self.altair_fork_activated() {
@@ -211,18 +241,30 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
             }
         };
 
-        // Spawn one task to publish all of the sync committee signatures.
+        // Spawn one task to publish all of the sync committee signatures, or several if they
+        // are jittered so that they are spread over the jitter window.
         let validator_duties = slot_duties.duties;
-        let service = self.clone();
-        self.inner.context.executor.spawn(
-            async move {
-                service
-                    .publish_sync_committee_signatures(slot, block_root, validator_duties)
-                    .map(|_| ())
-                    .await
-            },
-            "sync_committee_signature_publish",
-        );
+        let num_batches = if self.jitter.is_some() {
+            JITTER_BATCHES
+        } else {
+            1
+        };
+        let batch_size = validator_duties.len().div_ceil(num_batches);
+        for batch in validator_duties.chunks(batch_size) {
+            let batch = batch.to_vec();
+            let delay = self.random_jitter();
+            let service = self.clone();
+            self.inner.context.executor.spawn(
+                async move {
+                    sleep(delay).await;
+                    service
+                        .publish_sync_committee_signatures(slot, block_root, batch)
+                        .map(|_| ())
+                        .await
+                },
+                "sync_committee_signature_publish",
+            );
+        }
 
         let aggregators = slot_duties.aggregators;
         let service = self.clone();
@@ -278,6 +320,48 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
                     );
                     None
                 }
+                Err(ValidatorStoreError::Suspended(pubkey)) => {
+                    debug!(
+                        log,
+                        "Not signing sync committee signature";
+                        "reason" => "validator suspended",
+                        "pubkey" => ?pubkey,
+                        "slot" => slot,
+                    );
+                    None
+                }
+                Err(ValidatorStoreError::WatchOnly(pubkey)) => {
+                    debug!(
+                        log,
+                        "Not signing sync committee signature";
+                        "reason" => "validator is watch-only",
+                        "pubkey" => ?pubkey,
+                        "slot" => slot,
+                    );
+                    None
+                }
+                Err(ValidatorStoreError::SigningLeaseNotHeld(pubkey)) => {
+                    debug!(
+                        log,
+                        "Not signing sync committee signature";
+                        "reason" => "signing lease held by another instance",
+                        "pubkey" => ?pubkey,
+                        "slot" => slot,
+                    );
+                    None
+                }
+                // In a distributed validator cluster the signer may fail to produce some
+                // signatures, e.g. if a threshold of the cluster could not be reached.
+                Err(e) if self.duties_service.distributed => {
+                    warn!(
+                        log,
+                        "Failed to sign sync committee signature";
+                        "validator_index" => duty.validator_index,
+                        "slot" => slot,
+                        "error" => ?e,
+                    );
+                    None
+                }
                 Err(e) => {
                     crit!(
                         log,
@@ -339,6 +423,7 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
     ) {
         for (subnet_id, subnet_aggregators) in aggregators {
             let service = self.clone();
+            let aggregate_instant = aggregate_instant + self.random_jitter();
             self.inner.context.executor.spawn(
                 async move {
                     service
@@ -431,6 +516,16 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
                         );
                         None
                     }
+                    Err(ValidatorStoreError::Suspended(pubkey)) => {
+                        debug!(
+                            log,
+                            "Not signing sync contribution";
+                            "reason" => "validator suspended",
+                            "pubkey" => ?pubkey,
+                            "slot" => slot,
+                        );
+                        None
+                    }
                     Err(e) => {
                         crit!(
                             log,
@@ -453,7 +548,8 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
 
         // Publish to the beacon node.
         self.beacon_nodes
-            .first_success(
+            .first_success_for(
+                RequestClass::SyncCommittee,
                 RequireSynced::No,
                 OfflineOnFailure::Yes,
                 |beacon_node| async move {
@@ -487,8 +583,10 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
     fn spawn_subscription_tasks(&self) {
         let service = self.clone();
         let log = self.context.log().clone();
+        let delay = self.random_jitter();
         self.inner.context.executor.spawn(
             async move {
+                sleep(delay).await;
                 service.publish_subscriptions().await.unwrap_or_else(|e| {
                     error!(
                         log,
@@ -1,29 +1,38 @@
 use crate::{
     doppelganger_service::DoppelgangerService,
+    fee_recipient_file::FeeRecipientFile,
     http_metrics::metrics,
     initialized_validators::InitializedValidators,
+    signer_health_service::SignerHealth,
+    signing_lease::SigningLease,
     signing_method::{Error as SigningError, SignableMessage, SigningContext, SigningMethod},
     Config,
 };
 use account_utils::validator_definitions::{PasswordStorage, ValidatorDefinition};
+use eth2::lighthouse_vc::types::{DoppelgangerOverride, DoppelgangerStatusData};
+use futures::future::join_all;
 use parking_lot::{Mutex, RwLock};
 use slashing_protection::{
     interchange::Interchange, InterchangeError, NotSafe, Safe, SlashingDatabase,
+    DEFAULT_RETENTION_EPOCHS,
 };
-use slog::{crit, error, info, warn, Logger};
+use slog::{crit, debug, error, info, warn, Logger};
 use slot_clock::SlotClock;
+use std::collections::HashMap;
 use std::marker::PhantomData;
 use std::path::Path;
 use std::sync::Arc;
 use task_executor::TaskExecutor;
+use tokio::sync::Notify;
 use types::{
     attestation::Error as AttestationError, graffiti::GraffitiString, AbstractExecPayload, Address,
-    AggregateAndProof, Attestation, BeaconBlock, BlindedPayload, ChainSpec, ContributionAndProof,
-    Domain, Epoch, EthSpec, Fork, ForkName, Graffiti, Hash256, PublicKeyBytes, SelectionProof,
-    Signature, SignedAggregateAndProof, SignedBeaconBlock, SignedContributionAndProof, SignedRoot,
-    SignedValidatorRegistrationData, SignedVoluntaryExit, Slot, SyncAggregatorSelectionData,
-    SyncCommitteeContribution, SyncCommitteeMessage, SyncSelectionProof, SyncSubnetId,
-    ValidatorRegistrationData, VoluntaryExit,
+    AggregateAndProof, Attestation, AttestationData, BeaconBlock, BeaconBlockHeader,
+    BlindedPayload, BlsToExecutionChange, ChainSpec, ContributionAndProof, Domain, Epoch, EthSpec,
+    Fork, Graffiti, Hash256, Keypair, PublicKeyBytes, SelectionProof, Signature,
+    SignedAggregateAndProof, SignedBeaconBlock, SignedBlsToExecutionChange,
+    SignedContributionAndProof, SignedRoot, SignedValidatorRegistrationData, SignedVoluntaryExit,
+    Slot, SyncAggregatorSelectionData, SyncCommitteeContribution, SyncCommitteeMessage,
+    SyncSelectionProof, SyncSubnetId, ValidatorRegistrationData, VoluntaryExit,
 };
 
 pub use crate::doppelganger_service::DoppelgangerStatus;
@@ -36,15 +45,38 @@ pub enum Error {
     UnknownPubkey(PublicKeyBytes),
     Slashable(NotSafe),
     SameData,
-    GreaterThanCurrentSlot { slot: Slot, current_slot: Slot },
-    GreaterThanCurrentEpoch { epoch: Epoch, current_epoch: Epoch },
+    GreaterThanCurrentSlot {
+        slot: Slot,
+        current_slot: Slot,
+    },
+    GreaterThanCurrentEpoch {
+        epoch: Epoch,
+        current_epoch: Epoch,
+    },
     UnableToSignAttestation(AttestationError),
     UnableToSign(SigningError),
+    /// The remote signer declined to sign due to its own slashing protection.
+    RemoteSlashingProtection(String),
+    /// The remote signer of the validator is failing health checks.
+    SignerQuarantined(PublicKeyBytes),
+    /// The validator has been handed over to another validator client.
+    HandedOver(PublicKeyBytes),
+    /// The validator is suspended and must not sign.
+    Suspended(PublicKeyBytes),
+    /// The validator is watch-only and has no key to sign with.
+    WatchOnly(PublicKeyBytes),
+    /// Another validator client holds the signing lease.
+    SigningLeaseNotHeld(PublicKeyBytes),
 }
 
 impl From<SigningError> for Error {
     fn from(e: SigningError) -> Self {
-        Error::UnableToSign(e)
+        match e {
+            SigningError::Web3SignerSlashingProtection(message) => {
+                Error::RemoteSlashingProtection(message)
+            }
+            e => Error::UnableToSign(e),
+        }
     }
 }
 
@@ -68,12 +100,17 @@ pub struct ValidatorStore<T, E: EthSpec> {
     doppelganger_service: Option<Arc<DoppelgangerService>>,
     slot_clock: T,
     fee_recipient_process: Option<Address>,
+    fee_recipient_file: Option<RwLock<FeeRecipientFile>>,
     gas_limit: Option<u64>,
     builder_proposals: bool,
     enable_web3signer_slashing_protection: bool,
     prefer_builder_proposals: bool,
     builder_boost_factor: Option<u64>,
     task_executor: TaskExecutor,
+    signer_health: SignerHealth,
+    signing_lease: SigningLease,
+    /// Signalled when a fee recipient, gas limit or builder setting may have changed.
+    proposal_data_changed: Notify,
     _phantom: PhantomData<E>,
 }
 
@@ -102,12 +139,16 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             doppelganger_service,
             slot_clock,
             fee_recipient_process: config.fee_recipient,
+            fee_recipient_file: config.fee_recipient_file.clone().map(RwLock::new),
             gas_limit: config.gas_limit,
             builder_proposals: config.builder_proposals,
             enable_web3signer_slashing_protection: config.enable_web3signer_slashing_protection,
             prefer_builder_proposals: config.prefer_builder_proposals,
             builder_boost_factor: config.builder_boost_factor,
             task_executor,
+            signer_health: <_>::default(),
+            signing_lease: SigningLease::new(config.signing_lease_file.is_some()),
+            proposal_data_changed: Notify::new(),
             _phantom: PhantomData,
         }
     }
@@ -115,10 +156,15 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
     /// Register all local validators in doppelganger protection to try and prevent instances of
     /// duplicate validators operating on the network at the same time.
     ///
-    /// This function has no effect if doppelganger protection is disabled.
+    /// This function has no effect if doppelganger protection is disabled. Watch-only validators
+    /// are not registered, since their keys are expected to be live elsewhere.
     pub fn register_all_in_doppelganger_protection_if_enabled(&self) -> Result<(), String> {
         if let Some(doppelganger_service) = &self.doppelganger_service {
-            for pubkey in self.validators.read().iter_voting_pubkeys() {
+            let validators = self.validators.read();
+            for pubkey in validators.iter_voting_pubkeys() {
+                if validators.is_watch_only(pubkey) {
+                    continue;
+                }
                 doppelganger_service.register_new_validator::<E, _>(*pubkey, &self.slot_clock)?
             }
         }
@@ -131,6 +177,35 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         self.doppelganger_service.is_some()
     }
 
+    /// Returns the doppelganger detection state of all validators, or `None` if doppelganger
+    /// protection is disabled.
+    pub fn doppelganger_status_data(&self) -> Option<Vec<DoppelgangerStatusData>> {
+        self.doppelganger_service
+            .as_ref()
+            .map(|service| service.status_data())
+    }
+
+    /// Apply an operator override to the doppelganger detection state of `validator_pubkey`.
+    pub fn override_doppelganger_protection(
+        &self,
+        validator_pubkey: PublicKeyBytes,
+        action: DoppelgangerOverride,
+    ) -> Result<(), String> {
+        let doppelganger_service = self
+            .doppelganger_service
+            .as_ref()
+            .ok_or_else(|| "doppelganger protection is not enabled".to_string())?;
+
+        match action {
+            DoppelgangerOverride::Release => {
+                doppelganger_service.release_validator(validator_pubkey)
+            }
+            DoppelgangerOverride::Rearm => {
+                doppelganger_service.rearm_validator::<E, _>(validator_pubkey, &self.slot_clock)
+            }
+        }
+    }
+
     pub fn initialized_validators(&self) -> Arc<RwLock<InitializedValidators>> {
         self.validators.clone()
     }
@@ -195,8 +270,10 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             .map_err(|e| format!("failed to register validator: {:?}", e))?;
 
         if let Some(doppelganger_service) = &self.doppelganger_service {
-            doppelganger_service
-                .register_new_validator::<E, _>(validator_pubkey, &self.slot_clock)?;
+            if !validator_def.signing_definition.is_watch_only() {
+                doppelganger_service
+                    .register_new_validator::<E, _>(validator_pubkey, &self.slot_clock)?;
+            }
         }
 
         self.validators
@@ -218,7 +295,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             .map(|validator| ProposalData {
                 validator_index: validator.get_index(),
                 fee_recipient: self
-                    .get_fee_recipient_defaulting(validator.get_suggested_fee_recipient()),
+                    .get_fee_recipient_defaulting(pubkey, validator.get_suggested_fee_recipient()),
                 gas_limit: self.get_gas_limit_defaulting(validator.get_gas_limit()),
                 builder_proposals: self
                     .get_builder_proposals_defaulting(validator.get_builder_proposals()),
@@ -235,7 +312,10 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         self.validators.read().get_index(pubkey)
     }
 
-    /// Returns all voting pubkeys for all enabled validators.
+    /// Returns all voting pubkeys for all enabled validators which are not suspended.
+    ///
+    /// Watch-only validators, and all validators whilst another instance holds the signing lease,
+    /// are reported as `SigningDisabled`, so that their duties are collected but never performed.
     ///
     /// The `filter_func` allows for filtering pubkeys based upon their `DoppelgangerStatus`. There
     /// are two primary functions used here:
@@ -252,22 +332,18 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
     {
         // Collect all the pubkeys first to avoid interleaving locks on `self.validators` and
         // `self.doppelganger_service()`.
-        let pubkeys = self
-            .validators
-            .read()
-            .iter_voting_pubkeys()
-            .cloned()
-            .collect::<Vec<_>>();
+        let pubkeys = {
+            let validators = self.validators.read();
+            validators
+                .iter_voting_pubkeys()
+                .filter(|pubkey| !validators.is_suspended(pubkey))
+                .map(|pubkey| (*pubkey, validators.is_watch_only(pubkey)))
+                .collect::<Vec<_>>()
+        };
 
         pubkeys
             .into_iter()
-            .map(|pubkey| {
-                self.doppelganger_service
-                    .as_ref()
-                    .map(|doppelganger_service| doppelganger_service.validator_status(pubkey))
-                    // Allow signing on all pubkeys if doppelganger protection is disabled.
-                    .unwrap_or_else(|| DoppelgangerStatus::SigningEnabled(pubkey))
-            })
+            .map(|(pubkey, watch_only)| self.signing_status(pubkey, watch_only))
             .filter_map(filter_func)
             .collect()
     }
@@ -277,25 +353,33 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
     pub fn doppelganger_statuses(&self) -> Vec<DoppelgangerStatus> {
         // Collect all the pubkeys first to avoid interleaving locks on `self.validators` and
         // `self.doppelganger_service`.
-        let pubkeys = self
-            .validators
-            .read()
-            .iter_voting_pubkeys()
-            .cloned()
-            .collect::<Vec<_>>();
+        let pubkeys = {
+            let validators = self.validators.read();
+            validators
+                .iter_voting_pubkeys()
+                .map(|pubkey| (*pubkey, validators.is_watch_only(pubkey)))
+                .collect::<Vec<_>>()
+        };
 
         pubkeys
             .into_iter()
-            .map(|pubkey| {
-                self.doppelganger_service
-                    .as_ref()
-                    .map(|doppelganger_service| doppelganger_service.validator_status(pubkey))
-                    // Allow signing on all pubkeys if doppelganger protection is disabled.
-                    .unwrap_or_else(|| DoppelgangerStatus::SigningEnabled(pubkey))
-            })
+            .map(|(pubkey, watch_only)| self.signing_status(pubkey, watch_only))
             .collect()
     }
 
+    /// Returns the `DoppelgangerStatus` of `pubkey`, treating watch-only validators and validators
+    /// without the signing lease as never safe to sign.
+    fn signing_status(&self, pubkey: PublicKeyBytes, watch_only: bool) -> DoppelgangerStatus {
+        if watch_only || !self.signing_lease.allows_signing() {
+            return DoppelgangerStatus::SigningDisabled(pubkey);
+        }
+        self.doppelganger_service
+            .as_ref()
+            .map(|doppelganger_service| doppelganger_service.validator_status(pubkey))
+            // Allow signing on all pubkeys if doppelganger protection is disabled.
+            .unwrap_or_else(|| DoppelgangerStatus::SigningEnabled(pubkey))
+    }
+
     /// Check if the `validator_pubkey` is permitted by the doppleganger protection to sign
     /// messages.
     pub fn doppelganger_protection_allows_signing(&self, validator_pubkey: PublicKeyBytes) -> bool {
@@ -311,6 +395,11 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             })
     }
 
+    /// Returns `true` if the validator with `validator_pubkey` is watch-only.
+    pub fn is_watch_only(&self, validator_pubkey: &PublicKeyBytes) -> bool {
+        self.validators.read().is_watch_only(validator_pubkey)
+    }
+
     pub fn num_voting_validators(&self) -> usize {
         self.validators.read().num_enabled()
     }
@@ -325,11 +414,13 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         &self,
         validator_pubkey: PublicKeyBytes,
     ) -> Result<Arc<SigningMethod>, Error> {
+        // Watch-only validators are unknown to doppelganger protection, so check them first to
+        // return a more useful error.
+        if self.validators.read().is_watch_only(&validator_pubkey) {
+            return Err(Error::WatchOnly(validator_pubkey));
+        }
         if self.doppelganger_protection_allows_signing(validator_pubkey) {
-            self.validators
-                .read()
-                .signing_method(&validator_pubkey)
-                .ok_or(Error::UnknownPubkey(validator_pubkey))
+            self.doppelganger_bypassed_signing_method(validator_pubkey)
         } else {
             Err(Error::DoppelgangerProtected(validator_pubkey))
         }
@@ -345,42 +436,137 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         &self,
         validator_pubkey: PublicKeyBytes,
     ) -> Result<Arc<SigningMethod>, Error> {
+        let signing_method = {
+            let validators = self.validators.read();
+            if validators.is_suspended(&validator_pubkey) {
+                return Err(Error::Suspended(validator_pubkey));
+            }
+            if validators.is_watch_only(&validator_pubkey) {
+                return Err(Error::WatchOnly(validator_pubkey));
+            }
+            if !self.signing_lease.allows_signing() {
+                return Err(Error::SigningLeaseNotHeld(validator_pubkey));
+            }
+            validators
+                .signing_method(&validator_pubkey)
+                .ok_or(Error::UnknownPubkey(validator_pubkey))?
+        };
+
+        // Fail fast rather than waiting on a remote signer which is known to be unhealthy, unless
+        // the validator has a fallback keystore to sign with instead.
+        if !signing_method.has_fallback_keypair()
+            && signing_method
+                .web3signer_origin()
+                .map_or(false, |origin| self.signer_health.is_quarantined(&origin))
+        {
+            return Err(Error::SignerQuarantined(validator_pubkey));
+        }
+
+        Ok(signing_method)
+    }
+
+    /// Returns an error if `validator_pubkey` has been handed over to another validator client at
+    /// or before `epoch`, in which case it must not sign blocks or attestations for `epoch`.
+    fn check_handover(&self, validator_pubkey: PublicKeyBytes, epoch: Epoch) -> Result<(), Error> {
+        let handover_epoch = self.validators.read().handover_epoch(&validator_pubkey);
+        if handover_epoch.map_or(false, |handover_epoch| epoch >= handover_epoch) {
+            Err(Error::HandedOver(validator_pubkey))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Returns `true` if `validator_pubkey` uses a remote signer which is quarantined due to
+    /// failing health checks.
+    pub fn signer_quarantined(&self, validator_pubkey: &PublicKeyBytes) -> bool {
         self.validators
             .read()
-            .signing_method(&validator_pubkey)
-            .ok_or(Error::UnknownPubkey(validator_pubkey))
+            .signing_method(validator_pubkey)
+            .and_then(|signing_method| signing_method.web3signer_origin())
+            .map_or(false, |origin| self.signer_health.is_quarantined(&origin))
     }
 
-    fn signing_context(&self, domain: Domain, signing_epoch: Epoch) -> SigningContext {
-        if domain == Domain::VoluntaryExit {
-            match self.spec.fork_name_at_epoch(signing_epoch) {
-                ForkName::Base | ForkName::Altair | ForkName::Bellatrix | ForkName::Capella => {
-                    SigningContext {
-                        domain,
-                        epoch: signing_epoch,
-                        fork: self.fork(signing_epoch),
-                        genesis_validators_root: self.genesis_validators_root,
-                    }
-                }
-                // EIP-7044
-                ForkName::Deneb | ForkName::Electra => SigningContext {
-                    domain,
-                    epoch: signing_epoch,
-                    fork: Fork {
-                        previous_version: self.spec.capella_fork_version,
-                        current_version: self.spec.capella_fork_version,
-                        epoch: signing_epoch,
-                    },
-                    genesis_validators_root: self.genesis_validators_root,
-                },
+    /// Returns whether this instance holds the signing lease, if one is required.
+    pub fn signing_lease(&self) -> &SigningLease {
+        &self.signing_lease
+    }
+
+    /// Returns the health of the remote signers used by local validators.
+    pub fn signer_health(&self) -> &SignerHealth {
+        &self.signer_health
+    }
+
+    /// Returns a map from the origin of each Web3Signer instance to one of the validators which
+    /// uses it.
+    pub fn web3signer_validators(&self) -> HashMap<String, PublicKeyBytes> {
+        let validators = self.validators.read();
+        let mut signers = HashMap::new();
+        for pubkey in validators.iter_voting_pubkeys() {
+            if let Some(origin) = validators
+                .signing_method(pubkey)
+                .and_then(|signing_method| signing_method.web3signer_origin())
+            {
+                signers.entry(origin).or_insert(*pubkey);
             }
+        }
+        signers
+    }
+
+    /// Check that the remote signer of `validator_pubkey` is up and able to sign.
+    ///
+    /// The signer is asked for a selection proof for the genesis slot, which is never useful and
+    /// is not slashable. The signature is verified to ensure the signer is using the correct key.
+    /// Quarantined signers are checked, so that they can be released.
+    pub async fn check_remote_signer(
+        &self,
+        validator_pubkey: PublicKeyBytes,
+    ) -> Result<(), String> {
+        let signing_method = self
+            .validators
+            .read()
+            .signing_method(&validator_pubkey)
+            .ok_or_else(|| format!("unknown pubkey: {:?}", validator_pubkey))?;
+        let pubkey = validator_pubkey
+            .decompress()
+            .map_err(|e| format!("invalid pubkey: {:?}", e))?;
+
+        signing_method
+            .upcheck()
+            .await
+            .map_err(|e| format!("upcheck failed: {:?}", e))?;
+
+        let slot = Slot::new(0);
+        let signing_context =
+            self.signing_context(Domain::SelectionProof, slot.epoch(E::slots_per_epoch()));
+        let signing_root = slot.signing_root(signing_context.domain_hash(&self.spec));
+        let signature = signing_method
+            .get_signature::<E, BlindedPayload<E>>(
+                SignableMessage::SelectionProof(slot),
+                signing_context,
+                &self.spec,
+                &self.task_executor,
+            )
+            .await
+            .map_err(|e| format!("test signing failed: {:?}", e))?;
+
+        if signature.verify(&pubkey, signing_root) {
+            Ok(())
         } else {
-            SigningContext {
-                domain,
-                epoch: signing_epoch,
-                fork: self.fork(signing_epoch),
-                genesis_validators_root: self.genesis_validators_root,
-            }
+            Err("test signature is invalid".to_string())
+        }
+    }
+
+    fn signing_context(&self, domain: Domain, signing_epoch: Epoch) -> SigningContext {
+        let fork = self.fork(signing_epoch);
+        SigningContext {
+            domain,
+            epoch: signing_epoch,
+            fork: if domain == Domain::VoluntaryExit {
+                VoluntaryExit::signing_fork(&fork, &self.spec)
+            } else {
+                fork
+            },
+            genesis_validators_root: self.genesis_validators_root,
         }
     }
 
@@ -411,16 +597,71 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
     /// Returns the fee recipient for the given public key. The priority order for fetching
     /// the fee recipient is:
     /// 1. validator_definitions.yml
-    /// 2. process level fee recipient
+    /// 2. fee recipient file
+    /// 3. process level fee recipient
     pub fn get_fee_recipient(&self, validator_pubkey: &PublicKeyBytes) -> Option<Address> {
         // If there is a `suggested_fee_recipient` in the validator definitions yaml
         // file, use that value.
-        self.get_fee_recipient_defaulting(self.suggested_fee_recipient(validator_pubkey))
+        self.get_fee_recipient_defaulting(
+            validator_pubkey,
+            self.suggested_fee_recipient(validator_pubkey),
+        )
     }
 
-    pub fn get_fee_recipient_defaulting(&self, fee_recipient: Option<Address>) -> Option<Address> {
-        // If there's nothing in the file, try the process-level default value.
-        fee_recipient.or(self.fee_recipient_process)
+    pub fn get_fee_recipient_defaulting(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        fee_recipient: Option<Address>,
+    ) -> Option<Address> {
+        fee_recipient
+            // If there's nothing in the definitions, try the fee recipient file.
+            .or_else(|| {
+                self.fee_recipient_file
+                    .as_ref()
+                    .and_then(|file| file.read().get_fee_recipient(validator_pubkey))
+            })
+            // If there's nothing in the file, try the process-level default value.
+            .or(self.fee_recipient_process)
+    }
+
+    /// Returns a copy of the currently loaded fee recipient file, if one is configured.
+    pub fn fee_recipient_file(&self) -> Option<FeeRecipientFile> {
+        self.fee_recipient_file
+            .as_ref()
+            .map(|file| file.read().clone())
+    }
+
+    /// Atomically replaces the loaded fee recipient file with `fee_recipient_file`, returning the
+    /// number of entries which changed.
+    ///
+    /// Has no effect if no fee recipient file is configured.
+    pub fn update_fee_recipient_file(&self, fee_recipient_file: FeeRecipientFile) -> usize {
+        let Some(file) = &self.fee_recipient_file else {
+            return 0;
+        };
+        let mut file = file.write();
+        let changed = file.num_changed_entries(&fee_recipient_file);
+        *file = fee_recipient_file;
+        drop(file);
+        if changed > 0 {
+            self.notify_proposal_data_changed();
+        }
+        changed
+    }
+
+    /// Signals that the fee recipient, gas limit or builder settings of some validators may have
+    /// changed, so that their builder registrations are refreshed without waiting for the next
+    /// slot.
+    pub fn notify_proposal_data_changed(&self) {
+        self.proposal_data_changed.notify_one();
+    }
+
+    /// Waits for a call to `notify_proposal_data_changed`.
+    ///
+    /// A notification sent while nobody is waiting is kept for the next waiter, so this should
+    /// only be awaited by a single service.
+    pub async fn proposal_data_changed(&self) {
+        self.proposal_data_changed.notified().await
     }
 
     /// Returns the suggested_fee_recipient from `validator_definitions.yml` if any.
@@ -570,6 +811,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         }
 
         let signing_epoch = block.epoch();
+        self.check_handover(validator_pubkey, signing_epoch)?;
         let signing_context = self.signing_context(Domain::BeaconProposer, signing_epoch);
         let domain_hash = signing_context.domain_hash(&self.spec);
 
@@ -640,36 +882,88 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         attestation: &mut Attestation<E>,
         current_epoch: Epoch,
     ) -> Result<(), Error> {
-        // Make sure the target epoch is not higher than the current epoch to avoid potential attacks.
-        if attestation.data().target.epoch > current_epoch {
-            return Err(Error::GreaterThanCurrentEpoch {
-                epoch: attestation.data().target.epoch,
-                current_epoch,
-            });
-        }
+        self.sign_attestations(
+            vec![(validator_pubkey, validator_committee_position, attestation)],
+            current_epoch,
+        )
+        .await
+        .pop()
+        // Unreachable, since there is one result per attestation. Refuse to sign regardless.
+        .unwrap_or(Err(Error::Slashable(NotSafe::ConsistencyError)))
+    }
 
-        // Get the signing method and check doppelganger protection.
-        let signing_method = self.doppelganger_checked_signing_method(validator_pubkey)?;
+    /// Signs a batch of attestations, given as the validator's public key, its position in the
+    /// committee and the attestation to add its signature to.
+    ///
+    /// All attestations which require local slashing protection are checked and recorded in a
+    /// single database transaction, so that the validators attesting in the same slot don't each
+    /// wait for the write lock in turn. Returns one result per attestation, in the same order.
+    pub async fn sign_attestations(
+        &self,
+        mut attestations: Vec<(PublicKeyBytes, usize, &mut Attestation<E>)>,
+        current_epoch: Epoch,
+    ) -> Vec<Result<(), Error>> {
+        let mut signing_params = attestations
+            .iter()
+            .map(|(validator_pubkey, _, attestation)| {
+                self.attestation_signing_params(
+                    *validator_pubkey,
+                    attestation.data(),
+                    current_epoch,
+                )
+            })
+            .collect::<Vec<_>>();
 
         // Checking for slashing conditions.
-        let signing_epoch = attestation.data().target.epoch;
-        let signing_context = self.signing_context(Domain::BeaconAttester, signing_epoch);
-        let domain_hash = signing_context.domain_hash(&self.spec);
-        let slashing_status = if signing_method
-            .requires_local_slashing_protection(self.enable_web3signer_slashing_protection)
+        let (batch_indices, batch): (Vec<_>, Vec<_>) = attestations
+            .iter()
+            .zip(&signing_params)
+            .enumerate()
+            .filter_map(|(i, ((validator_pubkey, _, attestation), params))| {
+                let (signing_method, _, domain_hash) = params.as_ref().ok()?;
+                signing_method
+                    .requires_local_slashing_protection(self.enable_web3signer_slashing_protection)
+                    .then_some((i, (validator_pubkey, attestation.data(), *domain_hash)))
+            })
+            .unzip();
+        let mut slashing_statuses = attestations
+            .iter()
+            .map(|_| Ok(Safe::Valid))
+            .collect::<Vec<_>>();
+        match self
+            .slashing_protection
+            .check_and_insert_attestations(batch)
         {
-            self.slashing_protection.check_and_insert_attestation(
-                &validator_pubkey,
-                attestation.data(),
-                domain_hash,
-            )
-        } else {
-            Ok(Safe::Valid)
-        };
+            Ok(results) => {
+                for (i, result) in batch_indices.into_iter().zip(results) {
+                    slashing_statuses[i] = result;
+                }
+            }
+            // Nothing was recorded, so none of the batch is safe to sign.
+            Err(e) => {
+                for i in batch_indices {
+                    slashing_statuses[i] = Err(e.clone());
+                }
+            }
+        }
+        for ((params, slashing_status), (_, _, attestation)) in signing_params
+            .iter_mut()
+            .zip(slashing_statuses)
+            .zip(&attestations)
+        {
+            if params.is_ok() {
+                if let Err(e) =
+                    self.check_attestation_slashing_status(slashing_status, attestation.data())
+                {
+                    *params = Err(e);
+                }
+            }
+        }
 
-        match slashing_status {
-            // We can safely sign this attestation.
-            Ok(Safe::Valid) => {
+        // We can safely sign the remaining attestations.
+        let signatures = join_all(signing_params.into_iter().zip(&attestations).map(
+            |(params, (_, _, attestation))| async move {
+                let (signing_method, signing_context, _) = params?;
                 let signature = signing_method
                     .get_signature::<E, BlindedPayload<E>>(
                         SignableMessage::AttestationData(attestation.data()),
@@ -678,14 +972,67 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
                         &self.task_executor,
                     )
                     .await?;
-                attestation
-                    .add_signature(&signature, validator_committee_position)
-                    .map_err(Error::UnableToSignAttestation)?;
+                Ok::<_, Error>(signature)
+            },
+        ))
+        .await;
+
+        attestations
+            .iter_mut()
+            .zip(signatures)
+            .map(
+                |((_, validator_committee_position, attestation), signature)| {
+                    attestation
+                        .add_signature(&signature?, *validator_committee_position)
+                        .map_err(Error::UnableToSignAttestation)?;
+
+                    metrics::inc_counter_vec(
+                        &metrics::SIGNED_ATTESTATIONS_TOTAL,
+                        &[metrics::SUCCESS],
+                    );
+
+                    Ok(())
+                },
+            )
+            .collect()
+    }
 
-                metrics::inc_counter_vec(&metrics::SIGNED_ATTESTATIONS_TOTAL, &[metrics::SUCCESS]);
+    /// Returns the signing method, signing context and domain of an attestation, if its validator
+    /// may sign it.
+    fn attestation_signing_params(
+        &self,
+        validator_pubkey: PublicKeyBytes,
+        attestation_data: &AttestationData,
+        current_epoch: Epoch,
+    ) -> Result<(Arc<SigningMethod>, SigningContext, Hash256), Error> {
+        // Make sure the target epoch is not higher than the current epoch to avoid potential attacks.
+        let signing_epoch = attestation_data.target.epoch;
+        if signing_epoch > current_epoch {
+            return Err(Error::GreaterThanCurrentEpoch {
+                epoch: signing_epoch,
+                current_epoch,
+            });
+        }
 
-                Ok(())
-            }
+        self.check_handover(validator_pubkey, signing_epoch)?;
+
+        // Get the signing method and check doppelganger protection.
+        let signing_method = self.doppelganger_checked_signing_method(validator_pubkey)?;
+
+        let signing_context = self.signing_context(Domain::BeaconAttester, signing_epoch);
+        let domain_hash = signing_context.domain_hash(&self.spec);
+        Ok((signing_method, signing_context, domain_hash))
+    }
+
+    /// Logs and records metrics for the outcome of an attestation's slashing protection check,
+    /// returning an error if it must not be signed.
+    fn check_attestation_slashing_status(
+        &self,
+        slashing_status: Result<Safe, NotSafe>,
+        attestation_data: &AttestationData,
+    ) -> Result<(), Error> {
+        match slashing_status {
+            Ok(Safe::Valid) => Ok(()),
             Ok(Safe::SameData) => {
                 warn!(
                     self.log,
@@ -714,7 +1061,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
                 crit!(
                     self.log,
                     "Not signing slashable attestation";
-                    "attestation" => format!("{:?}", attestation.data()),
+                    "attestation" => format!("{:?}", attestation_data),
                     "error" => format!("{:?}", e)
                 );
                 metrics::inc_counter_vec(
@@ -752,6 +1099,26 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         })
     }
 
+    /// Signs a change of the withdrawal credentials of the validator at `validator_index` to
+    /// `to_execution_address`, using its BLS withdrawal key rather than its voting key.
+    pub fn sign_bls_to_execution_change(
+        &self,
+        withdrawal_keypair: &Keypair,
+        validator_index: u64,
+        to_execution_address: Address,
+    ) -> SignedBlsToExecutionChange {
+        BlsToExecutionChange {
+            validator_index,
+            from_bls_pubkey: withdrawal_keypair.pk.compress(),
+            to_execution_address,
+        }
+        .sign(
+            &withdrawal_keypair.sk,
+            self.genesis_validators_root,
+            &self.spec,
+        )
+    }
+
     pub async fn sign_validator_registration_data(
         &self,
         validator_registration_data: ValidatorRegistrationData,
@@ -971,6 +1338,44 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         Ok(())
     }
 
+    /// Check `block_header` against the slashing protection database without signing it or
+    /// recording it.
+    pub fn check_block_slashing_protection(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        block_header: &BeaconBlockHeader,
+    ) -> Result<Safe, NotSafe> {
+        let signing_epoch = block_header.slot.epoch(E::slots_per_epoch());
+        let domain_hash = self
+            .signing_context(Domain::BeaconProposer, signing_epoch)
+            .domain_hash(&self.spec);
+        self.slashing_protection
+            .check_block_proposal_without_insert(validator_pubkey, block_header, domain_hash)
+    }
+
+    /// Check `attestation_data` against the slashing protection database without signing it or
+    /// recording it.
+    pub fn check_attestation_slashing_protection(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        attestation_data: &AttestationData,
+    ) -> Result<Safe, NotSafe> {
+        let domain_hash = self
+            .signing_context(Domain::BeaconAttester, attestation_data.target.epoch)
+            .domain_hash(&self.spec);
+        self.slashing_protection.check_attestation_without_insert(
+            validator_pubkey,
+            attestation_data,
+            domain_hash,
+        )
+    }
+
+    /// Export the slashing protection data of every validator in the database.
+    pub fn export_slashing_protection(&self) -> Result<Interchange, InterchangeError> {
+        self.slashing_protection
+            .export_interchange_info(self.genesis_validators_root, None)
+    }
+
     /// Export slashing protection data while also disabling the given keys in the database.
     ///
     /// If any key is unknown to the slashing protection database it will be silently omitted
@@ -1061,6 +1466,29 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             return;
         }
 
+        // Validators which have since been removed from this client no longer appear above, so
+        // apply the slower retention policy to all validators in the database.
+        match self.slashing_protection.prune_all(
+            current_epoch,
+            DEFAULT_RETENTION_EPOCHS,
+            E::slots_per_epoch(),
+        ) {
+            Ok(summary) => debug!(
+                self.log,
+                "Pruned slashing protection history";
+                "blocks" => summary.blocks,
+                "attestations" => summary.attestations,
+            ),
+            Err(e) => {
+                error!(
+                    self.log,
+                    "Error during pruning of slashing protection history";
+                    "error" => ?e,
+                );
+                return;
+            }
+        }
+
         *last_prune = current_epoch;
 
         info!(self.log, "Completed pruning of slashing protection DB");
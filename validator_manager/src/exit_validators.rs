@@ -0,0 +1,560 @@
+use super::common::*;
+use crate::move_validators::Validators;
+use crate::DumpConfig;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use clap_utils::FLAG_HEADER;
+use eth2::{
+    types::{BlockId, StateId, ValidatorData},
+    BeaconNodeHttpClient, SensitiveUrl, Timeouts,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::time::sleep;
+use types::{ChainSpec, Epoch, EthSpec, PublicKeyBytes, Validator};
+
+pub const CMD: &str = "exit";
+pub const VC_URL_FLAG: &str = "vc-url";
+pub const VC_TOKEN_FLAG: &str = "vc-token";
+pub const BEACON_NODE_FLAG: &str = "beacon-node";
+pub const VALIDATORS_FLAG: &str = "validators";
+pub const STAGGER_FLAG: &str = "stagger";
+pub const DRY_RUN_FLAG: &str = "dry-run";
+
+/// The timeout for requests to the beacon node, which is generous since the entire validator set
+/// is downloaded.
+const BEACON_NODE_HTTP_TIMEOUT: Duration = Duration::from_secs(120);
+
+pub fn cli_app() -> Command {
+    Command::new(CMD)
+        .about(
+            "Exits validators of a validator client using the HTTP API. The exits are signed by \
+                the validator client and published to a beacon node, optionally staggered over \
+                several epochs. The expected exit and withdrawable epochs of each validator are \
+                computed from the current exit churn of the beacon chain.",
+        )
+        .arg(
+            Arg::new("help")
+                .long("help")
+                .short('h')
+                .help("Prints help information")
+                .action(ArgAction::HelpLong)
+                .display_order(0)
+                .help_heading(FLAG_HEADER),
+        )
+        .arg(
+            Arg::new(VC_URL_FLAG)
+                .long(VC_URL_FLAG)
+                .value_name("HTTP_ADDRESS")
+                .help(
+                    "A HTTP(S) address of a validator client using the keymanager-API. \
+                    This validator client signs the exits.",
+                )
+                .default_value("http://localhost:5062")
+                .requires(VC_TOKEN_FLAG)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(VC_TOKEN_FLAG)
+                .long(VC_TOKEN_FLAG)
+                .value_name("PATH")
+                .help("The file containing a token required by the validator client.")
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(BEACON_NODE_FLAG)
+                .long(BEACON_NODE_FLAG)
+                .value_name("HTTP_ADDRESS")
+                .help(
+                    "A HTTP(S) address of a beacon node using the beacon-API. The exit churn \
+                    is computed from the head state of this beacon node and the exits are \
+                    published to it.",
+                )
+                .default_value("http://localhost:5052")
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(VALIDATORS_FLAG)
+                .long(VALIDATORS_FLAG)
+                .value_name("STRING")
+                .help(
+                    "The validators to be exited. Either a list of 0x-prefixed \
+                    validator pubkeys or the keyword \"all\".",
+                )
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(COUNT_FLAG)
+                .long(COUNT_FLAG)
+                .value_name("VALIDATOR_COUNT")
+                .help(
+                    "The number of validators to exit. Validators which are able to exit are \
+                    selected in order of their pubkeys.",
+                )
+                .conflicts_with(VALIDATORS_FLAG)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(STAGGER_FLAG)
+                .long(STAGGER_FLAG)
+                .value_name("EPOCHS")
+                .help(
+                    "The number of epochs to wait between publishing the exit of each validator. \
+                    The command keeps running until the last exit is published.",
+                )
+                .default_value("0")
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(DRY_RUN_FLAG)
+                .long(DRY_RUN_FLAG)
+                .help(
+                    "If present, only print the expected exit and withdrawable epochs of each \
+                    validator, without signing or publishing any exits.",
+                )
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0),
+        )
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct ExitConfig {
+    pub vc_url: SensitiveUrl,
+    pub vc_token_path: PathBuf,
+    pub bn_url: SensitiveUrl,
+    pub validators: Validators,
+    pub stagger_epochs: u64,
+    pub dry_run: bool,
+}
+
+impl ExitConfig {
+    fn from_cli(matches: &ArgMatches) -> Result<Self, String> {
+        Ok(Self {
+            vc_url: clap_utils::parse_required(matches, VC_URL_FLAG)?,
+            vc_token_path: clap_utils::parse_required(matches, VC_TOKEN_FLAG)?,
+            bn_url: clap_utils::parse_required(matches, BEACON_NODE_FLAG)?,
+            validators: Validators::from_cli(matches)?,
+            stagger_epochs: clap_utils::parse_required(matches, STAGGER_FLAG)?,
+            dry_run: matches.get_flag(DRY_RUN_FLAG),
+        })
+    }
+}
+
+/// The exit of a single validator, as planned before it is published.
+#[derive(Clone, PartialEq, Debug)]
+struct PlannedExit {
+    pubkey: PublicKeyBytes,
+    index: u64,
+    publish_epoch: Epoch,
+    exit_epoch: Epoch,
+    withdrawable_epoch: Epoch,
+}
+
+/// The exit queue of the beacon chain, used to predict the epoch at which each exit takes effect.
+///
+/// Before Electra the churn is measured in validators, afterwards it is measured in Gwei of
+/// effective balance.
+#[derive(Debug)]
+struct ExitQueue {
+    /// The latest epoch at which an exit takes effect.
+    exit_epoch: Epoch,
+    /// The churn which remains at `exit_epoch`.
+    churn_to_consume: u64,
+    /// The churn available in each epoch.
+    churn_limit: u64,
+    balance_churn: bool,
+}
+
+impl ExitQueue {
+    fn new(validators: &[ValidatorData], current_epoch: Epoch, spec: &ChainSpec) -> Self {
+        let balance_churn = spec.fork_name_at_epoch(current_epoch).electra_enabled();
+        let active = validators
+            .iter()
+            .filter(|data| data.validator.is_active_at(current_epoch));
+        let churn_limit = if balance_churn {
+            let total_active_balance = active
+                .map(|data| data.validator.effective_balance)
+                .sum::<u64>()
+                .max(spec.effective_balance_increment);
+            let churn = std::cmp::max(
+                spec.min_per_epoch_churn_limit_electra,
+                total_active_balance / spec.churn_limit_quotient,
+            );
+            std::cmp::min(
+                spec.max_per_epoch_activation_exit_churn_limit,
+                churn - churn % spec.effective_balance_increment,
+            )
+        } else {
+            std::cmp::max(
+                spec.min_per_epoch_churn_limit,
+                active.count() as u64 / spec.churn_limit_quotient,
+            )
+        };
+
+        let mut queue = Self {
+            exit_epoch: Epoch::new(0),
+            churn_to_consume: churn_limit,
+            churn_limit,
+            balance_churn,
+        };
+        let exits = validators
+            .iter()
+            .filter(|data| data.validator.exit_epoch != spec.far_future_epoch);
+        if let Some(exit_epoch) = exits.clone().map(|data| data.validator.exit_epoch).max() {
+            let consumed = exits
+                .filter(|data| data.validator.exit_epoch == exit_epoch)
+                .map(|data| queue.churn(&data.validator))
+                .sum::<u64>();
+            queue.exit_epoch = exit_epoch;
+            queue.churn_to_consume = churn_limit.saturating_sub(consumed);
+        }
+        queue
+    }
+
+    /// Returns the churn consumed by the exit of `validator`.
+    fn churn(&self, validator: &Validator) -> u64 {
+        if self.balance_churn {
+            validator.effective_balance
+        } else {
+            1
+        }
+    }
+
+    /// Adds the exit of `validator`, published during `publish_epoch`, to the queue and returns
+    /// its exit epoch.
+    fn push(
+        &mut self,
+        validator: &Validator,
+        publish_epoch: Epoch,
+        spec: &ChainSpec,
+    ) -> Result<Epoch, String> {
+        let churn = self.churn(validator);
+        let earliest_exit_epoch = spec
+            .compute_activation_exit_epoch(publish_epoch)
+            .map_err(|e| format!("Unable to compute exit epoch: {:?}", e))?;
+        if earliest_exit_epoch > self.exit_epoch {
+            self.exit_epoch = earliest_exit_epoch;
+            self.churn_to_consume = self.churn_limit;
+        }
+
+        // The exit does not fit in the churn of the latest exit epoch.
+        if churn > self.churn_to_consume {
+            let additional_epochs = (churn - self.churn_to_consume - 1) / self.churn_limit + 1;
+            self.exit_epoch += additional_epochs;
+            self.churn_to_consume += additional_epochs * self.churn_limit;
+        }
+        self.churn_to_consume -= churn;
+        Ok(self.exit_epoch)
+    }
+}
+
+/// Returns `true` if `validator` is able to publish a voluntary exit during `epoch`.
+fn can_exit(validator: &Validator, epoch: Epoch, spec: &ChainSpec) -> bool {
+    validator.is_active_at(epoch)
+        && validator.exit_epoch == spec.far_future_epoch
+        && epoch >= validator.activation_epoch + spec.shard_committee_period
+}
+
+pub async fn cli_run<E: EthSpec>(
+    matches: &ArgMatches,
+    spec: &ChainSpec,
+    dump_config: DumpConfig,
+) -> Result<(), String> {
+    let config = ExitConfig::from_cli(matches)?;
+    if dump_config.should_exit_early(&config)? {
+        Ok(())
+    } else {
+        run::<E>(config, spec).await
+    }
+}
+
+async fn run<E: EthSpec>(config: ExitConfig, spec: &ChainSpec) -> Result<(), String> {
+    let ExitConfig {
+        vc_url,
+        vc_token_path,
+        bn_url,
+        validators,
+        stagger_epochs,
+        dry_run,
+    } = config;
+
+    let (vc_http_client, vc_keystores) = vc_http_client(vc_url.clone(), &vc_token_path).await?;
+    let bn_http_client =
+        BeaconNodeHttpClient::new(bn_url, Timeouts::set_all(BEACON_NODE_HTTP_TIMEOUT));
+
+    let current_epoch = bn_http_client
+        .get_beacon_headers_block_id(BlockId::Head)
+        .await
+        .map_err(|e| format!("Failed to get head from beacon node: {:?}", e))?
+        .ok_or("Beacon node has no head block")?
+        .data
+        .header
+        .message
+        .slot
+        .epoch(E::slots_per_epoch());
+    let chain_validators = bn_http_client
+        .get_beacon_states_validators(StateId::Head, None, None)
+        .await
+        .map_err(|e| format!("Failed to get validators from beacon node: {:?}", e))?
+        .ok_or("Beacon node has no head state")?
+        .data;
+    let chain_validators_map: HashMap<_, _> = chain_validators
+        .iter()
+        .map(|data| (data.validator.pubkey, data))
+        .collect();
+
+    let exitable = |pubkey: &PublicKeyBytes| {
+        chain_validators_map
+            .get(pubkey)
+            .map_or(false, |data| can_exit(&data.validator, current_epoch, spec))
+    };
+    let pubkeys_to_exit = match validators {
+        Validators::All => vc_keystores
+            .iter()
+            .map(|v| v.validating_pubkey)
+            .filter(exitable)
+            .collect::<Vec<_>>(),
+        Validators::Count(count) => {
+            let mut viable_pubkeys: Vec<_> = vc_keystores
+                .iter()
+                .map(|v| v.validating_pubkey)
+                .filter(exitable)
+                .collect();
+            viable_pubkeys.sort_unstable_by_key(PublicKeyBytes::serialize);
+            viable_pubkeys
+                .get(0..count)
+                .ok_or_else(|| {
+                    format!(
+                        "Cannot exit {} validators since the validator client only has {} \
+                        validators which are able to exit.",
+                        count,
+                        viable_pubkeys.len()
+                    )
+                })?
+                .to_vec()
+        }
+        Validators::Specific(request_pubkeys) => {
+            for pubkey in &request_pubkeys {
+                if !vc_keystores.iter().any(|v| v.validating_pubkey == *pubkey) {
+                    return Err(format!("{:?} is not present on {:?}", pubkey, vc_url));
+                }
+                if !exitable(pubkey) {
+                    return Err(format!(
+                        "{:?} is not able to exit, it may be inactive, already exiting or \
+                        activated too recently",
+                        pubkey
+                    ));
+                }
+            }
+            request_pubkeys
+        }
+    };
+
+    if pubkeys_to_exit.is_empty() {
+        return Err("No validators are able to exit".to_string());
+    }
+
+    let mut exit_queue = ExitQueue::new(&chain_validators, current_epoch, spec);
+    let mut planned_exits = Vec::with_capacity(pubkeys_to_exit.len());
+    for (i, pubkey) in pubkeys_to_exit.into_iter().enumerate() {
+        let data = chain_validators_map
+            .get(&pubkey)
+            .ok_or("Inconsistent validators map")?;
+        let publish_epoch = current_epoch + i as u64 * stagger_epochs;
+        let exit_epoch = exit_queue.push(&data.validator, publish_epoch, spec)?;
+        planned_exits.push(PlannedExit {
+            pubkey,
+            index: data.index,
+            publish_epoch,
+            exit_epoch,
+            withdrawable_epoch: exit_epoch + spec.min_validator_withdrawability_delay,
+        });
+    }
+
+    eprintln!(
+        "The current epoch is {} and the exit churn is {} {} per epoch",
+        current_epoch,
+        exit_queue.churn_limit,
+        if exit_queue.balance_churn {
+            "Gwei"
+        } else {
+            "validators"
+        }
+    );
+    for exit in &planned_exits {
+        eprintln!(
+            "Validator {} ({:?}) publishes its exit in epoch {}, expected exit epoch {}, \
+            withdrawable epoch {}",
+            exit.index, exit.pubkey, exit.publish_epoch, exit.exit_epoch, exit.withdrawable_epoch
+        );
+    }
+
+    if dry_run {
+        eprintln!("Dry run complete, no exits were published");
+        return Ok(());
+    }
+
+    let stagger_duration =
+        Duration::from_secs(spec.seconds_per_slot * E::slots_per_epoch() * stagger_epochs);
+    let count = planned_exits.len();
+    for (i, exit) in planned_exits.iter().enumerate() {
+        if i > 0 && !stagger_duration.is_zero() {
+            eprintln!(
+                "Waiting {} epochs before publishing the next exit",
+                stagger_epochs
+            );
+            sleep(stagger_duration).await;
+        }
+
+        let signed_exit = vc_http_client
+            .post_validator_voluntary_exit(&exit.pubkey, None)
+            .await
+            .map_err(|e| {
+                format!(
+                    "Failed to sign the exit of {:?} after publishing {} of {} exits: {:?}",
+                    exit.pubkey, i, count, e
+                )
+            })?
+            .data;
+        bn_http_client
+            .post_beacon_pool_voluntary_exits(&signed_exit)
+            .await
+            .map_err(|e| {
+                format!(
+                    "Failed to publish the exit of {:?} after publishing {} of {} exits: {:?}",
+                    exit.pubkey, i, count, e
+                )
+            })?;
+        eprintln!(
+            "Published exit {} of {} for validator {}",
+            i + 1,
+            count,
+            exit.index
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{Hash256, MainnetEthSpec};
+
+    fn validator(effective_balance: u64, exit_epoch: Epoch) -> Validator {
+        Validator {
+            pubkey: PublicKeyBytes::empty(),
+            withdrawal_credentials: Hash256::zero(),
+            effective_balance,
+            slashed: false,
+            activation_eligibility_epoch: Epoch::new(0),
+            activation_epoch: Epoch::new(0),
+            exit_epoch,
+            withdrawable_epoch: Epoch::new(u64::MAX),
+        }
+    }
+
+    fn validator_data(validator: Validator) -> ValidatorData {
+        ValidatorData {
+            index: 0,
+            balance: validator.effective_balance,
+            status: eth2::types::ValidatorStatus::ActiveOngoing,
+            validator,
+        }
+    }
+
+    #[test]
+    fn exits_are_limited_by_validator_churn() {
+        let spec = MainnetEthSpec::default_spec();
+        let current_epoch = Epoch::new(100);
+        let balance = spec.max_effective_balance;
+        let mut validators = (0..8)
+            .map(|_| validator_data(validator(balance, spec.far_future_epoch)))
+            .collect::<Vec<_>>();
+        // Two exits are already queued in the earliest exit epoch.
+        let earliest_exit_epoch = spec.compute_activation_exit_epoch(current_epoch).unwrap();
+        validators.push(validator_data(validator(balance, earliest_exit_epoch)));
+        validators.push(validator_data(validator(balance, earliest_exit_epoch)));
+
+        let mut queue = ExitQueue::new(&validators, current_epoch, &spec);
+        assert!(!queue.balance_churn);
+        assert_eq!(queue.churn_limit, spec.min_per_epoch_churn_limit);
+
+        let exit_epochs = (0..spec.min_per_epoch_churn_limit + 1)
+            .map(|_| {
+                queue
+                    .push(
+                        &validator(balance, spec.far_future_epoch),
+                        current_epoch,
+                        &spec,
+                    )
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        let churn_limit = spec.min_per_epoch_churn_limit as usize;
+        assert!(exit_epochs[..churn_limit - 2]
+            .iter()
+            .all(|epoch| *epoch == earliest_exit_epoch));
+        assert!(exit_epochs[churn_limit - 2..]
+            .iter()
+            .all(|epoch| *epoch == earliest_exit_epoch + 1));
+
+        // An exit published after the queue has cleared exits as early as possible.
+        let later_epoch = current_epoch + 10;
+        assert_eq!(
+            queue
+                .push(
+                    &validator(balance, spec.far_future_epoch),
+                    later_epoch,
+                    &spec
+                )
+                .unwrap(),
+            spec.compute_activation_exit_epoch(later_epoch).unwrap()
+        );
+    }
+
+    #[test]
+    fn exits_are_limited_by_balance_churn() {
+        let mut spec = MainnetEthSpec::default_spec();
+        spec.electra_fork_epoch = Some(Epoch::new(0));
+        let current_epoch = Epoch::new(100);
+        let earliest_exit_epoch = spec.compute_activation_exit_epoch(current_epoch).unwrap();
+
+        let mut queue = ExitQueue::new(&[], current_epoch, &spec);
+        assert!(queue.balance_churn);
+        assert_eq!(queue.churn_limit, spec.min_per_epoch_churn_limit_electra);
+
+        // An exit larger than the churn spans several epochs.
+        let large_balance = spec.min_per_epoch_churn_limit_electra * 5 / 2;
+        assert_eq!(
+            queue
+                .push(
+                    &validator(large_balance, spec.far_future_epoch),
+                    current_epoch,
+                    &spec
+                )
+                .unwrap(),
+            earliest_exit_epoch + 2
+        );
+        let remaining_churn = spec.min_per_epoch_churn_limit_electra / 2;
+        assert_eq!(queue.churn_to_consume, remaining_churn);
+        assert_eq!(
+            queue
+                .push(
+                    &validator(remaining_churn + 1, spec.far_future_epoch),
+                    current_epoch,
+                    &spec
+                )
+                .unwrap(),
+            earliest_exit_epoch + 3
+        );
+    }
+}
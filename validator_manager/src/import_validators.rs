@@ -2,15 +2,23 @@ use super::common::*;
 use crate::DumpConfig;
 use clap::{Arg, ArgAction, ArgMatches, Command};
 use clap_utils::FLAG_HEADER;
-use eth2::{lighthouse_vc::std_types::ImportKeystoreStatus, SensitiveUrl};
+use eth2::{
+    lighthouse_vc::{std_types::ImportKeystoreStatus, types::Web3SignerValidatorRequest},
+    reqwest::Client,
+    SensitiveUrl,
+};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use types::{Address, GraffitiString, PublicKey, PublicKeyBytes};
 
 pub const CMD: &str = "import";
 pub const VALIDATORS_FILE_FLAG: &str = "validators-file";
 pub const VC_URL_FLAG: &str = "vc-url";
 pub const VC_TOKEN_FLAG: &str = "vc-token";
+pub const FROM_WEB3SIGNER_FLAG: &str = "from-web3signer";
+pub const WEB3SIGNER_OVERRIDES_FLAG: &str = "web3signer-overrides";
 
 pub const DETECTED_DUPLICATE_MESSAGE: &str = "Duplicate validator detected!";
 
@@ -19,7 +27,8 @@ pub fn cli_app() -> Command {
         .about(
             "Uploads validators to a validator client using the HTTP API. The validators \
                 are defined in a JSON file which can be generated using the \"create-validators\" \
-                command.",
+                command. Alternatively, all the keys held by a Web3Signer can be added to the \
+                validator client as remote signing validators.",
         )
         .arg(
             Arg::new("help")
@@ -39,7 +48,35 @@ pub fn cli_app() -> Command {
                     imported to the validator client. This file is usually named \
                     \"validators.json\".",
                 )
-                .required(true)
+                .required_unless_present(FROM_WEB3SIGNER_FLAG)
+                .conflicts_with(FROM_WEB3SIGNER_FLAG)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(FROM_WEB3SIGNER_FLAG)
+                .long(FROM_WEB3SIGNER_FLAG)
+                .value_name("HTTP_ADDRESS")
+                .help(
+                    "The HTTP(S) address of a Web3Signer. Every public key listed by the \
+                    Web3Signer is added to the validator client as a validator which signs \
+                    using that Web3Signer.",
+                )
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(WEB3SIGNER_OVERRIDES_FLAG)
+                .long(WEB3SIGNER_OVERRIDES_FLAG)
+                .value_name("PATH_TO_JSON_FILE")
+                .help(
+                    "The path to a JSON file which maps public keys held by the Web3Signer to \
+                    the values to use for that validator instead of the validator client's \
+                    defaults. The supported fields are \"enabled\", \"description\", \
+                    \"graffiti\", \"fee_recipient\", \"gas_limit\", \"builder_proposals\", \
+                    \"builder_boost_factor\" and \"prefer_builder_proposals\".",
+                )
+                .requires(FROM_WEB3SIGNER_FLAG)
                 .action(ArgAction::Set)
                 .display_order(0),
         )
@@ -84,7 +121,9 @@ pub fn cli_app() -> Command {
 
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct ImportConfig {
-    pub validators_file_path: PathBuf,
+    pub validators_file_path: Option<PathBuf>,
+    pub web3signer_url: Option<SensitiveUrl>,
+    pub web3signer_overrides_path: Option<PathBuf>,
     pub vc_url: SensitiveUrl,
     pub vc_token_path: PathBuf,
     pub ignore_duplicates: bool,
@@ -93,7 +132,12 @@ pub struct ImportConfig {
 impl ImportConfig {
     fn from_cli(matches: &ArgMatches) -> Result<Self, String> {
         Ok(Self {
-            validators_file_path: clap_utils::parse_required(matches, VALIDATORS_FILE_FLAG)?,
+            validators_file_path: clap_utils::parse_optional(matches, VALIDATORS_FILE_FLAG)?,
+            web3signer_url: clap_utils::parse_optional(matches, FROM_WEB3SIGNER_FLAG)?,
+            web3signer_overrides_path: clap_utils::parse_optional(
+                matches,
+                WEB3SIGNER_OVERRIDES_FLAG,
+            )?,
             vc_url: clap_utils::parse_required(matches, VC_URL_FLAG)?,
             vc_token_path: clap_utils::parse_required(matches, VC_TOKEN_FLAG)?,
             ignore_duplicates: matches.get_flag(IGNORE_DUPLICATES_FLAG),
@@ -113,11 +157,31 @@ pub async fn cli_run(matches: &ArgMatches, dump_config: DumpConfig) -> Result<()
 async fn run<'a>(config: ImportConfig) -> Result<(), String> {
     let ImportConfig {
         validators_file_path,
+        web3signer_url,
+        web3signer_overrides_path,
         vc_url,
         vc_token_path,
         ignore_duplicates,
     } = config;
 
+    if let Some(web3signer_url) = web3signer_url {
+        return import_from_web3signer(
+            web3signer_url,
+            web3signer_overrides_path,
+            vc_url,
+            vc_token_path,
+            ignore_duplicates,
+        )
+        .await;
+    }
+
+    let validators_file_path = validators_file_path.ok_or_else(|| {
+        format!(
+            "Either --{} or --{} must be supplied",
+            VALIDATORS_FILE_FLAG, FROM_WEB3SIGNER_FLAG
+        )
+    })?;
+
     if !validators_file_path.exists() {
         return Err(format!("Unable to find file at {:?}", validators_file_path));
     }
@@ -244,6 +308,181 @@ async fn run<'a>(config: ImportConfig) -> Result<(), String> {
     Ok(())
 }
 
+/// Values to use for a single key imported from a Web3Signer, instead of the validator client's
+/// defaults.
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Web3SignerOverrides {
+    pub enabled: Option<bool>,
+    pub description: Option<String>,
+    pub graffiti: Option<GraffitiString>,
+    pub fee_recipient: Option<Address>,
+    pub gas_limit: Option<u64>,
+    pub builder_proposals: Option<bool>,
+    pub builder_boost_factor: Option<u64>,
+    pub prefer_builder_proposals: Option<bool>,
+}
+
+fn read_web3signer_overrides(
+    path: &Path,
+) -> Result<HashMap<PublicKeyBytes, Web3SignerOverrides>, String> {
+    let file = fs::File::open(path).map_err(|e| format!("Unable to open {:?}: {:?}", path, e))?;
+    serde_json::from_reader(file)
+        .map_err(|e| format!("Unable to parse JSON in {:?}: {:?}", path, e))
+}
+
+/// Checks that the Web3Signer at `url` is up and returns the public keys it holds.
+async fn web3signer_public_keys(url: &SensitiveUrl) -> Result<Vec<PublicKey>, String> {
+    let client = Client::new();
+    let endpoint = |segments: &[&str]| {
+        let mut endpoint = url.full.clone();
+        endpoint
+            .path_segments_mut()
+            .map_err(|()| format!("Invalid Web3Signer URL: {}", url))?
+            .pop_if_empty()
+            .extend(segments);
+        Ok::<_, String>(endpoint)
+    };
+
+    client
+        .get(endpoint(&["upcheck"])?)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| format!("Web3Signer at {} is not reachable: {:?}", url, e))?;
+
+    client
+        .get(endpoint(&["api", "v1", "eth2", "publicKeys"])?)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| format!("Failed to list public keys on Web3Signer: {:?}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Invalid public key list from Web3Signer: {:?}", e))
+}
+
+/// Builds a request to add each of `public_keys` to the validator client as a validator which
+/// signs using the Web3Signer at `url`.
+fn web3signer_validator_requests(
+    public_keys: Vec<PublicKey>,
+    overrides: &HashMap<PublicKeyBytes, Web3SignerOverrides>,
+    url: &SensitiveUrl,
+) -> Vec<Web3SignerValidatorRequest> {
+    public_keys
+        .into_iter()
+        .map(|voting_public_key| {
+            let Web3SignerOverrides {
+                enabled,
+                description,
+                graffiti,
+                fee_recipient,
+                gas_limit,
+                builder_proposals,
+                builder_boost_factor,
+                prefer_builder_proposals,
+            } = overrides
+                .get(&voting_public_key.compress())
+                .cloned()
+                .unwrap_or_default();
+
+            Web3SignerValidatorRequest {
+                enable: enabled.unwrap_or(true),
+                description: description.unwrap_or_default(),
+                graffiti,
+                suggested_fee_recipient: fee_recipient,
+                gas_limit,
+                builder_proposals,
+                voting_public_key,
+                url: url.full.to_string(),
+                root_certificate_path: None,
+                request_timeout_ms: None,
+                client_identity_path: None,
+                client_identity_password: None,
+                builder_boost_factor,
+                prefer_builder_proposals,
+            }
+        })
+        .collect()
+}
+
+async fn import_from_web3signer(
+    web3signer_url: SensitiveUrl,
+    overrides_path: Option<PathBuf>,
+    vc_url: SensitiveUrl,
+    vc_token_path: PathBuf,
+    ignore_duplicates: bool,
+) -> Result<(), String> {
+    let overrides = overrides_path
+        .map(|path| read_web3signer_overrides(&path))
+        .transpose()?
+        .unwrap_or_default();
+
+    let public_keys = web3signer_public_keys(&web3signer_url).await?;
+    eprintln!(
+        "Web3Signer is reachable at {} and reports {} keys",
+        web3signer_url,
+        public_keys.len()
+    );
+
+    // An override for a key which the Web3Signer doesn't hold is most likely a mistake.
+    let listed: HashSet<PublicKeyBytes> = public_keys.iter().map(PublicKey::compress).collect();
+    if let Some(unknown) = overrides.keys().find(|pubkey| !listed.contains(pubkey)) {
+        return Err(format!(
+            "Overrides were supplied for {:?}, which is not held by the Web3Signer",
+            unknown
+        ));
+    }
+
+    let (http_client, _keystores) = vc_http_client(vc_url, &vc_token_path).await?;
+    let existing: HashSet<PublicKeyBytes> = http_client
+        .get_lighthouse_validators()
+        .await
+        .map_err(|e| format!("Failed to list validators on VC: {:?}", e))?
+        .data
+        .into_iter()
+        .map(|validator| validator.voting_pubkey)
+        .collect();
+
+    let (duplicates, public_keys): (Vec<_>, Vec<_>) = public_keys
+        .into_iter()
+        .partition(|pubkey| existing.contains(&pubkey.compress()));
+    if !duplicates.is_empty() {
+        if !ignore_duplicates {
+            eprintln!(
+                "{} of the Web3Signer's keys already exist on the destination validator client, \
+                including {:?}. This may indicate that some validators are running in two places \
+                at once, which can lead to slashing. If you are certain that there is no risk, \
+                add the --{} flag to import only the remaining keys.",
+                duplicates.len(),
+                duplicates[0].compress(),
+                IGNORE_DUPLICATES_FLAG
+            );
+            return Err(DETECTED_DUPLICATE_MESSAGE.to_string());
+        }
+        eprintln!(
+            "Skipping {} keys which already exist on the VC",
+            duplicates.len()
+        );
+    }
+
+    if public_keys.is_empty() {
+        eprintln!("No validators to add to the VC");
+        return Ok(());
+    }
+
+    let count = public_keys.len();
+    let requests = web3signer_validator_requests(public_keys, &overrides, &web3signer_url);
+    http_client
+        .post_lighthouse_validators_web3signer(&requests)
+        .await
+        .map_err(|e| format!("Failed to add Web3Signer validators to VC: {:?}", e))?;
+
+    eprintln!("Added {} Web3Signer validators to the VC", count);
+
+    Ok(())
+}
+
 // The tests use crypto and are too slow in debug.
 #[cfg(not(debug_assertions))]
 #[cfg(test)]
@@ -279,7 +518,9 @@ pub mod tests {
             Self {
                 import_config: ImportConfig {
                     // This field will be overwritten later on.
-                    validators_file_path: dir.path().into(),
+                    validators_file_path: Some(dir.path().into()),
+                    web3signer_url: None,
+                    web3signer_overrides_path: None,
                     vc_url: vc.url.clone(),
                     vc_token_path,
                     ignore_duplicates: false,
@@ -307,7 +548,7 @@ pub mod tests {
                 create_result.result.is_ok(),
                 "precondition: validators are created"
             );
-            self.import_config.validators_file_path = create_result.validators_file_path();
+            self.import_config.validators_file_path = Some(create_result.validators_file_path());
             self.create_dir = Some(create_result.output_dir);
             self
         }
@@ -326,8 +567,8 @@ pub mod tests {
                 self.vc.ensure_key_cache_consistency().await;
 
                 let local_validators: Vec<ValidatorSpecification> = {
-                    let contents =
-                        fs::read_to_string(&self.import_config.validators_file_path).unwrap();
+                    let path = self.import_config.validators_file_path.as_ref().unwrap();
+                    let contents = fs::read_to_string(path).unwrap();
                     serde_json::from_str(&contents).unwrap()
                 };
                 let list_keystores_response = self.vc.client.get_keystores().await.unwrap().data;
@@ -445,4 +686,38 @@ pub mod tests {
             .await
             .assert_ok();
     }
+
+    #[test]
+    fn web3signer_requests_use_overrides() {
+        let keypairs = types::test_utils::generate_deterministic_keypairs(2);
+        let url = SensitiveUrl::parse("http://localhost:9000").unwrap();
+        let overridden = Web3SignerOverrides {
+            enabled: Some(false),
+            fee_recipient: Some(Address::repeat_byte(42)),
+            gas_limit: Some(36_000_000),
+            ..Web3SignerOverrides::default()
+        };
+        let overrides = HashMap::from([(keypairs[1].pk.compress(), overridden)]);
+
+        let requests = web3signer_validator_requests(
+            keypairs.iter().map(|keypair| keypair.pk.clone()).collect(),
+            &overrides,
+            &url,
+        );
+
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].enable);
+        assert_eq!(requests[0].suggested_fee_recipient, None);
+        assert_eq!(requests[0].gas_limit, None);
+        assert!(!requests[1].enable);
+        assert_eq!(
+            requests[1].suggested_fee_recipient,
+            Some(Address::repeat_byte(42))
+        );
+        assert_eq!(requests[1].gas_limit, Some(36_000_000));
+        for (request, keypair) in requests.iter().zip(&keypairs) {
+            assert_eq!(request.voting_public_key, keypair.pk);
+            assert_eq!(request.url, url.full.to_string());
+        }
+    }
 }
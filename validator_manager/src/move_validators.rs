@@ -227,23 +227,9 @@ pub enum Validators {
     Specific(Vec<PublicKeyBytes>),
 }
 
-#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
-pub struct MoveConfig {
-    pub src_vc_url: SensitiveUrl,
-    pub src_vc_token_path: PathBuf,
-    pub dest_vc_url: SensitiveUrl,
-    pub dest_vc_token_path: PathBuf,
-    pub validators: Validators,
-    pub builder_proposals: Option<bool>,
-    pub builder_boost_factor: Option<u64>,
-    pub prefer_builder_proposals: Option<bool>,
-    pub fee_recipient: Option<Address>,
-    pub gas_limit: Option<u64>,
-    pub password_source: PasswordSource,
-}
-
-impl MoveConfig {
-    fn from_cli(matches: &ArgMatches) -> Result<Self, String> {
+impl Validators {
+    /// Parses the validators selected by the `--validators` or `--count` flags.
+    pub fn from_cli(matches: &ArgMatches) -> Result<Self, String> {
         let count_flag = clap_utils::parse_optional(matches, COUNT_FLAG)?;
         let validators_flag = matches.get_one::<String>(VALIDATORS_FLAG);
         let validators = match (count_flag, validators_flag) {
@@ -263,6 +249,28 @@ impl MoveConfig {
                 Err("Cannot supply both --{VALIDATORS_FLAG} and --{COUNT_FLAG}.")?
             }
         };
+        Ok(validators)
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct MoveConfig {
+    pub src_vc_url: SensitiveUrl,
+    pub src_vc_token_path: PathBuf,
+    pub dest_vc_url: SensitiveUrl,
+    pub dest_vc_token_path: PathBuf,
+    pub validators: Validators,
+    pub builder_proposals: Option<bool>,
+    pub builder_boost_factor: Option<u64>,
+    pub prefer_builder_proposals: Option<bool>,
+    pub fee_recipient: Option<Address>,
+    pub gas_limit: Option<u64>,
+    pub password_source: PasswordSource,
+}
+
+impl MoveConfig {
+    fn from_cli(matches: &ArgMatches) -> Result<Self, String> {
+        let validators = Validators::from_cli(matches)?;
 
         Ok(Self {
             src_vc_url: clap_utils::parse_required(matches, SRC_VC_URL_FLAG)?,